@@ -1,42 +1,216 @@
-#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
-use crate::context::{categorize_app, get_active_app, is_developer_context, ToneStyle};
+use crate::context::active_app::{focus_window, focused_window_id, FocusError};
+use crate::context::{
+    categorize_app, extract_context_terms, get_active_app, get_text_before_cursor,
+    get_window_context, screen_ocr, ActiveAppInfo, ToneStyle,
+};
+use crate::cycle::{self, CycleKind};
+use crate::dual_script;
+use crate::events::{PostProcessFailed, PostProcessFallback, PostProcessStream};
+use crate::grammar::{self, GRAMMAR_ONLY_PROMPT};
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
 use crate::managers::live_preview::LivePreviewManager;
-use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, AppSettings, DeveloperMode, APPLE_INTELLIGENCE_PROVIDER_ID};
+use crate::managers::transcription::{
+    TranscriptionManager, TranscriptionOutput, TranscriptionOverrides,
+};
+use crate::settings::{
+    get_settings, AppSettings, PostProcessMode, PostProcessPipeline, PostProcessProvider,
+};
 use crate::shortcut;
+use crate::translation;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils::{self, show_recording_overlay, show_transcribing_overlay};
 use crate::ManagedToggleState;
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
-use log::{debug, error};
+use log::{debug, error, warn};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::AppHandle;
 use tauri::Manager;
+use tauri_specta::Event;
 
 // Shortcut Action Trait
 pub trait ShortcutAction: Send + Sync {
     fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
     fn stop(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str);
+
+    /// Actions that do their work once per press rather than holding a
+    /// recording. They skip toggle mode: `start` runs on every press and
+    /// `stop` on every release.
+    fn is_instant(&self) -> bool {
+        false
+    }
 }
 
 // Transcribe Action
 struct TranscribeAction;
 
+/// What was found about the window being dictated into when the recording
+/// started
+struct RecordingContext {
+    active_app: Option<ActiveAppInfo>,
+    /// Names and terms in the window's selected text, where it may be read
+    terms: Vec<String>,
+    /// The window to paste into, when pasting goes back to it
+    window: Option<String>,
+}
+
+static RECORDING_CONTEXT: Lazy<Mutex<Option<Receiver<RecordingContext>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Whether an app profile loaded its model in place of the selected one
+static PROFILE_MODEL_LOADED: AtomicBool = AtomicBool::new(false);
+
+/// Look up the app being dictated into when the recording starts, without
+/// holding up the start, and get its profile's model loading and the screen
+/// read. `take_recording_context` collects the app and the terms found.
+fn start_recording_context(tm: Arc<TranscriptionManager>, settings: AppSettings) {
+    let (sender, receiver) = mpsc::channel();
+    if let Ok(mut pending) = RECORDING_CONTEXT.lock() {
+        *pending = Some(receiver);
+    }
+
+    std::thread::spawn(move || {
+        // First, before the user has a chance to switch away
+        let window = settings
+            .paste_into_recording_window
+            .then(focused_window_id)
+            .flatten();
+        let active_app = get_active_app();
+        let profile = active_app
+            .as_ref()
+            .map(|app| settings.app_profile(app.context_name()))
+            .unwrap_or_default();
+
+        match &profile.model_id {
+            Some(model_id) => {
+                debug!("Using the profile's model {}", model_id);
+                PROFILE_MODEL_LOADED.store(*model_id != settings.selected_model, Ordering::Relaxed);
+                tm.initiate_model_switch(model_id);
+            }
+            // Back to the selected model after another app's profile
+            None if PROFILE_MODEL_LOADED.swap(false, Ordering::Relaxed)
+                && !settings.selected_model.is_empty() =>
+            {
+                tm.initiate_model_switch(&settings.selected_model);
+            }
+            None => {}
+        }
+
+        let terms = active_app
+            .as_ref()
+            .filter(|app| settings.reads_surrounding_text_in(app.context_name()))
+            .and_then(|_| get_window_context())
+            .map(|context| extract_context_terms(&context))
+            .unwrap_or_default();
+
+        // Read the window's terms while the user speaks
+        if let Some(app) = active_app.clone() {
+            if settings.context_awareness_enabled && settings.screen_ocr_enabled {
+                screen_ocr::start_capture(settings, app);
+            }
+        }
+
+        let _ = sender.send(RecordingContext {
+            active_app,
+            terms,
+            window,
+        });
+    });
+}
+
+/// What was found when the recording started, with the terms read on screen
+/// since. Falls back to the active app if the lookup didn't finish in time.
+fn take_recording_context() -> RecordingContext {
+    let receiver = RECORDING_CONTEXT
+        .lock()
+        .ok()
+        .and_then(|mut pending| pending.take());
+    let mut context = match receiver.map(|receiver| receiver.recv_timeout(Duration::from_secs(1))) {
+        Some(Ok(context)) => context,
+        _ => RecordingContext {
+            active_app: get_active_app(),
+            terms: Vec::new(),
+            window: None,
+        },
+    };
+    context.terms.extend(screen_ocr::take_terms());
+    context
+}
+
+/// Bring back the window that was focused when the recording started, so
+/// the transcript lands there rather than wherever focus moved since
+fn focus_recording_window(app: &AppHandle, window: &str) {
+    if focused_window_id().as_deref() == Some(window) {
+        return;
+    }
+    match focus_window(window) {
+        Ok(()) => {
+            debug!("Refocused the recording's window before pasting");
+            // Let the window take keyboard focus before the keystrokes arrive
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        Err(FocusError::AutomationDenied) => {
+            warn!("Automation permission denied, pasting into the current window");
+            show_automation_denied_dialog(app);
+        }
+        Err(FocusError::Failed) => {
+            warn!("Couldn't refocus the recording's window, pasting into the current one")
+        }
+    }
+}
+
+/// Set once the user was told about the missing Automation permission, so
+/// it is not repeated on every paste
+static AUTOMATION_DENIAL_SHOWN: AtomicBool = AtomicBool::new(false);
+
+fn show_automation_denied_dialog(app: &AppHandle) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+    if AUTOMATION_DENIAL_SHOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    app.dialog()
+        .message(
+            "PaperFlow isn't allowed to control System Events, so it couldn't switch \
+             back to the window you dictated into and pasted into the current one.\n\n\
+             Allow it under System Settings > Privacy & Security > Automation.",
+        )
+        .title("Automation permission needed")
+        .kind(MessageDialogKind::Warning)
+        .show(|_| {});
+}
+
+/// Run the post-processing mode on the transcription: the prompt routed to
+/// the active app, the selected pipeline or prompt, the grammar-only
+/// correction or the translation. Returns the result and the prompt that
+/// produced it.
 async fn maybe_post_process_transcription(
+    app: &AppHandle,
     settings: &AppSettings,
     transcription: &str,
-) -> Option<String> {
+    app_name: Option<&str>,
+    generation: u64,
+) -> Option<(String, String)> {
     if !settings.post_process_enabled {
         return None;
     }
 
+    // A prompt routed to the app or website being dictated into wins over the selection
+    let app_prompt = app_name.and_then(|name| settings.app_post_process_prompt(name));
+
+    if settings.post_process_mode == PostProcessMode::Custom && app_prompt.is_none() {
+        if let Some(pipeline) = settings.active_post_process_pipeline() {
+            return run_pipeline(app, settings, pipeline, transcription, app_name, generation)
+                .await;
+        }
+    }
+
     let provider = match settings.active_post_process_provider().cloned() {
         Some(provider) => provider,
         None => {
@@ -59,28 +233,75 @@ async fn maybe_post_process_transcription(
         return None;
     }
 
-    let selected_prompt_id = match &settings.post_process_selected_prompt_id {
-        Some(id) => id.clone(),
-        None => {
-            debug!("Post-processing skipped because no prompt is selected");
-            return None;
-        }
-    };
+    if settings.post_process_mode == PostProcessMode::GrammarOnly {
+        return grammar_only(
+            app,
+            settings,
+            &provider,
+            &model,
+            transcription,
+            app_name,
+            generation,
+        )
+        .await;
+    }
 
-    let prompt = match settings
-        .post_process_prompts
-        .iter()
-        .find(|prompt| prompt.id == selected_prompt_id)
-    {
-        Some(prompt) => prompt.prompt.clone(),
-        None => {
+    if settings.post_process_mode == PostProcessMode::Translate {
+        let language = translation::target_language(settings, app_name);
+        debug!(
+            "Translating into {} with provider '{}' (model: {})",
+            language, provider.id, model
+        );
+        let prompt = translation::translation_prompt(language);
+        return run_prompt(
+            app,
+            settings,
+            &provider,
+            &model,
+            &prompt,
+            transcription,
+            app_name,
+            generation,
+        )
+        .await
+        .map(|text| (text, prompt));
+    }
+
+    let selected_prompt = match app_prompt {
+        Some(prompt) => {
             debug!(
-                "Post-processing skipped because prompt '{}' was not found",
-                selected_prompt_id
+                "Using prompt '{}' for {}",
+                prompt.name,
+                app_name.unwrap_or_default()
             );
-            return None;
+            prompt
+        }
+        None => {
+            let selected_prompt_id = match &settings.post_process_selected_prompt_id {
+                Some(id) => id,
+                None => {
+                    debug!("Post-processing skipped because no prompt is selected");
+                    return None;
+                }
+            };
+
+            match settings
+                .post_process_prompts
+                .iter()
+                .find(|prompt| &prompt.id == selected_prompt_id)
+            {
+                Some(prompt) => prompt,
+                None => {
+                    debug!(
+                        "Post-processing skipped because prompt '{}' was not found",
+                        selected_prompt_id
+                    );
+                    return None;
+                }
+            }
         }
     };
+    let prompt = selected_prompt.prompt.clone();
 
     if prompt.trim().is_empty() {
         debug!("Post-processing skipped because the selected prompt is empty");
@@ -92,26 +313,150 @@ async fn maybe_post_process_transcription(
         provider.id, model
     );
 
+    run_prompt(
+        app,
+        settings,
+        &provider,
+        &model,
+        &prompt,
+        transcription,
+        app_name,
+        generation,
+    )
+    .await
+    .map(|text| (text, prompt))
+}
+
+/// Fix grammar with the built-in prompt, dropping a reply that rewords more
+/// of the transcription than `grammar_max_edit_ratio` allows
+async fn grammar_only(
+    app: &AppHandle,
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    transcription: &str,
+    app_name: Option<&str>,
+    generation: u64,
+) -> Option<(String, String)> {
+    debug!(
+        "Starting grammar-only correction with provider '{}' (model: {})",
+        provider.id, model
+    );
+    let corrected = run_prompt(
+        app,
+        settings,
+        provider,
+        model,
+        GRAMMAR_ONLY_PROMPT,
+        transcription,
+        app_name,
+        generation,
+    )
+    .await?;
+
+    if let Err(e) =
+        grammar::check_correction(transcription, &corrected, settings.grammar_max_edit_ratio)
+    {
+        warn!(
+            "Grammar correction rejected: {}. Keeping the original transcription.",
+            e
+        );
+        return None;
+    }
+    Some((corrected, GRAMMAR_ONLY_PROMPT.to_string()))
+}
+
+/// Run each step on the output of the previous one. A failed step stops the
+/// pipeline, so a half-transformed text is never pasted.
+async fn run_pipeline(
+    app: &AppHandle,
+    settings: &AppSettings,
+    pipeline: &PostProcessPipeline,
+    transcription: &str,
+    app_name: Option<&str>,
+    generation: u64,
+) -> Option<(String, String)> {
+    debug!(
+        "Starting post-processing pipeline '{}' with {} steps",
+        pipeline.name,
+        pipeline.steps.len()
+    );
+
+    let mut text = transcription.to_string();
+    for (index, step) in pipeline.steps.iter().enumerate() {
+        if crate::llm_client::cancelled_since(generation) {
+            return None;
+        }
+        let Some(provider) = settings.post_process_provider(&step.provider_id) else {
+            error!(
+                "Pipeline '{}' step {} uses unknown provider '{}'",
+                pipeline.name,
+                index + 1,
+                step.provider_id
+            );
+            return None;
+        };
+        debug!(
+            "Pipeline '{}' step {} ({}) with provider '{}' (model: {})",
+            pipeline.name,
+            index + 1,
+            step.name,
+            provider.id,
+            step.model
+        );
+        text = run_prompt(
+            app,
+            settings,
+            provider,
+            &step.model,
+            &step.prompt,
+            &text,
+            app_name,
+            generation,
+        )
+        .await?;
+    }
+
+    let prompts = pipeline
+        .steps
+        .iter()
+        .map(|step| format!("{}:\n{}", step.name, step.prompt))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some((text, prompts))
+}
+
+/// Run one prompt on `transcription`, streaming the reply to the frontend
+/// as `PostProcessStream` events. Gives up when the operation is cancelled
+/// after `generation` was taken.
+#[allow(clippy::too_many_arguments)]
+async fn run_prompt(
+    app: &AppHandle,
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    prompt: &str,
+    transcription: &str,
+    app_name: Option<&str>,
+    generation: u64,
+) -> Option<String> {
     // Replace ${output} variable in the prompt with the actual text
     let mut processed_prompt = prompt.replace("${output}", transcription);
 
     // Add context-aware instructions if enabled
-    let active_app = get_active_app();
     let mut context_additions = Vec::new();
 
-    // Tone adjustment
-    if settings.tone_adjustment_enabled {
-        let tone = if let Some(ref app_info) = active_app {
-            // Check for custom app mapping first
-            settings
-                .app_tone_mappings
-                .get(&app_info.name)
-                .copied()
-                .unwrap_or_else(|| {
-                    // Fall back to category-based tone
-                    let category = categorize_app(&app_info.name);
-                    category.default_tone()
-                })
+    // Tone adjustment, except when only grammar may change
+    if settings.tone_adjustment_enabled
+        && settings.post_process_mode != PostProcessMode::GrammarOnly
+    {
+        let tone = if let Some(name) = app_name {
+            // Check for the app or website's profile first
+            settings.app_profile(name).tone.unwrap_or_else(|| {
+                // Fall back to category-based tone
+                let category = categorize_app(name);
+                category.default_tone()
+            })
         } else {
             settings.default_tone
         };
@@ -134,20 +479,27 @@ async fn maybe_post_process_transcription(
     }
 
     // Developer mode
-    let in_developer_mode = match settings.developer_mode {
-        DeveloperMode::Off => false,
-        DeveloperMode::Always => true,
-        DeveloperMode::Auto => active_app
-            .as_ref()
-            .map(|app| is_developer_context(&app.name))
-            .unwrap_or(false),
-    };
+    let in_developer_mode = settings.developer_mode.is_active_in(app_name);
 
     if in_developer_mode {
         context_additions.push("Preserve code-related formatting including camelCase, snake_case, and PascalCase. Keep technical acronyms like API, HTTP, JSON, HTML, CSS unchanged.".to_string());
         debug!("Developer mode active");
     }
 
+    // Text already in the field, when the user allows sending it
+    let preceding_text = app_name
+        .filter(|name| {
+            settings.surrounding_text_in_llm_prompt && settings.reads_surrounding_text_in(name)
+        })
+        .and_then(|_| get_text_before_cursor());
+    if let Some(text) = preceding_text {
+        context_additions.push(format!(
+            "The transcript continues this text already in the field. Match its style and do not repeat it:\n\"\"\"\n{}\n\"\"\"",
+            text
+        ));
+        debug!("Added {} characters of surrounding text", text.len());
+    }
+
     // Append context additions to the prompt
     if !context_additions.is_empty() {
         processed_prompt = format!(
@@ -159,75 +511,148 @@ async fn maybe_post_process_transcription(
 
     debug!("Processed prompt length: {} chars", processed_prompt.len());
 
-    if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
-        #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
-        {
-            if !apple_intelligence::check_apple_intelligence_availability() {
-                debug!("Apple Intelligence selected but not currently available on this device");
-                return None;
-            }
-
-            let token_limit = model.trim().parse::<i32>().unwrap_or(0);
-            return match apple_intelligence::process_text(&processed_prompt, token_limit) {
-                Ok(result) => {
-                    if result.trim().is_empty() {
-                        debug!("Apple Intelligence returned an empty response");
-                        None
-                    } else {
-                        debug!(
-                            "Apple Intelligence post-processing succeeded. Output length: {} chars",
-                            result.len()
-                        );
-                        Some(result)
-                    }
-                }
-                Err(err) => {
-                    error!("Apple Intelligence post-processing failed: {}", err);
-                    None
-                }
-            };
-        }
-
-        #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
-        {
-            debug!("Apple Intelligence provider selected on unsupported platform");
-            return None;
-        }
+    let error = match complete_prompt(
+        app,
+        settings,
+        provider,
+        model,
+        &processed_prompt,
+        generation,
+    )
+    .await
+    {
+        Ok(content) => return Some(content),
+        Err(e) => e,
+    };
+    if crate::llm_client::cancelled_since(generation) {
+        return None;
     }
+    error!(
+        "LLM post-processing failed for provider '{}': {}",
+        provider.id, error
+    );
 
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
-
-    // Send the chat completion request
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
-    {
-        Ok(Some(content)) => {
+    // Try the fallback provider before giving up on post-processing
+    let fallback = settings
+        .fallback_post_process_provider()
+        .filter(|fallback| fallback.id != provider.id);
+    let result = match fallback {
+        Some(fallback) => {
+            let fallback_model = settings
+                .post_process_models
+                .get(&fallback.id)
+                .cloned()
+                .unwrap_or_default();
             debug!(
-                "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
-                provider.id,
-                content.len()
+                "Falling back to provider '{}' (model: {})",
+                fallback.id, fallback_model
             );
-            Some(content)
+            complete_prompt(
+                app,
+                settings,
+                fallback,
+                &fallback_model,
+                &processed_prompt,
+                generation,
+            )
+            .await
+            .map(|content| (fallback, content))
         }
-        Ok(None) => {
-            error!("LLM API response has no content");
-            None
+        None => Err(error.clone()),
+    };
+
+    match result {
+        Ok((fallback, content)) => {
+            let event = PostProcessFallback {
+                failed_provider_id: provider.id.clone(),
+                provider_id: fallback.id.clone(),
+                error,
+            };
+            if let Err(e) = event.emit(app) {
+                error!("Failed to emit post-process fallback event: {}", e);
+            }
+            Some(content)
         }
         Err(e) => {
             error!(
-                "LLM post-processing failed for provider '{}': {}. Falling back to original transcription.",
-                provider.id,
+                "LLM post-processing failed: {}. Falling back to original transcription.",
                 e
             );
+            let event = PostProcessFailed {
+                provider_id: provider.id.clone(),
+                error: e,
+            };
+            if let Err(e) = event.emit(app) {
+                error!("Failed to emit post-process failure event: {}", e);
+            }
             None
         }
     }
 }
 
+/// Send `prompt` to one provider, streaming the reply to the frontend as
+/// `PostProcessStream` events
+async fn complete_prompt(
+    app: &AppHandle,
+    settings: &AppSettings,
+    provider: &PostProcessProvider,
+    model: &str,
+    prompt: &str,
+    generation: u64,
+) -> Result<String, String> {
+    if model.trim().is_empty() {
+        return Err(format!(
+            "No model configured for provider '{}'",
+            provider.id
+        ));
+    }
+
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    // Send the chat completion request, forwarding the reply as it streams in
+    let mut text = String::new();
+    let result = crate::llm_client::stream_chat_completion(
+        provider,
+        api_key,
+        model,
+        prompt.to_string(),
+        generation,
+        |delta| {
+            text.push_str(delta);
+            let event = PostProcessStream {
+                delta: delta.to_string(),
+                text: text.clone(),
+                done: false,
+            };
+            if let Err(e) = event.emit(app) {
+                error!("Failed to emit post-process stream event: {}", e);
+            }
+        },
+    )
+    .await;
+
+    let done = PostProcessStream {
+        delta: String::new(),
+        text: result.clone().ok().flatten().unwrap_or_default(),
+        done: true,
+    };
+    if let Err(e) = done.emit(app) {
+        error!("Failed to emit post-process stream event: {}", e);
+    }
+
+    let content = result?.ok_or("LLM API response has no content")?;
+    debug!(
+        "LLM post-processing succeeded for provider '{}'. Output length: {} chars",
+        provider.id,
+        content.len()
+    );
+    Ok(content)
+}
+
 async fn maybe_convert_chinese_variant(
     settings: &AppSettings,
     transcription: &str,
@@ -272,6 +697,71 @@ async fn maybe_convert_chinese_variant(
     }
 }
 
+/// Result of the LLM and script conversion steps that follow transcription
+pub struct ProcessedTranscription {
+    /// Text to paste
+    pub final_text: String,
+    /// Set when any step changed the transcription
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    /// The operation was cancelled during post-processing; `final_text` is
+    /// the transcription without it and should not be pasted
+    pub cancelled: bool,
+}
+
+/// Chinese variant conversion, LLM post-processing and dual-script output, as
+/// configured in `settings`
+pub async fn process_transcription(
+    app: &AppHandle,
+    settings: &AppSettings,
+    transcription: &str,
+    app_name: Option<&str>,
+) -> ProcessedTranscription {
+    let generation = crate::llm_client::stream_generation();
+    let mut final_text = transcription.to_string();
+    let mut post_processed_text: Option<String> = None;
+    let mut post_process_prompt: Option<String> = None;
+
+    // First, check if Chinese variant conversion is needed
+    if let Some(converted_text) = maybe_convert_chinese_variant(settings, transcription).await {
+        final_text = converted_text;
+    }
+
+    // Then apply regular post-processing if enabled
+    // Note: Post-processing now uses final_text (which may be Chinese-converted)
+    let processed =
+        maybe_post_process_transcription(app, settings, &final_text, app_name, generation).await;
+    if crate::llm_client::cancelled_since(generation) {
+        return ProcessedTranscription {
+            final_text,
+            post_processed_text: None,
+            post_process_prompt: None,
+            cancelled: true,
+        };
+    }
+    if let Some((processed_text, prompt)) = processed {
+        post_processed_text = Some(processed_text.clone());
+        final_text = processed_text;
+        post_process_prompt = Some(prompt);
+    } else if final_text != transcription {
+        // Chinese conversion happened but no post-processing
+        post_processed_text = Some(final_text.clone());
+    }
+
+    // Language learning: interleave a translation after each sentence
+    if let Some(dual_text) = dual_script::maybe_dual_script(settings, &final_text).await {
+        post_processed_text = Some(dual_text.clone());
+        final_text = dual_text;
+    }
+
+    ProcessedTranscription {
+        final_text,
+        post_processed_text,
+        post_process_prompt,
+        cancelled: false,
+    }
+}
+
 impl ShortcutAction for TranscribeAction {
     fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
         let start_time = Instant::now();
@@ -289,7 +779,7 @@ impl ShortcutAction for TranscribeAction {
 
         // Get the microphone mode to determine audio feedback timing
         let settings = get_settings(app);
-        let is_always_on = settings.always_on_microphone;
+        let is_always_on = settings.keeps_microphone_open();
         debug!("Microphone mode - always_on: {}", is_always_on);
 
         let mut recording_started = false;
@@ -337,6 +827,8 @@ impl ShortcutAction for TranscribeAction {
                 lpm.start();
             }
 
+            start_recording_context(Arc::clone(&tm), settings.clone());
+
             // Dynamically register the cancel shortcut in a separate task to avoid deadlock
             shortcut::register_cancel_shortcut(app);
         }
@@ -382,6 +874,35 @@ impl ShortcutAction for TranscribeAction {
                 binding_id
             );
 
+            // The app and website being dictated into, for its profile and
+            // the history timeline
+            let RecordingContext {
+                active_app,
+                terms: context_terms,
+                window: target_window,
+            } = take_recording_context();
+            let app_name = active_app
+                .as_ref()
+                .map(|app| app.context_name().to_string());
+            let settings = get_settings(&ah);
+            let profile = app_name
+                .as_deref()
+                .map(|name| settings.app_profile(name))
+                .unwrap_or_default();
+            // Prompt the engine with the window's terms and the text the
+            // dictation continues, where allowed
+            let preceding_text = app_name
+                .as_deref()
+                .filter(|name| settings.reads_surrounding_text_in(name))
+                .and_then(|_| get_text_before_cursor());
+            let overrides = TranscriptionOverrides {
+                language: profile.language.clone(),
+                initial_prompt: preceding_text,
+                app_name: app_name.clone(),
+                context_terms: Some(context_terms).filter(|terms| !terms.is_empty()),
+                ..Default::default()
+            };
+
             let stop_recording_time = Instant::now();
             if let Some(samples) = rm.stop_recording(&binding_id) {
                 debug!(
@@ -392,8 +913,12 @@ impl ShortcutAction for TranscribeAction {
 
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
+                match tm.transcribe_detailed(samples, &overrides) {
+                    Ok(TranscriptionOutput {
+                        raw_text,
+                        text: transcription,
+                        ..
+                    }) => {
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
@@ -401,39 +926,18 @@ impl ShortcutAction for TranscribeAction {
                         );
                         if !transcription.is_empty() {
                             let settings = get_settings(&ah);
-                            let mut final_text = transcription.clone();
-                            let mut post_processed_text: Option<String> = None;
-                            let mut post_process_prompt: Option<String> = None;
-
-                            // First, check if Chinese variant conversion is needed
-                            if let Some(converted_text) =
-                                maybe_convert_chinese_variant(&settings, &transcription).await
-                            {
-                                final_text = converted_text;
-                            }
-
-                            // Then apply regular post-processing if enabled
-                            // Note: Post-processing now uses final_text (which may be Chinese-converted)
-                            if let Some(processed_text) =
-                                maybe_post_process_transcription(&settings, &final_text).await
-                            {
-                                post_processed_text = Some(processed_text.clone());
-                                final_text = processed_text;
-
-                                // Get the prompt that was used
-                                if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                    if let Some(prompt) = settings
-                                        .post_process_prompts
-                                        .iter()
-                                        .find(|p| &p.id == prompt_id)
-                                    {
-                                        post_process_prompt = Some(prompt.prompt.clone());
-                                    }
-                                }
-                            } else if final_text != transcription {
-                                // Chinese conversion happened but no post-processing
-                                post_processed_text = Some(final_text.clone());
-                            }
+                            let ProcessedTranscription {
+                                final_text,
+                                post_processed_text,
+                                post_process_prompt,
+                                cancelled,
+                            } = process_transcription(
+                                &ah,
+                                &settings,
+                                &transcription,
+                                app_name.as_deref(),
+                            )
+                            .await;
 
                             // Save to history with post-processed text and prompt
                             let hm_clone = Arc::clone(&hm);
@@ -443,8 +947,10 @@ impl ShortcutAction for TranscribeAction {
                                     .save_transcription(
                                         samples_clone,
                                         transcription_for_history,
+                                        raw_text,
                                         post_processed_text,
                                         post_process_prompt,
+                                        active_app,
                                     )
                                     .await
                                 {
@@ -452,26 +958,43 @@ impl ShortcutAction for TranscribeAction {
                                 }
                             });
 
-                            // Paste the final text (either processed or original)
-                            let ah_clone = ah.clone();
-                            let paste_time = Instant::now();
-                            ah.run_on_main_thread(move || {
-                                match utils::paste(final_text, ah_clone.clone()) {
-                                    Ok(()) => debug!(
-                                        "Text pasted successfully in {:?}",
-                                        paste_time.elapsed()
-                                    ),
-                                    Err(e) => error!("Failed to paste transcription: {}", e),
-                                }
-                                // Hide the overlay after transcription is complete
-                                utils::hide_recording_overlay(&ah_clone);
-                                change_tray_icon(&ah_clone, TrayIconState::Idle);
-                            })
-                            .unwrap_or_else(|e| {
-                                error!("Failed to run paste on main thread: {:?}", e);
+                            if cancelled {
+                                debug!("Post-processing cancelled, not pasting");
                                 utils::hide_recording_overlay(&ah);
                                 change_tray_icon(&ah, TrayIconState::Idle);
-                            });
+                            } else {
+                                // Paste the final text (either processed or original)
+                                let ah_clone = ah.clone();
+                                let paste_time = Instant::now();
+                                let paste_method =
+                                    profile.paste_method.unwrap_or(settings.paste_method);
+                                // Refocusing runs a helper process and waits for the
+                                // window, so it stays off the main thread
+                                if let Some(window) = &target_window {
+                                    focus_recording_window(&ah, window);
+                                }
+                                ah.run_on_main_thread(move || {
+                                    match utils::paste_with_method(
+                                        final_text,
+                                        ah_clone.clone(),
+                                        paste_method,
+                                    ) {
+                                        Ok(()) => debug!(
+                                            "Text pasted successfully in {:?}",
+                                            paste_time.elapsed()
+                                        ),
+                                        Err(e) => error!("Failed to paste transcription: {}", e),
+                                    }
+                                    // Hide the overlay after transcription is complete
+                                    utils::hide_recording_overlay(&ah_clone);
+                                    change_tray_icon(&ah_clone, TrayIconState::Idle);
+                                })
+                                .unwrap_or_else(|e| {
+                                    error!("Failed to run paste on main thread: {:?}", e);
+                                    utils::hide_recording_overlay(&ah);
+                                    change_tray_icon(&ah, TrayIconState::Idle);
+                                });
+                            }
                         } else {
                             utils::hide_recording_overlay(&ah);
                             change_tray_icon(&ah, TrayIconState::Idle);
@@ -574,7 +1097,8 @@ impl ShortcutAction for MeetingAction {
                     }
                 }
             }
-            MeetingState::Recording { meeting_id, .. } => {
+            MeetingState::Recording { meeting_id, .. }
+            | MeetingState::Paused { meeting_id, .. } => {
                 debug!("Stopping meeting from shortcut: {}", meeting_id);
                 match mm.stop_meeting(&meeting_id) {
                     Ok(()) => {
@@ -594,7 +1118,129 @@ impl ShortcutAction for MeetingAction {
     }
 
     fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
-        // No-op for toggle mode - meeting uses toggle behavior only
+        // Nothing to do on release, each press toggles the meeting
+    }
+
+    fn is_instant(&self) -> bool {
+        true
+    }
+}
+
+// Transcribe File Action
+struct TranscribeFileAction;
+
+impl ShortcutAction for TranscribeFileAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        use crate::managers::file_transcription::FileTranscriptionManager;
+        use tauri_plugin_dialog::DialogExt;
+
+        let extensions = FileTranscriptionManager::get_supported_extensions();
+        let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+        let ah = app.clone();
+        app.dialog()
+            .file()
+            .add_filter("Audio/Video Files", &extensions)
+            .pick_files(move |picked| {
+                let paths: Vec<String> = picked
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|file| file.into_path().ok())
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect();
+                if paths.is_empty() {
+                    return;
+                }
+
+                let fm = Arc::clone(&ah.state::<Arc<FileTranscriptionManager>>());
+                match fm.queue_files(&paths, None) {
+                    Ok(jobs) => {
+                        debug!("Queued {} file(s) from shortcut", jobs.len());
+                        // Nobody is in the app to press "process all"
+                        std::thread::spawn(move || {
+                            if let Err(e) = fm.process_all() {
+                                error!("Error processing files: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => error!("Failed to queue files: {}", e),
+                }
+            });
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {}
+
+    fn is_instant(&self) -> bool {
+        true
+    }
+}
+
+// Paste Last Transcript Action
+struct PasteLastTranscriptAction;
+
+impl ShortcutAction for PasteLastTranscriptAction {
+    fn start(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {}
+
+    fn stop(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Pasted on release so the shortcut's key doesn't mix into the paste keystroke
+        let Some(text) = crate::tray::latest_transcript(app) else {
+            return;
+        };
+        let ah = app.clone();
+        let _ = app.run_on_main_thread(move || {
+            if let Err(e) = utils::paste(text, ah) {
+                error!("Failed to paste last transcript: {}", e);
+            }
+        });
+    }
+
+    fn is_instant(&self) -> bool {
+        true
+    }
+}
+
+// Toggle Live Preview Action
+struct ToggleLivePreviewAction;
+
+impl ShortcutAction for ToggleLivePreviewAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        let enabled = !get_settings(app).live_preview_enabled;
+        crate::settings::update_settings(app, move |s| s.live_preview_enabled = enabled);
+        debug!(
+            "Live preview {} from shortcut",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        // Apply to a recording in progress; a disabled preview's worker
+        // notices the setting and ends by itself
+        let recording = app.state::<Arc<AudioRecordingManager>>().is_recording();
+        if let Some(lpm) = app.try_state::<Arc<LivePreviewManager>>() {
+            if enabled && recording {
+                lpm.start();
+            } else if !enabled {
+                lpm.stop();
+            }
+        }
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {}
+
+    fn is_instant(&self) -> bool {
+        true
+    }
+}
+
+// Cycle Action
+struct CycleAction(CycleKind);
+
+impl ShortcutAction for CycleAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        cycle::cycle(app, self.0);
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {}
+
+    fn is_instant(&self) -> bool {
+        true
     }
 }
 
@@ -617,5 +1263,29 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "meeting".to_string(),
         Arc::new(MeetingAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "transcribe_file".to_string(),
+        Arc::new(TranscribeFileAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "paste_last_transcript".to_string(),
+        Arc::new(PasteLastTranscriptAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "toggle_live_preview".to_string(),
+        Arc::new(ToggleLivePreviewAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "cycle_microphone".to_string(),
+        Arc::new(CycleAction(CycleKind::Microphone)) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "cycle_model".to_string(),
+        Arc::new(CycleAction(CycleKind::Model)) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        "cycle_language".to_string(),
+        Arc::new(CycleAction(CycleKind::Language)) as Arc<dyn ShortcutAction>,
+    );
     map
 });
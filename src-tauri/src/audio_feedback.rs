@@ -1,3 +1,5 @@
+use crate::focus_mode;
+use crate::quiet_hours;
 use crate::settings::SoundTheme;
 use crate::settings::{self, AppSettings};
 use cpal::traits::{DeviceTrait, HostTrait};
@@ -42,7 +44,10 @@ fn get_sound_base_dir(settings: &AppSettings) -> tauri::path::BaseDirectory {
 
 pub fn play_feedback_sound(app: &AppHandle, sound_type: SoundType) {
     let settings = settings::get_settings(app);
-    if !settings.audio_feedback {
+    if !settings.audio_feedback
+        || quiet_hours::is_active(app)
+        || focus_mode::should_suppress_sounds(app)
+    {
         return;
     }
     if let Some(path) = resolve_sound_path(app, &settings, sound_type) {
@@ -52,7 +57,10 @@ pub fn play_feedback_sound(app: &AppHandle, sound_type: SoundType) {
 
 pub fn play_feedback_sound_blocking(app: &AppHandle, sound_type: SoundType) {
     let settings = settings::get_settings(app);
-    if !settings.audio_feedback {
+    if !settings.audio_feedback
+        || quiet_hours::is_active(app)
+        || focus_mode::should_suppress_sounds(app)
+    {
         return;
     }
     if let Some(path) = resolve_sound_path(app, &settings, sound_type) {
@@ -0,0 +1,178 @@
+//! Decode media files through an ffmpeg binary.
+//!
+//! ffmpeg is run as a child process that writes 16 kHz mono f32 LE samples
+//! to stdout, so any container or codec it understands can be transcribed.
+//! The binary is taken from the configured path, a copy bundled next to the
+//! app's executable, or `PATH`, in that order. ffprobe is looked up the same
+//! way and used to read a file's duration before decoding it.
+
+use anyhow::{anyhow, Result};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const SAMPLE_RATE: u32 = 16000;
+
+/// Bytes read from ffmpeg between progress and cancellation checks, about 2 seconds of audio
+const READ_CHUNK_BYTES: usize = SAMPLE_RATE as usize * 4 * 2;
+
+/// Locate `tool` ("ffmpeg" or "ffprobe"). A configured ffmpeg path also
+/// points at the ffprobe in the same directory.
+pub fn find_tool(tool: &str, configured_ffmpeg: &str) -> Option<PathBuf> {
+    let file_name = format!("{}{}", tool, std::env::consts::EXE_SUFFIX);
+
+    let configured = Path::new(configured_ffmpeg.trim());
+    if !configured.as_os_str().is_empty() {
+        let candidate = if tool == "ffmpeg" {
+            configured.to_path_buf()
+        } else {
+            configured.with_file_name(&file_name)
+        };
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    find_executable(tool)
+}
+
+/// Locate a helper binary bundled next to the app's executable or on `PATH`
+pub fn find_executable(name: &str) -> Option<PathBuf> {
+    let file_name = format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+
+    let bundled = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&file_name)));
+    if let Some(bundled) = bundled.filter(|p| p.is_file()) {
+        return Some(bundled);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(&file_name))
+            .find(|p| p.is_file())
+    })
+}
+
+/// Duration of `path` in seconds as reported by ffprobe
+pub fn probe_duration(ffprobe: &Path, path: &Path) -> Option<f64> {
+    let output = Command::new(ffprobe)
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+        ])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_duration(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_duration(output: &str) -> Option<f64> {
+    output
+        .lines()
+        .find_map(|line| line.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs > 0.0)
+}
+
+/// Decode the first audio stream of `path` to 16 kHz mono samples.
+/// `on_progress` is called with the seconds decoded so far, and ffmpeg is
+/// stopped as soon as `is_cancelled` returns true.
+pub fn decode(
+    ffmpeg: &Path,
+    path: &Path,
+    is_cancelled: impl Fn() -> bool,
+    mut on_progress: impl FnMut(f64),
+) -> Result<Vec<f32>> {
+    let mut child = Command::new(ffmpeg)
+        .args(["-nostdin", "-v", "error", "-i"])
+        .arg(path)
+        .args([
+            "-map",
+            "0:a:0",
+            "-vn",
+            "-ac",
+            "1",
+            "-ar",
+            &SAMPLE_RATE.to_string(),
+            "-f",
+            "f32le",
+            "-",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start ffmpeg: {}", e))?;
+
+    // Drain stderr on its own thread so a chatty ffmpeg can't block on a full pipe
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let mut bytes = Vec::new();
+    let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+    loop {
+        if is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("Transcription cancelled during audio loading"));
+        }
+        let read = stdout.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&chunk[..read]);
+        on_progress((bytes.len() / 4) as f64 / SAMPLE_RATE as f64);
+    }
+
+    let status = child.wait()?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        let detail = stderr.lines().last().unwrap_or("unknown error");
+        return Err(anyhow!("ffmpeg failed to decode the file: {}", detail));
+    }
+
+    Ok(samples_from_f32le(&bytes))
+}
+
+fn samples_from_f32le(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_are_parsed_from_ffprobe_output() {
+        assert_eq!(parse_duration("3605.120000\n"), Some(3605.12));
+        assert_eq!(parse_duration("N/A\n"), None);
+        assert_eq!(parse_duration("0.000000\n"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn f32le_bytes_become_samples() {
+        let mut bytes: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        bytes.push(0);
+        assert_eq!(samples_from_f32le(&bytes), vec![0.5, -1.0]);
+    }
+}
@@ -0,0 +1,351 @@
+//! Minimal FLAC encoder for mono 16-bit recordings.
+//!
+//! Uses the fixed linear predictors (orders 0-4) with Rice-coded residuals.
+//! That is enough to make speech recordings roughly half the size of PCM WAV
+//! while staying lossless. Decoding is left to symphonia.
+
+const BLOCK_SIZE: usize = 4096;
+const BITS_PER_SAMPLE: u32 = 16;
+const MAX_FIXED_ORDER: usize = 4;
+/// Largest Rice parameter with 4-bit parameters (15 is the escape code)
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Encode mono samples in the range [-1.0, 1.0] as a 16-bit FLAC stream
+pub fn encode_flac(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let pcm: Vec<i32> = samples
+        .iter()
+        .map(|sample| (sample * i16::MAX as f32) as i16 as i32)
+        .collect();
+
+    let mut out = Vec::with_capacity(pcm.len());
+    out.extend_from_slice(b"fLaC");
+    write_stream_info(&mut out, sample_rate, pcm.len() as u64);
+
+    for (frame_number, block) in pcm.chunks(BLOCK_SIZE).enumerate() {
+        encode_frame(&mut out, block, frame_number as u64);
+    }
+
+    out
+}
+
+fn write_stream_info(out: &mut Vec<u8>, sample_rate: u32, total_samples: u64) {
+    let mut w = BitWriter::new();
+    // Last metadata block, type STREAMINFO, 34 bytes long
+    w.write(1, 1);
+    w.write(0, 7);
+    w.write(34, 24);
+
+    w.write(BLOCK_SIZE as u64, 16); // min block size
+    w.write(BLOCK_SIZE as u64, 16); // max block size
+    w.write(0, 24); // min frame size (unknown)
+    w.write(0, 24); // max frame size (unknown)
+    w.write(sample_rate as u64, 20);
+    w.write(0, 3); // channels - 1
+    w.write((BITS_PER_SAMPLE - 1) as u64, 5);
+    w.write(total_samples >> 32, 4);
+    w.write(total_samples & 0xFFFF_FFFF, 32);
+    // MD5 signature left unset, which decoders treat as "not computed"
+    for _ in 0..4 {
+        w.write(0, 32);
+    }
+
+    out.extend_from_slice(&w.into_bytes());
+}
+
+fn encode_frame(out: &mut Vec<u8>, block: &[i32], frame_number: u64) {
+    let mut w = BitWriter::new();
+
+    w.write(0b11_1111_1111_1110, 14); // sync code
+    w.write(0, 1); // reserved
+    w.write(0, 1); // fixed block size stream
+    if block.len() == BLOCK_SIZE {
+        w.write(12, 4); // 256 * 2^(12 - 8) = 4096
+    } else {
+        w.write(7, 4); // 16-bit block size - 1 follows the frame number
+    }
+    w.write(0, 4); // sample rate from STREAMINFO
+    w.write(0, 4); // mono
+    w.write(0b100, 3); // 16 bits per sample
+    w.write(0, 1); // reserved
+    write_utf8_number(&mut w, frame_number);
+    if block.len() != BLOCK_SIZE {
+        w.write((block.len() - 1) as u64, 16);
+    }
+    let header_crc = crc8(&w.bytes);
+    w.write(header_crc as u64, 8);
+
+    encode_subframe(&mut w, block);
+
+    w.align();
+    let frame_crc = crc16(&w.bytes);
+    w.write(frame_crc as u64, 16);
+
+    out.extend_from_slice(&w.into_bytes());
+}
+
+fn encode_subframe(w: &mut BitWriter, block: &[i32]) {
+    if block.iter().all(|&s| s == block[0]) {
+        w.write(0, 1);
+        w.write(0b000000, 6); // CONSTANT
+        w.write(0, 1);
+        w.write_signed(block[0] as i64, BITS_PER_SAMPLE);
+        return;
+    }
+
+    let max_order = MAX_FIXED_ORDER.min(block.len() - 1);
+    let (order, rice_param, residuals, bits) = (0..=max_order)
+        .map(|order| {
+            let residuals = fixed_residuals(block, order);
+            let (param, residual_bits) = best_rice_param(&residuals);
+            let bits = order as u64 * BITS_PER_SAMPLE as u64 + 6 + residual_bits;
+            (order, param, residuals, bits)
+        })
+        .min_by_key(|(_, _, _, bits)| *bits)
+        .expect("at least one predictor order");
+
+    if bits >= block.len() as u64 * BITS_PER_SAMPLE as u64 {
+        w.write(0, 1);
+        w.write(0b000001, 6); // VERBATIM
+        w.write(0, 1);
+        for &sample in block {
+            w.write_signed(sample as i64, BITS_PER_SAMPLE);
+        }
+        return;
+    }
+
+    w.write(0, 1);
+    w.write(0b001000 | order as u64, 6); // FIXED with predictor order
+    w.write(0, 1);
+    for &sample in &block[..order] {
+        w.write_signed(sample as i64, BITS_PER_SAMPLE);
+    }
+    w.write(0, 2); // Rice coding with 4-bit parameters
+    w.write(0, 4); // a single partition
+    w.write(rice_param as u64, 4);
+    for residual in residuals {
+        let folded = fold(residual);
+        w.write_unary(folded >> rice_param);
+        w.write(folded, rice_param);
+    }
+}
+
+/// Prediction residuals of the fixed polynomial predictor of the given order
+fn fixed_residuals(block: &[i32], order: usize) -> Vec<i64> {
+    let s = |i: usize| block[i] as i64;
+    (order..block.len())
+        .map(|i| match order {
+            0 => s(i),
+            1 => s(i) - s(i - 1),
+            2 => s(i) - 2 * s(i - 1) + s(i - 2),
+            3 => s(i) - 3 * s(i - 1) + 3 * s(i - 2) - s(i - 3),
+            _ => s(i) - 4 * s(i - 1) + 6 * s(i - 2) - 4 * s(i - 3) + s(i - 4),
+        })
+        .collect()
+}
+
+/// Rice parameter with the smallest encoded size, and that size in bits
+fn best_rice_param(residuals: &[i64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|param| {
+            let bits = residuals
+                .iter()
+                .map(|&r| 1 + param as u64 + (fold(r) >> param))
+                .sum();
+            (param, bits)
+        })
+        .min_by_key(|(_, bits)| *bits)
+        .expect("at least one Rice parameter")
+}
+
+/// Map signed residuals to unsigned: 0, -1, 1, -2, 2 ... -> 0, 1, 2, 3, 4 ...
+fn fold(residual: i64) -> u64 {
+    ((residual << 1) ^ (residual >> 63)) as u64
+}
+
+/// Frame numbers use the same variable-length scheme as UTF-8, extended to 36 bits
+fn write_utf8_number(w: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        w.write(value, 8);
+        return;
+    }
+
+    let mut len = 2;
+    while value >= 1 << ((7 - len) + 6 * (len - 1)) {
+        len += 1;
+    }
+    let prefix = (0xFF00u64 >> len) & 0xFF;
+    w.write(prefix | (value >> (6 * (len - 1))), 8);
+    for i in (0..len - 1).rev() {
+        w.write(0x80 | ((value >> (6 * i)) & 0x3F), 8);
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// MSB-first bit writer
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            acc: 0,
+            bits: 0,
+        }
+    }
+
+    /// Write the low `count` bits of `value` (at most 32)
+    fn write(&mut self, value: u64, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.acc = (self.acc << count) | (value & ((1u64 << count) - 1));
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push((self.acc >> self.bits) as u8);
+        }
+        self.acc &= (1u64 << self.bits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i64, count: u32) {
+        self.write(value as u64, count);
+    }
+
+    /// `zeros` zero bits followed by a one bit
+    fn write_unary(&mut self, mut zeros: u64) {
+        while zeros >= 32 {
+            self.write(0, 32);
+            zeros -= 32;
+        }
+        self.write(1, zeros as u32 + 1);
+    }
+
+    fn align(&mut self) {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    fn decode(bytes: Vec<u8>) -> Vec<i16> {
+        let mss = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("flac");
+        let mut format = symphonia::default::get_probe()
+            .format(
+                &hint,
+                mss,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .unwrap()
+            .format;
+        let track = format.default_track().unwrap();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions { verify: true })
+            .unwrap();
+
+        let mut samples = Vec::new();
+        while let Ok(packet) = format.next_packet() {
+            let decoded = decoder.decode(&packet).unwrap();
+            let mut buf = SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+            buf.copy_interleaved_ref(decoded);
+            samples.extend_from_slice(buf.samples());
+        }
+        samples
+    }
+
+    fn to_pcm(samples: &[f32]) -> Vec<i16> {
+        samples
+            .iter()
+            .map(|s| (s * i16::MAX as f32) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_speech_like_signal() {
+        // Two tones plus a little noise, spanning several blocks and a partial last block
+        let mut seed = 1u32;
+        let samples: Vec<f32> = (0..16000 * 2 + 123)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let noise = (seed >> 16) as f32 / 65536.0 - 0.5;
+                let t = i as f32 / 16000.0;
+                0.4 * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+                    + 0.2 * (2.0 * std::f32::consts::PI * 1300.0 * t).sin()
+                    + 0.01 * noise
+            })
+            .collect();
+
+        let encoded = encode_flac(&samples, 16000);
+        assert!(
+            encoded.len() < samples.len() * 2,
+            "FLAC should beat 16-bit PCM"
+        );
+        assert_eq!(decode(encoded), to_pcm(&samples));
+    }
+
+    #[test]
+    fn round_trips_silence_and_full_scale_noise() {
+        let mut seed = 7u32;
+        let mut samples = vec![0.0f32; BLOCK_SIZE];
+        samples.extend((0..BLOCK_SIZE).map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) as f32 / 32768.0 - 1.0
+        }));
+
+        assert_eq!(decode(encode_flac(&samples, 16000)), to_pcm(&samples));
+    }
+
+    #[test]
+    fn encodes_frame_numbers_past_one_byte() {
+        let samples = vec![0.25f32; BLOCK_SIZE * 130];
+        assert_eq!(decode(encode_flac(&samples, 16000)), to_pcm(&samples));
+    }
+}
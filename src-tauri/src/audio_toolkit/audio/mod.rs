@@ -1,12 +1,19 @@
 // Re-export all audio components
 mod device;
+pub mod ffmpeg;
+mod flac;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
+mod waveform;
 
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
+pub use flac::encode_flac;
 pub use recorder::AudioRecorder;
 pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use utils::{
+    decode_audio_bytes, decode_audio_file, encode_wav, read_audio_file, save_wav_file,
+};
 pub use visualizer::AudioVisualiser;
+pub use waveform::{compute_peaks, downsample_peaks};
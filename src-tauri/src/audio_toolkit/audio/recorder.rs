@@ -16,6 +16,40 @@ use crate::audio_toolkit::{
     VoiceActivityDetector,
 };
 
+/// Length of each chunk fed by a simulated input
+const SIMULATED_CHUNK: Duration = Duration::from_millis(30);
+
+/// Plays a file in fixed-size chunks, then silence until rewound
+struct SimulatedFeed {
+    samples: Vec<f32>,
+    chunk_len: usize,
+    position: usize,
+}
+
+impl SimulatedFeed {
+    /// Silent until the first `rewind`
+    fn new(samples: Vec<f32>, chunk_len: usize) -> Self {
+        let position = samples.len();
+        Self {
+            samples,
+            chunk_len: chunk_len.max(1),
+            position,
+        }
+    }
+
+    fn rewind(&mut self) {
+        self.position = 0;
+    }
+
+    fn next_chunk(&mut self) -> Vec<f32> {
+        let end = (self.position + self.chunk_len).min(self.samples.len());
+        let mut chunk = self.samples[self.position..end].to_vec();
+        chunk.resize(self.chunk_len, 0.0);
+        self.position = end;
+        chunk
+    }
+}
+
 enum Cmd {
     Start,
     Stop(mpsc::Sender<Vec<f32>>),
@@ -25,6 +59,9 @@ enum Cmd {
 /// Type alias for audio sample callback function
 type AudioCallback = Arc<dyn Fn(&[f32]) + Send + Sync + 'static>;
 
+/// Type alias for the callback receiving every frame and whether a recording is running
+type FrameCallback = Arc<dyn Fn(&[f32], bool) + Send + Sync + 'static>;
+
 pub struct AudioRecorder {
     device: Option<Device>,
     cmd_tx: Option<mpsc::Sender<Cmd>>,
@@ -32,6 +69,7 @@ pub struct AudioRecorder {
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
     audio_cb: Option<AudioCallback>,
+    frame_cb: Option<FrameCallback>,
 }
 
 impl AudioRecorder {
@@ -43,6 +81,7 @@ impl AudioRecorder {
             vad: None,
             level_cb: None,
             audio_cb: None,
+            frame_cb: None,
         })
     }
 
@@ -69,6 +108,17 @@ impl AudioRecorder {
         self
     }
 
+    /// Set a callback to receive every 16 kHz frame while the stream is open,
+    /// before VAD and whether or not a recording is running.
+    /// This is used for wake word detection.
+    pub fn with_frame_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(&[f32], bool) + Send + Sync + 'static,
+    {
+        self.frame_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -90,6 +140,7 @@ impl AudioRecorder {
         // Move the optional callbacks into the worker thread
         let level_cb = self.level_cb.clone();
         let audio_cb = self.audio_cb.clone();
+        let frame_cb = self.frame_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -133,7 +184,15 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb, audio_cb);
+            run_consumer(
+                sample_rate,
+                vad,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                audio_cb,
+                frame_cb,
+            );
             // stream is dropped here, after run_consumer returns
         });
 
@@ -144,6 +203,85 @@ impl AudioRecorder {
         Ok(())
     }
 
+    /// Open a simulated input that plays `samples` (mono, at `sample_rate`) in
+    /// real time instead of capturing from a device. Playback restarts on
+    /// every `start()` and is followed by silence, so a recording behaves like
+    /// someone speaking into a microphone. Lets the full dictation flow run on
+    /// machines without audio hardware.
+    pub fn open_simulated(
+        &mut self,
+        samples: Vec<f32>,
+        sample_rate: u32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.worker_handle.is_some() {
+            return Ok(()); // already open
+        }
+
+        let (sample_tx, sample_rx) = mpsc::channel::<Vec<f32>>();
+        let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
+        let (consumer_cmd_tx, consumer_cmd_rx) = mpsc::channel::<Cmd>();
+
+        let vad = self.vad.clone();
+        let level_cb = self.level_cb.clone();
+        let audio_cb = self.audio_cb.clone();
+        let frame_cb = self.frame_cb.clone();
+
+        let worker = std::thread::spawn(move || {
+            log::info!(
+                "Using simulated input: {:.1}s at {} Hz",
+                samples.len() as f32 / sample_rate as f32,
+                sample_rate
+            );
+
+            let consumer = std::thread::spawn(move || {
+                run_consumer(
+                    sample_rate,
+                    vad,
+                    sample_rx,
+                    consumer_cmd_rx,
+                    level_cb,
+                    audio_cb,
+                    frame_cb,
+                )
+            });
+
+            let chunk_len = sample_rate as usize * SIMULATED_CHUNK.as_millis() as usize / 1000;
+            let mut feed = SimulatedFeed::new(samples, chunk_len);
+
+            'feed: loop {
+                // Forward commands, rewinding the file when a recording starts
+                loop {
+                    match cmd_rx.try_recv() {
+                        Ok(Cmd::Start) => {
+                            feed.rewind();
+                            let _ = consumer_cmd_tx.send(Cmd::Start);
+                        }
+                        Ok(Cmd::Shutdown) | Err(mpsc::TryRecvError::Disconnected) => break 'feed,
+                        Ok(cmd) => {
+                            let _ = consumer_cmd_tx.send(cmd);
+                        }
+                        Err(mpsc::TryRecvError::Empty) => break,
+                    }
+                }
+
+                if sample_tx.send(feed.next_chunk()).is_err() {
+                    break;
+                }
+                std::thread::sleep(SIMULATED_CHUNK);
+            }
+
+            // Closing the sample channel ends the consumer even if it is waiting
+            let _ = consumer_cmd_tx.send(Cmd::Shutdown);
+            drop(sample_tx);
+            let _ = consumer.join();
+        });
+
+        self.cmd_tx = Some(cmd_tx);
+        self.worker_handle = Some(worker);
+
+        Ok(())
+    }
+
     pub fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(tx) = &self.cmd_tx {
             tx.send(Cmd::Start)?;
@@ -262,6 +400,7 @@ fn run_consumer(
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
     audio_cb: Option<AudioCallback>,
+    frame_cb: Option<FrameCallback>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
@@ -342,6 +481,9 @@ fn run_consumer(
 
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
+            if let Some(cb) = &frame_cb {
+                cb(frame, recording);
+            }
             handle_frame(frame, recording, &vad, &mut processed_samples, &audio_cb)
         });
 
@@ -372,3 +514,25 @@ fn run_consumer(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulated_input_replays_file_on_each_start() {
+        let samples: Vec<f32> = (1..=5).map(|i| i as f32).collect();
+        let mut feed = SimulatedFeed::new(samples, 2);
+
+        // Silence until a recording starts
+        assert_eq!(feed.next_chunk(), vec![0.0, 0.0]);
+
+        for _ in 0..2 {
+            feed.rewind();
+            assert_eq!(feed.next_chunk(), vec![1.0, 2.0]);
+            assert_eq!(feed.next_chunk(), vec![3.0, 4.0]);
+            assert_eq!(feed.next_chunk(), vec![5.0, 0.0]);
+            assert_eq!(feed.next_chunk(), vec![0.0, 0.0]);
+        }
+    }
+}
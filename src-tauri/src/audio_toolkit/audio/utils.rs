@@ -1,18 +1,19 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use hound::{WavSpec, WavWriter};
 use log::debug;
+use std::io::Cursor;
 use std::path::Path;
 
+const WAV_SPEC: WavSpec = WavSpec {
+    channels: 1,
+    sample_rate: 16000,
+    bits_per_sample: 16,
+    sample_format: hound::SampleFormat::Int,
+};
+
 /// Save audio samples as a WAV file
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
-    let spec = WavSpec {
-        channels: 1,
-        sample_rate: 16000,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
-
-    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+    let mut writer = WavWriter::create(file_path.as_ref(), WAV_SPEC)?;
 
     // Convert f32 samples to i16 for WAV
     for sample in samples {
@@ -24,3 +25,99 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Encode audio samples as a 16 kHz mono WAV file in memory
+pub fn encode_wav(samples: &[f32]) -> Result<Vec<u8>> {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = WavWriter::new(&mut cursor, WAV_SPEC)?;
+    for sample in samples {
+        writer.write_sample((sample * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+    Ok(cursor.into_inner())
+}
+
+/// Decode a saved recording (WAV or FLAC) back into mono f32 samples
+pub fn read_audio_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    Ok(decode_audio_file(file_path)?.0)
+}
+
+/// Decode any supported audio file into mono f32 samples at its own sample rate
+pub fn decode_audio_file<P: AsRef<Path>>(file_path: P) -> Result<(Vec<f32>, u32)> {
+    let path = file_path.as_ref();
+    let file = std::fs::File::open(path)?;
+    let extension = path.extension().and_then(|e| e.to_str());
+    decode_media(Box::new(file), extension)
+        .map_err(|e| anyhow!("Failed to decode {:?}: {}", path, e))
+}
+
+/// Decode audio held in memory, e.g. a recording decrypted for playback.
+/// `extension` helps the format probe, as it would for a file.
+pub fn decode_audio_bytes(bytes: Vec<u8>, extension: Option<&str>) -> Result<(Vec<f32>, u32)> {
+    decode_media(Box::new(Cursor::new(bytes)), extension)
+}
+
+fn decode_media(
+    source: Box<dyn symphonia::core::io::MediaSource>,
+    extension: Option<&str>,
+) -> Result<(Vec<f32>, u32)> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let mss = MediaSourceStream::new(source, Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = extension {
+        hint.with_extension(ext);
+    }
+
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )?
+        .format;
+    let track = format
+        .default_track()
+        .ok_or_else(|| anyhow!("No audio track"))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Unknown sample rate"))?;
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = decoder.decode(&packet)?;
+        let channels = decoded.spec().channels.count().max(1);
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+        buf.copy_interleaved_ref(decoded);
+        samples.extend(
+            buf.samples()
+                .chunks(channels)
+                .map(|frame| frame.iter().sum::<f32>() / channels as f32),
+        );
+    }
+
+    Ok((samples, sample_rate))
+}
@@ -0,0 +1,53 @@
+//! Waveform peaks for drawing a recording's scrubber.
+//!
+//! Peaks are the largest absolute sample in each window, quantized to a byte
+//! so a minute of speech at 50 peaks per second takes 3 KB. The UI asks for
+//! however many bars fit its width and gets the stored peaks merged down.
+
+/// Largest absolute sample of every `window` samples, scaled to 0-255
+pub fn compute_peaks(samples: &[f32], window: usize) -> Vec<u8> {
+    samples
+        .chunks(window.max(1))
+        .map(|chunk| {
+            let peak = chunk.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+            (peak.min(1.0) * u8::MAX as f32).round() as u8
+        })
+        .collect()
+}
+
+/// Merge stored peaks into `buckets` bars in the range 0.0-1.0. Recordings
+/// shorter than `buckets` windows return one bar per window.
+pub fn downsample_peaks(peaks: &[u8], buckets: usize) -> Vec<f32> {
+    if peaks.is_empty() || buckets == 0 {
+        return Vec::new();
+    }
+    let buckets = buckets.min(peaks.len());
+    (0..buckets)
+        .map(|bucket| {
+            let start = bucket * peaks.len() / buckets;
+            let end = ((bucket + 1) * peaks.len() / buckets).max(start + 1);
+            let peak = peaks[start..end].iter().copied().max().unwrap_or(0);
+            peak as f32 / u8::MAX as f32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peaks_take_the_loudest_sample_per_window() {
+        let samples = [0.0, -0.5, 0.25, 1.0, 2.0, 0.1, -0.2];
+        assert_eq!(compute_peaks(&samples, 3), vec![128, 255, 51]);
+        assert!(compute_peaks(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn downsampling_keeps_the_loudest_peak_per_bucket() {
+        let peaks = [0, 255, 51, 102, 0];
+        assert_eq!(downsample_peaks(&peaks, 2), vec![1.0, 0.4]);
+        assert_eq!(downsample_peaks(&peaks, 10).len(), 5);
+        assert!(downsample_peaks(&peaks, 0).is_empty());
+    }
+}
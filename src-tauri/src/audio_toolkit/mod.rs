@@ -3,13 +3,20 @@ pub mod constants;
 pub mod text;
 pub mod utils;
 pub mod vad;
+pub mod wake_word;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    compute_peaks, decode_audio_bytes, decode_audio_file, downsample_peaks, encode_flac,
+    encode_wav, list_input_devices, list_output_devices, read_audio_file, save_wav_file,
+    AudioRecorder, CpalDeviceInfo,
 };
 pub use text::{
-    apply_corrections, apply_custom_words, apply_formatting, filter_transcription_output,
-    FormattingRules,
+    apply_corrections, apply_custom_words, apply_formatting, apply_identifier_casing,
+    apply_inverse_text_normalization, apply_regex_rules, apply_spelling,
+    filter_transcription_output, is_itn_language, CustomWordRules, FillerFilter, FilterRules,
+    FormattingRules, ItnLocale, ProfanityFilter, RegexRule, WordMatchAlgorithm,
+    DEFAULT_FILLER_WORDS,
 };
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
+pub use wake_word::WakeWordDetector;
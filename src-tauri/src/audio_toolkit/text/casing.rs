@@ -0,0 +1,182 @@
+//! Identifier casing commands for dictating code: "camel case user name"
+//! becomes `userName`, "snake case http client" `http_client` and "constant
+//! case max retries" `MAX_RETRIES`.
+//!
+//! A command takes the words after it up to the end of the sentence or
+//! clause, so "set camel case user name, then save" gives "set userName,
+//! then save".
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Casing {
+    Camel,
+    Pascal,
+    Snake,
+    Constant,
+    Kebab,
+}
+
+impl Casing {
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "camel" => Some(Casing::Camel),
+            "pascal" => Some(Casing::Pascal),
+            "snake" => Some(Casing::Snake),
+            "constant" | "screaming" => Some(Casing::Constant),
+            "kebab" => Some(Casing::Kebab),
+            _ => None,
+        }
+    }
+
+    fn join(self, words: &[String]) -> String {
+        let capitalized = || words.iter().map(|word| capitalize(word));
+        match self {
+            Casing::Camel => words
+                .iter()
+                .take(1)
+                .cloned()
+                .chain(capitalized().skip(1))
+                .collect(),
+            Casing::Pascal => capitalized().collect(),
+            Casing::Snake => words.join("_"),
+            Casing::Constant => words.join("_").to_uppercase(),
+            Casing::Kebab => words.join("-"),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The word in `token`, lowercased, and the punctuation after it
+fn split_token(token: &str) -> (String, &str) {
+    let end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    let word = token[..end]
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    (word, &token[end..])
+}
+
+/// The casing a command starting at `tokens[i]` asks for, and how many
+/// tokens the command itself takes
+fn casing_command(tokens: &[&str], i: usize) -> Option<(Casing, usize)> {
+    let (word, trailing) = split_token(tokens[i]);
+    // Whisper sometimes writes the command as one word, "camelcase"
+    if let Some(casing) = word.strip_suffix("case").and_then(Casing::from_word) {
+        return trailing.is_empty().then_some((casing, 1));
+    }
+
+    let casing = Casing::from_word(&word)?;
+    if !trailing.is_empty() {
+        return None;
+    }
+    let (next, trailing) = split_token(tokens.get(i + 1)?);
+    // "screaming snake case"
+    if casing == Casing::Constant && next == "snake" && trailing.is_empty() {
+        let (last, trailing) = split_token(tokens.get(i + 2)?);
+        return (last == "case" && trailing.is_empty()).then_some((casing, 3));
+    }
+    (next == "case" && trailing.is_empty()).then_some((casing, 2))
+}
+
+/// Replaces identifier casing commands with the identifier they spell.
+///
+/// # Arguments
+/// * `text` - The transcription to scan for casing commands
+///
+/// # Returns
+/// The text with each casing command and its words joined into an identifier
+pub fn apply_identifier_casing(text: &str) -> String {
+    let mut result: Vec<String> = Vec::new();
+    let mut changed = false;
+
+    for line in text.split('\n') {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let mut out: Vec<String> = Vec::new();
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let Some((casing, command_len)) = casing_command(&tokens, i) else {
+                out.push(tokens[i].to_string());
+                i += 1;
+                continue;
+            };
+
+            // Take words up to the first one followed by punctuation
+            let mut words = Vec::new();
+            let mut trailing = "";
+            let mut j = i + command_len;
+            while j < tokens.len() {
+                let (word, after) = split_token(tokens[j]);
+                j += 1;
+                if !word.is_empty() {
+                    words.push(word);
+                }
+                if !after.is_empty() {
+                    trailing = after;
+                    break;
+                }
+            }
+
+            if words.is_empty() {
+                out.push(tokens[i].to_string());
+                i += 1;
+                continue;
+            }
+            out.push(format!("{}{}", casing.join(&words), trailing));
+            changed = true;
+            i = j;
+        }
+
+        result.push(out.join(" "));
+    }
+
+    if changed {
+        result.join("\n")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casing_commands_join_the_following_words() {
+        assert_eq!(apply_identifier_casing("camel case user name"), "userName");
+        assert_eq!(
+            apply_identifier_casing("Call snake case http client."),
+            "Call http_client."
+        );
+        assert_eq!(
+            apply_identifier_casing("set constant case max retries, then pascal case user service"),
+            "set MAX_RETRIES, then UserService"
+        );
+        assert_eq!(
+            apply_identifier_casing("Kebab case main nav bar\nscreaming snake case api key"),
+            "main-nav-bar\nAPI_KEY"
+        );
+        assert_eq!(apply_identifier_casing("camelcase get item"), "getItem");
+    }
+
+    #[test]
+    fn text_without_casing_commands_is_unchanged() {
+        let text = "In this case, the snake  ate a camel.";
+        assert_eq!(apply_identifier_casing(text), text);
+        // A command with nothing after it is left as spoken
+        assert_eq!(
+            apply_identifier_casing("use camel case."),
+            "use camel case."
+        );
+    }
+}
@@ -0,0 +1,851 @@
+//! Inverse text normalization: spoken numbers, dates, times, currencies and
+//! percentages in their written form, so "twenty five dollars on march
+//! third" becomes "$25 on March 3rd".
+//!
+//! The grammar is English; the locale picks how dates, times and pounds are
+//! written. Numbers below ten stay words unless a unit follows, as most
+//! style guides have it. Runs of numbers that cannot be read as one, like
+//! digit-by-digit dictation, are left alone, and so is every line without
+//! anything to convert.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// How converted text is written
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ItnLocale {
+    /// March 3rd, 2024 and 3:30 PM
+    #[default]
+    EnUs,
+    /// 3 March 2024, 3:30 pm and pounds as £
+    EnGb,
+}
+
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const ORDINAL_UNITS: [&str; 20] = [
+    "zeroth",
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+
+/// Indexed by the tens digit
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ORDINAL_TENS: [&str; 10] = [
+    "",
+    "",
+    "twentieth",
+    "thirtieth",
+    "fortieth",
+    "fiftieth",
+    "sixtieth",
+    "seventieth",
+    "eightieth",
+    "ninetieth",
+];
+
+const SCALES: [(&str, &str, u64); 4] = [
+    ("thousand", "thousandth", 1_000),
+    ("million", "millionth", 1_000_000),
+    ("billion", "billionth", 1_000_000_000),
+    ("trillion", "trillionth", 1_000_000_000_000),
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Whether text transcribed in `language` can be normalized. The grammar is
+/// English, so only English and auto-detected transcriptions are.
+pub fn is_itn_language(language: &str) -> bool {
+    language == "auto" || language == "en" || language.starts_with("en-")
+}
+
+/// Converts spoken numbers, dates, times, currencies and percentages in
+/// `text` to their written form
+pub fn apply_inverse_text_normalization(text: &str, locale: ItnLocale) -> String {
+    text.split('\n')
+        .map(|line| normalize_line(line, locale).unwrap_or_else(|| line.to_string()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A word with the punctuation around it
+struct Token<'a> {
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+    /// `core` lowercased
+    word: String,
+}
+
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for raw in line.split_whitespace() {
+        let Some(start) = raw.find(char::is_alphanumeric) else {
+            tokens.push(Token {
+                lead: raw,
+                core: "",
+                trail: "",
+                word: String::new(),
+            });
+            continue;
+        };
+        let end = raw
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(raw.len());
+        let (lead, core, trail) = (&raw[..start], &raw[start..end], &raw[end..]);
+
+        // "twenty-five" is two number words
+        let parts: Vec<&str> = core.split('-').collect();
+        if parts.len() > 1
+            && parts
+                .iter()
+                .all(|part| number_word(&part.to_lowercase()).is_some())
+        {
+            let last = parts.len() - 1;
+            for (i, part) in parts.into_iter().enumerate() {
+                tokens.push(Token {
+                    lead: if i == 0 { lead } else { "" },
+                    core: part,
+                    trail: if i == last { trail } else { "" },
+                    word: part.to_lowercase(),
+                });
+            }
+            continue;
+        }
+
+        tokens.push(Token {
+            lead,
+            core,
+            trail,
+            word: core.to_lowercase(),
+        });
+    }
+    tokens
+}
+
+/// Whether the token at `i` and the one after it can be read together
+fn joinable(tokens: &[Token], i: usize) -> bool {
+    i + 1 < tokens.len() && tokens[i].trail.is_empty() && tokens[i + 1].lead.is_empty()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum NumberWord {
+    /// Zero through nineteen
+    Unit(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+}
+
+/// The number word `word` is, and whether it is an ordinal
+fn number_word(word: &str) -> Option<(NumberWord, bool)> {
+    let position = |words: &[&str]| words.iter().position(|w| *w == word).map(|i| i as u64);
+    if let Some(n) = position(&UNITS) {
+        return Some((NumberWord::Unit(n), false));
+    }
+    if let Some(n) = position(&ORDINAL_UNITS) {
+        return Some((NumberWord::Unit(n), true));
+    }
+    if let Some(n) = position(&TENS).filter(|n| *n >= 2) {
+        return Some((NumberWord::Tens(n * 10), false));
+    }
+    if let Some(n) = position(&ORDINAL_TENS).filter(|n| *n >= 2) {
+        return Some((NumberWord::Tens(n * 10), true));
+    }
+    match word {
+        "hundred" => return Some((NumberWord::Hundred, false)),
+        "hundredth" => return Some((NumberWord::Hundred, true)),
+        _ => {}
+    }
+    SCALES.iter().find_map(|(cardinal, ordinal, scale)| {
+        if word == *cardinal {
+            Some((NumberWord::Scale(*scale), false))
+        } else if word == *ordinal {
+            Some((NumberWord::Scale(*scale), true))
+        } else {
+            None
+        }
+    })
+}
+
+/// Digits dictated after "point", or "oh" for zero in times and years
+fn digit_word(word: &str) -> Option<u64> {
+    match word {
+        "oh" => Some(0),
+        _ => UNITS[..10]
+            .iter()
+            .position(|w| *w == word)
+            .map(|i| i as u64),
+    }
+}
+
+fn starts_number(token: &Token) -> bool {
+    number_word(&token.word).is_some() || parse_digits(token.core).is_some()
+}
+
+/// A number written in digits, with optional thousands separators and decimals
+fn parse_digits(core: &str) -> Option<(u64, Option<String>)> {
+    if !core.starts_with(|c: char| c.is_ascii_digit())
+        || !core
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == '.')
+    {
+        return None;
+    }
+    let (integer, fraction) = match core.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction.to_string())),
+        None => (core, None),
+    };
+    let value = integer.replace(',', "").parse().ok()?;
+    match fraction {
+        Some(fraction) if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) => {
+            None
+        }
+        fraction => Some((value, fraction)),
+    }
+}
+
+struct SpokenNumber {
+    value: u64,
+    /// Digits after the decimal point
+    fraction: Option<String>,
+    /// Kept as a word after a fraction, as in "2.5 million"
+    scale: Option<&'static str>,
+    ordinal: bool,
+    /// Already written in digits
+    literal: bool,
+    /// Index of the first token after the number
+    end: usize,
+}
+
+impl SpokenNumber {
+    fn is_plain(&self) -> bool {
+        self.fraction.is_none() && self.scale.is_none() && !self.ordinal
+    }
+
+    /// Below ten, which style guides spell out
+    fn is_small(&self) -> bool {
+        self.value < 10 && self.fraction.is_none() && self.scale.is_none()
+    }
+
+    fn written(&self) -> String {
+        let mut written = grouped(self.value);
+        if let Some(fraction) = &self.fraction {
+            written.push('.');
+            written.push_str(fraction);
+        }
+        if let Some(scale) = self.scale {
+            written.push(' ');
+            written.push_str(scale);
+        }
+        if self.ordinal {
+            written.push_str(ordinal_suffix(self.value));
+        }
+        written
+    }
+}
+
+/// Digits, grouped in thousands from five digits on so years and other
+/// four digit numbers read naturally
+fn grouped(value: u64) -> String {
+    let digits = value.to_string();
+    if value < 10_000 {
+        return digits;
+    }
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+/// The longest number starting at `start`
+fn parse_number(tokens: &[Token], start: usize) -> Option<SpokenNumber> {
+    let first = tokens.get(start)?;
+    if let Some((value, fraction)) = parse_digits(first.core) {
+        return Some(SpokenNumber {
+            value,
+            fraction,
+            scale: None,
+            ordinal: false,
+            literal: true,
+            end: start + 1,
+        });
+    }
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Last {
+        Unit,
+        Tens,
+        Hundred,
+        Scale(u64),
+    }
+
+    let mut total = 0u64;
+    let mut current = 0u64;
+    let mut last: Option<Last> = None;
+    let mut ordinal = false;
+    let mut i = start;
+
+    // "a hundred", "a thousand"
+    if first.word == "a"
+        && joinable(tokens, start)
+        && matches!(
+            number_word(&tokens[start + 1].word),
+            Some((NumberWord::Hundred | NumberWord::Scale(_), _))
+        )
+    {
+        current = 1;
+        last = Some(Last::Unit);
+        i += 1;
+    }
+
+    while i < tokens.len() && (i == start || joinable(tokens, i - 1)) {
+        let Some((word, is_ordinal)) = number_word(&tokens[i].word) else {
+            // "one hundred and five"
+            let continues = tokens[i].word == "and"
+                && matches!(last, Some(Last::Hundred | Last::Scale(_)))
+                && joinable(tokens, i)
+                && matches!(
+                    number_word(&tokens[i + 1].word),
+                    Some((NumberWord::Unit(1..) | NumberWord::Tens(_), _))
+                );
+            if continues {
+                i += 1;
+                continue;
+            }
+            break;
+        };
+        let smallest_scale = match last {
+            Some(Last::Scale(scale)) => scale,
+            _ => u64::MAX,
+        };
+        match (word, last) {
+            (NumberWord::Unit(n), None | Some(Last::Hundred | Last::Scale(_))) => current += n,
+            (NumberWord::Unit(n @ 1..=9), Some(Last::Tens)) => current += n,
+            (NumberWord::Tens(n), None | Some(Last::Hundred | Last::Scale(_))) => current += n,
+            (NumberWord::Hundred, Some(Last::Unit | Last::Tens)) if current < 100 => current *= 100,
+            (NumberWord::Scale(scale), Some(Last::Unit | Last::Tens | Last::Hundred))
+                if current > 0 && scale < smallest_scale =>
+            {
+                total += current * scale;
+                current = 0;
+            }
+            _ => break,
+        }
+        last = Some(match word {
+            NumberWord::Unit(_) => Last::Unit,
+            NumberWord::Tens(_) => Last::Tens,
+            NumberWord::Hundred => Last::Hundred,
+            NumberWord::Scale(scale) => Last::Scale(scale),
+        });
+        i += 1;
+        if is_ordinal {
+            ordinal = true;
+            break;
+        }
+    }
+    if i == start || last.is_none() || (i == start + 1 && first.word == "a") {
+        return None;
+    }
+
+    let mut number = SpokenNumber {
+        value: total + current,
+        fraction: None,
+        scale: None,
+        ordinal,
+        literal: false,
+        end: i,
+    };
+
+    // "three point one four", "two point five million"
+    if !ordinal && joinable(tokens, i - 1) && tokens[i].word == "point" {
+        let mut fraction = String::new();
+        let mut j = i + 1;
+        while j < tokens.len() && joinable(tokens, j - 1) {
+            let Some(digit) = digit_word(&tokens[j].word) else {
+                break;
+            };
+            fraction.push_str(&digit.to_string());
+            j += 1;
+        }
+        if !fraction.is_empty() {
+            number.fraction = Some(fraction);
+            number.end = j;
+            if joinable(tokens, j - 1) {
+                if let Some((name, _, _)) = SCALES
+                    .iter()
+                    .find(|(cardinal, _, _)| tokens[j].word == *cardinal)
+                {
+                    number.scale = Some(name);
+                    number.end = j + 1;
+                }
+            }
+        }
+    }
+    Some(number)
+}
+
+/// A year read as a whole number ("two thousand five") or in pairs
+/// ("nineteen eighty four", "twenty oh five", "nineteen hundred")
+fn parse_year(tokens: &[Token], start: usize) -> Option<(u64, usize)> {
+    let first = parse_number(tokens, start)?;
+    if !first.is_plain() || first.literal {
+        return None;
+    }
+    if (1000..=2999).contains(&first.value) {
+        return Some((first.value, first.end));
+    }
+    if !(11..=20).contains(&first.value) || !joinable(tokens, first.end - 1) {
+        return None;
+    }
+    let next = first.end;
+    if tokens[next].word == "oh" && joinable(tokens, next) {
+        let digit = digit_word(&tokens[next + 1].word).filter(|d| *d > 0)?;
+        return Some((first.value * 100 + digit, next + 2));
+    }
+    let second = parse_number(tokens, next)?;
+    if second.is_plain() && !second.literal && (10..=99).contains(&second.value) {
+        return Some((first.value * 100 + second.value, second.end));
+    }
+    None
+}
+
+/// A converted stretch of tokens, without the punctuation around it
+struct Span {
+    text: String,
+    end: usize,
+}
+
+fn normalize_line(line: &str, locale: ItnLocale) -> Option<String> {
+    let tokens = tokenize(line);
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let span = parse_date(&tokens, i, locale)
+            .or_else(|| parse_time(&tokens, i, locale))
+            .or_else(|| parse_quantity(&tokens, i, locale));
+        let Some(Span { text, end }) = span else {
+            let token = &tokens[i];
+            out.push(format!("{}{}{}", token.lead, token.core, token.trail));
+            i += 1;
+            continue;
+        };
+
+        let last = &tokens[end - 1];
+        let mut trail = last.trail;
+        // The period of "p.m." only stays if it also ends the sentence
+        if is_meridiem(last).is_some() && last.core.contains('.') && trail.starts_with('.') {
+            let next_is_lowercase = tokens.get(end).is_some_and(|next| {
+                next.lead.is_empty() && next.core.starts_with(char::is_lowercase)
+            });
+            if next_is_lowercase {
+                trail = &trail[1..];
+            }
+        }
+        changed = true;
+        out.push(format!("{}{}{}", tokens[i].lead, text, trail));
+        i = end;
+    }
+    changed.then(|| out.join(" "))
+}
+
+/// Numbers, with the currency, percent sign or unit that follows them
+fn parse_quantity(tokens: &[Token], start: usize, locale: ItnLocale) -> Option<Span> {
+    let number = parse_number(tokens, start)?;
+    let next = number.end;
+    let unit = joinable(tokens, next - 1).then(|| tokens[next].word.as_str());
+
+    if let Some(symbol) = unit.and_then(|unit| currency_symbol(unit, locale)) {
+        if number.ordinal {
+            return None;
+        }
+        let mut span = Span {
+            text: format!("{}{}", symbol, number.written()),
+            end: next + 1,
+        };
+        // "five dollars and twenty cents"
+        if number.is_plain() && joinable(tokens, next) && tokens[next + 1].word == "and" {
+            let cents = joinable(tokens, next + 1)
+                .then(|| parse_number(tokens, next + 2))
+                .flatten()
+                .filter(|cents| cents.is_plain() && cents.value < 100);
+            if let Some(cents) = cents {
+                if joinable(tokens, cents.end - 1)
+                    && subunit_suffix(&tokens[cents.end].word, locale).is_some()
+                {
+                    span = Span {
+                        text: format!("{}{}.{:02}", symbol, grouped(number.value), cents.value),
+                        end: cents.end + 1,
+                    };
+                }
+            }
+        }
+        return Some(span);
+    }
+
+    if !number.ordinal {
+        match unit {
+            Some("percent") => {
+                return Some(Span {
+                    text: format!("{}%", number.written()),
+                    end: next + 1,
+                })
+            }
+            Some("per") if joinable(tokens, next) && tokens[next + 1].word == "cent" => {
+                return Some(Span {
+                    text: format!("{}%", number.written()),
+                    end: next + 2,
+                })
+            }
+            Some(unit) => {
+                if let Some(suffix) = subunit_suffix(unit, locale) {
+                    return Some(Span {
+                        text: format!("{}{}", number.written(), suffix),
+                        end: next + 1,
+                    });
+                }
+            }
+            None => {}
+        }
+    }
+
+    if number.literal {
+        return None;
+    }
+
+    // Another number right after this one: a year, or a run of numbers that
+    // is left as dictated
+    if unit.is_some() && (starts_number(&tokens[next]) || tokens[next].word == "oh") {
+        if let Some((year, end)) = parse_year(tokens, start).filter(|(_, end)| *end > next) {
+            return Some(Span {
+                text: year.to_string(),
+                end,
+            });
+        }
+        let mut end = next;
+        while let Some(number) = parse_number(tokens, end) {
+            end = number.end;
+            if !joinable(tokens, end - 1) || !starts_number(&tokens[end]) {
+                break;
+            }
+        }
+        let dictated = tokens[start..end]
+            .iter()
+            .map(|token| token.core)
+            .collect::<Vec<_>>()
+            .join(" ");
+        return Some(Span {
+            text: dictated,
+            end,
+        });
+    }
+
+    if number.is_small() {
+        return None;
+    }
+    Some(Span {
+        text: number.written(),
+        end: next,
+    })
+}
+
+fn currency_symbol(word: &str, locale: ItnLocale) -> Option<&'static str> {
+    match word {
+        "dollar" | "dollars" | "bucks" => Some("$"),
+        "euro" | "euros" => Some("€"),
+        // Pounds are weight in the US
+        "pound" | "pounds" if locale == ItnLocale::EnGb => Some("£"),
+        "rupee" | "rupees" => Some("₹"),
+        "yen" => Some("¥"),
+        _ => None,
+    }
+}
+
+/// How an amount of cents or pence is written after its number
+fn subunit_suffix(word: &str, locale: ItnLocale) -> Option<&'static str> {
+    match word {
+        "cent" | "cents" => Some(" cents"),
+        "penny" | "pence" | "p" if locale == ItnLocale::EnGb => Some("p"),
+        _ => None,
+    }
+}
+
+/// "am" or "pm", in any of the ways Whisper writes them
+fn is_meridiem(token: &Token) -> Option<&'static str> {
+    match token.word.replace('.', "").as_str() {
+        "am" => Some("am"),
+        "pm" => Some("pm"),
+        _ => None,
+    }
+}
+
+/// "three thirty pm", "seven oh five a.m.", "ten o'clock"
+fn parse_time(tokens: &[Token], start: usize, locale: ItnLocale) -> Option<Span> {
+    let hour = parse_number(tokens, start)?;
+    if !hour.is_plain() || !(1..=12).contains(&hour.value) || hour.end != start + 1 {
+        return None;
+    }
+
+    let mut next = hour.end;
+    let mut minutes = None;
+    if joinable(tokens, next - 1) && tokens[next].word == "oh" {
+        if joinable(tokens, next) {
+            minutes = digit_word(&tokens[next + 1].word).filter(|m| *m > 0);
+            next += 2;
+        }
+        minutes?;
+    } else if joinable(tokens, next - 1) {
+        if let Some(number) = parse_number(tokens, next)
+            .filter(|m| m.is_plain() && !m.literal && (10..=59).contains(&m.value))
+        {
+            minutes = Some(number.value);
+            next = number.end;
+        }
+    }
+    if !joinable(tokens, next - 1) {
+        return None;
+    }
+
+    let marker = &tokens[next];
+    let text = if let Some(meridiem) = is_meridiem(marker) {
+        let meridiem = match locale {
+            ItnLocale::EnUs => meridiem.to_uppercase(),
+            ItnLocale::EnGb => meridiem.to_string(),
+        };
+        match minutes {
+            Some(minutes) => format!("{}:{:02} {}", hour.value, minutes, meridiem),
+            None => format!("{} {}", hour.value, meridiem),
+        }
+    } else if marker.word == "o'clock" && minutes.is_none() {
+        format!("{} o'clock", hour.value)
+    } else {
+        return None;
+    };
+    Some(Span {
+        text,
+        end: next + 1,
+    })
+}
+
+fn month(token: &Token) -> Option<&'static str> {
+    let month = MONTHS
+        .iter()
+        .find(|month| month.eq_ignore_ascii_case(&token.word))
+        .copied()?;
+    // "may" is far more often the verb
+    if month == "May" && !token.core.starts_with('M') {
+        return None;
+    }
+    Some(month)
+}
+
+/// A day of the month, which must be dictated as an ordinal
+fn parse_day(tokens: &[Token], start: usize) -> Option<SpokenNumber> {
+    parse_number(tokens, start)
+        .filter(|day| day.ordinal && day.fraction.is_none() && (1..=31).contains(&day.value))
+}
+
+/// "march third", "march third twenty twenty four", "the third of march",
+/// "march twenty twenty four"
+fn parse_date(tokens: &[Token], start: usize, locale: ItnLocale) -> Option<Span> {
+    let (month, day, after) = if tokens[start].word == "the" {
+        let day = joinable(tokens, start)
+            .then(|| parse_day(tokens, start + 1))
+            .flatten()?;
+        if !joinable(tokens, day.end - 1) || tokens[day.end].word != "of" {
+            return None;
+        }
+        if !joinable(tokens, day.end) {
+            return None;
+        }
+        let month = month(&tokens[day.end + 1])?;
+        (month, day.value, day.end + 2)
+    } else {
+        let month = month(&tokens[start])?;
+        if !joinable(tokens, start) {
+            return None;
+        }
+        if let Some((year, end)) = parse_year(tokens, start + 1) {
+            return Some(Span {
+                text: format!("{} {}", month, year),
+                end,
+            });
+        }
+        let day = parse_day(tokens, start + 1)?;
+        (month, day.value, day.end)
+    };
+
+    // A year may follow, after a comma or not
+    let year = (after < tokens.len()
+        && matches!(tokens[after - 1].trail, "" | ",")
+        && tokens[after].lead.is_empty())
+    .then(|| parse_year(tokens, after))
+    .flatten();
+    let end = year.map_or(after, |(_, end)| end);
+
+    let text = match (locale, year) {
+        (ItnLocale::EnUs, Some((year, _))) => {
+            format!("{} {}{}, {}", month, day, ordinal_suffix(day), year)
+        }
+        (ItnLocale::EnUs, None) => format!("{} {}{}", month, day, ordinal_suffix(day)),
+        (ItnLocale::EnGb, Some((year, _))) => format!("{} {} {}", day, month, year),
+        (ItnLocale::EnGb, None) => format!("{} {}", day, month),
+    };
+    Some(Span { text, end })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn us(text: &str) -> String {
+        apply_inverse_text_normalization(text, ItnLocale::EnUs)
+    }
+
+    fn gb(text: &str) -> String {
+        apply_inverse_text_normalization(text, ItnLocale::EnGb)
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(us("twenty five people came"), "25 people came");
+        assert_eq!(us("one hundred and five"), "105");
+        assert_eq!(us("a thousand twenty-three"), "1023");
+        assert_eq!(
+            us("about two million three hundred thousand"),
+            "about 2,300,000"
+        );
+        assert_eq!(us("pi is three point one four"), "pi is 3.14");
+        assert_eq!(us("two point five million users"), "2.5 million users");
+        assert_eq!(us("the twenty first century"), "the 21st century");
+        // Small numbers stay words, and so does digit-by-digit dictation
+        assert_eq!(us("I have two cats"), "I have two cats");
+        assert_eq!(us("the first time"), "the first time");
+        assert_eq!(us("one second please"), "one second please");
+        assert_eq!(
+            us("call five five five one two"),
+            "call five five five one two"
+        );
+        assert_eq!(us("twenty, thirty"), "20, 30");
+    }
+
+    #[test]
+    fn test_currency_and_percent() {
+        assert_eq!(us("twenty five dollars on march third"), "$25 on March 3rd");
+        assert_eq!(us("five dollars and twenty cents"), "$5.20");
+        assert_eq!(us("fifty cents"), "50 cents");
+        assert_eq!(us("costs 40 euros."), "costs €40.");
+        assert_eq!(us("up five percent"), "up 5%");
+        assert_eq!(us("ten per cent off"), "10% off");
+        // Pounds are money only in the UK
+        assert_eq!(us("three pounds"), "three pounds");
+        assert_eq!(gb("three pounds and fifty pence"), "£3.50");
+    }
+
+    #[test]
+    fn test_dates_and_years() {
+        assert_eq!(
+            us("on march third twenty twenty four"),
+            "on March 3rd, 2024"
+        );
+        assert_eq!(gb("on march third, twenty twenty four"), "on 3 March 2024");
+        assert_eq!(us("the twenty second of May"), "May 22nd");
+        assert_eq!(us("you may first ask"), "you may first ask");
+        assert_eq!(us("in june nineteen eighty four"), "in June 1984");
+        assert_eq!(us("since twenty oh five"), "since 2005");
+        assert_eq!(us("in two thousand five"), "in 2005");
+        // A month name without a day is left alone
+        assert_eq!(us("we march on"), "we march on");
+    }
+
+    #[test]
+    fn test_times() {
+        assert_eq!(us("at three thirty pm"), "at 3:30 PM");
+        assert_eq!(gb("at seven oh five a.m. we left"), "at 7:05 am we left");
+        assert_eq!(us("by ten o'clock"), "by 10 o'clock");
+        assert_eq!(us("leaving at 9 p.m."), "leaving at 9 PM.");
+        assert_eq!(us("I am here"), "I am here");
+    }
+
+    #[test]
+    fn test_lines_without_numbers_are_untouched() {
+        let text = "first  line\n  second line with twelve items";
+        assert_eq!(us(text), "first  line\nsecond line with 12 items");
+        assert!(is_itn_language("en"));
+        assert!(is_itn_language("auto"));
+        assert!(!is_itn_language("de"));
+    }
+}
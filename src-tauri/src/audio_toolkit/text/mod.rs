@@ -1,29 +1,88 @@
+use log::warn;
 use natural::phonetics::soundex;
 use once_cell::sync::Lazy;
-use regex::Regex;
-use strsim::levenshtein;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use strsim::{jaro_winkler, levenshtein};
+
+mod casing;
+mod itn;
+mod phonetic;
+mod spelling;
+
+pub use casing::apply_identifier_casing;
+pub use itn::{apply_inverse_text_normalization, is_itn_language, ItnLocale};
+pub use spelling::apply_spelling;
+
+/// How transcribed words are compared with custom words
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WordMatchAlgorithm {
+    /// Levenshtein distance, boosted for words with the same Soundex code
+    #[default]
+    Soundex,
+    /// Levenshtein distance, boosted for words with a Double Metaphone code
+    /// in common. Fewer false matches on names that are not English.
+    DoubleMetaphone,
+    /// Jaro-Winkler distance alone, which favours words with the same start
+    JaroWinkler,
+}
+
+/// Settings for custom word correction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CustomWordRules {
+    /// Maximum score to accept (0.0 = exact match, 1.0 = any match)
+    pub threshold: f64,
+    pub algorithm: WordMatchAlgorithm,
+    /// Thresholds for single custom words, in place of `threshold`
+    pub word_thresholds: HashMap<String, f64>,
+}
 
 /// Applies custom word corrections to transcribed text using fuzzy matching
 ///
 /// This function corrects words in the input text by finding the best matches
-/// from a list of custom words using a combination of:
-/// - Levenshtein distance for string similarity
-/// - Soundex phonetic matching for pronunciation similarity
+/// from a list of custom words using the algorithm in `rules`:
+/// - Levenshtein distance for string similarity, with Soundex or Double
+///   Metaphone phonetic matching for pronunciation similarity
+/// - or Jaro-Winkler distance
 ///
 /// # Arguments
 /// * `text` - The input text to correct
 /// * `custom_words` - List of custom words to match against
-/// * `threshold` - Maximum similarity score to accept (0.0 = exact match, 1.0 = any match)
+/// * `rules` - The algorithm, and the maximum score to accept overall and per word
 ///
 /// # Returns
 /// The corrected text with custom words applied
-pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -> String {
+pub fn apply_custom_words(text: &str, custom_words: &[String], rules: &CustomWordRules) -> String {
     if custom_words.is_empty() {
         return text.to_string();
     }
 
     // Pre-compute lowercase versions to avoid repeated allocations
     let custom_words_lower: Vec<String> = custom_words.iter().map(|w| w.to_lowercase()).collect();
+    let word_thresholds: HashMap<String, f64> = rules
+        .word_thresholds
+        .iter()
+        .map(|(word, threshold)| (word.to_lowercase(), *threshold))
+        .collect();
+    let thresholds: Vec<f64> = custom_words_lower
+        .iter()
+        .map(|word| {
+            word_thresholds
+                .get(word)
+                .copied()
+                .unwrap_or(rules.threshold)
+        })
+        .collect();
+    let metaphones: Vec<(String, String)> = match rules.algorithm {
+        WordMatchAlgorithm::DoubleMetaphone => custom_words_lower
+            .iter()
+            .map(|word| phonetic::double_metaphone(word))
+            .collect(),
+        _ => Vec::new(),
+    };
 
     let words: Vec<&str> = text.split_whitespace().collect();
     let mut corrected_words = Vec::new();
@@ -46,6 +105,10 @@ pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -
 
         let mut best_match: Option<&String> = None;
         let mut best_score = f64::MAX;
+        let metaphone = match rules.algorithm {
+            WordMatchAlgorithm::DoubleMetaphone => Some(phonetic::double_metaphone(&cleaned_word)),
+            _ => None,
+        };
 
         for (i, custom_word_lower) in custom_words_lower.iter().enumerate() {
             // Skip if lengths are too different (optimization)
@@ -63,18 +126,26 @@ pub fn apply_custom_words(text: &str, custom_words: &[String], threshold: f64) -
                 1.0
             };
 
-            // Calculate phonetic similarity using Soundex
-            let phonetic_match = soundex(&cleaned_word, custom_word_lower);
+            // Calculate phonetic similarity with the selected algorithm
+            let phonetic_match = match (rules.algorithm, &metaphone) {
+                (WordMatchAlgorithm::Soundex, _) => soundex(&cleaned_word, custom_word_lower),
+                (WordMatchAlgorithm::DoubleMetaphone, Some(metaphone)) => {
+                    phonetic::sounds_alike(metaphone, &metaphones[i])
+                }
+                _ => false,
+            };
 
             // Combine scores: favor phonetic matches, but also consider string similarity
-            let combined_score = if phonetic_match {
+            let combined_score = if rules.algorithm == WordMatchAlgorithm::JaroWinkler {
+                1.0 - jaro_winkler(&cleaned_word, custom_word_lower)
+            } else if phonetic_match {
                 levenshtein_score * 0.3 // Give significant boost to phonetic matches
             } else {
                 levenshtein_score
             };
 
             // Accept if the score is good enough (configurable threshold)
-            if combined_score < threshold && combined_score < best_score {
+            if combined_score < thresholds[i] && combined_score < best_score {
                 best_match = Some(&custom_words[i]);
                 best_score = combined_score;
             }
@@ -134,17 +205,18 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
-/// Filler words to remove from transcriptions
-const FILLER_WORDS: &[&str] = &[
+/// Filler words removed from transcriptions until the user edits the list
+pub const DEFAULT_FILLER_WORDS: &[&str] = &[
     "uh", "um", "uhm", "umm", "uhh", "uhhh", "ah", "eh", "hmm", "hm", "mmm", "mm", "mh", "ha",
     "ehh",
 ];
 
 static MULTI_SPACE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s{2,}").unwrap());
 
-/// Collapses repeated 1-2 letter words (3+ repetitions) to a single instance.
+/// Collapses short repeated words to a single instance, by default 1-2 letter
+/// words repeated 3+ times, or words of any length if `rules` says so.
 /// E.g., "wh wh wh wh" -> "wh", "I I I I" -> "I"
-fn collapse_stutters(text: &str) -> String {
+fn collapse_stutters(text: &str, rules: &FilterRules) -> String {
     let words: Vec<&str> = text.split_whitespace().collect();
     if words.is_empty() {
         return text.to_string();
@@ -157,16 +229,18 @@ fn collapse_stutters(text: &str) -> String {
         let word = words[i];
         let word_lower = word.to_lowercase();
 
-        // Only process 1-2 letter words
-        if word_lower.len() <= 2 && word_lower.chars().all(|c| c.is_alphabetic()) {
+        // Only process short words, unless longer repeated words collapse too
+        let short_enough = rules.collapse_repeated_words
+            || word_lower.chars().count() <= rules.stutter_max_word_len;
+        if short_enough && word_lower.chars().all(|c| c.is_alphabetic()) {
             // Count consecutive repetitions (case-insensitive)
             let mut count = 1;
             while i + count < words.len() && words[i + count].to_lowercase() == word_lower {
                 count += 1;
             }
 
-            // If 3+ repetitions, collapse to single instance
-            if count >= 3 {
+            // If repeated often enough, collapse to single instance
+            if count >= rules.stutter_min_repeats {
                 result.push(word);
                 i += count;
             } else {
@@ -182,39 +256,213 @@ fn collapse_stutters(text: &str) -> String {
     result.join(" ")
 }
 
-/// Pre-compiled filler word patterns (built lazily)
-static FILLER_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
-    FILLER_WORDS
+/// Words the profanity filter catches before any the user adds
+const PROFANITY_WORDS: &[&str] = &[
+    "fuck",
+    "fucks",
+    "fucked",
+    "fucker",
+    "fuckers",
+    "fucking",
+    "motherfucker",
+    "motherfuckers",
+    "motherfucking",
+    "shit",
+    "shits",
+    "shitty",
+    "bullshit",
+    "bitch",
+    "bitches",
+    "asshole",
+    "assholes",
+    "bastard",
+    "bastards",
+    "dickhead",
+    "cunt",
+    "cunts",
+    "piss",
+    "pissed",
+    "damn",
+    "goddamn",
+    "crap",
+    "wanker",
+    "twat",
+    "prick",
+    "bollocks",
+    "slut",
+    "whore",
+];
+
+/// What the profanity filter does with the words it catches
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfanityFilter {
+    #[default]
+    Off,
+    /// Replace each letter with `*`
+    Mask,
+    /// Drop the word
+    Remove,
+}
+
+/// How eagerly filler words are removed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FillerFilter {
+    Off,
+    /// Remove the filler word list
+    #[default]
+    Conservative,
+    /// Also remove "like" and "you know" where commas set them off
+    Aggressive,
+}
+
+/// Fillers the aggressive level removes, but only where set off by a comma,
+/// so "I like it" and "do you know" survive
+const AGGRESSIVE_FILLER_WORDS: &[&str] = &["like", "you know"];
+
+/// Configuration for output filtering
+#[derive(Debug, Clone)]
+pub struct FilterRules {
+    /// How eagerly filler words are removed
+    pub fillers: FillerFilter,
+    /// Filler words to remove
+    pub filler_words: Vec<String>,
+    /// Longest word, in letters, collapsed when stuttered
+    pub stutter_max_word_len: usize,
+    /// Repetitions in a row that count as a stutter
+    pub stutter_min_repeats: usize,
+    /// Also collapse repeated words of any length, like "the the the"
+    pub collapse_repeated_words: bool,
+    /// What to do with profanity
+    pub profanity: ProfanityFilter,
+    /// Words filtered as profanity on top of the built-in list
+    pub profanity_words: Vec<String>,
+}
+
+impl Default for FilterRules {
+    fn default() -> Self {
+        Self {
+            fillers: FillerFilter::default(),
+            filler_words: DEFAULT_FILLER_WORDS
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+            stutter_max_word_len: 2,
+            stutter_min_repeats: 3,
+            collapse_repeated_words: false,
+            profanity: ProfanityFilter::default(),
+            profanity_words: Vec::new(),
+        }
+    }
+}
+
+/// Removes filler words at the configured level
+fn filter_fillers(text: &str, rules: &FilterRules) -> String {
+    if rules.fillers == FillerFilter::Off {
+        return text.to_string();
+    }
+
+    let mut filtered = text.to_string();
+
+    let alternatives = rules
+        .filler_words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join("|");
+    if !alternatives.is_empty() {
+        // Match filler words with word boundaries, optionally followed by comma or period
+        let pattern = Regex::new(&format!(r"(?i)\b(?:{})\b[,.]?", alternatives)).unwrap();
+        filtered = pattern.replace_all(&filtered, "").to_string();
+    }
+
+    if rules.fillers == FillerFilter::Aggressive {
+        filtered = AGGRESSIVE_FILLER_PATTERN
+            .replace_all(&filtered, |caps: &regex::Captures| {
+                if caps.get(1).is_some() || caps.get(2).is_some() {
+                    String::new()
+                } else {
+                    caps[0].to_string()
+                }
+            })
+            .to_string();
+    }
+
+    filtered
+}
+
+/// Masks or removes profanity, matching whole words case-insensitively
+fn filter_profanity(text: &str, rules: &FilterRules) -> String {
+    if rules.profanity == ProfanityFilter::Off {
+        return text.to_string();
+    }
+
+    let words: Vec<String> = PROFANITY_WORDS
         .iter()
-        .map(|word| {
-            // Match filler word with word boundaries, optionally followed by comma or period
-            Regex::new(&format!(r"(?i)\b{}\b[,.]?", regex::escape(word))).unwrap()
-        })
-        .collect()
+        .map(|word| word.to_string())
+        .chain(
+            rules
+                .profanity_words
+                .iter()
+                .map(|word| word.trim().to_string())
+                .filter(|word| !word.is_empty()),
+        )
+        .map(|word| regex::escape(&word))
+        .collect();
+    let alternatives = words.join("|");
+
+    if rules.profanity == ProfanityFilter::Mask {
+        let pattern = Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives)).unwrap();
+        pattern
+            .replace_all(text, |caps: &regex::Captures| {
+                "*".repeat(caps[0].chars().count())
+            })
+            .to_string()
+    } else {
+        // Take the space before the word and a comma after it, so
+        // "well, shit, okay" becomes "well, okay" and "that's shit." "that's."
+        let pattern = Regex::new(&format!(r"(?i)\s*\b(?:{})\b,?", alternatives)).unwrap();
+        pattern.replace_all(text, "").to_string()
+    }
+}
+
+/// An aggressive filler with the commas around it, which decide whether it goes
+static AGGRESSIVE_FILLER_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)(,\s*)?\b(?:{})\b(,)?",
+        AGGRESSIVE_FILLER_WORDS.join("|")
+    ))
+    .unwrap()
 });
 
 /// Filters transcription output by removing filler words and stutter artifacts.
 ///
 /// This function cleans up raw transcription text by:
-/// 1. Removing filler words (uh, um, hmm, etc.)
-/// 2. Collapsing repeated 1-2 letter stutters (e.g., "wh wh wh" -> "wh")
-/// 3. Cleaning up excess whitespace
+/// 1. Removing filler words (uh, um, hmm, etc.), as eagerly as configured
+/// 2. Masking or removing profanity, if enabled
+/// 3. Collapsing stutters, by default repeated 1-2 letter words (e.g., "wh wh wh" -> "wh")
+/// 4. Cleaning up excess whitespace
 ///
 /// # Arguments
 /// * `text` - The raw transcription text to filter
+/// * `rules` - The filtering rules to apply
 ///
 /// # Returns
 /// The filtered text with filler words and stutters removed
-pub fn filter_transcription_output(text: &str) -> String {
+pub fn filter_transcription_output(text: &str, rules: &FilterRules) -> String {
     let mut filtered = text.to_string();
 
     // Remove filler words
-    for pattern in FILLER_PATTERNS.iter() {
-        filtered = pattern.replace_all(&filtered, "").to_string();
-    }
+    filtered = filter_fillers(&filtered, rules);
+
+    // Mask or remove profanity
+    filtered = filter_profanity(&filtered, rules);
 
-    // Collapse repeated 1-2 letter words (stutter artifacts like "wh wh wh wh")
-    filtered = collapse_stutters(&filtered);
+    // Collapse repeated short words (stutter artifacts like "wh wh wh wh")
+    filtered = collapse_stutters(&filtered, rules);
 
     // Clean up multiple spaces to single space
     filtered = MULTI_SPACE_PATTERN.replace_all(&filtered, " ").to_string();
@@ -280,6 +528,67 @@ pub fn apply_corrections(text: &str) -> String {
     result.trim().to_string()
 }
 
+// === Regex Rules ===
+
+/// A user-defined find and replace, run in order with the other rules.
+/// `replacement` can refer to capture groups as `$1` or `${name}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct RegexRule {
+    pub id: String,
+    pub pattern: String,
+    pub replacement: String,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// App names the rule applies in; empty for every app
+    #[serde(default)]
+    pub apps: Vec<String>,
+    #[serde(default = "default_regex_rule_enabled")]
+    pub enabled: bool,
+}
+
+fn default_regex_rule_enabled() -> bool {
+    true
+}
+
+impl RegexRule {
+    /// The compiled pattern, or why it does not compile
+    pub fn compile(&self) -> Result<Regex, String> {
+        RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.case_insensitive)
+            .build()
+            .map_err(|e| format!("Invalid pattern '{}': {}", self.pattern, e))
+    }
+
+    /// Whether the rule runs when dictating into `app_name`
+    fn applies_in(&self, app_name: Option<&str>) -> bool {
+        self.apps.is_empty() || app_name.is_some_and(|name| self.apps.iter().any(|a| a == name))
+    }
+}
+
+/// Applies the enabled rules in order, each to the output of the one before
+///
+/// # Arguments
+/// * `text` - The text to rewrite
+/// * `rules` - The rules, in the order they run
+/// * `app_name` - The app being dictated into, for rules scoped to apps
+///
+/// # Returns
+/// The text with every matching rule applied. Rules whose pattern does not
+/// compile are skipped.
+pub fn apply_regex_rules(text: &str, rules: &[RegexRule], app_name: Option<&str>) -> String {
+    let mut result = text.to_string();
+    for rule in rules
+        .iter()
+        .filter(|rule| rule.enabled && rule.applies_in(app_name))
+    {
+        match rule.compile() {
+            Ok(regex) => result = regex.replace_all(&result, &rule.replacement).into_owned(),
+            Err(e) => warn!("Skipping regex rule '{}': {}", rule.id, e),
+        }
+    }
+    result
+}
+
 // === Auto-Formatting ===
 
 /// Verbal command patterns for paragraph/line breaks
@@ -314,6 +623,14 @@ static VERBAL_DELETE_THAT_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)\b(delete|scratch|erase|undo|cancel|remove)\s+that\b[,.]?").unwrap()
 });
 
+/// Verbal command pattern for editing - "correction, replace X with Y"
+/// The "correction" lead-in is required so sentences like "Replace the battery with
+/// a new one" are left alone. Y runs to the end of the sentence or line.
+static VERBAL_REPLACE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bcorrection[,:]?\s+replace\s+([^,.!?\n]+?)\s+with\s+([^,.!?\n]+)[.!?]?")
+        .unwrap()
+});
+
 /// Pattern to detect ordinal list items like "first, ..., second, ..., third, ..."
 static ORDINAL_LIST_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)\b(first|second|third|fourth|fifth|sixth|seventh|eighth|ninth|tenth)[,:]?\s+")
@@ -332,6 +649,8 @@ pub struct FormattingRules {
     pub auto_lists: bool,
     /// Enable verbal commands (new line, new paragraph, bullet point)
     pub verbal_commands: bool,
+    /// Emoji inserted by the "<name> emoji" verbal command, keyed on name
+    pub emoji: HashMap<String, String>,
 }
 
 /// Applies auto-formatting rules to transcribed text
@@ -351,7 +670,7 @@ pub fn apply_formatting(text: &str, rules: &FormattingRules) -> String {
 
     // Process verbal commands first (new line, new paragraph, bullet)
     if rules.verbal_commands {
-        result = process_verbal_commands(&result);
+        result = process_verbal_commands(&result, &rules.emoji);
     }
 
     // Detect and format lists
@@ -366,7 +685,7 @@ pub fn apply_formatting(text: &str, rules: &FormattingRules) -> String {
 }
 
 /// Processes verbal commands in the text
-fn process_verbal_commands(text: &str) -> String {
+fn process_verbal_commands(text: &str, emoji: &HashMap<String, String>) -> String {
     let mut result = text.to_string();
 
     // Process deletion commands FIRST (before other verbal commands)
@@ -380,12 +699,59 @@ fn process_verbal_commands(text: &str) -> String {
     // Replace "new line" with single newline
     result = VERBAL_LINE_PATTERN.replace_all(&result, "\n").to_string();
 
+    // Apply "correction, replace X with Y" edits, once line breaks end the replacement
+    result = process_replace_commands(&result);
+
     // Replace "bullet point X" with "• X"
     result = VERBAL_BULLET_PATTERN.replace_all(&result, "• ").to_string();
 
+    // Replace "thumbs up emoji" with 👍
+    result = process_emoji_commands(&result, emoji);
+
     result
 }
 
+/// Replaces "<name> emoji" with the emoji `emoji` maps the name to.
+/// Names match case-insensitively, and unknown names are left as spoken.
+fn process_emoji_commands(text: &str, emoji: &HashMap<String, String>) -> String {
+    let by_name: HashMap<String, &str> = emoji
+        .iter()
+        .map(|(name, emoji)| (normalize_emoji_name(name), emoji.as_str()))
+        .filter(|(name, _)| !name.is_empty())
+        .collect();
+    if by_name.is_empty() {
+        return text.to_string();
+    }
+
+    // Longest names first, so "thumbs up" wins over "up"
+    let mut names: Vec<&String> = by_name.keys().collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    let alternatives = names
+        .iter()
+        .map(|name| {
+            name.split(' ')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(r"\s+")
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+    let pattern = Regex::new(&format!(r"(?i)\b({})\s+emoji\b", alternatives)).unwrap();
+
+    pattern
+        .replace_all(text, |caps: &regex::Captures| {
+            by_name[&normalize_emoji_name(&caps[1])].to_string()
+        })
+        .to_string()
+}
+
+fn normalize_emoji_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 /// Processes deletion commands in the text
 /// Handles "delete that", "scratch that", etc. to remove the last word
 /// Handles "scratch last sentence", "delete last sentence" to remove the last sentence
@@ -552,6 +918,59 @@ fn process_delete_that(text: &str) -> String {
     result.trim().to_string()
 }
 
+/// Processes "correction, replace X with Y" commands
+/// Rewrites the last occurrence of X before the command as Y, keeping its
+/// capitalization. Only commands spoken as their own sentence count; those
+/// mid-sentence, or whose X does not occur, are left as spoken.
+fn process_replace_commands(text: &str) -> String {
+    let mut result = text.to_string();
+    let mut search_from = 0;
+
+    // Process from left to right, one match at a time
+    while let Some(caps) = VERBAL_REPLACE_PATTERN.captures_at(&result, search_from) {
+        let mat = caps.get(0).unwrap();
+        let target = caps[1].trim();
+        let replacement = caps[2].trim();
+        let before_command = &result[..mat.start()];
+        let after_command = &result[mat.end()..];
+        if !starts_sentence(before_command) {
+            search_from = mat.end();
+            continue;
+        }
+
+        let target_pattern = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(target))).unwrap();
+        let Some(found) = target_pattern.find_iter(before_command).last() else {
+            search_from = mat.end();
+            continue;
+        };
+
+        let new_before = format!(
+            "{}{}{}",
+            &before_command[..found.start()],
+            preserve_case_pattern(found.as_str(), replacement),
+            before_command[found.end()..].trim_end()
+        );
+        search_from = new_before.len();
+        result = if after_command.trim().is_empty() {
+            new_before
+        } else if new_before.is_empty() {
+            after_command.trim_start().to_string()
+        } else {
+            format!("{} {}", new_before, after_command.trim_start())
+        };
+    }
+
+    result.trim().to_string()
+}
+
+/// Whether text following `before` starts a sentence or line
+fn starts_sentence(before: &str) -> bool {
+    matches!(
+        before.trim_end_matches([' ', '\t']).chars().last(),
+        None | Some('.' | '!' | '?' | '\n')
+    )
+}
+
 /// Maps ordinal words to numbers
 fn ordinal_to_number(word: &str) -> Option<u32> {
     match word.to_lowercase().as_str() {
@@ -723,11 +1142,18 @@ fn clean_formatting(text: &str) -> String {
 mod tests {
     use super::*;
 
+    fn rules(threshold: f64) -> CustomWordRules {
+        CustomWordRules {
+            threshold,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_apply_custom_words_exact_match() {
         let text = "hello world";
         let custom_words = vec!["Hello".to_string(), "World".to_string()];
-        let result = apply_custom_words(text, &custom_words, 0.5);
+        let result = apply_custom_words(text, &custom_words, &rules(0.5));
         assert_eq!(result, "Hello World");
     }
 
@@ -735,10 +1161,44 @@ mod tests {
     fn test_apply_custom_words_fuzzy_match() {
         let text = "helo wrold";
         let custom_words = vec!["hello".to_string(), "world".to_string()];
-        let result = apply_custom_words(text, &custom_words, 0.5);
+        let result = apply_custom_words(text, &custom_words, &rules(0.5));
         assert_eq!(result, "hello world");
     }
 
+    #[test]
+    fn test_apply_custom_words_algorithms_and_word_thresholds() {
+        let custom_words = vec!["Jiang".to_string(), "Siobhan".to_string()];
+
+        // Soundex codes "jinx" like "Jiang" and corrects it, Double Metaphone does not
+        let text = "what a jinx";
+        assert_eq!(
+            apply_custom_words(text, &custom_words, &rules(0.18)),
+            "what a Jiang"
+        );
+        let metaphone = CustomWordRules {
+            algorithm: WordMatchAlgorithm::DoubleMetaphone,
+            ..rules(0.18)
+        };
+        assert_eq!(apply_custom_words(text, &custom_words, &metaphone), text);
+
+        let jaro = CustomWordRules {
+            algorithm: WordMatchAlgorithm::JaroWinkler,
+            ..rules(0.1)
+        };
+        assert_eq!(
+            apply_custom_words("ask siobhann", &custom_words, &jaro),
+            "ask Siobhan"
+        );
+
+        // A per-word threshold, matched case-insensitively, overrides the global one
+        let mut strict = jaro.clone();
+        strict.word_thresholds.insert("siobhan".to_string(), 0.01);
+        assert_eq!(
+            apply_custom_words("ask siobhann", &custom_words, &strict),
+            "ask siobhann"
+        );
+    }
+
     #[test]
     fn test_preserve_case_pattern() {
         assert_eq!(preserve_case_pattern("HELLO", "world"), "WORLD");
@@ -757,87 +1217,156 @@ mod tests {
     fn test_empty_custom_words() {
         let text = "hello world";
         let custom_words = vec![];
-        let result = apply_custom_words(text, &custom_words, 0.5);
+        let result = apply_custom_words(text, &custom_words, &rules(0.5));
         assert_eq!(result, "hello world");
     }
 
     #[test]
     fn test_filter_filler_words() {
         let text = "So um I was thinking uh about this";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "So I was thinking about this");
     }
 
     #[test]
     fn test_filter_filler_words_case_insensitive() {
         let text = "UM this is UH a test";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "this is a test");
     }
 
     #[test]
     fn test_filter_filler_words_with_punctuation() {
         let text = "Well, um, I think, uh. that's right";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "Well, I think, that's right");
     }
 
     #[test]
     fn test_filter_cleans_whitespace() {
         let text = "Hello    world   test";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "Hello world test");
     }
 
     #[test]
     fn test_filter_trims() {
         let text = "  Hello world  ";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "Hello world");
     }
 
     #[test]
     fn test_filter_combined() {
         let text = "  Um, so I was, uh, thinking about this  ";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "so I was, thinking about this");
     }
 
     #[test]
     fn test_filter_preserves_valid_text() {
         let text = "This is a completely normal sentence.";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "This is a completely normal sentence.");
     }
 
     #[test]
     fn test_filter_stutter_collapse() {
         let text = "w wh wh wh wh wh wh wh wh wh why";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "w wh why");
     }
 
     #[test]
     fn test_filter_stutter_short_words() {
         let text = "I I I I think so so so so";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "I think so");
     }
 
     #[test]
     fn test_filter_stutter_mixed_case() {
         let text = "No NO no NO no";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "No");
     }
 
     #[test]
     fn test_filter_stutter_preserves_two_repetitions() {
         let text = "no no is fine";
-        let result = filter_transcription_output(text);
+        let result = filter_transcription_output(text, &FilterRules::default());
         assert_eq!(result, "no no is fine");
     }
 
+    #[test]
+    fn test_filter_stutter_thresholds() {
+        let mut rules = FilterRules {
+            stutter_max_word_len: 3,
+            stutter_min_repeats: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_transcription_output("the the cat sat sat there there", &rules),
+            "the cat sat there there"
+        );
+
+        rules.collapse_repeated_words = true;
+        rules.stutter_min_repeats = 3;
+        assert_eq!(
+            filter_transcription_output("that that is it it it, there there there", &rules),
+            "that that is it it it, there"
+        );
+    }
+
+    #[test]
+    fn test_filter_profanity_mask_and_remove() {
+        let mut rules = FilterRules {
+            profanity: ProfanityFilter::Mask,
+            profanity_words: vec!["frak".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_transcription_output("Well, Shit, that frakking frak broke", &rules),
+            "Well, ****, that frakking **** broke"
+        );
+
+        rules.profanity = ProfanityFilter::Remove;
+        assert_eq!(
+            filter_transcription_output("Well, shit, that frak is damn good.", &rules),
+            "Well, that is good."
+        );
+
+        rules.profanity = ProfanityFilter::Off;
+        assert_eq!(filter_transcription_output("oh shit", &rules), "oh shit");
+    }
+
+    #[test]
+    fn test_filter_filler_levels_and_custom_words() {
+        let mut rules = FilterRules {
+            filler_words: vec!["um".to_string(), "sort of".to_string()],
+            ..Default::default()
+        };
+        let text = "So um it was, like, sort of uh fine, you know.";
+        assert_eq!(
+            filter_transcription_output(text, &rules),
+            "So it was, like, uh fine, you know."
+        );
+
+        rules.fillers = FillerFilter::Aggressive;
+        assert_eq!(
+            filter_transcription_output(text, &rules),
+            "So it was uh fine."
+        );
+        // Without commas "like" and "you know" are not fillers
+        assert_eq!(
+            filter_transcription_output("I like it, do you know why", &rules),
+            "I like it, do you know why"
+        );
+
+        rules.fillers = FillerFilter::Off;
+        assert_eq!(filter_transcription_output(text, &rules), text);
+    }
+
     // === Auto-Formatting Tests ===
 
     #[test]
@@ -845,6 +1374,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: true,
             auto_lists: false,
+            ..Default::default()
         };
         let text = "First paragraph new paragraph second paragraph";
         let result = apply_formatting(text, &rules);
@@ -858,6 +1388,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: true,
             auto_lists: false,
+            ..Default::default()
         };
         let text = "Line one new line line two";
         let result = apply_formatting(text, &rules);
@@ -871,6 +1402,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: true,
             auto_lists: false,
+            ..Default::default()
         };
         let text = "Items: bullet point item one, bullet point item two";
         let result = apply_formatting(text, &rules);
@@ -878,11 +1410,27 @@ mod tests {
         assert!(result.contains("• item two"));
     }
 
+    #[test]
+    fn test_verbal_emoji() {
+        let rules = FormattingRules {
+            verbal_commands: true,
+            emoji: HashMap::from([
+                ("thumbs up".to_string(), "👍".to_string()),
+                ("Fire".to_string(), "🔥".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let text = "Shipped it fire emoji. Thumbs  Up emoji and a unicorn emoji";
+        let result = apply_formatting(text, &rules);
+        assert_eq!(result, "Shipped it 🔥. 👍 and a unicorn emoji");
+    }
+
     #[test]
     fn test_ordinal_list_detection() {
         let rules = FormattingRules {
             verbal_commands: false,
             auto_lists: true,
+            ..Default::default()
         };
         let text = "First get the data, second process it, third display results";
         let result = apply_formatting(text, &rules);
@@ -896,6 +1444,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: false,
             auto_lists: true,
+            ..Default::default()
         };
         let text = "One open the app, two click the button, three submit the form";
         let result = apply_formatting(text, &rules);
@@ -909,6 +1458,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: false,
             auto_lists: false,
+            ..Default::default()
         };
         let text = "First thing new paragraph second thing";
         let result = apply_formatting(text, &rules);
@@ -920,6 +1470,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: true,
             auto_lists: true,
+            ..Default::default()
         };
         let text = "";
         let result = apply_formatting(text, &rules);
@@ -931,6 +1482,7 @@ mod tests {
         let rules = FormattingRules {
             verbal_commands: true,
             auto_lists: true,
+            ..Default::default()
         };
         let text = "Introduction new paragraph first step one, second step two";
         let result = apply_formatting(text, &rules);
@@ -971,6 +1523,72 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    // === Regex Rule Tests ===
+
+    fn regex_rule(pattern: &str, replacement: &str) -> RegexRule {
+        RegexRule {
+            id: pattern.to_string(),
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            case_insensitive: false,
+            apps: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_regex_rules_use_capture_groups_in_order() {
+        let rules = vec![
+            regex_rule(r"(\d+) percent", "$1%"),
+            regex_rule(
+                r"(?P<user>\w+) at (?P<host>\w+) dot com",
+                "${user}@${host}.com",
+            ),
+            regex_rule(r"%", " percent"),
+        ];
+        let result = apply_regex_rules("jane at example dot com got 50 percent", &rules, None);
+        assert_eq!(result, "jane@example.com got 50 percent");
+    }
+
+    #[test]
+    fn test_regex_rules_case_and_enabled() {
+        let mut rule = regex_rule("okay", "OK");
+        assert_eq!(
+            apply_regex_rules("Okay then", &[rule.clone()], None),
+            "Okay then"
+        );
+        rule.case_insensitive = true;
+        assert_eq!(
+            apply_regex_rules("Okay then", &[rule.clone()], None),
+            "OK then"
+        );
+        rule.enabled = false;
+        assert_eq!(apply_regex_rules("Okay then", &[rule], None), "Okay then");
+    }
+
+    #[test]
+    fn test_regex_rules_scoped_to_apps() {
+        let mut rule = regex_rule("function", "fn");
+        rule.apps = vec!["Code".to_string()];
+        let rules = [rule];
+        assert_eq!(
+            apply_regex_rules("a function", &rules, Some("Code")),
+            "a fn"
+        );
+        assert_eq!(
+            apply_regex_rules("a function", &rules, Some("Mail")),
+            "a function"
+        );
+        assert_eq!(apply_regex_rules("a function", &rules, None), "a function");
+    }
+
+    #[test]
+    fn test_regex_rules_skip_invalid_patterns() {
+        let rules = [regex_rule("(unclosed", "x"), regex_rule("b", "c")];
+        assert!(rules[0].compile().is_err());
+        assert_eq!(apply_regex_rules("abc", &rules, None), "acc");
+    }
+
     // === Deletion Command Tests ===
 
     mod deletion_tests {
@@ -983,6 +1601,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello world delete that";
             let result = apply_formatting(text, &rules);
@@ -994,6 +1613,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Send report scratch that";
             let result = apply_formatting(text, &rules);
@@ -1005,6 +1625,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Quick test erase that";
             let result = apply_formatting(text, &rules);
@@ -1016,6 +1637,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello there undo that";
             let result = apply_formatting(text, &rules);
@@ -1027,6 +1649,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Message sent cancel that";
             let result = apply_formatting(text, &rules);
@@ -1038,6 +1661,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello DELETE THAT";
             let result = apply_formatting(text, &rules);
@@ -1049,6 +1673,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "delete that more text";
             let result = apply_formatting(text, &rules);
@@ -1060,6 +1685,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "delete that hello world";
             let result = apply_formatting(text, &rules);
@@ -1071,6 +1697,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "one two delete that three four delete that";
             let result = apply_formatting(text, &rules);
@@ -1082,6 +1709,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "";
             let result = apply_formatting(text, &rules);
@@ -1093,6 +1721,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "delete that";
             let result = apply_formatting(text, &rules);
@@ -1104,6 +1733,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello world delete that, more text";
             let result = apply_formatting(text, &rules);
@@ -1117,6 +1747,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First. Second. scratch last sentence";
             let result = apply_formatting(text, &rules);
@@ -1128,6 +1759,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello! World! delete last sentence";
             let result = apply_formatting(text, &rules);
@@ -1139,6 +1771,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A? B? erase last sentence";
             let result = apply_formatting(text, &rules);
@@ -1150,6 +1783,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A. B. scratch that last sentence";
             let result = apply_formatting(text, &rules);
@@ -1161,6 +1795,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Just words delete last sentence";
             let result = apply_formatting(text, &rules);
@@ -1172,6 +1807,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First. Second. DELETE LAST SENTENCE";
             let result = apply_formatting(text, &rules);
@@ -1183,6 +1819,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A. B. delete last sentence more text";
             let result = apply_formatting(text, &rules);
@@ -1194,6 +1831,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "scratch last sentence";
             let result = apply_formatting(text, &rules);
@@ -1207,6 +1845,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: false,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello delete that";
             let result = apply_formatting(text, &rules);
@@ -1218,6 +1857,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: false,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A. B. delete last sentence";
             let result = apply_formatting(text, &rules);
@@ -1231,6 +1871,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello world delete that new line more text";
             let result = apply_formatting(text, &rules);
@@ -1246,6 +1887,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "a b c delete that delete that";
             let result = apply_formatting(text, &rules);
@@ -1257,6 +1899,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             // Word with trailing comma - should delete "world," as a unit
             let text = "Hello, world, delete that";
@@ -1269,6 +1912,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             // Abbreviations like "Dr." have periods but aren't sentence endings
             // This is a known limitation - we treat all periods as sentence endings
@@ -1283,6 +1927,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A. B. C. delete last sentence";
             let result = apply_formatting(text, &rules);
@@ -1296,6 +1941,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First line\nSecond line delete last line";
             let result = apply_formatting(text, &rules);
@@ -1307,6 +1953,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Line one\nLine two scratch last line";
             let result = apply_formatting(text, &rules);
@@ -1318,6 +1965,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A\nB erase last line";
             let result = apply_formatting(text, &rules);
@@ -1329,6 +1977,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First\nSecond undo last line";
             let result = apply_formatting(text, &rules);
@@ -1340,6 +1989,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Top\nBottom cancel last line";
             let result = apply_formatting(text, &rules);
@@ -1351,6 +2001,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Line A\nLine B delete that last line";
             let result = apply_formatting(text, &rules);
@@ -1362,6 +2013,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             // No newlines - entire text is one line, should delete everything
             let text = "Just one line delete last line";
@@ -1374,6 +2026,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Line 1\nLine 2\nLine 3 delete last line";
             let result = apply_formatting(text, &rules);
@@ -1385,6 +2038,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A\nB delete last line more text";
             let result = apply_formatting(text, &rules);
@@ -1396,6 +2050,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First\nSecond DELETE LAST LINE";
             let result = apply_formatting(text, &rules);
@@ -1407,6 +2062,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: false,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A\nB delete last line";
             let result = apply_formatting(text, &rules);
@@ -1420,6 +2076,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Hello world remove that";
             let result = apply_formatting(text, &rules);
@@ -1431,6 +2088,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "First. Second. undo last sentence";
             let result = apply_formatting(text, &rules);
@@ -1442,6 +2100,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A! B! cancel last sentence";
             let result = apply_formatting(text, &rules);
@@ -1455,6 +2114,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             // "dash" should NOT be converted to bullet anymore
             let text = "I need to dash to the store";
@@ -1467,6 +2127,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Items: bullet item one, bullet item two";
             let result = apply_formatting(text, &rules);
@@ -1479,6 +2140,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "List: bullet point first, bullet point second";
             let result = apply_formatting(text, &rules);
@@ -1493,6 +2155,7 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "Line A\nLine B delete last line new line Line C";
             let result = apply_formatting(text, &rules);
@@ -1506,10 +2169,72 @@ mod tests {
             let rules = FormattingRules {
                 verbal_commands: true,
                 auto_lists: false,
+                ..Default::default()
             };
             let text = "A\nB\nC delete last line delete last line";
             let result = apply_formatting(text, &rules);
             assert_eq!(result, "A");
         }
     }
+
+    // === Replace Command Tests ===
+
+    mod replace_tests {
+        use super::*;
+
+        fn verbal_rules() -> FormattingRules {
+            FormattingRules {
+                verbal_commands: true,
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn test_replace_rewrites_last_occurrence() {
+            let text = "The meeting moved, the meeting is at noon. Correction, replace meeting with standup";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, "The meeting moved, the standup is at noon.");
+        }
+
+        #[test]
+        fn test_replace_keeps_capitalization_and_text_after() {
+            let text =
+                "Meeting at noon. correction: replace meeting with daily standup. See you there";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, "Daily standup at noon. See you there");
+        }
+
+        #[test]
+        fn test_replace_multi_word_target() {
+            let text = "Ship it on Friday morning.\nCorrection replace Friday morning with Monday";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, "Ship it on Monday.");
+        }
+
+        #[test]
+        fn test_replace_missing_target_is_left_as_spoken() {
+            let text = "Clean the tank. Correction, replace the filter with a new one.";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, text);
+        }
+
+        #[test]
+        fn test_replace_needs_the_command_form() {
+            let text = "The battery died. Replace the battery with a new one.";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, text);
+
+            let text = "Ship it Friday after the correction replace Friday with Monday";
+            let result = apply_formatting(text, &verbal_rules());
+            assert_eq!(result, text);
+        }
+
+        #[test]
+        fn test_replace_disabled() {
+            let rules = FormattingRules::default();
+            let text = "Hello world. Correction, replace world with there";
+            let result = apply_formatting(text, &rules);
+            assert_eq!(result, text);
+        }
+    }
 }
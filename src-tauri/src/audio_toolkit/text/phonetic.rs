@@ -0,0 +1,688 @@
+//! Double Metaphone phonetic encoding (Lawrence Philips, 2000).
+//!
+//! Unlike Soundex, which keeps the first letter and codes the rest by
+//! consonant group, Double Metaphone knows the spelling rules of English and
+//! of the Germanic, Slavic, Romance and other names common in English text.
+//! It gives a primary code and an alternate one for names with two likely
+//! pronunciations, so "Smith" and "Schmidt" share a code while "Cathy" and
+//! "Sammy" do not.
+
+/// Length the codes are cut to
+const MAX_CODE_LEN: usize = 4;
+
+/// The primary and alternate Double Metaphone codes of `word`. Both are
+/// empty for a word without letters.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let chars: Vec<char> = word.trim().chars().flat_map(char::to_uppercase).collect();
+    let mut encoder = Encoder {
+        slavo_germanic: is_slavo_germanic(&chars),
+        chars,
+        primary: String::new(),
+        alternate: String::new(),
+    };
+    encoder.encode();
+    (encoder.primary, encoder.alternate)
+}
+
+/// Whether two words sound alike: any of their primary and alternate codes match
+pub fn sounds_alike(a: &(String, String), b: &(String, String)) -> bool {
+    let codes = |c: &(String, String)| [c.0.clone(), c.1.clone()];
+    let (a, b) = (codes(a), codes(b));
+    a.iter()
+        .any(|code| !code.is_empty() && b.iter().any(|other| other == code))
+}
+
+fn is_slavo_germanic(chars: &[char]) -> bool {
+    let word: String = chars.iter().collect();
+    word.contains('W') || word.contains('K') || word.contains("CZ") || word.contains("WITZ")
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+struct Encoder {
+    chars: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl Encoder {
+    fn len(&self) -> isize {
+        self.chars.len() as isize
+    }
+
+    /// The letter at `index`, or `'\0'` outside the word
+    fn at(&self, index: isize) -> char {
+        if index < 0 {
+            return '\0';
+        }
+        self.chars.get(index as usize).copied().unwrap_or('\0')
+    }
+
+    /// Whether the `len` letters at `start` are one of `options`
+    fn is_at(&self, start: isize, len: isize, options: &[&str]) -> bool {
+        if start < 0 || start + len > self.len() {
+            return false;
+        }
+        let part: String = self.chars[start as usize..(start + len) as usize]
+            .iter()
+            .collect();
+        options.contains(&part.as_str())
+    }
+
+    fn is_done(&self) -> bool {
+        self.primary.len() >= MAX_CODE_LEN && self.alternate.len() >= MAX_CODE_LEN
+    }
+
+    fn add(&mut self, code: &str) {
+        self.add_both(code, code);
+    }
+
+    fn add_both(&mut self, primary: &str, alternate: &str) {
+        self.add_primary(primary);
+        self.add_alternate(alternate);
+    }
+
+    fn add_primary(&mut self, code: &str) {
+        let room = MAX_CODE_LEN.saturating_sub(self.primary.len());
+        self.primary.extend(code.chars().take(room));
+    }
+
+    fn add_alternate(&mut self, code: &str) {
+        let room = MAX_CODE_LEN.saturating_sub(self.alternate.len());
+        self.alternate.extend(code.chars().take(room));
+    }
+
+    /// Past a doubled letter, or just past this one
+    fn skip_double(&self, index: isize, letter: char) -> isize {
+        if self.at(index + 1) == letter {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode(&mut self) {
+        // Skip silent letters at the start
+        let mut index = if self.is_at(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            1
+        } else {
+            0
+        };
+
+        while index < self.len() && !self.is_done() {
+            index = match self.at(index) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    // Only a leading vowel is coded
+                    if index == 0 {
+                        self.add("A");
+                    }
+                    index + 1
+                }
+                'B' => {
+                    self.add("P");
+                    self.skip_double(index, 'B')
+                }
+                'Ç' => {
+                    self.add("S");
+                    index + 1
+                }
+                'C' => self.encode_c(index),
+                'D' => self.encode_d(index),
+                'F' => {
+                    self.add("F");
+                    self.skip_double(index, 'F')
+                }
+                'G' => self.encode_g(index),
+                'H' => self.encode_h(index),
+                'J' => self.encode_j(index),
+                'K' => {
+                    self.add("K");
+                    self.skip_double(index, 'K')
+                }
+                'L' => self.encode_l(index),
+                'M' => {
+                    self.add("M");
+                    let silent_b = self.is_at(index - 1, 3, &["UMB"])
+                        && (index + 1 == self.len() - 1 || self.is_at(index + 2, 2, &["ER"]));
+                    if self.at(index + 1) == 'M' || silent_b {
+                        index + 2
+                    } else {
+                        index + 1
+                    }
+                }
+                'N' => {
+                    self.add("N");
+                    self.skip_double(index, 'N')
+                }
+                'Ñ' => {
+                    self.add("N");
+                    index + 1
+                }
+                'P' => {
+                    if self.at(index + 1) == 'H' {
+                        self.add("F");
+                        index + 2
+                    } else {
+                        self.add("P");
+                        if self.is_at(index + 1, 1, &["P", "B"]) {
+                            index + 2
+                        } else {
+                            index + 1
+                        }
+                    }
+                }
+                'Q' => {
+                    self.add("K");
+                    self.skip_double(index, 'Q')
+                }
+                'R' => self.encode_r(index),
+                'S' => self.encode_s(index),
+                'T' => self.encode_t(index),
+                'V' => {
+                    self.add("F");
+                    self.skip_double(index, 'V')
+                }
+                'W' => self.encode_w(index),
+                'X' => self.encode_x(index),
+                'Z' => self.encode_z(index),
+                _ => index + 1,
+            };
+        }
+    }
+
+    fn encode_c(&mut self, index: isize) -> isize {
+        // Germanic "ACH", as in "Bacher"
+        if self.is_germanic_ach(index) {
+            self.add("K");
+            return index + 2;
+        }
+        if index == 0 && self.is_at(index, 6, &["CAESAR"]) {
+            self.add("S");
+            return index + 2;
+        }
+        if self.is_at(index, 2, &["CH"]) {
+            return self.encode_ch(index);
+        }
+        // "Czerny"
+        if self.is_at(index, 2, &["CZ"]) && !self.is_at(index - 2, 4, &["WICZ"]) {
+            self.add_both("S", "X");
+            return index + 2;
+        }
+        // "focaccia"
+        if self.is_at(index + 1, 3, &["CIA"]) {
+            self.add("X");
+            return index + 3;
+        }
+        // Double C, but not as in "McClellan"
+        if self.is_at(index, 2, &["CC"]) && !(index == 1 && self.at(0) == 'M') {
+            if self.is_at(index + 2, 1, &["I", "E", "H"]) && !self.is_at(index + 2, 2, &["HU"]) {
+                // "accident", "accede", "succeed", but "bacchus"
+                if (index == 1 && self.at(index - 1) == 'A')
+                    || self.is_at(index - 1, 5, &["UCCEE", "UCCES"])
+                {
+                    self.add("KS");
+                } else {
+                    self.add("X");
+                }
+                return index + 3;
+            }
+            // Pierce's rule
+            self.add("K");
+            return index + 2;
+        }
+        if self.is_at(index, 2, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            return index + 2;
+        }
+        if self.is_at(index, 2, &["CI", "CE", "CY"]) {
+            // Italian versus English
+            if self.is_at(index, 3, &["CIO", "CIE", "CIA"]) {
+                self.add_both("S", "X");
+            } else {
+                self.add("S");
+            }
+            return index + 2;
+        }
+
+        self.add("K");
+        // "Mac Caffrey", "Mac Gregor"
+        if self.is_at(index + 1, 2, &[" C", " Q", " G"]) {
+            index + 3
+        } else if self.is_at(index + 1, 1, &["C", "K", "Q"])
+            && !self.is_at(index + 1, 2, &["CE", "CI"])
+        {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn is_germanic_ach(&self, index: isize) -> bool {
+        if self.is_at(index, 4, &["CHIA"]) {
+            return true;
+        }
+        if index <= 1 || is_vowel(self.at(index - 2)) || !self.is_at(index - 1, 3, &["ACH"]) {
+            return false;
+        }
+        let next = self.at(index + 2);
+        (next != 'I' && next != 'E') || self.is_at(index - 2, 6, &["BACHER", "MACHER"])
+    }
+
+    fn encode_ch(&mut self, index: isize) -> isize {
+        // "Michael"
+        if index > 0 && self.is_at(index, 4, &["CHAE"]) {
+            self.add_both("K", "X");
+            return index + 2;
+        }
+        // Greek roots at the start, like "chemistry" and "chorus"
+        let greek_start = index == 0
+            && (self.is_at(index + 1, 5, &["HARAC", "HARIS"])
+                || self.is_at(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.is_at(0, 5, &["CHORE"]);
+        // Germanic names and Greek roots elsewhere, like "orchestra" and "architect"
+        let hard = self.is_at(0, 4, &["VAN ", "VON "])
+            || self.is_at(0, 3, &["SCH"])
+            || self.is_at(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.is_at(index + 2, 1, &["T", "S"])
+            || ((self.is_at(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.is_at(
+                    index + 2,
+                    1,
+                    &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                ) || index + 1 == self.len() - 1));
+        if greek_start || hard {
+            self.add("K");
+        } else if index > 0 {
+            if self.is_at(0, 2, &["MC"]) {
+                self.add("K");
+            } else {
+                self.add_both("X", "K");
+            }
+        } else {
+            self.add("X");
+        }
+        index + 2
+    }
+
+    fn encode_d(&mut self, index: isize) -> isize {
+        if self.is_at(index, 2, &["DG"]) {
+            // "edge"
+            if self.is_at(index + 2, 1, &["I", "E", "Y"]) {
+                self.add("J");
+                return index + 3;
+            }
+            // "Edgar"
+            self.add("TK");
+            return index + 2;
+        }
+        self.add("T");
+        if self.is_at(index, 2, &["DT", "DD"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode_g(&mut self, index: isize) -> isize {
+        let next = self.at(index + 1);
+        if next == 'H' {
+            return self.encode_gh(index);
+        }
+        if next == 'N' {
+            if index == 1 && is_vowel(self.at(0)) && !self.slavo_germanic {
+                self.add_both("KN", "N");
+            } else if !self.is_at(index + 2, 2, &["EY"]) && !self.slavo_germanic {
+                // Not like "cagney"
+                self.add_both("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            return index + 2;
+        }
+        // "tagliaro"
+        if self.is_at(index + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.add_both("KL", "L");
+            return index + 2;
+        }
+        // -ges-, -gep-, -gel- and -gie- at the start
+        if index == 0
+            && (next == 'Y'
+                || self.is_at(
+                    index + 1,
+                    2,
+                    &[
+                        "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                    ],
+                ))
+        {
+            self.add_both("K", "J");
+            return index + 2;
+        }
+        // -ger- and -gy-
+        if (self.is_at(index + 1, 2, &["ER"]) || next == 'Y')
+            && !self.is_at(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.is_at(index - 1, 1, &["E", "I"])
+            && !self.is_at(index - 1, 3, &["RGY", "OGY"])
+        {
+            self.add_both("K", "J");
+            return index + 2;
+        }
+        // Italian "biaggi"
+        if self.is_at(index + 1, 1, &["E", "I", "Y"]) || self.is_at(index - 1, 4, &["AGGI", "OGGI"])
+        {
+            if self.is_at(0, 4, &["VAN ", "VON "])
+                || self.is_at(0, 3, &["SCH"])
+                || self.is_at(index + 1, 2, &["ET"])
+            {
+                // Germanic
+                self.add("K");
+            } else if self.is_at(index + 1, 3, &["IER"]) {
+                self.add("J");
+            } else {
+                self.add_both("J", "K");
+            }
+            return index + 2;
+        }
+
+        self.add("K");
+        self.skip_double(index, 'G')
+    }
+
+    fn encode_gh(&mut self, index: isize) -> isize {
+        if index > 0 && !is_vowel(self.at(index - 1)) {
+            self.add("K");
+        } else if index == 0 {
+            // "ghislane", "ghiradelli"
+            if self.at(index + 2) == 'I' {
+                self.add("J");
+            } else {
+                self.add("K");
+            }
+        } else if (index > 1 && self.is_at(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.is_at(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.is_at(index - 4, 1, &["B", "H"]))
+        {
+            // Silent, as in "hugh", "bough" and "broughton"
+        } else if index > 2
+            && self.at(index - 1) == 'U'
+            && self.is_at(index - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // "laugh", "McLaughlin", "cough", "rough", "tough"
+            self.add("F");
+        } else if index > 0 && self.at(index - 1) != 'I' {
+            self.add("K");
+        }
+        index + 2
+    }
+
+    fn encode_h(&mut self, index: isize) -> isize {
+        // Only kept between vowels or at the start before one
+        if (index == 0 || is_vowel(self.at(index - 1))) && is_vowel(self.at(index + 1)) {
+            self.add("H");
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode_j(&mut self, index: isize) -> isize {
+        // Spanish "Jose", "San Jacinto"
+        if self.is_at(index, 4, &["JOSE"]) || self.is_at(0, 4, &["SAN "]) {
+            if (index == 0 && self.at(index + 4) == ' ')
+                || self.len() == 4
+                || self.is_at(0, 4, &["SAN "])
+            {
+                self.add("H");
+            } else {
+                self.add_both("J", "H");
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            // "Yankelovich", "Jankelowicz"
+            self.add_both("J", "A");
+        } else if is_vowel(self.at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.at(index + 1), 'A' | 'O')
+        {
+            // Spanish "bajador"
+            self.add_both("J", "H");
+        } else if index == self.len() - 1 {
+            self.add_primary("J");
+        } else if !self.is_at(index + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.is_at(index - 1, 1, &["S", "K", "L"])
+        {
+            self.add("J");
+        }
+        self.skip_double(index, 'J')
+    }
+
+    fn encode_l(&mut self, index: isize) -> isize {
+        if self.at(index + 1) != 'L' {
+            self.add("L");
+            return index + 1;
+        }
+        // Spanish "cabrillo", "gallegos"
+        let len = self.len();
+        let spanish = (index == len - 3 && self.is_at(index - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+            || ((self.is_at(len - 2, 2, &["AS", "OS"]) || self.is_at(len - 1, 1, &["A", "O"]))
+                && self.is_at(index - 1, 4, &["ALLE"]));
+        if spanish {
+            self.add_primary("L");
+        } else {
+            self.add("L");
+        }
+        index + 2
+    }
+
+    fn encode_r(&mut self, index: isize) -> isize {
+        // French "Rogier", but not "hochmeier"
+        if index == self.len() - 1
+            && !self.slavo_germanic
+            && self.is_at(index - 2, 2, &["IE"])
+            && !self.is_at(index - 4, 2, &["ME", "MA"])
+        {
+            self.add_alternate("R");
+        } else {
+            self.add("R");
+        }
+        self.skip_double(index, 'R')
+    }
+
+    fn encode_s(&mut self, index: isize) -> isize {
+        // Silent, as in "island", "carlisle" and "carlysle"
+        if self.is_at(index - 1, 3, &["ISL", "YSL"]) {
+            return index + 1;
+        }
+        // "sugar"
+        if index == 0 && self.is_at(index, 5, &["SUGAR"]) {
+            self.add_both("X", "S");
+            return index + 1;
+        }
+        if self.is_at(index, 2, &["SH"]) {
+            // Germanic
+            if self.is_at(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            return index + 2;
+        }
+        // Italian and Armenian
+        if self.is_at(index, 3, &["SIO", "SIA"]) || self.is_at(index, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_both("S", "X");
+            }
+            return index + 3;
+        }
+        // German and anglicisations, like "Smith" and "Schmidt", "snider" and "schneider"
+        if (index == 0 && self.is_at(index + 1, 1, &["M", "N", "L", "W"]))
+            || self.is_at(index + 1, 1, &["Z"])
+        {
+            self.add_both("S", "X");
+            return self.skip_double(index, 'Z');
+        }
+        if self.is_at(index, 2, &["SC"]) {
+            return self.encode_sc(index);
+        }
+
+        // French, as in "resnais" and "artois"
+        if index == self.len() - 1 && self.is_at(index - 2, 2, &["AI", "OI"]) {
+            self.add_alternate("S");
+        } else {
+            self.add("S");
+        }
+        if self.is_at(index + 1, 1, &["S", "Z"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode_sc(&mut self, index: isize) -> isize {
+        if self.at(index + 2) == 'H' {
+            if self.is_at(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch "school", "schooner", or German "schermerhorn", "schenker"
+                if self.is_at(index + 3, 2, &["ER", "EN"]) {
+                    self.add_both("X", "SK");
+                } else {
+                    self.add("SK");
+                }
+            } else if index == 0 && !is_vowel(self.at(3)) && self.at(3) != 'W' {
+                self.add_both("X", "S");
+            } else {
+                self.add("X");
+            }
+        } else if self.is_at(index + 2, 1, &["I", "E", "Y"]) {
+            self.add("S");
+        } else {
+            self.add("SK");
+        }
+        index + 3
+    }
+
+    fn encode_t(&mut self, index: isize) -> isize {
+        if self.is_at(index, 4, &["TION"]) || self.is_at(index, 3, &["TIA", "TCH"]) {
+            self.add("X");
+            return index + 3;
+        }
+        if self.is_at(index, 2, &["TH"]) || self.is_at(index, 3, &["TTH"]) {
+            // "Thomas", "Thames", or Germanic
+            if self.is_at(index + 2, 2, &["OM", "AM"])
+                || self.is_at(0, 4, &["VAN ", "VON "])
+                || self.is_at(0, 3, &["SCH"])
+            {
+                self.add("T");
+            } else {
+                self.add_both("0", "T");
+            }
+            return index + 2;
+        }
+        self.add("T");
+        if self.is_at(index + 1, 1, &["T", "D"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode_w(&mut self, index: isize) -> isize {
+        // "Wright" sounds like "Right"
+        if self.is_at(index, 2, &["WR"]) {
+            self.add("R");
+            return index + 2;
+        }
+        let next_is_vowel = is_vowel(self.at(index + 1));
+        if index == 0 && (next_is_vowel || self.is_at(index, 2, &["WH"])) {
+            // "Wasserman" should match "Vasserman"
+            if next_is_vowel {
+                self.add_both("A", "F");
+            } else {
+                self.add("A");
+            }
+            return index + 1;
+        }
+        // Polish, as in "Filipowicz", and Arnow written as Arnoff
+        if (index == self.len() - 1 && is_vowel(self.at(index - 1)))
+            || self.is_at(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.is_at(0, 3, &["SCH"])
+        {
+            self.add_alternate("F");
+            return index + 1;
+        }
+        if self.is_at(index, 4, &["WICZ", "WITZ"]) {
+            self.add_both("TS", "FX");
+            return index + 4;
+        }
+        index + 1
+    }
+
+    fn encode_x(&mut self, index: isize) -> isize {
+        // "Xavier"
+        if index == 0 {
+            self.add("S");
+            return index + 1;
+        }
+        // Silent at the end of French words, as in "breaux"
+        let french = index == self.len() - 1
+            && (self.is_at(index - 3, 3, &["IAU", "EAU"])
+                || self.is_at(index - 2, 2, &["AU", "OU"]));
+        if !french {
+            self.add("KS");
+        }
+        if self.is_at(index + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn encode_z(&mut self, index: isize) -> isize {
+        // Chinese pinyin, as in "Zhao"
+        if self.at(index + 1) == 'H' {
+            self.add("J");
+            return index + 2;
+        }
+        if self.is_at(index + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.at(index - 1) != 'T')
+        {
+            self.add_both("S", "TS");
+        } else {
+            self.add("S");
+        }
+        self.skip_double(index, 'Z')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(word: &str) -> (String, String) {
+        double_metaphone(word)
+    }
+
+    #[test]
+    fn double_metaphone_codes() {
+        assert_eq!(codes("Smith"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(codes("Schmidt"), ("XMT".to_string(), "SMT".to_string()));
+        assert_eq!(codes("Thomas"), ("TMS".to_string(), "TMS".to_string()));
+        assert_eq!(codes("Knight"), ("NT".to_string(), "NT".to_string()));
+        assert_eq!(codes("Xavier"), ("SF".to_string(), "SFR".to_string()));
+        assert_eq!(codes(""), (String::new(), String::new()));
+    }
+
+    #[test]
+    fn names_spelled_differently_sound_alike() {
+        assert!(sounds_alike(&codes("Smith"), &codes("Schmidt")));
+        assert!(sounds_alike(&codes("Catherine"), &codes("Kathryn")));
+        assert!(!sounds_alike(&codes("Cathy"), &codes("Sammy")));
+        assert!(!sounds_alike(&codes(""), &codes("")));
+    }
+}
@@ -0,0 +1,329 @@
+//! Spelling mode: letters, NATO alphabet words, digits and symbols dictated
+//! one at a time are written as the characters they stand for.
+//!
+//! "spell mode ... end spell" spells everything in between and keeps words
+//! it has no spelling for as they are, so "spell mode j doe at example dot
+//! com end spell" becomes "jdoe@example.com". "spell that alpha bravo one"
+//! spells the words after it, up to the first one that cannot be spelled.
+//! "capital" before a letter writes it in upper case.
+
+/// Words that stand for a single character
+const SPELLINGS: &[(&str, char)] = &[
+    // NATO phonetic alphabet
+    ("alpha", 'a'),
+    ("alfa", 'a'),
+    ("bravo", 'b'),
+    ("charlie", 'c'),
+    ("delta", 'd'),
+    ("echo", 'e'),
+    ("foxtrot", 'f'),
+    ("golf", 'g'),
+    ("hotel", 'h'),
+    ("india", 'i'),
+    ("juliet", 'j'),
+    ("juliett", 'j'),
+    ("kilo", 'k'),
+    ("lima", 'l'),
+    ("mike", 'm'),
+    ("november", 'n'),
+    ("oscar", 'o'),
+    ("papa", 'p'),
+    ("quebec", 'q'),
+    ("romeo", 'r'),
+    ("sierra", 's'),
+    ("tango", 't'),
+    ("uniform", 'u'),
+    ("victor", 'v'),
+    ("whiskey", 'w'),
+    ("whisky", 'w'),
+    ("x-ray", 'x'),
+    ("xray", 'x'),
+    ("yankee", 'y'),
+    ("zulu", 'z'),
+    // Letter names the recognizer writes out in full
+    ("bee", 'b'),
+    ("cee", 'c'),
+    ("dee", 'd'),
+    ("gee", 'g'),
+    ("aitch", 'h'),
+    ("jay", 'j'),
+    ("kay", 'k'),
+    ("ell", 'l'),
+    ("em", 'm'),
+    ("en", 'n'),
+    ("pee", 'p'),
+    ("cue", 'q'),
+    ("tee", 't'),
+    ("vee", 'v'),
+    ("double-u", 'w'),
+    ("ex", 'x'),
+    ("zed", 'z'),
+    ("zee", 'z'),
+    // Digits
+    ("zero", '0'),
+    ("one", '1'),
+    ("two", '2'),
+    ("three", '3'),
+    ("four", '4'),
+    ("five", '5'),
+    ("six", '6'),
+    ("seven", '7'),
+    ("eight", '8'),
+    ("nine", '9'),
+    // Symbols
+    ("at", '@'),
+    ("dot", '.'),
+    ("period", '.'),
+    ("dash", '-'),
+    ("hyphen", '-'),
+    ("underscore", '_'),
+    ("slash", '/'),
+    ("plus", '+'),
+    ("hash", '#'),
+    ("space", ' '),
+];
+
+/// Words that write the next letter in upper case
+const CAPITAL_WORDS: &[&str] = &["capital", "cap", "uppercase"];
+
+/// Symbols kept when dictated as themselves rather than by name
+const SYMBOLS: &str = "@#+/_-";
+
+enum Piece {
+    Char(char),
+    Digits(String),
+    Word(String),
+    Capital,
+}
+
+impl Piece {
+    /// Whether a spelled run may end on this piece
+    fn is_solid(&self) -> bool {
+        match self {
+            Piece::Char(c) => c.is_alphanumeric(),
+            Piece::Digits(_) | Piece::Word(_) => true,
+            Piece::Capital => false,
+        }
+    }
+}
+
+/// Splits a token into its leading punctuation, the word, and its trailing punctuation
+fn split_token(token: &str) -> (&str, &str, &str) {
+    let Some(start) = token.find(|c: char| c.is_alphanumeric()) else {
+        return (token, "", "");
+    };
+    let end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(token.len());
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+/// What `token` spells, if it spells anything
+fn lookup(token: &str) -> Option<Piece> {
+    let (_, word, _) = split_token(token);
+    if word.is_empty() {
+        let symbol = token.trim();
+        return (symbol.chars().count() == 1 && SYMBOLS.contains(symbol))
+            .then(|| Piece::Char(symbol.chars().next().unwrap()));
+    }
+
+    let lower = word.to_lowercase();
+    if CAPITAL_WORDS.contains(&lower.as_str()) {
+        return Some(Piece::Capital);
+    }
+    let mut chars = lower.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Piece::Char(c));
+    }
+    if lower.chars().all(|c| c.is_ascii_digit()) {
+        return Some(Piece::Digits(lower));
+    }
+    SPELLINGS
+        .iter()
+        .find(|(name, _)| *name == lower)
+        .map(|(_, c)| Piece::Char(*c))
+}
+
+fn spell(pieces: Vec<Piece>) -> String {
+    let mut spelled = String::new();
+    let mut capital = false;
+    for piece in pieces {
+        match piece {
+            Piece::Capital => {
+                capital = true;
+                continue;
+            }
+            Piece::Char(c) if capital => spelled.extend(c.to_uppercase()),
+            Piece::Char(c) => spelled.push(c),
+            Piece::Digits(digits) => spelled.push_str(&digits),
+            Piece::Word(word) if capital => {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    spelled.extend(first.to_uppercase());
+                    spelled.push_str(chars.as_str());
+                }
+            }
+            Piece::Word(word) => spelled.push_str(&word),
+        }
+        capital = false;
+    }
+    spelled
+}
+
+/// Sentence punctuation ending `token`, kept after the spelled text
+fn sentence_end(token: &str) -> String {
+    let (_, _, trailing) = split_token(token);
+    trailing
+        .chars()
+        .filter(|c| matches!(c, '.' | '!' | '?'))
+        .collect()
+}
+
+fn is_end(words: &[String], i: usize) -> bool {
+    words[i] == "end"
+        && words
+            .get(i + 1)
+            .is_some_and(|next| next == "spell" || next == "spelling")
+}
+
+/// Replaces "spell mode ... end spell" and "spell that ..." dictation with
+/// the characters spelled out.
+///
+/// # Arguments
+/// * `text` - The transcription to scan for spelling commands
+///
+/// # Returns
+/// The text with each spelling command replaced by what it spells
+pub fn apply_spelling(text: &str) -> String {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = tokens
+        .iter()
+        .map(|token| split_token(token).1.to_lowercase())
+        .collect();
+
+    let mut result: Vec<String> = Vec::new();
+    let mut changed = false;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let next = words.get(i + 1).map(String::as_str);
+        let (lead, _, _) = split_token(tokens[i]);
+
+        if (words[i] == "spell" || words[i] == "spelling") && next == Some("mode") {
+            // Spell everything up to "end spell", or to the end of the text
+            let start = i + 2;
+            let stop = (start..tokens.len())
+                .find(|&j| is_end(&words, j))
+                .unwrap_or(tokens.len());
+            let pieces = tokens[start..stop]
+                .iter()
+                .filter_map(|token| {
+                    lookup(token).or_else(|| {
+                        let (_, word, _) = split_token(token);
+                        (!word.is_empty()).then(|| Piece::Word(word.to_string()))
+                    })
+                })
+                .collect();
+            let after = if stop < tokens.len() { stop + 2 } else { stop };
+            let spelled = spell(pieces);
+            if !spelled.is_empty() {
+                result.push(format!(
+                    "{}{}{}",
+                    lead,
+                    spelled,
+                    sentence_end(tokens[after - 1])
+                ));
+            }
+            changed = true;
+            i = after;
+            continue;
+        }
+
+        if words[i] == "spell" && next == Some("that") {
+            // Spell the run of spellable words that follows, without
+            // trailing symbols, so "at" in "... at the office" stays a word
+            let start = i + 2;
+            let mut pieces = Vec::new();
+            let mut end = start;
+            for (j, token) in tokens.iter().enumerate().skip(start) {
+                let Some(piece) = lookup(token) else {
+                    break;
+                };
+                if piece.is_solid() {
+                    end = j + 1;
+                }
+                pieces.push(piece);
+            }
+            if end > start {
+                pieces.truncate(end - start);
+                result.push(format!(
+                    "{}{}{}",
+                    lead,
+                    spell(pieces),
+                    sentence_end(tokens[end - 1])
+                ));
+                changed = true;
+                i = end;
+                continue;
+            }
+        }
+
+        result.push(tokens[i].to_string());
+        i += 1;
+    }
+
+    if changed {
+        result.join(" ")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spell_mode_spells_until_end_spell() {
+        assert_eq!(
+            apply_spelling("My email is spell mode j doe at example dot com end spell thanks"),
+            "My email is jdoe@example.com thanks"
+        );
+        assert_eq!(
+            apply_spelling("Spell mode capital kilo nine underscore x-ray end spell."),
+            "K9_x."
+        );
+        // Without "end spell" everything to the end is spelled
+        assert_eq!(
+            apply_spelling("the id is spell mode alpha, bravo, one two"),
+            "the id is ab12"
+        );
+    }
+
+    #[test]
+    fn spell_that_spells_the_following_run() {
+        assert_eq!(
+            apply_spelling("The code is spell that alpha bravo 3 capital x-ray and then some"),
+            "The code is ab3X and then some"
+        );
+        assert_eq!(apply_spelling("Spell that, Charlie. Delta. Echo."), "cde.");
+        // A trailing symbol word is left as a word
+        assert_eq!(
+            apply_spelling("meet spell that tango at the office"),
+            "meet t at the office"
+        );
+    }
+
+    #[test]
+    fn text_without_spelling_commands_is_unchanged() {
+        assert_eq!(
+            apply_spelling("I can't  spell that word"),
+            "I can't  spell that word"
+        );
+        assert_eq!(
+            apply_spelling("alpha bravo at the hotel"),
+            "alpha bravo at the hotel"
+        );
+    }
+}
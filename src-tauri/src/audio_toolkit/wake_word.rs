@@ -0,0 +1,147 @@
+//! Wake word detection with openWakeWord models.
+//!
+//! openWakeWord splits detection in three ONNX models: a melspectrogram
+//! front end and a speech embedding model, both shared by every wake word,
+//! and a small classifier trained for one phrase. Audio is processed in
+//! 80 ms chunks, each adding eight mel frames and one embedding; the
+//! classifier scores the last 16 embeddings (about 1.3 s of speech).
+//!
+//! Everything runs in-process on 16 kHz mono audio; nothing is kept beyond
+//! the rolling buffers the models need.
+
+use anyhow::{anyhow, Result};
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::Tensor;
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Samples per chunk, 80 ms at 16 kHz
+const CHUNK_SAMPLES: usize = 1280;
+/// Samples from the previous chunk the melspectrogram needs for its window
+const MEL_CONTEXT_SAMPLES: usize = 160 * 3;
+const MEL_BINS: usize = 32;
+/// Mel frames per embedding
+const EMBEDDING_WINDOW: usize = 76;
+const EMBEDDING_SIZE: usize = 96;
+/// Embeddings the classifier scores at once
+const FEATURE_WINDOW: usize = 16;
+
+pub struct WakeWordDetector {
+    melspectrogram: Session,
+    embedding: Session,
+    classifier: Session,
+    classifier_input: String,
+    /// Samples not yet making up a full chunk
+    pending: Vec<f32>,
+    /// The tail of the last chunk, in 16-bit sample scale
+    context: Vec<f32>,
+    mel_frames: VecDeque<[f32; MEL_BINS]>,
+    embeddings: VecDeque<Vec<f32>>,
+}
+
+fn load_session(path: &Path) -> Result<Session> {
+    Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .with_intra_threads(1)?
+        .commit_from_file(path)
+        .map_err(|e| anyhow!("Failed to load {:?}: {}", path, e))
+}
+
+impl WakeWordDetector {
+    pub fn new(melspectrogram: &Path, embedding: &Path, classifier: &Path) -> Result<Self> {
+        let classifier = load_session(classifier)?;
+        let classifier_input = classifier
+            .inputs
+            .first()
+            .map(|input| input.name.clone())
+            .ok_or_else(|| anyhow!("Wake word model has no input"))?;
+
+        Ok(Self {
+            melspectrogram: load_session(melspectrogram)?,
+            embedding: load_session(embedding)?,
+            classifier,
+            classifier_input,
+            pending: Vec::with_capacity(CHUNK_SAMPLES),
+            context: Vec::new(),
+            mel_frames: VecDeque::with_capacity(EMBEDDING_WINDOW),
+            embeddings: VecDeque::with_capacity(FEATURE_WINDOW),
+        })
+    }
+
+    /// Feeds 16 kHz mono samples and returns the highest wake word score,
+    /// 0 to 1, of the chunks they completed. `None` until enough audio has
+    /// been heard to score.
+    pub fn push(&mut self, samples: &[f32]) -> Result<Option<f32>> {
+        self.pending.extend_from_slice(samples);
+        let mut best: Option<f32> = None;
+        while self.pending.len() >= CHUNK_SAMPLES {
+            let chunk: Vec<f32> = self.pending.drain(..CHUNK_SAMPLES).collect();
+            if let Some(score) = self.process_chunk(&chunk)? {
+                best = Some(best.map_or(score, |best| best.max(score)));
+            }
+        }
+        Ok(best)
+    }
+
+    /// Forgets the audio heard so far, e.g. after a detection so the same
+    /// phrase isn't scored again
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.context.clear();
+        self.mel_frames.clear();
+        self.embeddings.clear();
+    }
+
+    fn process_chunk(&mut self, chunk: &[f32]) -> Result<Option<f32>> {
+        // The models were trained on 16-bit samples
+        let mut audio = std::mem::take(&mut self.context);
+        audio.extend(chunk.iter().map(|s| s * i16::MAX as f32));
+        self.context = audio[audio.len().saturating_sub(MEL_CONTEXT_SAMPLES)..].to_vec();
+
+        let len = audio.len();
+        let outputs = self.melspectrogram.run(ort::inputs![
+            "input" => Tensor::from_array(([1usize, len], audio))?,
+        ])?;
+        let (_, mel) = outputs[0].try_extract_tensor::<f32>()?;
+        for frame in mel.chunks_exact(MEL_BINS) {
+            let mut scaled = [0.0; MEL_BINS];
+            for (out, value) in scaled.iter_mut().zip(frame) {
+                // Same scaling openWakeWord applies before the embedding model
+                *out = value / 10.0 + 2.0;
+            }
+            if self.mel_frames.len() == EMBEDDING_WINDOW {
+                self.mel_frames.pop_front();
+            }
+            self.mel_frames.push_back(scaled);
+        }
+        drop(outputs);
+        if self.mel_frames.len() < EMBEDDING_WINDOW {
+            return Ok(None);
+        }
+
+        let window: Vec<f32> = self.mel_frames.iter().flatten().copied().collect();
+        let outputs = self.embedding.run(ort::inputs![
+            "input_1" => Tensor::from_array(([1usize, EMBEDDING_WINDOW, MEL_BINS, 1], window))?,
+        ])?;
+        let (_, embedding) = outputs[0].try_extract_tensor::<f32>()?;
+        if embedding.len() != EMBEDDING_SIZE {
+            return Err(anyhow!("Unexpected wake word embedding size"));
+        }
+        if self.embeddings.len() == FEATURE_WINDOW {
+            self.embeddings.pop_front();
+        }
+        self.embeddings.push_back(embedding.to_vec());
+        drop(outputs);
+        if self.embeddings.len() < FEATURE_WINDOW {
+            return Ok(None);
+        }
+
+        let features: Vec<f32> = self.embeddings.iter().flatten().copied().collect();
+        let outputs = self.classifier.run(ort::inputs![
+            self.classifier_input.as_str() => Tensor::from_array(([1usize, FEATURE_WINDOW, EMBEDDING_SIZE], features))?,
+        ])?;
+        let (_, scores) = outputs[0].try_extract_tensor::<f32>()?;
+        Ok(scores.first().copied())
+    }
+}
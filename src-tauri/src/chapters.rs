@@ -0,0 +1,169 @@
+//! Split long file transcriptions into chapters.
+//!
+//! The timed segments are grouped into numbered lines of about a minute, and
+//! the configured post-processing LLM picks the lines where each chapter
+//! starts, the same way meetings are split into topics. The first chapter is
+//! moved to the start of the file, as YouTube and podcast apps expect.
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::commands::export::{TranscriptChapter, TranscriptSegment};
+use crate::managers::meeting::complete_prompt;
+use crate::settings::AppSettings;
+
+/// Transcripts shorter than this are not split into chapters
+pub const MIN_CHAPTERS_DURATION_MS: u64 = 5 * 60 * 1000;
+
+/// Length of the transcript lines the LLM picks chapter starts from
+const LINE_MS: u64 = 60 * 1000;
+
+/// Text shown per line, so a long file still fits one prompt
+const MAX_LINE_CHARS: usize = 400;
+
+/// Chapters of a transcript, from its timed segments
+pub async fn detect_chapters(
+    settings: &AppSettings,
+    segments: &[TranscriptSegment],
+) -> Result<Vec<TranscriptChapter>> {
+    let lines = group_lines(segments);
+    if lines.len() < 2 {
+        return Ok(Vec::new());
+    }
+    let reply = complete_prompt(settings, chapter_prompt(&lines), "chapter detection").await?;
+    parse_chapters(&reply, &lines)
+}
+
+/// A stretch of the transcript shown to the LLM as one numbered line
+#[derive(Debug, PartialEq)]
+struct Line {
+    start_ms: u64,
+    text: String,
+}
+
+/// Consecutive segments joined into lines of about `LINE_MS`
+fn group_lines(segments: &[TranscriptSegment]) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::new();
+    for segment in segments.iter().filter(|s| !s.text.trim().is_empty()) {
+        match lines.last_mut() {
+            Some(line) if segment.start_ms < line.start_ms + LINE_MS => {
+                line.text.push(' ');
+                line.text.push_str(segment.text.trim());
+            }
+            _ => lines.push(Line {
+                start_ms: segment.start_ms,
+                text: segment.text.trim().to_string(),
+            }),
+        }
+    }
+    lines
+}
+
+/// Prompt asking for chapter starts, given the numbered lines
+fn chapter_prompt(lines: &[Line]) -> String {
+    let mut prompt = String::from(
+        "Split the following transcript into chapters, as for a podcast or video. Each numbered line is about a minute of it. \
+Reply with only a JSON array, in order, of objects with a short \"title\" for the chapter and the number of the \"line\" where it starts, \
+e.g. [{\"title\": \"Introduction\", \"line\": 0}].\n\n",
+    );
+    for (index, line) in lines.iter().enumerate() {
+        let text: String = line.text.chars().take(MAX_LINE_CHARS).collect();
+        prompt.push_str(&format!("{}: {}\n", index, text));
+    }
+    prompt
+}
+
+/// Chapters from the LLM's reply to `chapter_prompt`
+fn parse_chapters(response: &str, lines: &[Line]) -> Result<Vec<TranscriptChapter>> {
+    #[derive(Deserialize)]
+    struct ChapterStart {
+        title: String,
+        line: usize,
+    }
+
+    let start = response
+        .find('[')
+        .ok_or_else(|| anyhow!("Reply contains no JSON array"))?;
+    let end = response
+        .rfind(']')
+        .filter(|end| *end > start)
+        .ok_or_else(|| anyhow!("Reply contains no JSON array"))?;
+    let mut starts: Vec<ChapterStart> = serde_json::from_str(&response[start..=end])
+        .map_err(|e| anyhow!("Failed to parse chapters: {}", e))?;
+
+    starts.retain(|chapter| chapter.line < lines.len() && !chapter.title.trim().is_empty());
+    starts.sort_by_key(|chapter| chapter.line);
+    starts.dedup_by_key(|chapter| chapter.line);
+
+    Ok(starts
+        .iter()
+        .enumerate()
+        .map(|(index, chapter)| TranscriptChapter {
+            title: chapter.title.trim().to_string(),
+            start_ms: if index == 0 {
+                0
+            } else {
+                lines[chapter.line].start_ms
+            },
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_secs: u64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start_ms: start_secs * 1000,
+            end_ms: start_secs * 1000 + 5000,
+            text: text.to_string(),
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn chapters_start_on_lines_and_the_first_at_zero() {
+        let segments = vec![
+            segment(3, "Welcome."),
+            segment(30, "Today: chapters."),
+            segment(70, "First topic."),
+            segment(150, "Second topic."),
+        ];
+        let lines = group_lines(&segments);
+        assert_eq!(
+            lines,
+            vec![
+                Line {
+                    start_ms: 3000,
+                    text: "Welcome. Today: chapters.".into()
+                },
+                Line {
+                    start_ms: 70_000,
+                    text: "First topic.".into()
+                },
+                Line {
+                    start_ms: 150_000,
+                    text: "Second topic.".into()
+                },
+            ]
+        );
+
+        let reply = r#"Sure! [{"title": "Topic two", "line": 2}, {"title": "Intro", "line": 0},
+            {"title": "Again", "line": 2}, {"title": "Out of range", "line": 9}]"#;
+        assert_eq!(
+            parse_chapters(reply, &lines).unwrap(),
+            vec![
+                TranscriptChapter {
+                    title: "Intro".into(),
+                    start_ms: 0
+                },
+                TranscriptChapter {
+                    title: "Topic two".into(),
+                    start_ms: 150_000
+                },
+            ]
+        );
+        assert!(parse_chapters("No chapters here", &lines).is_err());
+    }
+}
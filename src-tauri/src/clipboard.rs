@@ -1,7 +1,14 @@
+use crate::context::active_app::focused_window_id;
 use crate::input::{self, EnigoState};
-use crate::settings::{get_settings, ClipboardHandling, PasteMethod};
+use crate::settings::{get_settings, AppSettings, ClipboardHandling, PasteMethod, TypingSettings};
 use enigo::Enigo;
-use log::info;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
@@ -10,15 +17,159 @@ use crate::utils::is_wayland;
 #[cfg(target_os = "linux")]
 use std::process::Command;
 
+/// Kept for the life of the app: on X11 dropping the last handle hands the
+/// clipboard over to the clipboard manager, which can block
+static SYSTEM_CLIPBOARD: Lazy<Mutex<Option<arboard::Clipboard>>> =
+    Lazy::new(|| Mutex::new(arboard::Clipboard::new().ok()));
+
+/// Attempts at putting the previous clipboard back before giving up
+const RESTORE_ATTEMPTS: u32 = 3;
+
+/// Snapshot waiting to be put back after the last paste
+static PENDING_RESTORE: Mutex<Option<ClipboardSnapshot>> = Mutex::new(None);
+/// Bumped by each paste that schedules a restore
+static RESTORE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// How often paced typing checks that the target window still has focus
+const FOCUS_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Feeds the paste worker, started with the first paced paste
+static PASTE_QUEUE: OnceLock<Sender<QueuedPaste>> = OnceLock::new();
+/// Pastes sent to the worker that it hasn't finished yet
+static QUEUED_PASTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A paste handed to the paste worker
+struct QueuedPaste {
+    app_handle: AppHandle,
+    text: String,
+    paste_method: PasteMethod,
+    settings: AppSettings,
+}
+
+/// What was on the clipboard before a paste.
+///
+/// Apps offer one copied item in several formats, but only one of them can
+/// be written back, so the snapshot keeps the richest one that still holds
+/// everything: copied files, then HTML with its plain text, then plain
+/// text. Without text an image wins over HTML, which for a copied picture
+/// is just a link to it; with text it is usually a picture of spreadsheet
+/// cells and the text matters more.
+#[derive(Debug, Clone)]
+enum ClipboardSnapshot {
+    Files(Vec<PathBuf>),
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    Text(String),
+    Image(arboard::ImageData<'static>),
+    Empty,
+}
+
+impl ClipboardSnapshot {
+    fn take(clipboard: &mut arboard::Clipboard) -> Self {
+        let files = clipboard.get().file_list().ok();
+        let html = clipboard.get().html().ok();
+        let text = clipboard.get_text().ok();
+        Self::select(files, html, text, || clipboard.get_image().ok())
+    }
+
+    /// Picks what to keep from the formats on offer. The image is only
+    /// read when it could win, as it is the slowest format to fetch.
+    fn select(
+        files: Option<Vec<PathBuf>>,
+        html: Option<String>,
+        text: Option<String>,
+        image: impl FnOnce() -> Option<arboard::ImageData<'static>>,
+    ) -> Self {
+        if let Some(files) = files.filter(|files| !files.is_empty()) {
+            return Self::Files(files);
+        }
+        let html = html.filter(|html| !html.is_empty());
+        match (html, text.filter(|text| !text.is_empty())) {
+            (Some(html), Some(text)) => Self::Html {
+                html,
+                alt_text: Some(text),
+            },
+            (None, Some(text)) => Self::Text(text),
+            (html, None) => match (image(), html) {
+                (Some(image), _) => Self::Image(image),
+                (None, Some(html)) => Self::Html {
+                    html,
+                    alt_text: None,
+                },
+                (None, None) => Self::Empty,
+            },
+        }
+    }
+
+    fn restore(&self, clipboard: &mut arboard::Clipboard) -> Result<(), arboard::Error> {
+        match self {
+            Self::Files(files) => clipboard.set().file_list(files),
+            Self::Html { html, alt_text } => {
+                clipboard.set().html(html.as_str(), alt_text.as_deref())
+            }
+            Self::Text(text) => clipboard.set_text(text.as_str()),
+            Self::Image(image) => clipboard.set_image(image.clone()),
+            Self::Empty => clipboard.clear(),
+        }
+    }
+
+    /// Whether the clipboard holds the snapshot again. Formats can come
+    /// back slightly changed, e.g. HTML with a platform header, so this
+    /// compares what survives a round trip.
+    fn is_restored(&self, clipboard: &mut arboard::Clipboard) -> bool {
+        match self {
+            Self::Files(files) => clipboard.get().file_list().is_ok_and(|f| &f == files),
+            Self::Html {
+                alt_text: Some(text),
+                ..
+            }
+            | Self::Text(text) => clipboard.get_text().is_ok_and(|t| &t == text),
+            Self::Html { alt_text: None, .. } => clipboard.get().html().is_ok(),
+            Self::Image(image) => clipboard
+                .get_image()
+                .is_ok_and(|i| i.width == image.width && i.height == image.height),
+            Self::Empty => clipboard.get_text().map_or(true, |t| t.is_empty()),
+        }
+    }
+}
+
+/// Puts `snapshot` back on the clipboard, checking that it took
+fn restore_clipboard(snapshot: &ClipboardSnapshot, delay: Duration) -> Result<(), String> {
+    let mut guard = SYSTEM_CLIPBOARD
+        .lock()
+        .map_err(|e| format!("Failed to lock clipboard: {}", e))?;
+    let clipboard = guard.as_mut().ok_or("Clipboard is not available")?;
+
+    for attempt in 1..=RESTORE_ATTEMPTS {
+        match snapshot.restore(clipboard) {
+            Ok(()) if snapshot.is_restored(clipboard) => return Ok(()),
+            Ok(()) => warn!("Clipboard restore didn't take (attempt {})", attempt),
+            Err(e) => warn!("Failed to restore clipboard (attempt {}): {}", attempt, e),
+        }
+        std::thread::sleep(delay);
+    }
+    Err("Failed to restore clipboard".to_string())
+}
+
 /// Pastes text using the clipboard: saves current content, writes text, sends paste keystroke, restores clipboard.
 fn paste_via_clipboard(
     enigo: &mut Enigo,
     text: &str,
     app_handle: &AppHandle,
     paste_method: &PasteMethod,
+    restore_delay: Option<Duration>,
 ) -> Result<(), String> {
     let clipboard = app_handle.clipboard();
-    let clipboard_content = clipboard.read_text().unwrap_or_default();
+    // Nothing to keep when the transcript stays on the clipboard anyway
+    let snapshot = restore_delay.and_then(|_| {
+        if let Some(pending) = PENDING_RESTORE.lock().ok().and_then(|mut p| p.take()) {
+            return Some(pending);
+        }
+        let mut guard = SYSTEM_CLIPBOARD.lock().ok()?;
+        Some(ClipboardSnapshot::take(guard.as_mut()?))
+    });
 
     // Write text to clipboard first
     clipboard
@@ -44,16 +195,46 @@ fn paste_via_clipboard(
         }
     }
 
-    std::thread::sleep(std::time::Duration::from_millis(50));
-
-    // Restore original clipboard content
-    clipboard
-        .write_text(&clipboard_content)
-        .map_err(|e| format!("Failed to restore clipboard: {}", e))?;
+    // Restore original clipboard content once the app had time to read it
+    if let (Some(snapshot), Some(delay)) = (snapshot, restore_delay) {
+        schedule_restore(snapshot, delay);
+    }
 
     Ok(())
 }
 
+/// Restores `snapshot` from a background thread after `delay`. The paste
+/// already went through, so a failed restore is only logged.
+///
+/// A paste made while a restore is still pending takes over its snapshot,
+/// since the clipboard then holds the earlier transcript, not what the
+/// user had copied.
+fn schedule_restore(snapshot: ClipboardSnapshot, delay: Duration) {
+    let generation = RESTORE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if let Ok(mut pending) = PENDING_RESTORE.lock() {
+        *pending = Some(snapshot);
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name("clipboard-restore".into())
+        .spawn(move || {
+            std::thread::sleep(delay);
+            // A later paste owns the snapshot now
+            if RESTORE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let Some(snapshot) = PENDING_RESTORE.lock().ok().and_then(|mut p| p.take()) else {
+                return;
+            };
+            if let Err(e) = restore_clipboard(&snapshot, delay) {
+                warn!("{}; the transcript stays on the clipboard", e);
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to schedule clipboard restore: {}", e);
+    }
+}
+
 /// Attempts to send a key combination using Linux-native tools.
 /// Returns `Ok(true)` if a native tool handled it, `Ok(false)` to fall back to enigo.
 #[cfg(target_os = "linux")]
@@ -348,8 +529,154 @@ fn send_key_combo_via_xdotool(paste_method: &PasteMethod) -> Result<(), String>
     Ok(())
 }
 
-/// Types text directly by simulating individual key presses.
-fn paste_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
+/// Hands a paste to the paste worker, which runs pastes one at a time in
+/// the order they were queued. Paced typing runs there so the pauses between
+/// chunks never hold up the event loop, and any paste made while the worker
+/// is busy waits behind it instead of interleaving with the typing.
+fn queue_paste(paste: QueuedPaste) -> Result<(), String> {
+    let queue = PASTE_QUEUE.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<QueuedPaste>();
+        let spawned = std::thread::Builder::new()
+            .name("paste-worker".into())
+            .spawn(move || {
+                for paste in rx {
+                    // Failures are only logged, the paste has returned by now
+                    if let Err(e) = run_queued_paste(paste) {
+                        warn!("Queued paste failed: {}", e);
+                    }
+                    QUEUED_PASTES.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        if let Err(e) = spawned {
+            warn!("Failed to start the paste worker: {}", e);
+        }
+        tx
+    });
+
+    QUEUED_PASTES.fetch_add(1, Ordering::SeqCst);
+    queue.send(paste).map_err(|_| {
+        QUEUED_PASTES.fetch_sub(1, Ordering::SeqCst);
+        "The paste worker is not running".to_string()
+    })
+}
+
+fn run_queued_paste(paste: QueuedPaste) -> Result<(), String> {
+    let QueuedPaste {
+        app_handle,
+        text,
+        paste_method,
+        settings,
+    } = paste;
+
+    let plan = plan_typing(&text, &settings.typing);
+    let pasted = if paste_method == PasteMethod::Direct && plan.len() > 1 {
+        info!(
+            "Typing {} characters in {} chunks",
+            text.chars().count(),
+            plan.len()
+        );
+        type_paced(&app_handle, &plan, settings.typing.stop_on_focus_change)
+    } else {
+        let text = text.clone();
+        let settings = settings.clone();
+        run_on_main_thread_and_wait(&app_handle, move |app_handle| {
+            send_paste(app_handle, &text, paste_method, &settings)
+        })
+    };
+
+    // Copy even when typing stopped early, so the rest can still be pasted
+    copy_if_configured(&app_handle, &text, &settings)?;
+    pasted
+}
+
+fn type_paced(
+    app_handle: &AppHandle,
+    plan: &[(String, Duration)],
+    stop_on_focus_change: bool,
+) -> Result<(), String> {
+    let focused = stop_on_focus_change.then(focused_window_id).flatten();
+    let mut last_focus_check = Instant::now();
+    for (index, (chunk, pause)) in plan.iter().enumerate() {
+        // Looking up the focused window runs a helper process, so only
+        // check every so often rather than before each chunk
+        if index > 0 && focused.is_some() && last_focus_check.elapsed() >= FOCUS_CHECK_INTERVAL {
+            last_focus_check = Instant::now();
+            if focused_window_id() != focused {
+                return Err("another window took focus".to_string());
+            }
+        }
+        type_chunk_on_main_thread(app_handle, chunk)?;
+        if index + 1 < plan.len() {
+            std::thread::sleep(*pause);
+        }
+    }
+    Ok(())
+}
+
+/// Types `chunk` on the main thread and waits until it has been sent
+fn type_chunk_on_main_thread(app_handle: &AppHandle, chunk: &str) -> Result<(), String> {
+    let chunk = chunk.to_string();
+    run_on_main_thread_and_wait(app_handle, move |app_handle| {
+        with_enigo(app_handle, |enigo| type_chunk(enigo, &chunk))
+    })
+}
+
+/// Runs `f` on the main thread and waits for its result
+fn run_on_main_thread_and_wait<T: Send + 'static>(
+    app_handle: &AppHandle,
+    f: impl FnOnce(&AppHandle) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    let (tx, rx) = mpsc::channel();
+    let ah = app_handle.clone();
+    app_handle
+        .run_on_main_thread(move || {
+            let _ = tx.send(f(&ah));
+        })
+        .map_err(|e| format!("Failed to run input on main thread: {}", e))?;
+    rx.recv()
+        .map_err(|_| "Input was dropped by the main thread".to_string())?
+}
+
+/// Runs `f` with the managed Enigo instance
+fn with_enigo<T>(
+    app_handle: &AppHandle,
+    f: impl FnOnce(&mut Enigo) -> Result<T, String>,
+) -> Result<T, String> {
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+    f(&mut enigo)
+}
+
+/// Splits `text` into the chunks to type and the pause after each
+fn plan_typing(text: &str, typing: &TypingSettings) -> Vec<(String, Duration)> {
+    let chars: Vec<char> = text.chars().collect();
+    let chunk_size = match (typing.chunk_size, typing.chars_per_second) {
+        (0, 0) => chars.len(),
+        // Pace evenly, about ten chunks a second
+        (0, cps) => (cps as usize / 10).max(1),
+        (size, _) => size as usize,
+    }
+    .max(1);
+
+    chars
+        .chunks(chunk_size)
+        .map(|chunk| {
+            let paced = match typing.chars_per_second {
+                0 => Duration::ZERO,
+                cps => Duration::from_secs_f64(chunk.len() as f64 / cps as f64),
+            };
+            let pause = paced.max(Duration::from_millis(typing.chunk_delay_ms as u64));
+            (chunk.iter().collect(), pause)
+        })
+        .collect()
+}
+
+fn type_chunk(enigo: &mut Enigo, text: &str) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     {
         if try_direct_typing_linux(text)? {
@@ -362,8 +689,17 @@ fn paste_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
 }
 
 pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
+    let paste_method = get_settings(&app_handle).paste_method;
+    paste_with_method(text, app_handle, paste_method)
+}
+
+/// Like `paste`, with `paste_method` instead of the configured one
+pub fn paste_with_method(
+    text: String,
+    app_handle: AppHandle,
+    paste_method: PasteMethod,
+) -> Result<(), String> {
     let settings = get_settings(&app_handle);
-    let paste_method = settings.paste_method;
 
     // Append trailing space if setting is enabled
     let text = if settings.append_trailing_space {
@@ -374,35 +710,151 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 
     info!("Using paste method: {:?}", paste_method);
 
-    // Get the managed Enigo instance
-    let enigo_state = app_handle
-        .try_state::<EnigoState>()
-        .ok_or("Enigo state not initialized")?;
-    let mut enigo = enigo_state
-        .0
-        .lock()
-        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+    // Paced typing goes to the paste worker, and so does anything pasted
+    // while it is still busy, so the two never type over each other
+    let paced =
+        paste_method == PasteMethod::Direct && plan_typing(&text, &settings.typing).len() > 1;
+    if paced || QUEUED_PASTES.load(Ordering::SeqCst) > 0 {
+        return queue_paste(QueuedPaste {
+            app_handle,
+            text,
+            paste_method,
+            settings,
+        });
+    }
+
+    send_paste(&app_handle, &text, paste_method, &settings)?;
+    copy_if_configured(&app_handle, &text, &settings)
+}
 
-    // Perform the paste operation
+/// Sends `text` to the focused window in one go
+fn send_paste(
+    app_handle: &AppHandle,
+    text: &str,
+    paste_method: PasteMethod,
+    settings: &AppSettings,
+) -> Result<(), String> {
     match paste_method {
         PasteMethod::None => {
             info!("PasteMethod::None selected - skipping paste action");
+            Ok(())
         }
-        PasteMethod::Direct => {
-            paste_direct(&mut enigo, &text)?;
-        }
+        PasteMethod::Direct => with_enigo(app_handle, |enigo| type_chunk(enigo, text)),
         PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
-            paste_via_clipboard(&mut enigo, &text, &app_handle, &paste_method)?
+            let restore_delay = (settings.clipboard_handling == ClipboardHandling::DontModify)
+                .then(|| Duration::from_millis(settings.clipboard_restore_delay_ms as u64));
+            with_enigo(app_handle, |enigo| {
+                paste_via_clipboard(enigo, text, app_handle, &paste_method, restore_delay)
+            })
         }
     }
+}
 
-    // After pasting, optionally copy to clipboard based on settings
+/// After pasting, optionally copy to clipboard based on settings
+fn copy_if_configured(
+    app_handle: &AppHandle,
+    text: &str,
+    settings: &AppSettings,
+) -> Result<(), String> {
     if settings.clipboard_handling == ClipboardHandling::CopyToClipboard {
         let clipboard = app_handle.clipboard();
         clipboard
-            .write_text(&text)
+            .write_text(text)
             .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typing(chars_per_second: u32, chunk_size: u32, chunk_delay_ms: u32) -> TypingSettings {
+        TypingSettings {
+            chars_per_second,
+            chunk_size,
+            chunk_delay_ms,
+            stop_on_focus_change: true,
+        }
+    }
+
+    #[test]
+    fn plan_typing_defaults_to_one_chunk() {
+        let plan = plan_typing("Hello there", &TypingSettings::default());
+        assert_eq!(plan, vec![("Hello there".to_string(), Duration::ZERO)]);
+    }
+
+    #[test]
+    fn plan_typing_paces_chunks() {
+        let plan = plan_typing("Grüße aus Köln", &typing(0, 5, 200));
+        let chunks: Vec<&str> = plan.iter().map(|(chunk, _)| chunk.as_str()).collect();
+        assert_eq!(chunks, vec!["Grüße", " aus ", "Köln"]);
+        assert!(plan
+            .iter()
+            .all(|(_, pause)| *pause == Duration::from_millis(200)));
+
+        // 100 characters a second in chunks of 10 is 100 ms per chunk
+        let plan = plan_typing(&"a".repeat(25), &typing(100, 0, 0));
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].1, Duration::from_millis(100));
+        assert_eq!(plan[2].1, Duration::from_millis(50));
+    }
+
+    fn image() -> arboard::ImageData<'static> {
+        arboard::ImageData {
+            width: 1,
+            height: 1,
+            bytes: vec![0, 0, 0, 255].into(),
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_files_over_everything_else() {
+        let files = vec![PathBuf::from("/tmp/report.pdf")];
+        let snapshot = ClipboardSnapshot::select(
+            Some(files.clone()),
+            Some("<b>report</b>".into()),
+            Some("report".into()),
+            || panic!("image read although files win"),
+        );
+        assert!(matches!(snapshot, ClipboardSnapshot::Files(f) if f == files));
+    }
+
+    #[test]
+    fn snapshot_keeps_html_with_its_text() {
+        let snapshot = ClipboardSnapshot::select(
+            Some(Vec::new()),
+            Some("<b>hi</b>".into()),
+            Some("hi".into()),
+            || panic!("image read although text is present"),
+        );
+        assert!(matches!(
+            snapshot,
+            ClipboardSnapshot::Html { html, alt_text: Some(text) } if html == "<b>hi</b>" && text == "hi"
+        ));
+
+        let snapshot =
+            ClipboardSnapshot::select(None, Some(String::new()), Some("hi".into()), || {
+                Some(image())
+            });
+        assert!(matches!(snapshot, ClipboardSnapshot::Text(text) if text == "hi"));
+    }
+
+    #[test]
+    fn snapshot_prefers_an_image_without_text() {
+        let snapshot =
+            ClipboardSnapshot::select(None, Some("<img src=\"x\">".into()), None, || Some(image()));
+        assert!(matches!(snapshot, ClipboardSnapshot::Image(i) if i.width == 1));
+
+        let snapshot =
+            ClipboardSnapshot::select(None, Some("<img>".into()), Some(String::new()), || None);
+        assert!(matches!(
+            snapshot,
+            ClipboardSnapshot::Html { alt_text: None, .. }
+        ));
+
+        let snapshot = ClipboardSnapshot::select(None, None, None, || None);
+        assert!(matches!(snapshot, ClipboardSnapshot::Empty));
+    }
+}
@@ -2,10 +2,11 @@ use crate::audio_feedback;
 use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
 use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
 use crate::managers::system_audio::SystemAudioManager;
-use crate::settings::{get_settings, write_settings};
+use crate::settings::{get_settings, update_settings, DeveloperMode};
 use log::warn;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -245,13 +246,11 @@ pub struct AudioDevice {
 #[specta::specta]
 pub fn update_microphone_mode(app: AppHandle, always_on: bool) -> Result<(), String> {
     // Update settings
-    let mut settings = get_settings(&app);
-    settings.always_on_microphone = always_on;
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.always_on_microphone = always_on);
 
-    // Update the audio manager mode
+    // Update the audio manager mode; wake word listening keeps it open too
     let rm = app.state::<Arc<AudioRecordingManager>>();
-    let new_mode = if always_on {
+    let new_mode = if get_settings(&app).keeps_microphone_open() {
         MicrophoneMode::AlwaysOn
     } else {
         MicrophoneMode::OnDemand
@@ -297,13 +296,12 @@ pub fn get_available_microphones() -> Result<Vec<AudioDevice>, String> {
 #[tauri::command]
 #[specta::specta]
 pub fn set_selected_microphone(app: AppHandle, device_name: String) -> Result<(), String> {
-    let mut settings = get_settings(&app);
-    settings.selected_microphone = if device_name == "default" {
+    let selected = if device_name == "default" {
         None
     } else {
         Some(device_name)
     };
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.selected_microphone = selected);
 
     // Update the audio manager to use the new device
     let rm = app.state::<Arc<AudioRecordingManager>>();
@@ -348,13 +346,12 @@ pub fn get_available_output_devices() -> Result<Vec<AudioDevice>, String> {
 #[tauri::command]
 #[specta::specta]
 pub fn set_selected_output_device(app: AppHandle, device_name: String) -> Result<(), String> {
-    let mut settings = get_settings(&app);
-    settings.selected_output_device = if device_name == "default" {
+    let selected = if device_name == "default" {
         None
     } else {
         Some(device_name)
     };
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.selected_output_device = selected);
     Ok(())
 }
 
@@ -384,13 +381,12 @@ pub async fn play_test_sound(app: AppHandle, sound_type: String) {
 #[tauri::command]
 #[specta::specta]
 pub fn set_clamshell_microphone(app: AppHandle, device_name: String) -> Result<(), String> {
-    let mut settings = get_settings(&app);
-    settings.clamshell_microphone = if device_name == "default" {
+    let selected = if device_name == "default" {
         None
     } else {
         Some(device_name)
     };
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.clamshell_microphone = selected);
     Ok(())
 }
 
@@ -409,3 +405,25 @@ pub fn is_recording(app: AppHandle) -> bool {
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.is_recording()
 }
+
+/// Record from an audio file instead of the microphone (developer tooling,
+/// only available with developer mode on). Pass `None` to go back to the
+/// microphone.
+#[tauri::command]
+#[specta::specta]
+pub fn set_simulated_input(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    if path.is_some() && get_settings(&app).developer_mode == DeveloperMode::Off {
+        return Err("Simulated input requires developer mode".to_string());
+    }
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.set_simulated_input(path.map(PathBuf::from))
+        .map_err(|e| format!("Failed to set simulated input: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_simulated_input(app: AppHandle) -> Option<String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.get_simulated_input()
+        .map(|path| path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,228 @@
+//! Export many history and meeting entries in one go.
+//!
+//! Every entry is rendered in the same format and written either as separate
+//! files into a folder or, when the destination ends in `.zip`, as the files
+//! of one zip archive. Entries that fail to render are reported and skipped,
+//! so one bad entry does not lose the rest of the batch.
+
+use anyhow::{anyhow, Result};
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use super::export::{get_export_file_extension, render_transcript, ExportFormat, TranscriptExport};
+use super::meeting_export::{load_meeting_export, render_meeting};
+use crate::managers::history::{HistoryEntry, HistoryManager};
+
+/// An entry to export
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchExportEntry {
+    History { id: i64 },
+    Meeting { meeting_id: String },
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct BatchExportResult {
+    /// Names of the written files, inside the archive when exporting a zip
+    pub files: Vec<String>,
+    /// Entries that could not be exported, with the reason
+    pub errors: Vec<String>,
+}
+
+fn history_transcript(entry: &HistoryEntry) -> TranscriptExport {
+    TranscriptExport {
+        title: Some(entry.title.clone()).filter(|t| !t.trim().is_empty()),
+        source_file: None,
+        duration_ms: entry.duration_ms.map(|ms| ms.max(0) as u64),
+        created_at: entry.timestamp,
+        text: entry
+            .post_processed_text
+            .clone()
+            .unwrap_or_else(|| entry.transcription_text.clone()),
+        segments: None,
+        chapters: None,
+    }
+}
+
+/// Name for an exported file, without characters file systems reject and
+/// numbered when the same name was already used in this batch
+fn unique_file_name(stem: &str, extension: &str, used: &mut HashSet<String>) -> String {
+    let stem: String = stem
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let stem = stem.trim().trim_matches('.');
+    let stem = if stem.is_empty() { "Transcript" } else { stem };
+
+    let mut name = format!("{}.{}", stem, extension);
+    let mut counter = 2;
+    while !used.insert(name.to_lowercase()) {
+        name = format!("{} ({}).{}", stem, counter, extension);
+        counter += 1;
+    }
+    name
+}
+
+fn write_archive(path: &Path, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, bytes) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+fn date_label(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H-%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Render `entry`, returning the file stem and bytes
+async fn render_entry(
+    hm: &HistoryManager,
+    entry: &BatchExportEntry,
+    format: &ExportFormat,
+) -> Result<(String, Vec<u8>)> {
+    match entry {
+        BatchExportEntry::History { id } => {
+            let entry = hm
+                .get_entry_by_id(*id)
+                .await?
+                .ok_or_else(|| anyhow!("History entry not found: {}", id))?;
+            let stem = format!("{} {}", date_label(entry.timestamp), entry.title);
+            Ok((
+                stem,
+                render_transcript(&history_transcript(&entry), format)?,
+            ))
+        }
+        BatchExportEntry::Meeting { meeting_id } => {
+            let meeting = load_meeting_export(hm, meeting_id)?;
+            let stem = format!("{} Meeting", date_label(meeting.started_at));
+            Ok((stem, render_meeting(&meeting, format)?))
+        }
+    }
+}
+
+/// Export several history or meeting entries in one format. `dest` is a
+/// folder for separate files, or a `.zip` file to package them in.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_transcripts_batch(
+    app: AppHandle,
+    entry_ids: Vec<BatchExportEntry>,
+    format: ExportFormat,
+    dest: String,
+) -> Result<BatchExportResult, String> {
+    if dest.is_empty() {
+        return Err("Destination cannot be empty".to_string());
+    }
+    if entry_ids.is_empty() {
+        return Err("No entries to export".to_string());
+    }
+
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let extension = get_export_file_extension(format.clone());
+
+    let mut used = HashSet::new();
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for entry in &entry_ids {
+        match render_entry(&hm, entry, &format).await {
+            Ok((stem, bytes)) => {
+                files.push((unique_file_name(&stem, &extension, &mut used), bytes))
+            }
+            Err(e) => errors.push(e.to_string()),
+        }
+    }
+    if files.is_empty() {
+        return Err(format!("Nothing could be exported: {}", errors.join("; ")));
+    }
+
+    let dest = Path::new(&dest);
+    let is_zip = dest
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"));
+    if is_zip {
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        write_archive(dest, &files)
+            .map_err(|e| format!("Failed to write archive {}: {}", dest.display(), e))?;
+    } else {
+        std::fs::create_dir_all(dest)
+            .map_err(|e| format!("Failed to create directory {}: {}", dest.display(), e))?;
+        for (name, bytes) in &files {
+            let path = dest.join(name);
+            std::fs::write(&path, bytes)
+                .map_err(|e| format!("Failed to write file {}: {}", path.display(), e))?;
+        }
+    }
+
+    Ok(BatchExportResult {
+        files: files.into_iter().map(|(name, _)| name).collect(),
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn batch_files_get_unique_names_in_the_archive() {
+        let mut used = HashSet::new();
+        let names: Vec<String> = ["Call: notes", "call_ notes", " ", "a/b"]
+            .iter()
+            .map(|stem| unique_file_name(stem, "md", &mut used))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Call_ notes.md",
+                "call_ notes (2).md",
+                "Transcript.md",
+                "a_b.md"
+            ]
+        );
+
+        let path = std::env::temp_dir().join(format!("paperflow-batch-{}.zip", std::process::id()));
+        let files = vec![
+            (names[0].clone(), b"first".to_vec()),
+            (names[1].clone(), b"second".to_vec()),
+        ];
+        write_archive(&path, &files).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut text = String::new();
+        archive
+            .by_name("call_ notes (2).md")
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "second");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use crate::managers::diarization::{DiarizationManager, DiarizationModelStatus};
-use crate::settings::{get_settings, write_settings};
+use crate::settings::{get_settings, update_settings};
 use log::warn;
 use serde::Serialize;
 use specta::Type;
@@ -84,9 +84,7 @@ pub fn change_diarization_enabled_setting(app: AppHandle, enabled: bool) -> Resu
         }
     }
 
-    let mut settings = get_settings(&app);
-    settings.diarization_enabled = enabled;
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.diarization_enabled = enabled);
     Ok(())
 }
 
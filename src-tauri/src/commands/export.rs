@@ -2,6 +2,8 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::io::Cursor;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 /// Export format options
 #[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
@@ -15,11 +17,51 @@ pub enum ExportFormat {
     Csv,
     Html,
     Docx,
+    /// OpenDocument text, for LibreOffice
+    Odt,
     Pdf,
+    /// "0:00 Title" lines for a YouTube description
+    YoutubeChapters,
+    /// Podcasting 2.0 chapters JSON
+    PodcastChapters,
+    /// W3C Timed Text Markup Language, for broadcast workflows
+    Ttml,
+    /// Apple iTunes Timed Text, the TTML profile used by iTunes and Final Cut
+    Itt,
+    /// WebVTT with a timestamp per word, so players highlight words as they
+    /// are spoken
+    KaraokeVtt,
+}
+
+/// Version of the JSON export, raised whenever its fields change
+pub const TRANSCRIPT_JSON_VERSION: u32 = 1;
+
+/// The JSON export: a transcript tagged with the schema version it was
+/// written with. Files from before versioning have no `schema_version`.
+#[derive(Serialize, Deserialize)]
+struct TranscriptJson {
+    #[serde(default)]
+    schema_version: u32,
+    #[serde(flatten)]
+    transcript: TranscriptExport,
+}
+
+/// Read back a transcript from the JSON export
+pub fn parse_transcript_json(json: &str) -> Result<TranscriptExport> {
+    let file: TranscriptJson =
+        serde_json::from_str(json).map_err(|e| anyhow!("Not a transcript JSON export: {}", e))?;
+    if file.schema_version > TRANSCRIPT_JSON_VERSION {
+        return Err(anyhow!(
+            "Transcript was exported by a newer version of PaperFlow (schema {}, supported up to {})",
+            file.schema_version,
+            TRANSCRIPT_JSON_VERSION
+        ));
+    }
+    Ok(file.transcript)
 }
 
 /// Segment with timing information for SRT/VTT export
-#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+#[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
 pub struct TranscriptSegment {
     pub start_ms: u64,
     pub end_ms: u64,
@@ -36,6 +78,15 @@ pub struct TranscriptExport {
     pub created_at: i64,
     pub text: String,
     pub segments: Option<Vec<TranscriptSegment>>,
+    #[serde(default)]
+    pub chapters: Option<Vec<TranscriptChapter>>,
+}
+
+/// A chapter of a long transcript, running until the next one starts
+#[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
+pub struct TranscriptChapter {
+    pub title: String,
+    pub start_ms: u64,
 }
 
 /// Format milliseconds to SRT timestamp format: HH:MM:SS,mmm
@@ -64,6 +115,19 @@ fn format_vtt_timestamp(ms: u64) -> String {
     )
 }
 
+/// Format a chapter start as "M:SS", or "H:MM:SS" from an hour on
+fn format_chapter_timestamp(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
 /// Create segments from plain text (when no timing info is available)
 /// Splits text into segments of approximately max_chars_per_segment characters
 fn create_segments_from_text(
@@ -154,6 +218,16 @@ fn export_as_txt(transcript: &TranscriptExport) -> String {
             .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
             .unwrap_or_else(|| "Unknown".to_string())
     ));
+    if let Some(chapters) = transcript.chapters.as_ref().filter(|c| !c.is_empty()) {
+        output.push_str("\nChapters:\n");
+        for chapter in chapters {
+            output.push_str(&format!(
+                "{} {}\n",
+                format_chapter_timestamp(chapter.start_ms),
+                chapter.title
+            ));
+        }
+    }
     output.push_str("\n---\n\n");
 
     // Add transcript text
@@ -213,6 +287,15 @@ fn export_as_srt(transcript: &TranscriptExport) -> String {
 
 /// Export transcript as WebVTT format
 fn export_as_vtt(transcript: &TranscriptExport) -> String {
+    vtt_document(transcript, false)
+}
+
+/// Export transcript as WebVTT with a `<c>` span and timestamp tag per word
+fn export_as_karaoke_vtt(transcript: &TranscriptExport) -> String {
+    vtt_document(transcript, true)
+}
+
+fn vtt_document(transcript: &TranscriptExport, karaoke: bool) -> String {
     let segments = transcript
         .segments
         .clone()
@@ -249,11 +332,17 @@ fn export_as_vtt(transcript: &TranscriptExport) -> String {
             format_vtt_timestamp(segment.end_ms)
         ));
 
+        let text = if karaoke {
+            karaoke_cue_text(segment)
+        } else {
+            segment.text.clone()
+        };
+
         // Text (with optional speaker using <v> tag)
         if let Some(speaker) = &segment.speaker {
-            output.push_str(&format!("<v {}>{}\n", speaker, segment.text));
+            output.push_str(&format!("<v {}>{}\n", speaker, text));
         } else {
-            output.push_str(&format!("{}\n", segment.text));
+            output.push_str(&format!("{}\n", text));
         }
 
         // Blank line between entries
@@ -263,9 +352,52 @@ fn export_as_vtt(transcript: &TranscriptExport) -> String {
     output
 }
 
+/// When each word of a segment starts. Segments only carry their own start
+/// and end, so the span is shared out by word length.
+fn word_start_times(segment: &TranscriptSegment) -> Vec<(u64, &str)> {
+    let words: Vec<&str> = segment.text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|w| w.chars().count() + 1).sum();
+    let span = segment.end_ms.saturating_sub(segment.start_ms);
+
+    let mut chars_before = 0;
+    words
+        .into_iter()
+        .map(|word| {
+            let start = segment.start_ms + span * chars_before as u64 / total_chars.max(1) as u64;
+            chars_before += word.chars().count() + 1;
+            (start, word)
+        })
+        .collect()
+}
+
+/// Cue text with each word in a `<c>` span, preceded by its start time from
+/// the second word on, as WebVTT karaoke expects
+fn karaoke_cue_text(segment: &TranscriptSegment) -> String {
+    word_start_times(segment)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (start_ms, word))| {
+            let word = word
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            if index == 0 {
+                format!("<c>{}</c>", word)
+            } else {
+                format!("<{}><c>{}</c>", format_vtt_timestamp(start_ms), word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Export transcript as JSON
 fn export_as_json(transcript: &TranscriptExport) -> Result<String> {
-    serde_json::to_string_pretty(transcript).map_err(|e| anyhow!("Failed to serialize JSON: {}", e))
+    let file = TranscriptJson {
+        schema_version: TRANSCRIPT_JSON_VERSION,
+        transcript: transcript.clone(),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| anyhow!("Failed to serialize JSON: {}", e))
 }
 
 /// Escape special characters for CSV
@@ -279,7 +411,105 @@ fn csv_escape(text: &str) -> String {
 }
 
 /// Escape special characters for HTML
-fn html_escape(text: &str) -> String {
+/// Colors given to speakers in TTML, in order of appearance
+const TTML_SPEAKER_COLORS: &[&str] = &["white", "yellow", "cyan", "lime", "magenta", "silver"];
+
+/// Export transcript as TTML, or with `itt` as Apple's iTT profile of it.
+/// Each speaker becomes a `ttm:agent` with its own text color.
+fn export_as_ttml(transcript: &TranscriptExport, itt: bool) -> String {
+    let segments = transcript
+        .segments
+        .clone()
+        .unwrap_or_else(|| create_segments_from_text(&transcript.text, transcript.duration_ms, 80));
+
+    let mut speakers: Vec<&str> = Vec::new();
+    for speaker in segments.iter().filter_map(|s| s.speaker.as_deref()) {
+        if !speakers.contains(&speaker) {
+            speakers.push(speaker);
+        }
+    }
+    let speaker_id = |speaker: &str| {
+        speakers
+            .iter()
+            .position(|s| *s == speaker)
+            .map(|index| format!("speaker{}", index + 1))
+    };
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(
+        "<tt xmlns=\"http://www.w3.org/ns/ttml\" xmlns:tts=\"http://www.w3.org/ns/ttml#styling\" \
+xmlns:ttm=\"http://www.w3.org/ns/ttml#metadata\" xmlns:ttp=\"http://www.w3.org/ns/ttml#parameter\"",
+    );
+    if itt {
+        output.push_str(" ttp:timeBase=\"media\" ttp:frameRate=\"30\"");
+    }
+    output.push_str(" xml:lang=\"\">\n  <head>\n    <metadata>\n");
+    if let Some(title) = &transcript.title {
+        output.push_str(&format!(
+            "      <ttm:title>{}</ttm:title>\n",
+            html_escape(title)
+        ));
+    }
+    if let Some(source) = &transcript.source_file {
+        output.push_str(&format!(
+            "      <ttm:desc>{}</ttm:desc>\n",
+            html_escape(source)
+        ));
+    }
+    for (index, speaker) in speakers.iter().enumerate() {
+        output.push_str(&format!(
+            "      <ttm:agent xml:id=\"speaker{}\" type=\"person\"><ttm:name type=\"full\">{}</ttm:name></ttm:agent>\n",
+            index + 1,
+            html_escape(speaker)
+        ));
+    }
+    output.push_str("    </metadata>\n    <styling>\n");
+    output.push_str(
+        "      <style xml:id=\"normal\" tts:fontFamily=\"sansSerif\" tts:fontSize=\"100%\" tts:color=\"white\" tts:textAlign=\"center\"/>\n",
+    );
+    for index in 0..speakers.len() {
+        output.push_str(&format!(
+            "      <style xml:id=\"speaker{}-style\" style=\"normal\" tts:color=\"{}\"/>\n",
+            index + 1,
+            TTML_SPEAKER_COLORS[index % TTML_SPEAKER_COLORS.len()]
+        ));
+    }
+    output.push_str("    </styling>\n    <layout>\n");
+    output.push_str(
+        "      <region xml:id=\"bottom\" tts:origin=\"0% 80%\" tts:extent=\"100% 20%\" tts:displayAlign=\"after\"/>\n",
+    );
+    output.push_str(
+        "    </layout>\n  </head>\n  <body region=\"bottom\" style=\"normal\">\n    <div>\n",
+    );
+
+    for segment in &segments {
+        let timing = format!(
+            "begin=\"{}\" end=\"{}\"",
+            format_vtt_timestamp(segment.start_ms),
+            format_vtt_timestamp(segment.end_ms)
+        );
+        match segment.speaker.as_deref().and_then(speaker_id) {
+            Some(id) => output.push_str(&format!(
+                "      <p {} ttm:agent=\"{}\" style=\"{}-style\"><span tts:fontWeight=\"bold\">{}:</span> {}</p>\n",
+                timing,
+                id,
+                id,
+                html_escape(segment.speaker.as_deref().unwrap_or_default()),
+                html_escape(&segment.text)
+            )),
+            None => output.push_str(&format!(
+                "      <p {}>{}</p>\n",
+                timing,
+                html_escape(&segment.text)
+            )),
+        }
+    }
+
+    output.push_str("    </div>\n  </body>\n</tt>\n");
+    output
+}
+
+pub(super) fn html_escape(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -320,6 +550,19 @@ fn export_as_markdown(transcript: &TranscriptExport) -> String {
     ));
     output.push_str("\n---\n\n");
 
+    // Add chapters as a list of start times
+    if let Some(chapters) = transcript.chapters.as_ref().filter(|c| !c.is_empty()) {
+        output.push_str("## Chapters\n\n");
+        for chapter in chapters {
+            output.push_str(&format!(
+                "- **{}** {}\n",
+                format_chapter_timestamp(chapter.start_ms),
+                chapter.title
+            ));
+        }
+        output.push('\n');
+    }
+
     // Add transcript content
     output.push_str("## Content\n\n");
     if let Some(segments) = &transcript.segments {
@@ -338,6 +581,46 @@ fn export_as_markdown(transcript: &TranscriptExport) -> String {
     output
 }
 
+/// Chapters of a transcript, or an error for the chapter-only formats
+fn require_chapters(transcript: &TranscriptExport) -> Result<&[TranscriptChapter]> {
+    transcript
+        .chapters
+        .as_deref()
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| anyhow!("This transcript has no chapters"))
+}
+
+/// Export chapters as YouTube description timestamps
+fn export_as_youtube_chapters(transcript: &TranscriptExport) -> Result<String> {
+    Ok(require_chapters(transcript)?
+        .iter()
+        .map(|chapter| {
+            format!(
+                "{} {}\n",
+                format_chapter_timestamp(chapter.start_ms),
+                chapter.title
+            )
+        })
+        .collect())
+}
+
+/// Export chapters in the Podcasting 2.0 JSON chapters format
+fn export_as_podcast_chapters(transcript: &TranscriptExport) -> Result<String> {
+    let chapters: Vec<serde_json::Value> = require_chapters(transcript)?
+        .iter()
+        .map(|chapter| {
+            serde_json::json!({
+                "startTime": chapter.start_ms as f64 / 1000.0,
+                "title": chapter.title,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&serde_json::json!({
+        "version": "1.2.0",
+        "chapters": chapters,
+    }))?)
+}
+
 /// Export transcript as CSV
 fn export_as_csv(transcript: &TranscriptExport) -> String {
     let mut output = String::new();
@@ -609,32 +892,172 @@ fn export_as_docx(transcript: &TranscriptExport) -> Result<Vec<u8>> {
     Ok(buffer.into_inner())
 }
 
+/// Export transcript as an OpenDocument text file, styled like the DOCX
+fn export_as_odt(transcript: &TranscriptExport) -> Result<Vec<u8>> {
+    use zip::write::FileOptions;
+    use zip::CompressionMethod;
+
+    let accent_color = "#da5893"; // PaperFlow pink
+    let title_text = transcript.title.as_deref().unwrap_or("Transcript");
+
+    // A paragraph of (text style, text) spans
+    let paragraph = |spans: &[(Option<&str>, &str)]| {
+        let mut para = String::from("<text:p>");
+        for (style, text) in spans {
+            match style {
+                Some(style) => para.push_str(&format!(
+                    "<text:span text:style-name=\"{}\">{}</text:span>",
+                    style,
+                    html_escape(text)
+                )),
+                None => para.push_str(&html_escape(text)),
+            }
+        }
+        para.push_str("</text:p>\n");
+        para
+    };
+
+    let mut body = paragraph(&[(Some("Title"), title_text)]);
+    body.push_str("<text:p/>\n");
+
+    // Metadata section
+    if let Some(source) = &transcript.source_file {
+        body.push_str(&paragraph(&[(Some("Bold"), "Source: "), (None, source)]));
+    }
+    if let Some(duration) = transcript.duration_ms {
+        let seconds = duration / 1000;
+        let duration_text = format!("{}:{:02}", seconds / 60, seconds % 60);
+        body.push_str(&paragraph(&[
+            (Some("Bold"), "Duration: "),
+            (None, &duration_text),
+        ]));
+    }
+    let created_text = chrono::DateTime::from_timestamp(transcript.created_at, 0)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "Unknown".to_string());
+    body.push_str(&paragraph(&[
+        (Some("Bold"), "Created: "),
+        (None, &created_text),
+    ]));
+
+    // Separator
+    body.push_str("<text:p/>\n");
+    body.push_str(&paragraph(&[(
+        None,
+        "───────────────────────────────────────",
+    )]));
+    body.push_str("<text:p/>\n");
+
+    // Content
+    if let Some(segments) = &transcript.segments {
+        for segment in segments {
+            let timestamp = format!("[{}] ", format_vtt_timestamp(segment.start_ms));
+            let speaker = segment
+                .speaker
+                .as_ref()
+                .map(|speaker| format!("{}: ", speaker));
+            let mut spans = vec![(Some("Timestamp"), timestamp.as_str())];
+            if let Some(speaker) = &speaker {
+                spans.push((Some("Speaker"), speaker.as_str()));
+            }
+            spans.push((None, segment.text.as_str()));
+            body.push_str(&paragraph(&spans));
+        }
+    } else {
+        for line in transcript.text.lines() {
+            body.push_str(&paragraph(&[(None, line)]));
+        }
+    }
+
+    // Footer
+    body.push_str("<text:p/>\n");
+    body.push_str(&paragraph(&[(Some("Footer"), "Generated by PaperFlow")]));
+
+    let content = format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" office:version="1.2">
+<office:automatic-styles>
+<style:style style:name="Title" style:family="text"><style:text-properties fo:font-size="24pt" fo:font-weight="bold" fo:color="{accent}"/></style:style>
+<style:style style:name="Bold" style:family="text"><style:text-properties fo:font-weight="bold"/></style:style>
+<style:style style:name="Timestamp" style:family="text"><style:text-properties fo:color="#666666"/></style:style>
+<style:style style:name="Speaker" style:family="text"><style:text-properties fo:font-weight="bold" fo:color="{accent}"/></style:style>
+<style:style style:name="Footer" style:family="text"><style:text-properties fo:font-size="9pt" fo:color="#666666"/></style:style>
+</office:automatic-styles>
+<office:body>
+<office:text>
+{body}</office:text>
+</office:body>
+</office:document-content>
+"##,
+        accent = accent_color,
+        body = body
+    );
+    let meta = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.2">
+<office:meta><dc:title>{}</dc:title><meta:generator>PaperFlow</meta:generator></office:meta>
+</office:document-meta>
+"#,
+        html_escape(title_text)
+    );
+    let manifest = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+<manifest:file-entry manifest:full-path="/" manifest:media-type="application/vnd.oasis.opendocument.text"/>
+<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+<manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#;
+
+    // The mimetype must come first and uncompressed, so tools can sniff it
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut add = |name: &str, options: FileOptions, data: &str| -> Result<()> {
+        zip.start_file(name, options)?;
+        std::io::Write::write_all(&mut zip, data.as_bytes())?;
+        Ok(())
+    };
+    add(
+        "mimetype",
+        stored,
+        "application/vnd.oasis.opendocument.text",
+    )?;
+    add("META-INF/manifest.xml", deflated, manifest)?;
+    add("meta.xml", deflated, &meta)?;
+    add("content.xml", deflated, &content)?;
+
+    let buffer = zip
+        .finish()
+        .map_err(|e| anyhow!("Failed to build ODT: {}", e))?;
+    Ok(buffer.into_inner())
+}
+
+/// Load the fonts PDF export renders with. DejaVu Sans is built into the
+/// app, so PDF export works without any fonts installed.
+pub(super) fn load_pdf_font_family() -> Result<genpdf::fonts::FontFamily<genpdf::fonts::FontData>> {
+    use genpdf::fonts::{FontData, FontFamily};
+
+    let load = |bytes: &[u8]| {
+        FontData::new(bytes.to_vec(), None).map_err(|e| anyhow!("Failed to load PDF font: {}", e))
+    };
+    Ok(FontFamily {
+        regular: load(include_bytes!("../../fonts/DejaVuSans.ttf"))?,
+        bold: load(include_bytes!("../../fonts/DejaVuSans-Bold.ttf"))?,
+        italic: load(include_bytes!("../../fonts/DejaVuSans-Oblique.ttf"))?,
+        bold_italic: load(include_bytes!("../../fonts/DejaVuSans-BoldOblique.ttf"))?,
+    })
+}
+
 /// Export transcript as PDF
-/// Note: Requires Liberation Sans fonts installed on the system, or falls back to simpler output
 fn export_as_pdf(transcript: &TranscriptExport) -> Result<Vec<u8>> {
     use genpdf::elements::{Break, Paragraph as PdfParagraph};
-    use genpdf::fonts;
     use genpdf::style::{Color, Style};
     use genpdf::{Document, Element, SimplePageDecorator};
 
     let accent_color = Color::Rgb(218, 88, 147); // PaperFlow pink
     let gray_color = Color::Rgb(102, 102, 102);
 
-    // Try to load Liberation Sans fonts from common system paths
-    let font_paths = [
-        "/usr/share/fonts/truetype/liberation",
-        "/usr/share/fonts/liberation-sans",
-        "/Library/Fonts",
-        "/System/Library/Fonts",
-        "C:\\Windows\\Fonts",
-    ];
-
-    let font_family = font_paths.iter()
-        .find_map(|path| fonts::from_files(path, "LiberationSans", None).ok())
-        .or_else(|| fonts::from_files("/System/Library/Fonts", "Helvetica", None).ok())
-        .ok_or_else(|| anyhow!("PDF export requires Liberation Sans or Helvetica fonts. Please use HTML or DOCX export instead."))?;
-
-    let mut doc = Document::new(font_family);
+    let mut doc = Document::new(load_pdf_font_family()?);
     doc.set_title(transcript.title.as_deref().unwrap_or("Transcript"));
 
     // Set page decorator for margins
@@ -728,6 +1151,7 @@ pub fn export_transcript(
     source_file: Option<String>,
     duration_ms: Option<u64>,
     segments: Option<Vec<TranscriptSegment>>,
+    chapters: Option<Vec<TranscriptChapter>>,
 ) -> Result<String, String> {
     let transcript = TranscriptExport {
         title,
@@ -736,6 +1160,7 @@ pub fn export_transcript(
         created_at: chrono::Utc::now().timestamp(),
         text,
         segments,
+        chapters,
     };
 
     match format {
@@ -746,8 +1171,17 @@ pub fn export_transcript(
         ExportFormat::Markdown => Ok(export_as_markdown(&transcript)),
         ExportFormat::Csv => Ok(export_as_csv(&transcript)),
         ExportFormat::Html => Ok(export_as_html(&transcript)),
-        ExportFormat::Docx | ExportFormat::Pdf => {
-            Err("Binary formats (DOCX, PDF) must use export_transcript_binary or export_transcript_to_file".to_string())
+        ExportFormat::YoutubeChapters => {
+            export_as_youtube_chapters(&transcript).map_err(|e| e.to_string())
+        }
+        ExportFormat::PodcastChapters => {
+            export_as_podcast_chapters(&transcript).map_err(|e| e.to_string())
+        }
+        ExportFormat::Ttml => Ok(export_as_ttml(&transcript, false)),
+        ExportFormat::Itt => Ok(export_as_ttml(&transcript, true)),
+        ExportFormat::KaraokeVtt => Ok(export_as_karaoke_vtt(&transcript)),
+        ExportFormat::Docx | ExportFormat::Odt | ExportFormat::Pdf => {
+            Err("Binary formats (DOCX, ODT, PDF) must use export_transcript_binary or export_transcript_to_file".to_string())
         }
     }
 }
@@ -755,6 +1189,7 @@ pub fn export_transcript(
 /// Export a transcript and save to file
 #[tauri::command]
 #[specta::specta]
+#[allow(clippy::too_many_arguments)]
 pub fn export_transcript_to_file(
     text: String,
     format: ExportFormat,
@@ -763,6 +1198,7 @@ pub fn export_transcript_to_file(
     source_file: Option<String>,
     duration_ms: Option<u64>,
     segments: Option<Vec<TranscriptSegment>>,
+    chapters: Option<Vec<TranscriptChapter>>,
 ) -> Result<(), String> {
     // Validate file path
     if file_path.is_empty() {
@@ -785,35 +1221,86 @@ pub fn export_transcript_to_file(
         created_at: chrono::Utc::now().timestamp(),
         text,
         segments,
+        chapters,
     };
 
-    // Handle binary formats separately
-    match format {
-        ExportFormat::Docx => {
-            let bytes = export_as_docx(&transcript).map_err(|e| e.to_string())?;
-            std::fs::write(&file_path, bytes)
-                .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
-        }
-        ExportFormat::Pdf => {
-            let bytes = export_as_pdf(&transcript).map_err(|e| e.to_string())?;
-            std::fs::write(&file_path, bytes)
-                .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
-        }
-        _ => {
-            let content = match format {
-                ExportFormat::Txt => export_as_txt(&transcript),
-                ExportFormat::Srt => export_as_srt(&transcript),
-                ExportFormat::Vtt => export_as_vtt(&transcript),
-                ExportFormat::Json => export_as_json(&transcript).map_err(|e| e.to_string())?,
-                ExportFormat::Markdown => export_as_markdown(&transcript),
-                ExportFormat::Csv => export_as_csv(&transcript),
-                ExportFormat::Html => export_as_html(&transcript),
-                ExportFormat::Docx | ExportFormat::Pdf => unreachable!(),
-            };
-            std::fs::write(&file_path, content)
-                .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
-        }
+    let bytes = render_transcript(&transcript, &format).map_err(|e| e.to_string())?;
+    std::fs::write(&file_path, bytes)
+        .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
+}
+
+/// Export a transcript onto the clipboard. HTML goes on as rich text, with a
+/// plain text version for apps that can't take it.
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub fn export_transcript_to_clipboard(
+    app: AppHandle,
+    text: String,
+    format: ExportFormat,
+    title: Option<String>,
+    source_file: Option<String>,
+    duration_ms: Option<u64>,
+    segments: Option<Vec<TranscriptSegment>>,
+    chapters: Option<Vec<TranscriptChapter>>,
+) -> Result<(), String> {
+    let transcript = TranscriptExport {
+        title,
+        source_file,
+        duration_ms,
+        created_at: chrono::Utc::now().timestamp(),
+        text,
+        segments,
+        chapters,
+    };
+
+    let rendered = render_transcript(&transcript, &format).map_err(|e| e.to_string())?;
+    write_to_clipboard(&app, &format, rendered, export_as_txt(&transcript))
+}
+
+/// Put an export on the clipboard, HTML with the `text/html` flavor and
+/// `plain_text` as its fallback, other text formats as plain text
+pub(super) fn write_to_clipboard(
+    app: &AppHandle,
+    format: &ExportFormat,
+    rendered: Vec<u8>,
+    plain_text: String,
+) -> Result<(), String> {
+    if matches!(
+        format,
+        ExportFormat::Docx | ExportFormat::Odt | ExportFormat::Pdf
+    ) {
+        return Err("Binary formats (DOCX, ODT, PDF) can't be copied to the clipboard".to_string());
     }
+    let rendered = String::from_utf8(rendered).map_err(|e| e.to_string())?;
+    let clipboard = app.clipboard();
+    let result = if *format == ExportFormat::Html {
+        clipboard.write_html(rendered, Some(plain_text))
+    } else {
+        clipboard.write_text(rendered)
+    };
+    result.map_err(|e| format!("Failed to write to clipboard: {}", e))
+}
+
+/// Render a transcript in any format, text formats as UTF-8
+pub fn render_transcript(transcript: &TranscriptExport, format: &ExportFormat) -> Result<Vec<u8>> {
+    Ok(match format {
+        ExportFormat::Txt => export_as_txt(transcript).into_bytes(),
+        ExportFormat::Srt => export_as_srt(transcript).into_bytes(),
+        ExportFormat::Vtt => export_as_vtt(transcript).into_bytes(),
+        ExportFormat::Json => export_as_json(transcript)?.into_bytes(),
+        ExportFormat::Markdown => export_as_markdown(transcript).into_bytes(),
+        ExportFormat::Csv => export_as_csv(transcript).into_bytes(),
+        ExportFormat::Html => export_as_html(transcript).into_bytes(),
+        ExportFormat::Docx => export_as_docx(transcript)?,
+        ExportFormat::Odt => export_as_odt(transcript)?,
+        ExportFormat::Pdf => export_as_pdf(transcript)?,
+        ExportFormat::YoutubeChapters => export_as_youtube_chapters(transcript)?.into_bytes(),
+        ExportFormat::PodcastChapters => export_as_podcast_chapters(transcript)?.into_bytes(),
+        ExportFormat::Ttml => export_as_ttml(transcript, false).into_bytes(),
+        ExportFormat::Itt => export_as_ttml(transcript, true).into_bytes(),
+        ExportFormat::KaraokeVtt => export_as_karaoke_vtt(transcript).into_bytes(),
+    })
 }
 
 /// Get the appropriate file extension for a format
@@ -829,7 +1316,13 @@ pub fn get_export_file_extension(format: ExportFormat) -> String {
         ExportFormat::Csv => "csv".to_string(),
         ExportFormat::Html => "html".to_string(),
         ExportFormat::Docx => "docx".to_string(),
+        ExportFormat::Odt => "odt".to_string(),
         ExportFormat::Pdf => "pdf".to_string(),
+        ExportFormat::YoutubeChapters => "chapters.txt".to_string(),
+        ExportFormat::PodcastChapters => "chapters.json".to_string(),
+        ExportFormat::Ttml => "ttml".to_string(),
+        ExportFormat::Itt => "itt".to_string(),
+        ExportFormat::KaraokeVtt => "vtt".to_string(),
     }
 }
 
@@ -846,7 +1339,13 @@ pub fn get_available_export_formats() -> Vec<ExportFormat> {
         ExportFormat::Csv,
         ExportFormat::Html,
         ExportFormat::Docx,
+        ExportFormat::Odt,
         ExportFormat::Pdf,
+        ExportFormat::YoutubeChapters,
+        ExportFormat::PodcastChapters,
+        ExportFormat::Ttml,
+        ExportFormat::Itt,
+        ExportFormat::KaraokeVtt,
     ]
 }
 
@@ -908,6 +1407,7 @@ mod tests {
                 text: "Hello world.".to_string(),
                 speaker: None,
             }]),
+            chapters: None,
         };
 
         let srt = export_as_srt(&transcript);
@@ -925,6 +1425,7 @@ mod tests {
             created_at: 0,
             text: "".to_string(),
             segments: None,
+            chapters: None,
         };
 
         let srt = export_as_srt(&transcript);
@@ -945,6 +1446,7 @@ mod tests {
                 text: "Hello world.".to_string(),
                 speaker: Some("Speaker 1".to_string()),
             }]),
+            chapters: None,
         };
 
         let vtt = export_as_vtt(&transcript);
@@ -962,6 +1464,7 @@ mod tests {
             created_at: 0,
             text: "".to_string(),
             segments: None,
+            chapters: None,
         };
 
         let vtt = export_as_vtt(&transcript);
@@ -979,10 +1482,186 @@ mod tests {
             created_at: 0,
             text: "".to_string(),
             segments: None,
+            chapters: None,
         };
 
         let txt = export_as_txt(&transcript);
         // Should still have created timestamp
         assert!(txt.contains("Created:"));
     }
+
+    #[test]
+    fn test_chapter_exports() {
+        let mut transcript = TranscriptExport {
+            title: None,
+            source_file: None,
+            duration_ms: Some(4_000_000),
+            created_at: 0,
+            text: "".to_string(),
+            segments: None,
+            chapters: Some(vec![
+                TranscriptChapter {
+                    title: "Intro".to_string(),
+                    start_ms: 0,
+                },
+                TranscriptChapter {
+                    title: "Q&A".to_string(),
+                    start_ms: 3_725_500,
+                },
+            ]),
+        };
+
+        assert_eq!(
+            export_as_youtube_chapters(&transcript).unwrap(),
+            "0:00 Intro\n1:02:05 Q&A\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&export_as_podcast_chapters(&transcript).unwrap()).unwrap();
+        assert_eq!(json["version"], "1.2.0");
+        assert_eq!(json["chapters"][1]["startTime"], 3725.5);
+        assert_eq!(json["chapters"][1]["title"], "Q&A");
+
+        transcript.chapters = None;
+        assert!(export_as_youtube_chapters(&transcript).is_err());
+    }
+
+    #[test]
+    fn test_export_as_ttml() {
+        let transcript = TranscriptExport {
+            title: Some("Q&A".to_string()),
+            source_file: None,
+            duration_ms: Some(5000),
+            created_at: 0,
+            text: "".to_string(),
+            segments: Some(vec![
+                TranscriptSegment {
+                    start_ms: 0,
+                    end_ms: 2000,
+                    text: "Is 1 < 2?".to_string(),
+                    speaker: Some("Ann".to_string()),
+                },
+                TranscriptSegment {
+                    start_ms: 2000,
+                    end_ms: 3500,
+                    text: "Yes.".to_string(),
+                    speaker: None,
+                },
+            ]),
+            chapters: None,
+        };
+
+        let ttml = export_as_ttml(&transcript, false);
+        assert!(ttml.contains("<ttm:title>Q&amp;A</ttm:title>"));
+        assert!(ttml.contains("<ttm:name type=\"full\">Ann</ttm:name>"));
+        assert!(ttml.contains(
+            "<p begin=\"00:00:00.000\" end=\"00:00:02.000\" ttm:agent=\"speaker1\" style=\"speaker1-style\"><span tts:fontWeight=\"bold\">Ann:</span> Is 1 &lt; 2?</p>"
+        ));
+        assert!(ttml.contains("<p begin=\"00:00:02.000\" end=\"00:00:03.500\">Yes.</p>"));
+        assert!(!ttml.contains("ttp:timeBase"));
+        assert!(export_as_ttml(&transcript, true).contains("ttp:timeBase=\"media\""));
+    }
+
+    #[test]
+    fn test_export_as_pdf_uses_bundled_fonts() {
+        let transcript = TranscriptExport {
+            title: Some("Notes".to_string()),
+            source_file: None,
+            duration_ms: None,
+            created_at: 0,
+            text: "Works without installed fonts.".to_string(),
+            segments: None,
+            chapters: None,
+        };
+
+        assert!(load_pdf_font_family().is_ok());
+        assert!(export_as_pdf(&transcript).is_ok());
+    }
+
+    #[test]
+    fn test_export_as_karaoke_vtt() {
+        let transcript = TranscriptExport {
+            title: None,
+            source_file: None,
+            duration_ms: Some(2000),
+            created_at: 0,
+            text: "".to_string(),
+            segments: Some(vec![TranscriptSegment {
+                start_ms: 1000,
+                end_ms: 2000,
+                text: "Hi you & me".to_string(),
+                speaker: Some("Ann".to_string()),
+            }]),
+            chapters: None,
+        };
+
+        let vtt = export_as_karaoke_vtt(&transcript);
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("00:00:01.000 --> 00:00:02.000\n"));
+        assert!(vtt.contains(
+            "<v Ann><c>Hi</c> <00:00:01.250><c>you</c> <00:00:01.583><c>&amp;</c> <00:00:01.750><c>me</c>\n"
+        ));
+    }
+
+    #[test]
+    fn test_export_as_odt() {
+        use std::io::Read;
+
+        let transcript = TranscriptExport {
+            title: Some("Notes & ideas".to_string()),
+            source_file: None,
+            duration_ms: Some(2000),
+            created_at: 0,
+            text: "".to_string(),
+            segments: Some(vec![TranscriptSegment {
+                start_ms: 0,
+                end_ms: 2000,
+                text: "Hello <world>".to_string(),
+                speaker: Some("Ann".to_string()),
+            }]),
+            chapters: None,
+        };
+
+        let bytes = export_as_odt(&transcript).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.by_index(0).unwrap().name(), "mimetype");
+
+        let mut content = String::new();
+        archive
+            .by_name("content.xml")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert!(
+            content.contains("<text:span text:style-name=\"Title\">Notes &amp; ideas</text:span>")
+        );
+        assert!(content.contains(
+            "<text:span text:style-name=\"Speaker\">Ann: </text:span>Hello &lt;world&gt;</text:p>"
+        ));
+    }
+
+    #[test]
+    fn test_json_export_round_trips_with_schema_version() {
+        let transcript = TranscriptExport {
+            title: Some("Notes".to_string()),
+            source_file: None,
+            duration_ms: Some(2000),
+            created_at: 1_700_000_000,
+            text: "Hello world".to_string(),
+            segments: None,
+            chapters: None,
+        };
+
+        let json = export_as_json(&transcript).unwrap();
+        assert!(json.contains("\"schema_version\": 1"));
+        let parsed = parse_transcript_json(&json).unwrap();
+        assert_eq!(parsed.title, transcript.title);
+        assert_eq!(parsed.text, transcript.text);
+        assert_eq!(parsed.created_at, transcript.created_at);
+
+        // Exports from before versioning still read, newer ones don't
+        let legacy = r#"{"title": null, "source_file": null, "duration_ms": null, "created_at": 0, "text": "Old", "segments": null}"#;
+        assert_eq!(parse_transcript_json(legacy).unwrap().text, "Old");
+        let newer = json.replace("\"schema_version\": 1", "\"schema_version\": 99");
+        assert!(parse_transcript_json(&newer).is_err());
+    }
 }
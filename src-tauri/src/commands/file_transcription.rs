@@ -1,4 +1,7 @@
-use crate::managers::file_transcription::{FileTranscriptionJob, FileTranscriptionManager};
+use crate::managers::file_transcription::{
+    FileTranscriptionJob, FileTranscriptionManager, FolderQueueResult,
+};
+use crate::managers::transcription::TranscriptionOverrides;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -9,29 +12,64 @@ pub fn get_supported_file_extensions() -> Vec<String> {
     FileTranscriptionManager::get_supported_extensions()
 }
 
-/// Queue a file for transcription
+/// Queue a file for transcription. `overrides` replace the global model,
+/// language, diarization and translation settings for this job only.
 #[tauri::command]
 #[specta::specta]
 pub async fn queue_file_for_transcription(
     _app: AppHandle,
     file_manager: State<'_, Arc<FileTranscriptionManager>>,
     file_path: String,
+    overrides: Option<TranscriptionOverrides>,
 ) -> Result<FileTranscriptionJob, String> {
     file_manager
-        .queue_file(&file_path)
+        .queue_file_with_overrides(&file_path, overrides)
         .map_err(|e| e.to_string())
 }
 
-/// Queue multiple files for transcription
+/// Download the media at a URL (a podcast episode, a YouTube video, ...) and
+/// queue it for transcription once downloaded
+#[tauri::command]
+#[specta::specta]
+pub async fn queue_url_for_transcription(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+    url: String,
+    overrides: Option<TranscriptionOverrides>,
+) -> Result<FileTranscriptionJob, String> {
+    file_manager
+        .queue_url(&url, overrides)
+        .map_err(|e| e.to_string())
+}
+
+/// Queue multiple files for transcription, all with the same overrides
 #[tauri::command]
 #[specta::specta]
 pub async fn queue_files_for_transcription(
     _app: AppHandle,
     file_manager: State<'_, Arc<FileTranscriptionManager>>,
     file_paths: Vec<String>,
+    overrides: Option<TranscriptionOverrides>,
 ) -> Result<Vec<FileTranscriptionJob>, String> {
     file_manager
-        .queue_files(&file_paths)
+        .queue_files(&file_paths, overrides)
+        .map_err(|e| e.to_string())
+}
+
+/// Queue the supported files in a folder, optionally walking its
+/// subfolders and keeping only files that match `glob`
+#[tauri::command]
+#[specta::specta]
+pub async fn queue_folder_for_transcription(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+    path: String,
+    recursive: bool,
+    glob: Option<String>,
+    overrides: Option<TranscriptionOverrides>,
+) -> Result<FolderQueueResult, String> {
+    file_manager
+        .queue_folder(&path, recursive, glob.as_deref(), overrides)
         .map_err(|e| e.to_string())
 }
 
@@ -137,3 +175,68 @@ pub fn is_file_transcription_processing(
 ) -> bool {
     file_manager.is_processing()
 }
+
+/// Move a queued job to `position` among the queued jobs
+#[tauri::command]
+#[specta::specta]
+pub fn move_file_transcription_job(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+    job_id: String,
+    position: u32,
+) -> Result<(), String> {
+    file_manager
+        .move_job(&job_id, position as usize)
+        .map_err(|e| e.to_string())
+}
+
+/// Mark a queued job as high priority so it runs before the normal jobs
+#[tauri::command]
+#[specta::specta]
+pub fn set_file_transcription_job_priority(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+    job_id: String,
+    high_priority: bool,
+) -> Result<(), String> {
+    file_manager
+        .set_job_priority(&job_id, high_priority)
+        .map_err(|e| e.to_string())
+}
+
+/// Pause the queue; running jobs finish but no new ones start
+#[tauri::command]
+#[specta::specta]
+pub fn pause_file_transcription_queue(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+) {
+    file_manager.pause();
+}
+
+/// Resume a paused queue and carry on processing it
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_file_transcription_queue(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+) -> Result<(), String> {
+    file_manager.resume();
+    let manager = file_manager.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = manager.process_all() {
+            log::error!("Error processing files: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// Check if the file transcription queue is paused
+#[tauri::command]
+#[specta::specta]
+pub fn is_file_transcription_queue_paused(
+    _app: AppHandle,
+    file_manager: State<'_, Arc<FileTranscriptionManager>>,
+) -> bool {
+    file_manager.is_paused()
+}
@@ -1,4 +1,14 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::actions::{process_transcription, ProcessedTranscription};
+use crate::commands::export::parse_transcript_json;
+use crate::managers::history::{
+    HistoryEntry, HistoryEntryVersion, HistoryManager, HistoryPage, HistoryQuery, RetentionReport,
+    Waveform,
+};
+use crate::managers::history_sync::{self, HistorySyncManager, SyncReport};
+use crate::managers::transcription::apply_text_processing;
+use crate::recording_protocol;
+use crate::settings::{RecordingFormat, RetentionRule};
+use crate::usage::{self, ApiUsageStats};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -14,6 +24,30 @@ pub async fn get_history_entries(
         .map_err(|e| e.to_string())
 }
 
+/// One page of history for the history view, see `HistoryQuery`
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_page(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: HistoryQuery,
+) -> Result<HistoryPage, String> {
+    history_manager
+        .get_history_page(&query)
+        .map_err(|e| e.to_string())
+}
+
+/// Number of entries matching the filters of `query`
+#[tauri::command]
+#[specta::specta]
+pub async fn count_history_entries(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: HistoryQuery,
+) -> Result<i64, String> {
+    history_manager
+        .count_history_entries(&query)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn toggle_history_entry_saved(
@@ -27,6 +61,8 @@ pub async fn toggle_history_entry_saved(
         .map_err(|e| e.to_string())
 }
 
+/// Address of a recording for the `recording` protocol, which decrypts it
+/// in memory for playback
 #[tauri::command]
 #[specta::specta]
 pub async fn get_audio_file_path(
@@ -34,10 +70,42 @@ pub async fn get_audio_file_path(
     history_manager: State<'_, Arc<HistoryManager>>,
     file_name: String,
 ) -> Result<String, String> {
-    let path = history_manager.get_audio_file_path(&file_name);
-    path.to_str()
-        .ok_or_else(|| "Invalid file path".to_string())
-        .map(|s| s.to_string())
+    if !history_manager.get_audio_file_path(&file_name).exists() {
+        return Err(format!("Recording {} not found", file_name));
+    }
+    Ok(recording_protocol::address(&file_name, false))
+}
+
+/// Address of an entry's recording as WAV, for playing it through the
+/// `recording` protocol
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_playback_path(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<String, String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+    Ok(recording_protocol::address(&entry.file_name, true))
+}
+
+/// Waveform peaks of an entry's recording, merged into `buckets` bars
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_waveform(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    buckets: u32,
+) -> Result<Waveform, String> {
+    // Older entries decode their whole recording the first time
+    let manager = history_manager.inner().clone();
+    tokio::task::spawn_blocking(move || manager.get_waveform(id, buckets as usize))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -53,6 +121,94 @@ pub async fn delete_history_entry(
         .map_err(|e| e.to_string())
 }
 
+/// Run an entry through the current custom words, formatting and
+/// post-processing prompt again. The previous texts are kept as a version.
+#[tauri::command]
+#[specta::specta]
+pub async fn reprocess_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<HistoryEntry, String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+
+    // Start from what the engine heard, if it was recorded
+    let settings = crate::settings::get_settings(&app);
+    let source = entry.raw_text.unwrap_or(entry.transcription_text);
+    // Route by the website it went to, like the original dictation
+    let app_name = entry.app_domain.as_deref().or(entry.app_name.as_deref());
+    let transcription = apply_text_processing(&settings, source, app_name);
+    let ProcessedTranscription {
+        post_processed_text,
+        post_process_prompt,
+        cancelled,
+        ..
+    } = process_transcription(&app, &settings, &transcription, app_name).await;
+    if cancelled {
+        return Err("Reprocessing was cancelled".to_string());
+    }
+
+    history_manager
+        .update_entry_text(id, transcription, post_processed_text, post_process_prompt)
+        .map_err(|e| e.to_string())?;
+
+    history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))
+}
+
+/// Add a transcript from a JSON export, e.g. one made on another machine,
+/// to history. Returns the id of the new entry.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_transcript(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+) -> Result<i64, String> {
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read file {}: {}", path, e))?;
+    let transcript = parse_transcript_json(&json).map_err(|e| e.to_string())?;
+
+    let text = match &transcript.segments {
+        Some(segments) if transcript.text.trim().is_empty() => segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => transcript.text,
+    };
+    if text.trim().is_empty() {
+        return Err("Transcript is empty".to_string());
+    }
+
+    history_manager
+        .import_transcript(
+            transcript.title,
+            text,
+            transcript.created_at,
+            transcript.duration_ms,
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_entry_versions(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<HistoryEntryVersion>, String> {
+    history_manager
+        .get_entry_versions(id)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn update_history_limit(
@@ -60,9 +216,7 @@ pub async fn update_history_limit(
     history_manager: State<'_, Arc<HistoryManager>>,
     limit: usize,
 ) -> Result<(), String> {
-    let mut settings = crate::settings::get_settings(&app);
-    settings.history_limit = limit;
-    crate::settings::write_settings(&app, settings);
+    crate::settings::update_settings(&app, move |s| s.history_limit = limit);
 
     history_manager
         .cleanup_old_entries()
@@ -89,9 +243,9 @@ pub async fn update_recording_retention_period(
         _ => return Err(format!("Invalid retention period: {}", period)),
     };
 
-    let mut settings = crate::settings::get_settings(&app);
-    settings.recording_retention_period = retention_period;
-    crate::settings::write_settings(&app, settings);
+    crate::settings::update_settings(&app, move |s| {
+        s.recording_retention_period = retention_period
+    });
 
     history_manager
         .cleanup_old_entries()
@@ -99,3 +253,153 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_retention_rules(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    rules: Vec<RetentionRule>,
+) -> Result<RetentionReport, String> {
+    let mut ids = std::collections::HashSet::new();
+    for rule in &rules {
+        if rule.id.trim().is_empty() {
+            return Err("Retention rules need an id".to_string());
+        }
+        if !ids.insert(rule.id.as_str()) {
+            return Err(format!("Duplicate retention rule id: {}", rule.id));
+        }
+    }
+
+    crate::settings::update_settings(&app, move |s| s.retention_rules = rules);
+
+    history_manager
+        .apply_retention_rules()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_retention_rules(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<RetentionReport, String> {
+    history_manager
+        .apply_retention_rules()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_recording_format(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    format: RecordingFormat,
+) -> Result<(), String> {
+    crate::settings::update_settings(&app, move |s| s.recording_format = format);
+
+    // Existing WAV recordings are converted when switching to FLAC
+    history_manager.start_recording_migration();
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_history_encryption(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    enabled: bool,
+) -> Result<(), String> {
+    if enabled && crate::settings::get_settings(&app).history_sync_enabled {
+        return Err(format!(
+            "Turn off history sync first. {}",
+            history_sync::ENCRYPTED_HISTORY_ERROR
+        ));
+    }
+
+    // Re-encrypting the database and every recording can take a while
+    let history_manager = history_manager.inner().clone();
+    tokio::task::spawn_blocking(move || history_manager.set_encryption_enabled(enabled))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_history_sync(
+    app: AppHandle,
+    sync_manager: State<'_, Arc<HistorySyncManager>>,
+    enabled: bool,
+    folder: Option<String>,
+) -> Result<(), String> {
+    if enabled {
+        if crate::settings::get_settings(&app).history_encryption_enabled {
+            return Err(history_sync::ENCRYPTED_HISTORY_ERROR.to_string());
+        }
+        match &folder {
+            Some(path) if std::path::Path::new(path).is_dir() => {}
+            Some(path) => return Err(format!("Sync folder does not exist: {}", path)),
+            None => return Err("Choose a sync folder first".to_string()),
+        }
+    }
+
+    let was_enabled = crate::settings::get_settings(&app).history_sync_enabled;
+    crate::settings::update_settings(&app, move |s| {
+        s.history_sync_enabled = enabled;
+        s.history_sync_folder = folder;
+    });
+
+    if enabled && !was_enabled {
+        // Share everything recorded so far, then sync right away
+        let sync_manager = sync_manager.inner().clone();
+        tokio::task::spawn_blocking(move || {
+            sync_manager.queue_full_history()?;
+            sync_manager.sync_now()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_history_now(
+    _app: AppHandle,
+    sync_manager: State<'_, Arc<HistorySyncManager>>,
+) -> Result<SyncReport, String> {
+    let sync_manager = sync_manager.inner().clone();
+    tokio::task::spawn_blocking(move || sync_manager.sync_now())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Cloud API usage and estimated cost per provider and day, for the last
+/// `days` days (30 by default)
+#[tauri::command]
+#[specta::specta]
+pub fn get_api_usage_stats(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    days: Option<u32>,
+) -> Result<Vec<ApiUsageStats>, String> {
+    let since = chrono::Utc::now().timestamp() - i64::from(days.unwrap_or(30)) * 86_400;
+    let conn = history_manager
+        .get_connection()
+        .map_err(|e| e.to_string())?;
+    usage::get_usage_stats(&conn, since).map_err(|e| e.to_string())
+}
+
+/// Forget every cached post-processing reply
+#[tauri::command]
+#[specta::specta]
+pub fn clear_llm_cache(history_manager: State<'_, Arc<HistoryManager>>) -> Result<(), String> {
+    let conn = history_manager
+        .get_connection()
+        .map_err(|e| e.to_string())?;
+    crate::llm_client::cache::clear(&conn).map_err(|e| e.to_string())
+}
@@ -1,9 +1,15 @@
 //! Meeting mode Tauri commands
 
 use crate::managers::history::HistoryManager;
-use crate::managers::meeting::{MeetingManager, MeetingSession, MeetingState};
+use crate::managers::meeting::{
+    task_note, MeetingChunk, MeetingManager, MeetingSession, MeetingState, MeetingTopic,
+};
+use crate::recording_protocol;
+use crate::settings::get_settings;
+use crate::task_providers::TaskBatch;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 
@@ -18,6 +24,7 @@ pub struct MeetingHistoryEntry {
     pub full_transcript: String,
     pub summary: Option<String>,
     pub action_items: Option<Vec<String>>,
+    pub topics: Option<Vec<MeetingTopic>>,
     pub chunk_count: u32,
     pub saved: bool,
 }
@@ -72,6 +79,107 @@ pub fn stop_meeting(app: AppHandle, meeting_id: String) -> Result<(), String> {
     mm.stop_meeting(&meeting_id).map_err(|e| e.to_string())
 }
 
+/// Pause the current meeting
+#[tauri::command]
+#[specta::specta]
+pub fn pause_meeting(app: AppHandle, meeting_id: String) -> Result<(), String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.pause_meeting(&meeting_id).map_err(|e| e.to_string())
+}
+
+/// Resume a paused meeting
+#[tauri::command]
+#[specta::specta]
+pub fn resume_meeting(app: AppHandle, meeting_id: String) -> Result<(), String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.resume_meeting(&meeting_id).map_err(|e| e.to_string())
+}
+
+/// Send a saved meeting's action items to the configured task managers.
+/// Providers that failed are listed in the error.
+#[tauri::command]
+#[specta::specta]
+pub async fn push_meeting_action_items(app: AppHandle, meeting_id: String) -> Result<(), String> {
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let entry = hm
+        .get_meeting_entries()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|entry| entry.meeting_id == meeting_id)
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let titles = entry.action_items.unwrap_or_default();
+    if titles.is_empty() {
+        return Err("This meeting has no action items".to_string());
+    }
+
+    let destinations = get_settings(&app).meeting_task_destinations;
+    if destinations.is_empty() {
+        return Err("No task managers configured".to_string());
+    }
+    let batch = TaskBatch {
+        titles,
+        note: task_note(entry.started_at),
+    };
+    let errors = crate::task_providers::push_to_all(&destinations, &batch).await;
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors
+            .iter()
+            .map(|(provider, e)| format!("{}: {}", provider, e))
+            .collect::<Vec<_>>()
+            .join("; "))
+    }
+}
+
+/// The meeting interrupted by a crash, if any
+#[tauri::command]
+#[specta::specta]
+pub fn get_recoverable_meeting(app: AppHandle) -> Result<Option<MeetingSession>, String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    Ok(mm.get_recoverable_meeting())
+}
+
+/// Continue recording the meeting interrupted by a crash
+#[tauri::command]
+#[specta::specta]
+pub fn resume_recovered_meeting(app: AppHandle, binding_id: String) -> Result<String, String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.resume_recovered_meeting(&binding_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Process the audio the interrupted meeting recorded before the crash
+#[tauri::command]
+#[specta::specta]
+pub fn finalize_recovered_meeting(app: AppHandle) -> Result<String, String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.finalize_recovered_meeting().map_err(|e| e.to_string())
+}
+
+/// Throw away the meeting interrupted by a crash
+#[tauri::command]
+#[specta::specta]
+pub fn discard_recovered_meeting(app: AppHandle) -> Result<(), String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.discard_recovered_meeting();
+    Ok(())
+}
+
 /// Cancel the current meeting without processing
 #[tauri::command]
 #[specta::specta]
@@ -92,6 +200,37 @@ pub fn get_meeting_history(app: AppHandle) -> Result<Vec<MeetingHistoryEntry>, S
     hm.get_meeting_entries().map_err(|e| e.to_string())
 }
 
+/// Address of a meeting chunk's audio as WAV, for playing it through the
+/// `recording` protocol
+#[tauri::command]
+#[specta::specta]
+pub async fn get_meeting_chunk_audio_path(
+    app: AppHandle,
+    meeting_id: String,
+    chunk_id: u32,
+) -> Result<String, String> {
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?
+        .inner()
+        .clone();
+    let file_name = tokio::task::spawn_blocking(move || {
+        let chunk = hm
+            .get_meeting_chunks(&meeting_id)?
+            .into_iter()
+            .find(|chunk| chunk.chunk_id == chunk_id)
+            .ok_or_else(|| anyhow::anyhow!("Meeting chunk {} not found", chunk_id))?;
+        chunk
+            .audio_path
+            .ok_or_else(|| anyhow::anyhow!("No audio was kept for chunk {}", chunk_id))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    Ok(recording_protocol::address(&file_name, true))
+}
+
 /// Delete a meeting from history
 #[tauri::command]
 #[specta::specta]
@@ -101,3 +240,63 @@ pub fn delete_meeting(app: AppHandle, meeting_id: String) -> Result<(), String>
         .ok_or("History manager not initialized")?;
     hm.delete_meeting(&meeting_id).map_err(|e| e.to_string())
 }
+
+/// A speaker told apart in a meeting and the name given to them
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MeetingSpeaker {
+    /// Diarization label, e.g. "Speaker 1"
+    pub label: String,
+    pub name: Option<String>,
+}
+
+/// Get the speakers of a meeting in order of first appearance
+#[tauri::command]
+#[specta::specta]
+pub fn get_meeting_speakers(
+    app: AppHandle,
+    meeting_id: String,
+) -> Result<Vec<MeetingSpeaker>, String> {
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let chunks = hm
+        .get_meeting_chunks(&meeting_id)
+        .map_err(|e| e.to_string())?;
+    let names = hm
+        .get_meeting_speaker_names(&meeting_id)
+        .map_err(|e| e.to_string())?;
+    Ok(meeting_speakers(&chunks, &names))
+}
+
+/// Speakers of the chunks in order of first appearance, with their names
+pub(crate) fn meeting_speakers(
+    chunks: &[MeetingChunk],
+    names: &HashMap<String, String>,
+) -> Vec<MeetingSpeaker> {
+    let mut speakers: Vec<MeetingSpeaker> = Vec::new();
+    for label in chunks.iter().filter_map(|chunk| chunk.speaker.as_ref()) {
+        if !speakers.iter().any(|speaker| &speaker.label == label) {
+            speakers.push(MeetingSpeaker {
+                label: label.clone(),
+                name: names.get(label).cloned(),
+            });
+        }
+    }
+    speakers
+}
+
+/// Name a meeting speaker, or clear their name with an empty string
+#[tauri::command]
+#[specta::specta]
+pub fn rename_meeting_speaker(
+    app: AppHandle,
+    meeting_id: String,
+    label: String,
+    name: String,
+) -> Result<(), String> {
+    let mm = app
+        .try_state::<Arc<MeetingManager>>()
+        .ok_or("Meeting manager not initialized")?;
+    mm.rename_speaker(&meeting_id, &label, &name)
+        .map_err(|e| e.to_string())
+}
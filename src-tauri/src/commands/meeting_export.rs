@@ -0,0 +1,609 @@
+//! Export a whole meeting session: metadata, the chunked transcript with
+//! timestamps and speakers, the summary, the action items and the topics.
+//! Topic headings are also placed in the transcript where each topic starts.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use super::export::{html_escape, load_pdf_font_family, write_to_clipboard, ExportFormat};
+use super::meeting::{meeting_speakers, MeetingHistoryEntry, MeetingSpeaker};
+use crate::managers::history::HistoryManager;
+use crate::managers::meeting::{speaker_display, MeetingChunk, MeetingTopic};
+
+/// One stretch of the meeting transcript
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct MeetingExportSegment {
+    /// Offset from the start of the recording
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Speaker name, or label if unnamed
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct MeetingExport {
+    pub meeting_id: String,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub duration_seconds: i64,
+    pub speakers: Vec<MeetingSpeaker>,
+    pub summary: Option<String>,
+    pub action_items: Vec<String>,
+    pub topics: Vec<MeetingTopic>,
+    pub segments: Vec<MeetingExportSegment>,
+}
+
+fn build_meeting_export(
+    entry: MeetingHistoryEntry,
+    chunks: &[MeetingChunk],
+    names: &HashMap<String, String>,
+) -> MeetingExport {
+    let mut segments: Vec<MeetingExportSegment> = chunks
+        .iter()
+        .filter_map(|chunk| {
+            let text = chunk.transcription.as_deref()?.trim();
+            (!text.is_empty()).then(|| MeetingExportSegment {
+                start_ms: chunk.start_time_ms,
+                end_ms: chunk.end_time_ms,
+                speaker: chunk
+                    .speaker
+                    .as_deref()
+                    .map(|label| speaker_display(label, names).to_string()),
+                text: text.to_string(),
+            })
+        })
+        .collect();
+
+    // Meetings saved before chunks were kept only have the full transcript
+    if segments.is_empty() && !entry.full_transcript.trim().is_empty() {
+        segments.push(MeetingExportSegment {
+            start_ms: 0,
+            end_ms: entry.duration_seconds.max(0) as u64 * 1000,
+            speaker: None,
+            text: entry.full_transcript.trim().to_string(),
+        });
+    }
+
+    MeetingExport {
+        meeting_id: entry.meeting_id,
+        started_at: entry.started_at,
+        ended_at: entry.ended_at,
+        duration_seconds: entry.duration_seconds,
+        speakers: meeting_speakers(chunks, names),
+        summary: entry.summary,
+        action_items: entry.action_items.unwrap_or_default(),
+        topics: entry.topics.unwrap_or_default(),
+        segments,
+    }
+}
+
+/// H:MM:SS
+fn format_clock(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn format_local_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|dt| {
+            dt.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+fn speaker_list(meeting: &MeetingExport) -> String {
+    meeting
+        .speakers
+        .iter()
+        .map(|speaker| {
+            speaker
+                .name
+                .clone()
+                .unwrap_or_else(|| speaker.label.clone())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Metadata lines shared by every format, as (label, value)
+fn metadata(meeting: &MeetingExport) -> Vec<(&'static str, String)> {
+    let mut lines = vec![
+        ("Started", format_local_time(meeting.started_at)),
+        (
+            "Duration",
+            format_clock(meeting.duration_seconds.max(0) as u64),
+        ),
+    ];
+    if !meeting.speakers.is_empty() {
+        lines.push(("Speakers", speaker_list(meeting)));
+    }
+    lines
+}
+
+/// Heading of the topic starting at each segment, if one does
+fn topic_headings(meeting: &MeetingExport) -> Vec<Option<&str>> {
+    let mut topics = meeting.topics.iter().peekable();
+    meeting
+        .segments
+        .iter()
+        .map(|segment| {
+            let mut heading = None;
+            while let Some(topic) = topics.next_if(|topic| topic.start_ms <= segment.start_ms) {
+                heading = Some(topic.title.as_str());
+            }
+            heading
+        })
+        .collect()
+}
+
+/// "[H:MM:SS] Title" line of the topic list
+fn topic_line(topic: &MeetingTopic) -> String {
+    format!("[{}] {}", format_clock(topic.start_ms / 1000), topic.title)
+}
+
+fn title(meeting: &MeetingExport) -> String {
+    format!("Meeting on {}", format_local_time(meeting.started_at))
+}
+
+fn export_as_markdown(meeting: &MeetingExport) -> String {
+    let mut output = format!("# {}\n\n", title(meeting));
+    for (label, value) in metadata(meeting) {
+        output.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+    output.push('\n');
+
+    if let Some(summary) = &meeting.summary {
+        output.push_str(&format!("## Summary\n\n{}\n\n", summary.trim()));
+    }
+
+    if !meeting.action_items.is_empty() {
+        output.push_str("## Action Items\n\n");
+        for item in &meeting.action_items {
+            output.push_str(&format!("- [ ] {}\n", item));
+        }
+        output.push('\n');
+    }
+
+    if !meeting.topics.is_empty() {
+        output.push_str("## Topics\n\n");
+        for topic in &meeting.topics {
+            output.push_str(&format!("- {}\n", topic_line(topic)));
+        }
+        output.push('\n');
+    }
+
+    output.push_str("## Transcript\n\n");
+    for (segment, heading) in meeting.segments.iter().zip(topic_headings(meeting)) {
+        if let Some(heading) = heading {
+            output.push_str(&format!("### {}\n\n", heading));
+        }
+        let clock = format_clock(segment.start_ms / 1000);
+        match &segment.speaker {
+            Some(speaker) => output.push_str(&format!(
+                "**[{}] {}:** {}\n\n",
+                clock, speaker, segment.text
+            )),
+            None => output.push_str(&format!("**[{}]** {}\n\n", clock, segment.text)),
+        }
+    }
+
+    output
+}
+
+/// HTML fragment with inline styles only, so it keeps its formatting when
+/// pasted into an email or a notes app
+fn export_as_html(meeting: &MeetingExport) -> String {
+    let accent_color = "#da5893"; // PaperFlow pink
+    let heading = |text: &str| {
+        format!(
+            "<h2 style=\"color: {};\">{}</h2>\n",
+            accent_color,
+            html_escape(text)
+        )
+    };
+
+    let mut output = format!(
+        "<h1 style=\"color: {};\">{}</h1>\n",
+        accent_color,
+        html_escape(&title(meeting))
+    );
+    for (label, value) in metadata(meeting) {
+        output.push_str(&format!(
+            "<p><strong>{}:</strong> {}</p>\n",
+            label,
+            html_escape(&value)
+        ));
+    }
+
+    if let Some(summary) = &meeting.summary {
+        output.push_str(&heading("Summary"));
+        for line in summary.lines().filter(|line| !line.trim().is_empty()) {
+            output.push_str(&format!("<p>{}</p>\n", html_escape(line.trim())));
+        }
+    }
+
+    if !meeting.action_items.is_empty() {
+        output.push_str(&heading("Action Items"));
+        output.push_str("<ul>\n");
+        for item in &meeting.action_items {
+            output.push_str(&format!("<li>☐ {}</li>\n", html_escape(item)));
+        }
+        output.push_str("</ul>\n");
+    }
+
+    if !meeting.topics.is_empty() {
+        output.push_str(&heading("Topics"));
+        output.push_str("<ul>\n");
+        for topic in &meeting.topics {
+            output.push_str(&format!("<li>{}</li>\n", html_escape(&topic_line(topic))));
+        }
+        output.push_str("</ul>\n");
+    }
+
+    output.push_str(&heading("Transcript"));
+    for (segment, topic) in meeting.segments.iter().zip(topic_headings(meeting)) {
+        if let Some(topic) = topic {
+            output.push_str(&format!("<h3>{}</h3>\n", html_escape(topic)));
+        }
+        output.push_str(&format!(
+            "<p><span style=\"color: #666666;\">[{}]</span> ",
+            format_clock(segment.start_ms / 1000)
+        ));
+        if let Some(speaker) = &segment.speaker {
+            output.push_str(&format!(
+                "<strong style=\"color: {};\">{}:</strong> ",
+                accent_color,
+                html_escape(speaker)
+            ));
+        }
+        output.push_str(&format!("{}</p>\n", html_escape(&segment.text)));
+    }
+
+    output
+}
+
+fn export_as_docx(meeting: &MeetingExport) -> Result<Vec<u8>> {
+    use docx_rs::*;
+
+    let accent_color = "DA5893"; // PaperFlow pink
+    let heading = |text: &str| {
+        Paragraph::new().add_run(
+            Run::new()
+                .add_text(text)
+                .bold()
+                .size(32)
+                .color(accent_color),
+        )
+    };
+
+    let mut docx = Docx::new().add_paragraph(
+        Paragraph::new().add_run(
+            Run::new()
+                .add_text(title(meeting))
+                .bold()
+                .size(48)
+                .color(accent_color),
+        ),
+    );
+    for (label, value) in metadata(meeting) {
+        docx = docx.add_paragraph(
+            Paragraph::new()
+                .add_run(Run::new().add_text(format!("{}: ", label)).bold())
+                .add_run(Run::new().add_text(value)),
+        );
+    }
+
+    if let Some(summary) = &meeting.summary {
+        docx = docx
+            .add_paragraph(Paragraph::new())
+            .add_paragraph(heading("Summary"));
+        for line in summary.lines().filter(|line| !line.trim().is_empty()) {
+            docx = docx.add_paragraph(Paragraph::new().add_run(Run::new().add_text(line.trim())));
+        }
+    }
+
+    if !meeting.action_items.is_empty() {
+        docx = docx
+            .add_paragraph(Paragraph::new())
+            .add_paragraph(heading("Action Items"));
+        for item in &meeting.action_items {
+            docx = docx.add_paragraph(
+                Paragraph::new().add_run(Run::new().add_text(format!("☐ {}", item))),
+            );
+        }
+    }
+
+    if !meeting.topics.is_empty() {
+        docx = docx
+            .add_paragraph(Paragraph::new())
+            .add_paragraph(heading("Topics"));
+        for topic in &meeting.topics {
+            docx = docx
+                .add_paragraph(Paragraph::new().add_run(Run::new().add_text(topic_line(topic))));
+        }
+    }
+
+    docx = docx
+        .add_paragraph(Paragraph::new())
+        .add_paragraph(heading("Transcript"));
+    for (segment, topic) in meeting.segments.iter().zip(topic_headings(meeting)) {
+        if let Some(topic) = topic {
+            docx = docx.add_paragraph(
+                Paragraph::new().add_run(Run::new().add_text(topic).bold().size(26)),
+            );
+        }
+        let mut para = Paragraph::new().add_run(
+            Run::new()
+                .add_text(format!("[{}] ", format_clock(segment.start_ms / 1000)))
+                .color("666666"),
+        );
+        if let Some(speaker) = &segment.speaker {
+            para = para.add_run(
+                Run::new()
+                    .add_text(format!("{}: ", speaker))
+                    .bold()
+                    .color(accent_color),
+            );
+        }
+        docx = docx.add_paragraph(para.add_run(Run::new().add_text(&segment.text)));
+    }
+
+    let mut buffer = Cursor::new(Vec::new());
+    docx.build()
+        .pack(&mut buffer)
+        .map_err(|e| anyhow!("Failed to build DOCX: {}", e))?;
+    Ok(buffer.into_inner())
+}
+
+fn export_as_pdf(meeting: &MeetingExport) -> Result<Vec<u8>> {
+    use genpdf::elements::{Break, Paragraph as PdfParagraph};
+    use genpdf::style::{Color, Style};
+    use genpdf::{Document, Element, SimplePageDecorator};
+
+    let accent_color = Color::Rgb(218, 88, 147); // PaperFlow pink
+    let gray_color = Color::Rgb(102, 102, 102);
+    let heading_style = Style::new()
+        .with_font_size(16)
+        .bold()
+        .with_color(accent_color);
+
+    let mut doc = Document::new(load_pdf_font_family()?);
+    doc.set_title(title(meeting));
+    doc.set_page_decorator(SimplePageDecorator::new());
+
+    doc.push(
+        PdfParagraph::new(title(meeting)).styled(
+            Style::new()
+                .with_font_size(24)
+                .bold()
+                .with_color(accent_color),
+        ),
+    );
+    doc.push(Break::new(1));
+    for (label, value) in metadata(meeting) {
+        let mut para = PdfParagraph::default();
+        para.push_styled(format!("{}: ", label), Style::new().bold());
+        para.push(value);
+        doc.push(para);
+    }
+
+    if let Some(summary) = &meeting.summary {
+        doc.push(Break::new(1));
+        doc.push(PdfParagraph::new("Summary").styled(heading_style));
+        for line in summary.lines().filter(|line| !line.trim().is_empty()) {
+            doc.push(PdfParagraph::new(line.trim()));
+        }
+    }
+
+    if !meeting.action_items.is_empty() {
+        doc.push(Break::new(1));
+        doc.push(PdfParagraph::new("Action Items").styled(heading_style));
+        for item in &meeting.action_items {
+            doc.push(PdfParagraph::new(format!("- {}", item)));
+        }
+    }
+
+    if !meeting.topics.is_empty() {
+        doc.push(Break::new(1));
+        doc.push(PdfParagraph::new("Topics").styled(heading_style));
+        for topic in &meeting.topics {
+            doc.push(PdfParagraph::new(topic_line(topic)));
+        }
+    }
+
+    doc.push(Break::new(1));
+    doc.push(PdfParagraph::new("Transcript").styled(heading_style));
+    for (segment, topic) in meeting.segments.iter().zip(topic_headings(meeting)) {
+        if let Some(topic) = topic {
+            doc.push(PdfParagraph::new(topic).styled(Style::new().with_font_size(13).bold()));
+        }
+        let mut para = PdfParagraph::default();
+        para.push_styled(
+            format!("[{}] ", format_clock(segment.start_ms / 1000)),
+            Style::new().with_color(gray_color),
+        );
+        if let Some(speaker) = &segment.speaker {
+            para.push_styled(
+                format!("{}: ", speaker),
+                Style::new().bold().with_color(accent_color),
+            );
+        }
+        para.push(&segment.text);
+        doc.push(para);
+    }
+
+    let mut buffer = Vec::new();
+    doc.render(&mut buffer)
+        .map_err(|e| anyhow!("Failed to render PDF: {}", e))?;
+    Ok(buffer)
+}
+
+pub(crate) fn render_meeting(meeting: &MeetingExport, format: &ExportFormat) -> Result<Vec<u8>> {
+    match format {
+        ExportFormat::Markdown => Ok(export_as_markdown(meeting).into_bytes()),
+        ExportFormat::Html => Ok(export_as_html(meeting).into_bytes()),
+        ExportFormat::Json => Ok(serde_json::to_vec_pretty(meeting)?),
+        ExportFormat::Docx => export_as_docx(meeting),
+        ExportFormat::Pdf => export_as_pdf(meeting),
+        _ => Err(anyhow!(
+            "Meetings can only be exported as Markdown, HTML, DOCX, PDF or JSON"
+        )),
+    }
+}
+
+/// A saved meeting with its chunks and speaker names, ready to render
+pub(crate) fn load_meeting_export(hm: &HistoryManager, meeting_id: &str) -> Result<MeetingExport> {
+    let entry = hm
+        .get_meeting_entries()?
+        .into_iter()
+        .find(|entry| entry.meeting_id == meeting_id)
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+    let chunks = hm.get_meeting_chunks(meeting_id)?;
+    let names = hm.get_meeting_speaker_names(meeting_id)?;
+    Ok(build_meeting_export(entry, &chunks, &names))
+}
+
+/// Export a saved meeting with its summary, action items, topics and
+/// transcript
+#[tauri::command]
+#[specta::specta]
+pub fn export_meeting(
+    app: AppHandle,
+    meeting_id: String,
+    format: ExportFormat,
+    file_path: String,
+) -> Result<(), String> {
+    if file_path.is_empty() {
+        return Err("File path cannot be empty".to_string());
+    }
+
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let meeting = load_meeting_export(&hm, &meeting_id).map_err(|e| e.to_string())?;
+    let bytes = render_meeting(&meeting, &format).map_err(|e| e.to_string())?;
+    std::fs::write(&file_path, bytes)
+        .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
+}
+
+/// Copy a saved meeting to the clipboard, e.g. as HTML to paste its summary
+/// into an email
+#[tauri::command]
+#[specta::specta]
+pub fn export_meeting_to_clipboard(
+    app: AppHandle,
+    meeting_id: String,
+    format: ExportFormat,
+) -> Result<(), String> {
+    let hm = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let meeting = load_meeting_export(&hm, &meeting_id).map_err(|e| e.to_string())?;
+    let rendered = render_meeting(&meeting, &format).map_err(|e| e.to_string())?;
+    write_to_clipboard(&app, &format, rendered, export_as_markdown(&meeting))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(start_s: u64, speaker: Option<&str>, text: &str) -> MeetingChunk {
+        MeetingChunk {
+            chunk_id: start_s as u32,
+            start_time_ms: start_s * 1000,
+            end_time_ms: (start_s + 30) * 1000,
+            audio_path: None,
+            transcription: Some(text.to_string()),
+            speaker: speaker.map(str::to_string),
+        }
+    }
+
+    fn entry() -> MeetingHistoryEntry {
+        MeetingHistoryEntry {
+            id: 1,
+            meeting_id: "m1".to_string(),
+            started_at: 1_700_000_000,
+            ended_at: 1_700_003_725,
+            duration_seconds: 3_725,
+            full_transcript: "Whole meeting".to_string(),
+            summary: Some("We agreed on the launch date.".to_string()),
+            action_items: Some(vec!["Send the invite".to_string()]),
+            topics: Some(vec![
+                MeetingTopic {
+                    title: "Welcome".to_string(),
+                    start_ms: 0,
+                    end_ms: 3_661_000,
+                },
+                MeetingTopic {
+                    title: "Wrap-up".to_string(),
+                    start_ms: 3_661_000,
+                    end_ms: 3_691_000,
+                },
+            ]),
+            chunk_count: 3,
+            saved: false,
+        }
+    }
+
+    #[test]
+    fn markdown_contains_every_section() {
+        let chunks = [
+            chunk(0, Some("Speaker 1"), "Welcome everyone."),
+            chunk(30, Some("Speaker 2"), " "),
+            chunk(3_661, Some("Speaker 2"), "Thanks."),
+        ];
+        let names = HashMap::from([("Speaker 1".to_string(), "Alice".to_string())]);
+        let meeting = build_meeting_export(entry(), &chunks, &names);
+        assert_eq!(meeting.segments.len(), 2);
+
+        let markdown = export_as_markdown(&meeting);
+        assert!(markdown.contains("- **Duration:** 1:02:05"));
+        assert!(markdown.contains("- **Speakers:** Alice, Speaker 2"));
+        assert!(markdown.contains("## Summary\n\nWe agreed on the launch date."));
+        assert!(markdown.contains("- [ ] Send the invite"));
+        assert!(markdown.contains("## Topics\n\n- [0:00:00] Welcome\n- [1:01:01] Wrap-up\n"));
+        assert!(markdown.contains("### Wrap-up\n\n**[1:01:01] Speaker 2:** Thanks."));
+        assert!(markdown.contains("**[0:00:00] Alice:** Welcome everyone."));
+        assert!(markdown.contains("**[1:01:01] Speaker 2:** Thanks."));
+    }
+
+    #[test]
+    fn html_is_an_escaped_fragment() {
+        let chunks = [chunk(0, Some("Speaker 1"), "Q&A <now>")];
+        let meeting = build_meeting_export(entry(), &chunks, &HashMap::new());
+
+        let html =
+            String::from_utf8(render_meeting(&meeting, &ExportFormat::Html).unwrap()).unwrap();
+        assert!(!html.contains("<html"));
+        assert!(html.contains("<li>☐ Send the invite</li>"));
+        assert!(html.contains("<p>We agreed on the launch date.</p>"));
+        assert!(html.contains("Speaker 1:</strong> Q&amp;A &lt;now&gt;</p>"));
+    }
+
+    #[test]
+    fn meetings_without_chunks_fall_back_to_the_transcript() {
+        let meeting = build_meeting_export(entry(), &[], &HashMap::new());
+        assert_eq!(meeting.segments.len(), 1);
+        assert_eq!(meeting.segments[0].text, "Whole meeting");
+        assert!(meeting.speakers.is_empty());
+
+        assert!(render_meeting(&meeting, &ExportFormat::Srt).is_err());
+        let json = render_meeting(&meeting, &ExportFormat::Json).unwrap();
+        assert!(String::from_utf8(json)
+            .unwrap()
+            .contains("\"action_items\""));
+    }
+}
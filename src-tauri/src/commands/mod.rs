@@ -1,14 +1,19 @@
 pub mod audio;
+pub mod batch_export;
 pub mod diarization;
 pub mod export;
 pub mod file_transcription;
 pub mod history;
 pub mod meeting;
+pub mod meeting_export;
 pub mod models;
+pub mod punctuation;
+pub mod timeline;
 pub mod transcription;
+pub mod wake_word;
 pub mod watch_folder;
 
-use crate::settings::{get_settings, write_settings, AppSettings, LogLevel};
+use crate::settings::{get_settings, update_settings, AppSettings, LogLevel};
 use crate::utils::cancel_current_operation;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_opener::OpenerExt;
@@ -64,9 +69,7 @@ pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
         std::sync::atomic::Ordering::Relaxed,
     );
 
-    let mut settings = get_settings(&app);
-    settings.log_level = level;
-    write_settings(&app, settings);
+    update_settings(&app, move |s| s.log_level = level);
 
     Ok(())
 }
@@ -1,7 +1,9 @@
 use crate::groq_transcription;
-use crate::managers::model::{EngineType, ModelInfo, ModelManager};
+#[cfg(feature = "local-llm")]
+use crate::local_llm::LocalLlmManager;
+use crate::managers::model::{EngineType, ModelInfo, ModelManager, PartialDownload};
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, write_settings};
+use crate::settings::{get_settings, update_settings};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -13,6 +15,15 @@ pub async fn get_available_models(
     Ok(model_manager.get_available_models())
 }
 
+/// GGUF models the embedded llama.cpp engine can post-process with
+#[tauri::command]
+#[specta::specta]
+pub async fn get_local_llm_models(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelInfo>, String> {
+    Ok(model_manager.get_local_llm_models())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_model_info(
@@ -40,6 +51,15 @@ pub async fn delete_model(
     model_manager: State<'_, Arc<ModelManager>>,
     model_id: String,
 ) -> Result<(), String> {
+    #[cfg(feature = "local-llm")]
+    {
+        let is_local_llm = model_manager
+            .get_model_info(&model_id)
+            .is_some_and(|model| model.engine_type == EngineType::Llama);
+        if let Some(manager) = LocalLlmManager::get().filter(|_| is_local_llm) {
+            manager.unload();
+        }
+    }
     model_manager
         .delete_model(&model_id)
         .map_err(|e| e.to_string())
@@ -68,9 +88,8 @@ pub async fn set_active_model(
         .map_err(|e| e.to_string())?;
 
     // Update settings
-    let mut settings = get_settings(&app_handle);
-    settings.selected_model = model_id.clone();
-    write_settings(&app_handle, settings);
+    let selected_model = model_id.clone();
+    update_settings(&app_handle, move |s| s.selected_model = selected_model);
 
     Ok(())
 }
@@ -124,9 +143,29 @@ pub async fn has_any_models_or_downloads(
 pub async fn cancel_download(
     model_manager: State<'_, Arc<ModelManager>>,
     model_id: String,
+    purge: Option<bool>,
+) -> Result<(), String> {
+    model_manager
+        .cancel_download(&model_id, purge.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_interrupted_downloads(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<PartialDownload>, String> {
+    Ok(model_manager.get_partial_downloads())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_partial_download(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
 ) -> Result<(), String> {
     model_manager
-        .cancel_download(&model_id)
+        .delete_partial_download(&model_id)
         .map_err(|e| e.to_string())
 }
 
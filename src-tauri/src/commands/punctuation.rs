@@ -0,0 +1,95 @@
+use crate::managers::punctuation::{PunctuationManager, PunctuationModelStatus};
+use crate::settings::{get_settings, update_settings};
+use log::warn;
+use serde::Serialize;
+use specta::Type;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Punctuation restoration status response
+#[derive(Serialize, Type)]
+pub struct PunctuationStatus {
+    pub available: bool,
+    pub enabled: bool,
+    pub model_downloaded: bool,
+    pub model_size_bytes: u64,
+    pub download_progress: Option<f32>,
+    pub error: Option<String>,
+}
+
+/// Get the current punctuation restoration status
+#[tauri::command]
+#[specta::specta]
+pub fn get_punctuation_status(app: AppHandle) -> PunctuationStatus {
+    let enabled = get_settings(&app).punctuation_restoration_enabled;
+    let (_, model_size_bytes) = PunctuationManager::get_model_info();
+
+    let Some(pm) = app.try_state::<Arc<PunctuationManager>>() else {
+        return PunctuationStatus {
+            available: false,
+            enabled,
+            model_downloaded: false,
+            model_size_bytes,
+            download_progress: None,
+            error: Some("Punctuation manager not initialized".to_string()),
+        };
+    };
+
+    let status = pm.get_status();
+    PunctuationStatus {
+        available: status == PunctuationModelStatus::Ready,
+        enabled,
+        model_downloaded: status == PunctuationModelStatus::Ready,
+        model_size_bytes,
+        download_progress: match status {
+            PunctuationModelStatus::Downloading { progress } => Some(progress),
+            _ => None,
+        },
+        error: match status {
+            PunctuationModelStatus::Error(err) => Some(err),
+            _ => None,
+        },
+    }
+}
+
+/// Enable or disable punctuation restoration
+#[tauri::command]
+#[specta::specta]
+pub fn change_punctuation_restoration_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    // Only allow enabling once the model is downloaded and ready
+    if enabled {
+        let pm = app
+            .try_state::<Arc<PunctuationManager>>()
+            .ok_or_else(|| "Punctuation manager not available".to_string())?;
+        if !pm.is_available() {
+            return Err(
+                "Cannot enable punctuation restoration: model not downloaded or initialization failed"
+                    .to_string(),
+            );
+        }
+    }
+
+    update_settings(&app, move |s| s.punctuation_restoration_enabled = enabled);
+    Ok(())
+}
+
+/// Download the punctuation model
+#[tauri::command]
+#[specta::specta]
+pub async fn download_punctuation_model(app: AppHandle) -> Result<(), String> {
+    let pm = app
+        .try_state::<Arc<PunctuationManager>>()
+        .ok_or_else(|| "Punctuation manager not initialized".to_string())?;
+
+    match pm.get_status() {
+        PunctuationModelStatus::Downloading { .. } => {
+            warn!("Download already in progress, ignoring duplicate request");
+            Ok(())
+        }
+        PunctuationModelStatus::Ready => Ok(()),
+        _ => pm
+            .download_model()
+            .await
+            .map_err(|e| format!("Failed to download model: {}", e)),
+    }
+}
@@ -0,0 +1,294 @@
+//! Daily timeline of dictations and meetings, for time tracking and reviews.
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use serde::Serialize;
+use specta::Type;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use super::export::ExportFormat;
+use super::meeting::MeetingHistoryEntry;
+use crate::managers::history::{HistoryEntry, HistoryManager};
+
+/// Characters of each transcript shown in the Markdown timeline
+const PREVIEW_CHARS: usize = 120;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineItemKind {
+    Dictation,
+    Meeting,
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct TimelineItem {
+    pub kind: TimelineItemKind,
+    /// Unix seconds. Dictations are saved when they finish, so their start
+    /// is derived from the recording length.
+    pub started_at: i64,
+    pub duration_ms: Option<i64>,
+    pub app_name: Option<String>,
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct Timeline {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    pub items: Vec<TimelineItem>,
+    pub total_dictation_ms: i64,
+    pub total_meeting_ms: i64,
+}
+
+/// Start and end of a local calendar day, in unix seconds
+fn day_bounds(date: &str) -> Result<(i64, i64), String> {
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date '{}': {}", date, e))?;
+    let local_midnight = |day: NaiveDate| {
+        Local
+            .from_local_datetime(&day.and_hms_opt(0, 0, 0).expect("midnight exists"))
+            .earliest()
+            .map(|dt| dt.timestamp())
+            .ok_or_else(|| format!("Midnight does not exist on {}", day))
+    };
+    let next = day.succ_opt().ok_or("Date is out of range")?;
+    Ok((local_midnight(day)?, local_midnight(next)?))
+}
+
+fn build_timeline(
+    date: &str,
+    (start, end): (i64, i64),
+    entries: Vec<HistoryEntry>,
+    meetings: Vec<MeetingHistoryEntry>,
+) -> Timeline {
+    let dictations = entries.into_iter().map(|entry| {
+        let duration_ms = entry.duration_ms;
+        TimelineItem {
+            kind: TimelineItemKind::Dictation,
+            started_at: entry.timestamp - duration_ms.unwrap_or(0) / 1000,
+            duration_ms,
+            app_name: entry.app_name,
+            title: entry.title,
+            text: entry
+                .post_processed_text
+                .unwrap_or(entry.transcription_text),
+        }
+    });
+    let meetings = meetings
+        .into_iter()
+        .filter(|meeting| meeting.started_at >= start && meeting.started_at < end)
+        .map(|meeting| TimelineItem {
+            kind: TimelineItemKind::Meeting,
+            started_at: meeting.started_at,
+            duration_ms: Some(meeting.duration_seconds * 1000),
+            app_name: None,
+            title: "Meeting".to_string(),
+            text: meeting.summary.unwrap_or(meeting.full_transcript),
+        });
+
+    let mut items: Vec<TimelineItem> = dictations.chain(meetings).collect();
+    items.sort_by_key(|item| item.started_at);
+
+    let total = |kind: TimelineItemKind| {
+        items
+            .iter()
+            .filter(|item| item.kind == kind)
+            .filter_map(|item| item.duration_ms)
+            .sum()
+    };
+    Timeline {
+        date: date.to_string(),
+        total_dictation_ms: total(TimelineItemKind::Dictation),
+        total_meeting_ms: total(TimelineItemKind::Meeting),
+        items,
+    }
+}
+
+/// H:MM:SS, or M:SS under an hour
+fn format_duration(ms: i64) -> String {
+    let seconds = ms / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > PREVIEW_CHARS {
+        format!("{}…", flat.chars().take(PREVIEW_CHARS).collect::<String>())
+    } else {
+        flat
+    }
+}
+
+fn timeline_as_markdown(timeline: &Timeline) -> String {
+    let count = |kind: TimelineItemKind| timeline.items.iter().filter(|i| i.kind == kind).count();
+
+    let mut output = format!("# Timeline for {}\n\n", timeline.date);
+    output.push_str(&format!(
+        "- **Dictations:** {} ({})\n- **Meetings:** {} ({})\n\n",
+        count(TimelineItemKind::Dictation),
+        format_duration(timeline.total_dictation_ms),
+        count(TimelineItemKind::Meeting),
+        format_duration(timeline.total_meeting_ms),
+    ));
+
+    if timeline.items.is_empty() {
+        output.push_str("Nothing was recorded on this day.\n");
+        return output;
+    }
+
+    output.push_str("| Time | Activity | App | Duration | Text |\n");
+    output.push_str("|------|----------|-----|----------|------|\n");
+    for item in &timeline.items {
+        let time = DateTime::from_timestamp(item.started_at, 0)
+            .map(|dt| dt.with_timezone(&Local).format("%H:%M").to_string())
+            .unwrap_or_default();
+        let activity = match item.kind {
+            TimelineItemKind::Dictation => "Dictation",
+            TimelineItemKind::Meeting => "Meeting",
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            time,
+            activity,
+            item.app_name.as_deref().unwrap_or("—"),
+            item.duration_ms.map(format_duration).unwrap_or_default(),
+            preview(&item.text).replace('|', "\\|"),
+        ));
+    }
+
+    output
+}
+
+fn render_timeline(app: &AppHandle, date: &str, format: &ExportFormat) -> Result<String, String> {
+    if !matches!(format, ExportFormat::Markdown | ExportFormat::Json) {
+        return Err("Timelines can only be exported as Markdown or JSON".to_string());
+    }
+
+    let bounds = day_bounds(date)?;
+    let history = app
+        .try_state::<Arc<HistoryManager>>()
+        .ok_or("History manager not initialized")?;
+    let entries = history
+        .get_entries_between(bounds.0, bounds.1)
+        .map_err(|e| e.to_string())?;
+    let meetings = history.get_meeting_entries().map_err(|e| e.to_string())?;
+    let timeline = build_timeline(date, bounds, entries, meetings);
+
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&timeline).map_err(|e| e.to_string()),
+        _ => Ok(timeline_as_markdown(&timeline)),
+    }
+}
+
+/// Export the dictations and meetings of a local day (YYYY-MM-DD)
+#[tauri::command]
+#[specta::specta]
+pub fn export_timeline(
+    app: AppHandle,
+    date: String,
+    format: ExportFormat,
+) -> Result<String, String> {
+    render_timeline(&app, &date, &format)
+}
+
+/// Export a day's timeline and save it to a file
+#[tauri::command]
+#[specta::specta]
+pub fn export_timeline_to_file(
+    app: AppHandle,
+    date: String,
+    format: ExportFormat,
+    file_path: String,
+) -> Result<(), String> {
+    if file_path.is_empty() {
+        return Err("File path cannot be empty".to_string());
+    }
+    let content = render_timeline(&app, &date, &format)?;
+    std::fs::write(&file_path, content)
+        .map_err(|e| format!("Failed to write file {}: {}", file_path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictation(timestamp: i64, app: &str, duration_ms: i64, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: timestamp,
+            file_name: String::new(),
+            timestamp,
+            saved: false,
+            title: "Recording".to_string(),
+            transcription_text: text.to_string(),
+            raw_text: None,
+            post_processed_text: None,
+            post_process_prompt: None,
+            app_name: Some(app.to_string()),
+            app_domain: None,
+            duration_ms: Some(duration_ms),
+        }
+    }
+
+    fn meeting(started_at: i64, duration_seconds: i64) -> MeetingHistoryEntry {
+        MeetingHistoryEntry {
+            id: started_at,
+            meeting_id: started_at.to_string(),
+            started_at,
+            ended_at: started_at + duration_seconds,
+            duration_seconds,
+            full_transcript: "Long transcript".to_string(),
+            summary: Some("Planning sync".to_string()),
+            action_items: None,
+            topics: None,
+            chunk_count: 1,
+            saved: false,
+        }
+    }
+
+    #[test]
+    fn merges_dictations_and_meetings_of_the_day() {
+        let bounds = (1_000, 100_000);
+        let timeline = build_timeline(
+            "2026-01-05",
+            bounds,
+            vec![
+                dictation(5_030, "Slack", 30_000, "On my way"),
+                dictation(2_010, "Mail", 10_000, "Dear team"),
+            ],
+            vec![meeting(3_000, 1_800), meeting(200_000, 60)],
+        );
+
+        let starts: Vec<i64> = timeline.items.iter().map(|i| i.started_at).collect();
+        assert_eq!(starts, vec![2_000, 3_000, 5_000]);
+        assert_eq!(timeline.items[1].kind, TimelineItemKind::Meeting);
+        assert_eq!(timeline.items[1].text, "Planning sync");
+        assert_eq!(timeline.total_dictation_ms, 40_000);
+        assert_eq!(timeline.total_meeting_ms, 1_800_000);
+
+        let markdown = timeline_as_markdown(&timeline);
+        assert!(markdown.contains("- **Dictations:** 2 (0:40)"));
+        assert!(markdown.contains("- **Meetings:** 1 (30:00)"));
+        assert!(markdown.contains("| Dictation | Slack | 0:30 | On my way |"));
+    }
+
+    #[test]
+    fn formats_durations_and_previews() {
+        assert_eq!(format_duration(59_999), "0:59");
+        assert_eq!(format_duration(3_725_000), "1:02:05");
+        assert_eq!(preview("two\n\nlines"), "two lines");
+        assert!(preview(&"a".repeat(200)).ends_with('…'));
+    }
+
+    #[test]
+    fn day_bounds_span_one_day() {
+        let (start, end) = day_bounds("2026-03-10").unwrap();
+        assert_eq!(end - start, 24 * 60 * 60);
+        assert!(day_bounds("10/03/2026").is_err());
+    }
+}
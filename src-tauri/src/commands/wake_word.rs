@@ -0,0 +1,144 @@
+use crate::managers::wake_word::{WakeWordManager, WakeWordModelStatus};
+use crate::settings::{get_settings, update_settings};
+use log::warn;
+use serde::Serialize;
+use specta::Type;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// Wake word status response
+#[derive(Serialize, Type)]
+pub struct WakeWordStatus {
+    pub available: bool,
+    pub enabled: bool,
+    /// What to say to wake the app; unknown with a custom model
+    pub phrase: Option<String>,
+    pub model_downloaded: bool,
+    pub model_size_bytes: u64,
+    pub download_progress: Option<f32>,
+    pub error: Option<String>,
+}
+
+fn wake_word_manager(app: &AppHandle) -> Result<tauri::State<'_, Arc<WakeWordManager>>, String> {
+    app.try_state::<Arc<WakeWordManager>>()
+        .ok_or_else(|| "Wake word manager not initialized".to_string())
+}
+
+/// Restart listening if it is on, to pick up a changed setting
+fn reapply_if_enabled(app: &AppHandle) -> Result<(), String> {
+    if !get_settings(app).wake_word_enabled {
+        return Ok(());
+    }
+    wake_word_manager(app)?
+        .apply_settings()
+        .map_err(|e| format!("Failed to restart wake word listening: {}", e))
+}
+
+/// Get the current wake word status
+#[tauri::command]
+#[specta::specta]
+pub fn get_wake_word_status(app: AppHandle) -> WakeWordStatus {
+    let settings = get_settings(&app);
+    let enabled = settings.wake_word_enabled;
+    let phrase = settings
+        .wake_word_model_path
+        .is_none()
+        .then(|| WakeWordManager::DEFAULT_PHRASE.to_string());
+    let model_size_bytes = WakeWordManager::get_model_size();
+
+    let Ok(wm) = wake_word_manager(&app) else {
+        return WakeWordStatus {
+            available: false,
+            enabled,
+            phrase,
+            model_downloaded: false,
+            model_size_bytes,
+            download_progress: None,
+            error: Some("Wake word manager not initialized".to_string()),
+        };
+    };
+
+    let status = wm.get_status();
+    WakeWordStatus {
+        available: status == WakeWordModelStatus::Ready,
+        enabled,
+        phrase,
+        model_downloaded: status == WakeWordModelStatus::Ready,
+        model_size_bytes,
+        download_progress: match status {
+            WakeWordModelStatus::Downloading { progress } => Some(progress),
+            _ => None,
+        },
+        error: match status {
+            WakeWordModelStatus::Error(err) => Some(err),
+            _ => None,
+        },
+    }
+}
+
+/// Start or stop listening for the wake word
+#[tauri::command]
+#[specta::specta]
+pub fn change_wake_word_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let wm = wake_word_manager(&app)?;
+    // Only allow enabling once the models are downloaded
+    if enabled && !wm.is_available() {
+        return Err("Cannot enable the wake word: models not downloaded".to_string());
+    }
+
+    update_settings(&app, move |s| s.wake_word_enabled = enabled);
+    if let Err(e) = wm.apply_settings() {
+        // Don't leave the setting on while nothing is listening
+        update_settings(&app, |s| s.wake_word_enabled = false);
+        let _ = wm.apply_settings();
+        return Err(format!("Failed to start wake word listening: {}", e));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_wake_word_sensitivity_setting(
+    app: AppHandle,
+    sensitivity: f32,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&sensitivity) {
+        return Err("Wake word sensitivity must be between 0 and 1".to_string());
+    }
+    update_settings(&app, move |s| s.wake_word_sensitivity = sensitivity);
+    reapply_if_enabled(&app)
+}
+
+/// Use a custom openWakeWord model, or the downloaded one with `None`
+#[tauri::command]
+#[specta::specta]
+pub fn change_wake_word_model_setting(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    if let Some(path) = &path {
+        let model = Path::new(path);
+        if !model.is_file() || model.extension().is_none_or(|ext| ext != "onnx") {
+            return Err(format!("'{}' is not an ONNX model file", path));
+        }
+    }
+    update_settings(&app, move |s| s.wake_word_model_path = path);
+    reapply_if_enabled(&app)
+}
+
+/// Download the wake word models
+#[tauri::command]
+#[specta::specta]
+pub async fn download_wake_word_models(app: AppHandle) -> Result<(), String> {
+    let wm = wake_word_manager(&app)?;
+
+    match wm.get_status() {
+        WakeWordModelStatus::Downloading { .. } => {
+            warn!("Download already in progress, ignoring duplicate request");
+            Ok(())
+        }
+        WakeWordModelStatus::Ready => Ok(()),
+        _ => wm
+            .download_models()
+            .await
+            .map_err(|e| format!("Failed to download models: {}", e)),
+    }
+}
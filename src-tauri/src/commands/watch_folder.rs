@@ -19,9 +19,17 @@ pub fn add_watch_folder(
     manager: State<'_, Arc<WatchFolderManager>>,
     path: String,
     recursive: bool,
+    include_globs: Option<Vec<String>>,
+    exclude_globs: Option<Vec<String>>,
 ) -> Result<WatchFolderConfig, String> {
-    let config =
-        watch_folder::add_watch_folder(&app, path, recursive).map_err(|e| e.to_string())?;
+    let config = watch_folder::add_watch_folder(
+        &app,
+        path,
+        recursive,
+        include_globs.unwrap_or_default(),
+        exclude_globs.unwrap_or_default(),
+    )
+    .map_err(|e| e.to_string())?;
 
     // Start watching if enabled
     if config.enabled {
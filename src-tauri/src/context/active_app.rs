@@ -3,7 +3,11 @@
 //! This module provides functions to detect the currently active (frontmost) application
 //! on the user's system. This is used for:
 //! - Context-aware tone adjustment (formal for email, casual for messaging)
-//! - Developer mode auto-detection (VS Code, terminals, etc.)
+//! - Developer mode auto-detection (VS Code, terminals, GitHub, etc.)
+//! - Noticing video calls, to suggest meeting mode
+//!
+//! In a browser on macOS the domain of the current tab is resolved as well, so
+//! settings routed to apps can be routed to websites like "github.com".
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -16,6 +20,18 @@ pub struct ActiveAppInfo {
     /// The bundle identifier on macOS (e.g., "com.microsoft.VSCode")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bundle_id: Option<String>,
+    /// The domain of the current tab when the app is a browser that reports
+    /// it (e.g., "github.com")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+impl ActiveAppInfo {
+    /// The name tone, prompts, vocabulary and other per-app settings are
+    /// looked up by: the tab's domain in a browser, otherwise the app name
+    pub fn context_name(&self) -> &str {
+        self.domain.as_deref().unwrap_or(&self.name)
+    }
 }
 
 /// Application categories for tone and behavior adjustments
@@ -223,8 +239,163 @@ const BROWSER_APPS: &[&str] = &[
     "chromium",
 ];
 
-/// Categorizes an application based on its name
+/// Websites that belong to an app category, matched on the domain and its
+/// subdomains
+const SITE_CATEGORIES: &[(&str, AppCategory)] = &[
+    ("github.com", AppCategory::Ide),
+    ("gitlab.com", AppCategory::Ide),
+    ("bitbucket.org", AppCategory::Ide),
+    ("stackoverflow.com", AppCategory::Ide),
+    ("codesandbox.io", AppCategory::Ide),
+    ("replit.com", AppCategory::Ide),
+    ("vscode.dev", AppCategory::Ide),
+    ("mail.google.com", AppCategory::Email),
+    ("outlook.live.com", AppCategory::Email),
+    ("outlook.office.com", AppCategory::Email),
+    ("mail.proton.me", AppCategory::Email),
+    ("fastmail.com", AppCategory::Email),
+    ("app.slack.com", AppCategory::Messaging),
+    ("discord.com", AppCategory::Messaging),
+    ("web.whatsapp.com", AppCategory::Messaging),
+    ("web.telegram.org", AppCategory::Messaging),
+    ("teams.microsoft.com", AppCategory::Messaging),
+    ("messenger.com", AppCategory::Messaging),
+    ("zoom.us", AppCategory::Messaging),
+    ("docs.google.com", AppCategory::Documents),
+    ("notion.so", AppCategory::Documents),
+    ("coda.io", AppCategory::Documents),
+    ("quip.com", AppCategory::Documents),
+    ("atlassian.net", AppCategory::Documents),
+];
+
+/// Video call services and what their window titles show only during a
+/// call: every word of an entry appears in the title. Google Meet calls are
+/// told by their meeting code instead, see `has_meet_code`.
+const VIDEO_CALL_TITLES: &[(&str, &[&str])] = &[
+    ("Zoom", &["zoom meeting"]),
+    ("Zoom", &["zoom webinar"]),
+    ("Microsoft Teams", &["microsoft teams", "meeting"]),
+    ("Microsoft Teams", &["microsoft teams", "call with"]),
+    ("Webex", &["webex", "meeting"]),
+];
+
+/// macOS bundle identifiers of video call apps. macOS reports the app
+/// rather than the window, so their front window's title is looked up.
+const VIDEO_CALL_BUNDLES: &[(&str, &str)] = &[
+    ("us.zoom.xos", "Zoom"),
+    ("com.microsoft.teams", "Microsoft Teams"),
+    ("com.microsoft.teams2", "Microsoft Teams"),
+    ("com.cisco.webexmeetingsapp", "Webex"),
+    ("com.webex.meetingmanager", "Webex"),
+];
+
+/// Sites of browser video calls, for browsers that report their domain
+const VIDEO_CALL_DOMAINS: &[(&str, &str)] = &[("meet.google.com", "Google Meet")];
+
+/// The video call service `app` is in a call with, if any. Having a call
+/// app open is not enough; its window has to show a call.
+pub fn detect_video_call(app: &ActiveAppInfo) -> Option<&'static str> {
+    detect_video_call_with(app, front_window_title)
+}
+
+fn detect_video_call_with(
+    app: &ActiveAppInfo,
+    window_title: impl FnOnce() -> Option<String>,
+) -> Option<&'static str> {
+    if let Some(service) = in_call_service(&app.name) {
+        return Some(service);
+    }
+
+    // Where the name is the app's, the call shows in its window's title
+    let by_bundle = app
+        .bundle_id
+        .as_deref()
+        .and_then(|bundle_id| VIDEO_CALL_BUNDLES.iter().find(|(id, _)| *id == bundle_id));
+    let by_domain = app
+        .domain
+        .as_deref()
+        .and_then(|domain| VIDEO_CALL_DOMAINS.iter().find(|(d, _)| *d == domain));
+    let (_, service) = by_bundle.or(by_domain)?;
+    in_call_service(&window_title()?).filter(|found| found == service)
+}
+
+/// The service whose in-call window `title` belongs to
+fn in_call_service(title: &str) -> Option<&'static str> {
+    let title = title.to_lowercase();
+    if title.contains("meet") && has_meet_code(&title) {
+        return Some("Google Meet");
+    }
+    VIDEO_CALL_TITLES
+        .iter()
+        .find(|(_, words)| words.iter().all(|word| title.contains(word)))
+        .map(|(service, _)| *service)
+}
+
+/// Whether `title` holds a Google Meet meeting code like "abc-defg-hij"
+fn has_meet_code(title: &str) -> bool {
+    title
+        .split(|c: char| !(c.is_ascii_lowercase() || c == '-'))
+        .any(|word| word.split('-').map(str::len).eq([3, 4, 3]))
+}
+
+/// Title of the frontmost window
+#[cfg(target_os = "macos")]
+fn front_window_title() -> Option<String> {
+    let script = r#"tell application "System Events" to get name of front window of (first application process whose frontmost is true)"#;
+    let output = std::process::Command::new("osascript")
+        .args(["-e", script])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let title = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
+/// Elsewhere the active app's name already is the window title
+#[cfg(not(target_os = "macos"))]
+fn front_window_title() -> Option<String> {
+    None
+}
+
+/// The domain of an http(s) URL, lowercased and without "www."
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn domain_from_url(url: &str) -> Option<String> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// The category of a website, for names that look like a domain. Unknown
+/// sites are `Browser`, as that is where they are dictated into.
+fn categorize_site(name: &str) -> Option<AppCategory> {
+    if !name.contains('.') || name.contains(char::is_whitespace) {
+        return None;
+    }
+    let domain = name.to_lowercase();
+    let category = SITE_CATEGORIES
+        .iter()
+        .find(|(site, _)| {
+            domain == *site
+                || domain
+                    .strip_suffix(site)
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+        .map(|(_, category)| *category);
+    Some(category.unwrap_or(AppCategory::Browser))
+}
+
+/// Categorizes an application based on its name, or a website on its domain
 pub fn categorize_app(app_name: &str) -> AppCategory {
+    if let Some(category) = categorize_site(app_name) {
+        return category;
+    }
+
     let name_lower = app_name.to_lowercase();
 
     // Check each category
@@ -255,7 +426,8 @@ pub fn categorize_app(app_name: &str) -> AppCategory {
     AppCategory::Other
 }
 
-/// Returns true if the app is an IDE or terminal (developer context)
+/// Returns true if the app is an IDE or terminal, or a code site like
+/// GitHub (developer context)
 pub fn is_developer_context(app_name: &str) -> bool {
     let category = categorize_app(app_name);
     matches!(category, AppCategory::Ide | AppCategory::Terminal)
@@ -310,7 +482,56 @@ pub fn get_active_app() -> Option<ActiveAppInfo> {
             }
         });
 
-    Some(ActiveAppInfo { name, bundle_id })
+    let domain = browser_url(&name).and_then(|url| domain_from_url(&url));
+
+    Some(ActiveAppInfo {
+        name,
+        bundle_id,
+        domain,
+    })
+}
+
+/// The URL of the current tab of `app_name`, for browsers that expose it to
+/// AppleScript. Firefox does not.
+#[cfg(target_os = "macos")]
+fn browser_url(app_name: &str) -> Option<String> {
+    use std::process::Command;
+
+    const SAFARI_BROWSERS: &[&str] = &["Safari", "Safari Technology Preview", "Orion"];
+    const CHROMIUM_BROWSERS: &[&str] = &[
+        "Google Chrome",
+        "Google Chrome Canary",
+        "Chromium",
+        "Brave Browser",
+        "Microsoft Edge",
+        "Vivaldi",
+        "Opera",
+        "Arc",
+    ];
+
+    let script = if SAFARI_BROWSERS.contains(&app_name) {
+        format!(
+            r#"tell application "{}" to get URL of front document"#,
+            app_name
+        )
+    } else if CHROMIUM_BROWSERS.contains(&app_name) {
+        format!(
+            r#"tell application "{}" to get URL of active tab of front window"#,
+            app_name
+        )
+    } else {
+        return None;
+    };
+
+    let output = Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
 }
 
 #[cfg(target_os = "windows")]
@@ -344,10 +565,124 @@ pub fn get_active_app() -> Option<ActiveAppInfo> {
         Some(ActiveAppInfo {
             name,
             bundle_id: None,
+            domain: None,
         })
     }
 }
 
+/// Something that tells the focused window apart from others, to notice
+/// focus moving away and to bring it back with `focus_window`. Unlike the
+/// app name it stays the same while the window's title changes. `None`
+/// where it can't be read, e.g. on Wayland.
+///
+/// On macOS this is the frontmost app's process, not one of its windows.
+#[cfg(target_os = "macos")]
+pub fn focused_window_id() -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            r#"tell application "System Events" to get unix id of first application process whose frontmost is true"#,
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(target_os = "windows")]
+pub fn focused_window_id() -> Option<String> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    Some((hwnd.0 as isize).to_string())
+}
+
+#[cfg(target_os = "linux")]
+pub fn focused_window_id() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .arg("getactivewindow")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!id.is_empty()).then_some(id)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn focused_window_id() -> Option<String> {
+    None
+}
+
+/// Why `focus_window` couldn't bring a window back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusError {
+    /// The window is gone or the system refused
+    Failed,
+    /// macOS denied the Automation permission for System Events
+    AutomationDenied,
+}
+
+/// Bring the window from `focused_window_id` back to the front. Runs a
+/// helper process on some platforms, so keep it off the main thread.
+#[cfg(target_os = "macos")]
+pub fn focus_window(id: &str) -> Result<(), FocusError> {
+    // Only ever a process ID, never anything to splice into the script
+    let Ok(pid) = id.parse::<u32>() else {
+        return Err(FocusError::Failed);
+    };
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            &format!(
+                r#"tell application "System Events" to set frontmost of (first process whose unix id is {}) to true"#,
+                pid
+            ),
+        ])
+        .output()
+        .map_err(|_| FocusError::Failed)?;
+    if output.status.success() {
+        return Ok(());
+    }
+    // errAEEventNotPermitted: the user said no to controlling System Events
+    if String::from_utf8_lossy(&output.stderr).contains("-1743") {
+        Err(FocusError::AutomationDenied)
+    } else {
+        Err(FocusError::Failed)
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn focus_window(id: &str) -> Result<(), FocusError> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
+
+    let Ok(handle) = id.parse::<isize>() else {
+        return Err(FocusError::Failed);
+    };
+    unsafe { SetForegroundWindow(HWND(handle as *mut std::ffi::c_void)).as_bool() }
+        .then_some(())
+        .ok_or(FocusError::Failed)
+}
+
+#[cfg(target_os = "linux")]
+pub fn focus_window(id: &str) -> Result<(), FocusError> {
+    let Ok(window) = id.parse::<u64>() else {
+        return Err(FocusError::Failed);
+    };
+    std::process::Command::new("xdotool")
+        .args(["windowactivate", "--sync", &window.to_string()])
+        .status()
+        .is_ok_and(|status| status.success())
+        .then_some(())
+        .ok_or(FocusError::Failed)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub fn focus_window(_id: &str) -> Result<(), FocusError> {
+    Err(FocusError::Failed)
+}
+
 #[cfg(target_os = "linux")]
 pub fn get_active_app() -> Option<ActiveAppInfo> {
     use std::process::Command;
@@ -363,6 +698,7 @@ pub fn get_active_app() -> Option<ActiveAppInfo> {
                 return Some(ActiveAppInfo {
                     name,
                     bundle_id: None,
+                    domain: None,
                 });
             }
         }
@@ -383,6 +719,7 @@ pub fn get_active_app() -> Option<ActiveAppInfo> {
                             return Some(ActiveAppInfo {
                                 name: parts[3].to_string(),
                                 bundle_id: None,
+                                domain: None,
                             });
                         }
                     }
@@ -456,6 +793,43 @@ mod tests {
         assert_eq!(categorize_app("Some Random App"), AppCategory::Other);
     }
 
+    #[test]
+    fn test_categorize_sites() {
+        assert_eq!(categorize_app("github.com"), AppCategory::Ide);
+        assert_eq!(categorize_app("gist.github.com"), AppCategory::Ide);
+        assert_eq!(categorize_app("mail.google.com"), AppCategory::Email);
+        assert_eq!(categorize_app("acme.atlassian.net"), AppCategory::Documents);
+        assert_eq!(categorize_app("notgithub.com"), AppCategory::Browser);
+        assert_eq!(categorize_app("stripe.com"), AppCategory::Browser);
+        assert!(is_developer_context("github.com"));
+    }
+
+    #[test]
+    fn test_domain_from_url() {
+        assert_eq!(
+            domain_from_url("https://www.GitHub.com/org/repo?tab=1"),
+            Some("github.com".to_string())
+        );
+        assert_eq!(
+            domain_from_url("http://user@localhost:3000/#x"),
+            Some("localhost".to_string())
+        );
+        assert_eq!(domain_from_url("file:///Users/me/a.html"), None);
+        assert_eq!(domain_from_url("about:blank"), None);
+    }
+
+    #[test]
+    fn test_context_name_prefers_domain() {
+        let mut app = ActiveAppInfo {
+            name: "Safari".to_string(),
+            bundle_id: None,
+            domain: None,
+        };
+        assert_eq!(app.context_name(), "Safari");
+        app.domain = Some("github.com".to_string());
+        assert_eq!(app.context_name(), "github.com");
+    }
+
     #[test]
     fn test_is_developer_context() {
         assert!(is_developer_context("Visual Studio Code"));
@@ -472,4 +846,64 @@ mod tests {
         assert_eq!(AppCategory::Ide.default_tone(), ToneStyle::Technical);
         assert_eq!(AppCategory::Other.default_tone(), ToneStyle::Neutral);
     }
+
+    #[test]
+    fn test_detect_video_calls() {
+        let app = |name: &str, bundle_id: Option<&str>| ActiveAppInfo {
+            name: name.to_string(),
+            bundle_id: bundle_id.map(str::to_string),
+            domain: None,
+        };
+        let detect = |name: &str| detect_video_call_with(&app(name, None), || None);
+        assert_eq!(detect("Zoom Meeting"), Some("Zoom"));
+        assert_eq!(
+            detect("Meeting with Ann | Microsoft Teams"),
+            Some("Microsoft Teams")
+        );
+        assert_eq!(
+            detect("Meet - abc-defg-hij - Google Chrome"),
+            Some("Google Meet")
+        );
+        assert_eq!(detect("Preview"), None);
+
+        // Open but not in a call
+        assert_eq!(detect("Zoom Workplace"), None);
+        assert_eq!(detect("Chat | Weekly sync | Microsoft Teams"), None);
+        assert_eq!(detect("Meet - Google Chrome"), None);
+        assert_eq!(detect("Huddle notes - Google Docs"), None);
+        assert_eq!(detect("Skype"), None);
+    }
+
+    #[test]
+    fn test_detect_video_calls_by_window_title() {
+        let zoom = ActiveAppInfo {
+            name: "zoom.us".to_string(),
+            bundle_id: Some("us.zoom.xos".to_string()),
+            domain: None,
+        };
+        assert_eq!(
+            detect_video_call_with(&zoom, || Some("Zoom Meeting".to_string())),
+            Some("Zoom")
+        );
+        assert_eq!(
+            detect_video_call_with(&zoom, || Some("Zoom Workplace".to_string())),
+            None
+        );
+        assert_eq!(detect_video_call_with(&zoom, || None), None);
+
+        let meet = ActiveAppInfo {
+            name: "Google Chrome".to_string(),
+            bundle_id: Some("com.google.Chrome".to_string()),
+            domain: Some("meet.google.com".to_string()),
+        };
+        assert_eq!(
+            detect_video_call_with(&meet, || Some("Meet - abc-defg-hij".to_string())),
+            Some("Google Meet")
+        );
+        // A Meet title in another app's window doesn't count for that app
+        assert_eq!(
+            detect_video_call_with(&zoom, || Some("Meet - abc-defg-hij".to_string())),
+            None
+        );
+    }
 }
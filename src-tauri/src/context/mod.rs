@@ -1,14 +1,18 @@
 //! Context-awareness features for improved transcription.
 //!
 //! This module provides functionality to understand the user's context:
-//! - Active application detection
+//! - Active application detection, and the domain of the current browser tab
 //! - Developer mode detection
-//! - Window context reading (optional, privacy-sensitive)
+//! - Window context reading and screen OCR (optional, privacy-sensitive)
 
 pub mod active_app;
+pub mod screen_ocr;
 pub mod window_context;
 
 pub use active_app::{
-    categorize_app, get_active_app, is_developer_context, ActiveAppInfo, AppCategory, ToneStyle,
+    categorize_app, detect_video_call, get_active_app, is_developer_context, ActiveAppInfo,
+    AppCategory, ToneStyle,
+};
+pub use window_context::{
+    extract_context_terms, get_text_before_cursor, get_window_context, whisper_prompt,
 };
-pub use window_context::{extract_context_terms, get_window_context, WindowContext};
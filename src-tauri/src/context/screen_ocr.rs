@@ -0,0 +1,209 @@
+//! Text recognition over the active window, to pick up names and jargon
+//! shown on screen while dictating.
+//!
+//! Like window context reading this is opt-in and local: the screenshot is
+//! recognized with the system's OCR (Vision on macOS, Windows.Media.Ocr on
+//! Windows), deleted right after, and only the terms found are kept.
+
+use super::active_app::ActiveAppInfo;
+use super::window_context::{extract_context_terms, WindowContext};
+use crate::settings::AppSettings;
+use log::debug;
+use once_cell::sync::Lazy;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long the transcription waits for a capture that is still running
+const OCR_WAIT: Duration = Duration::from_secs(2);
+
+/// Terms from the capture started with the current recording
+static PENDING: Lazy<Mutex<Option<Receiver<Vec<String>>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts recognizing the window of `app` in the background, if screen OCR
+/// is allowed for it. Call when a recording starts; `take_terms` collects the
+/// result.
+pub fn start_capture(settings: AppSettings, app: ActiveAppInfo) {
+    let (sender, receiver) = mpsc::channel();
+    if let Ok(mut pending) = PENDING.lock() {
+        *pending = Some(receiver);
+    }
+
+    std::thread::spawn(move || {
+        if !settings.captures_screen_in(app.context_name()) {
+            return;
+        }
+        let Some(screen_text) = recognize_active_window() else {
+            return;
+        };
+        let terms = extract_context_terms(&WindowContext {
+            app_name: app.name,
+            selected_text: None,
+            nearby_text: None,
+            screen_text: Some(screen_text),
+        });
+        debug!("Found {} terms on screen", terms.len());
+        let _ = sender.send(terms);
+    });
+}
+
+/// The terms found by the last capture, waiting briefly for it to finish.
+/// Empty when no capture ran.
+pub fn take_terms() -> Vec<String> {
+    let receiver = PENDING.lock().ok().and_then(|mut pending| pending.take());
+    receiver
+        .and_then(|receiver| receiver.recv_timeout(OCR_WAIT).ok())
+        .unwrap_or_default()
+}
+
+/// A temporary file for the screenshot
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn screenshot_path() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("paperflow-ocr-{}.png", std::process::id()))
+}
+
+/// Recognizes the text in the front window. Needs the screen recording
+/// permission.
+#[cfg(target_os = "macos")]
+pub fn recognize_active_window() -> Option<String> {
+    use std::process::Command;
+
+    let bounds = Command::new("osascript")
+        .args([
+            "-e",
+            r#"
+            tell application "System Events"
+                set frontApp to first application process whose frontmost is true
+                tell front window of frontApp
+                    set {x, y} to position
+                    set {w, h} to size
+                end tell
+            end tell
+            return (x as text) & "," & (y as text) & "," & (w as text) & "," & (h as text)
+            "#,
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let bounds = String::from_utf8(bounds.stdout).ok()?.trim().to_string();
+
+    let path = screenshot_path();
+    let captured = Command::new("screencapture")
+        .args(["-x", "-R", &bounds])
+        .arg(&path)
+        .status()
+        .is_ok_and(|status| status.success());
+    if !captured {
+        return None;
+    }
+
+    // Vision through the JavaScript for Automation bridge, accurate level
+    let output = Command::new("osascript")
+        .args([
+            "-l",
+            "JavaScript",
+            "-e",
+            r#"
+            ObjC.import('Vision');
+            function run(argv) {
+                const url = $.NSURL.fileURLWithPath(argv[0]);
+                const request = $.VNRecognizeTextRequest.alloc.init;
+                request.recognitionLevel = 0;
+                const handler = $.VNImageRequestHandler.alloc.initWithURLOptions(url, $({}));
+                if (!handler.performRequestsError($([request]), null)) {
+                    return '';
+                }
+                const lines = [];
+                const results = request.results;
+                for (let i = 0; i < results.count; i++) {
+                    const candidates = results.objectAtIndex(i).topCandidates(1);
+                    if (candidates.count > 0) {
+                        lines.push(candidates.objectAtIndex(0).string.js);
+                    }
+                }
+                return lines.join('\n');
+            }
+            "#,
+        ])
+        .arg(&path)
+        .output();
+    let _ = std::fs::remove_file(&path);
+
+    let output = output.ok().filter(|output| output.status.success())?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(target_os = "windows")]
+pub fn recognize_active_window() -> Option<String> {
+    use std::process::Command;
+
+    let path = screenshot_path();
+    let script = r#"
+        Add-Type -AssemblyName System.Drawing
+        Add-Type -AssemblyName System.Runtime.WindowsRuntime
+        Add-Type @"
+            using System;
+            using System.Runtime.InteropServices;
+            public class ForegroundWindow {
+                [StructLayout(LayoutKind.Sequential)]
+                public struct RECT { public int Left, Top, Right, Bottom; }
+                [DllImport("user32.dll")]
+                public static extern IntPtr GetForegroundWindow();
+                [DllImport("user32.dll")]
+                public static extern bool GetWindowRect(IntPtr hWnd, out RECT rect);
+            }
+"@
+        [Console]::OutputEncoding = [System.Text.Encoding]::UTF8
+        $rect = New-Object ForegroundWindow+RECT
+        if (-not [ForegroundWindow]::GetWindowRect([ForegroundWindow]::GetForegroundWindow(), [ref]$rect)) { exit 1 }
+        $width = $rect.Right - $rect.Left
+        $height = $rect.Bottom - $rect.Top
+        if ($width -le 0 -or $height -le 0) { exit 1 }
+        $bitmap = New-Object System.Drawing.Bitmap $width, $height
+        $graphics = [System.Drawing.Graphics]::FromImage($bitmap)
+        $graphics.CopyFromScreen($rect.Left, $rect.Top, 0, 0, $bitmap.Size)
+        $bitmap.Save($env:PAPERFLOW_OCR_PATH, [System.Drawing.Imaging.ImageFormat]::Png)
+        $graphics.Dispose()
+        $bitmap.Dispose()
+
+        $asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object {
+            $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and
+            $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1'
+        })[0]
+        function Await($operation, $type) {
+            $task = $asTask.MakeGenericMethod($type).Invoke($null, @($operation))
+            $task.Wait(-1) | Out-Null
+            $task.Result
+        }
+        [Windows.Storage.StorageFile, Windows.Storage, ContentType = WindowsRuntime] | Out-Null
+        [Windows.Media.Ocr.OcrEngine, Windows.Foundation, ContentType = WindowsRuntime] | Out-Null
+        [Windows.Graphics.Imaging.BitmapDecoder, Windows.Graphics, ContentType = WindowsRuntime] | Out-Null
+
+        $file = Await ([Windows.Storage.StorageFile]::GetFileFromPathAsync($env:PAPERFLOW_OCR_PATH)) ([Windows.Storage.StorageFile])
+        $stream = Await ($file.OpenAsync([Windows.Storage.FileAccessMode]::Read)) ([Windows.Storage.Streams.IRandomAccessStream])
+        $decoder = Await ([Windows.Graphics.Imaging.BitmapDecoder]::CreateAsync($stream)) ([Windows.Graphics.Imaging.BitmapDecoder])
+        $image = Await ($decoder.GetSoftwareBitmapAsync()) ([Windows.Graphics.Imaging.SoftwareBitmap])
+        $engine = [Windows.Media.Ocr.OcrEngine]::TryCreateFromUserProfileLanguages()
+        if ($engine -eq $null) { exit 1 }
+        $result = Await ($engine.RecognizeAsync($image)) ([Windows.Media.Ocr.OcrResult])
+        $stream.Dispose()
+        $result.Lines | ForEach-Object { $_.Text }
+    "#;
+
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .env("PAPERFLOW_OCR_PATH", &path)
+        .output();
+    let _ = std::fs::remove_file(&path);
+
+    let output = output.ok().filter(|output| output.status.success())?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    (!text.trim().is_empty()).then_some(text)
+}
+
+// No system OCR to call on Linux
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn recognize_active_window() -> Option<String> {
+    None
+}
@@ -6,11 +6,23 @@
 //! IMPORTANT: This feature is privacy-sensitive and should be:
 //! - Disabled by default
 //! - Require explicit user opt-in
-//! - Process data locally only (never send to cloud)
+//! - Process data locally only (never send to cloud), unless the user
+//!   separately allows it in the LLM prompt
 //! - Provide clear user notification when active
 
 use serde::{Deserialize, Serialize};
 
+/// Most text before the cursor kept as context for the LLM
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+pub const MAX_SURROUNDING_CHARS: usize = 1000;
+
+/// Most text before the cursor given to Whisper as its prompt, which it
+/// limits to about 224 tokens
+pub const MAX_WHISPER_PROMPT_CHARS: usize = 400;
+
+/// Most context terms kept, so a busy screen doesn't crowd out the rest
+const MAX_CONTEXT_TERMS: usize = 50;
+
 /// Context information from the active window.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowContext {
@@ -20,6 +32,8 @@ pub struct WindowContext {
     pub selected_text: Option<String>,
     /// Text near the cursor/selection (surrounding paragraph)
     pub nearby_text: Option<String>,
+    /// Text recognized on screen in the active window (if screen OCR is on)
+    pub screen_text: Option<String>,
 }
 
 /// Gets context from the active window.
@@ -93,6 +107,7 @@ pub fn get_window_context() -> Option<WindowContext> {
         app_name,
         selected_text,
         nearby_text: None, // Full nearby text reading requires more complex accessibility API usage
+        screen_text: None,
     })
 }
 
@@ -161,6 +176,7 @@ pub fn get_window_context() -> Option<WindowContext> {
         app_name,
         selected_text: None,
         nearby_text: None,
+        screen_text: None,
     })
 }
 
@@ -209,6 +225,7 @@ pub fn get_window_context() -> Option<WindowContext> {
         app_name,
         selected_text: None, // Getting selected text on Linux requires xclip or similar
         nearby_text: None,
+        screen_text: None,
     })
 }
 
@@ -217,6 +234,159 @@ pub fn get_window_context() -> Option<WindowContext> {
     None
 }
 
+/// Reads the text before the cursor in the focused text field, so a
+/// dictation that continues a sentence can be transcribed to match it.
+///
+/// Returns None when the focused element is not a text field, it is empty,
+/// or accessibility permissions are missing.
+#[cfg(target_os = "macos")]
+pub fn get_text_before_cursor() -> Option<String> {
+    use std::process::Command;
+
+    // The first line is where the selection starts, counted from 1, and the
+    // rest is the field's text
+    let output = Command::new("osascript")
+        .args([
+            "-e",
+            r#"
+            tell application "System Events"
+                set frontApp to first application process whose frontmost is true
+                tell frontApp
+                    set focusedElement to value of attribute "AXFocusedUIElement"
+                    set fieldText to value of attribute "AXValue" of focusedElement
+                    set cursorStart to 0
+                    try
+                        set cursorStart to item 1 of (value of attribute "AXSelectedTextRange" of focusedElement)
+                    end try
+                end tell
+            end tell
+            return (cursorStart as text) & linefeed & fieldText
+            "#,
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let output = String::from_utf8(output.stdout).ok()?;
+    let (cursor, text) = output.split_once('\n')?;
+    let text = text.trim_end_matches('\n');
+    // Without a cursor position, dictation goes at the end of the field
+    let before = match cursor.trim().parse::<usize>() {
+        Ok(start) if start > 0 => text.chars().take(start - 1).collect(),
+        _ => text.to_string(),
+    };
+    let before = tail_at_word(&before, MAX_SURROUNDING_CHARS);
+    (!before.trim().is_empty()).then(|| before.to_string())
+}
+
+#[cfg(target_os = "windows")]
+pub fn get_text_before_cursor() -> Option<String> {
+    use std::process::Command;
+
+    // UI Automation: the document range up to the start of the selection, or
+    // the field's value when it has no text pattern
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            r#"
+            Add-Type -AssemblyName UIAutomationClient
+            Add-Type -AssemblyName UIAutomationTypes
+            [Console]::OutputEncoding = [System.Text.Encoding]::UTF8
+            $element = [System.Windows.Automation.AutomationElement]::FocusedElement
+            if ($element -eq $null) { exit 1 }
+            $pattern = $null
+            if ($element.TryGetCurrentPattern([System.Windows.Automation.TextPattern]::Pattern, [ref]$pattern)) {
+                $range = $pattern.DocumentRange.Clone()
+                $selection = $pattern.GetSelection()
+                if ($selection.Length -gt 0) {
+                    $range.MoveEndpointByRange(
+                        [System.Windows.Automation.Text.TextPatternRangeEndpoint]::End,
+                        $selection[0],
+                        [System.Windows.Automation.Text.TextPatternRangeEndpoint]::Start)
+                }
+                $range.GetText(-1)
+            } elseif ($element.TryGetCurrentPattern([System.Windows.Automation.ValuePattern]::Pattern, [ref]$pattern)) {
+                $pattern.Current.Value
+            } else {
+                exit 1
+            }
+            "#,
+        ])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8(output.stdout).ok()?;
+    let before = tail_at_word(text.trim_end_matches(['\r', '\n']), MAX_SURROUNDING_CHARS);
+    (!before.trim().is_empty()).then(|| before.to_string())
+}
+
+// Reading text fields on Linux needs AT-SPI, which is not wired up
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn get_text_before_cursor() -> Option<String> {
+    None
+}
+
+/// The end of `text`, at most `max_chars` characters long, starting at a
+/// word when it had to be cut
+pub fn tail_at_word(text: &str, max_chars: usize) -> &str {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text;
+    }
+    let (cut, _) = text
+        .char_indices()
+        .nth(count - max_chars)
+        .unwrap_or((text.len(), ' '));
+    let tail = &text[cut..];
+    // Skip the word the cut went through, if a whole one follows
+    match tail.find(char::is_whitespace) {
+        Some(space) if !text[..cut].ends_with(char::is_whitespace) => tail[space..].trim_start(),
+        _ => tail.trim_start(),
+    }
+}
+
+/// Builds Whisper's prompt from context terms and the text before the
+/// cursor. The text goes last, since Whisper leans on the end of its prompt
+/// most, and the terms get at most half of the room.
+pub fn whisper_prompt(terms: &[String], preceding_text: Option<&str>) -> Option<String> {
+    let mut glossary = String::new();
+    for term in terms {
+        let next = if glossary.is_empty() {
+            format!("Glossary: {}", term)
+        } else {
+            format!(", {}", term)
+        };
+        if glossary.chars().count() + next.chars().count() + 1 > MAX_WHISPER_PROMPT_CHARS / 2 {
+            break;
+        }
+        glossary.push_str(&next);
+    }
+    if !glossary.is_empty() {
+        glossary.push('.');
+    }
+
+    // Leaving a space between the glossary and the text
+    let room = MAX_WHISPER_PROMPT_CHARS - glossary.chars().count() - 1;
+    let preceding = preceding_text
+        .map(|text| tail_at_word(text, room))
+        .unwrap_or("");
+    let prompt = match (glossary.is_empty(), preceding.is_empty()) {
+        (true, true) => return None,
+        (false, true) => glossary,
+        (true, false) => preceding.to_string(),
+        (false, false) => format!("{} {}", glossary, preceding),
+    };
+    Some(prompt)
+}
+
 /// Extracts relevant context terms from window context.
 ///
 /// This function processes the window context to extract names, terms,
@@ -224,42 +394,50 @@ pub fn get_window_context() -> Option<WindowContext> {
 pub fn extract_context_terms(context: &WindowContext) -> Vec<String> {
     let mut terms = Vec::new();
 
-    // Extract terms from selected text
-    if let Some(ref selected) = context.selected_text {
-        // Extract capitalized words (likely names or proper nouns)
-        for word in selected.split_whitespace() {
-            let cleaned: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
-            if cleaned.len() >= 2
-                && cleaned
-                    .chars()
-                    .next()
-                    .map(|c| c.is_uppercase())
-                    .unwrap_or(false)
-            {
-                terms.push(cleaned);
-            }
-        }
+    // Selected text first, as the most relevant, then nearby text
+    for text in [&context.selected_text, &context.nearby_text]
+        .into_iter()
+        .flatten()
+    {
+        push_capitalized_words(text, false, &mut terms);
     }
 
-    // Extract terms from nearby text
-    if let Some(ref nearby) = context.nearby_text {
-        for word in nearby.split_whitespace() {
+    // Screen text is full of menus and sentences, so a capital at the start
+    // of a line or sentence says little about a word
+    if let Some(ref screen) = context.screen_text {
+        push_capitalized_words(screen, true, &mut terms);
+    }
+
+    terms.truncate(MAX_CONTEXT_TERMS);
+    terms
+}
+
+/// Adds the capitalized words of `text` (likely names or proper nouns) to
+/// `terms`, skipping duplicates
+fn push_capitalized_words(text: &str, skip_sentence_starts: bool, terms: &mut Vec<String>) {
+    for line in text.lines() {
+        let mut sentence_start = true;
+        for word in line.split_whitespace() {
             let cleaned: String = word.chars().filter(|c| c.is_alphanumeric()).collect();
-            if cleaned.len() >= 2
+            let at_start = sentence_start;
+            sentence_start = word.ends_with(['.', '!', '?', ':']);
+
+            let capitalized = cleaned.len() >= 2
                 && cleaned
                     .chars()
                     .next()
                     .map(|c| c.is_uppercase())
-                    .unwrap_or(false)
-            {
-                if !terms.contains(&cleaned) {
-                    terms.push(cleaned);
-                }
+                    .unwrap_or(false);
+            // Inner capitals (API, DatabaseManager) still mark a term
+            let inner_capitals = cleaned.chars().skip(1).any(|c| c.is_uppercase());
+            if !capitalized || (skip_sentence_starts && at_start && !inner_capitals) {
+                continue;
+            }
+            if !terms.contains(&cleaned) {
+                terms.push(cleaned);
             }
         }
     }
-
-    terms
 }
 
 #[cfg(test)]
@@ -272,6 +450,7 @@ mod tests {
             app_name: "Test App".to_string(),
             selected_text: Some("Meeting with John Smith about the API project".to_string()),
             nearby_text: Some("Sarah mentioned the DatabaseManager class".to_string()),
+            screen_text: None,
         };
 
         let terms = extract_context_terms(&context);
@@ -282,12 +461,58 @@ mod tests {
         assert!(terms.contains(&"DatabaseManager".to_string()));
     }
 
+    #[test]
+    fn test_extract_context_terms_from_screen() {
+        let context = WindowContext {
+            app_name: "Slack".to_string(),
+            selected_text: None,
+            nearby_text: None,
+            screen_text: Some(
+                "The deploy with Priya is blocked. Waiting\nOn the OAuth fix for Kubernetes"
+                    .to_string(),
+            ),
+        };
+
+        let terms = extract_context_terms(&context);
+        assert_eq!(terms, vec!["Priya", "OAuth", "Kubernetes"]);
+    }
+
+    #[test]
+    fn test_whisper_prompt() {
+        let terms = vec!["Priya".to_string(), "OAuth".to_string()];
+        assert_eq!(whisper_prompt(&[], None), None);
+        assert_eq!(
+            whisper_prompt(&terms, None).as_deref(),
+            Some("Glossary: Priya, OAuth.")
+        );
+        assert_eq!(
+            whisper_prompt(&terms, Some("Dear team,")).as_deref(),
+            Some("Glossary: Priya, OAuth. Dear team,")
+        );
+
+        let many: Vec<String> = (0..100).map(|i| format!("Term{}", i)).collect();
+        let long_text = "word ".repeat(200);
+        let prompt = whisper_prompt(&many, Some(long_text.trim_end())).unwrap();
+        assert!(prompt.chars().count() <= MAX_WHISPER_PROMPT_CHARS);
+        assert!(prompt.ends_with("word"));
+    }
+
+    #[test]
+    fn test_tail_at_word() {
+        assert_eq!(tail_at_word("short text", 20), "short text");
+        assert_eq!(tail_at_word("the quick brown fox", 9), "brown fox");
+        assert_eq!(tail_at_word("the quick brown fox", 12), "brown fox");
+        assert_eq!(tail_at_word("ünïcödé wörds hère", 10), "wörds hère");
+        assert_eq!(tail_at_word("unbroken", 4), "oken");
+    }
+
     #[test]
     fn test_extract_context_terms_empty() {
         let context = WindowContext {
             app_name: "Test".to_string(),
             selected_text: None,
             nearby_text: None,
+            screen_text: None,
         };
 
         let terms = extract_context_terms(&context);
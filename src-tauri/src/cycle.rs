@@ -0,0 +1,130 @@
+//! Shortcuts that step through a short list of microphones, models or
+//! languages the user picked, to switch mid-flow without opening settings.
+//!
+//! Each step emits `selection-cycled` so the overlay and main window can
+//! show what is selected now.
+
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, update_settings};
+
+/// What a cycle shortcut steps through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CycleKind {
+    Microphone,
+    Model,
+    Language,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SelectionCycledEvent {
+    pub kind: CycleKind,
+    /// The setting's new value: a device name, model ID or language code
+    pub value: String,
+    /// What to show for it
+    pub label: String,
+}
+
+/// The entry after `current` in `list`, wrapping around. Starts from the
+/// top when `current` isn't in the list.
+fn next_in_cycle<'a>(list: &'a [String], current: &str) -> Option<&'a String> {
+    let next = list
+        .iter()
+        .position(|item| item == current)
+        .map_or(0, |index| (index + 1) % list.len());
+    list.get(next)
+}
+
+/// Switch to the next selection in the user's list for `kind`
+pub fn cycle(app: &AppHandle, kind: CycleKind) {
+    let settings = get_settings(app);
+    let (list, current) = match kind {
+        CycleKind::Microphone => (
+            settings.cycle_lists.microphones,
+            settings
+                .selected_microphone
+                .unwrap_or_else(|| "default".to_string()),
+        ),
+        CycleKind::Model => (settings.cycle_lists.models, settings.selected_model),
+        CycleKind::Language => (settings.cycle_lists.languages, settings.selected_language),
+    };
+    let Some(next) = next_in_cycle(&list, &current).cloned() else {
+        debug!("Nothing to cycle through for {:?}", kind);
+        return;
+    };
+    if next == current {
+        return;
+    }
+
+    let label = match kind {
+        CycleKind::Microphone => {
+            let selected = (next != "default").then(|| next.clone());
+            update_settings(app, move |s| s.selected_microphone = selected);
+            let rm = app.state::<Arc<AudioRecordingManager>>();
+            if let Err(e) = rm.update_selected_device() {
+                error!("Failed to switch to microphone '{}': {}", next, e);
+            }
+            next.clone()
+        }
+        CycleKind::Model => {
+            let mm = app.state::<Arc<ModelManager>>();
+            let Some(info) = mm.get_model_info(&next).filter(|info| info.is_downloaded) else {
+                warn!("Model '{}' isn't downloaded, not switching to it", next);
+                return;
+            };
+            let selected = next.clone();
+            update_settings(app, move |s| s.selected_model = selected);
+            // Loading can take a while; the selection shows right away
+            let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
+            let model_id = next.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = tm.load_model(&model_id) {
+                    error!("Failed to load model '{}': {}", model_id, e);
+                }
+            });
+            info.name
+        }
+        CycleKind::Language => {
+            let selected = next.clone();
+            update_settings(app, move |s| s.selected_language = selected);
+            next.clone()
+        }
+    };
+
+    debug!("Cycled {:?} to {}", kind, label);
+    let event = SelectionCycledEvent {
+        kind,
+        value: next,
+        label,
+    };
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit cycled selection: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn next_in_cycle_wraps_and_starts_over_for_unknown() {
+        let languages = list(&["en", "de", "fr"]);
+        assert_eq!(next_in_cycle(&languages, "en").unwrap(), "de");
+        assert_eq!(next_in_cycle(&languages, "fr").unwrap(), "en");
+        assert_eq!(next_in_cycle(&languages, "auto").unwrap(), "en");
+        assert_eq!(next_in_cycle(&[], "en"), None);
+    }
+}
@@ -0,0 +1,203 @@
+//! Language learning output: the original transcript with a translation
+//! interleaved after every sentence.
+//!
+//! Translation goes through the configured post-processing provider. All
+//! sentences are sent in one request and come back as a JSON array in the
+//! same order. If the model returns a different number of translations, the
+//! output is skipped rather than mispaired.
+
+use log::{debug, error};
+
+use crate::settings::AppSettings;
+
+/// Marks the translation line under each original sentence
+const TRANSLATION_PREFIX: &str = "→ ";
+
+/// Dual-script version of `text` if the mode is enabled and translation works
+pub async fn maybe_dual_script(settings: &AppSettings, text: &str) -> Option<String> {
+    if !settings.dual_script_enabled {
+        return None;
+    }
+
+    match dual_script(settings, text).await {
+        Ok(output) => Some(output),
+        Err(e) => {
+            error!(
+                "Dual-script output failed: {}. Keeping the original text.",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Translate `text` sentence by sentence and interleave the translations
+pub async fn dual_script(settings: &AppSettings, text: &str) -> Result<String, String> {
+    let sentences = split_sentences(text);
+    if sentences.is_empty() {
+        return Ok(text.to_string());
+    }
+
+    let provider = settings
+        .active_post_process_provider()
+        .cloned()
+        .ok_or("No post-processing provider is selected")?;
+    let model = settings
+        .post_process_models
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+    if model.trim().is_empty() {
+        return Err(format!(
+            "Provider '{}' has no model configured",
+            provider.id
+        ));
+    }
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    let language = &settings.dual_script_language;
+    debug!(
+        "Translating {} sentences into {} for dual-script output",
+        sentences.len(),
+        language
+    );
+
+    let prompt = format!(
+        "Translate each sentence in the following JSON array into {}. \
+         Reply with only a JSON array of strings containing exactly one translation per sentence, \
+         in the same order. Do not add notes or explanations.\n\n{}",
+        language,
+        serde_json::to_string(&sentences).map_err(|e| e.to_string())?
+    );
+
+    let response = crate::llm_client::send_chat_completion(&provider, api_key, &model, prompt)
+        .await?
+        .ok_or("LLM API response has no content")?;
+    let translations = parse_translations(&response)?;
+    if translations.len() != sentences.len() {
+        return Err(format!(
+            "Expected {} translations but got {}",
+            sentences.len(),
+            translations.len()
+        ));
+    }
+
+    Ok(interleave(&sentences, &translations))
+}
+
+/// Split text into sentences, keeping their closing punctuation
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+        let full_width = matches!(c, '。' | '！' | '？');
+        let ends_sentence = full_width
+            || (matches!(c, '.' | '!' | '?')
+                && chars
+                    .peek()
+                    .is_none_or(|&next| next.is_whitespace() || is_closing(next)));
+        if ends_sentence {
+            // Keep closing quotes and brackets with their sentence
+            while let Some(&next) = chars.peek() {
+                if is_closing(next) {
+                    current.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            push_sentence(&mut sentences, &mut current);
+        }
+    }
+    push_sentence(&mut sentences, &mut current);
+
+    sentences
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | '”' | '’' | ')' | '」')
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let sentence = current.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_string());
+    }
+    current.clear();
+}
+
+/// Pull the JSON array out of a reply that may be wrapped in prose or fences
+fn parse_translations(response: &str) -> Result<Vec<String>, String> {
+    let start = response.find('[').ok_or("Reply contains no JSON array")?;
+    let end = response.rfind(']').ok_or("Reply contains no JSON array")?;
+    if end < start {
+        return Err("Reply contains no JSON array".to_string());
+    }
+    serde_json::from_str(&response[start..=end])
+        .map_err(|e| format!("Failed to parse translations: {}", e))
+}
+
+/// Each original sentence followed by its translation, one pair per paragraph
+pub fn interleave(sentences: &[String], translations: &[String]) -> String {
+    sentences
+        .iter()
+        .zip(translations)
+        .map(|(sentence, translation)| {
+            format!("{}\n{}{}", sentence, TRANSLATION_PREFIX, translation.trim())
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sentences_on_terminal_punctuation() {
+        assert_eq!(
+            split_sentences("Hola, ¿qué tal? Me llamo Ana. Vivo en 3.5 km de aquí!"),
+            vec![
+                "Hola, ¿qué tal?",
+                "Me llamo Ana.",
+                "Vivo en 3.5 km de aquí!"
+            ]
+        );
+        assert_eq!(
+            split_sentences("今日は暑い。水を飲みます"),
+            vec!["今日は暑い。", "水を飲みます"]
+        );
+        assert_eq!(
+            split_sentences("Er sagte: \"Komm!\" Dann ging er."),
+            vec!["Er sagte: \"Komm!\"", "Dann ging er."]
+        );
+        assert!(split_sentences("   ").is_empty());
+    }
+
+    #[test]
+    fn parses_arrays_wrapped_in_fences() {
+        let reply = "```json\n[\"Hello, how are you?\", \"My name is Ana.\"]\n```";
+        assert_eq!(
+            parse_translations(reply).unwrap(),
+            vec!["Hello, how are you?", "My name is Ana."]
+        );
+        assert!(parse_translations("Sorry, I can't help").is_err());
+    }
+
+    #[test]
+    fn interleaves_pairs() {
+        let sentences = vec!["Hola.".to_string(), "Adiós.".to_string()];
+        let translations = vec!["Hello.".to_string(), " Goodbye. ".to_string()];
+        assert_eq!(
+            interleave(&sentences, &translations),
+            "Hola.\n→ Hello.\n\nAdiós.\n→ Goodbye."
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Encryption at rest for the history archive.
+//!
+//! A single random 256-bit key protects both the SQLCipher history database
+//! and the saved recordings. The key never touches disk: it lives in the OS
+//! keychain (Keychain on macOS, Credential Manager on Windows, Secret Service
+//! on Linux). Recordings are sealed with AES-256-GCM in a small container:
+//! magic, random nonce, then ciphertext with its authentication tag.
+
+use anyhow::{anyhow, Result};
+use keyring::Entry;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt::Write as _;
+
+const KEYCHAIN_SERVICE: &str = "com.pais.paperflow";
+const KEYCHAIN_ACCOUNT: &str = "history-encryption-key";
+const KEY_LEN: usize = 32;
+const MAGIC: &[u8; 6] = b"PFENC1";
+
+/// File name suffix of encrypted recordings
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Key used for the history database and recordings
+#[derive(Clone)]
+pub struct HistoryKey([u8; KEY_LEN]);
+
+impl HistoryKey {
+    fn generate() -> Result<Self> {
+        let mut key = [0u8; KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| anyhow!("Failed to generate encryption key"))?;
+        Ok(Self(key))
+    }
+
+    /// Raw key in the `x'…'` form SQLCipher's `PRAGMA key` accepts
+    pub fn sqlcipher_pragma(&self) -> String {
+        format!("x'{}'", to_hex(&self.0))
+    }
+
+    /// Seal data into the encrypted recording container
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| anyhow!("Failed to generate nonce"))?;
+
+        let mut sealed = plaintext.to_vec();
+        self.aead_key()
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow!("Encryption failed"))?;
+
+        let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + sealed.len());
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    /// Open a container produced by [`HistoryKey::encrypt`]
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let header = MAGIC.len() + NONCE_LEN;
+        if data.len() < header || &data[..MAGIC.len()] != MAGIC {
+            return Err(anyhow!("Not an encrypted PaperFlow file"));
+        }
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&data[MAGIC.len()..header]);
+        let mut sealed = data[header..].to_vec();
+        let plaintext = self
+            .aead_key()
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(MAGIC),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow!("Decryption failed: wrong key or corrupted file"))?;
+        Ok(plaintext.to_vec())
+    }
+
+    fn aead_key(&self) -> LessSafeKey {
+        LessSafeKey::new(
+            UnboundKey::new(&AES_256_GCM, &self.0).expect("key has the AES-256 length"),
+        )
+    }
+}
+
+fn keychain_entry() -> Result<Entry> {
+    Ok(Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)?)
+}
+
+/// Load the key from the keychain, if one was stored
+pub fn load_key() -> Result<Option<HistoryKey>> {
+    let stored = match keychain_entry()?.get_password() {
+        Ok(stored) => stored,
+        Err(keyring::Error::NoEntry) => return Ok(None),
+        Err(e) => return Err(anyhow!("Failed to read key from keychain: {}", e)),
+    };
+
+    let bytes = from_hex(&stored).ok_or_else(|| anyhow!("Keychain entry is not a valid key"))?;
+    let key: [u8; KEY_LEN] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("Keychain entry has the wrong key length"))?;
+    Ok(Some(HistoryKey(key)))
+}
+
+/// Load the stored key, or generate one and store it in the keychain
+pub fn load_or_create_key() -> Result<HistoryKey> {
+    if let Some(key) = load_key()? {
+        return Ok(key);
+    }
+
+    let key = HistoryKey::generate()?;
+    keychain_entry()?
+        .set_password(&to_hex(&key.0))
+        .map_err(|e| anyhow!("Failed to store key in keychain: {}", e))?;
+    Ok(key)
+}
+
+/// Remove the key from the keychain once nothing is encrypted with it
+pub fn delete_key() -> Result<()> {
+    match keychain_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(anyhow!("Failed to remove key from keychain: {}", e)),
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{:02x}", byte);
+        out
+    })
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trips() {
+        let key = HistoryKey::generate().unwrap();
+        let data = b"meeting notes with sensitive content".to_vec();
+
+        let sealed = key.encrypt(&data).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert_ne!(&sealed[MAGIC.len() + NONCE_LEN..], data.as_slice());
+        assert_eq!(key.decrypt(&sealed).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypt_rejects_tampering_and_wrong_key() {
+        let key = HistoryKey::generate().unwrap();
+        let mut sealed = key.encrypt(b"hello").unwrap();
+
+        assert!(HistoryKey::generate().unwrap().decrypt(&sealed).is_err());
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(key.decrypt(&sealed).is_err());
+        assert!(key.decrypt(b"RIFF....WAVE").is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        assert_eq!(to_hex(&bytes), "0001abff");
+        assert_eq!(from_hex("0001abff").unwrap(), bytes);
+        assert!(from_hex("abc").is_none());
+        assert!(from_hex("zz").is_none());
+    }
+}
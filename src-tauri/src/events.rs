@@ -0,0 +1,225 @@
+//! Every event the backend emits to the frontend.
+//!
+//! Each event is a specta-typed payload with a fixed name, registered with the
+//! tauri-specta builder so the generated bindings carry both the name and the
+//! payload type. Emit through `tauri_specta::Event::emit` rather than passing a
+//! string to `Emitter::emit`, so a renamed or reshaped event fails to compile
+//! instead of silently drifting from the frontend.
+//!
+//! Payloads owned by a manager live next to it; this module holds the payloads
+//! that have no better home and the name of every event.
+
+use serde::{Deserialize, Serialize};
+use specta::datatype::DataType;
+use specta::{Generics, Type, TypeCollection};
+
+use crate::cycle::SelectionCycledEvent;
+use crate::focus_mode::FocusStateChangedEvent;
+use crate::managers::audio::RecordingLimitReachedEvent;
+use crate::managers::file_transcription::{FileTranscriptionEtaEvent, FileTranscriptionEvent};
+use crate::managers::live_preview::{LivePreviewErrorEvent, LivePreviewEvent};
+use crate::managers::meeting::{
+    MeetingChunkEvent, MeetingFinalizationProgressEvent, MeetingSession, MeetingStateEvent,
+};
+use crate::managers::model::{
+    DownloadCancelledEvent, DownloadFailedEvent, DownloadProgress, PartialDownload,
+};
+use crate::managers::transcription::{CoreMLCompilationEvent, ModelStateEvent};
+use crate::managers::watch_folder::WatchFolderFileDetected;
+use crate::meeting_detection::MeetingDetectedEvent;
+use crate::quiet_hours::QuietHoursChangedEvent;
+
+/// Implements `tauri_specta::Event` for each payload under its wire name and
+/// collects them all for the specta builder.
+macro_rules! app_events {
+    ($($event:path => $name:literal),* $(,)?) => {
+        $(
+            impl tauri_specta::Event for $event {
+                const NAME: &'static str = $name;
+            }
+        )*
+
+        /// All events, for `tauri_specta::Builder::events`
+        pub fn collect() -> tauri_specta::Events {
+            tauri_specta::collect_events![$($event),*]
+        }
+    };
+}
+
+app_events! {
+    // Window and tray
+    NavigateToSettings => "navigate-to-settings",
+    CheckForUpdates => "check-for-updates",
+    ShowOverlay => "show-overlay",
+    HideOverlay => "hide-overlay",
+    MicLevel => "mic-level",
+
+    // Settings
+    SettingsChanged => "settings-changed",
+    WhisperModeChanged => "whisper-mode-changed",
+    QuietHoursChangedEvent => "quiet-hours-changed",
+    FocusStateChangedEvent => "focus-state-changed",
+    SelectionCycledEvent => "selection-cycled",
+
+    // Recording and history
+    RecordingLimitReachedEvent => "recording-limit-reached",
+    HistoryUpdated => "history-updated",
+    LivePreviewEvent => "live-preview-update",
+    LivePreviewErrorEvent => "live-preview-error",
+    PostProcessStream => "post-process-stream",
+    PostProcessFallback => "post-process-fallback",
+    PostProcessFailed => "post-process-failed",
+
+    // Models
+    ModelStateEvent => "model-state-changed",
+    DownloadProgress => "model-download-progress",
+    ModelDownloadComplete => "model-download-complete",
+    DownloadCancelledEvent => "model-download-cancelled",
+    DownloadFailedEvent => "model-download-failed",
+    ModelDownloadsInterrupted => "model-downloads-interrupted",
+    ModelExtractionStarted => "model-extraction-started",
+    ModelExtractionCompleted => "model-extraction-completed",
+    ModelExtractionFailed => "model-extraction-failed",
+    CoreMLDownloadStarted => "coreml-download-started",
+    CoreMLDownloadProgress => "coreml-download-progress",
+    CoreMLExtractionStarted => "coreml-extraction-started",
+    CoreMLDownloadComplete => "coreml-download-complete",
+    CoreMLCompilationEvent => "coreml-compilation-status",
+
+    // File transcription and watch folders
+    FileTranscriptionEvent => "file-transcription-update",
+    FileTranscriptionEtaEvent => "file-transcription-eta",
+    WatchFolderFileDetected => "watch-folder-file-detected",
+
+    // Meetings
+    MeetingStateEvent => "meeting-state-changed",
+    MeetingHeartbeat => "meeting-heartbeat",
+    MeetingChunkEvent => "meeting-chunk-transcribed",
+    MeetingFinalizationProgressEvent => "meeting-finalization-progress",
+    MeetingCompleted => "meeting-completed",
+    MeetingRecoveryAvailable => "meeting-recovery-available",
+    MeetingHistoryUpdated => "meeting-history-updated",
+    MeetingDetectedEvent => "meeting-detected",
+}
+
+/// Open the settings page from the tray menu
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NavigateToSettings;
+
+/// Ask the frontend to run an update check
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CheckForUpdates;
+
+/// Which state the recording overlay should show
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ShowOverlay {
+    Recording,
+    Meeting,
+    Transcribing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HideOverlay;
+
+/// Microphone levels for the overlay's level meter
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MicLevel(pub Vec<f32>);
+
+/// A top-level setting changed; `setting` is the `AppSettings` field name
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SettingsChanged {
+    pub setting: String,
+    #[specta(type = AnyJson)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WhisperModeChanged(pub bool);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HistoryUpdated;
+
+/// Post-processing reply as it arrives. `text` is everything received so
+/// far; the last event has `done` set and an empty `delta`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PostProcessStream {
+    pub delta: String,
+    pub text: String,
+    pub done: bool,
+}
+
+/// The post-processing provider failed and the fallback provider was used
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PostProcessFallback {
+    pub failed_provider_id: String,
+    pub provider_id: String,
+    pub error: String,
+}
+
+/// Post-processing failed on every provider; the transcription is used as is
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PostProcessFailed {
+    pub provider_id: String,
+    pub error: String,
+}
+
+/// Model id of a finished download
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelDownloadComplete(pub String);
+
+/// Downloads that were interrupted by a previous run and can be resumed
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ModelDownloadsInterrupted(pub Vec<PartialDownload>);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelExtractionStarted(pub String);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelExtractionCompleted(pub String);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelExtractionFailed {
+    pub model_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CoreMLDownloadStarted(pub String);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CoreMLDownloadProgress {
+    pub model_id: String,
+    pub downloaded: u64,
+    pub total: u64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CoreMLExtractionStarted(pub String);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CoreMLDownloadComplete(pub String);
+
+/// Periodic update while a meeting is recording, same shape as a state change
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MeetingHeartbeat(pub MeetingStateEvent);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MeetingCompleted(pub MeetingSession);
+
+/// A meeting from a previous run was interrupted and can be recovered
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MeetingRecoveryAvailable(pub MeetingSession);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MeetingHistoryUpdated;
+
+/// Arbitrary JSON, exported as `any`
+pub struct AnyJson;
+
+impl Type for AnyJson {
+    fn inline(_: &mut TypeCollection, _: Generics) -> DataType {
+        DataType::Any
+    }
+}
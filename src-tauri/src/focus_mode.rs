@@ -0,0 +1,228 @@
+//! Respect macOS Focus and presentations.
+//!
+//! A monitor thread polls whether a Focus (Do Not Disturb, Work, ...) is on
+//! and whether the user is presenting or sharing their screen. Depending on
+//! the `focus_suppression` settings, the recording overlay and feedback
+//! sounds are then held back so nothing pops up on a shared screen. The
+//! frontend gets `focus-state-changed` on every transition so it can hold
+//! back its own toasts.
+//!
+//! Both checks are best effort. Focus is read from the assertion store the
+//! system keeps in `~/Library/DoNotDisturb`, which is only readable with Full
+//! Disk Access on recent macOS versions. Presenting is inferred from display
+//! sleep assertions held by presentation and video call apps. On other
+//! platforms neither state is ever reported.
+
+use log::{error, info};
+use serde::Serialize;
+use specta::Type;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::settings::get_settings;
+
+static FOCUS_ACTIVE: AtomicBool = AtomicBool::new(false);
+static PRESENTING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+pub struct FocusState {
+    /// A macOS Focus is turned on
+    pub focus_active: bool,
+    /// A presentation or screen share is running
+    pub presenting: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FocusStateChangedEvent {
+    pub state: FocusState,
+    /// Whether the frontend should hold back its notifications
+    pub suppress_notifications: bool,
+}
+
+/// Last state seen by the monitor
+pub fn current_state() -> FocusState {
+    FocusState {
+        focus_active: FOCUS_ACTIVE.load(Ordering::Relaxed),
+        presenting: PRESENTING.load(Ordering::Relaxed),
+    }
+}
+
+/// Whether the current state matches one the user asked to respect
+fn is_suppressing(app: &AppHandle) -> bool {
+    let suppression = get_settings(app).focus_suppression;
+    let state = current_state();
+    suppression.enabled
+        && ((suppression.respect_focus && state.focus_active)
+            || (suppression.respect_presenting && state.presenting))
+}
+
+/// Whether the recording overlay should stay hidden right now
+pub fn should_suppress_overlay(app: &AppHandle) -> bool {
+    get_settings(app).focus_suppression.suppress_overlay && is_suppressing(app)
+}
+
+/// Whether feedback sounds should stay muted right now
+pub fn should_suppress_sounds(app: &AppHandle) -> bool {
+    get_settings(app).focus_suppression.suppress_sounds && is_suppressing(app)
+}
+
+/// Whether the frontend should hold back its notifications right now
+pub fn should_suppress_notifications(app: &AppHandle) -> bool {
+    get_settings(app).focus_suppression.suppress_notifications && is_suppressing(app)
+}
+
+/// Poll the system state and react when it changes
+#[cfg(target_os = "macos")]
+pub fn start_monitor(app: &AppHandle) {
+    use std::thread;
+    use std::time::Duration;
+
+    /// Short enough that the overlay is hidden soon after a share starts
+    const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("focus-mode".into())
+        .spawn(move || loop {
+            let state = macos::detect();
+            if state != current_state() {
+                FOCUS_ACTIVE.store(state.focus_active, Ordering::Relaxed);
+                PRESENTING.store(state.presenting, Ordering::Relaxed);
+                on_transition(&app, state);
+            }
+            thread::sleep(CHECK_INTERVAL);
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn focus mode monitor: {}", e);
+    }
+}
+
+/// Focus and presentation detection is macOS-only
+#[cfg(not(target_os = "macos"))]
+pub fn start_monitor(_app: &AppHandle) {}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn on_transition(app: &AppHandle, state: FocusState) {
+    info!(
+        "Focus state changed: focus={}, presenting={}",
+        state.focus_active, state.presenting
+    );
+
+    // An overlay that is already up would otherwise stay on the shared screen
+    if should_suppress_overlay(app) {
+        crate::overlay::hide_recording_overlay(app);
+    }
+
+    let event = FocusStateChangedEvent {
+        state,
+        suppress_notifications: should_suppress_notifications(app),
+    };
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit focus state change: {}", e);
+    }
+}
+
+/// Whether the DoNotDisturb assertion store lists an active Focus
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_focus_assertions(json: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return false;
+    };
+    value["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|store| store["storeAssertionRecords"].as_array())
+        .any(|records| !records.is_empty())
+}
+
+/// Apps whose display sleep assertions mean a presentation or call is running
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const PRESENTING_PROCESSES: &[&str] = &["Keynote", "Microsoft PowerPoint", "zoom.us", "CptHost"];
+
+/// Whether `pmset -g assertions` output shows a presentation or screen share
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_presenting_assertions(output: &str) -> bool {
+    output
+        .lines()
+        .filter(|line| line.contains("PreventUserIdleDisplaySleep") && line.contains("pid "))
+        .any(|line| {
+            let process = line
+                .split_once('(')
+                .and_then(|(_, rest)| rest.split_once(')'))
+                .map(|(name, _)| name)
+                .unwrap_or_default();
+            let name = line
+                .split_once("named:")
+                .map(|(_, name)| name.to_lowercase())
+                .unwrap_or_default();
+            PRESENTING_PROCESSES.contains(&process)
+                || name.contains("present")
+                || name.contains("screen shar")
+                || name.contains("screensharing")
+        })
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::{parse_focus_assertions, parse_presenting_assertions, FocusState};
+    use std::process::Command;
+
+    pub fn detect() -> FocusState {
+        FocusState {
+            focus_active: focus_active(),
+            presenting: presenting(),
+        }
+    }
+
+    fn focus_active() -> bool {
+        let Some(home) = std::env::var_os("HOME") else {
+            return false;
+        };
+        let path = std::path::Path::new(&home).join("Library/DoNotDisturb/DB/Assertions.json");
+        std::fs::read_to_string(path)
+            .map(|json| parse_focus_assertions(&json))
+            .unwrap_or(false)
+    }
+
+    fn presenting() -> bool {
+        match Command::new("pmset").args(["-g", "assertions"]).output() {
+            Ok(output) => parse_presenting_assertions(&String::from_utf8_lossy(&output.stdout)),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_is_active_when_an_assertion_is_stored() {
+        let active = r#"{"data":[{"storeAssertionRecords":[{"assertionDetails":
+            {"assertionDetailsModeIdentifier":"com.apple.donotdisturb.mode.default"}}]}]}"#;
+        assert!(parse_focus_assertions(active));
+
+        assert!(!parse_focus_assertions(
+            r#"{"data":[{"storeAssertionRecords":[]}]}"#
+        ));
+        assert!(!parse_focus_assertions(r#"{"data":[{}]}"#));
+        assert!(!parse_focus_assertions("not json"));
+    }
+
+    #[test]
+    fn presenting_is_detected_from_display_sleep_assertions() {
+        let keynote = "Listed by owning process:\n   \
+            pid 812(Keynote): [0x0000a1b2000193c4] 00:02:13 PreventUserIdleDisplaySleep named: \"Keynote playing\"\n";
+        assert!(parse_presenting_assertions(keynote));
+
+        let share = "   pid 977(Google Chrome): [0x00001] 00:10:00 PreventUserIdleDisplaySleep named: \"Screen sharing\"\n";
+        assert!(parse_presenting_assertions(share));
+
+        let video = "   pid 977(Google Chrome): [0x00001] 00:10:00 PreventUserIdleDisplaySleep named: \"Video Wake Lock\"\n   \
+            pid 120(coreaudiod): [0x00002] 00:00:05 PreventUserIdleSystemSleep named: \"com.apple.audio.context present\"\n";
+        assert!(!parse_presenting_assertions(video));
+    }
+}
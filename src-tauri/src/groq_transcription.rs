@@ -9,6 +9,8 @@ use serde::Deserialize;
 use std::io::Cursor;
 use std::time::Duration;
 
+use crate::usage::{self, ApiUsage};
+
 const GROQ_API_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
 
 /// Base timeout for Groq API requests (30 seconds)
@@ -171,6 +173,8 @@ fn classify_error(
 /// * `model_id` - Model to use (e.g., "whisper-large-v3", "whisper-large-v3-turbo", or "distil-whisper-large-v3-en")
 /// * `audio_samples` - Audio samples as f32 (mono, 16kHz expected)
 /// * `language` - Optional language code (e.g., "en", "es", "auto")
+/// * `prompt` - Optional prompt with terms to recognize and the text the
+///   speech continues
 ///
 /// # Returns
 /// The transcribed text or an error.
@@ -179,6 +183,7 @@ pub async fn transcribe(
     model_id: &str,
     audio_samples: &[f32],
     language: Option<&str>,
+    prompt: Option<&str>,
 ) -> Result<String, String> {
     if api_key.is_empty() {
         return Err(GroqErrorKind::MissingApiKey.user_message().to_string());
@@ -212,7 +217,7 @@ pub async fn transcribe(
     debug!("Using timeout: {:?}", timeout);
 
     // Create HTTP client with dynamic timeout
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .timeout(timeout)
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -253,6 +258,10 @@ pub async fn transcribe(
             }
         }
 
+        if let Some(p) = prompt {
+            form = form.text("prompt", p.to_string());
+        }
+
         // Send request to Groq API
         let response = match client
             .post(GROQ_API_URL)
@@ -301,6 +310,12 @@ pub async fn transcribe(
             );
             debug!("Transcription result: {}", transcription.text);
 
+            usage::record(ApiUsage {
+                provider_id: "groq".to_string(),
+                model: groq_model.to_string(),
+                audio_seconds: audio_samples.len() as f64 / 16000.0,
+                ..Default::default()
+            });
             return Ok(transcription.text);
         }
 
@@ -355,6 +370,8 @@ pub async fn transcribe(
 /// * `audio_samples` - Audio samples as f32 (mono, 16kHz expected)
 /// * `primary_language` - Optional primary language code (e.g., "en")
 /// * `secondary_language` - Optional secondary language code (e.g., "es")
+/// * `context_prompt` - Optional prompt with terms to recognize and the text
+///   the speech continues, after the language hint
 ///
 /// # Returns
 /// The transcribed text or an error.
@@ -364,6 +381,7 @@ pub async fn transcribe_multilingual(
     audio_samples: &[f32],
     primary_language: Option<&str>,
     secondary_language: Option<&str>,
+    context_prompt: Option<&str>,
 ) -> Result<String, String> {
     if api_key.is_empty() {
         return Err(GroqErrorKind::MissingApiKey.user_message().to_string());
@@ -409,7 +427,7 @@ pub async fn transcribe_multilingual(
     debug!("Using timeout: {:?}", timeout);
 
     // Create HTTP client with dynamic timeout
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .timeout(timeout)
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -435,6 +453,10 @@ pub async fn transcribe_multilingual(
     } else {
         None
     };
+    let prompt = match (prompt, context_prompt) {
+        (Some(hint), Some(context)) => Some(format!("{} {}", hint, context)),
+        (hint, context) => hint.or_else(|| context.map(str::to_string)),
+    };
 
     if let Some(ref p) = prompt {
         debug!("Using multilingual prompt hint: {}", p);
@@ -515,6 +537,12 @@ pub async fn transcribe_multilingual(
             );
             debug!("Transcription result: {}", transcription.text);
 
+            usage::record(ApiUsage {
+                provider_id: "groq".to_string(),
+                model: groq_model.to_string(),
+                audio_seconds: audio_samples.len() as f64 / 16000.0,
+                ..Default::default()
+            });
             return Ok(transcription.text);
         }
 
@@ -579,7 +607,7 @@ pub async fn validate_api_key(api_key: &str) -> Result<(), String> {
     let silence_samples: Vec<f32> = vec![0.0; 1600]; // 0.1 seconds at 16kHz
     let wav_data = samples_to_wav(&silence_samples, 16000)?;
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder()
         .timeout(Duration::from_secs(10))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
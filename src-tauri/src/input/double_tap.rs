@@ -0,0 +1,307 @@
+//! Double-tapping a modifier key, e.g. Right Ctrl, as a shortcut.
+//!
+//! The global shortcut plugin needs a main key, so a binding written as
+//! `double_tap:<key>` is registered here instead and picked up by the shared
+//! input listener. The second tap counts as the shortcut's press and its
+//! release as the release, so holding the second tap works as push-to-talk.
+//!
+//! A tap is a press and release within the tap timeout with no other key in
+//! between, which keeps Ctrl+C and the like from counting. `fn` is only seen
+//! on macOS; elsewhere the keyboard handles it without telling the system.
+
+use log::{debug, error};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use super::listener;
+use crate::settings::{get_settings, ShortcutBinding};
+
+/// Binding strings starting with this are double-tap triggers
+pub const PREFIX: &str = "double_tap:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    LeftCtrl,
+    RightCtrl,
+    LeftShift,
+    RightShift,
+    LeftAlt,
+    RightAlt,
+    LeftMeta,
+    RightMeta,
+    Fn,
+}
+
+const MODIFIERS: &[(&str, Modifier, rdev::Key)] = &[
+    ("left_ctrl", Modifier::LeftCtrl, rdev::Key::ControlLeft),
+    ("right_ctrl", Modifier::RightCtrl, rdev::Key::ControlRight),
+    ("left_shift", Modifier::LeftShift, rdev::Key::ShiftLeft),
+    ("right_shift", Modifier::RightShift, rdev::Key::ShiftRight),
+    ("left_alt", Modifier::LeftAlt, rdev::Key::Alt),
+    ("right_alt", Modifier::RightAlt, rdev::Key::AltGr),
+    ("left_meta", Modifier::LeftMeta, rdev::Key::MetaLeft),
+    ("right_meta", Modifier::RightMeta, rdev::Key::MetaRight),
+    ("fn", Modifier::Fn, rdev::Key::Function),
+];
+
+/// Registered bindings by the modifier that triggers them
+static REGISTERED: Lazy<Mutex<HashMap<Modifier, ShortcutBinding>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static DETECTOR: Lazy<Mutex<TapDetector>> = Lazy::new(|| Mutex::new(TapDetector::default()));
+
+/// Kept outside the settings so key events don't have to load them
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Trigger presses and releases in order, for the worker thread
+static EVENTS: OnceLock<Sender<(ShortcutBinding, bool)>> = OnceLock::new();
+
+pub fn is_double_tap(binding: &str) -> bool {
+    binding.trim().starts_with(PREFIX)
+}
+
+/// The modifier in a `double_tap:<key>` binding
+pub fn parse(binding: &str) -> Result<Modifier, String> {
+    let name = binding
+        .trim()
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| format!("'{}' is not a double-tap shortcut", binding))?
+        .trim()
+        .to_lowercase();
+    MODIFIERS
+        .iter()
+        .find(|(known, _, _)| *known == name)
+        .map(|(_, modifier, _)| *modifier)
+        .ok_or_else(|| format!("Double-tap shortcuts need a modifier key, not '{}'", name))
+}
+
+fn modifier_for_key(key: rdev::Key) -> Option<Modifier> {
+    MODIFIERS
+        .iter()
+        .find(|(_, _, known)| *known == key)
+        .map(|(_, modifier, _)| *modifier)
+}
+
+pub fn set_timeout(timeout_ms: u32) {
+    TIMEOUT_MS.store(timeout_ms as u64, Ordering::SeqCst);
+}
+
+pub fn register(app: &AppHandle, binding: &ShortcutBinding) -> Result<(), String> {
+    let modifier = parse(&binding.current_binding)?;
+    {
+        let mut registered = REGISTERED.lock().unwrap();
+        if registered.contains_key(&modifier) {
+            return Err(format!(
+                "Shortcut '{}' is already in use",
+                binding.current_binding
+            ));
+        }
+        registered.insert(modifier, binding.clone());
+    }
+    set_timeout(get_settings(app).double_tap_timeout_ms);
+    listener::start(app);
+    Ok(())
+}
+
+pub fn unregister(binding: &ShortcutBinding) -> Result<(), String> {
+    let modifier = parse(&binding.current_binding)?;
+    REGISTERED.lock().unwrap().remove(&modifier);
+    Ok(())
+}
+
+/// Called by the input listener for every key press and release
+pub(super) fn on_key(app: &AppHandle, key: rdev::Key, pressed: bool) {
+    if REGISTERED.lock().unwrap().is_empty() {
+        return;
+    }
+
+    let timeout = Duration::from_millis(TIMEOUT_MS.load(Ordering::SeqCst));
+    let modifier = modifier_for_key(key);
+    let now = Instant::now();
+    let trigger = {
+        let mut detector = DETECTOR.lock().unwrap();
+        if pressed {
+            detector.press(modifier, now, timeout)
+        } else {
+            detector.release(modifier, now, timeout)
+        }
+    };
+    let Some((modifier, pressed)) = trigger else {
+        return;
+    };
+    let Some(binding) = REGISTERED.lock().unwrap().get(&modifier).cloned() else {
+        return;
+    };
+    debug!(
+        "Double-tap {} {}",
+        binding.current_binding,
+        if pressed { "pressed" } else { "released" }
+    );
+
+    // Keep the hook callback short, but hand presses and releases to one
+    // thread so a push-to-talk release never overtakes its press
+    let sender = EVENTS.get_or_init(|| spawn_worker(app.clone()));
+    let _ = sender.send((binding, pressed));
+}
+
+fn spawn_worker(app: AppHandle) -> Sender<(ShortcutBinding, bool)> {
+    let (sender, receiver) = mpsc::channel::<(ShortcutBinding, bool)>();
+    let spawned = thread::Builder::new()
+        .name("double-tap".into())
+        .spawn(move || {
+            for (binding, pressed) in receiver {
+                crate::shortcut::handle_shortcut_event(
+                    &app,
+                    &binding.id,
+                    &binding.current_binding,
+                    pressed,
+                );
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to spawn double-tap worker: {}", e);
+    }
+    sender
+}
+
+/// Tells taps apart from holds and key combinations
+#[derive(Debug, Default)]
+struct TapDetector {
+    /// The modifier that is down and when it went down
+    down: Option<(Modifier, Instant)>,
+    /// Another key was pressed while `down` was held
+    interrupted: bool,
+    /// The last completed tap and when it ended
+    last_tap: Option<(Modifier, Instant)>,
+    /// The modifier whose second tap is being held
+    triggered: Option<Modifier>,
+}
+
+impl TapDetector {
+    /// `modifier` is None for keys that aren't modifiers. Returns the
+    /// modifier whose second tap just went down.
+    fn press(
+        &mut self,
+        modifier: Option<Modifier>,
+        now: Instant,
+        timeout: Duration,
+    ) -> Option<(Modifier, bool)> {
+        let Some(modifier) = modifier else {
+            self.interrupted = true;
+            self.last_tap = None;
+            return None;
+        };
+        match self.down {
+            // Key repeat
+            Some((down, _)) if down == modifier => return None,
+            Some(_) => {
+                self.interrupted = true;
+                self.last_tap = None;
+                return None;
+            }
+            None => {}
+        }
+
+        self.down = Some((modifier, now));
+        self.interrupted = false;
+        match self.last_tap.take() {
+            Some((tapped, at)) if tapped == modifier && now.duration_since(at) <= timeout => {
+                self.triggered = Some(modifier);
+                Some((modifier, true))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the modifier whose second tap was just let go
+    fn release(
+        &mut self,
+        modifier: Option<Modifier>,
+        now: Instant,
+        timeout: Duration,
+    ) -> Option<(Modifier, bool)> {
+        let modifier = modifier?;
+        let (down, at) = self.down?;
+        if down != modifier {
+            return None;
+        }
+        self.down = None;
+
+        if self.triggered == Some(modifier) {
+            self.triggered = None;
+            return Some((modifier, false));
+        }
+        if !self.interrupted && now.duration_since(at) <= timeout {
+            self.last_tap = Some((modifier, now));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_millis(300);
+
+    #[test]
+    fn parse_accepts_modifier_names_only() {
+        assert_eq!(parse("double_tap:right_ctrl"), Ok(Modifier::RightCtrl));
+        assert_eq!(parse(" double_tap:Fn "), Ok(Modifier::Fn));
+        assert!(parse("double_tap:space").is_err());
+        assert!(parse("ctrl+space").is_err());
+    }
+
+    #[test]
+    fn second_tap_triggers_until_released() {
+        let mut detector = TapDetector::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let ctrl = Some(Modifier::RightCtrl);
+
+        assert_eq!(detector.press(ctrl, at(0), TIMEOUT), None);
+        assert_eq!(detector.release(ctrl, at(80), TIMEOUT), None);
+        assert_eq!(
+            detector.press(ctrl, at(200), TIMEOUT),
+            Some((Modifier::RightCtrl, true))
+        );
+        // Key repeat while holding the second tap
+        assert_eq!(detector.press(ctrl, at(700), TIMEOUT), None);
+        assert_eq!(
+            detector.release(ctrl, at(2000), TIMEOUT),
+            Some((Modifier::RightCtrl, false))
+        );
+    }
+
+    #[test]
+    fn slow_taps_and_combinations_do_not_trigger() {
+        let mut detector = TapDetector::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let ctrl = Some(Modifier::RightCtrl);
+
+        // Too long between the taps
+        detector.press(ctrl, at(0), TIMEOUT);
+        detector.release(ctrl, at(50), TIMEOUT);
+        assert_eq!(detector.press(ctrl, at(500), TIMEOUT), None);
+        detector.release(ctrl, at(550), TIMEOUT);
+
+        // Ctrl+C, then Ctrl
+        detector.press(ctrl, at(1000), TIMEOUT);
+        detector.press(None, at(1020), TIMEOUT);
+        detector.release(ctrl, at(1050), TIMEOUT);
+        assert_eq!(detector.press(ctrl, at(1100), TIMEOUT), None);
+        detector.release(ctrl, at(1150), TIMEOUT);
+
+        // Held too long to be a tap
+        detector.press(ctrl, at(2000), TIMEOUT);
+        detector.release(ctrl, at(2600), TIMEOUT);
+        assert_eq!(detector.press(ctrl, at(2700), TIMEOUT), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Hardware mic-mute and media keys.
+//!
+//! The global shortcut plugin can't register mic-mute or play/pause keys, so
+//! they are picked up by a low-level key listener and mapped to the actions
+//! in the `hardware_keys` settings. The listener only observes keys; other
+//! apps still receive them.
+//!
+//! Which keys arrive depends on the platform. On Linux, X11 reports both
+//! (XF86AudioMicMute and XF86AudioPlay). On Windows only play/pause reaches a
+//! keyboard hook, as mic-mute keys are handled by vendor drivers. macOS
+//! delivers media keys as system events the listener never sees.
+//!
+//! Where the kernel exposes a writable mic-mute LED (`/sys/class/leds/*::micmute`),
+//! the LED is lit while PaperFlow records.
+
+use log::{debug, error, info, warn};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::listener;
+use crate::managers::audio::AudioRecordingManager;
+use crate::settings::{get_settings, HardwareKeyAction};
+
+/// How often the LED is brought in line with the recording state
+const LED_SYNC_INTERVAL: Duration = Duration::from_millis(250);
+
+static LED_SYNC_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+enum HardwareKey {
+    MicMute,
+    PlayPause,
+}
+
+/// Raw codes rdev reports as `Key::Unknown` for keys it has no name for
+#[cfg(target_os = "linux")]
+const KEY_CODES: &[(u32, HardwareKey)] = &[
+    (198, HardwareKey::MicMute),   // XF86AudioMicMute
+    (172, HardwareKey::PlayPause), // XF86AudioPlay
+];
+#[cfg(target_os = "windows")]
+const KEY_CODES: &[(u32, HardwareKey)] = &[(0xB3, HardwareKey::PlayPause)]; // VK_MEDIA_PLAY_PAUSE
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+const KEY_CODES: &[(u32, HardwareKey)] = &[];
+
+fn hardware_key(key: rdev::Key) -> Option<HardwareKey> {
+    let rdev::Key::Unknown(code) = key else {
+        return None;
+    };
+    KEY_CODES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, hardware_key)| *hardware_key)
+}
+
+/// Start listening if hardware keys are enabled. Called again whenever the
+/// setting changes; the listener itself is only started once and ignores
+/// keys while the feature is turned off.
+pub fn start(app: &AppHandle) {
+    if !get_settings(app).hardware_keys.enabled {
+        return;
+    }
+    if KEY_CODES.is_empty() {
+        info!("Hardware keys are not supported on this platform");
+        return;
+    }
+
+    listener::start(app);
+    if !LED_SYNC_STARTED.swap(true, Ordering::SeqCst) {
+        start_led_sync(app);
+    }
+}
+
+/// Called by the input listener for every key press
+pub(super) fn on_key_press(app: &AppHandle, key: rdev::Key) {
+    if let Some(hardware_key) = hardware_key(key) {
+        // Keep the hook callback short; actions can take a while
+        let app = app.clone();
+        thread::spawn(move || handle_key(&app, hardware_key));
+    }
+}
+
+fn handle_key(app: &AppHandle, key: HardwareKey) {
+    let settings = get_settings(app).hardware_keys;
+    if !settings.enabled {
+        return;
+    }
+    let action = match key {
+        HardwareKey::MicMute => settings.mic_mute,
+        HardwareKey::PlayPause => settings.play_pause,
+    };
+    debug!("Hardware key {:?} pressed, action {:?}", key, action);
+
+    match action {
+        HardwareKeyAction::Disabled => {}
+        HardwareKeyAction::ToggleRecording => super::toggle_transcription(app, "hardware-key"),
+        HardwareKeyAction::MuteWhileRecording => {
+            let audio_manager = app.state::<Arc<AudioRecordingManager>>();
+            audio_manager.toggle_mute();
+        }
+    }
+}
+
+/// Mic-mute LEDs exposed by the kernel, e.g. `platform::micmute`
+#[cfg(target_os = "linux")]
+fn mic_mute_leds() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/leds") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with("::micmute"))
+        .map(|entry| entry.path().join("brightness"))
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mic_mute_leds() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+fn start_led_sync(app: &AppHandle) {
+    let leds = mic_mute_leds();
+    if leds.is_empty() {
+        return;
+    }
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("mic-mute-led".into())
+        .spawn(move || {
+            let mut lit = None;
+            loop {
+                let settings = get_settings(&app).hardware_keys;
+                let recording = app.state::<Arc<AudioRecordingManager>>().is_recording();
+                let want = settings.enabled && settings.sync_led && recording;
+
+                if lit != Some(want) {
+                    for led in &leds {
+                        if let Err(e) = std::fs::write(led, if want { "1" } else { "0" }) {
+                            // Usually needs a udev rule granting write access
+                            warn!("Can't set mic-mute LED {:?}, giving up: {}", led, e);
+                            return;
+                        }
+                    }
+                    lit = Some(want);
+                }
+                thread::sleep(LED_SYNC_INTERVAL);
+            }
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn mic-mute LED sync: {}", e);
+    }
+}
@@ -0,0 +1,314 @@
+//! Foot pedals, presenters and other HID button devices as dictation triggers.
+//!
+//! Transcription pedals usually aren't keyboards: they send vendor-specific
+//! input reports in which each pedal sets a bit. A binding names the device
+//! and that bit, found with `capture_button` while the user clicks it.
+//!
+//! Bound devices are read on one thread, which opens them again when they
+//! are plugged back in and picks up binding changes within a couple of
+//! seconds. Reading doesn't take the device away from other apps.
+
+use hidapi::{DeviceInfo, HidApi, HidDevice};
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::settings::{get_settings, HidBinding, HidButtonAction};
+
+/// How long a read waits for a report, bounding the delay of other devices
+const READ_TIMEOUT_MS: i32 = 50;
+/// How often settings are re-read and missing devices looked for
+const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+/// How long `capture_button` waits for a press
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Large enough for full-speed USB input reports
+const REPORT_SIZE: usize = 64;
+
+/// Passed to the actions in place of a shortcut string
+const SOURCE: &str = "hid-device";
+
+static MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// A connected HID device that can be bound
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct HidDeviceInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub name: String,
+}
+
+/// The bit a button sets in its device's input report
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+pub struct HidButton {
+    pub byte_index: u32,
+    pub mask: u8,
+}
+
+fn open_api() -> Result<HidApi, String> {
+    HidApi::new().map_err(|e| format!("Failed to access HID devices: {}", e))
+}
+
+fn non_empty(s: Option<&str>) -> Option<&str> {
+    s.map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn device_name(info: &DeviceInfo) -> String {
+    match (
+        non_empty(info.manufacturer_string()),
+        non_empty(info.product_string()),
+    ) {
+        (Some(manufacturer), Some(product)) if !product.starts_with(manufacturer) => {
+            format!("{} {}", manufacturer, product)
+        }
+        (_, Some(product)) => product.to_string(),
+        (Some(manufacturer), None) => manufacturer.to_string(),
+        (None, None) => format!("{:04x}:{:04x}", info.vendor_id(), info.product_id()),
+    }
+}
+
+/// Connected HID devices, one entry per device rather than per interface
+pub fn list_devices() -> Result<Vec<HidDeviceInfo>, String> {
+    let api = open_api()?;
+    let mut devices: Vec<HidDeviceInfo> = Vec::new();
+    for info in api.device_list() {
+        let known = devices
+            .iter()
+            .any(|d| d.vendor_id == info.vendor_id() && d.product_id == info.product_id());
+        if !known {
+            devices.push(HidDeviceInfo {
+                vendor_id: info.vendor_id(),
+                product_id: info.product_id(),
+                name: device_name(info),
+            });
+        }
+    }
+    devices.sort_by_key(|d| d.name.to_lowercase());
+    Ok(devices)
+}
+
+/// Waits for a button on the device to be pressed and released and returns
+/// the bit it set
+pub fn capture_button(vendor_id: u16, product_id: u16) -> Result<HidButton, String> {
+    let api = open_api()?;
+    let device = api
+        .open(vendor_id, product_id)
+        .map_err(|e| format!("Failed to open HID device: {}", e))?;
+
+    // Look for a bit that clears again: report IDs and other constant bytes
+    // never do, and most pedals only report changes, so the first report may
+    // already be the press
+    let mut previous = Vec::new();
+    let mut buf = [0u8; REPORT_SIZE];
+    let deadline = Instant::now() + CAPTURE_TIMEOUT;
+    while Instant::now() < deadline {
+        let len = device
+            .read_timeout(&mut buf, READ_TIMEOUT_MS)
+            .map_err(|e| format!("Failed to read HID device: {}", e))?;
+        if len == 0 {
+            continue;
+        }
+        let report = &buf[..len];
+        if let Some(button) = released_button(&previous, report) {
+            return Ok(button);
+        }
+        previous = report.to_vec();
+    }
+    Err("No button was pressed on the device".to_string())
+}
+
+/// The lowest bit set in `previous` that `report` cleared
+fn released_button(previous: &[u8], report: &[u8]) -> Option<HidButton> {
+    previous.iter().enumerate().find_map(|(index, byte)| {
+        let cleared = byte & !report.get(index).copied().unwrap_or(0);
+        (cleared != 0).then(|| HidButton {
+            byte_index: index as u32,
+            mask: cleared & cleared.wrapping_neg(),
+        })
+    })
+}
+
+fn is_down(report: &[u8], binding: &HidBinding) -> bool {
+    report
+        .get(binding.byte_index as usize)
+        .is_some_and(|byte| byte & binding.mask == binding.mask)
+}
+
+fn device_key(binding: &HidBinding) -> (u16, u16) {
+    (binding.vendor_id, binding.product_id)
+}
+
+/// Start reading bound devices if HID triggers are enabled. Called again
+/// whenever the settings change; the thread is only started once.
+pub fn start(app: &AppHandle) {
+    if !get_settings(app).hid_triggers_enabled {
+        return;
+    }
+    if MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("hid-triggers".into())
+        .spawn(move || monitor(app));
+    if let Err(e) = spawned {
+        error!("Failed to spawn HID trigger monitor: {}", e);
+        MONITOR_STARTED.store(false, Ordering::SeqCst);
+    }
+}
+
+struct OpenDevice {
+    device: HidDevice,
+    /// The last report, to tell presses from releases
+    report: Vec<u8>,
+}
+
+fn monitor(app: AppHandle) {
+    let mut api = match open_api() {
+        Ok(api) => api,
+        Err(e) => {
+            error!("{}", e);
+            MONITOR_STARTED.store(false, Ordering::SeqCst);
+            return;
+        }
+    };
+
+    let mut devices: HashMap<(u16, u16), OpenDevice> = HashMap::new();
+    let mut bindings: Vec<HidBinding> = Vec::new();
+    // The binding currently held for push-to-talk
+    let mut talking: Option<String> = None;
+    let mut last_scan: Option<Instant> = None;
+    let mut buf = [0u8; REPORT_SIZE];
+
+    loop {
+        if last_scan.is_none_or(|at| at.elapsed() >= RESCAN_INTERVAL) {
+            let settings = get_settings(&app);
+            bindings = if settings.hid_triggers_enabled {
+                settings.hid_bindings
+            } else {
+                Vec::new()
+            };
+            devices.retain(|key, _| bindings.iter().any(|b| device_key(b) == *key));
+
+            if bindings
+                .iter()
+                .any(|b| !devices.contains_key(&device_key(b)))
+            {
+                let _ = api.refresh_devices();
+                for binding in &bindings {
+                    let key = device_key(binding);
+                    if devices.contains_key(&key) {
+                        continue;
+                    }
+                    // Not plugged in is the usual reason; try again next scan
+                    if let Ok(device) = api.open(key.0, key.1) {
+                        info!("Reading HID device {:04x}:{:04x}", key.0, key.1);
+                        devices.insert(
+                            key,
+                            OpenDevice {
+                                device,
+                                report: Vec::new(),
+                            },
+                        );
+                    }
+                }
+            }
+            last_scan = Some(Instant::now());
+        }
+
+        let mut lost = Vec::new();
+        for (key, open) in devices.iter_mut() {
+            match open.device.read_timeout(&mut buf, READ_TIMEOUT_MS) {
+                Ok(0) => {}
+                Ok(len) => {
+                    let report = &buf[..len];
+                    for binding in bindings.iter().filter(|b| device_key(b) == *key) {
+                        let down = is_down(report, binding);
+                        if down != is_down(&open.report, binding) {
+                            on_button(&app, binding, down, &mut talking);
+                        }
+                    }
+                    open.report = report.to_vec();
+                }
+                Err(e) => {
+                    warn!("Lost HID device {:04x}:{:04x}: {}", key.0, key.1, e);
+                    lost.push(*key);
+                }
+            }
+        }
+        for key in lost {
+            devices.remove(&key);
+        }
+
+        // Don't leave a recording running when its pedal is gone or unbound
+        if let Some(id) = &talking {
+            let still_held = bindings
+                .iter()
+                .any(|b| &b.id == id && devices.contains_key(&device_key(b)));
+            if !still_held {
+                talking = None;
+                super::push_to_talk(&app, false, SOURCE);
+            }
+        }
+
+        if devices.is_empty() {
+            thread::sleep(RESCAN_INTERVAL);
+        }
+    }
+}
+
+fn on_button(app: &AppHandle, binding: &HidBinding, pressed: bool, talking: &mut Option<String>) {
+    debug!(
+        "HID button '{}' {}, action {:?}",
+        binding.name,
+        if pressed { "pressed" } else { "released" },
+        binding.action
+    );
+
+    match binding.action {
+        HidButtonAction::ToggleRecording if pressed => super::toggle_transcription(app, SOURCE),
+        HidButtonAction::StartRecording if pressed => super::set_transcription(app, true, SOURCE),
+        HidButtonAction::StopRecording if pressed => super::set_transcription(app, false, SOURCE),
+        HidButtonAction::PushToTalk => {
+            if pressed && talking.is_none() {
+                *talking = Some(binding.id.clone());
+                super::push_to_talk(app, true, SOURCE);
+            } else if !pressed && talking.as_ref() == Some(&binding.id) {
+                *talking = None;
+                super::push_to_talk(app, false, SOURCE);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_button_ignores_bits_that_stay_set() {
+        // Report ID byte, then one bit per pedal
+        assert_eq!(released_button(&[], &[1, 0b010]), None);
+        assert_eq!(released_button(&[1, 0b010], &[1, 0b010]), None);
+        assert_eq!(
+            released_button(&[1, 0b110], &[1, 0]),
+            Some(HidButton {
+                byte_index: 1,
+                mask: 0b010
+            })
+        );
+        assert_eq!(
+            released_button(&[1, 0b110], &[1, 0b010]),
+            Some(HidButton {
+                byte_index: 1,
+                mask: 0b100
+            })
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! The low-level input listener shared by hardware keys, mouse buttons and
+//! double-tap shortcuts.
+//!
+//! rdev keeps a single global hook callback on Windows and macOS, so a second
+//! `rdev::listen` would replace the first. One listener is started for the
+//! lifetime of the app and hands each event to the features that use it.
+
+use log::error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use tauri::AppHandle;
+
+use super::{double_tap, hardware_keys, mouse_buttons};
+
+static LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Start the listener unless it is already running
+pub fn start(app: &AppHandle) {
+    if LISTENER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let listener_app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("input-listener".into())
+        .spawn(move || {
+            let result = rdev::listen(move |event| match event.event_type {
+                rdev::EventType::KeyPress(key) => {
+                    hardware_keys::on_key_press(&listener_app, key);
+                    double_tap::on_key(&listener_app, key, true);
+                }
+                rdev::EventType::KeyRelease(key) => double_tap::on_key(&listener_app, key, false),
+                rdev::EventType::ButtonPress(button) => {
+                    mouse_buttons::on_button(&listener_app, button, true)
+                }
+                rdev::EventType::ButtonRelease(button) => {
+                    mouse_buttons::on_button(&listener_app, button, false)
+                }
+                _ => {}
+            });
+            if let Err(e) = result {
+                error!("Input listener stopped: {:?}", e);
+            }
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn input listener: {}", e);
+        LISTENER_STARTED.store(false, Ordering::SeqCst);
+    }
+}
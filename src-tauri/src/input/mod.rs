@@ -1,7 +1,17 @@
+pub mod double_tap;
+pub mod hardware_keys;
+pub mod hid;
+mod listener;
+pub mod mouse_buttons;
+
 use enigo::{Enigo, Key, Keyboard, Mouse, Settings};
+use log::warn;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 
+use crate::actions::ACTION_MAP;
+use crate::ManagedToggleState;
+
 /// Wrapper for Enigo to store in Tauri's managed state.
 /// Enigo is wrapped in a Mutex since it requires mutable access.
 pub struct EnigoState(pub Mutex<Enigo>);
@@ -121,3 +131,61 @@ pub fn paste_text_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Same toggle the transcribe shortcut performs in toggle mode, for triggers
+/// outside the global shortcuts. `source` stands in for the shortcut string.
+pub fn toggle_transcription(app: &AppHandle, source: &str) {
+    switch_transcription(app, source, |active| !active);
+}
+
+/// Start or stop dictation unless it is already in that state, sharing the
+/// toggle state with the transcribe shortcut
+pub fn set_transcription(app: &AppHandle, active: bool, source: &str) {
+    switch_transcription(app, source, |_| active);
+}
+
+fn switch_transcription(app: &AppHandle, source: &str, next: impl FnOnce(bool) -> bool) {
+    let binding_id = "transcribe";
+    let Some(action) = ACTION_MAP.get(binding_id) else {
+        warn!("No action defined in ACTION_MAP for binding ID '{binding_id}'");
+        return;
+    };
+
+    // Release the lock before calling the action, which may take it itself
+    let (was_active, should_start) = {
+        let toggle_state_manager = app.state::<ManagedToggleState>();
+        let mut states = toggle_state_manager
+            .lock()
+            .expect("Failed to lock toggle state manager");
+        let is_currently_active = states
+            .active_toggles
+            .entry(binding_id.to_string())
+            .or_insert(false);
+        let was_active = *is_currently_active;
+        *is_currently_active = next(was_active);
+        (was_active, *is_currently_active)
+    };
+
+    if should_start == was_active {
+        return;
+    }
+    if should_start {
+        action.start(app, binding_id, source);
+    } else {
+        action.stop(app, binding_id, source);
+    }
+}
+
+/// Same start and stop the transcribe shortcut performs in push-to-talk mode
+pub fn push_to_talk(app: &AppHandle, pressed: bool, source: &str) {
+    let binding_id = "transcribe";
+    let Some(action) = ACTION_MAP.get(binding_id) else {
+        warn!("No action defined in ACTION_MAP for binding ID '{binding_id}'");
+        return;
+    };
+    if pressed {
+        action.start(app, binding_id, source);
+    } else {
+        action.stop(app, binding_id, source);
+    }
+}
@@ -0,0 +1,116 @@
+//! Back and forward mouse buttons (MB4/MB5) as dictation triggers.
+//!
+//! The global shortcut plugin only handles keys, so side buttons are picked
+//! up by the shared input listener and mapped to the actions in the
+//! `mouse_buttons` settings. Like hardware keys, the listener only observes
+//! clicks; the app under the cursor still receives them.
+//!
+//! rdev reports side buttons as `Button::Unknown` with the platform's own
+//! number: 8 and 9 from X11, XBUTTON1 and XBUTTON2 on Windows. macOS only
+//! delivers left and right clicks to the listener.
+
+use log::{debug, error, info};
+use std::sync::mpsc::{self, Sender};
+use std::sync::OnceLock;
+use std::thread;
+use tauri::AppHandle;
+
+use super::listener;
+use crate::settings::{get_settings, MouseButtonAction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(any(target_os = "linux", target_os = "windows")), allow(dead_code))]
+enum MouseButton {
+    Back,
+    Forward,
+}
+
+#[cfg(target_os = "linux")]
+const BUTTON_CODES: &[(u8, MouseButton)] = &[(8, MouseButton::Back), (9, MouseButton::Forward)];
+#[cfg(target_os = "windows")]
+const BUTTON_CODES: &[(u8, MouseButton)] = &[(1, MouseButton::Back), (2, MouseButton::Forward)];
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+const BUTTON_CODES: &[(u8, MouseButton)] = &[];
+
+/// Button events in the order they happened, for the worker thread
+static EVENTS: OnceLock<Sender<(MouseButton, bool)>> = OnceLock::new();
+
+fn mouse_button(button: rdev::Button) -> Option<MouseButton> {
+    let rdev::Button::Unknown(code) = button else {
+        return None;
+    };
+    BUTTON_CODES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, mouse_button)| *mouse_button)
+}
+
+/// Start listening if mouse buttons are enabled. Called again whenever the
+/// setting changes; button events are ignored while the feature is off.
+pub fn start(app: &AppHandle) {
+    if !get_settings(app).mouse_buttons.enabled {
+        return;
+    }
+    if BUTTON_CODES.is_empty() {
+        info!("Mouse button bindings are not supported on this platform");
+        return;
+    }
+    listener::start(app);
+}
+
+/// Called by the input listener for every button press and release
+pub(super) fn on_button(app: &AppHandle, button: rdev::Button, pressed: bool) {
+    let Some(button) = mouse_button(button) else {
+        return;
+    };
+    // Keep the hook callback short, but unlike hardware keys hand the events
+    // to a single thread so a push-to-talk release never overtakes its press
+    let sender = EVENTS.get_or_init(|| spawn_worker(app.clone()));
+    let _ = sender.send((button, pressed));
+}
+
+fn spawn_worker(app: AppHandle) -> Sender<(MouseButton, bool)> {
+    let (sender, receiver) = mpsc::channel::<(MouseButton, bool)>();
+    let spawned = thread::Builder::new()
+        .name("mouse-buttons".into())
+        .spawn(move || {
+            // The button currently held for push-to-talk
+            let mut talking: Option<MouseButton> = None;
+            for (button, pressed) in receiver {
+                if pressed {
+                    on_press(&app, button, &mut talking);
+                } else if talking == Some(button) {
+                    // Finish even if the setting changed mid-press
+                    talking = None;
+                    super::push_to_talk(&app, false, "mouse-button");
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        error!("Failed to spawn mouse button worker: {}", e);
+    }
+    sender
+}
+
+fn on_press(app: &AppHandle, button: MouseButton, talking: &mut Option<MouseButton>) {
+    let settings = get_settings(app).mouse_buttons;
+    if !settings.enabled {
+        return;
+    }
+    let action = match button {
+        MouseButton::Back => settings.back,
+        MouseButton::Forward => settings.forward,
+    };
+    debug!("Mouse button {:?} pressed, action {:?}", button, action);
+
+    match action {
+        MouseButtonAction::Disabled => {}
+        MouseButtonAction::ToggleRecording => super::toggle_transcription(app, "mouse-button"),
+        MouseButtonAction::PushToTalk => {
+            if talking.is_none() {
+                *talking = Some(button);
+                super::push_to_talk(app, true, "mouse-button");
+            }
+        }
+    }
+}
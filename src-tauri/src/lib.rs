@@ -3,35 +3,62 @@ mod actions;
 mod apple_intelligence;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod chapters;
 mod clipboard;
 mod commands;
 mod context;
+mod cycle;
+mod dual_script;
+mod encryption;
+mod events;
+mod focus_mode;
+mod grammar;
 mod groq_transcription;
 mod helpers;
 mod input;
 mod llm_client;
+#[cfg(feature = "local-llm")]
+mod local_llm;
 mod managers;
+mod media_download;
+mod meeting_detection;
+mod metrics;
 mod overlay;
+mod proxy;
+mod quiet_hours;
+mod recording_protocol;
+mod remote_control;
 mod settings;
 mod shortcut;
 mod signal_handle;
+mod task_providers;
+mod translation;
 mod tray;
 mod tray_i18n;
+mod usage;
 mod utils;
+mod webhook;
 use specta_typescript::{BigIntExportBehavior, Typescript};
-use tauri_specta::{collect_commands, Builder};
+use tauri_specta::{collect_commands, Builder, Event};
 
 use env_filter::Builder as EnvFilterBuilder;
+#[cfg(feature = "local-llm")]
+use local_llm::LocalLlmManager;
 use managers::audio::AudioRecordingManager;
 use managers::diarization::DiarizationManager;
 use managers::file_transcription::FileTranscriptionManager;
 use managers::history::HistoryManager;
+use managers::history_sync::HistorySyncManager;
 use managers::live_preview::LivePreviewManager;
 use managers::meeting::MeetingManager;
 use managers::model::ModelManager;
+use managers::punctuation::PunctuationManager;
+use managers::settings_writer::SettingsWriter;
 use managers::system_audio::SystemAudioManager;
 use managers::transcription::TranscriptionManager;
+use managers::wake_word::WakeWordManager;
 use managers::watch_folder::WatchFolderManager;
+use metrics::MetricsServer;
 #[cfg(unix)]
 use signal_hook::consts::SIGUSR2;
 #[cfg(unix)]
@@ -39,14 +66,15 @@ use signal_hook::iterator::Signals;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 use tauri::image::Image;
 
 use tauri::tray::TrayIconBuilder;
-use tauri::Emitter;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
 use tauri_plugin_log::{Builder as LogBuilder, RotationStrategy, Target, TargetKind};
 
+use crate::events::{CheckForUpdates, NavigateToSettings};
 use crate::settings::get_settings;
 
 // Global atomic to store the file log level filter
@@ -91,6 +119,9 @@ fn build_console_filter() -> env_filter::Filter {
 struct ShortcutToggleStates {
     // Map: shortcut_binding_id -> is_active
     active_toggles: HashMap<String, bool>,
+    // Map: shortcut_binding_id -> when the hybrid push-to-talk press that
+    // started it went down, until it is released
+    held_since: HashMap<String, Instant>,
 }
 
 type ManagedToggleState = Mutex<ShortcutToggleStates>;
@@ -123,18 +154,37 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     // after onboarding completes. This avoids triggering permission dialogs
     // on macOS before the user is ready.
 
+    // Route all settings changes through a single writer before anything else can write
+    let settings_writer =
+        Arc::new(SettingsWriter::new(app_handle).expect("Failed to initialize settings writer"));
+    settings_writer.start_watchdog();
+    app_handle.manage(settings_writer);
+
+    // Every HTTP client built from here on goes through the configured proxy
+    proxy::configure(&settings::get_settings(app_handle).proxy);
+
     // Initialize the managers
     let recording_manager = Arc::new(
         AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
     );
     let model_manager =
         Arc::new(ModelManager::new(app_handle).expect("Failed to initialize model manager"));
+    #[cfg(feature = "local-llm")]
+    {
+        let local_llm_manager = Arc::new(LocalLlmManager::new(model_manager.clone()));
+        LocalLlmManager::register(local_llm_manager.clone());
+        app_handle.manage(local_llm_manager);
+    }
     let transcription_manager = Arc::new(
         TranscriptionManager::new(app_handle, model_manager.clone())
             .expect("Failed to initialize transcription manager"),
     );
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
+    let history_sync_manager = Arc::new(
+        HistorySyncManager::new(app_handle, history_manager.clone())
+            .expect("Failed to initialize history sync manager"),
+    );
     let meeting_manager =
         Arc::new(MeetingManager::new(app_handle).expect("Failed to initialize meeting manager"));
     let live_preview_manager = Arc::new(LivePreviewManager::new(
@@ -142,18 +192,29 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         transcription_manager.clone(),
     ));
     let file_transcription_manager = Arc::new(
-        FileTranscriptionManager::new(app_handle, transcription_manager.clone())
-            .expect("Failed to initialize file transcription manager"),
+        FileTranscriptionManager::new(
+            app_handle,
+            transcription_manager.clone(),
+            history_manager.clone(),
+        )
+        .expect("Failed to initialize file transcription manager"),
     );
     let watch_folder_manager = Arc::new(
-        WatchFolderManager::new(app_handle).expect("Failed to initialize watch folder manager"),
+        WatchFolderManager::new(app_handle, history_manager.clone())
+            .expect("Failed to initialize watch folder manager"),
     );
     let diarization_manager = Arc::new(
         DiarizationManager::new(app_handle).expect("Failed to initialize diarization manager"),
     );
+    let punctuation_manager = Arc::new(
+        PunctuationManager::new(app_handle).expect("Failed to initialize punctuation manager"),
+    );
     let system_audio_manager = Arc::new(
         SystemAudioManager::new(app_handle).expect("Failed to initialize system audio manager"),
     );
+    let wake_word_manager =
+        Arc::new(WakeWordManager::new(app_handle).expect("Failed to initialize wake word manager"));
+    let metrics_server = Arc::new(MetricsServer::new(app_handle));
 
     // Wire up the live preview manager to the recording manager
     recording_manager.set_live_preview_manager(live_preview_manager.clone());
@@ -163,17 +224,47 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(model_manager.clone());
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
+    usage::register(history_manager.clone());
+    llm_client::cache::register(history_manager.clone());
+    llm_client::cache::set_enabled(settings::get_settings(app_handle).llm_cache_enabled);
+    app_handle.manage(history_sync_manager.clone());
     app_handle.manage(meeting_manager.clone());
     app_handle.manage(live_preview_manager.clone());
     app_handle.manage(file_transcription_manager.clone());
     app_handle.manage(watch_folder_manager.clone());
     app_handle.manage(diarization_manager.clone());
+    app_handle.manage(punctuation_manager.clone());
     app_handle.manage(system_audio_manager.clone());
+    app_handle.manage(wake_word_manager.clone());
+    app_handle.manage(metrics_server.clone());
 
     // Start watching all enabled folders
     if let Err(e) = watch_folder_manager.start_all() {
         log::error!("Failed to start watch folders: {}", e);
     }
+    watch_folder_manager.start_health_check();
+    quiet_hours::start_monitor(app_handle);
+    focus_mode::start_monitor(app_handle);
+    meeting_detection::start_monitor(app_handle);
+    input::hardware_keys::start(app_handle);
+    input::mouse_buttons::start(app_handle);
+    input::hid::start(app_handle);
+    if let Err(e) = wake_word_manager.apply_settings() {
+        log::warn!("Wake word listening not started: {}", e);
+    }
+
+    if let Some(reason) = history_manager.lock_reason() {
+        show_history_locked_dialog(app_handle, reason);
+    }
+
+    // Convert recordings left over from before FLAC storage
+    history_manager.start_recording_migration();
+    history_manager.start_retention_task();
+    history_sync_manager.start();
+
+    if let Err(e) = metrics_server.apply_settings() {
+        log::error!("Failed to start metrics endpoint: {}", e);
+    }
 
     // Initialize the shortcuts
     shortcut::init_shortcuts(app_handle);
@@ -213,18 +304,24 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         .on_menu_event(|app, event| match event.id.as_ref() {
             "settings" => {
                 show_main_window(app);
-                let _ = app.emit("navigate-to-settings", ());
+                let _ = NavigateToSettings.emit(app);
             }
             "check_updates" => {
                 let settings = settings::get_settings(app);
                 if settings.update_checks_enabled {
                     show_main_window(app);
-                    let _ = app.emit("check-for-updates", ());
+                    let _ = CheckForUpdates.emit(app);
                 }
             }
             "copy_last_transcript" => {
                 tray::copy_last_transcript(app);
             }
+            id if id.starts_with(tray::SHORTCUT_PROFILE_ITEM_PREFIX) => {
+                let profile_id = id[tray::SHORTCUT_PROFILE_ITEM_PREFIX.len()..].to_string();
+                if let Err(e) = shortcut::switch_shortcut_profile(app.clone(), profile_id) {
+                    log::warn!("Failed to switch shortcut profile: {}", e);
+                }
+            }
             "cancel" => {
                 use crate::utils::cancel_current_operation;
 
@@ -236,6 +333,13 @@ fn initialize_core_logic(app_handle: &AppHandle) {
                 if let Some(watch_manager) = app.try_state::<Arc<WatchFolderManager>>() {
                     watch_manager.shutdown();
                 }
+                if let Some(metrics_server) = app.try_state::<Arc<MetricsServer>>() {
+                    metrics_server.shutdown();
+                }
+                // Make sure debounced settings changes reach disk
+                if let Some(settings_writer) = app.try_state::<Arc<SettingsWriter>>() {
+                    settings_writer.shutdown();
+                }
                 app.exit(0);
             }
             _ => {}
@@ -263,6 +367,23 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     utils::create_recording_overlay(app_handle);
 }
 
+/// Tell the user their encrypted history could not be unlocked. Dictation
+/// keeps working; nothing is saved to history until the key is back.
+fn show_history_locked_dialog(app_handle: &AppHandle, reason: &str) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+    app_handle
+        .dialog()
+        .message(format!(
+            "{}.\n\nDictation still works, but nothing is saved to history until the \
+             key is restored to the keychain and PaperFlow is restarted.",
+            reason
+        ))
+        .title("History is locked")
+        .kind(MessageDialogKind::Warning)
+        .show(|_| {});
+}
+
 #[tauri::command]
 #[specta::specta]
 fn trigger_update_check(app: AppHandle) -> Result<(), String> {
@@ -270,8 +391,7 @@ fn trigger_update_check(app: AppHandle) -> Result<(), String> {
     if !settings.update_checks_enabled {
         return Ok(());
     }
-    app.emit("check-for-updates", ())
-        .map_err(|e| e.to_string())?;
+    CheckForUpdates.emit(&app).map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -281,163 +401,282 @@ pub fn run() {
     // when the variable is unset
     let console_filter = build_console_filter();
 
-    let specta_builder = Builder::<tauri::Wry>::new().commands(collect_commands![
-        shortcut::change_binding,
-        shortcut::reset_binding,
-        shortcut::change_ptt_setting,
-        shortcut::change_audio_feedback_setting,
-        shortcut::change_audio_feedback_volume_setting,
-        shortcut::change_sound_theme_setting,
-        shortcut::change_start_hidden_setting,
-        shortcut::change_autostart_setting,
-        shortcut::change_translate_to_english_setting,
-        shortcut::change_selected_language_setting,
-        shortcut::change_overlay_position_setting,
-        shortcut::change_debug_mode_setting,
-        shortcut::change_word_correction_threshold_setting,
-        shortcut::change_paste_method_setting,
-        shortcut::change_clipboard_handling_setting,
-        shortcut::change_post_process_enabled_setting,
-        shortcut::change_post_process_base_url_setting,
-        shortcut::change_post_process_api_key_setting,
-        shortcut::change_post_process_model_setting,
-        shortcut::set_post_process_provider,
-        shortcut::fetch_post_process_models,
-        shortcut::add_post_process_prompt,
-        shortcut::update_post_process_prompt,
-        shortcut::delete_post_process_prompt,
-        shortcut::set_post_process_selected_prompt,
-        shortcut::update_custom_words,
-        shortcut::suspend_binding,
-        shortcut::resume_binding,
-        shortcut::change_mute_while_recording_setting,
-        shortcut::change_append_trailing_space_setting,
-        shortcut::change_app_language_setting,
-        shortcut::change_update_checks_setting,
-        shortcut::change_snippets_enabled_setting,
-        shortcut::update_snippets,
-        shortcut::change_auto_format_enabled_setting,
-        shortcut::change_auto_format_lists_setting,
-        shortcut::change_verbal_commands_enabled_setting,
-        shortcut::change_tone_adjustment_enabled_setting,
-        shortcut::change_default_tone_setting,
-        shortcut::update_app_tone_mappings,
-        shortcut::change_developer_mode_setting,
-        shortcut::change_preserve_code_syntax_setting,
-        shortcut::update_developer_dictionary,
-        shortcut::change_correction_detection_enabled_setting,
-        shortcut::change_context_awareness_enabled_setting,
-        shortcut::update_context_per_app_permissions,
-        shortcut::change_groq_transcription_api_key_setting,
-        shortcut::change_multilingual_mode_enabled_setting,
-        shortcut::change_primary_language_setting,
-        shortcut::change_secondary_language_setting,
-        shortcut::change_language_detection_sensitivity_setting,
-        shortcut::change_show_meeting_menu_setting,
-        shortcut::change_meeting_mode_enabled_setting,
-        shortcut::change_meeting_chunk_duration_setting,
-        shortcut::change_meeting_auto_summarize_setting,
-        shortcut::change_meeting_extract_action_items_setting,
-        shortcut::change_meeting_summary_prompt_setting,
-        shortcut::change_meeting_action_items_prompt_setting,
-        shortcut::change_live_preview_enabled_setting,
-        shortcut::change_live_preview_interval_setting,
-        shortcut::change_whisper_mode_enabled_setting,
-        shortcut::change_vad_threshold_setting,
-        commands::meeting::get_meeting_state,
-        commands::meeting::get_current_meeting_session,
-        commands::meeting::get_meeting_elapsed_seconds,
-        commands::meeting::start_meeting,
-        commands::meeting::stop_meeting,
-        commands::meeting::cancel_meeting,
-        commands::meeting::get_meeting_history,
-        commands::meeting::delete_meeting,
-        trigger_update_check,
-        commands::cancel_operation,
-        commands::get_app_dir_path,
-        commands::get_app_settings,
-        commands::get_default_settings,
-        commands::get_log_dir_path,
-        commands::set_log_level,
-        commands::open_recordings_folder,
-        commands::open_log_dir,
-        commands::open_app_data_dir,
-        commands::check_apple_intelligence_available,
-        commands::initialize_enigo,
-        commands::models::get_available_models,
-        commands::models::get_model_info,
-        commands::models::download_model,
-        commands::models::delete_model,
-        commands::models::cancel_download,
-        commands::models::set_active_model,
-        commands::models::get_current_model,
-        commands::models::get_transcription_model_status,
-        commands::models::is_model_loading,
-        commands::models::has_any_models_available,
-        commands::models::has_any_models_or_downloads,
-        commands::models::get_recommended_first_model,
-        commands::models::download_coreml_model,
-        commands::models::delete_coreml_model,
-        commands::models::is_coreml_available,
-        commands::models::validate_groq_api_key,
-        commands::models::model_requires_api_key,
-        commands::models::is_groq_api_key_configured,
-        commands::audio::update_microphone_mode,
-        commands::audio::get_microphone_mode,
-        commands::audio::get_available_microphones,
-        commands::audio::set_selected_microphone,
-        commands::audio::get_selected_microphone,
-        commands::audio::get_available_output_devices,
-        commands::audio::set_selected_output_device,
-        commands::audio::get_selected_output_device,
-        commands::audio::play_test_sound,
-        commands::audio::check_custom_sounds,
-        commands::audio::set_clamshell_microphone,
-        commands::audio::get_clamshell_microphone,
-        commands::audio::is_recording,
-        commands::audio::get_system_audio_info,
-        commands::audio::is_native_system_audio_available,
-        commands::audio::start_system_audio_capture,
-        commands::audio::stop_system_audio_capture,
-        commands::audio::is_capturing_system_audio,
-        commands::diarization::get_diarization_status,
-        commands::diarization::change_diarization_enabled_setting,
-        commands::diarization::get_diarization_model_info,
-        commands::diarization::download_diarization_models,
-        commands::transcription::set_model_unload_timeout,
-        commands::transcription::get_model_load_status,
-        commands::transcription::unload_model_manually,
-        commands::history::get_history_entries,
-        commands::history::toggle_history_entry_saved,
-        commands::history::get_audio_file_path,
-        commands::history::delete_history_entry,
-        commands::history::update_history_limit,
-        commands::history::update_recording_retention_period,
-        commands::file_transcription::get_supported_file_extensions,
-        commands::file_transcription::queue_file_for_transcription,
-        commands::file_transcription::queue_files_for_transcription,
-        commands::file_transcription::process_next_file,
-        commands::file_transcription::process_all_files,
-        commands::file_transcription::cancel_file_transcription,
-        commands::file_transcription::cancel_file_transcription_job,
-        commands::file_transcription::get_file_transcription_jobs,
-        commands::file_transcription::get_file_transcription_job,
-        commands::file_transcription::clear_completed_file_jobs,
-        commands::file_transcription::remove_file_transcription_job,
-        commands::file_transcription::is_file_transcription_processing,
-        commands::export::export_transcript,
-        commands::export::export_transcript_to_file,
-        commands::export::get_export_file_extension,
-        commands::export::get_available_export_formats,
-        commands::watch_folder::get_watch_folders,
-        commands::watch_folder::add_watch_folder,
-        commands::watch_folder::remove_watch_folder,
-        commands::watch_folder::update_watch_folder,
-        commands::watch_folder::get_watch_folder_status,
-        commands::watch_folder::start_watch_folder,
-        commands::watch_folder::stop_watch_folder,
-        helpers::clamshell::is_laptop,
-    ]);
+    let specta_builder = Builder::<tauri::Wry>::new()
+        .commands(collect_commands![
+            shortcut::change_binding,
+            shortcut::reset_binding,
+            shortcut::add_shortcut_profile,
+            shortcut::delete_shortcut_profile,
+            shortcut::switch_shortcut_profile,
+            shortcut::change_ptt_setting,
+            shortcut::change_ptt_hybrid_mode_setting,
+            shortcut::change_ptt_latch_threshold_setting,
+            shortcut::change_double_tap_timeout_setting,
+            shortcut::change_audio_feedback_setting,
+            shortcut::change_audio_feedback_volume_setting,
+            shortcut::change_sound_theme_setting,
+            shortcut::change_start_hidden_setting,
+            shortcut::change_autostart_setting,
+            shortcut::change_translate_to_english_setting,
+            shortcut::change_selected_language_setting,
+            shortcut::change_overlay_position_setting,
+            shortcut::change_debug_mode_setting,
+            shortcut::change_word_correction_threshold_setting,
+            shortcut::change_word_match_algorithm_setting,
+            shortcut::update_word_correction_thresholds,
+            shortcut::change_paste_method_setting,
+            shortcut::change_clipboard_handling_setting,
+            shortcut::change_clipboard_restore_delay_setting,
+            shortcut::change_paste_into_recording_window_setting,
+            shortcut::change_post_process_enabled_setting,
+            shortcut::change_post_process_base_url_setting,
+            shortcut::change_post_process_api_key_setting,
+            shortcut::change_post_process_model_setting,
+            shortcut::set_post_process_provider,
+            shortcut::set_post_process_fallback_provider,
+            shortcut::change_post_process_mode_setting,
+            shortcut::change_grammar_max_edit_ratio_setting,
+            shortcut::change_translation_target_language_setting,
+            shortcut::update_app_translation_languages,
+            shortcut::fetch_post_process_models,
+            shortcut::add_post_process_prompt,
+            shortcut::update_post_process_prompt,
+            shortcut::delete_post_process_prompt,
+            shortcut::set_post_process_selected_prompt,
+            shortcut::update_app_prompt_mappings,
+            shortcut::change_llm_cache_enabled_setting,
+            shortcut::add_post_process_pipeline,
+            shortcut::update_post_process_pipeline,
+            shortcut::delete_post_process_pipeline,
+            shortcut::set_post_process_selected_pipeline,
+            shortcut::update_custom_words,
+            shortcut::update_custom_word_sets,
+            shortcut::change_filler_filter_setting,
+            shortcut::update_filler_words,
+            shortcut::change_stutter_settings,
+            shortcut::change_profanity_filter_setting,
+            shortcut::update_profanity_words,
+            shortcut::suspend_binding,
+            shortcut::resume_binding,
+            shortcut::change_mute_while_recording_setting,
+            shortcut::change_append_trailing_space_setting,
+            shortcut::change_app_language_setting,
+            shortcut::change_update_checks_setting,
+            shortcut::change_snippets_enabled_setting,
+            shortcut::update_snippets,
+            shortcut::update_regex_rules,
+            shortcut::test_regex_rule,
+            shortcut::get_text_pipeline,
+            shortcut::update_text_pipeline,
+            shortcut::remove_app_text_pipeline,
+            shortcut::change_auto_format_enabled_setting,
+            shortcut::change_auto_format_lists_setting,
+            shortcut::change_verbal_commands_enabled_setting,
+            shortcut::update_emoji_map,
+            shortcut::change_spell_mode_enabled_setting,
+            shortcut::change_itn_enabled_setting,
+            shortcut::change_itn_locale_setting,
+            shortcut::change_tone_adjustment_enabled_setting,
+            shortcut::change_default_tone_setting,
+            shortcut::update_app_tone_mappings,
+            shortcut::update_app_profiles,
+            shortcut::change_developer_mode_setting,
+            shortcut::change_preserve_code_syntax_setting,
+            shortcut::update_developer_dictionary,
+            shortcut::change_correction_detection_enabled_setting,
+            shortcut::change_context_awareness_enabled_setting,
+            shortcut::update_context_per_app_permissions,
+            shortcut::change_surrounding_text_in_llm_prompt_setting,
+            shortcut::change_screen_ocr_enabled_setting,
+            shortcut::change_groq_transcription_api_key_setting,
+            shortcut::change_multilingual_mode_enabled_setting,
+            shortcut::change_primary_language_setting,
+            shortcut::change_secondary_language_setting,
+            shortcut::change_language_detection_sensitivity_setting,
+            shortcut::change_show_meeting_menu_setting,
+            shortcut::change_meeting_mode_enabled_setting,
+            shortcut::change_meeting_chunk_duration_setting,
+            shortcut::change_meeting_auto_summarize_setting,
+            shortcut::change_meeting_extract_action_items_setting,
+            shortcut::change_meeting_summary_prompt_setting,
+            shortcut::change_meeting_action_items_prompt_setting,
+            shortcut::change_meeting_save_audio_setting,
+            shortcut::change_meeting_segment_topics_setting,
+            shortcut::change_meeting_webhook_url_setting,
+            shortcut::change_meeting_webhook_secret_setting,
+            shortcut::change_meeting_detection_enabled_setting,
+            shortcut::change_meeting_auto_start_setting,
+            shortcut::change_meeting_task_destinations_setting,
+            shortcut::change_live_preview_enabled_setting,
+            shortcut::change_live_preview_interval_setting,
+            shortcut::change_ffmpeg_path_setting,
+            shortcut::change_file_transcription_detect_chapters_setting,
+            shortcut::change_whisper_mode_enabled_setting,
+            shortcut::change_vad_threshold_setting,
+            shortcut::change_max_recording_duration_setting,
+            shortcut::change_metrics_endpoint_setting,
+            shortcut::change_proxy_settings,
+            shortcut::change_quiet_hours_setting,
+            shortcut::get_quiet_hours_active,
+            shortcut::change_focus_suppression_setting,
+            shortcut::get_focus_state,
+            shortcut::change_hardware_keys_setting,
+            shortcut::change_mouse_buttons_setting,
+            shortcut::change_cycle_lists_setting,
+            shortcut::change_typing_settings,
+            shortcut::change_hid_triggers_enabled_setting,
+            shortcut::get_hid_devices,
+            shortcut::capture_hid_button,
+            shortcut::add_hid_binding,
+            shortcut::remove_hid_binding,
+            shortcut::change_dual_script_setting,
+            shortcut::convert_to_dual_script,
+            commands::meeting::get_meeting_state,
+            commands::meeting::get_current_meeting_session,
+            commands::meeting::get_meeting_elapsed_seconds,
+            commands::meeting::start_meeting,
+            commands::meeting::stop_meeting,
+            commands::meeting::pause_meeting,
+            commands::meeting::resume_meeting,
+            commands::meeting::get_recoverable_meeting,
+            commands::meeting::resume_recovered_meeting,
+            commands::meeting::finalize_recovered_meeting,
+            commands::meeting::discard_recovered_meeting,
+            commands::meeting::push_meeting_action_items,
+            commands::meeting::cancel_meeting,
+            commands::meeting::get_meeting_history,
+            commands::meeting::delete_meeting,
+            commands::meeting::get_meeting_speakers,
+            commands::meeting::rename_meeting_speaker,
+            commands::meeting::get_meeting_chunk_audio_path,
+            trigger_update_check,
+            commands::cancel_operation,
+            commands::get_app_dir_path,
+            commands::get_app_settings,
+            commands::get_default_settings,
+            commands::get_log_dir_path,
+            commands::set_log_level,
+            commands::open_recordings_folder,
+            commands::open_log_dir,
+            commands::open_app_data_dir,
+            commands::check_apple_intelligence_available,
+            commands::initialize_enigo,
+            commands::models::get_available_models,
+            commands::models::get_local_llm_models,
+            commands::models::get_model_info,
+            commands::models::download_model,
+            commands::models::delete_model,
+            commands::models::cancel_download,
+            commands::models::get_interrupted_downloads,
+            commands::models::delete_partial_download,
+            commands::models::set_active_model,
+            commands::models::get_current_model,
+            commands::models::get_transcription_model_status,
+            commands::models::is_model_loading,
+            commands::models::has_any_models_available,
+            commands::models::has_any_models_or_downloads,
+            commands::models::get_recommended_first_model,
+            commands::models::download_coreml_model,
+            commands::models::delete_coreml_model,
+            commands::models::is_coreml_available,
+            commands::models::validate_groq_api_key,
+            commands::models::model_requires_api_key,
+            commands::models::is_groq_api_key_configured,
+            commands::audio::update_microphone_mode,
+            commands::audio::get_microphone_mode,
+            commands::audio::get_available_microphones,
+            commands::audio::set_selected_microphone,
+            commands::audio::get_selected_microphone,
+            commands::audio::get_available_output_devices,
+            commands::audio::set_selected_output_device,
+            commands::audio::get_selected_output_device,
+            commands::audio::play_test_sound,
+            commands::audio::check_custom_sounds,
+            commands::audio::set_clamshell_microphone,
+            commands::audio::get_clamshell_microphone,
+            commands::audio::is_recording,
+            commands::audio::set_simulated_input,
+            commands::audio::get_simulated_input,
+            commands::audio::get_system_audio_info,
+            commands::audio::is_native_system_audio_available,
+            commands::audio::start_system_audio_capture,
+            commands::audio::stop_system_audio_capture,
+            commands::audio::is_capturing_system_audio,
+            commands::diarization::get_diarization_status,
+            commands::diarization::change_diarization_enabled_setting,
+            commands::diarization::get_diarization_model_info,
+            commands::diarization::download_diarization_models,
+            commands::punctuation::get_punctuation_status,
+            commands::punctuation::change_punctuation_restoration_setting,
+            commands::punctuation::download_punctuation_model,
+            commands::wake_word::get_wake_word_status,
+            commands::wake_word::change_wake_word_enabled_setting,
+            commands::wake_word::change_wake_word_sensitivity_setting,
+            commands::wake_word::change_wake_word_model_setting,
+            commands::wake_word::download_wake_word_models,
+            commands::transcription::set_model_unload_timeout,
+            commands::transcription::get_model_load_status,
+            commands::transcription::unload_model_manually,
+            commands::history::get_history_entries,
+            commands::history::get_history_page,
+            commands::history::count_history_entries,
+            commands::history::toggle_history_entry_saved,
+            commands::history::get_audio_file_path,
+            commands::history::get_history_playback_path,
+            commands::history::get_history_waveform,
+            commands::history::delete_history_entry,
+            commands::history::reprocess_history_entry,
+            commands::history::get_history_entry_versions,
+            commands::history::import_transcript,
+            commands::history::get_api_usage_stats,
+            commands::history::clear_llm_cache,
+            commands::history::update_history_limit,
+            commands::history::update_recording_retention_period,
+            commands::history::update_retention_rules,
+            commands::history::apply_retention_rules,
+            commands::history::update_recording_format,
+            commands::history::set_history_encryption,
+            commands::history::set_history_sync,
+            commands::history::sync_history_now,
+            commands::file_transcription::get_supported_file_extensions,
+            commands::file_transcription::queue_file_for_transcription,
+            commands::file_transcription::queue_files_for_transcription,
+            commands::file_transcription::queue_url_for_transcription,
+            commands::file_transcription::queue_folder_for_transcription,
+            commands::file_transcription::process_next_file,
+            commands::file_transcription::process_all_files,
+            commands::file_transcription::cancel_file_transcription,
+            commands::file_transcription::cancel_file_transcription_job,
+            commands::file_transcription::get_file_transcription_jobs,
+            commands::file_transcription::get_file_transcription_job,
+            commands::file_transcription::clear_completed_file_jobs,
+            commands::file_transcription::remove_file_transcription_job,
+            commands::file_transcription::is_file_transcription_processing,
+            commands::file_transcription::move_file_transcription_job,
+            commands::file_transcription::set_file_transcription_job_priority,
+            commands::file_transcription::pause_file_transcription_queue,
+            commands::file_transcription::resume_file_transcription_queue,
+            commands::file_transcription::is_file_transcription_queue_paused,
+            commands::export::export_transcript,
+            commands::export::export_transcript_to_file,
+            commands::export::export_transcript_to_clipboard,
+            commands::export::get_export_file_extension,
+            commands::export::get_available_export_formats,
+            commands::timeline::export_timeline,
+            commands::timeline::export_timeline_to_file,
+            commands::meeting_export::export_meeting,
+            commands::meeting_export::export_meeting_to_clipboard,
+            commands::batch_export::export_transcripts_batch,
+            commands::watch_folder::get_watch_folders,
+            commands::watch_folder::add_watch_folder,
+            commands::watch_folder::remove_watch_folder,
+            commands::watch_folder::update_watch_folder,
+            commands::watch_folder::get_watch_folder_status,
+            commands::watch_folder::start_watch_folder,
+            commands::watch_folder::stop_watch_folder,
+            helpers::clamshell::is_laptop,
+        ])
+        .events(events::collect());
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
     specta_builder
@@ -477,8 +716,11 @@ pub fn run() {
     }
 
     builder
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            show_main_window(app);
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // Stream Deck plugins and scripts relaunch the app with a command
+            if !remote_control::handle_args(app, &args) {
+                show_main_window(app);
+            }
         }))
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
@@ -493,8 +735,15 @@ pub fn run() {
             MacosLauncher::LaunchAgent,
             Some(vec![]),
         ))
+        .register_asynchronous_uri_scheme_protocol(
+            recording_protocol::SCHEME,
+            recording_protocol::handle,
+        )
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .invoke_handler(specta_builder.invoke_handler())
         .setup(move |app| {
+            // Typed events panic if emitted before they are mounted
+            specta_builder.mount_events(app);
             let settings = get_settings(&app.handle());
             let tauri_log_level: tauri_plugin_log::LogLevel = settings.log_level.into();
             let file_log_level: log::Level = tauri_log_level.into();
@@ -535,7 +784,6 @@ pub fn run() {
             }
             _ => {}
         })
-        .invoke_handler(specta_builder.invoke_handler())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,205 @@
+//! Anthropic's native Messages API.
+//!
+//! Requests must carry `max_tokens`, replies are a list of content blocks
+//! and the stream is a sequence of typed events instead of OpenAI-style
+//! chunks. The `x-api-key` and `anthropic-version` headers are set by
+//! `build_headers`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChatUsage, SseLine};
+
+/// Longest reply, which the Messages API requires to be set
+const MAX_TOKENS: u32 = 4096;
+
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    messages: [Message<'a>; 1],
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    #[serde(default)]
+    content: Vec<ContentBlock>,
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct Usage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+impl From<Usage> for ChatUsage {
+    fn from(usage: Usage) -> Self {
+        ChatUsage {
+            prompt_tokens: usage.input_tokens,
+            completion_tokens: usage.output_tokens,
+        }
+    }
+}
+
+/// The streamed events, with only the fields used here
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    MessageStart {
+        message: StartedMessage,
+    },
+    ContentBlockDelta {
+        delta: TextDelta,
+    },
+    MessageDelta {
+        usage: Option<Usage>,
+    },
+    MessageStop,
+    Error {
+        error: ApiError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StartedMessage {
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize)]
+struct TextDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+pub(super) fn request(
+    client: &reqwest::Client,
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    client
+        .post(format!("{}/messages", base_url))
+        .json(&MessagesRequest {
+            model,
+            max_tokens: MAX_TOKENS,
+            messages: [Message {
+                role: "user",
+                content: prompt,
+            }],
+            stream,
+        })
+}
+
+/// Text and token counts of a complete reply
+pub(super) fn parse_response(body: &str) -> Result<(Option<String>, Option<ChatUsage>), String> {
+    let response: MessagesResponse =
+        serde_json::from_str(body).map_err(|e| format!("Failed to parse API response: {}", e))?;
+    let text: String = response
+        .content
+        .into_iter()
+        .filter(|block| block.kind == "text")
+        .filter_map(|block| block.text)
+        .collect();
+    Ok((
+        Some(text).filter(|text| !text.is_empty()),
+        response.usage.map(ChatUsage::from),
+    ))
+}
+
+pub(super) fn parse_sse_line(line: &str) -> SseLine {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return SseLine::Other;
+    };
+    match serde_json::from_str::<StreamEvent>(data) {
+        Ok(StreamEvent::MessageStart { message }) => SseLine::Chunk {
+            text: None,
+            usage: message.usage.map(ChatUsage::from),
+        },
+        Ok(StreamEvent::ContentBlockDelta { delta }) => SseLine::Chunk {
+            text: delta.text.filter(|text| !text.is_empty()),
+            usage: None,
+        },
+        Ok(StreamEvent::MessageDelta { usage }) => SseLine::Chunk {
+            text: None,
+            usage: usage.map(ChatUsage::from),
+        },
+        Ok(StreamEvent::MessageStop) => SseLine::Done,
+        Ok(StreamEvent::Error { error }) => SseLine::Error(error.message),
+        Ok(StreamEvent::Other) | Err(_) => SseLine::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_events_become_text_usage_and_done() {
+        assert_eq!(
+            parse_sse_line(
+                r#"data: {"type":"message_start","message":{"id":"msg_1","usage":{"input_tokens":25,"output_tokens":1}}}"#
+            ),
+            SseLine::Chunk {
+                text: None,
+                usage: Some(ChatUsage {
+                    prompt_tokens: 25,
+                    completion_tokens: 1
+                })
+            }
+        );
+        assert_eq!(
+            parse_sse_line(
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#
+            ),
+            SseLine::Chunk {
+                text: Some("Hi".to_string()),
+                usage: None
+            }
+        );
+        assert_eq!(parse_sse_line("event: ping"), SseLine::Other);
+        assert_eq!(parse_sse_line(r#"data: {"type":"ping"}"#), SseLine::Other);
+        assert_eq!(
+            parse_sse_line(r#"data: {"type":"message_stop"}"#),
+            SseLine::Done
+        );
+        assert_eq!(
+            parse_sse_line(
+                r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+            ),
+            SseLine::Error("Overloaded".to_string())
+        );
+
+        let (text, usage) = parse_response(
+            r#"{"content":[{"type":"thinking","thinking":"..."},{"type":"text","text":"Done."}],"usage":{"input_tokens":3,"output_tokens":2}}"#,
+        )
+        .unwrap();
+        assert_eq!(text.as_deref(), Some("Done."));
+        assert_eq!(usage.unwrap().completion_tokens, 2);
+    }
+}
@@ -0,0 +1,181 @@
+//! Cache of completions, so running an unchanged prompt again costs nothing.
+//!
+//! Replies are kept in the `llm_cache` table of the history database, which
+//! is encrypted along with the history, keyed on the provider, the model and
+//! a SHA-256 hash of the prompt. The least recently used entries are evicted
+//! past `MAX_ENTRIES`. Like `usage`, the history manager is registered here
+//! once at startup; failing to read or write the cache never fails a request.
+
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, warn};
+use ring::digest::{digest, SHA256};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use crate::managers::history::HistoryManager;
+
+/// Replies kept before the least recently used are evicted
+const MAX_ENTRIES: i64 = 1000;
+
+/// Where replies are cached, set once at startup
+static HISTORY: OnceLock<Arc<HistoryManager>> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Make `history` the database replies are cached in
+pub fn register(history: Arc<HistoryManager>) {
+    let _ = HISTORY.set(history);
+}
+
+/// Turn the cache on or off; entries already cached are kept
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+fn connection() -> Option<Connection> {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return None;
+    }
+    match HISTORY.get()?.get_connection() {
+        Ok(conn) => Some(conn),
+        Err(e) => {
+            warn!("Failed to open the completion cache: {}", e);
+            None
+        }
+    }
+}
+
+fn prompt_hash(prompt: &str) -> String {
+    crate::encryption::to_hex(digest(&SHA256, prompt.as_bytes()).as_ref())
+}
+
+/// The cached reply to `prompt`, if there is one
+pub fn get(provider_id: &str, model: &str, prompt: &str) -> Option<String> {
+    let conn = connection()?;
+    match lookup(&conn, provider_id, model, prompt, Utc::now().timestamp()) {
+        Ok(Some(reply)) => {
+            debug!("Using cached completion from provider '{}'", provider_id);
+            Some(reply)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to read the completion cache: {}", e);
+            None
+        }
+    }
+}
+
+/// Cache `reply` as the reply to `prompt`
+pub fn put(provider_id: &str, model: &str, prompt: &str, reply: &str) {
+    let Some(conn) = connection() else {
+        return;
+    };
+    if let Err(e) = insert(
+        &conn,
+        provider_id,
+        model,
+        prompt,
+        reply,
+        Utc::now().timestamp(),
+    ) {
+        warn!("Failed to write the completion cache: {}", e);
+    }
+}
+
+/// Remove every cached reply
+pub fn clear(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM llm_cache", [])?;
+    Ok(())
+}
+
+fn lookup(
+    conn: &Connection,
+    provider_id: &str,
+    model: &str,
+    prompt: &str,
+    now: i64,
+) -> Result<Option<String>> {
+    let hash = prompt_hash(prompt);
+    let reply = conn
+        .query_row(
+            "SELECT reply FROM llm_cache WHERE provider_id = ?1 AND model = ?2 AND prompt_hash = ?3",
+            params![provider_id, model, hash],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    if reply.is_some() {
+        conn.execute(
+            "UPDATE llm_cache SET last_used_at = ?4
+             WHERE provider_id = ?1 AND model = ?2 AND prompt_hash = ?3",
+            params![provider_id, model, hash, now],
+        )?;
+    }
+    Ok(reply)
+}
+
+fn insert(
+    conn: &Connection,
+    provider_id: &str,
+    model: &str,
+    prompt: &str,
+    reply: &str,
+    now: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO llm_cache (provider_id, model, prompt_hash, reply, last_used_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![provider_id, model, prompt_hash(prompt), reply, now],
+    )?;
+    conn.execute(
+        "DELETE FROM llm_cache WHERE rowid IN (
+            SELECT rowid FROM llm_cache ORDER BY last_used_at DESC LIMIT -1 OFFSET ?1
+        )",
+        params![MAX_ENTRIES],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_are_keyed_on_provider_model_and_prompt() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE llm_cache (
+                provider_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                prompt_hash TEXT NOT NULL,
+                reply TEXT NOT NULL,
+                last_used_at INTEGER NOT NULL,
+                PRIMARY KEY (provider_id, model, prompt_hash)
+            );",
+        )
+        .unwrap();
+
+        insert(&conn, "openai", "gpt-4o-mini", "fix: hi", "Hi.", 1).unwrap();
+        let get = |provider: &str, model: &str, prompt: &str| {
+            lookup(&conn, provider, model, prompt, 2).unwrap()
+        };
+        assert_eq!(
+            get("openai", "gpt-4o-mini", "fix: hi").as_deref(),
+            Some("Hi.")
+        );
+        assert_eq!(get("openai", "gpt-4o", "fix: hi"), None);
+        assert_eq!(get("groq", "gpt-4o-mini", "fix: hi"), None);
+        assert_eq!(get("openai", "gpt-4o-mini", "fix: hi "), None);
+
+        // The entry just read is the most recently used, so it survives
+        // eviction of everything past the limit
+        for i in 0..MAX_ENTRIES {
+            insert(&conn, "openai", "gpt-4o-mini", &i.to_string(), "x", 0).unwrap();
+        }
+        assert!(get("openai", "gpt-4o-mini", "fix: hi").is_some());
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM llm_cache", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, MAX_ENTRIES);
+    }
+}
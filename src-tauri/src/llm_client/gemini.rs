@@ -0,0 +1,223 @@
+//! Google's native Gemini API.
+//!
+//! The model is part of the URL, prompts go in as `contents` parts and the
+//! API key is sent as `x-goog-api-key` by `build_headers`. Safety filters
+//! only block high-probability harm: the text is the user's own dictation,
+//! and a blocked reply would lose it.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ChatUsage, SseLine};
+
+const SAFETY_CATEGORIES: [&str; 4] = [
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateRequest<'a> {
+    contents: [Content<'a>; 1],
+    safety_settings: Vec<SafetySetting>,
+}
+
+#[derive(Serialize)]
+struct Content<'a> {
+    role: &'a str,
+    parts: [Part<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Part<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+struct SafetySetting {
+    category: &'static str,
+    threshold: &'static str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerateResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    usage_metadata: Option<UsageMetadata>,
+    prompt_feedback: Option<PromptFeedback>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<CandidateContent>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<ResponsePart>,
+}
+
+#[derive(Deserialize)]
+struct ResponsePart {
+    text: Option<String>,
+    /// Set on the model's reasoning, which is not part of the reply
+    #[serde(default)]
+    thought: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    block_reason: Option<String>,
+}
+
+impl GenerateResponse {
+    /// The reply text and token counts, or why the safety filters blocked it
+    fn into_reply(self) -> Result<(Option<String>, Option<ChatUsage>), String> {
+        if let Some(reason) = self.prompt_feedback.and_then(|f| f.block_reason) {
+            return Err(format!("Gemini blocked the prompt: {}", reason));
+        }
+        let usage = self.usage_metadata.map(|usage| ChatUsage {
+            prompt_tokens: usage.prompt_token_count,
+            completion_tokens: usage.candidates_token_count,
+        });
+        let Some(candidate) = self.candidates.into_iter().next() else {
+            return Ok((None, usage));
+        };
+        if candidate.finish_reason.as_deref() == Some("SAFETY") {
+            return Err("Gemini blocked the reply for safety reasons".to_string());
+        }
+        let text: String = candidate
+            .content
+            .map(|content| content.parts)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|part| !part.thought)
+            .filter_map(|part| part.text)
+            .collect();
+        Ok((Some(text).filter(|text| !text.is_empty()), usage))
+    }
+}
+
+pub(super) fn request(
+    client: &reqwest::Client,
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    // The model list returns ids as `models/...`
+    let model = model.trim_start_matches("models/");
+    let url = if stream {
+        format!(
+            "{}/models/{}:streamGenerateContent?alt=sse",
+            base_url, model
+        )
+    } else {
+        format!("{}/models/{}:generateContent", base_url, model)
+    };
+    client.post(url).json(&GenerateRequest {
+        contents: [Content {
+            role: "user",
+            parts: [Part { text: prompt }],
+        }],
+        safety_settings: SAFETY_CATEGORIES
+            .iter()
+            .map(|category| SafetySetting {
+                category,
+                threshold: "BLOCK_ONLY_HIGH",
+            })
+            .collect(),
+    })
+}
+
+/// Text and token counts of a complete reply
+pub(super) fn parse_response(body: &str) -> Result<(Option<String>, Option<ChatUsage>), String> {
+    serde_json::from_str::<GenerateResponse>(body)
+        .map_err(|e| format!("Failed to parse API response: {}", e))?
+        .into_reply()
+}
+
+/// Every streamed event is a partial response; the stream just ends
+pub(super) fn parse_sse_line(line: &str) -> SseLine {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return SseLine::Other;
+    };
+    let Ok(response) = serde_json::from_str::<GenerateResponse>(data) else {
+        return SseLine::Other;
+    };
+    match response.into_reply() {
+        Ok((text, usage)) => SseLine::Chunk { text, usage },
+        Err(e) => SseLine::Error(e),
+    }
+}
+
+/// Paged up to the largest page, which holds every model there is today
+pub(super) fn models_url(base_url: &str) -> String {
+    format!("{}/models?pageSize=1000", base_url)
+}
+
+/// Ids of the models that can generate text
+pub(super) fn parse_models(parsed: &serde_json::Value) -> Vec<String> {
+    let Some(models) = parsed.get("models").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
+    models
+        .iter()
+        .filter(|model| {
+            model
+                .get("supportedGenerationMethods")
+                .and_then(|m| m.as_array())
+                .is_some_and(|methods| methods.iter().any(|m| m == "generateContent"))
+        })
+        .filter_map(|model| model.get("name").and_then(|n| n.as_str()))
+        .map(|name| name.trim_start_matches("models/").to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replies_skip_thoughts_and_report_blocks() {
+        let (text, usage) = parse_response(
+            r#"{"candidates":[{"content":{"parts":[{"text":"plan","thought":true},{"text":"Hello."}]},"finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":7,"candidatesTokenCount":2}}"#,
+        )
+        .unwrap();
+        assert_eq!(text.as_deref(), Some("Hello."));
+        assert_eq!(
+            usage,
+            Some(ChatUsage {
+                prompt_tokens: 7,
+                completion_tokens: 2
+            })
+        );
+
+        assert!(parse_response(r#"{"promptFeedback":{"blockReason":"SAFETY"}}"#).is_err());
+        assert_eq!(
+            parse_sse_line(r#"data: {"candidates":[{"finishReason":"SAFETY"}]}"#),
+            SseLine::Error("Gemini blocked the reply for safety reasons".to_string())
+        );
+
+        let models = serde_json::json!({"models": [
+            {"name": "models/gemini-2.0-flash", "supportedGenerationMethods": ["generateContent", "countTokens"]},
+            {"name": "models/text-embedding-004", "supportedGenerationMethods": ["embedContent"]}
+        ]});
+        assert_eq!(parse_models(&models), vec!["gemini-2.0-flash"]);
+    }
+}
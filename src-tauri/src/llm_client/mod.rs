@@ -0,0 +1,688 @@
+//! Chat completions for post-processing.
+//!
+//! Most providers speak the OpenAI API. Anthropic and Gemini are spoken to
+//! through their own APIs. Local models run on the embedded llama.cpp engine
+//! and Apple Intelligence on the system's on-device model, so their text
+//! never leaves the machine. Cloud replies are cached, see `cache`.
+
+mod anthropic;
+pub mod cache;
+mod gemini;
+
+#[cfg(feature = "local-llm")]
+use crate::local_llm::LocalLlmManager;
+use crate::settings::{
+    PostProcessProvider, ANTHROPIC_PROVIDER_ID, APPLE_INTELLIGENCE_PROVIDER_ID, GEMINI_PROVIDER_ID,
+    LOCAL_LLM_PROVIDER_ID,
+};
+use crate::usage::{self, ApiUsage};
+use futures_util::StreamExt;
+use log::{debug, warn};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Attempts per request before the provider is given up on
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Longest wait for a streamed reply to start
+const STREAM_START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bumped by `cancel_streams`; a stream started before the bump stops
+static STREAM_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Current stream generation, to pass to `stream_chat_completion`
+pub fn stream_generation() -> u64 {
+    STREAM_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Abort every running streamed completion
+pub fn cancel_streams() {
+    STREAM_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Whether `cancel_streams` was called after `generation` was taken
+pub fn cancelled_since(generation: u64) -> bool {
+    stream_generation() != generation
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+/// Token counts reported by the provider
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+struct ChatUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessageResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessageResponse {
+    content: Option<String>,
+}
+
+/// One server-sent event of a streamed completion
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChatChunkChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunkChoice {
+    #[serde(default)]
+    delta: ChatChunkDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatChunkDelta {
+    content: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum SseLine {
+    /// Text added to the reply and token counts so far, either may be missing
+    Chunk {
+        text: Option<String>,
+        usage: Option<ChatUsage>,
+    },
+    /// The reply is complete
+    Done,
+    /// The provider reported an error mid-stream
+    Error(String),
+    /// Comments, keep-alives, role-only chunks and other event fields
+    Other,
+}
+
+fn parse_sse_line(line: &str) -> SseLine {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return SseLine::Other;
+    };
+    if data == "[DONE]" {
+        return SseLine::Done;
+    }
+    let Ok(chunk) = serde_json::from_str::<ChatCompletionChunk>(data) else {
+        return SseLine::Other;
+    };
+    let content = chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta.content)
+        .filter(|content| !content.is_empty());
+    if content.is_none() && chunk.usage.is_none() {
+        return SseLine::Other;
+    }
+    SseLine::Chunk {
+        text: content,
+        usage: chunk.usage,
+    }
+}
+
+/// Token counts reported across a stream, where each provider sends them in
+/// different events and some repeat running totals
+fn merge_usage(reported: Option<ChatUsage>, usage: ChatUsage) -> ChatUsage {
+    let reported = reported.unwrap_or(usage);
+    ChatUsage {
+        prompt_tokens: reported.prompt_tokens.max(usage.prompt_tokens),
+        completion_tokens: reported.completion_tokens.max(usage.completion_tokens),
+    }
+}
+
+/// Record the tokens of a completion, estimated from the text when the
+/// provider did not report them
+fn record_usage(
+    provider: &PostProcessProvider,
+    model: &str,
+    reported: Option<ChatUsage>,
+    prompt_tokens: u64,
+    reply: Option<&str>,
+) {
+    let usage = reported.unwrap_or(ChatUsage {
+        prompt_tokens,
+        completion_tokens: reply.map(usage::estimate_tokens).unwrap_or(0),
+    });
+    usage::record(ApiUsage {
+        provider_id: provider.id.clone(),
+        model: model.to_string(),
+        input_tokens: usage.prompt_tokens,
+        output_tokens: usage.completion_tokens,
+        audio_seconds: 0.0,
+    });
+}
+
+/// Build headers for API requests based on provider type
+fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<HeaderMap, String> {
+    let mut headers = HeaderMap::new();
+
+    // Common headers
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        REFERER,
+        HeaderValue::from_static("https://github.com/solomonshalom/PaperFlow"),
+    );
+    headers.insert(
+        USER_AGENT,
+        HeaderValue::from_static("PaperFlow/1.0 (+https://github.com/solomonshalom/PaperFlow)"),
+    );
+    headers.insert("X-Title", HeaderValue::from_static("PaperFlow"));
+
+    // Provider-specific auth headers
+    if !api_key.is_empty() {
+        if provider.id == ANTHROPIC_PROVIDER_ID {
+            headers.insert(
+                "x-api-key",
+                HeaderValue::from_str(api_key)
+                    .map_err(|e| format!("Invalid API key header value: {}", e))?,
+            );
+            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+        } else if provider.id == GEMINI_PROVIDER_ID {
+            headers.insert(
+                "x-goog-api-key",
+                HeaderValue::from_str(api_key)
+                    .map_err(|e| format!("Invalid API key header value: {}", e))?,
+            );
+        } else {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", api_key))
+                    .map_err(|e| format!("Invalid authorization header value: {}", e))?,
+            );
+        }
+    }
+
+    Ok(headers)
+}
+
+/// Create an HTTP client with provider-specific headers
+fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwest::Client, String> {
+    let headers = build_headers(provider, api_key)?;
+    crate::proxy::client_builder()
+        .default_headers(headers)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// Whether a failed request may succeed when sent again
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+async fn error_from_response(response: reqwest::Response) -> String {
+    let status = response.status();
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Failed to read error response".to_string());
+    format!("API request failed with status {}: {}", status, error_text)
+}
+
+/// Send `request`, retrying with exponential backoff on network errors, rate
+/// limits and server errors. `timeout` bounds the wait for the response
+/// headers of each attempt, not for the body.
+async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> Result<reqwest::Response, String> {
+    let mut delay = INITIAL_RETRY_DELAY;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let attempt_request = request
+            .try_clone()
+            .ok_or("Request body cannot be sent again")?;
+        let sent = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, attempt_request.send())
+                .await
+                .map_err(|_| format!("No response within {} seconds", timeout.as_secs()))
+                .and_then(|sent| sent.map_err(|e| format!("HTTP request failed: {}", e))),
+            None => attempt_request
+                .send()
+                .await
+                .map_err(|e| format!("HTTP request failed: {}", e)),
+        };
+
+        let error = match sent {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) if !is_retryable(response.status()) => {
+                return Err(error_from_response(response).await)
+            }
+            Ok(response) => error_from_response(response).await,
+            Err(e) => e,
+        };
+
+        if attempt == MAX_ATTEMPTS {
+            return Err(error);
+        }
+        warn!(
+            "{} (attempt {}/{}), retrying in {:?}",
+            error, attempt, MAX_ATTEMPTS, delay
+        );
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+    }
+    unreachable!("the last attempt always returns")
+}
+
+/// Build the completion request for `provider`'s API
+fn completion_request(
+    provider: &PostProcessProvider,
+    client: &reqwest::Client,
+    model: &str,
+    prompt: &str,
+    stream: bool,
+) -> reqwest::RequestBuilder {
+    let base_url = provider.base_url.trim_end_matches('/');
+    match provider.id.as_str() {
+        ANTHROPIC_PROVIDER_ID => anthropic::request(client, base_url, model, prompt, stream),
+        GEMINI_PROVIDER_ID => gemini::request(client, base_url, model, prompt, stream),
+        _ => client
+            .post(format!("{}/chat/completions", base_url))
+            .json(&ChatCompletionRequest {
+                model: model.to_string(),
+                messages: vec![ChatMessage {
+                    role: "user".to_string(),
+                    content: prompt.to_string(),
+                }],
+                stream,
+            }),
+    }
+}
+
+/// Text and token counts of a complete reply from `provider`'s API
+fn parse_completion(
+    provider: &PostProcessProvider,
+    body: &str,
+) -> Result<(Option<String>, Option<ChatUsage>), String> {
+    match provider.id.as_str() {
+        ANTHROPIC_PROVIDER_ID => anthropic::parse_response(body),
+        GEMINI_PROVIDER_ID => gemini::parse_response(body),
+        _ => {
+            let completion: ChatCompletionResponse = serde_json::from_str(body)
+                .map_err(|e| format!("Failed to parse API response: {}", e))?;
+            let content = completion
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content);
+            Ok((content, completion.usage))
+        }
+    }
+}
+
+async fn read_completion(
+    provider: &PostProcessProvider,
+    response: reqwest::Response,
+) -> Result<(Option<String>, Option<ChatUsage>), String> {
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read API response: {}", e))?;
+    parse_completion(provider, &body)
+}
+
+/// Send a chat completion request to the provider's API
+/// Returns Ok(Some(content)) on success, Ok(None) if response has no content,
+/// or Err on actual errors (HTTP, parsing, etc.)
+pub async fn send_chat_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    prompt: String,
+) -> Result<Option<String>, String> {
+    if is_on_device(provider) {
+        return complete_on_device(provider, model, prompt).await;
+    }
+
+    if let Some(content) = cache::get(&provider.id, model, &prompt) {
+        return Ok(Some(content));
+    }
+
+    debug!(
+        "Sending chat completion request to provider '{}'",
+        provider.id
+    );
+
+    let client = create_client(provider, &api_key)?;
+    let prompt_tokens = usage::estimate_tokens(&prompt);
+    let request = completion_request(provider, &client, model, &prompt, false);
+    let response = send_with_retry(request, None).await?;
+
+    let (content, reported_usage) = read_completion(provider, response).await?;
+    record_usage(
+        provider,
+        model,
+        reported_usage,
+        prompt_tokens,
+        content.as_deref(),
+    );
+    if let Some(content) = &content {
+        cache::put(&provider.id, model, &prompt, content);
+    }
+    Ok(content)
+}
+
+/// Like `send_chat_completion`, but asks for a streamed reply and calls
+/// `on_delta` with each piece of text as it arrives. Stops with an error
+/// once `cancel_streams` is called after `generation` was taken. Servers
+/// that answer with a plain JSON body instead of a stream still work.
+pub async fn stream_chat_completion(
+    provider: &PostProcessProvider,
+    api_key: String,
+    model: &str,
+    prompt: String,
+    generation: u64,
+    mut on_delta: impl FnMut(&str),
+) -> Result<Option<String>, String> {
+    if is_on_device(provider) {
+        let content = complete_on_device(provider, model, prompt).await?;
+        if cancelled_since(generation) {
+            return Err("Post-processing was cancelled".to_string());
+        }
+        if let Some(content) = &content {
+            on_delta(content);
+        }
+        return Ok(content);
+    }
+
+    if let Some(content) = cache::get(&provider.id, model, &prompt) {
+        on_delta(&content);
+        return Ok(Some(content));
+    }
+
+    debug!(
+        "Sending streamed chat completion request to provider '{}'",
+        provider.id
+    );
+
+    let client = create_client(provider, &api_key)?;
+    let prompt_tokens = usage::estimate_tokens(&prompt);
+    let request = completion_request(provider, &client, model, &prompt, true);
+    let response = send_with_retry(request, Some(STREAM_START_TIMEOUT)).await?;
+
+    let is_stream = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !is_stream {
+        let (content, reported_usage) = read_completion(provider, response).await?;
+        record_usage(
+            provider,
+            model,
+            reported_usage,
+            prompt_tokens,
+            content.as_deref(),
+        );
+        if let Some(content) = &content {
+            cache::put(&provider.id, model, &prompt, content);
+            on_delta(content);
+        }
+        return Ok(content);
+    }
+
+    let parse_line: fn(&str) -> SseLine = match provider.id.as_str() {
+        ANTHROPIC_PROVIDER_ID => anthropic::parse_sse_line,
+        GEMINI_PROVIDER_ID => gemini::parse_sse_line,
+        _ => parse_sse_line,
+    };
+
+    let mut stream = response.bytes_stream();
+    // Bytes of a line not received in full yet, which may end mid-character
+    let mut pending: Vec<u8> = Vec::new();
+    let mut content = String::new();
+    let mut reported_usage = None;
+    'stream: while let Some(bytes) = stream.next().await {
+        if cancelled_since(generation) {
+            record_usage(
+                provider,
+                model,
+                reported_usage,
+                prompt_tokens,
+                Some(&content),
+            );
+            return Err("Post-processing was cancelled".to_string());
+        }
+        pending.extend_from_slice(&bytes.map_err(|e| format!("Stream failed: {}", e))?);
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            match parse_line(String::from_utf8_lossy(&line).trim_end()) {
+                SseLine::Chunk { text, usage } => {
+                    if let Some(usage) = usage {
+                        reported_usage = Some(merge_usage(reported_usage, usage));
+                    }
+                    if let Some(text) = text {
+                        content.push_str(&text);
+                        on_delta(&text);
+                    }
+                }
+                SseLine::Done => break 'stream,
+                SseLine::Error(e) => return Err(format!("Stream failed: {}", e)),
+                SseLine::Other => {}
+            }
+        }
+    }
+
+    record_usage(
+        provider,
+        model,
+        reported_usage,
+        prompt_tokens,
+        Some(&content),
+    );
+    if !content.is_empty() {
+        cache::put(&provider.id, model, &prompt, &content);
+    }
+    Ok(Some(content).filter(|c| !c.is_empty()))
+}
+
+/// Whether `provider` runs on this machine rather than behind an API
+pub fn is_on_device(provider: &PostProcessProvider) -> bool {
+    provider.id == LOCAL_LLM_PROVIDER_ID || provider.id == APPLE_INTELLIGENCE_PROVIDER_ID
+}
+
+async fn complete_on_device(
+    provider: &PostProcessProvider,
+    model: &str,
+    prompt: String,
+) -> Result<Option<String>, String> {
+    if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
+        complete_with_apple_intelligence(model, prompt).await
+    } else {
+        complete_locally(model, prompt).await
+    }
+}
+
+/// Run the prompt on Apple's on-device model, with the instructions of the
+/// task `model` names
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+async fn complete_with_apple_intelligence(
+    model: &str,
+    prompt: String,
+) -> Result<Option<String>, String> {
+    use crate::apple_intelligence::{self, AppleIntelligenceTask};
+
+    if !apple_intelligence::check_apple_intelligence_availability() {
+        return Err("Apple Intelligence is not currently available on this device".to_string());
+    }
+    let task = AppleIntelligenceTask::from_model_id(model);
+    debug!("Running Apple Intelligence completion: {:?}", task);
+
+    let content = tauri::async_runtime::spawn_blocking(move || {
+        apple_intelligence::process_text(&prompt, task.instructions().as_deref(), 0)
+    })
+    .await
+    .map_err(|e| format!("Apple Intelligence task failed: {}", e))??;
+    Ok(Some(content).filter(|content| !content.trim().is_empty()))
+}
+
+#[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+async fn complete_with_apple_intelligence(
+    _model: &str,
+    _prompt: String,
+) -> Result<Option<String>, String> {
+    Err("Apple Intelligence is not supported on this platform".to_string())
+}
+
+/// Run the prompt on the embedded llama.cpp engine, off the async runtime
+#[cfg(feature = "local-llm")]
+async fn complete_locally(model: &str, prompt: String) -> Result<Option<String>, String> {
+    let manager = LocalLlmManager::get().ok_or("Local LLM engine not initialized")?;
+    let model = model.to_string();
+    debug!("Running local LLM completion with model: {}", model);
+
+    let content = tauri::async_runtime::spawn_blocking(move || manager.complete(&model, &prompt))
+        .await
+        .map_err(|e| format!("Local LLM task failed: {}", e))?
+        .map_err(|e| format!("Local LLM failed: {}", e))?;
+    Ok(Some(content).filter(|content| !content.is_empty()))
+}
+
+#[cfg(not(feature = "local-llm"))]
+async fn complete_locally(_model: &str, _prompt: String) -> Result<Option<String>, String> {
+    Err("This build has no local LLM engine".to_string())
+}
+
+/// Fetch available models from an OpenAI-compatible API
+/// Returns a list of model IDs
+pub async fn fetch_models(
+    provider: &PostProcessProvider,
+    api_key: String,
+) -> Result<Vec<String>, String> {
+    let base_url = provider.base_url.trim_end_matches('/');
+    let url = match provider.id.as_str() {
+        GEMINI_PROVIDER_ID => gemini::models_url(base_url),
+        // Anthropic pages the list, 20 models at a time by default
+        ANTHROPIC_PROVIDER_ID => format!("{}/models?limit=1000", base_url),
+        _ => format!("{}/models", base_url),
+    };
+
+    debug!("Fetching models from: {}", url);
+
+    let client = create_client(provider, &api_key)?;
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch models: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!(
+            "Model list request failed ({}): {}",
+            status, error_text
+        ));
+    }
+
+    let parsed: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    if provider.id == GEMINI_PROVIDER_ID {
+        return Ok(gemini::parse_models(&parsed));
+    }
+
+    let mut models = Vec::new();
+
+    // Handle OpenAI format: { data: [ { id: "..." }, ... ] }
+    if let Some(data) = parsed.get("data").and_then(|d| d.as_array()) {
+        for entry in data {
+            if let Some(id) = entry.get("id").and_then(|i| i.as_str()) {
+                models.push(id.to_string());
+            } else if let Some(name) = entry.get("name").and_then(|n| n.as_str()) {
+                models.push(name.to_string());
+            }
+        }
+    }
+    // Handle array format: [ "model1", "model2", ... ]
+    else if let Some(array) = parsed.as_array() {
+        for entry in array {
+            if let Some(model) = entry.as_str() {
+                models.push(model.to_string());
+            }
+        }
+    }
+
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_lines_yield_deltas_until_done() {
+        assert_eq!(
+            parse_sse_line(r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#),
+            SseLine::Chunk {
+                text: Some("Hel".to_string()),
+                usage: None
+            }
+        );
+        assert_eq!(
+            parse_sse_line(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            SseLine::Other
+        );
+        assert_eq!(
+            parse_sse_line(
+                r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3}}"#
+            ),
+            SseLine::Chunk {
+                text: None,
+                usage: Some(ChatUsage {
+                    prompt_tokens: 12,
+                    completion_tokens: 3
+                })
+            }
+        );
+        assert_eq!(parse_sse_line(": keep-alive"), SseLine::Other);
+        assert_eq!(parse_sse_line("data: [DONE]"), SseLine::Done);
+    }
+
+    #[test]
+    fn cancelling_stops_streams_started_before() {
+        let generation = stream_generation();
+        assert!(!cancelled_since(generation));
+        cancel_streams();
+        assert!(cancelled_since(generation));
+        assert!(!cancelled_since(stream_generation()));
+    }
+
+    #[test]
+    fn only_rate_limits_and_server_errors_are_retried() {
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(reqwest::StatusCode::BAD_REQUEST));
+    }
+}
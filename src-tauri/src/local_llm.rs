@@ -0,0 +1,168 @@
+//! Embedded llama.cpp engine, so post-processing can run on a downloaded
+//! GGUF model without any server.
+//!
+//! The models are downloaded and deleted through the `ModelManager` like the
+//! transcription models. `llm_client` hands prompts for the local provider
+//! to the registered `LocalLlmManager`; the last used model stays loaded
+//! until another one is asked for.
+
+use anyhow::{anyhow, Result};
+use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::llama_backend::LlamaBackend;
+use llama_cpp_2::llama_batch::LlamaBatch;
+use llama_cpp_2::model::params::LlamaModelParams;
+use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+use llama_cpp_2::sampling::LlamaSampler;
+use log::{debug, info};
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::managers::model::{EngineType, ModelManager};
+
+/// Most tokens generated for one prompt
+const MAX_NEW_TOKENS: usize = 1024;
+
+/// The manager `llm_client` sends local prompts to, set once at startup
+static MANAGER: OnceLock<Arc<LocalLlmManager>> = OnceLock::new();
+
+#[derive(Default)]
+struct Engine {
+    /// llama.cpp can only be initialized once per process
+    backend: Option<LlamaBackend>,
+    model: Option<(String, LlamaModel)>,
+}
+
+pub struct LocalLlmManager {
+    model_manager: Arc<ModelManager>,
+    engine: Mutex<Engine>,
+}
+
+impl LocalLlmManager {
+    pub fn new(model_manager: Arc<ModelManager>) -> Self {
+        Self {
+            model_manager,
+            engine: Mutex::new(Engine::default()),
+        }
+    }
+
+    /// Make `manager` the one local prompts are sent to
+    pub fn register(manager: Arc<Self>) {
+        let _ = MANAGER.set(manager);
+    }
+
+    pub fn get() -> Option<Arc<Self>> {
+        MANAGER.get().cloned()
+    }
+
+    /// Complete `prompt` with the downloaded model `model_id`, loading it
+    /// first if needed. Blocks until the whole reply is generated.
+    pub fn complete(&self, model_id: &str, prompt: &str) -> Result<String> {
+        let info = self
+            .model_manager
+            .get_model_info(model_id)
+            .filter(|info| info.engine_type == EngineType::Llama)
+            .ok_or_else(|| anyhow!("Not a local LLM model: {}", model_id))?;
+
+        let mut guard = self.engine.lock().unwrap();
+        let engine = &mut *guard;
+        if engine.backend.is_none() {
+            engine.backend = Some(LlamaBackend::init()?);
+        }
+        let backend = engine.backend.as_ref().unwrap();
+
+        if engine.model.as_ref().map(|(id, _)| id.as_str()) != Some(model_id) {
+            // Free the previous model before loading the next one
+            engine.model = None;
+            let path = self.model_manager.get_model_path(model_id)?;
+            info!("Loading local LLM {}", info.name);
+            let model = LlamaModel::load_from_file(backend, &path, &LlamaModelParams::default())
+                .map_err(|e| anyhow!("Failed to load {}: {}", info.name, e))?;
+            engine.model = Some((model_id.to_string(), model));
+        }
+        let (_, model) = engine.model.as_ref().unwrap();
+
+        generate(backend, model, prompt)
+    }
+
+    /// Free the loaded model, e.g. before it is deleted
+    pub fn unload(&self) {
+        self.engine.lock().unwrap().model = None;
+    }
+}
+
+/// Context large enough for the prompt and the reply, within what the model
+/// was trained on
+fn context_size(prompt_tokens: usize, n_ctx_train: usize) -> Result<usize> {
+    if prompt_tokens >= n_ctx_train {
+        return Err(anyhow!(
+            "Prompt is too long for the model ({} tokens, at most {})",
+            prompt_tokens,
+            n_ctx_train
+        ));
+    }
+    Ok((prompt_tokens + MAX_NEW_TOKENS).min(n_ctx_train))
+}
+
+fn generate(backend: &LlamaBackend, model: &LlamaModel, prompt: &str) -> Result<String> {
+    // Instruct models expect their own chat format; base models get the
+    // prompt as it is
+    let text = match model.chat_template(None) {
+        Ok(template) => {
+            let message = LlamaChatMessage::new("user".to_string(), prompt.to_string())?;
+            model.apply_chat_template(&template, &[message], true)?
+        }
+        Err(_) => prompt.to_string(),
+    };
+
+    let tokens = model.str_to_token(&text, AddBos::Always)?;
+    if tokens.is_empty() {
+        return Err(anyhow!("Prompt is empty"));
+    }
+    let n_ctx = context_size(tokens.len(), model.n_ctx_train() as usize)?;
+    let params = LlamaContextParams::default()
+        .with_n_ctx(NonZeroU32::new(n_ctx as u32))
+        .with_n_batch(n_ctx as u32);
+    let mut ctx = model.new_context(backend, params)?;
+
+    let mut batch = LlamaBatch::new(n_ctx, 1);
+    let last = tokens.len() - 1;
+    for (pos, token) in tokens.iter().enumerate() {
+        batch.add(*token, pos as i32, &[0], pos == last)?;
+    }
+    ctx.decode(&mut batch)?;
+
+    // Greedy sampling: post-processing wants the likeliest text, not variety
+    let mut sampler = LlamaSampler::greedy();
+    let mut output = Vec::new();
+    for pos in tokens.len()..n_ctx {
+        let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+        sampler.accept(token);
+        if model.is_eog_token(token) {
+            break;
+        }
+        output.extend(model.token_to_bytes(token, Special::Plaintext)?);
+
+        batch.clear();
+        batch.add(token, pos as i32, &[0], true)?;
+        ctx.decode(&mut batch)?;
+    }
+
+    debug!(
+        "Local LLM generated {} bytes for a {} token prompt",
+        output.len(),
+        tokens.len()
+    );
+    Ok(String::from_utf8_lossy(&output).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_fits_the_reply_within_the_training_context() {
+        assert_eq!(context_size(100, 32_768).unwrap(), 100 + MAX_NEW_TOKENS);
+        assert_eq!(context_size(4000, 4096).unwrap(), 4096);
+        assert!(context_size(4096, 4096).is_err());
+    }
+}
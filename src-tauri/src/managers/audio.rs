@@ -1,12 +1,21 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::actions::ACTION_MAP;
+use crate::audio_toolkit::{
+    decode_audio_file, list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad,
+};
 use crate::helpers::clamshell;
 use crate::managers::live_preview::LivePreviewManager;
 use crate::settings::{get_settings, AppSettings};
 use crate::utils;
-use log::{debug, error, info};
+use crate::ManagedToggleState;
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use specta::Type;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
 use tauri::Manager;
+use tauri_specta::Event;
 
 fn set_mute(mute: bool) {
     // Expected behavior:
@@ -99,6 +108,31 @@ fn set_mute(mute: bool) {
 
 const WHISPER_SAMPLE_RATE: usize = 16000;
 
+/// How often the duration guard checks whether its recording is still running
+const DURATION_GUARD_POLL: Duration = Duration::from_millis(500);
+
+/// The longest a dictation may run, `None` when the limit is off (0 minutes)
+fn recording_limit(max_minutes: u32) -> Option<Duration> {
+    (max_minutes > 0).then(|| Duration::from_secs(u64::from(max_minutes) * 60))
+}
+
+/// Wait out `limit`, checking every `poll` that the recording is still
+/// running. Returns whether the limit was reached before it ended.
+fn wait_for_limit(
+    limit: Duration,
+    poll: Duration,
+    mut still_recording: impl FnMut() -> bool,
+) -> bool {
+    let started = Instant::now();
+    while started.elapsed() < limit {
+        thread::sleep(poll);
+        if !still_recording() {
+            return false;
+        }
+    }
+    true
+}
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone, Debug)]
@@ -113,12 +147,29 @@ pub enum MicrophoneMode {
     OnDemand,
 }
 
+/// Event payload emitted when a recording hits the maximum duration and is
+/// stopped automatically
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct RecordingLimitReachedEvent {
+    pub binding_id: String,
+    pub max_duration_seconds: u64,
+}
+
+/// Receives the recorded (VAD-filtered) samples as they arrive
+pub type AudioTap = Arc<dyn Fn(&[f32]) + Send + Sync>;
+
+/// Receives every frame while the microphone is open, and whether it is
+/// being recorded
+pub type FrameTap = Arc<dyn Fn(&[f32], bool) + Send + Sync>;
+
 /* ──────────────────────────────────────────────────────────────── */
 
 fn create_audio_recorder(
     vad_path: &str,
     app_handle: &tauri::AppHandle,
     live_preview_manager: Option<Arc<LivePreviewManager>>,
+    audio_tap: Arc<Mutex<Option<AudioTap>>>,
+    frame_tap: Arc<Mutex<Option<FrameTap>>>,
 ) -> Result<AudioRecorder, anyhow::Error> {
     let settings = get_settings(app_handle);
 
@@ -152,16 +203,28 @@ fn create_audio_recorder(
             }
         });
 
-    // Add audio callback for live preview if manager is provided
-    if let Some(lpm) = live_preview_manager {
-        recorder = recorder.with_audio_callback(move |samples| {
+    // Stream audio to live preview, if provided, and to the audio tap
+    recorder = recorder.with_audio_callback(move |samples| {
+        if let Some(lpm) = &live_preview_manager {
             lpm.push_audio(samples);
-        });
-    }
+        }
+        if let Some(tap) = audio_tap.lock().unwrap().as_ref() {
+            tap(samples);
+        }
+    });
+    recorder = recorder.with_frame_callback(move |samples, recording| {
+        if let Some(tap) = frame_tap.lock().unwrap().as_ref() {
+            tap(samples, recording);
+        }
+    });
 
     Ok(recorder)
 }
 
+/// Environment variable naming an audio file to record from instead of the
+/// microphone, for development and hardware-free end-to-end tests
+const SIMULATED_INPUT_ENV: &str = "PAPERFLOW_SIMULATED_INPUT";
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone)]
@@ -175,6 +238,13 @@ pub struct AudioRecordingManager {
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
     live_preview_manager: Arc<Mutex<Option<Arc<LivePreviewManager>>>>,
+    audio_tap: Arc<Mutex<Option<AudioTap>>>,
+    frame_tap: Arc<Mutex<Option<FrameTap>>>,
+    /// Incremented on every recording start so a duration guard can tell
+    /// whether the recording it was watching is still the active one
+    recording_session: Arc<Mutex<u64>>,
+    /// Audio file played in place of the microphone, if set
+    simulated_input: Arc<Mutex<Option<PathBuf>>>,
 }
 
 impl AudioRecordingManager {
@@ -182,12 +252,20 @@ impl AudioRecordingManager {
 
     pub fn new(app: &tauri::AppHandle) -> Result<Self, anyhow::Error> {
         let settings = get_settings(app);
-        let mode = if settings.always_on_microphone {
+        let mode = if settings.keeps_microphone_open() {
             MicrophoneMode::AlwaysOn
         } else {
             MicrophoneMode::OnDemand
         };
 
+        let simulated_input = std::env::var_os(SIMULATED_INPUT_ENV).map(PathBuf::from);
+        if let Some(path) = &simulated_input {
+            warn!(
+                "Recording from simulated input {:?} instead of a microphone",
+                path
+            );
+        }
+
         let manager = Self {
             state: Arc::new(Mutex::new(RecordingState::Idle)),
             mode: Arc::new(Mutex::new(mode.clone())),
@@ -198,6 +276,10 @@ impl AudioRecordingManager {
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
             live_preview_manager: Arc::new(Mutex::new(None)),
+            audio_tap: Arc::new(Mutex::new(None)),
+            frame_tap: Arc::new(Mutex::new(None)),
+            recording_session: Arc::new(Mutex::new(0)),
+            simulated_input: Arc::new(Mutex::new(simulated_input)),
         };
 
         // Always-on?  Open immediately.
@@ -213,6 +295,16 @@ impl AudioRecordingManager {
         *self.live_preview_manager.lock().unwrap() = Some(lpm);
     }
 
+    /// Send recorded samples to `tap` as they arrive, or stop with `None`
+    pub fn set_audio_tap(&self, tap: Option<AudioTap>) {
+        *self.audio_tap.lock().unwrap() = tap;
+    }
+
+    /// Send every microphone frame to `tap`, recording or not, or stop with `None`
+    pub fn set_frame_tap(&self, tap: Option<FrameTap>) {
+        *self.frame_tap.lock().unwrap() = tap;
+    }
+
     /* ---------- helper methods --------------------------------------------- */
 
     fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
@@ -266,6 +358,19 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Flips the mute for the rest of the current recording, whatever
+    /// mute_while_recording is set to. Returns whether audio is now muted.
+    pub fn toggle_mute(&self) -> bool {
+        if !self.is_recording() {
+            return false;
+        }
+        let mut did_mute_guard = self.did_mute.lock().unwrap();
+        *did_mute_guard = !*did_mute_guard;
+        set_mute(*did_mute_guard);
+        debug!("Mute toggled to {}", *did_mute_guard);
+        *did_mute_guard
+    }
+
     pub fn start_microphone_stream(&self) -> Result<(), anyhow::Error> {
         let mut open_flag = self.is_open.lock().unwrap();
         if *open_flag {
@@ -295,16 +400,28 @@ impl AudioRecordingManager {
                 vad_path.to_str().unwrap(),
                 &self.app_handle,
                 lpm,
+                self.audio_tap.clone(),
+                self.frame_tap.clone(),
             )?);
         }
 
-        // Get the selected device from settings, considering clamshell mode
-        let settings = get_settings(&self.app_handle);
-        let selected_device = self.get_effective_microphone_device(&settings);
+        let simulated_input = self.simulated_input.lock().unwrap().clone();
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
-                .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+            match simulated_input {
+                Some(path) => {
+                    let (samples, sample_rate) = decode_audio_file(&path).map_err(|e| {
+                        anyhow::anyhow!("Failed to read simulated input {:?}: {}", path, e)
+                    })?;
+                    rec.open_simulated(samples, sample_rate)
+                }
+                None => {
+                    // Get the selected device from settings, considering clamshell mode
+                    let settings = get_settings(&self.app_handle);
+                    rec.open(self.get_effective_microphone_device(&settings))
+                }
+            }
+            .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
         }
 
         *open_flag = true;
@@ -385,6 +502,8 @@ impl AudioRecordingManager {
                         binding_id: binding_id.to_string(),
                     };
                     debug!("Recording started for binding {binding_id}");
+                    drop(state);
+                    self.start_duration_guard(binding_id);
                     return true;
                 }
             }
@@ -431,6 +550,67 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Stop a dictation automatically once it exceeds the configured maximum
+    /// duration, handing the audio off to transcription like a normal stop.
+    /// This keeps a stuck toggle shortcut from recording for hours.
+    fn start_duration_guard(&self, binding_id: &str) {
+        let session = {
+            let mut session = self.recording_session.lock().unwrap();
+            *session += 1;
+            *session
+        };
+
+        let max_minutes = get_settings(&self.app_handle).max_recording_duration_minutes;
+        let Some(max_duration) = recording_limit(max_minutes) else {
+            return;
+        };
+
+        let manager = self.clone();
+        let binding_id = binding_id.to_string();
+        thread::spawn(move || {
+            let reached = wait_for_limit(max_duration, DURATION_GUARD_POLL, || {
+                manager.is_recording_session(session, &binding_id)
+            });
+            if !reached {
+                return;
+            }
+
+            warn!(
+                "Recording for binding {} reached the {} minute limit, stopping automatically",
+                binding_id, max_minutes
+            );
+            let _ = RecordingLimitReachedEvent {
+                binding_id: binding_id.clone(),
+                max_duration_seconds: max_duration.as_secs(),
+            }
+            .emit(&manager.app_handle);
+
+            // Reset the toggle so the next shortcut press starts a new recording
+            let toggle_state_manager = manager.app_handle.state::<ManagedToggleState>();
+            if let Ok(mut states) = toggle_state_manager.lock() {
+                states.active_toggles.insert(binding_id.clone(), false);
+            }
+
+            match ACTION_MAP.get(&binding_id) {
+                Some(action) => action.stop(&manager.app_handle, &binding_id, "max-duration"),
+                None => {
+                    warn!("No action for binding {}, discarding recording", binding_id);
+                    manager.cancel_recording();
+                }
+            }
+        });
+    }
+
+    fn is_recording_session(&self, session: u64, binding_id: &str) -> bool {
+        if *self.recording_session.lock().unwrap() != session {
+            return false;
+        }
+        matches!(
+            &*self.state.lock().unwrap(),
+            RecordingState::Recording { binding_id: active } if active == binding_id
+        )
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
@@ -440,6 +620,26 @@ impl AudioRecordingManager {
         Ok(())
     }
 
+    /// Record from an audio file instead of the microphone, or go back to the
+    /// microphone with `None`. Reopens the stream if it is currently active.
+    pub fn set_simulated_input(&self, path: Option<PathBuf>) -> Result<(), anyhow::Error> {
+        if self.is_recording() {
+            return Err(anyhow::anyhow!("Cannot change the input while recording"));
+        }
+        if let Some(path) = &path {
+            if !path.is_file() {
+                return Err(anyhow::anyhow!("Simulated input {:?} does not exist", path));
+            }
+        }
+
+        *self.simulated_input.lock().unwrap() = path;
+        self.update_selected_device()
+    }
+
+    pub fn get_simulated_input(&self) -> Option<PathBuf> {
+        self.simulated_input.lock().unwrap().clone()
+    }
+
     /// Recreate the audio recorder to apply new VAD settings (e.g., whisper mode)
     /// This clears the cached recorder so it will be recreated with updated settings
     pub fn refresh_vad_settings(&self) -> Result<(), anyhow::Error> {
@@ -541,3 +741,37 @@ impl AudioRecordingManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recordings_are_unlimited_by_default() {
+        let default = crate::settings::get_default_settings().max_recording_duration_minutes;
+        assert_eq!(recording_limit(default), None);
+        assert_eq!(recording_limit(10), Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn limit_is_reached_only_while_recording() {
+        let mut checks = 0;
+        assert!(wait_for_limit(Duration::ZERO, Duration::ZERO, || {
+            checks += 1;
+            true
+        }));
+        assert_eq!(checks, 0);
+
+        // Stopped before the limit: the guard gives up at the first check after
+        let mut checks = 0;
+        assert!(!wait_for_limit(
+            Duration::from_secs(3600),
+            Duration::ZERO,
+            || {
+                checks += 1;
+                checks < 3
+            }
+        ));
+        assert_eq!(checks, 3);
+    }
+}
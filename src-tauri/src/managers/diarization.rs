@@ -393,7 +393,10 @@ impl DiarizationManager {
 
         let temp_path = path.with_extension("tmp");
 
-        let response = reqwest::get(url)
+        let response = crate::proxy::client_builder()
+            .build()?
+            .get(url)
+            .send()
             .await
             .map_err(|e| anyhow!("Failed to connect to download server: {}", e))?;
 
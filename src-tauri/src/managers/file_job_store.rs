@@ -0,0 +1,198 @@
+//! On-disk state of the file transcription queue, so jobs survive a restart.
+//!
+//! The queue is saved as `queue.json` whenever a job is added, finishes or
+//! moves. While a job runs, each transcribed window is added to the job's
+//! checkpoint, and a job resumed after a restart picks up after the last
+//! window in it. Files a watch folder holds back until its processing hours
+//! are kept in `deferred.json`. With history encryption on, all of them are
+//! sealed with the history key like everything else that holds transcripts.
+
+use anyhow::Result;
+use log::warn;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use super::file_transcription::FileTranscriptionJob;
+use super::history::HistoryManager;
+use super::watch_folder::DeferredFile;
+use crate::commands::export::TranscriptSegment;
+use crate::encryption::HistoryKey;
+
+/// Windows of a job transcribed before it was interrupted
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct JobCheckpoint {
+    /// Size of the source file, to notice it was replaced in the meantime
+    pub file_size: u64,
+    /// Length of the decoded audio, which fixes where the windows fall
+    pub total_samples: usize,
+    /// End sample of the last transcribed window
+    pub completed_until: usize,
+    /// Processed text of each transcribed window
+    pub parts: Vec<String>,
+    /// Timed segments of the transcribed windows, before speakers are assigned
+    pub segments: Vec<TranscriptSegment>,
+}
+
+pub struct FileJobStore {
+    dir: PathBuf,
+    history_manager: Arc<HistoryManager>,
+}
+
+impl FileJobStore {
+    pub fn new(dir: PathBuf, history_manager: Arc<HistoryManager>) -> Self {
+        Self {
+            dir,
+            history_manager,
+        }
+    }
+
+    /// The store in the app's data directory
+    pub fn open(app_handle: &AppHandle, history_manager: Arc<HistoryManager>) -> Result<Self> {
+        Ok(Self::new(
+            app_handle.path().app_data_dir()?.join("file_jobs"),
+            history_manager,
+        ))
+    }
+
+    fn queue_path(&self) -> PathBuf {
+        self.dir.join("queue.json")
+    }
+
+    fn deferred_path(&self) -> PathBuf {
+        self.dir.join("deferred.json")
+    }
+
+    fn checkpoint_path(&self, job_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.checkpoint", job_id))
+    }
+
+    /// The saved queue; empty if there is none or it can't be read
+    pub fn load_jobs(&self) -> Vec<FileTranscriptionJob> {
+        let key = match self.history_manager.encryption_key() {
+            Ok(key) => key,
+            Err(e) => {
+                warn!("Not loading the file transcription queue: {}", e);
+                return Vec::new();
+            }
+        };
+        match read_sealed(&self.queue_path(), key.as_ref()) {
+            Ok(jobs) => jobs.unwrap_or_default(),
+            Err(e) => {
+                warn!("Discarding unreadable file transcription queue: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn save_jobs(&self, jobs: &[FileTranscriptionJob]) -> Result<()> {
+        let key = self.history_manager.encryption_key()?;
+        write_sealed(&self.queue_path(), &jobs, key.as_ref())
+    }
+
+    /// Files held back by watch folders; empty if there are none or they can't be read
+    pub fn load_deferred(&self) -> Vec<DeferredFile> {
+        let key = match self.history_manager.encryption_key() {
+            Ok(key) => key,
+            Err(e) => {
+                warn!("Not loading deferred watch folder files: {}", e);
+                return Vec::new();
+            }
+        };
+        read_sealed(&self.deferred_path(), key.as_ref())
+            .unwrap_or_else(|e| {
+                warn!("Discarding unreadable deferred watch folder files: {}", e);
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save_deferred(&self, files: &[DeferredFile]) -> Result<()> {
+        let key = self.history_manager.encryption_key()?;
+        write_sealed(&self.deferred_path(), &files, key.as_ref())
+    }
+
+    /// The job's checkpoint, if it has one that can be read
+    pub fn load_checkpoint(&self, job_id: &str) -> Option<JobCheckpoint> {
+        let key = self.history_manager.encryption_key().ok()?;
+        read_sealed(&self.checkpoint_path(job_id), key.as_ref()).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable checkpoint of job {}: {}", job_id, e);
+            None
+        })
+    }
+
+    pub fn save_checkpoint(&self, job_id: &str, checkpoint: &JobCheckpoint) -> Result<()> {
+        let key = self.history_manager.encryption_key()?;
+        write_sealed(&self.checkpoint_path(job_id), checkpoint, key.as_ref())
+    }
+
+    pub fn remove_checkpoint(&self, job_id: &str) {
+        let path = self.checkpoint_path(job_id);
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove checkpoint {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Write `value` as JSON, sealed with `key` when given. The file is replaced
+/// in one rename so a crash never leaves half of it behind.
+fn write_sealed<T: Serialize + ?Sized>(
+    path: &Path,
+    value: &T,
+    key: Option<&HistoryKey>,
+) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut bytes = serde_json::to_vec(value)?;
+    if let Some(key) = key {
+        bytes = key.encrypt(&bytes)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+fn read_sealed<T: DeserializeOwned>(path: &Path, key: Option<&HistoryKey>) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let mut bytes = fs::read(path)?;
+    if let Some(key) = key {
+        bytes = key.decrypt(&bytes)?;
+    }
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_files_round_trip() {
+        let dir = std::env::temp_dir().join(format!("paperflow-file-jobs-{}", std::process::id()));
+        let path = dir.join("1.checkpoint");
+        let checkpoint = JobCheckpoint {
+            file_size: 42,
+            total_samples: 16000 * 120,
+            completed_until: 16000 * 60,
+            parts: vec!["First minute.".into()],
+            segments: Vec::new(),
+        };
+
+        write_sealed(&path, &checkpoint, None).unwrap();
+        assert_eq!(read_sealed(&path, None).unwrap(), Some(checkpoint));
+        assert!(!path.with_extension("tmp").exists());
+        assert_eq!(
+            read_sealed::<JobCheckpoint>(&dir.join("missing"), None).unwrap(),
+            None
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,13 +1,31 @@
 use anyhow::{anyhow, Result};
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::path::Path;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
-
-use crate::managers::transcription::TranscriptionManager;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::audio_toolkit::audio::ffmpeg;
+use crate::chapters;
+use crate::commands::export::{
+    get_export_file_extension, render_transcript, TranscriptChapter, TranscriptExport,
+    TranscriptSegment,
+};
+use crate::managers::diarization::{DiarizationManager, DiarizedSegment};
+use crate::managers::file_job_store::{FileJobStore, JobCheckpoint};
+use crate::managers::history::HistoryManager;
+use crate::managers::transcription::{
+    apply_text_processing, TranscriptionManager, TranscriptionOverrides,
+};
+use crate::managers::watch_folder::WatchFolderManager;
+use crate::media_download;
+use crate::settings::get_settings;
 
 /// Supported audio file extensions
 const AUDIO_EXTENSIONS: &[&str] = &[
@@ -17,10 +35,29 @@ const AUDIO_EXTENSIONS: &[&str] = &[
 /// Supported video file extensions (audio will be extracted)
 const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "mkv", "webm", "m4v", "wmv", "flv"];
 
+/// Jobs `process_all` runs at once: one decodes its file while another
+/// transcribes. They take turns on the engine, so more wouldn't be faster.
+const QUEUE_WORKERS: usize = 2;
+
+/// Share of a job's progress bar taken up by decoding the file
+const DECODE_PROGRESS_SHARE: f32 = 0.1;
+
+/// Length of the windows long files are transcribed in, so progress and
+/// the ETA can be updated as the job goes
+const TRANSCRIPTION_WINDOW_SECONDS: usize = 60;
+
+/// How far back from a window's end to look for a quiet spot to cut at
+const WINDOW_CUT_SEARCH_SECONDS: usize = 5;
+
+/// Frame used to find the quietest spot near a window's end (20 ms)
+const CUT_FRAME_SAMPLES: usize = 320;
+
 /// Status of a file transcription job
 #[derive(Clone, Debug, Serialize, Deserialize, Type, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum FileTranscriptionStatus {
+    /// Fetching the media of a job queued from a URL
+    Downloading,
     Queued,
     Processing,
     Completed,
@@ -42,16 +79,66 @@ pub struct FileTranscriptionJob {
     pub duration_seconds: Option<f64>,
     pub created_at: i64,
     pub completed_at: Option<i64>,
+    /// Model, language and diarization overrides for this job
+    #[serde(default)]
+    pub overrides: Option<TranscriptionOverrides>,
+    /// Expected transcription time for this job alone, from the model's realtime factor
+    #[serde(default)]
+    pub estimated_processing_seconds: Option<f64>,
+    /// Unix timestamp at which this job is expected to finish, counting the jobs ahead of it
+    #[serde(default)]
+    pub estimated_completion_at: Option<i64>,
+    /// URL the media was downloaded from, for jobs queued from a URL
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// High-priority jobs are kept ahead of the rest of the queue
+    #[serde(default)]
+    pub high_priority: bool,
+    /// Timed segments of the transcription, with speakers when diarized
+    #[serde(default)]
+    pub segments: Option<Vec<TranscriptSegment>>,
+    /// Chapters found in the transcription, when chapter detection is on
+    #[serde(default)]
+    pub chapters: Option<Vec<TranscriptChapter>>,
+    /// Watch folder the file was picked up from, which is told once it completes
+    #[serde(default)]
+    pub watch_folder_id: Option<String>,
+}
+
+/// Outcome of queueing a folder
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct FolderQueueResult {
+    /// Jobs added for the folder's files
+    pub jobs: Vec<FileTranscriptionJob>,
+    /// Files left out: unsupported, not matching the glob, already queued
+    /// or failing to queue
+    pub skipped: u32,
 }
 
 /// Event emitted during file transcription
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Type)]
 pub struct FileTranscriptionEvent {
     pub job_id: String,
     pub status: FileTranscriptionStatus,
     pub progress: f32,
     pub transcription: Option<String>,
     pub error: Option<String>,
+    pub estimated_completion_at: Option<i64>,
+}
+
+/// Estimated completion time of a single queued or running job
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct FileTranscriptionJobEta {
+    pub job_id: String,
+    pub estimated_completion_at: Option<i64>,
+}
+
+/// Event emitted whenever the queue's completion estimates change
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct FileTranscriptionEtaEvent {
+    pub jobs: Vec<FileTranscriptionJobEta>,
+    /// When the whole queue is expected to be done, if every job could be estimated
+    pub queue_completion_at: Option<i64>,
 }
 
 /// Manager for handling file-based transcription
@@ -59,23 +146,52 @@ pub struct FileTranscriptionManager {
     app_handle: AppHandle,
     transcription_manager: Arc<TranscriptionManager>,
     jobs: Arc<Mutex<Vec<FileTranscriptionJob>>>,
+    /// Cancels every running job and stops the queue
     cancel_flag: Arc<AtomicBool>,
     is_processing: Arc<AtomicBool>,
-    current_job_id: Arc<Mutex<Option<String>>>,
+    /// While set, workers finish their current job and take no new ones
+    is_paused: Arc<AtomicBool>,
+    /// Jobs being processed right now, by job ID
+    running_jobs: Arc<Mutex<HashMap<String, RunningJob>>>,
+    /// Cancel flags of the URL jobs still downloading, by job ID
+    downloads: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    /// Held while a job switches models or runs an engine. Jobs decode
+    /// their files in parallel but take turns transcribing.
+    engine_lock: Arc<Mutex<()>>,
+    /// Runs the jobs that ask for another model than the selected one,
+    /// leaving the dictation model loaded
+    job_engine: TranscriptionManager,
+    /// Saved queue and per-job checkpoints
+    store: FileJobStore,
+}
+
+struct RunningJob {
+    /// When the job got the engine, for the remaining-time estimate
+    started: Instant,
+    cancel_flag: Arc<AtomicBool>,
+    /// Time left as measured from the windows transcribed so far
+    measured_remaining_seconds: Option<f64>,
 }
 
 impl FileTranscriptionManager {
     pub fn new(
         app_handle: &AppHandle,
         transcription_manager: Arc<TranscriptionManager>,
+        history_manager: Arc<HistoryManager>,
     ) -> Result<Self> {
+        let store = FileJobStore::open(app_handle, history_manager)?;
         let manager = Self {
             app_handle: app_handle.clone(),
+            job_engine: transcription_manager.job_engine(),
             transcription_manager,
-            jobs: Arc::new(Mutex::new(Vec::new())),
+            jobs: Arc::new(Mutex::new(store.load_jobs())),
             cancel_flag: Arc::new(AtomicBool::new(false)),
             is_processing: Arc::new(AtomicBool::new(false)),
-            current_job_id: Arc::new(Mutex::new(None)),
+            is_paused: Arc::new(AtomicBool::new(false)),
+            running_jobs: Arc::new(Mutex::new(HashMap::new())),
+            downloads: Arc::new(Mutex::new(HashMap::new())),
+            engine_lock: Arc::new(Mutex::new(())),
+            store,
         };
 
         // Recovery: Reset any stuck "processing" jobs from previous session
@@ -85,7 +201,8 @@ impl FileTranscriptionManager {
         Ok(manager)
     }
 
-    /// Reset any jobs that were stuck in "processing" state (from app crash)
+    /// Reset any jobs that were stuck in "processing" state (from app crash).
+    /// Their checkpoints let them continue where they stopped.
     fn recover_stuck_jobs(&self) {
         let mut jobs = self.jobs.lock().unwrap();
         for job in jobs.iter_mut() {
@@ -94,8 +211,24 @@ impl FileTranscriptionManager {
                 job.status = FileTranscriptionStatus::Queued;
                 job.progress = 0.0;
                 job.error = None;
+            } else if job.status == FileTranscriptionStatus::Downloading {
+                warn!("Download of job {} was interrupted", job.id);
+                job.status = FileTranscriptionStatus::Failed;
+                job.error = Some("Download was interrupted".to_string());
+                job.completed_at = Some(chrono::Utc::now().timestamp());
             }
         }
+        drop(jobs);
+        self.save_jobs();
+    }
+
+    /// Save the queue so it survives a restart. Called whenever jobs are
+    /// added, removed, reordered or change status, but not on progress.
+    fn save_jobs(&self) {
+        let jobs = self.jobs.lock().unwrap().clone();
+        if let Err(e) = self.store.save_jobs(&jobs) {
+            warn!("Failed to save file transcription queue: {}", e);
+        }
     }
 
     /// Check if a file path has a supported audio extension
@@ -131,8 +264,13 @@ impl FileTranscriptionManager {
         extensions
     }
 
-    /// Add a file to the transcription queue
-    pub fn queue_file(&self, file_path: &str) -> Result<FileTranscriptionJob> {
+    /// Add a file to the transcription queue, transcribing it with the given
+    /// overrides instead of the global model/language/diarization/translation settings
+    pub fn queue_file_with_overrides(
+        &self,
+        file_path: &str,
+        overrides: Option<TranscriptionOverrides>,
+    ) -> Result<FileTranscriptionJob> {
         let path = Path::new(file_path);
 
         // Validate file exists
@@ -170,16 +308,7 @@ impl FileTranscriptionManager {
             );
         }
 
-        // Generate unique job ID
-        let job_id = format!(
-            "job_{}_{}",
-            chrono::Utc::now().timestamp_millis(),
-            uuid::Uuid::new_v4()
-                .to_string()
-                .split('-')
-                .next()
-                .unwrap_or("0000")
-        );
+        let job_id = Self::new_job_id();
 
         let file_name = path
             .file_name()
@@ -196,9 +325,17 @@ impl FileTranscriptionManager {
             progress: 0.0,
             transcription: None,
             error: None,
-            duration_seconds: None,
+            duration_seconds: self.probe_duration_seconds(path),
             created_at: chrono::Utc::now().timestamp(),
             completed_at: None,
+            overrides: overrides.filter(|o| !o.is_empty()),
+            estimated_processing_seconds: None,
+            estimated_completion_at: None,
+            source_url: None,
+            high_priority: false,
+            segments: None,
+            chapters: None,
+            watch_folder_id: None,
         };
 
         // Add to queue
@@ -206,6 +343,9 @@ impl FileTranscriptionManager {
             let mut jobs = self.jobs.lock().unwrap();
             jobs.push(job.clone());
         }
+        self.save_jobs();
+        self.refresh_estimates();
+        let job = self.get_job(&job_id).unwrap_or(job);
 
         // Emit queued event
         self.emit_job_event(&job);
@@ -215,13 +355,170 @@ impl FileTranscriptionManager {
         Ok(job)
     }
 
+    /// Queue a file picked up by a watch folder. The folder's post-processing
+    /// action runs once the job completes.
+    pub fn queue_watched_file(
+        &self,
+        file_path: &str,
+        folder_id: &str,
+        overrides: TranscriptionOverrides,
+    ) -> Result<FileTranscriptionJob> {
+        let mut job = self.queue_file_with_overrides(file_path, Some(overrides))?;
+        if let Some(queued) = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|j| j.id == job.id)
+        {
+            queued.watch_folder_id = Some(folder_id.to_string());
+            job = queued.clone();
+        }
+        self.save_jobs();
+        Ok(job)
+    }
+
+    /// Generate a unique job ID
+    fn new_job_id() -> String {
+        format!(
+            "job_{}_{}",
+            chrono::Utc::now().timestamp_millis(),
+            uuid::Uuid::new_v4()
+                .to_string()
+                .split('-')
+                .next()
+                .unwrap_or("0000")
+        )
+    }
+
+    /// Add a job that downloads the media at `url` and then joins the queue
+    /// like any other file. Download progress is reported on the job while
+    /// its status is `Downloading`.
+    pub fn queue_url(
+        self: &Arc<Self>,
+        url: &str,
+        overrides: Option<TranscriptionOverrides>,
+    ) -> Result<FileTranscriptionJob> {
+        let parsed = media_download::validate_url(url)?;
+        let job_id = Self::new_job_id();
+        let file_name = parsed
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .or_else(|| parsed.host_str().map(str::to_string))
+            .unwrap_or_else(|| parsed.to_string());
+
+        let job = FileTranscriptionJob {
+            id: job_id.clone(),
+            file_path: String::new(),
+            file_name,
+            file_size: 0,
+            status: FileTranscriptionStatus::Downloading,
+            progress: 0.0,
+            transcription: None,
+            error: None,
+            duration_seconds: None,
+            created_at: chrono::Utc::now().timestamp(),
+            completed_at: None,
+            overrides: overrides.filter(|o| !o.is_empty()),
+            estimated_processing_seconds: None,
+            estimated_completion_at: None,
+            source_url: Some(parsed.to_string()),
+            high_priority: false,
+            segments: None,
+            chapters: None,
+            watch_folder_id: None,
+        };
+        self.jobs.lock().unwrap().push(job.clone());
+        self.save_jobs();
+        self.emit_job_event(&job);
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        self.downloads
+            .lock()
+            .unwrap()
+            .insert(job_id.clone(), cancel_flag.clone());
+
+        let downloads_dir = self.app_handle.path().app_data_dir()?.join("downloads");
+        let manager = self.clone();
+        let url = parsed.to_string();
+        tauri::async_runtime::spawn(async move {
+            let progress_manager = manager.clone();
+            let progress_job_id = job_id.clone();
+            let on_progress: media_download::ProgressFn = Arc::new(move |progress| {
+                progress_manager.update_job_progress(&progress_job_id, progress);
+            });
+            let result =
+                media_download::download(&url, &downloads_dir, &job_id, cancel_flag, on_progress)
+                    .await;
+            manager.downloads.lock().unwrap().remove(&job_id);
+            manager.finish_download(&job_id, result);
+        });
+
+        info!("Queued URL for transcription: {} ({})", parsed, job.id);
+        Ok(job)
+    }
+
+    /// Queue a downloaded job's file, or fail the job if the download failed
+    fn finish_download(&self, job_id: &str, result: Result<std::path::PathBuf>) {
+        let path = match result {
+            Ok(path) => path,
+            Err(e) => {
+                let error_msg = e.to_string();
+                let status = if error_msg.contains("cancelled") {
+                    FileTranscriptionStatus::Cancelled
+                } else {
+                    FileTranscriptionStatus::Failed
+                };
+                warn!("Download for job {} failed: {}", job_id, error_msg);
+                self.update_job_status(job_id, status, None, Some(error_msg));
+                return;
+            }
+        };
+
+        let file_size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let duration_seconds = self.probe_duration_seconds(&path);
+        let job = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let Some(job) = jobs.iter_mut().find(|j| j.id == job_id) else {
+                // Removed while downloading
+                let _ = std::fs::remove_file(&path);
+                return;
+            };
+            job.file_path = path.to_string_lossy().to_string();
+            job.file_size = file_size;
+            job.duration_seconds = duration_seconds;
+            job.status = FileTranscriptionStatus::Queued;
+            job.progress = 0.0;
+            job.clone()
+        };
+        info!("Downloaded {} for job {}", job.file_path, job.id);
+        self.save_jobs();
+        self.refresh_estimates();
+        self.emit_job_event(&self.get_job(job_id).unwrap_or(job));
+    }
+
+    /// Delete the media a URL job downloaded
+    fn remove_downloaded_file(job: &FileTranscriptionJob) {
+        if job.source_url.is_some() && !job.file_path.is_empty() {
+            if let Err(e) = std::fs::remove_file(&job.file_path) {
+                debug!("Could not remove downloaded file {}: {}", job.file_path, e);
+            }
+        }
+    }
+
     /// Queue multiple files at once
-    pub fn queue_files(&self, file_paths: &[String]) -> Result<Vec<FileTranscriptionJob>> {
+    pub fn queue_files(
+        &self,
+        file_paths: &[String],
+        overrides: Option<TranscriptionOverrides>,
+    ) -> Result<Vec<FileTranscriptionJob>> {
         let mut jobs = Vec::new();
         let mut errors = Vec::new();
 
         for path in file_paths {
-            match self.queue_file(path) {
+            match self.queue_file_with_overrides(path, overrides.clone()) {
                 Ok(job) => jobs.push(job),
                 Err(e) => errors.push(format!("{}: {}", path, e)),
             }
@@ -234,49 +531,167 @@ impl FileTranscriptionManager {
         Ok(jobs)
     }
 
+    /// Queue the supported files in `folder`, and with `recursive` in its
+    /// subfolders too. `glob` narrows them down: a pattern without a `/`,
+    /// like `*.m4a`, is matched against file names, any other against the
+    /// path below `folder`, like `2024/**/*.mp3`. Hidden files and folders
+    /// are left out, as are files already waiting in the queue.
+    pub fn queue_folder(
+        &self,
+        folder: &str,
+        recursive: bool,
+        glob: Option<&str>,
+        overrides: Option<TranscriptionOverrides>,
+    ) -> Result<FolderQueueResult> {
+        let root = Path::new(folder);
+        if !root.is_dir() {
+            return Err(anyhow!("Folder does not exist: {}", folder));
+        }
+        let pattern = glob
+            .map(str::trim)
+            .filter(|g| !g.is_empty())
+            .map(|g| glob::Pattern::new(g).map_err(|e| anyhow!("Invalid glob '{}': {}", g, e)))
+            .transpose()?;
+
+        let pending: Vec<String> = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|j| {
+                j.status == FileTranscriptionStatus::Queued
+                    || j.status == FileTranscriptionStatus::Processing
+            })
+            .map(|j| j.file_path.clone())
+            .collect();
+
+        let mut jobs = Vec::new();
+        let mut skipped = 0;
+        for path in folder_files(root, recursive)? {
+            let wanted = Self::is_supported_file(&path)
+                && pattern
+                    .as_ref()
+                    .is_none_or(|p| glob_matches(p, root, &path));
+            let file_path = path.to_string_lossy().to_string();
+            if !wanted || pending.contains(&file_path) {
+                skipped += 1;
+                continue;
+            }
+            match self.queue_file_with_overrides(&file_path, overrides.clone()) {
+                Ok(job) => jobs.push(job),
+                Err(e) => {
+                    warn!("Skipping {}: {}", file_path, e);
+                    skipped += 1;
+                }
+            }
+        }
+
+        info!(
+            "Queued {} files from {} ({} skipped)",
+            jobs.len(),
+            folder,
+            skipped
+        );
+        Ok(FolderQueueResult { jobs, skipped })
+    }
+
     /// Process the next job in the queue
     pub fn process_next(&self) -> Result<Option<String>> {
         // Check if already processing
-        if self.is_processing.load(Ordering::SeqCst) {
+        if self.is_processing.swap(true, Ordering::SeqCst) {
             return Ok(None);
         }
+        self.cancel_flag.store(false, Ordering::SeqCst);
 
-        // Find next queued job
-        let job_to_process = {
-            let jobs = self.jobs.lock().unwrap();
-            jobs.iter()
-                .find(|j| j.status == FileTranscriptionStatus::Queued)
-                .cloned()
-        };
+        let processed = self.run_next_job();
 
-        let job = match job_to_process {
-            Some(j) => j,
-            None => return Ok(None),
-        };
+        self.is_processing.store(false, Ordering::SeqCst);
+        Ok(processed)
+    }
 
-        // Mark as processing
-        self.is_processing.store(true, Ordering::SeqCst);
+    /// Process all queued jobs, decoding the next file while the current one
+    /// is transcribed
+    pub fn process_all(&self) -> Result<Vec<String>> {
+        if self.is_processing.swap(true, Ordering::SeqCst) {
+            return Ok(Vec::new());
+        }
         self.cancel_flag.store(false, Ordering::SeqCst);
-        {
-            let mut current = self.current_job_id.lock().unwrap();
-            *current = Some(job.id.clone());
+
+        let processed_ids = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for _ in 0..QUEUE_WORKERS {
+                scope.spawn(|| {
+                    // Check for cancellation between jobs
+                    while !self.cancel_flag.load(Ordering::SeqCst) {
+                        match self.run_next_job() {
+                            Some(id) => processed_ids.lock().unwrap().push(id),
+                            None => break,
+                        }
+                    }
+                });
+            }
+        });
+
+        self.is_processing.store(false, Ordering::SeqCst);
+        Ok(processed_ids.into_inner().unwrap())
+    }
+
+    /// Claim the next queued job and process it, returning its ID, or None
+    /// once the queue is empty
+    fn run_next_job(&self) -> Option<String> {
+        if self.is_paused.load(Ordering::SeqCst) {
+            return None;
         }
 
+        // Find the next queued job and mark it in the same lock, so two
+        // workers never pick the same one
+        let job = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let job = jobs
+                .iter_mut()
+                .find(|j| j.status == FileTranscriptionStatus::Queued)?;
+            job.status = FileTranscriptionStatus::Processing;
+            job.clone()
+        };
+        self.save_jobs();
+
         let job_id = job.id.clone();
-        self.update_job_status(&job_id, FileTranscriptionStatus::Processing, None, None);
+        self.running_jobs.lock().unwrap().insert(
+            job_id.clone(),
+            RunningJob {
+                started: Instant::now(),
+                cancel_flag: Arc::new(AtomicBool::new(false)),
+                measured_remaining_seconds: None,
+            },
+        );
+        self.emit_job_event(&job);
+        self.refresh_estimates();
 
         // Process the file
         let result = self.process_file(&job);
 
         // Update final status
         match result {
-            Ok(transcription) => {
+            Ok((transcription, segments)) => {
+                let chapters = self.detect_chapters(&job_id, &segments);
+                self.write_sidecar_files(&job, &transcription, &segments, chapters.as_ref());
+                if let Some(done) = self
+                    .jobs
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .find(|j| j.id == job_id)
+                {
+                    done.segments = Some(segments).filter(|s| !s.is_empty());
+                    done.chapters = chapters;
+                }
                 self.update_job_status(
                     &job_id,
                     FileTranscriptionStatus::Completed,
                     Some(transcription),
                     None,
                 );
+                self.finish_watched_file(&job_id);
             }
             Err(e) => {
                 let error_msg = e.to_string();
@@ -298,41 +713,375 @@ impl FileTranscriptionManager {
             }
         }
 
-        // Clear processing state
-        self.is_processing.store(false, Ordering::SeqCst);
-        {
-            let mut current = self.current_job_id.lock().unwrap();
-            *current = None;
-        }
+        // Clear processing state. A job that didn't complete starts over if
+        // it is queued again, so its checkpoint goes either way.
+        self.store.remove_checkpoint(&job_id);
+        self.running_jobs.lock().unwrap().remove(&job_id);
+        self.release_job_engine_if_unneeded();
+        self.refresh_estimates();
 
-        Ok(Some(job_id))
+        Some(job_id)
     }
 
-    /// Process all queued jobs
-    pub fn process_all(&self) -> Result<Vec<String>> {
-        let mut processed_ids = Vec::new();
+    /// Write the transcription next to the job's source file, or into its
+    /// sidecar folder, in each of its sidecar formats, e.g. `interview.mp3` ->
+    /// `interview.srt`
+    fn write_sidecar_files(
+        &self,
+        job: &FileTranscriptionJob,
+        transcription: &str,
+        segments: &[TranscriptSegment],
+        chapters: Option<&Vec<TranscriptChapter>>,
+    ) {
+        let Some(formats) = job
+            .overrides
+            .as_ref()
+            .and_then(|o| o.sidecar_formats.as_ref())
+        else {
+            return;
+        };
+        // Downloads live in app data, where nobody would find their sidecars
+        if job.source_url.is_some() {
+            return;
+        }
 
-        loop {
-            match self.process_next()? {
-                Some(id) => processed_ids.push(id),
-                None => break,
+        let source = Path::new(&job.file_path);
+        let transcript = TranscriptExport {
+            title: source
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string()),
+            source_file: Some(job.file_name.clone()),
+            duration_ms: job.duration_seconds.map(|secs| (secs * 1000.0) as u64),
+            created_at: chrono::Utc::now().timestamp(),
+            text: transcription.to_string(),
+            segments: Some(segments.to_vec()).filter(|s| !s.is_empty()),
+            chapters: chapters.cloned(),
+        };
+        let overrides = job.overrides.clone().unwrap_or_default();
+        let dir = match &overrides.sidecar_folder {
+            Some(folder) => {
+                if let Err(e) = std::fs::create_dir_all(folder) {
+                    warn!("Failed to create transcript folder {}: {}", folder, e);
+                    return;
+                }
+                PathBuf::from(folder)
             }
+            None => source.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        let source_stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| job.file_name.clone());
+        let stem = match &overrides.sidecar_name_template {
+            Some(template) => {
+                let model = overrides
+                    .model_id
+                    .clone()
+                    .or_else(|| self.transcription_manager.get_current_model())
+                    .unwrap_or_default();
+                let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+                render_name_template(template, &source_stem, &date, &model).unwrap_or_else(|e| {
+                    warn!("Ignoring name template of job {}: {}", job.id, e);
+                    source_stem.clone()
+                })
+            }
+            None => source_stem,
+        };
+        for format in formats {
+            let path = dir.join(format!(
+                "{}.{}",
+                stem,
+                get_export_file_extension(format.clone())
+            ));
+            let written = render_transcript(&transcript, format)
+                .and_then(|bytes| std::fs::write(&path, bytes).map_err(Into::into));
+            match written {
+                Ok(()) => info!("Wrote {}", path.display()),
+                Err(e) => warn!("Failed to write {}: {}", path.display(), e),
+            }
+        }
+    }
 
-            // Check for cancellation between jobs
-            if self.cancel_flag.load(Ordering::SeqCst) {
-                break;
+    /// Run the post-processing action of the watch folder a completed job's
+    /// file came from, following the file if it was moved
+    fn finish_watched_file(&self, job_id: &str) {
+        let Some(job) = self.get_job(job_id) else {
+            return;
+        };
+        let Some(folder_id) = &job.watch_folder_id else {
+            return;
+        };
+        let Some(watch_folders) = self.app_handle.try_state::<Arc<WatchFolderManager>>() else {
+            return;
+        };
+        match watch_folders.finish_file(folder_id, Path::new(&job.file_path)) {
+            Ok(Some(moved_to)) => {
+                if let Some(done) = self
+                    .jobs
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .find(|j| j.id == job_id)
+                {
+                    done.file_path = moved_to.to_string_lossy().to_string();
+                }
+                self.save_jobs();
             }
+            Ok(None) => {}
+            Err(e) => warn!("Post-processing of {} failed: {}", job.file_path, e),
         }
+    }
 
-        Ok(processed_ids)
+    /// Chapters of a completed job, when chapter detection is on and the
+    /// file is long enough to need them
+    fn detect_chapters(
+        &self,
+        job_id: &str,
+        segments: &[TranscriptSegment],
+    ) -> Option<Vec<TranscriptChapter>> {
+        let settings = get_settings(&self.app_handle);
+        if !settings.file_transcription_detect_chapters
+            || segments
+                .last()
+                .is_none_or(|s| s.end_ms < chapters::MIN_CHAPTERS_DURATION_MS)
+        {
+            return None;
+        }
+        match tauri::async_runtime::block_on(chapters::detect_chapters(&settings, segments)) {
+            Ok(chapters) => {
+                debug!("Found {} chapters in job {}", chapters.len(), job_id);
+                Some(chapters).filter(|c| !c.is_empty())
+            }
+            Err(e) => {
+                warn!("Failed to detect chapters in job {}: {}", job_id, e);
+                None
+            }
+        }
     }
 
-    /// Process a single file and return the transcription
-    fn process_file(&self, job: &FileTranscriptionJob) -> Result<String> {
-        let path = Path::new(&job.file_path);
+    /// Whether the job, or the whole queue, has been cancelled
+    fn is_cancelled(&self, job_id: &str) -> bool {
+        self.cancel_flag.load(Ordering::SeqCst)
+            || self
+                .running_jobs
+                .lock()
+                .unwrap()
+                .get(job_id)
+                .is_some_and(|running| running.cancel_flag.load(Ordering::SeqCst))
+    }
 
+    /// Process a single file and return the transcription and its timed segments
+    fn process_file(&self, job: &FileTranscriptionJob) -> Result<(String, Vec<TranscriptSegment>)> {
         info!("Processing file: {}", job.file_path);
 
+        let overrides = job.overrides.clone().unwrap_or_default();
+
+        // Load and decode the audio file
+        let audio_samples = self.load_audio_file(job)?;
+
+        // Now that the real duration is known, tighten the estimates
+        let duration_seconds = audio_samples.len() as f64 / 16000.0;
+        if let Some(queued) = self
+            .jobs
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|j| j.id == job.id)
+        {
+            queued.duration_seconds = Some(duration_seconds);
+        }
+        self.refresh_estimates();
+
+        // Check for cancellation
+        if self.is_cancelled(&job.id) {
+            return Err(anyhow!("Transcription cancelled"));
+        }
+
+        // Update progress
+        self.update_job_progress(&job.id, DECODE_PROGRESS_SHARE);
+
+        // Diarize the whole file once so speakers stay consistent across windows
+        let mut settings = get_settings(&self.app_handle);
+        overrides.apply(&mut settings);
+        let speaker_segments = if settings.diarization_enabled {
+            self.diarize_file(&audio_samples)
+        } else {
+            Vec::new()
+        };
+        let window_overrides = TranscriptionOverrides {
+            diarization_enabled: Some(false),
+            ..overrides.clone()
+        };
+
+        // Wait for the engine, which the other workers may be using
+        let _engine = self.engine_lock.lock().unwrap();
+        // The estimate is transcription time, so it counts from here
+        if let Some(running) = self.running_jobs.lock().unwrap().get_mut(&job.id) {
+            running.started = Instant::now();
+        }
+
+        // A job asking for another model gets the job engine, so dictation
+        // keeps its model while the job runs
+        let engine = match &overrides.model_id {
+            Some(model_id) if *model_id != settings.selected_model => {
+                if self.job_engine.get_current_model().as_deref() != Some(model_id) {
+                    info!("Loading model {} for job {}", model_id, job.id);
+                    self.job_engine
+                        .load_model(model_id)
+                        .map_err(|e| anyhow!("Failed to load model {}: {}", model_id, e))?;
+                }
+                &self.job_engine
+            }
+            _ => {
+                self.wait_for_selected_model(&job.id)?;
+                &*self.transcription_manager
+            }
+        };
+
+        let windows = plan_windows(
+            &audio_samples,
+            TRANSCRIPTION_WINDOW_SECONDS * 16000,
+            WINDOW_CUT_SEARCH_SECONDS * 16000,
+        );
+
+        // Continue from the job's checkpoint if it was interrupted, unless the
+        // file has changed since
+        let file_size = std::fs::metadata(&job.file_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let mut checkpoint = self
+            .store
+            .load_checkpoint(&job.id)
+            .filter(|c| c.file_size == file_size && c.total_samples == audio_samples.len())
+            .unwrap_or_else(|| JobCheckpoint {
+                file_size,
+                total_samples: audio_samples.len(),
+                ..Default::default()
+            });
+        if checkpoint.completed_until > 0 {
+            info!(
+                "Resuming job {} at {:.0}s",
+                job.id,
+                checkpoint.completed_until as f64 / 16000.0
+            );
+        }
+
+        // Transcribe window by window, updating the progress and ETA after each
+        let started = Instant::now();
+        let total_samples = audio_samples.len() as f64;
+        let resumed = checkpoint.completed_until as f64 / total_samples;
+        for window in windows {
+            if window.end <= checkpoint.completed_until {
+                continue;
+            }
+            if self.is_cancelled(&job.id) {
+                return Err(anyhow!("Transcription cancelled"));
+            }
+
+            let output = engine
+                .transcribe_detailed(audio_samples[window.clone()].to_vec(), &window_overrides)?;
+            let text = output.text.trim();
+            if !text.is_empty() {
+                checkpoint.parts.push(text.to_string());
+            }
+
+            // Segment times are relative to the window; engines without
+            // timings get one segment for the whole window
+            let offset_ms = window.start as u64 / 16;
+            if output.segments.is_empty() && !output.raw_text.trim().is_empty() {
+                checkpoint.segments.push(TranscriptSegment {
+                    start_ms: offset_ms,
+                    end_ms: window.end as u64 / 16,
+                    text: output.raw_text.trim().to_string(),
+                    speaker: None,
+                });
+            }
+            checkpoint
+                .segments
+                .extend(output.segments.into_iter().map(|s| TranscriptSegment {
+                    start_ms: s.start_ms + offset_ms,
+                    end_ms: s.end_ms + offset_ms,
+                    ..s
+                }));
+
+            checkpoint.completed_until = window.end;
+            if let Err(e) = self.store.save_checkpoint(&job.id, &checkpoint) {
+                warn!("Failed to save checkpoint of job {}: {}", job.id, e);
+            }
+
+            // Only the windows transcribed since a resume tell the pace
+            let done = window.end as f64 / total_samples;
+            let elapsed = started.elapsed().as_secs_f64();
+            if let Some(running) = self.running_jobs.lock().unwrap().get_mut(&job.id) {
+                running.measured_remaining_seconds =
+                    Some(elapsed / (done - resumed) * (1.0 - done));
+            }
+            self.refresh_estimates();
+            self.update_job_progress(
+                &job.id,
+                DECODE_PROGRESS_SHARE + (1.0 - DECODE_PROGRESS_SHARE) * done as f32,
+            );
+        }
+
+        let JobCheckpoint {
+            parts, segments, ..
+        } = checkpoint;
+        if speaker_segments.is_empty() {
+            return Ok((parts.join(" "), segments));
+        }
+
+        // Label each segment with its speaker and write the text as speaker turns
+        let timed: Vec<(u64, u64, String)> = segments
+            .into_iter()
+            .map(|s| (s.start_ms, s.end_ms, s.text))
+            .collect();
+        let segments: Vec<TranscriptSegment> =
+            DiarizationManager::assign_speakers_to_segments(&timed, &speaker_segments)
+                .into_iter()
+                .map(|(start_ms, end_ms, text, speaker)| TranscriptSegment {
+                    start_ms,
+                    end_ms,
+                    text,
+                    speaker,
+                })
+                .collect();
+        let text = speaker_turns(&segments)
+            .into_iter()
+            .map(|(speaker, text)| {
+                format!(
+                    "[{}]: {}",
+                    speaker,
+                    apply_text_processing(&settings, text, None)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok((text, segments))
+    }
+
+    /// Speaker turns across the whole file, if its models are available
+    fn diarize_file(&self, audio: &[f32]) -> Vec<DiarizedSegment> {
+        let Some(dm) = self.app_handle.try_state::<Arc<DiarizationManager>>() else {
+            return Vec::new();
+        };
+        if !dm.is_available() {
+            warn!("Diarization is enabled but its models are not available");
+            return Vec::new();
+        }
+        match dm.diarize(audio) {
+            Ok(segments) => {
+                info!("File diarization found {} segments", segments.len());
+                segments
+            }
+            Err(e) => {
+                warn!("File diarization failed: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Load the selected model into the shared engine if it isn't yet
+    fn wait_for_selected_model(&self, job_id: &str) -> Result<()> {
         // Ensure model is loaded before processing
         if !self.transcription_manager.is_model_loaded() {
             info!("Model not loaded, initiating load for file transcription");
@@ -350,36 +1099,209 @@ impl FileTranscriptionManager {
                 }
 
                 // Check for cancellation while waiting
-                if self.cancel_flag.load(Ordering::SeqCst) {
+                if self.is_cancelled(job_id) {
                     return Err(anyhow!("Transcription cancelled while waiting for model"));
                 }
 
                 std::thread::sleep(std::time::Duration::from_millis(100));
             }
         }
+        Ok(())
+    }
 
-        // Load and decode the audio file
-        let audio_samples = self.load_audio_file(path)?;
+    /// Unload the job engine's model once no job is running, unless the
+    /// next queued job wants it
+    fn release_job_engine_if_unneeded(&self) {
+        if !self.running_jobs.lock().unwrap().is_empty() {
+            return;
+        }
+        let _engine = self.engine_lock.lock().unwrap();
+        let Some(current_model) = self.job_engine.get_current_model() else {
+            return;
+        };
 
-        // Check for cancellation
-        if self.cancel_flag.load(Ordering::SeqCst) {
-            return Err(anyhow!("Transcription cancelled"));
+        let next_model = {
+            let jobs = self.jobs.lock().unwrap();
+            jobs.iter()
+                .find(|j| j.status == FileTranscriptionStatus::Queued)
+                .and_then(|j| j.overrides.as_ref())
+                .and_then(|o| o.model_id.clone())
+        };
+        if next_model.as_deref() == Some(current_model.as_str()) {
+            return;
         }
 
-        // Update progress
-        self.update_job_progress(&job.id, 0.5);
+        info!(
+            "Unloading job model {} after file transcription",
+            current_model
+        );
+        if let Err(e) = self.job_engine.unload_model() {
+            warn!("Failed to unload job model {}: {}", current_model, e);
+        }
+    }
 
-        // Transcribe
-        let transcription = self.transcription_manager.transcribe(audio_samples)?;
+    /// Read the duration from the file's container metadata without decoding it
+    fn probe_duration_seconds(&self, path: &Path) -> Option<f64> {
+        let ffmpeg_path = get_settings(&self.app_handle).ffmpeg_path;
+        if let Some(ffprobe) = ffmpeg::find_tool("ffprobe", &ffmpeg_path) {
+            if let Some(duration) = ffmpeg::probe_duration(&ffprobe, path) {
+                return Some(duration);
+            }
+        }
 
-        // Update progress
-        self.update_job_progress(&job.id, 1.0);
+        use symphonia::core::formats::FormatOptions;
+        use symphonia::core::io::MediaSourceStream;
+        use symphonia::core::meta::MetadataOptions;
+        use symphonia::core::probe::Hint;
+
+        let file = std::fs::File::open(path).ok()?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let probed = symphonia::default::get_probe()
+            .format(
+                &hint,
+                mss,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )
+            .ok()?;
+        let track = probed
+            .format
+            .tracks()
+            .iter()
+            .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)?;
+
+        let frames = track.codec_params.n_frames?;
+        let sample_rate = track.codec_params.sample_rate?;
+        Some(frames as f64 / sample_rate as f64)
+    }
 
-        Ok(transcription)
+    /// Recompute the estimated completion time of every pending job and emit
+    /// them. Jobs are transcribed in queue order, one at a time: workers only
+    /// overlap decoding, and the estimates are transcription time.
+    fn refresh_estimates(&self) {
+        let settings = get_settings(&self.app_handle);
+        let running: HashMap<String, (f64, Option<f64>)> = self
+            .running_jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, job)| {
+                let elapsed = job.started.elapsed().as_secs_f64();
+                (id.clone(), (elapsed, job.measured_remaining_seconds))
+            })
+            .collect();
+        let now = chrono::Utc::now().timestamp();
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut pending: Vec<&mut FileTranscriptionJob> = jobs
+            .iter_mut()
+            .filter(|j| {
+                j.status == FileTranscriptionStatus::Processing
+                    || j.status == FileTranscriptionStatus::Queued
+            })
+            .collect();
+
+        let remaining: Vec<Option<f64>> = pending
+            .iter_mut()
+            .map(|job| {
+                let model_id = job
+                    .overrides
+                    .as_ref()
+                    .and_then(|o| o.model_id.as_deref())
+                    .unwrap_or(&settings.selected_model);
+                job.estimated_processing_seconds = job.duration_seconds.and_then(|duration| {
+                    self.transcription_manager
+                        .estimate_processing_seconds(Some(model_id), duration)
+                });
+                match running.get(&job.id) {
+                    Some((_, Some(measured))) => Some(*measured),
+                    Some((elapsed, None)) => job
+                        .estimated_processing_seconds
+                        .map(|estimate| (estimate - elapsed).max(0.0)),
+                    None => job.estimated_processing_seconds,
+                }
+            })
+            .collect();
+        let finish_in = schedule_jobs(&remaining);
+
+        let mut etas = Vec::new();
+        for (job, finish_in) in pending.into_iter().zip(finish_in) {
+            job.estimated_completion_at = finish_in.map(|secs| now + secs.ceil() as i64);
+            etas.push(FileTranscriptionJobEta {
+                job_id: job.id.clone(),
+                estimated_completion_at: job.estimated_completion_at,
+            });
+        }
+        drop(jobs);
+
+        let queue_completion_at = etas
+            .iter()
+            .map(|eta| eta.estimated_completion_at)
+            .try_fold(None, |latest: Option<i64>, at| Some(latest.max(Some(at?))));
+        let event = FileTranscriptionEtaEvent {
+            queue_completion_at: queue_completion_at.flatten(),
+            jobs: etas,
+        };
+        if let Err(e) = event.emit(&self.app_handle) {
+            error!("Failed to emit file transcription ETA: {}", e);
+        }
+    }
+
+    /// Load a job's file and return samples at 16kHz mono. ffmpeg is used when
+    /// it can be found, as it handles far more containers and codecs; otherwise
+    /// the built-in decoder is used.
+    fn load_audio_file(&self, job: &FileTranscriptionJob) -> Result<Vec<f32>> {
+        let path = Path::new(&job.file_path);
+        let ffmpeg_path = get_settings(&self.app_handle).ffmpeg_path;
+        let Some(ffmpeg) = ffmpeg::find_tool("ffmpeg", &ffmpeg_path) else {
+            return self.decode_with_symphonia(&job.id, path);
+        };
+
+        // Decoding takes up the start of the progress bar
+        let duration = job.duration_seconds.filter(|d| *d > 0.0);
+        let mut last_progress = 0.0;
+        let decoded = ffmpeg::decode(
+            &ffmpeg,
+            path,
+            || self.is_cancelled(&job.id),
+            |decoded_seconds| {
+                if let Some(duration) = duration {
+                    let progress =
+                        (decoded_seconds / duration).min(1.0) as f32 * DECODE_PROGRESS_SHARE;
+                    if progress - last_progress >= 0.01 {
+                        last_progress = progress;
+                        self.update_job_progress(&job.id, progress);
+                    }
+                }
+            },
+        );
+
+        match decoded {
+            Ok(samples) if samples.is_empty() => Err(anyhow!("No audio data found in file")),
+            Ok(samples) => {
+                info!(
+                    "Decoded {} samples ({:.2}s) from {} with ffmpeg",
+                    samples.len(),
+                    samples.len() as f64 / 16000.0,
+                    path.display()
+                );
+                Ok(samples)
+            }
+            Err(e) if self.is_cancelled(&job.id) => Err(e),
+            Err(e) => {
+                warn!("{}, falling back to the built-in decoder", e);
+                self.decode_with_symphonia(&job.id, path)
+            }
+        }
     }
 
-    /// Load an audio file and return samples at 16kHz mono
-    fn load_audio_file(&self, path: &Path) -> Result<Vec<f32>> {
+    /// Decode a file with symphonia and return samples at 16kHz mono
+    fn decode_with_symphonia(&self, job_id: &str, path: &Path) -> Result<Vec<f32>> {
         use symphonia::core::audio::SampleBuffer;
         use symphonia::core::codecs::DecoderOptions;
         use symphonia::core::formats::FormatOptions;
@@ -441,7 +1363,7 @@ impl FileTranscriptionManager {
         // Decode all packets
         loop {
             // Check for cancellation periodically
-            if self.cancel_flag.load(Ordering::SeqCst) {
+            if self.is_cancelled(job_id) {
                 return Err(anyhow!("Transcription cancelled during audio loading"));
             }
 
@@ -582,7 +1504,7 @@ impl FileTranscriptionManager {
         Ok(output)
     }
 
-    /// Cancel the current transcription job
+    /// Cancel every running transcription job and stop the queue
     pub fn cancel_current(&self) {
         info!("Cancelling current file transcription");
         self.cancel_flag.store(true, Ordering::SeqCst);
@@ -590,15 +1512,21 @@ impl FileTranscriptionManager {
 
     /// Cancel a specific job by ID
     pub fn cancel_job(&self, job_id: &str) -> Result<()> {
-        // Check if this is the current job - release lock before potentially acquiring jobs lock
-        let is_current_job = {
-            let current = self.current_job_id.lock().unwrap();
-            current.as_deref() == Some(job_id)
-        };
+        // Check if this job is running - release lock before potentially acquiring jobs lock
+        let running_flag = self
+            .running_jobs
+            .lock()
+            .unwrap()
+            .get(job_id)
+            .map(|running| running.cancel_flag.clone());
 
-        if is_current_job {
-            // Cancel the current job
-            self.cancel_current();
+        let running_flag =
+            running_flag.or_else(|| self.downloads.lock().unwrap().get(job_id).cloned());
+
+        if let Some(cancel_flag) = running_flag {
+            // Cancel just this job; the other workers keep going
+            info!("Cancelling file transcription job {}", job_id);
+            cancel_flag.store(true, Ordering::SeqCst);
         } else {
             // Remove from queue if not yet processing
             let mut jobs = self.jobs.lock().unwrap();
@@ -607,7 +1535,9 @@ impl FileTranscriptionManager {
                     job.status = FileTranscriptionStatus::Cancelled;
                     let job_clone = job.clone();
                     drop(jobs); // Release lock before emitting
+                    self.save_jobs();
                     self.emit_job_event(&job_clone);
+                    self.refresh_estimates();
                 } else {
                     return Err(anyhow!(
                         "Cannot cancel job that is not queued or already completed"
@@ -640,25 +1570,87 @@ impl FileTranscriptionManager {
     pub fn clear_completed(&self) {
         let mut jobs = self.jobs.lock().unwrap();
         jobs.retain(|j| {
-            j.status != FileTranscriptionStatus::Completed
-                && j.status != FileTranscriptionStatus::Failed
-                && j.status != FileTranscriptionStatus::Cancelled
+            let finished = j.status == FileTranscriptionStatus::Completed
+                || j.status == FileTranscriptionStatus::Failed
+                || j.status == FileTranscriptionStatus::Cancelled;
+            if finished {
+                Self::remove_downloaded_file(j);
+            }
+            !finished
         });
+        drop(jobs);
+        self.save_jobs();
     }
 
     /// Remove a specific job
     pub fn remove_job(&self, job_id: &str) -> Result<()> {
         let mut jobs = self.jobs.lock().unwrap();
-        let initial_len = jobs.len();
-        jobs.retain(|j| j.id != job_id);
-
-        if jobs.len() == initial_len {
+        let Some(index) = jobs.iter().position(|j| j.id == job_id) else {
             return Err(anyhow!("Job not found: {}", job_id));
+        };
+        let job = jobs.remove(index);
+        drop(jobs);
+
+        // Stop a download in progress; the downloader drops the file once it sees the job is gone
+        if let Some(cancel_flag) = self.downloads.lock().unwrap().get(job_id) {
+            cancel_flag.store(true, Ordering::SeqCst);
         }
+        Self::remove_downloaded_file(&job);
+        self.store.remove_checkpoint(job_id);
+
+        self.save_jobs();
+        self.refresh_estimates();
+        Ok(())
+    }
+
+    /// Move a queued job to `position` among the queued jobs
+    pub fn move_job(&self, job_id: &str, position: usize) -> Result<()> {
+        move_queued_job(&mut self.jobs.lock().unwrap(), job_id, position)?;
+        self.save_jobs();
+        self.refresh_estimates();
+        Ok(())
+    }
 
+    /// Mark a queued job as high priority, moving it ahead of every normal
+    /// job, or back to normal at the front of the normal jobs
+    pub fn set_job_priority(&self, job_id: &str, high_priority: bool) -> Result<()> {
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            let job = jobs
+                .iter_mut()
+                .find(|j| j.id == job_id)
+                .ok_or_else(|| anyhow!("Job not found: {}", job_id))?;
+            if job.status != FileTranscriptionStatus::Queued {
+                return Err(anyhow!("Only queued jobs can be prioritized"));
+            }
+            job.high_priority = high_priority;
+            let position = if high_priority { usize::MAX } else { 0 };
+            move_queued_job(&mut jobs, job_id, position)?;
+        }
+        self.save_jobs();
+        if let Some(job) = self.get_job(job_id) {
+            self.emit_job_event(&job);
+        }
+        self.refresh_estimates();
         Ok(())
     }
 
+    /// Stop starting new jobs; running jobs finish
+    pub fn pause(&self) {
+        info!("Pausing file transcription queue");
+        self.is_paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Let jobs start again. The queue must be processed again to pick them up.
+    pub fn resume(&self) {
+        info!("Resuming file transcription queue");
+        self.is_paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.load(Ordering::SeqCst)
+    }
+
     /// Check if currently processing
     pub fn is_processing(&self) -> bool {
         self.is_processing.load(Ordering::SeqCst)
@@ -695,6 +1687,7 @@ impl FileTranscriptionManager {
 
             let job_clone = job.clone();
             drop(jobs); // Release lock before emitting
+            self.save_jobs();
             self.emit_job_event(&job_clone);
         }
     }
@@ -718,10 +1711,373 @@ impl FileTranscriptionManager {
             progress: job.progress,
             transcription: job.transcription.clone(),
             error: job.error.clone(),
+            estimated_completion_at: job.estimated_completion_at,
         };
 
-        if let Err(e) = self.app_handle.emit("file-transcription-update", event) {
+        if let Err(e) = event.emit(&self.app_handle) {
             error!("Failed to emit file transcription event: {}", e);
         }
     }
 }
+
+/// File name from a sidecar name template, e.g. "{date} {stem} ({model})".
+/// Fails on unknown placeholders and on names that would leave the folder.
+pub(crate) fn render_name_template(
+    template: &str,
+    stem: &str,
+    date: &str,
+    model: &str,
+) -> Result<String> {
+    let mut name = String::new();
+    let mut rest = template.trim();
+    while let Some(open) = rest.find('{') {
+        name.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| anyhow!("Unclosed placeholder in '{}'", template))?;
+        name.push_str(match &rest[open + 1..open + close] {
+            "stem" => stem,
+            "date" => date,
+            "model" => model,
+            other => return Err(anyhow!("Unknown placeholder {{{}}}", other)),
+        });
+        rest = &rest[open + close + 1..];
+    }
+    name.push_str(rest);
+
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(anyhow!("'{}' is not a valid file name", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Files in `dir`, and with `recursive` in its subfolders, sorted by path.
+/// Hidden entries are skipped and symlinked folders are not followed, so a
+/// link back up the tree can't loop.
+fn folder_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if is_dir {
+            if recursive {
+                files.extend(folder_files(&path, true)?);
+            }
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Whether `path` below `root` matches `pattern`, by file name when the
+/// pattern has no `/`
+pub(crate) fn glob_matches(pattern: &glob::Pattern, root: &Path, path: &Path) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+    if !pattern.as_str().contains('/') {
+        return path
+            .file_name()
+            .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options));
+    }
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let relative = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    pattern.matches_with(&relative, options)
+}
+
+/// Consecutive segments by the same speaker merged into turns
+fn speaker_turns(segments: &[TranscriptSegment]) -> Vec<(String, String)> {
+    let mut turns: Vec<(String, String)> = Vec::new();
+    for segment in segments.iter().filter(|s| !s.text.is_empty()) {
+        let speaker = segment.speaker.as_deref().unwrap_or("Unknown");
+        match turns.last_mut() {
+            Some((last, text)) if last == speaker => {
+                text.push(' ');
+                text.push_str(&segment.text);
+            }
+            _ => turns.push((speaker.to_string(), segment.text.clone())),
+        }
+    }
+    turns
+}
+
+/// Move queued job `job_id` to `position` among the queued jobs. High-priority
+/// jobs stay ahead of normal ones, so positions are clamped to the job's section.
+fn move_queued_job(
+    jobs: &mut Vec<FileTranscriptionJob>,
+    job_id: &str,
+    position: usize,
+) -> Result<()> {
+    let index = jobs
+        .iter()
+        .position(|j| j.id == job_id)
+        .ok_or_else(|| anyhow!("Job not found: {}", job_id))?;
+    if jobs[index].status != FileTranscriptionStatus::Queued {
+        return Err(anyhow!("Only queued jobs can be moved"));
+    }
+    let job = jobs.remove(index);
+
+    let queued: Vec<usize> = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, j)| j.status == FileTranscriptionStatus::Queued)
+        .map(|(i, _)| i)
+        .collect();
+    let high_priority = queued.iter().filter(|&&i| jobs[i].high_priority).count();
+    let position = if job.high_priority {
+        position.min(high_priority)
+    } else {
+        position.clamp(high_priority, queued.len())
+    };
+    let insert_at = match queued.get(position) {
+        Some(&i) => i,
+        None => queued.last().map_or(jobs.len(), |&i| i + 1),
+    };
+    jobs.insert(insert_at, job);
+    Ok(())
+}
+
+/// Sample ranges covering `samples` in windows of about `window` samples.
+/// Each window ends at the quietest frame of its last `search` samples, so
+/// words aren't cut in half.
+fn plan_windows(samples: &[f32], window: usize, search: usize) -> Vec<Range<usize>> {
+    let search = search.min(window / 2);
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while samples.len() - start > window {
+        let search_start = start + window - search;
+        let quietest = samples[search_start..start + window]
+            .chunks(CUT_FRAME_SAMPLES)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| energy(a).total_cmp(&energy(b)))
+            .map_or(search, |(frame, _)| frame * CUT_FRAME_SAMPLES);
+        let end = (search_start + quietest).max(start + 1);
+        windows.push(start..end);
+        start = end;
+    }
+    if start < samples.len() {
+        windows.push(start..samples.len());
+    }
+    windows
+}
+
+fn energy(frame: &[f32]) -> f32 {
+    frame.iter().map(|s| s * s).sum()
+}
+
+/// Seconds from now until each job finishes when the jobs are transcribed
+/// in order, one at a time, given each job's remaining processing time.
+/// Once a job can't be estimated, neither can the ones after it.
+fn schedule_jobs(remaining: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut finished_at = 0.0f64;
+    let mut known = true;
+    remaining
+        .iter()
+        .map(|remaining| {
+            known &= remaining.is_some();
+            finished_at += remaining.filter(|_| known)?;
+            Some(finished_at)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: &str, status: FileTranscriptionStatus) -> FileTranscriptionJob {
+        FileTranscriptionJob {
+            id: id.to_string(),
+            file_path: format!("/tmp/{}.mp3", id),
+            file_name: format!("{}.mp3", id),
+            file_size: 0,
+            status,
+            progress: 0.0,
+            transcription: None,
+            error: None,
+            duration_seconds: None,
+            created_at: 0,
+            completed_at: None,
+            overrides: None,
+            estimated_processing_seconds: None,
+            estimated_completion_at: None,
+            source_url: None,
+            high_priority: false,
+            segments: None,
+            chapters: None,
+            watch_folder_id: None,
+        }
+    }
+
+    fn ids(jobs: &[FileTranscriptionJob]) -> Vec<&str> {
+        jobs.iter().map(|j| j.id.as_str()).collect()
+    }
+
+    #[test]
+    fn queued_jobs_move_within_their_priority_section() {
+        use FileTranscriptionStatus::*;
+        let mut jobs = vec![
+            job("done", Completed),
+            job("a", Queued),
+            job("b", Queued),
+            job("c", Queued),
+        ];
+
+        move_queued_job(&mut jobs, "c", 0).unwrap();
+        assert_eq!(ids(&jobs), ["done", "c", "a", "b"]);
+        move_queued_job(&mut jobs, "c", 10).unwrap();
+        assert_eq!(ids(&jobs), ["done", "a", "b", "c"]);
+
+        // An urgent job goes ahead of the batch, and normal jobs can't pass it
+        jobs[3].high_priority = true;
+        move_queued_job(&mut jobs, "c", usize::MAX).unwrap();
+        assert_eq!(ids(&jobs), ["done", "c", "a", "b"]);
+        move_queued_job(&mut jobs, "b", 0).unwrap();
+        assert_eq!(ids(&jobs), ["done", "c", "b", "a"]);
+
+        assert!(move_queued_job(&mut jobs, "done", 0).is_err());
+        assert!(move_queued_job(&mut jobs, "missing", 0).is_err());
+    }
+
+    #[test]
+    fn segments_merge_into_speaker_turns() {
+        let segment = |text: &str, speaker: Option<&str>| TranscriptSegment {
+            start_ms: 0,
+            end_ms: 0,
+            text: text.to_string(),
+            speaker: speaker.map(str::to_string),
+        };
+        let segments = [
+            segment("Welcome back.", Some("Speaker 1")),
+            segment("Today we talk about bees.", Some("Speaker 1")),
+            segment("", Some("Speaker 2")),
+            segment("Thanks for having me.", Some("Speaker 2")),
+            segment("Hm.", None),
+        ];
+        assert_eq!(
+            speaker_turns(&segments),
+            vec![
+                (
+                    "Speaker 1".to_string(),
+                    "Welcome back. Today we talk about bees.".to_string()
+                ),
+                ("Speaker 2".to_string(), "Thanks for having me.".to_string()),
+                ("Unknown".to_string(), "Hm.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_are_cut_at_the_quietest_spot() {
+        // 10 frames of noise with a silent frame at index 7
+        let mut samples = vec![0.5f32; CUT_FRAME_SAMPLES * 10];
+        samples[CUT_FRAME_SAMPLES * 7..CUT_FRAME_SAMPLES * 8].fill(0.0);
+
+        let windows = plan_windows(&samples, CUT_FRAME_SAMPLES * 8, CUT_FRAME_SAMPLES * 4);
+        assert_eq!(
+            windows,
+            vec![
+                0..CUT_FRAME_SAMPLES * 7,
+                CUT_FRAME_SAMPLES * 7..samples.len()
+            ]
+        );
+        assert_eq!(
+            plan_windows(&samples, samples.len(), 100),
+            vec![0..samples.len()]
+        );
+        assert!(plan_windows(&[], 100, 10).is_empty());
+    }
+
+    #[test]
+    fn jobs_are_scheduled_one_after_another() {
+        let remaining = [Some(10.0), Some(4.0), Some(3.0), Some(5.0)];
+        assert_eq!(
+            schedule_jobs(&remaining),
+            vec![Some(10.0), Some(14.0), Some(17.0), Some(22.0)]
+        );
+        assert_eq!(
+            schedule_jobs(&[Some(1.0), None, Some(2.0)]),
+            vec![Some(1.0), None, None]
+        );
+    }
+
+    #[test]
+    fn folders_are_walked_and_filtered_by_glob() {
+        let root = std::env::temp_dir().join(format!("paperflow-folder-{}", std::process::id()));
+        for file in [
+            "a.mp3",
+            "notes.txt",
+            ".hidden.mp3",
+            "2024/b.m4a",
+            "2024/june/c.mp3",
+            ".cache/d.mp3",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"").unwrap();
+        }
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| {
+                    f.strip_prefix(&root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            relative(folder_files(&root, false).unwrap()),
+            vec!["a.mp3", "notes.txt"]
+        );
+        let all = folder_files(&root, true).unwrap();
+        assert_eq!(
+            relative(all.clone()),
+            vec!["2024/b.m4a", "2024/june/c.mp3", "a.mp3", "notes.txt"]
+        );
+
+        let matching = |glob: &str| -> Vec<String> {
+            let pattern = glob::Pattern::new(glob).unwrap();
+            relative(
+                all.iter()
+                    .filter(|f| glob_matches(&pattern, &root, f))
+                    .cloned()
+                    .collect(),
+            )
+        };
+        assert_eq!(matching("*.MP3"), vec!["2024/june/c.mp3", "a.mp3"]);
+        assert_eq!(matching("2024/*"), vec!["2024/b.m4a"]);
+        assert_eq!(matching("2024/**/*.mp3"), vec!["2024/june/c.mp3"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn name_templates_fill_in_placeholders() {
+        let render = |template| render_name_template(template, "memo", "2024-06-01", "parakeet");
+        assert_eq!(
+            render("{date} {stem} ({model})").unwrap(),
+            "2024-06-01 memo (parakeet)"
+        );
+        assert_eq!(render("transcript").unwrap(), "transcript");
+        assert!(render("{speaker}").is_err());
+        assert!(render("{stem").is_err());
+        assert!(render("../{stem}").is_err());
+        assert!(render("  ").is_err());
+    }
+}
@@ -1,72 +1,49 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local, Utc};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use rusqlite::{params, Connection, OptionalExtension};
-use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use tauri::{AppHandle, Emitter, Manager};
-
-use crate::audio_toolkit::save_wav_file;
-
-/// Database migrations for transcription history.
-/// Each migration is applied in order. The library tracks which migrations
-/// have been applied using SQLite's user_version pragma.
-///
-/// Note: For users upgrading from tauri-plugin-sql, migrate_from_tauri_plugin_sql()
-/// converts the old _sqlx_migrations table tracking to the user_version pragma,
-/// ensuring migrations don't re-run on existing databases.
-static MIGRATIONS: &[M] = &[
-    M::up(
-        "CREATE TABLE IF NOT EXISTS transcription_history (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            file_name TEXT NOT NULL,
-            timestamp INTEGER NOT NULL,
-            saved BOOLEAN NOT NULL DEFAULT 0,
-            title TEXT NOT NULL,
-            transcription_text TEXT NOT NULL
-        );",
-    ),
-    M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
-    M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
-    // Meeting mode tables
-    M::up(
-        "CREATE TABLE IF NOT EXISTS meeting_history (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            meeting_id TEXT NOT NULL UNIQUE,
-            started_at INTEGER NOT NULL,
-            ended_at INTEGER NOT NULL,
-            duration_seconds INTEGER NOT NULL,
-            full_transcript TEXT NOT NULL,
-            summary TEXT,
-            action_items TEXT,
-            chunk_count INTEGER NOT NULL,
-            saved BOOLEAN NOT NULL DEFAULT 0
-        );",
-    ),
-    M::up(
-        "CREATE TABLE IF NOT EXISTS meeting_chunks (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            meeting_id TEXT NOT NULL,
-            chunk_index INTEGER NOT NULL,
-            start_time_ms INTEGER NOT NULL,
-            end_time_ms INTEGER NOT NULL,
-            transcription TEXT NOT NULL,
-            audio_file_name TEXT,
-            FOREIGN KEY (meeting_id) REFERENCES meeting_history(meeting_id)
-        );",
-    ),
-    M::up(
-        "CREATE TABLE IF NOT EXISTS meeting_recovery (
-            id INTEGER PRIMARY KEY CHECK (id = 1),
-            session_data TEXT NOT NULL,
-            updated_at INTEGER NOT NULL
-        );",
-    ),
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::audio_toolkit::{
+    compute_peaks, decode_audio_bytes, downsample_peaks, encode_flac, encode_wav, read_audio_file,
+};
+use crate::context::ActiveAppInfo;
+use crate::encryption::{self, HistoryKey, ENCRYPTED_EXTENSION};
+use crate::events::{HistoryUpdated, MeetingHistoryUpdated};
+use crate::managers::history_migrations;
+use crate::managers::history_sync::{self, SyncChange};
+use crate::managers::meeting::MeetingChunk;
+use crate::managers::meeting_journal::MeetingJournal;
+use crate::settings::{
+    get_settings, update_settings, RecordingFormat, RetentionAction, RetentionRule,
+};
+
+/// Columns read into a `HistoryEntry`, see `HistoryManager::entry_from_row`
+const ENTRY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, raw_text, post_processed_text, post_process_prompt, app_name, app_domain, duration_ms";
+
+/// How often the retention rules are re-applied
+const RETENTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Resolution of the stored waveform peaks
+const PEAKS_PER_SECOND: usize = 50;
+
+/// Tables and columns that reference files in the recordings directory
+const RECORDING_COLUMNS: &[(&str, &str)] = &[
+    ("transcription_history", "file_name"),
+    ("meeting_chunks", "audio_file_name"),
 ];
 
+/// Largest page the history view can ask for
+const MAX_PAGE_SIZE: u32 = 500;
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct HistoryEntry {
     pub id: i64,
@@ -74,15 +51,128 @@ pub struct HistoryEntry {
     pub timestamp: i64,
     pub saved: bool,
     pub title: String,
+    /// Text after custom words, filtering and formatting
+    pub transcription_text: String,
+    /// What the engine produced, before any local processing. Missing for
+    /// entries saved before it was recorded.
+    pub raw_text: Option<String>,
+    /// LLM or script conversion output, when it changed the text
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    /// App that was in front when the dictation finished
+    pub app_name: Option<String>,
+    /// Domain of the browser tab the dictation went to, when known
+    pub app_domain: Option<String>,
+    /// Length of the recording
+    pub duration_ms: Option<i64>,
+}
+
+/// Texts an entry had before it was re-processed
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct HistoryEntryVersion {
+    pub id: i64,
+    /// When these texts were replaced
+    pub replaced_at: i64,
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
 }
 
+/// Order of history pages. Ties are broken by id so cursors stay stable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySort {
+    #[default]
+    Newest,
+    Oldest,
+    Longest,
+    Shortest,
+}
+
+impl HistorySort {
+    /// Expression the page is ordered by, matching an index
+    fn key_expression(self) -> &'static str {
+        match self {
+            HistorySort::Newest | HistorySort::Oldest => "timestamp",
+            HistorySort::Longest | HistorySort::Shortest => "COALESCE(duration_ms, 0)",
+        }
+    }
+
+    fn descending(self) -> bool {
+        matches!(self, HistorySort::Newest | HistorySort::Longest)
+    }
+
+    fn key_of(self, entry: &HistoryEntry) -> i64 {
+        match self {
+            HistorySort::Newest | HistorySort::Oldest => entry.timestamp,
+            HistorySort::Longest | HistorySort::Shortest => entry.duration_ms.unwrap_or(0),
+        }
+    }
+}
+
+/// Which entries a history page or count covers
+#[derive(Clone, Debug, Default, Deserialize, Type)]
+pub struct HistoryQuery {
+    #[serde(default)]
+    pub sort: HistorySort,
+    /// `next_cursor` of the previous page, `None` for the first page
+    #[serde(default)]
+    pub cursor: Option<String>,
+    pub limit: u32,
+    #[serde(default)]
+    pub saved_only: bool,
+    /// Only entries whose text contains this, ignoring case
+    #[serde(default)]
+    pub search: Option<String>,
+}
+
+impl HistoryQuery {
+    fn search_text(&self) -> Option<&str> {
+        self.search
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Cursor for the following page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Shared by pages and counts so both agree on what matches
+const HISTORY_FILTER: &str = "(?1 = 0 OR saved = 1)
+    AND (?2 IS NULL
+        OR instr(lower(transcription_text), lower(?2)) > 0
+        OR instr(lower(COALESCE(post_processed_text, '')), lower(?2)) > 0)";
+
+/// Peaks of a recording for drawing its scrubber
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct Waveform {
+    pub duration_ms: i64,
+    /// Loudest sample per bar, from 0.0 to 1.0
+    pub peaks: Vec<f32>,
+}
+
+/// What a pass of the retention rules removed
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct RetentionReport {
+    pub entries_deleted: usize,
+    pub audio_purged: usize,
+}
+
 pub struct HistoryManager {
     app_handle: AppHandle,
     recordings_dir: PathBuf,
     db_path: PathBuf,
+    migration_running: AtomicBool,
+    /// Set while history encryption is enabled
+    key: RwLock<Option<HistoryKey>>,
+    /// Why history is unavailable this session, when it is encrypted and the
+    /// key could not be loaded. Nothing is read or written while locked.
+    locked: Option<String>,
 }
 
 impl HistoryManager {
@@ -91,6 +181,7 @@ impl HistoryManager {
         let app_data_dir = app_handle.path().app_data_dir()?;
         let recordings_dir = app_data_dir.join("recordings");
         let db_path = app_data_dir.join("history.db");
+        let legacy_playback_dir = app_handle.path().app_cache_dir()?.join("playback");
 
         // Ensure recordings directory exists
         if !recordings_dir.exists() {
@@ -98,14 +189,46 @@ impl HistoryManager {
             debug!("Created recordings directory: {:?}", recordings_dir);
         }
 
+        // Earlier versions decrypted recordings into the cache for playback
+        if legacy_playback_dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&legacy_playback_dir) {
+                warn!("Failed to clear decrypted playback files: {}", e);
+            }
+        }
+
+        let (key, locked) = if get_settings(app_handle).history_encryption_enabled {
+            match encryption::load_key() {
+                Ok(Some(key)) => (Some(key), None),
+                Ok(None) => (
+                    None,
+                    Some("History is encrypted but its key is missing from the keychain".into()),
+                ),
+                Err(e) => (
+                    None,
+                    Some(format!(
+                        "History is encrypted but its key could not be read from the keychain: {}",
+                        e
+                    )),
+                ),
+            }
+        } else {
+            (None, None)
+        };
+
         let manager = Self {
             app_handle: app_handle.clone(),
             recordings_dir,
             db_path,
+            migration_running: AtomicBool::new(false),
+            key: RwLock::new(key),
+            locked,
         };
 
         // Initialize database and run migrations synchronously
-        manager.init_database()?;
+        match &manager.locked {
+            Some(reason) => error!("{}, history stays locked for this session", reason),
+            None => manager.init_database()?,
+        }
 
         Ok(manager)
     }
@@ -113,118 +236,242 @@ impl HistoryManager {
     fn init_database(&self) -> Result<()> {
         info!("Initializing database at {:?}", self.db_path);
 
-        let mut conn = Connection::open(&self.db_path)?;
-
-        // Handle migration from tauri-plugin-sql to rusqlite_migration
-        // tauri-plugin-sql used _sqlx_migrations table, rusqlite_migration uses user_version pragma
-        self.migrate_from_tauri_plugin_sql(&conn)?;
-
-        // Create migrations object and run to latest version
-        let migrations = Migrations::new(MIGRATIONS.to_vec());
+        let key = self.key.read().unwrap();
+        history_migrations::upgrade(&self.db_path, |path| {
+            Self::open_connection(path, key.as_ref())
+        })
+    }
 
-        // Validate migrations in debug builds
-        #[cfg(debug_assertions)]
-        migrations.validate().expect("Invalid migrations");
+    /// Why history could not be unlocked this session, if it couldn't
+    pub fn lock_reason(&self) -> Option<&str> {
+        self.locked.as_deref()
+    }
 
-        // Get current version before migration
-        let version_before: i32 =
-            conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
-        debug!("Database version before migration: {}", version_before);
+    fn ensure_unlocked(&self) -> Result<()> {
+        match &self.locked {
+            Some(reason) => Err(anyhow!("{}", reason)),
+            None => Ok(()),
+        }
+    }
 
-        // Apply any pending migrations
-        migrations.to_latest(&mut conn)?;
+    pub(crate) fn get_connection(&self) -> Result<Connection> {
+        self.ensure_unlocked()?;
+        let key = self.key.read().unwrap();
+        Self::open_connection(&self.db_path, key.as_ref())
+    }
 
-        // Get version after migration
-        let version_after: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    /// Open the database, unlocking it with SQLCipher when a key is given
+    fn open_connection(path: &Path, key: Option<&HistoryKey>) -> Result<Connection> {
+        let conn = Connection::open(path)?;
+        if let Some(key) = key {
+            conn.execute_batch(&format!("PRAGMA key = \"{}\";", key.sqlcipher_pragma()))?;
+            // SQLCipher only checks the key on first access
+            conn.query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map_err(|e| anyhow!("Failed to unlock history database: {}", e))?;
+        }
+        Ok(conn)
+    }
 
-        if version_after > version_before {
-            info!(
-                "Database migrated from version {} to {}",
-                version_before, version_after
-            );
-        } else {
-            debug!("Database already at latest version {}", version_after);
+    /// Turn encryption at rest on or off, converting the database and every
+    /// recording. The key is created in the OS keychain when encryption is
+    /// first enabled and removed once everything has been decrypted again.
+    pub fn set_encryption_enabled(&self, enabled: bool) -> Result<()> {
+        self.ensure_unlocked()?;
+        let mut current = self.key.write().unwrap();
+
+        if enabled && current.is_none() {
+            let key = encryption::load_or_create_key()?;
+            self.rekey_database(None, Some(&key))?;
+            *current = Some(key.clone());
+            update_settings(&self.app_handle, |s| s.history_encryption_enabled = true);
+
+            let conn = Self::open_connection(&self.db_path, Some(&key))?;
+            let converted = self.convert_recordings(&conn, &key, true)?;
+            info!("History encrypted ({} recordings)", converted);
+        } else if !enabled {
+            let Some(key) = current.clone() else {
+                return Ok(());
+            };
+
+            let conn = Self::open_connection(&self.db_path, Some(&key))?;
+            let converted = self.convert_recordings(&conn, &key, false)?;
+            drop(conn);
+            self.rekey_database(Some(&key), None)?;
+            *current = None;
+            update_settings(&self.app_handle, |s| s.history_encryption_enabled = false);
+            info!("History decrypted ({} recordings)", converted);
+
+            if let Err(e) = encryption::delete_key() {
+                warn!("{}", e);
+            }
         }
 
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
         Ok(())
     }
 
-    /// Migrate from tauri-plugin-sql's migration tracking to rusqlite_migration's.
-    /// tauri-plugin-sql used a _sqlx_migrations table, while rusqlite_migration uses
-    /// SQLite's user_version pragma. This function checks if the old system was in use
-    /// and sets the user_version accordingly so migrations don't re-run.
-    fn migrate_from_tauri_plugin_sql(&self, conn: &Connection) -> Result<()> {
-        // Check if the old _sqlx_migrations table exists
-        let has_sqlx_migrations: bool = conn
-            .query_row(
-                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='_sqlx_migrations'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(false);
-
-        if !has_sqlx_migrations {
-            return Ok(());
+    /// Rewrite the database under a new key (`None` for plaintext) and swap it
+    /// into place. The original file is only replaced once the copy is complete.
+    fn rekey_database(&self, from: Option<&HistoryKey>, to: Option<&HistoryKey>) -> Result<()> {
+        let rekeyed_path = self.db_path.with_extension("db.rekey");
+        if rekeyed_path.exists() {
+            fs::remove_file(&rekeyed_path)?;
         }
 
-        // Check current user_version
-        let current_version: i32 =
-            conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        {
+            let conn = Self::open_connection(&self.db_path, from)?;
+            let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+            let target_key = to.map(HistoryKey::sqlcipher_pragma).unwrap_or_default();
 
-        if current_version > 0 {
-            // Already migrated to rusqlite_migration system
-            return Ok(());
+            conn.execute(
+                "ATTACH DATABASE ?1 AS rekeyed KEY ?2",
+                params![rekeyed_path.to_string_lossy(), target_key],
+            )?;
+            conn.query_row("SELECT sqlcipher_export('rekeyed')", [], |_| Ok(()))?;
+            conn.execute_batch(&format!(
+                "PRAGMA rekeyed.user_version = {}; DETACH DATABASE rekeyed;",
+                version
+            ))?;
         }
 
-        // Get the highest version from the old migrations table
-        let old_version: i32 = conn
-            .query_row(
-                "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
+        // Make sure the copy opens with the new key before replacing the original
+        Self::open_connection(&rekeyed_path, to)?;
+        fs::rename(&rekeyed_path, &self.db_path)?;
+        Ok(())
+    }
 
-        if old_version > 0 {
-            info!(
-                "Migrating from tauri-plugin-sql (version {}) to rusqlite_migration",
-                old_version
-            );
+    /// Encrypt or decrypt every recording referenced by history or a meeting
+    /// that is not already in the wanted state. Returns the number of
+    /// converted files.
+    fn convert_recordings(
+        &self,
+        conn: &Connection,
+        key: &HistoryKey,
+        encrypt: bool,
+    ) -> Result<usize> {
+        let mut converted = 0;
+        for (table, column) in RECORDING_COLUMNS {
+            let entries: Vec<(i64, String)> = {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT id, {column} FROM {table} WHERE {column} IS NOT NULL"
+                ))?;
+                let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                rows.collect::<rusqlite::Result<_>>()?
+            };
+            converted +=
+                self.convert_recording_files(conn, key, encrypt, table, column, entries)?;
+        }
 
-            // Set user_version to match the old migration state
-            conn.pragma_update(None, "user_version", old_version)?;
+        Ok(converted)
+    }
 
-            // Optionally drop the old migrations table (keeping it doesn't hurt)
-            // conn.execute("DROP TABLE IF EXISTS _sqlx_migrations", [])?;
+    fn convert_recording_files(
+        &self,
+        conn: &Connection,
+        key: &HistoryKey,
+        encrypt: bool,
+        table: &str,
+        column: &str,
+        entries: Vec<(i64, String)>,
+    ) -> Result<usize> {
+        let mut converted = 0;
+        for (id, file_name) in entries {
+            let is_encrypted = is_encrypted_recording(&file_name);
+            if is_encrypted == encrypt {
+                continue;
+            }
+            let path = self.get_audio_file_path(&file_name);
+            if !path.exists() {
+                continue;
+            }
 
-            info!(
-                "Migration tracking converted: user_version set to {}",
-                old_version
-            );
+            let (new_name, contents) = if encrypt {
+                let contents = key.encrypt(&fs::read(&path)?)?;
+                (format!("{}.{}", file_name, ENCRYPTED_EXTENSION), contents)
+            } else {
+                let contents = match key.decrypt(&fs::read(&path)?) {
+                    Ok(contents) => contents,
+                    Err(e) => {
+                        warn!("Skipping recording {}: {}", file_name, e);
+                        continue;
+                    }
+                };
+                (plain_recording_name(&file_name).to_string(), contents)
+            };
+
+            fs::write(self.get_audio_file_path(&new_name), contents)?;
+            conn.execute(
+                &format!("UPDATE {table} SET {column} = ?1 WHERE id = ?2"),
+                params![new_name, id],
+            )?;
+            if let Err(e) = fs::remove_file(&path) {
+                warn!("Failed to remove converted recording {}: {}", file_name, e);
+            }
+            converted += 1;
         }
 
-        Ok(())
+        Ok(converted)
+    }
+
+    /// Save 16 kHz samples to the recordings directory as `{stem}.{ext}` in
+    /// the configured format, sealed if history is encrypted. Returns the
+    /// file name.
+    fn write_recording(&self, stem: &str, samples: &[f32]) -> Result<String> {
+        // Without the key this would write plaintext into an encrypted archive
+        self.ensure_unlocked()?;
+        let format = get_settings(&self.app_handle).recording_format;
+        let (extension, audio) = match format {
+            RecordingFormat::Wav => ("wav", encode_wav(samples)?),
+            RecordingFormat::Flac => ("flac", encode_flac(samples, 16000)),
+        };
+
+        let key = self.key.read().unwrap().clone();
+        let (file_name, contents) = match key {
+            Some(key) => (
+                format!("{}.{}.{}", stem, extension, ENCRYPTED_EXTENSION),
+                key.encrypt(&audio)?,
+            ),
+            None => (format!("{}.{}", stem, extension), audio),
+        };
+        fs::write(self.recordings_dir.join(&file_name), contents)?;
+        debug!("Saved recording {}", file_name);
+        Ok(file_name)
     }
 
-    fn get_connection(&self) -> Result<Connection> {
-        Ok(Connection::open(&self.db_path)?)
+    /// Save the audio of one meeting chunk. Returns the file name to store
+    /// with the chunk.
+    pub fn save_meeting_chunk_audio(
+        &self,
+        meeting_id: &str,
+        chunk_index: u32,
+        samples: &[f32],
+    ) -> Result<String> {
+        self.write_recording(
+            &format!("meeting-{}-{:04}", meeting_id, chunk_index),
+            samples,
+        )
     }
 
-    /// Save a transcription to history (both database and WAV file)
+    /// Save a transcription to history (both database and audio file)
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
         transcription_text: String,
+        raw_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        active_app: Option<ActiveAppInfo>,
     ) -> Result<()> {
         let timestamp = Utc::now().timestamp();
-        let file_name = format!("paperflow-{}.wav", timestamp);
+        let duration_ms = audio_samples.len() as i64 * 1000 / 16000;
+        let peaks = compute_peaks(&audio_samples, 16000 / PEAKS_PER_SECOND);
         let title = self.format_timestamp_title(timestamp);
-
-        // Save WAV file
-        let file_path = self.recordings_dir.join(&file_name);
-        save_wav_file(file_path, &audio_samples).await?;
+        let file_name =
+            self.write_recording(&format!("paperflow-{}", timestamp), &audio_samples)?;
 
         // Save to database
         self.save_to_database(
@@ -232,38 +479,100 @@ impl HistoryManager {
             timestamp,
             title,
             transcription_text,
+            raw_text,
             post_processed_text,
             post_process_prompt,
+            active_app.as_ref().map(|app| app.name.clone()),
+            active_app.and_then(|app| app.domain),
+            duration_ms,
+            peaks,
         )?;
 
         // Clean up old entries
         self.cleanup_old_entries()?;
 
         // Emit history updated event
-        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
             error!("Failed to emit history-updated event: {}", e);
         }
 
         Ok(())
     }
 
+    /// Add a transcript from another machine or tool, which has no recording.
+    /// Returns the id of the new entry.
+    pub fn import_transcript(
+        &self,
+        title: Option<String>,
+        text: String,
+        timestamp: i64,
+        duration_ms: Option<u64>,
+    ) -> Result<i64> {
+        let title = title
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| self.format_timestamp_title(timestamp));
+        // The placeholder name never matches a local file
+        let file_name = format!("imported-{}", uuid::Uuid::new_v4().simple());
+        let id = self.save_to_database(
+            file_name,
+            timestamp,
+            title,
+            text.clone(),
+            text,
+            None,
+            None,
+            None,
+            None,
+            duration_ms.unwrap_or_default() as i64,
+            Vec::new(),
+        )?;
+
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(id)
+    }
+
+    /// Insert an entry and record it for sync. Returns its id.
+    #[allow(clippy::too_many_arguments)]
     fn save_to_database(
         &self,
         file_name: String,
         timestamp: i64,
         title: String,
         transcription_text: String,
+        raw_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
-    ) -> Result<()> {
+        app_name: Option<String>,
+        app_domain: Option<String>,
+        duration_ms: i64,
+        waveform_peaks: Vec<u8>,
+    ) -> Result<i64> {
         let conn = self.get_connection()?;
+        let sync_uid = uuid::Uuid::new_v4().simple().to_string();
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, raw_text, post_processed_text, post_process_prompt, sync_uid, app_name, app_domain, duration_ms, waveform_peaks) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![file_name, timestamp, false, title, transcription_text, raw_text, post_processed_text, post_process_prompt, sync_uid, app_name, app_domain, duration_ms, waveform_peaks],
+        )?;
+        let id = conn.last_insert_rowid();
+
+        self.record_sync_change(
+            &conn,
+            &SyncChange::Add {
+                uid: sync_uid,
+                timestamp,
+                saved: false,
+                title,
+                transcription_text,
+                raw_text: Some(raw_text),
+                post_processed_text,
+                post_process_prompt,
+            },
         )?;
 
         debug!("Saved transcription to database");
-        Ok(())
+        Ok(id)
     }
 
     pub fn cleanup_old_entries(&self) -> Result<()> {
@@ -301,13 +610,13 @@ impl HistoryManager {
                 params![id],
             )?;
 
-            // Delete WAV file
+            // Delete audio file
             let file_path = self.recordings_dir.join(file_name);
             if file_path.exists() {
                 if let Err(e) = fs::remove_file(&file_path) {
-                    error!("Failed to delete WAV file {}: {}", file_name, e);
+                    error!("Failed to delete audio file {}: {}", file_name, e);
                 } else {
-                    debug!("Deleted old WAV file: {}", file_name);
+                    debug!("Deleted old audio file: {}", file_name);
                     deleted_count += 1;
                 }
             }
@@ -386,60 +695,273 @@ impl HistoryManager {
         Ok(())
     }
 
-    pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
+    /// Apply the configured retention rules in order, once now and then
+    /// every hour on a background thread
+    pub fn start_retention_task(self: &Arc<Self>) {
+        let manager = self.clone();
+        let spawned = std::thread::Builder::new()
+            .name("history-retention".into())
+            .spawn(move || loop {
+                if let Err(e) = manager.apply_retention_rules() {
+                    error!("Failed to apply retention rules: {}", e);
+                }
+                std::thread::sleep(RETENTION_INTERVAL);
+            });
+
+        if let Err(e) = spawned {
+            error!("Failed to spawn history retention task: {}", e);
+        }
+    }
+
+    /// Run every enabled retention rule against the whole history
+    pub fn apply_retention_rules(&self) -> Result<RetentionReport> {
+        let rules = get_settings(&self.app_handle).retention_rules;
+        let mut report = RetentionReport::default();
+        if rules.iter().all(|rule| !rule.enabled) {
+            return Ok(report);
+        }
+
         let conn = self.get_connection()?;
+        let now = Utc::now().timestamp();
+        let kept = Self::select_kept_entries(&conn, &rules, now)?;
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let entries: Vec<(i64, String)> = Self::select_retention_matches(&conn, rule, now)?
+                .into_iter()
+                .filter(|(id, _)| !kept.contains(id))
+                .collect();
+            match rule.action {
+                RetentionAction::Keep => {}
+                RetentionAction::DeleteEntry => {
+                    self.delete_entries_and_files(&entries)?;
+                    report.entries_deleted += entries.len();
+                }
+                RetentionAction::DeleteAudio => {
+                    for (id, file_name) in &entries {
+                        let file_path = self.recordings_dir.join(file_name);
+                        if !file_path.exists() {
+                            continue;
+                        }
+                        if let Err(e) = fs::remove_file(&file_path) {
+                            error!("Failed to delete audio file {}: {}", file_name, e);
+                            continue;
+                        }
+                        // Like imported entries, point at a name that never matches a file
+                        let placeholder = format!("purged-{}", uuid::Uuid::new_v4().simple());
+                        conn.execute(
+                            "UPDATE transcription_history SET file_name = ?1 WHERE id = ?2",
+                            params![placeholder, id],
+                        )?;
+                        report.audio_purged += 1;
+                    }
+                }
+            }
+        }
+
+        if report.entries_deleted > 0 || report.audio_purged > 0 {
+            info!(
+                "Retention rules deleted {} entries and {} recordings",
+                report.entries_deleted, report.audio_purged
+            );
+            if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
+                error!("Failed to emit history-updated event: {}", e);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Ids of the entries an enabled `Keep` rule protects from the others
+    fn select_kept_entries(
+        conn: &Connection,
+        rules: &[RetentionRule],
+        now: i64,
+    ) -> Result<HashSet<i64>> {
+        let mut kept = HashSet::new();
+        for rule in rules
+            .iter()
+            .filter(|rule| rule.enabled && rule.action == RetentionAction::Keep)
+        {
+            kept.extend(
+                Self::select_retention_matches(conn, rule, now)?
+                    .into_iter()
+                    .map(|(id, _)| id),
+            );
+        }
+        Ok(kept)
+    }
+
+    /// Entries a retention rule applies to at `now`, as (id, file_name)
+    fn select_retention_matches(
+        conn: &Connection,
+        rule: &RetentionRule,
+        now: i64,
+    ) -> Result<Vec<(i64, String)>> {
+        let cutoff = now - i64::from(rule.older_than_days) * 24 * 60 * 60;
+        let text = rule
+            .text_contains
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty());
+
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
+            "SELECT id, file_name FROM transcription_history
+             WHERE timestamp < ?1
+               AND (?2 IS NULL OR saved = ?2)
+               AND (?3 IS NULL OR instr(lower(transcription_text), lower(?3)) > 0)",
         )?;
+        let rows = stmt.query_map(params![cutoff, rule.saved, text], |row| {
+            Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
+        })?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC",
+            ENTRY_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map([], Self::entry_from_row)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+
+        Ok(entries)
+    }
+
+    /// One page of history, sorted and filtered by `query`
+    pub fn get_history_page(&self, query: &HistoryQuery) -> Result<HistoryPage> {
+        let conn = self.get_connection()?;
+        Self::get_history_page_with_conn(&conn, query)
+    }
+
+    fn get_history_page_with_conn(conn: &Connection, query: &HistoryQuery) -> Result<HistoryPage> {
+        let cursor = query
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                cursor
+                    .split_once(':')
+                    .and_then(|(key, id)| Some((key.parse::<i64>().ok()?, id.parse::<i64>().ok()?)))
+                    .ok_or_else(|| anyhow!("Invalid history cursor '{}'", cursor))
             })
-        })?;
+            .transpose()?;
+        let limit = query.limit.clamp(1, MAX_PAGE_SIZE);
+
+        let key = query.sort.key_expression();
+        let (op, direction) = if query.sort.descending() {
+            ("<", "DESC")
+        } else {
+            (">", "ASC")
+        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {columns} FROM transcription_history
+             WHERE {filter}
+               AND (?3 IS NULL OR {key} {op} ?3 OR ({key} = ?3 AND id {op} ?4))
+             ORDER BY {key} {direction}, id {direction}
+             LIMIT ?5",
+            columns = ENTRY_COLUMNS,
+            filter = HISTORY_FILTER,
+        ))?;
+        // One extra row tells whether another page follows
+        let rows = stmt.query_map(
+            params![
+                query.saved_only,
+                query.search_text(),
+                cursor.map(|(key, _)| key),
+                cursor.map(|(_, id)| id),
+                limit + 1
+            ],
+            Self::entry_from_row,
+        )?;
 
         let mut entries = Vec::new();
         for row in rows {
             entries.push(row?);
         }
 
+        let next_cursor = if entries.len() > limit as usize {
+            entries.truncate(limit as usize);
+            entries
+                .last()
+                .map(|entry| format!("{}:{}", query.sort.key_of(entry), entry.id))
+        } else {
+            None
+        };
+
+        Ok(HistoryPage {
+            entries,
+            next_cursor,
+        })
+    }
+
+    /// Number of entries matching the filters of `query`, across all pages
+    pub fn count_history_entries(&self, query: &HistoryQuery) -> Result<i64> {
+        let conn = self.get_connection()?;
+        let count = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM transcription_history WHERE {}",
+                HISTORY_FILTER
+            ),
+            params![query.saved_only, query.search_text()],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    /// Entries saved in `[start, end)`, oldest first
+    pub fn get_entries_between(&self, start: i64, end: i64) -> Result<Vec<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE timestamp >= ?1 AND timestamp < ?2 ORDER BY timestamp",
+            ENTRY_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![start, end], Self::entry_from_row)?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
         Ok(entries)
     }
 
+    fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+        Ok(HistoryEntry {
+            id: row.get("id")?,
+            file_name: row.get("file_name")?,
+            timestamp: row.get("timestamp")?,
+            saved: row.get("saved")?,
+            title: row.get("title")?,
+            transcription_text: row.get("transcription_text")?,
+            raw_text: row.get("raw_text")?,
+            post_processed_text: row.get("post_processed_text")?,
+            post_process_prompt: row.get("post_process_prompt")?,
+            app_name: row.get("app_name")?,
+            app_domain: row.get("app_domain")?,
+            duration_ms: row.get("duration_ms")?,
+        })
+    }
+
     pub fn get_latest_entry(&self) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         Self::get_latest_entry_with_conn(&conn)
     }
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history
-             ORDER BY timestamp DESC
-             LIMIT 1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC LIMIT 1",
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([], Self::entry_from_row).optional()?;
 
         Ok(entry)
     }
@@ -460,42 +982,297 @@ impl HistoryManager {
             "UPDATE transcription_history SET saved = ?1 WHERE id = ?2",
             params![new_saved, id],
         )?;
+        if let Some(uid) = Self::sync_uid(&conn, id)? {
+            self.record_sync_change(
+                &conn,
+                &SyncChange::SetSaved {
+                    uid,
+                    saved: new_saved,
+                },
+            )?;
+        }
 
         debug!("Toggled saved status for entry {}: {}", id, new_saved);
 
         // Emit history updated event
-        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Replace an entry's texts, keeping the current ones as an earlier version
+    pub fn update_entry_text(
+        &self,
+        id: i64,
+        transcription_text: String,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+
+        let archived = tx.execute(
+            "INSERT INTO transcription_versions (entry_id, replaced_at, transcription_text, post_processed_text, post_process_prompt)
+             SELECT id, ?2, transcription_text, post_processed_text, post_process_prompt
+             FROM transcription_history WHERE id = ?1",
+            params![id, Utc::now().timestamp()],
+        )?;
+        if archived == 0 {
+            return Err(anyhow!("History entry {} not found", id));
+        }
+        tx.execute(
+            "UPDATE transcription_history
+             SET transcription_text = ?1, post_processed_text = ?2, post_process_prompt = ?3
+             WHERE id = ?4",
+            params![
+                transcription_text,
+                post_processed_text,
+                post_process_prompt,
+                id
+            ],
+        )?;
+
+        if let Some(uid) = Self::sync_uid(&tx, id)? {
+            self.record_sync_change(
+                &tx,
+                &SyncChange::SetText {
+                    uid,
+                    transcription_text,
+                    post_processed_text,
+                    post_process_prompt,
+                },
+            )?;
+        }
+        tx.commit()?;
+
+        debug!("Stored new version of history entry {}", id);
+
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
             error!("Failed to emit history-updated event: {}", e);
         }
 
         Ok(())
     }
 
+    /// Earlier texts of an entry, newest first
+    pub fn get_entry_versions(&self, id: i64) -> Result<Vec<HistoryEntryVersion>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, replaced_at, transcription_text, post_processed_text, post_process_prompt
+             FROM transcription_versions WHERE entry_id = ?1 ORDER BY replaced_at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map([id], |row| {
+            Ok(HistoryEntryVersion {
+                id: row.get("id")?,
+                replaced_at: row.get("replaced_at")?,
+                transcription_text: row.get("transcription_text")?,
+                post_processed_text: row.get("post_processed_text")?,
+                post_process_prompt: row.get("post_process_prompt")?,
+            })
+        })?;
+
+        let mut versions = Vec::new();
+        for row in rows {
+            versions.push(row?);
+        }
+        Ok(versions)
+    }
+
     pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
         self.recordings_dir.join(file_name)
     }
 
+    /// Contents of a recording, decrypted in memory when history encryption
+    /// sealed it. Plaintext audio of an encrypted archive never goes to disk.
+    pub fn read_recording(&self, file_name: &str) -> Result<Vec<u8>> {
+        let data = fs::read(self.get_audio_file_path(file_name))?;
+        if !is_encrypted_recording(file_name) {
+            return Ok(data);
+        }
+
+        let key = self
+            .key
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| anyhow!("History encryption key is not available"))?;
+        key.decrypt(&data)
+    }
+
+    /// Decode a recording into mono samples at its own sample rate
+    pub fn decode_recording(&self, file_name: &str) -> Result<(Vec<f32>, u32)> {
+        let extension = Path::new(plain_recording_name(file_name))
+            .extension()
+            .and_then(|ext| ext.to_str());
+        decode_audio_bytes(self.read_recording(file_name)?, extension)
+    }
+
+    /// A recording as plain WAV, which every webview can play and seek
+    /// precisely. FLAC recordings are decoded in memory.
+    pub fn read_recording_as_wav(&self, file_name: &str) -> Result<Vec<u8>> {
+        let is_wav = Path::new(plain_recording_name(file_name))
+            .extension()
+            .and_then(|ext| ext.to_str())
+            == Some("wav");
+        if is_wav {
+            return self.read_recording(file_name);
+        }
+
+        let (samples, sample_rate) = self.decode_recording(file_name)?;
+        if sample_rate != 16000 {
+            return Err(anyhow!(
+                "Unexpected sample rate {} in {}",
+                sample_rate,
+                file_name
+            ));
+        }
+        encode_wav(&samples)
+    }
+
+    /// Waveform of an entry merged into `buckets` bars. Entries saved before
+    /// peaks were stored have them computed from the recording once.
+    pub fn get_waveform(&self, id: i64, buckets: usize) -> Result<Waveform> {
+        let conn = self.get_connection()?;
+        let (file_name, duration_ms, stored): (String, Option<i64>, Option<Vec<u8>>) = conn
+            .query_row(
+                "SELECT file_name, duration_ms, waveform_peaks FROM transcription_history WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+            .ok_or_else(|| anyhow!("History entry {} not found", id))?;
+
+        let (peaks, duration_ms) = match (stored, duration_ms) {
+            (Some(peaks), Some(duration_ms)) => (peaks, duration_ms),
+            _ => {
+                let (samples, sample_rate) = self.decode_recording(&file_name)?;
+                let window = (sample_rate as usize / PEAKS_PER_SECOND).max(1);
+                let peaks = compute_peaks(&samples, window);
+                let duration_ms = samples.len() as i64 * 1000 / sample_rate.max(1) as i64;
+                conn.execute(
+                    "UPDATE transcription_history SET waveform_peaks = ?1, duration_ms = ?2 WHERE id = ?3",
+                    params![peaks, duration_ms, id],
+                )?;
+                (peaks, duration_ms)
+            }
+        };
+
+        Ok(Waveform {
+            duration_ms,
+            peaks: downsample_peaks(&peaks, buckets),
+        })
+    }
+
+    /// Bring existing recordings in line with the current settings on a
+    /// background thread: WAV files are converted to FLAC if that is the
+    /// configured format, and plaintext files are encrypted if history
+    /// encryption is on (e.g. after an interrupted switch).
+    pub fn start_recording_migration(self: &Arc<Self>) {
+        let to_flac = get_settings(&self.app_handle).recording_format == RecordingFormat::Flac;
+        let encrypt = self.key.read().unwrap().is_some();
+        if (!to_flac && !encrypt) || self.locked.is_some() {
+            return;
+        }
+        if self.migration_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let manager = self.clone();
+        std::thread::spawn(move || {
+            // Holding the key for the whole pass keeps encryption from being
+            // toggled underneath it
+            let key = manager.key.read().unwrap();
+            let mut changed = false;
+            match Self::open_connection(&manager.db_path, key.as_ref()) {
+                Ok(conn) => {
+                    if to_flac {
+                        match manager.migrate_recordings_to_flac(&conn) {
+                            Ok(0) => {}
+                            Ok(count) => {
+                                info!("Converted {} WAV recordings to FLAC", count);
+                                changed = true;
+                            }
+                            Err(e) => error!("Failed to convert recordings to FLAC: {}", e),
+                        }
+                    }
+                    if let Some(key) = key.as_ref() {
+                        match manager.convert_recordings(&conn, key, true) {
+                            Ok(0) => {}
+                            Ok(count) => {
+                                info!("Encrypted {} pending recordings", count);
+                                changed = true;
+                            }
+                            Err(e) => error!("Failed to encrypt recordings: {}", e),
+                        }
+                    }
+                }
+                Err(e) => error!("Failed to open history database for migration: {}", e),
+            }
+            drop(key);
+
+            if changed {
+                if let Err(e) = HistoryUpdated.emit(&manager.app_handle) {
+                    error!("Failed to emit history-updated event: {}", e);
+                }
+            }
+            manager.migration_running.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Re-encode every WAV recording referenced by history as FLAC and point
+    /// the entry at the new file. Returns the number of converted recordings.
+    fn migrate_recordings_to_flac(&self, conn: &Connection) -> Result<usize> {
+        let entries: Vec<(i64, String)> = {
+            let mut stmt = conn.prepare(
+                "SELECT id, file_name FROM transcription_history WHERE file_name LIKE '%.wav'",
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let mut converted = 0;
+        for (id, wav_name) in entries {
+            let wav_path = self.get_audio_file_path(&wav_name);
+            if !wav_path.exists() {
+                continue;
+            }
+
+            let samples = match read_audio_file(&wav_path) {
+                Ok(samples) => samples,
+                Err(e) => {
+                    warn!("Skipping unreadable recording {}: {}", wav_name, e);
+                    continue;
+                }
+            };
+
+            let flac_name = format!("{}.flac", wav_name.trim_end_matches(".wav"));
+            let flac_path = self.get_audio_file_path(&flac_name);
+            fs::write(&flac_path, encode_flac(&samples, 16000))?;
+
+            // Only drop the WAV once the entry points at the new file
+            conn.execute(
+                "UPDATE transcription_history SET file_name = ?1 WHERE id = ?2",
+                params![flac_name, id],
+            )?;
+            if let Err(e) = fs::remove_file(&wav_path) {
+                warn!("Failed to remove converted recording {}: {}", wav_name, e);
+            }
+            converted += 1;
+        }
+
+        Ok(converted)
+    }
+
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            ENTRY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], Self::entry_from_row).optional()?;
 
         Ok(entry)
     }
@@ -515,6 +1292,11 @@ impl HistoryManager {
             }
         }
 
+        // Other devices drop the entry too; retention cleanup stays local
+        if let Some(uid) = Self::sync_uid(&conn, id)? {
+            self.record_sync_change(&conn, &SyncChange::Delete { uid })?;
+        }
+
         // Delete from database
         conn.execute(
             "DELETE FROM transcription_history WHERE id = ?1",
@@ -524,13 +1306,32 @@ impl HistoryManager {
         debug!("Deleted history entry with id: {}", id);
 
         // Emit history updated event
-        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+        if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
             error!("Failed to emit history-updated event: {}", e);
         }
 
         Ok(())
     }
 
+    fn sync_uid(conn: &Connection, id: i64) -> Result<Option<String>> {
+        Ok(conn
+            .query_row(
+                "SELECT sync_uid FROM transcription_history WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten())
+    }
+
+    /// Queue a change for the sync folder, if history sync is enabled
+    fn record_sync_change(&self, conn: &Connection, change: &SyncChange) -> Result<()> {
+        if get_settings(&self.app_handle).history_sync_enabled {
+            history_sync::queue_change(conn, change)?;
+        }
+        Ok(())
+    }
+
     fn format_timestamp_title(&self, timestamp: i64) -> String {
         if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
             // Convert UTC to local timezone
@@ -574,15 +1375,57 @@ impl HistoryManager {
         Ok(result)
     }
 
-    /// Clear meeting recovery data
+    /// Clear meeting recovery data, including the audio journal
     pub fn clear_meeting_recovery(&self) -> Result<()> {
         let conn = self.get_connection()?;
         conn.execute("DELETE FROM meeting_recovery WHERE id = 1", [])?;
+        let journal_dir = self.meeting_journal_dir();
+        if journal_dir.exists() {
+            fs::remove_dir_all(&journal_dir)?;
+        }
         debug!("Cleared meeting recovery data");
         Ok(())
     }
 
+    /// Journals of the meeting in progress. Only one meeting records at a
+    /// time, so clearing recovery data removes the whole directory.
+    fn meeting_journal_dir(&self) -> PathBuf {
+        self.recordings_dir.join("journal")
+    }
+
+    fn meeting_journal_path(&self, meeting_id: &str) -> PathBuf {
+        self.meeting_journal_dir()
+            .join(format!("meeting-{}.pcm", meeting_id))
+    }
+
+    /// Open the audio journal of a meeting, continuing it with `append`
+    pub fn open_meeting_journal(&self, meeting_id: &str, append: bool) -> Result<MeetingJournal> {
+        self.ensure_unlocked()?;
+        let key = self.key.read().unwrap().clone();
+        MeetingJournal::open(&self.meeting_journal_path(meeting_id), key, append)
+    }
+
+    /// Key to seal other files holding transcripts with, while history
+    /// encryption is enabled. Fails while history is locked, so callers
+    /// don't fall back to plaintext.
+    pub fn encryption_key(&self) -> Result<Option<HistoryKey>> {
+        self.ensure_unlocked()?;
+        Ok(self.key.read().unwrap().clone())
+    }
+
+    /// Audio journaled for a meeting; empty if there is no journal
+    pub fn read_meeting_journal(&self, meeting_id: &str) -> Result<Vec<f32>> {
+        let path = self.meeting_journal_path(meeting_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        self.ensure_unlocked()?;
+        let key = self.key.read().unwrap();
+        MeetingJournal::read(&path, key.as_ref())
+    }
+
     /// Save a completed meeting to history
+    #[allow(clippy::too_many_arguments)]
     pub fn save_meeting(
         &self,
         meeting_id: &str,
@@ -592,13 +1435,14 @@ impl HistoryManager {
         full_transcript: &str,
         summary: Option<&str>,
         action_items: Option<&str>, // JSON array
+        topics: Option<&str>,       // JSON array
         chunk_count: u32,
     ) -> Result<()> {
         let conn = self.get_connection()?;
 
         conn.execute(
-            "INSERT INTO meeting_history (meeting_id, started_at, ended_at, duration_seconds, full_transcript, summary, action_items, chunk_count, saved)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0)",
+            "INSERT INTO meeting_history (meeting_id, started_at, ended_at, duration_seconds, full_transcript, summary, action_items, topics, chunk_count, saved)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0)",
             params![
                 meeting_id,
                 started_at,
@@ -607,6 +1451,7 @@ impl HistoryManager {
                 full_transcript,
                 summary,
                 action_items,
+                topics,
                 chunk_count
             ],
         )?;
@@ -614,7 +1459,7 @@ impl HistoryManager {
         debug!("Saved meeting {} to history", meeting_id);
 
         // Emit meeting history updated event
-        if let Err(e) = self.app_handle.emit("meeting-history-updated", ()) {
+        if let Err(e) = MeetingHistoryUpdated.emit(&self.app_handle) {
             error!("Failed to emit meeting-history-updated event: {}", e);
         }
 
@@ -622,6 +1467,7 @@ impl HistoryManager {
     }
 
     /// Save a meeting chunk
+    #[allow(clippy::too_many_arguments)]
     pub fn save_meeting_chunk(
         &self,
         meeting_id: &str,
@@ -630,25 +1476,112 @@ impl HistoryManager {
         end_time_ms: u64,
         transcription: &str,
         audio_file_name: Option<&str>,
+        speaker: Option<&str>,
     ) -> Result<()> {
         let conn = self.get_connection()?;
 
         conn.execute(
-            "INSERT INTO meeting_chunks (meeting_id, chunk_index, start_time_ms, end_time_ms, transcription, audio_file_name)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO meeting_chunks (meeting_id, chunk_index, start_time_ms, end_time_ms, transcription, audio_file_name, speaker)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 meeting_id,
                 chunk_index,
                 start_time_ms as i64,
                 end_time_ms as i64,
                 transcription,
-                audio_file_name
+                audio_file_name,
+                speaker
             ],
         )?;
 
         Ok(())
     }
 
+    /// Chunks of a saved meeting, in recording order
+    pub fn get_meeting_chunks(&self, meeting_id: &str) -> Result<Vec<MeetingChunk>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT chunk_index, start_time_ms, end_time_ms, transcription, audio_file_name, speaker
+             FROM meeting_chunks
+             WHERE meeting_id = ?1
+             ORDER BY chunk_index",
+        )?;
+
+        let rows = stmt.query_map(params![meeting_id], |row| {
+            Ok(MeetingChunk {
+                chunk_id: row.get("chunk_index")?,
+                start_time_ms: row.get::<_, i64>("start_time_ms")? as u64,
+                end_time_ms: row.get::<_, i64>("end_time_ms")? as u64,
+                audio_path: row.get("audio_file_name")?,
+                transcription: row.get("transcription")?,
+                speaker: row.get("speaker")?,
+            })
+        })?;
+
+        let mut chunks = Vec::new();
+        for row in rows {
+            chunks.push(row?);
+        }
+        Ok(chunks)
+    }
+
+    /// Names given to a meeting's speakers, keyed by label ("Speaker 1")
+    pub fn get_meeting_speaker_names(&self, meeting_id: &str) -> Result<HashMap<String, String>> {
+        let conn = self.get_connection()?;
+        let mut stmt =
+            conn.prepare("SELECT label, name FROM meeting_speakers WHERE meeting_id = ?1")?;
+        let rows = stmt.query_map(params![meeting_id], |row| {
+            Ok((
+                row.get::<_, String>("label")?,
+                row.get::<_, String>("name")?,
+            ))
+        })?;
+
+        let mut names = HashMap::new();
+        for row in rows {
+            let (label, name) = row?;
+            names.insert(label, name);
+        }
+        Ok(names)
+    }
+
+    /// Store a speaker's name (or drop it, for `None`) together with the
+    /// meeting texts rewritten to use it
+    pub fn save_meeting_speaker_name(
+        &self,
+        meeting_id: &str,
+        label: &str,
+        name: Option<&str>,
+        full_transcript: &str,
+        summary: Option<&str>,
+        action_items: Option<&str>, // JSON array
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+
+        match name {
+            Some(name) => tx.execute(
+                "INSERT OR REPLACE INTO meeting_speakers (meeting_id, label, name) VALUES (?1, ?2, ?3)",
+                params![meeting_id, label, name],
+            )?,
+            None => tx.execute(
+                "DELETE FROM meeting_speakers WHERE meeting_id = ?1 AND label = ?2",
+                params![meeting_id, label],
+            )?,
+        };
+        tx.execute(
+            "UPDATE meeting_history SET full_transcript = ?1, summary = ?2, action_items = ?3 WHERE meeting_id = ?4",
+            params![full_transcript, summary, action_items, meeting_id],
+        )?;
+        tx.commit()?;
+
+        if let Err(e) = MeetingHistoryUpdated.emit(&self.app_handle) {
+            error!("Failed to emit meeting-history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
     /// Get meeting history entries
     pub fn get_meeting_entries(
         &self,
@@ -656,7 +1589,7 @@ impl HistoryManager {
         let conn = self.get_connection()?;
 
         let mut stmt = conn.prepare(
-            "SELECT id, meeting_id, started_at, ended_at, duration_seconds, full_transcript, summary, action_items, chunk_count, saved
+            "SELECT id, meeting_id, started_at, ended_at, duration_seconds, full_transcript, summary, action_items, topics, chunk_count, saved
              FROM meeting_history
              ORDER BY started_at DESC",
         )?;
@@ -665,6 +1598,8 @@ impl HistoryManager {
             let action_items_json: Option<String> = row.get("action_items")?;
             let action_items: Option<Vec<String>> =
                 action_items_json.and_then(|json| serde_json::from_str(&json).ok());
+            let topics_json: Option<String> = row.get("topics")?;
+            let topics = topics_json.and_then(|json| serde_json::from_str(&json).ok());
 
             Ok(crate::commands::meeting::MeetingHistoryEntry {
                 id: row.get("id")?,
@@ -675,6 +1610,7 @@ impl HistoryManager {
                 full_transcript: row.get("full_transcript")?,
                 summary: row.get("summary")?,
                 action_items,
+                topics,
                 chunk_count: row.get("chunk_count")?,
                 saved: row.get("saved")?,
             })
@@ -692,11 +1628,32 @@ impl HistoryManager {
     pub fn delete_meeting(&self, meeting_id: &str) -> Result<()> {
         let conn = self.get_connection()?;
 
-        // Delete associated chunks first
+        // Delete the chunks' audio, then the chunks and speaker names
+        let audio_files: Vec<String> = {
+            let mut stmt = conn.prepare(
+                "SELECT audio_file_name FROM meeting_chunks
+                 WHERE meeting_id = ?1 AND audio_file_name IS NOT NULL",
+            )?;
+            let rows = stmt.query_map(params![meeting_id], |row| row.get(0))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+        for file_name in audio_files {
+            let path = self.get_audio_file_path(&file_name);
+            if path.exists() {
+                if let Err(e) = fs::remove_file(&path) {
+                    error!("Failed to delete meeting audio {}: {}", file_name, e);
+                }
+            }
+        }
+
         conn.execute(
             "DELETE FROM meeting_chunks WHERE meeting_id = ?1",
             params![meeting_id],
         )?;
+        conn.execute(
+            "DELETE FROM meeting_speakers WHERE meeting_id = ?1",
+            params![meeting_id],
+        )?;
 
         // Delete the meeting entry
         let rows_affected = conn.execute(
@@ -711,7 +1668,7 @@ impl HistoryManager {
         debug!("Deleted meeting {} from history", meeting_id);
 
         // Emit meeting history updated event
-        if let Err(e) = self.app_handle.emit("meeting-history-updated", ()) {
+        if let Err(e) = MeetingHistoryUpdated.emit(&self.app_handle) {
             error!("Failed to emit meeting-history-updated event: {}", e);
         }
 
@@ -719,9 +1676,25 @@ impl HistoryManager {
     }
 }
 
+fn is_encrypted_recording(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        == Some(ENCRYPTED_EXTENSION)
+}
+
+/// Name of the recording without the encrypted suffix
+fn plain_recording_name(file_name: &str) -> &str {
+    file_name
+        .strip_suffix(ENCRYPTED_EXTENSION)
+        .and_then(|name| name.strip_suffix('.'))
+        .unwrap_or(file_name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::{RetentionAction, RetentionRule};
     use rusqlite::{params, Connection};
 
     fn setup_conn() -> Connection {
@@ -734,8 +1707,12 @@ mod tests {
                 saved BOOLEAN NOT NULL DEFAULT 0,
                 title TEXT NOT NULL,
                 transcription_text TEXT NOT NULL,
+                raw_text TEXT,
                 post_processed_text TEXT,
-                post_process_prompt TEXT
+                post_process_prompt TEXT,
+                app_name TEXT,
+                app_domain TEXT,
+                duration_ms INTEGER
             );",
         )
         .expect("create transcription_history table");
@@ -759,6 +1736,133 @@ mod tests {
         .expect("insert history entry");
     }
 
+    #[test]
+    fn retention_rules_filter_by_age_saved_and_text() {
+        let conn = setup_conn();
+        let day = 24 * 60 * 60;
+        insert_entry(&conn, 0, "Standup meeting notes", None);
+        insert_entry(&conn, day, "Grocery list", None);
+        insert_entry(&conn, 9 * day, "Another MEETING", None);
+        conn.execute(
+            "UPDATE transcription_history SET saved = 1 WHERE timestamp = ?1",
+            params![day],
+        )
+        .expect("mark entry saved");
+        let now = 10 * day;
+
+        let rule = |older_than_days, saved, text: Option<&str>| RetentionRule {
+            id: "rule".to_string(),
+            enabled: true,
+            older_than_days,
+            action: RetentionAction::DeleteEntry,
+            saved,
+            text_contains: text.map(str::to_string),
+        };
+        let matched = |rule: RetentionRule| -> Vec<i64> {
+            HistoryManager::select_retention_matches(&conn, &rule, now)
+                .expect("select matches")
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        assert_eq!(matched(rule(7, None, None)), vec![1, 2]);
+        assert_eq!(matched(rule(7, Some(false), None)), vec![1]);
+        assert_eq!(matched(rule(0, None, Some(" meeting "))), vec![1, 3]);
+        assert_eq!(
+            matched(rule(0, Some(true), Some("meeting"))),
+            Vec::<i64>::new()
+        );
+        assert_eq!(matched(rule(0, None, Some(""))), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn keep_rules_exempt_entries_from_other_rules() {
+        let conn = setup_conn();
+        let day = 24 * 60 * 60;
+        insert_entry(&conn, 0, "Saved notes", None);
+        insert_entry(&conn, day, "Scratch", None);
+        conn.execute(
+            "UPDATE transcription_history SET saved = 1 WHERE timestamp = 0",
+            [],
+        )
+        .expect("mark entry saved");
+
+        let rule = |action, saved| RetentionRule {
+            id: "rule".to_string(),
+            enabled: true,
+            older_than_days: 0,
+            action,
+            saved,
+            text_contains: None,
+        };
+        let mut rules = vec![
+            rule(RetentionAction::DeleteEntry, None),
+            rule(RetentionAction::Keep, Some(true)),
+        ];
+        let kept = HistoryManager::select_kept_entries(&conn, &rules, 10 * day)
+            .expect("select kept entries");
+        assert_eq!(kept, HashSet::from([1]));
+
+        rules[1].enabled = false;
+        let kept = HistoryManager::select_kept_entries(&conn, &rules, 10 * day)
+            .expect("select kept entries");
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn history_pages_follow_cursors_without_gaps() {
+        let conn = setup_conn();
+        for (timestamp, duration) in [(100, 3_000), (200, 1_000), (300, 3_000), (400, 2_000)] {
+            insert_entry(&conn, timestamp, &format!("entry {}", timestamp), None);
+            conn.execute(
+                "UPDATE transcription_history SET duration_ms = ?1 WHERE timestamp = ?2",
+                params![duration, timestamp],
+            )
+            .expect("set duration");
+        }
+
+        let all_pages = |sort: HistorySort| -> Vec<i64> {
+            let mut query = HistoryQuery {
+                sort,
+                limit: 3,
+                ..Default::default()
+            };
+            let mut timestamps = Vec::new();
+            loop {
+                let page =
+                    HistoryManager::get_history_page_with_conn(&conn, &query).expect("fetch page");
+                timestamps.extend(page.entries.iter().map(|entry| entry.timestamp));
+                match page.next_cursor {
+                    Some(cursor) => query.cursor = Some(cursor),
+                    None => return timestamps,
+                }
+            }
+        };
+
+        assert_eq!(all_pages(HistorySort::Newest), vec![400, 300, 200, 100]);
+        assert_eq!(all_pages(HistorySort::Oldest), vec![100, 200, 300, 400]);
+        // Equal durations fall back to id order
+        assert_eq!(all_pages(HistorySort::Longest), vec![300, 100, 400, 200]);
+        assert_eq!(all_pages(HistorySort::Shortest), vec![200, 400, 100, 300]);
+
+        let search = HistoryQuery {
+            limit: 10,
+            search: Some(" ENTRY 3".to_string()),
+            ..Default::default()
+        };
+        let page = HistoryManager::get_history_page_with_conn(&conn, &search).expect("search");
+        assert_eq!(page.entries.len(), 1);
+        assert!(page.next_cursor.is_none());
+
+        let bad = HistoryQuery {
+            cursor: Some("nope".to_string()),
+            limit: 10,
+            ..Default::default()
+        };
+        assert!(HistoryManager::get_history_page_with_conn(&conn, &bad).is_err());
+    }
+
     #[test]
     fn get_latest_entry_returns_none_when_empty() {
         let conn = setup_conn();
@@ -0,0 +1,509 @@
+//! Schema upgrades for the history database.
+//!
+//! Migrations are applied in order and tracked with SQLite's user_version
+//! pragma. A database with pending migrations is copied to a backup first and
+//! must pass `PRAGMA integrity_check` afterwards; if the upgrade fails either
+//! way the backup is put back, so a bad migration can never take the archive
+//! with it. A database that is already corrupt at startup is moved aside and
+//! replaced by an empty one instead of keeping the app from starting.
+//!
+//! New schema changes go at the end of [`MIGRATIONS`]. Never edit or reorder
+//! an entry that has shipped.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use rusqlite::Connection;
+use rusqlite_migration::{Migrations, M};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Database migrations for transcription history.
+///
+/// Note: For users upgrading from tauri-plugin-sql, migrate_from_tauri_plugin_sql()
+/// converts the old _sqlx_migrations table tracking to the user_version pragma,
+/// ensuring migrations don't re-run on existing databases.
+static MIGRATIONS: &[M] = &[
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            file_name TEXT NOT NULL,
+            timestamp INTEGER NOT NULL,
+            saved BOOLEAN NOT NULL DEFAULT 0,
+            title TEXT NOT NULL,
+            transcription_text TEXT NOT NULL
+        );",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    // Meeting mode tables
+    M::up(
+        "CREATE TABLE IF NOT EXISTS meeting_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            meeting_id TEXT NOT NULL UNIQUE,
+            started_at INTEGER NOT NULL,
+            ended_at INTEGER NOT NULL,
+            duration_seconds INTEGER NOT NULL,
+            full_transcript TEXT NOT NULL,
+            summary TEXT,
+            action_items TEXT,
+            chunk_count INTEGER NOT NULL,
+            saved BOOLEAN NOT NULL DEFAULT 0
+        );",
+    ),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS meeting_chunks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            meeting_id TEXT NOT NULL,
+            chunk_index INTEGER NOT NULL,
+            start_time_ms INTEGER NOT NULL,
+            end_time_ms INTEGER NOT NULL,
+            transcription TEXT NOT NULL,
+            audio_file_name TEXT,
+            FOREIGN KEY (meeting_id) REFERENCES meeting_history(meeting_id)
+        );",
+    ),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS meeting_recovery (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            session_data TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        );",
+    ),
+    // History sync: a stable id per entry, changes waiting to be written to
+    // the sync folder, and how far each other device's journal was imported
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN sync_uid TEXT;
+        UPDATE transcription_history SET sync_uid = lower(hex(randomblob(16)));
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_transcription_history_sync_uid
+            ON transcription_history(sync_uid);
+        CREATE TABLE IF NOT EXISTS sync_outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            change TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS sync_peers (
+            device_id TEXT PRIMARY KEY,
+            last_seq INTEGER NOT NULL
+        );",
+    ),
+    // Engine output before custom words, filtering and formatting
+    M::up("ALTER TABLE transcription_history ADD COLUMN raw_text TEXT;"),
+    // Earlier texts of entries that were re-processed, dropped with their entry
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entry_id INTEGER NOT NULL,
+            replaced_at INTEGER NOT NULL,
+            transcription_text TEXT NOT NULL,
+            post_processed_text TEXT,
+            post_process_prompt TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_transcription_versions_entry
+            ON transcription_versions(entry_id);
+        CREATE TRIGGER IF NOT EXISTS delete_transcription_versions
+            AFTER DELETE ON transcription_history
+        BEGIN
+            DELETE FROM transcription_versions WHERE entry_id = OLD.id;
+        END;",
+    ),
+    // App a dictation went to and how long it was recorded for
+    M::up(
+        "ALTER TABLE transcription_history ADD COLUMN app_name TEXT;
+        ALTER TABLE transcription_history ADD COLUMN duration_ms INTEGER;",
+    ),
+    // Waveform peaks for the playback scrubber, filled in lazily for older entries
+    M::up("ALTER TABLE transcription_history ADD COLUMN waveform_peaks BLOB;"),
+    // Keyset pagination of the history view, see `HistorySort`
+    M::up(
+        "CREATE INDEX IF NOT EXISTS idx_transcription_history_timestamp
+            ON transcription_history(timestamp, id);
+        CREATE INDEX IF NOT EXISTS idx_transcription_history_duration
+            ON transcription_history(COALESCE(duration_ms, 0), id);",
+    ),
+    // Speaker of each meeting chunk and the names given to speakers per meeting
+    M::up(
+        "ALTER TABLE meeting_chunks ADD COLUMN speaker TEXT;
+        CREATE TABLE IF NOT EXISTS meeting_speakers (
+            meeting_id TEXT NOT NULL,
+            label TEXT NOT NULL,
+            name TEXT NOT NULL,
+            PRIMARY KEY (meeting_id, label)
+        );",
+    ),
+    // Topic sections of a meeting, as a JSON array of `MeetingTopic`
+    M::up("ALTER TABLE meeting_history ADD COLUMN topics TEXT;"),
+    // Tokens and audio sent to cloud APIs, see `crate::usage`
+    M::up(
+        "CREATE TABLE IF NOT EXISTS api_usage (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            provider_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            input_tokens INTEGER NOT NULL DEFAULT 0,
+            output_tokens INTEGER NOT NULL DEFAULT 0,
+            audio_seconds REAL NOT NULL DEFAULT 0
+        );
+        CREATE INDEX IF NOT EXISTS idx_api_usage_timestamp ON api_usage(timestamp);",
+    ),
+    // Cached completions, see `crate::llm_client::cache`
+    M::up(
+        "CREATE TABLE IF NOT EXISTS llm_cache (
+            provider_id TEXT NOT NULL,
+            model TEXT NOT NULL,
+            prompt_hash TEXT NOT NULL,
+            reply TEXT NOT NULL,
+            last_used_at INTEGER NOT NULL,
+            PRIMARY KEY (provider_id, model, prompt_hash)
+        );",
+    ),
+    // Domain of the browser tab a dictation went to
+    M::up("ALTER TABLE transcription_history ADD COLUMN app_domain TEXT;"),
+];
+
+/// Pre-migration backups kept next to the database
+const BACKUPS_KEPT: usize = 3;
+const BACKUP_DIR: &str = "history-backups";
+
+/// Bring the database at `db_path` up to the latest schema. `open` must
+/// return a usable connection (unlocked, if the database is encrypted).
+pub fn upgrade<F>(db_path: &Path, open: F) -> Result<()>
+where
+    F: Fn(&Path) -> Result<Connection>,
+{
+    // Validate migrations in debug builds
+    #[cfg(debug_assertions)]
+    Migrations::new(MIGRATIONS.to_vec())
+        .validate()
+        .expect("Invalid migrations");
+
+    upgrade_with(db_path, open, MIGRATIONS)
+}
+
+fn upgrade_with<F>(db_path: &Path, open: F, migrations: &[M]) -> Result<()>
+where
+    F: Fn(&Path) -> Result<Connection>,
+{
+    if db_path.exists() {
+        // Failing to open (e.g. a missing key) or to run the check (e.g. a
+        // locked file) is not corruption; only a database that SQLite reports
+        // as corrupt gets quarantined
+        let conn = open(db_path)?;
+        if let Some(problems) = find_corruption(&conn)? {
+            drop(conn);
+            let quarantined = quarantine(db_path)?;
+            error!(
+                "History database is corrupt ({}); moved it to {:?} and starting a new one",
+                problems, quarantined
+            );
+        }
+    }
+
+    let mut conn = open(db_path)?;
+    migrate_from_tauri_plugin_sql(&conn)?;
+
+    let version = user_version(&conn)?;
+    let latest = migrations.len();
+    if version > latest {
+        // Written by a newer build; leave it alone rather than refusing to start
+        warn!(
+            "History database is at schema version {}, newer than this build's {}",
+            version, latest
+        );
+        return Ok(());
+    }
+    if version == latest {
+        debug!("Database already at latest version {}", version);
+        return Ok(());
+    }
+
+    // A brand new database has nothing worth backing up
+    let backup = if version > 0 {
+        Some(backup(db_path, version)?)
+    } else {
+        None
+    };
+
+    let result = Migrations::new(migrations.to_vec())
+        .to_latest(&mut conn)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| integrity_check(&conn));
+    drop(conn);
+
+    match result {
+        Ok(()) => {
+            info!("Database migrated from version {} to {}", version, latest);
+            Ok(())
+        }
+        Err(e) => {
+            if let Some(backup) = backup {
+                fs::copy(&backup, db_path).with_context(|| {
+                    format!("Failed to restore history backup from {:?}", backup)
+                })?;
+                error!("Restored history database from {:?}", backup);
+            }
+            Err(e.context(format!(
+                "Failed to upgrade history database from version {} to {}",
+                version, latest
+            )))
+        }
+    }
+}
+
+fn user_version(conn: &Connection) -> Result<usize> {
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    Ok(version.max(0) as usize)
+}
+
+fn integrity_check(conn: &Connection) -> Result<()> {
+    match find_corruption(conn)? {
+        Some(problems) => Err(anyhow!("integrity check failed: {}", problems)),
+        None => Ok(()),
+    }
+}
+
+/// What `PRAGMA integrity_check` found wrong, if anything. Errors that
+/// don't say the file is damaged, like a busy database, are returned as
+/// errors rather than taken for corruption.
+fn find_corruption(conn: &Connection) -> Result<Option<String>> {
+    let problems = conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+        stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+    });
+    match problems {
+        Ok(problems) if problems == ["ok"] => Ok(None),
+        Ok(problems) => Ok(Some(problems.join("; "))),
+        Err(e) if is_corruption(&e) => Ok(Some(e.to_string())),
+        Err(e) => Err(anyhow!(e).context("Failed to check the history database")),
+    }
+}
+
+fn is_corruption(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(rusqlite::ErrorCode::DatabaseCorrupt | rusqlite::ErrorCode::NotADatabase)
+    )
+}
+
+/// Copy the database before upgrading it, keeping the last few copies
+fn backup(db_path: &Path, version: usize) -> Result<PathBuf> {
+    let dir = backup_dir(db_path);
+    fs::create_dir_all(&dir)?;
+
+    let backup = dir.join(format!(
+        "history-{}-v{}.db",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        version
+    ));
+    fs::copy(db_path, &backup)
+        .with_context(|| format!("Failed to back up history database to {:?}", backup))?;
+    info!("Backed up history database to {:?}", backup);
+
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db"))
+        .collect();
+    // Names start with the timestamp, so they sort oldest first
+    backups.sort();
+    let excess = backups.len().saturating_sub(BACKUPS_KEPT);
+    for old in &backups[..excess] {
+        if let Err(e) = fs::remove_file(old) {
+            warn!("Failed to remove old history backup {:?}: {}", old, e);
+        }
+    }
+
+    Ok(backup)
+}
+
+fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .map(|parent| parent.join(BACKUP_DIR))
+        .unwrap_or_else(|| PathBuf::from(BACKUP_DIR))
+}
+
+/// Move a corrupt database and its journal files out of the way. Nothing is
+/// deleted, so the data can still be recovered by hand.
+fn quarantine(db_path: &Path) -> Result<PathBuf> {
+    let suffix = format!("corrupt-{}", Utc::now().format("%Y%m%d%H%M%S"));
+    let quarantined = PathBuf::from(format!("{}.{}", db_path.display(), suffix));
+    fs::rename(db_path, &quarantined)?;
+
+    for sidecar in ["-journal", "-wal", "-shm"] {
+        let path = PathBuf::from(format!("{}{}", db_path.display(), sidecar));
+        if path.exists() {
+            let target = PathBuf::from(format!("{}.{}", path.display(), suffix));
+            if let Err(e) = fs::rename(&path, &target) {
+                warn!("Failed to quarantine {:?}: {}", path, e);
+            }
+        }
+    }
+
+    Ok(quarantined)
+}
+
+/// Migrate from tauri-plugin-sql's migration tracking to rusqlite_migration's.
+/// tauri-plugin-sql used a _sqlx_migrations table, while rusqlite_migration uses
+/// SQLite's user_version pragma. This function checks if the old system was in use
+/// and sets the user_version accordingly so migrations don't re-run.
+fn migrate_from_tauri_plugin_sql(conn: &Connection) -> Result<()> {
+    // Check if the old _sqlx_migrations table exists
+    let has_sqlx_migrations: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='_sqlx_migrations'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_sqlx_migrations {
+        return Ok(());
+    }
+
+    if user_version(conn)? > 0 {
+        // Already migrated to rusqlite_migration system
+        return Ok(());
+    }
+
+    // Get the highest version from the old migrations table
+    let old_version: i32 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM _sqlx_migrations WHERE success = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(0);
+
+    if old_version > 0 {
+        info!(
+            "Migrating from tauri-plugin-sql (version {}) to rusqlite_migration",
+            old_version
+        );
+
+        // Set user_version to match the old migration state
+        conn.pragma_update(None, "user_version", old_version)?;
+
+        info!(
+            "Migration tracking converted: user_version set to {}",
+            old_version
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "paperflow-migrations-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("history.db")
+    }
+
+    fn open(path: &Path) -> Result<Connection> {
+        Ok(Connection::open(path)?)
+    }
+
+    #[test]
+    fn creates_new_database_at_latest_version() {
+        let db = temp_db("new");
+        upgrade(&db, open).unwrap();
+
+        let conn = open(&db).unwrap();
+        assert_eq!(user_version(&conn).unwrap(), MIGRATIONS.len());
+        assert!(!backup_dir(&db).exists());
+    }
+
+    #[test]
+    fn backs_up_before_upgrading() {
+        let db = temp_db("backup");
+        upgrade_with(&db, open, &MIGRATIONS[..2]).unwrap();
+        upgrade(&db, open).unwrap();
+
+        let backups: Vec<_> = fs::read_dir(backup_dir(&db)).unwrap().collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(user_version(&open(&db).unwrap()).unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn failed_migration_restores_backup() {
+        let db = temp_db("restore");
+        upgrade_with(&db, open, &MIGRATIONS[..2]).unwrap();
+        open(&db)
+            .unwrap()
+            .execute(
+                "INSERT INTO transcription_history (file_name, timestamp, title, transcription_text)
+                 VALUES ('a.wav', 1, 'title', 'kept')",
+                [],
+            )
+            .unwrap();
+
+        let broken = [
+            MIGRATIONS[0].clone(),
+            MIGRATIONS[1].clone(),
+            M::up("DROP TABLE transcription_history;"),
+            M::up("THIS IS NOT SQL;"),
+        ];
+        assert!(upgrade_with(&db, open, &broken).is_err());
+
+        let conn = open(&db).unwrap();
+        assert_eq!(user_version(&conn).unwrap(), 2);
+        let text: String = conn
+            .query_row(
+                "SELECT transcription_text FROM transcription_history",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(text, "kept");
+    }
+
+    #[test]
+    fn corrupt_database_is_quarantined() {
+        let db = temp_db("corrupt");
+        fs::write(
+            &db,
+            b"definitely not a sqlite database, just some garbage bytes",
+        )
+        .unwrap();
+
+        upgrade(&db, open).unwrap();
+
+        assert_eq!(user_version(&open(&db).unwrap()).unwrap(), MIGRATIONS.len());
+        let quarantined = fs::read_dir(db.parent().unwrap())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name().to_string_lossy().contains(".corrupt-"));
+        assert!(quarantined);
+    }
+
+    #[test]
+    fn only_corruption_errors_count_as_corruption() {
+        let failure = |code| rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), None);
+        assert!(is_corruption(&failure(rusqlite::ffi::SQLITE_CORRUPT)));
+        assert!(is_corruption(&failure(rusqlite::ffi::SQLITE_NOTADB)));
+        assert!(!is_corruption(&failure(rusqlite::ffi::SQLITE_BUSY)));
+        assert!(!is_corruption(&failure(rusqlite::ffi::SQLITE_IOERR)));
+        assert!(!is_corruption(&rusqlite::Error::InvalidQuery));
+    }
+
+    #[test]
+    fn newer_database_is_left_alone() {
+        let db = temp_db("newer");
+        upgrade(&db, open).unwrap();
+        let newer = MIGRATIONS.len() as i64 + 5;
+        open(&db)
+            .unwrap()
+            .pragma_update(None, "user_version", newer)
+            .unwrap();
+
+        upgrade(&db, open).unwrap();
+        assert_eq!(user_version(&open(&db).unwrap()).unwrap(), newer as usize);
+    }
+}
@@ -0,0 +1,507 @@
+//! Cross-device history sync through a shared folder.
+//!
+//! Each device only ever writes its own subdirectory of the sync folder,
+//! `<folder>/<device id>/`, as numbered journal files of history changes. Other
+//! devices read those journals and apply whatever they have not seen yet. As
+//! no file has more than one writer, any folder-syncing tool (Syncthing, a
+//! WebDAV mount, Dropbox, ...) can carry them without producing conflicts.
+//!
+//! Only the text of an entry travels; recordings stay on the device that
+//! made them. Journals are plain JSON, and each device's encryption key
+//! stays in its own keychain, so sync is refused while history encryption is
+//! on rather than leaving transcripts readable in the sync folder.
+
+use anyhow::{anyhow, Result};
+use log::{debug, error, info, warn};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::events::HistoryUpdated;
+use crate::managers::history::HistoryManager;
+use crate::settings::get_settings;
+
+/// How often the background thread syncs while enabled
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+const DEVICE_ID_FILE: &str = "sync-device-id";
+const JOURNAL_EXTENSION: &str = "json";
+/// Why sync is refused while history is encrypted
+pub const ENCRYPTED_HISTORY_ERROR: &str =
+    "History sync is unavailable while history encryption is on, as journals are not encrypted";
+
+/// One change to the history, as written to the sync folder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SyncChange {
+    Add {
+        uid: String,
+        timestamp: i64,
+        saved: bool,
+        title: String,
+        transcription_text: String,
+        #[serde(default)]
+        raw_text: Option<String>,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+    },
+    SetSaved {
+        uid: String,
+        saved: bool,
+    },
+    SetText {
+        uid: String,
+        transcription_text: String,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+    },
+    Delete {
+        uid: String,
+    },
+}
+
+/// A journal file: the changes one device made between two syncs
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    device_id: String,
+    changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Type)]
+pub struct SyncReport {
+    /// Local changes written to the sync folder
+    pub exported: usize,
+    /// Changes from other devices applied locally
+    pub imported: usize,
+}
+
+/// Queue a change to be written on the next sync
+pub fn queue_change(conn: &Connection, change: &SyncChange) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sync_outbox (change) VALUES (?1)",
+        params![serde_json::to_string(change)?],
+    )?;
+    Ok(())
+}
+
+pub struct HistorySyncManager {
+    app_handle: AppHandle,
+    history: Arc<HistoryManager>,
+    device_id: String,
+    /// Serialises syncs from the background thread and the sync-now command
+    sync_lock: Mutex<()>,
+}
+
+impl HistorySyncManager {
+    pub fn new(app_handle: &AppHandle, history: Arc<HistoryManager>) -> Result<Self> {
+        let id_path = app_handle.path().app_data_dir()?.join(DEVICE_ID_FILE);
+        let device_id = match fs::read_to_string(&id_path) {
+            Ok(id) if !id.trim().is_empty() => id.trim().to_string(),
+            _ => {
+                let id = uuid::Uuid::new_v4().simple().to_string();
+                fs::write(&id_path, &id)?;
+                id
+            }
+        };
+
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            history,
+            device_id,
+            sync_lock: Mutex::new(()),
+        })
+    }
+
+    /// Sync periodically in the background while sync is enabled
+    pub fn start(self: &Arc<Self>) {
+        let manager = self.clone();
+        let spawned = thread::Builder::new()
+            .name("history-sync".into())
+            .spawn(move || loop {
+                let settings = get_settings(&manager.app_handle);
+                if settings.history_sync_enabled && !settings.history_encryption_enabled {
+                    if let Err(e) = manager.sync_now() {
+                        warn!("History sync failed: {}", e);
+                    }
+                }
+                thread::sleep(SYNC_INTERVAL);
+            });
+
+        if let Err(e) = spawned {
+            error!("Failed to spawn history sync thread: {}", e);
+        }
+    }
+
+    /// Queue every existing entry, so a newly enabled device shares its
+    /// whole history. Devices that already have an entry ignore it.
+    pub fn queue_full_history(&self) -> Result<usize> {
+        let conn = self.history.get_connection()?;
+        let changes: Vec<SyncChange> = {
+            let mut stmt = conn.prepare(
+                "SELECT sync_uid, timestamp, saved, title, transcription_text, raw_text, post_processed_text, post_process_prompt
+                 FROM transcription_history WHERE sync_uid IS NOT NULL ORDER BY timestamp",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(SyncChange::Add {
+                    uid: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    saved: row.get(2)?,
+                    title: row.get(3)?,
+                    transcription_text: row.get(4)?,
+                    raw_text: row.get(5)?,
+                    post_processed_text: row.get(6)?,
+                    post_process_prompt: row.get(7)?,
+                })
+            })?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        for change in &changes {
+            queue_change(&conn, change)?;
+        }
+        Ok(changes.len())
+    }
+
+    /// Write pending local changes and apply new ones from other devices
+    pub fn sync_now(&self) -> Result<SyncReport> {
+        let _guard = self.sync_lock.lock().unwrap();
+        if self.history.encryption_key()?.is_some() {
+            return Err(anyhow!(ENCRYPTED_HISTORY_ERROR));
+        }
+        let folder = sync_folder(&self.app_handle)?;
+        let conn = self.history.get_connection()?;
+
+        let exported = export_outbox(&conn, &folder, &self.device_id)?;
+        let mut removed = Vec::new();
+        let imported = import_journals(&conn, &folder, &self.device_id, &mut removed)?;
+
+        // Recordings of entries deleted on another device
+        for file_name in removed {
+            let path = self.history.get_audio_file_path(&file_name);
+            if path.exists() {
+                if let Err(e) = fs::remove_file(&path) {
+                    warn!("Failed to delete audio file {}: {}", file_name, e);
+                }
+            }
+        }
+
+        if exported > 0 || imported > 0 {
+            info!(
+                "History sync: {} changes written, {} applied",
+                exported, imported
+            );
+        }
+        if imported > 0 {
+            if let Err(e) = HistoryUpdated.emit(&self.app_handle) {
+                error!("Failed to emit history-updated event: {}", e);
+            }
+        }
+
+        Ok(SyncReport { exported, imported })
+    }
+}
+
+fn sync_folder(app: &AppHandle) -> Result<PathBuf> {
+    let folder = get_settings(app)
+        .history_sync_folder
+        .ok_or_else(|| anyhow!("No sync folder configured"))?;
+    let folder = PathBuf::from(folder);
+    if !folder.is_dir() {
+        return Err(anyhow!("Sync folder {:?} is not available", folder));
+    }
+    Ok(folder)
+}
+
+/// Move the outbox into a new journal file named after its last change
+fn export_outbox(conn: &Connection, folder: &Path, device_id: &str) -> Result<usize> {
+    let pending: Vec<(i64, String)> = {
+        let mut stmt = conn.prepare("SELECT id, change FROM sync_outbox ORDER BY id")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    let Some(&(last_id, _)) = pending.last() else {
+        return Ok(0);
+    };
+
+    let changes = pending
+        .iter()
+        .map(|(_, change)| serde_json::from_str(change))
+        .collect::<serde_json::Result<Vec<SyncChange>>>()?;
+    let journal = Journal {
+        device_id: device_id.to_string(),
+        changes,
+    };
+
+    let device_dir = folder.join(device_id);
+    fs::create_dir_all(&device_dir)?;
+    // Write under a hidden name first so other devices never read half a file
+    let path = device_dir.join(format!("{:020}.{}", last_id, JOURNAL_EXTENSION));
+    let tmp_path = device_dir.join(format!(".{:020}.tmp", last_id));
+    fs::write(&tmp_path, serde_json::to_vec(&journal)?)?;
+    fs::rename(&tmp_path, &path)?;
+
+    conn.execute("DELETE FROM sync_outbox WHERE id <= ?1", params![last_id])?;
+    Ok(journal.changes.len())
+}
+
+/// Apply journals from every other device that are newer than the last one
+/// imported from it. File names of deleted entries are added to `removed`.
+fn import_journals(
+    conn: &Connection,
+    folder: &Path,
+    device_id: &str,
+    removed: &mut Vec<String>,
+) -> Result<usize> {
+    let mut applied = 0;
+
+    for entry in fs::read_dir(folder)?.flatten() {
+        let peer_dir = entry.path();
+        let Some(peer_id) = peer_dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if peer_id == device_id || !peer_dir.is_dir() {
+            continue;
+        }
+
+        let last_seq: i64 = conn
+            .query_row(
+                "SELECT last_seq FROM sync_peers WHERE device_id = ?1",
+                params![peer_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(0);
+
+        for (seq, path) in pending_journals(&peer_dir, last_seq)? {
+            // A journal that does not parse is probably still syncing; retry
+            // it (and everything after it) next time
+            let journal: Journal = match fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|bytes| Ok(serde_json::from_slice(&bytes)?))
+            {
+                Ok(journal) => journal,
+                Err(e) => {
+                    debug!("Skipping unreadable journal {:?} for now: {}", path, e);
+                    break;
+                }
+            };
+
+            let tx = conn.unchecked_transaction()?;
+            applied += apply_changes(&tx, &journal.changes, removed)?;
+            tx.execute(
+                "INSERT INTO sync_peers (device_id, last_seq) VALUES (?1, ?2)
+                 ON CONFLICT(device_id) DO UPDATE SET last_seq = excluded.last_seq",
+                params![peer_id, seq],
+            )?;
+            tx.commit()?;
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Journal files in `dir` newer than `after`, oldest first
+fn pending_journals(dir: &Path, after: i64) -> Result<Vec<(i64, PathBuf)>> {
+    let mut journals: Vec<(i64, PathBuf)> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == JOURNAL_EXTENSION))
+        .filter_map(|path| {
+            let seq = path.file_stem()?.to_str()?.parse().ok()?;
+            Some((seq, path))
+        })
+        .filter(|(seq, _)| *seq > after)
+        .collect();
+    journals.sort();
+    Ok(journals)
+}
+
+/// Apply changes from another device. Returns how many changed anything;
+/// file names of deleted entries are added to `removed`.
+fn apply_changes(
+    conn: &Connection,
+    changes: &[SyncChange],
+    removed: &mut Vec<String>,
+) -> Result<usize> {
+    let mut applied = 0;
+    for change in changes {
+        applied += match change {
+            SyncChange::Add {
+                uid,
+                timestamp,
+                saved,
+                title,
+                transcription_text,
+                raw_text,
+                post_processed_text,
+                post_process_prompt,
+            } => conn.execute(
+                // The recording stays on the other device; the placeholder name
+                // never matches a local file
+                "INSERT OR IGNORE INTO transcription_history (file_name, timestamp, saved, title, transcription_text, raw_text, post_processed_text, post_process_prompt, sync_uid)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    format!("synced-{}", uid),
+                    timestamp,
+                    saved,
+                    title,
+                    transcription_text,
+                    raw_text,
+                    post_processed_text,
+                    post_process_prompt,
+                    uid
+                ],
+            )?,
+            SyncChange::SetSaved { uid, saved } => conn.execute(
+                "UPDATE transcription_history SET saved = ?1 WHERE sync_uid = ?2",
+                params![saved, uid],
+            )?,
+            SyncChange::SetText {
+                uid,
+                transcription_text,
+                post_processed_text,
+                post_process_prompt,
+            } => conn.execute(
+                "UPDATE transcription_history
+                 SET transcription_text = ?1, post_processed_text = ?2, post_process_prompt = ?3
+                 WHERE sync_uid = ?4",
+                params![transcription_text, post_processed_text, post_process_prompt, uid],
+            )?,
+            SyncChange::Delete { uid } => {
+                let file_name: Option<String> = conn
+                    .query_row(
+                        "SELECT file_name FROM transcription_history WHERE sync_uid = ?1",
+                        params![uid],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                removed.extend(file_name);
+                conn.execute(
+                    "DELETE FROM transcription_history WHERE sync_uid = ?1",
+                    params![uid],
+                )?
+            }
+        };
+    }
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_conn() -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory db");
+        conn.execute_batch(
+            "CREATE TABLE transcription_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                file_name TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                saved BOOLEAN NOT NULL DEFAULT 0,
+                title TEXT NOT NULL,
+                transcription_text TEXT NOT NULL,
+                raw_text TEXT,
+                post_processed_text TEXT,
+                post_process_prompt TEXT,
+                sync_uid TEXT UNIQUE
+            );
+            CREATE TABLE sync_outbox (id INTEGER PRIMARY KEY AUTOINCREMENT, change TEXT NOT NULL);
+            CREATE TABLE sync_peers (device_id TEXT PRIMARY KEY, last_seq INTEGER NOT NULL);",
+        )
+        .expect("create tables");
+        conn
+    }
+
+    fn add(uid: &str, text: &str) -> SyncChange {
+        SyncChange::Add {
+            uid: uid.to_string(),
+            timestamp: 100,
+            saved: false,
+            title: "title".to_string(),
+            transcription_text: text.to_string(),
+            raw_text: None,
+            post_processed_text: None,
+            post_process_prompt: None,
+        }
+    }
+
+    fn texts(conn: &Connection) -> Vec<(String, bool)> {
+        let mut stmt = conn
+            .prepare("SELECT transcription_text, saved FROM transcription_history ORDER BY id")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn applies_adds_once_then_updates_and_deletes() {
+        let conn = setup_conn();
+        let changes = vec![
+            add("a", "first"),
+            add("b", "second"),
+            add("a", "first again"),
+            SyncChange::SetSaved {
+                uid: "b".to_string(),
+                saved: true,
+            },
+            SyncChange::SetText {
+                uid: "b".to_string(),
+                transcription_text: "second, reprocessed".to_string(),
+                post_processed_text: None,
+                post_process_prompt: None,
+            },
+            SyncChange::Delete {
+                uid: "a".to_string(),
+            },
+        ];
+
+        let mut removed = Vec::new();
+        assert_eq!(apply_changes(&conn, &changes, &mut removed).unwrap(), 5);
+        assert_eq!(
+            texts(&conn),
+            vec![("second, reprocessed".to_string(), true)]
+        );
+        assert_eq!(removed, vec!["synced-a".to_string()]);
+    }
+
+    #[test]
+    fn journals_round_trip_between_devices() {
+        let folder = std::env::temp_dir().join(format!("paperflow-sync-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&folder);
+        fs::create_dir_all(&folder).unwrap();
+
+        let laptop = setup_conn();
+        let desktop = setup_conn();
+        let mut removed = Vec::new();
+        for (conn, change) in [
+            (&laptop, add("a", "from the laptop")),
+            (&desktop, add("b", "from the desktop")),
+        ] {
+            apply_changes(conn, std::slice::from_ref(&change), &mut removed).unwrap();
+            queue_change(conn, &change).unwrap();
+        }
+
+        assert_eq!(export_outbox(&laptop, &folder, "laptop").unwrap(), 1);
+        assert_eq!(export_outbox(&desktop, &folder, "desktop").unwrap(), 1);
+        assert_eq!(export_outbox(&laptop, &folder, "laptop").unwrap(), 0);
+
+        let import = |conn: &Connection, device: &str| {
+            import_journals(conn, &folder, device, &mut Vec::new()).unwrap()
+        };
+        assert_eq!(import(&laptop, "laptop"), 1);
+        assert_eq!(import(&desktop, "desktop"), 1);
+        // Already imported journals are not applied twice
+        assert_eq!(import(&laptop, "laptop"), 0);
+
+        assert_eq!(texts(&laptop).len(), 2);
+        assert_eq!(texts(&desktop).len(), 2);
+    }
+}
@@ -2,11 +2,13 @@ use crate::managers::transcription::TranscriptionManager;
 use crate::settings::get_settings;
 use log::{debug, error, info, warn};
 use serde::Serialize;
+use specta::Type;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
+use tauri_specta::Event;
 
 // ============================================================================
 // Constants
@@ -40,13 +42,13 @@ const BACKOFF_MAX_MS: u64 = 5000;
 // Events
 // ============================================================================
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Type)]
 pub struct LivePreviewEvent {
     pub text: String,
     pub is_final: bool,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Type)]
 pub struct LivePreviewErrorEvent {
     pub error_type: String,
     pub message: String,
@@ -297,7 +299,7 @@ impl LivePreviewManager {
             message: message.to_string(),
             is_fatal,
         };
-        if let Err(e) = self.app_handle.emit("live-preview-error", event) {
+        if let Err(e) = event.emit(&self.app_handle) {
             error!("Failed to emit live preview error event: {}", e);
         }
     }
@@ -469,7 +471,7 @@ impl LivePreviewManager {
             text: text.to_string(),
             is_final: false,
         };
-        if let Err(e) = self.app_handle.emit("live-preview-update", event) {
+        if let Err(e) = event.emit(&self.app_handle) {
             error!("Failed to emit live preview event: {}", e);
         }
     }
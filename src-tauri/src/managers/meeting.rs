@@ -2,19 +2,41 @@
 //!
 //! Handles long-form recording with chunked transcription, auto-summarization,
 //! and action item extraction.
+//!
+//! With diarization enabled, the whole recording is diarized once so speaker
+//! labels stay the same across chunks, and chunks are cut along speaker turns
+//! so each has a single speaker. Speakers can be given names afterwards; the
+//! stored transcript, summary and action items are rewritten to use them.
+//!
+//! Summaries are built map-reduce style: each chunk is summarized as soon as
+//! it is transcribed, while the next one transcribes, and the chunk summaries
+//! are combined in rounds until they fit the final summary prompt.
+//!
+//! While a meeting records, its audio is journaled to disk. After a crash the
+//! interrupted meeting can be resumed, picking up where the journal ends, or
+//! finalized from the journaled audio.
+//!
+//! Long meetings can also be split into topics: the LLM is shown the
+//! numbered chunks and picks where each topic starts, so the sections line
+//! up with the recording.
 
 use anyhow::Result;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
 use uuid::Uuid;
 
+use crate::events::{MeetingCompleted, MeetingHeartbeat, MeetingRecoveryAvailable};
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::diarization::{DiarizationManager, DiarizedSegment};
 use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::meeting_journal::MeetingJournal;
+use crate::managers::transcription::{TranscriptionManager, TranscriptionOverrides};
 use crate::settings::get_settings;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils;
@@ -33,6 +55,13 @@ pub enum MeetingState {
         /// The binding_id used to start the recording (needed to stop it correctly)
         binding_id: String,
     },
+    /// Meeting is paused; nothing is recorded until it is resumed
+    Paused {
+        meeting_id: String,
+        started_at: i64,
+        chunk_count: u32,
+        binding_id: String,
+    },
     /// Meeting is being processed (transcription/summarization)
     Processing { meeting_id: String },
 }
@@ -45,6 +74,9 @@ pub struct MeetingChunk {
     pub end_time_ms: u64,
     pub audio_path: Option<String>,
     pub transcription: Option<String>,
+    /// Diarization label ("Speaker 1") when speakers were told apart
+    #[serde(default)]
+    pub speaker: Option<String>,
 }
 
 /// A complete meeting session
@@ -57,9 +89,20 @@ pub struct MeetingSession {
     pub full_transcript: Option<String>,
     pub summary: Option<String>,
     pub action_items: Option<Vec<String>>,
+    #[serde(default)]
+    pub topics: Option<Vec<MeetingTopic>>,
     pub duration_seconds: Option<i64>,
 }
 
+/// A section of a meeting about one topic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct MeetingTopic {
+    pub title: String,
+    /// Offsets from the start of the recording
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
 /// Event emitted when meeting state changes
 #[derive(Debug, Clone, Serialize, Type)]
 pub struct MeetingStateEvent {
@@ -76,6 +119,352 @@ pub struct MeetingChunkEvent {
     pub transcription: String,
 }
 
+/// Event emitted while a stopped meeting is being transcribed
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MeetingFinalizationProgressEvent {
+    pub meeting_id: String,
+    pub chunks_done: u32,
+    pub chunk_total: u32,
+    /// Unix timestamp at which transcription is expected to finish, once the
+    /// model has a measured speed
+    pub estimated_completion_at: Option<i64>,
+}
+
+/// Span of the recording transcribed as one chunk, in samples
+#[derive(Debug, Clone, PartialEq)]
+struct ChunkPlan {
+    start: usize,
+    end: usize,
+    speaker: Option<String>,
+}
+
+/// Diarization turns shorter than this are merged into their neighbours, so
+/// a quick "yeah" or a misattributed breath doesn't become its own chunk
+const MIN_TURN_MS: usize = 2_000;
+
+/// Split a recording of `total` samples into chunks of at most `max_samples`.
+/// With diarization segments, chunks follow speaker turns and carry the
+/// speaker, relabelled "Speaker 1", "Speaker 2", ... in order of appearance.
+fn plan_chunks(total: usize, max_samples: usize, segments: &[DiarizedSegment]) -> Vec<ChunkPlan> {
+    let max_samples = max_samples.max(1);
+    let samples_per_ms = 16000 / 1000;
+    let min_turn = MIN_TURN_MS * samples_per_ms;
+
+    let mut sorted: Vec<&DiarizedSegment> = segments.iter().collect();
+    sorted.sort_by_key(|segment| segment.start_ms);
+
+    // Turn starts; the first turn also covers any silence before it
+    let mut raw_turns: Vec<(usize, &str)> = Vec::new();
+    for segment in sorted {
+        if raw_turns.last().map(|(_, speaker)| *speaker) == Some(segment.speaker.as_str()) {
+            continue;
+        }
+        let start = if raw_turns.is_empty() {
+            0
+        } else {
+            (segment.start_ms as usize * samples_per_ms).min(total)
+        };
+        raw_turns.push((start, segment.speaker.as_str()));
+    }
+
+    // Short turns go to the speaker before them, which may join two turns
+    // of the same speaker
+    let mut merged: Vec<(usize, &str)> = Vec::new();
+    for (index, &(start, speaker)) in raw_turns.iter().enumerate() {
+        let end = raw_turns.get(index + 1).map_or(total, |(next, _)| *next);
+        if end - start < min_turn && !merged.is_empty() {
+            continue;
+        }
+        if merged.last().map(|(_, last)| *last) == Some(speaker) {
+            continue;
+        }
+        merged.push((start, speaker));
+    }
+    // A short first turn has nothing before it, so it goes to the next one
+    if merged.len() > 1 && merged[1].0 < min_turn {
+        merged.remove(0);
+        merged[0].0 = 0;
+    }
+
+    let mut labels: HashMap<&str, String> = HashMap::new();
+    let mut turns: Vec<(usize, Option<String>)> = merged
+        .into_iter()
+        .map(|(start, speaker)| {
+            let next_label = format!("Speaker {}", labels.len() + 1);
+            let label = labels.entry(speaker).or_insert(next_label).clone();
+            (start, Some(label))
+        })
+        .collect();
+    if turns.is_empty() {
+        turns.push((0, None));
+    }
+
+    let mut plan = Vec::new();
+    for (index, (start, speaker)) in turns.iter().enumerate() {
+        let end = turns.get(index + 1).map_or(total, |(next, _)| *next);
+        let mut offset = *start;
+        while offset < end {
+            let chunk_end = (offset + max_samples).min(end);
+            plan.push(ChunkPlan {
+                start: offset,
+                end: chunk_end,
+                speaker: speaker.clone(),
+            });
+            offset = chunk_end;
+        }
+    }
+    plan
+}
+
+/// Name shown for a speaker: the one given by the user, else the label
+pub fn speaker_display<'a>(label: &'a str, names: &'a HashMap<String, String>) -> &'a str {
+    names.get(label).map(String::as_str).unwrap_or(label)
+}
+
+/// Full transcript of a meeting. When speakers are known, each turn starts
+/// a new line with the speaker's name.
+pub fn render_transcript(chunks: &[MeetingChunk], names: &HashMap<String, String>) -> String {
+    let texts = chunks
+        .iter()
+        .filter_map(|chunk| Some((chunk.speaker.as_deref(), chunk.transcription.as_deref()?)))
+        .filter(|(_, text)| !text.trim().is_empty());
+
+    if chunks.iter().all(|chunk| chunk.speaker.is_none()) {
+        return texts.map(|(_, text)| text).collect::<Vec<_>>().join(" ");
+    }
+
+    let mut output = String::new();
+    let mut last_speaker = None;
+    for (speaker, text) in texts {
+        if output.is_empty() || speaker != last_speaker {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            let name = speaker.map_or("Unknown", |label| speaker_display(label, names));
+            output.push_str(&format!("[{}]: ", name));
+            last_speaker = speaker;
+        } else {
+            output.push(' ');
+        }
+        output.push_str(text.trim());
+    }
+    output
+}
+
+/// Replace whole-word occurrences of a speaker name, so renaming "Speaker 1"
+/// leaves "Speaker 10" alone
+fn replace_speaker_name(text: &str, from: &str, to: &str) -> String {
+    if from.is_empty() {
+        return text.to_string();
+    }
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(from) {
+        let before = rest[..index]
+            .chars()
+            .last()
+            .or_else(|| output.chars().last());
+        let after = rest[index + from.len()..].chars().next();
+        output.push_str(&rest[..index]);
+        if is_word(before) || is_word(after) {
+            output.push_str(from);
+        } else {
+            output.push_str(to);
+        }
+        rest = &rest[index + from.len()..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Characters of chunk notes sent to the LLM in one prompt
+const SUMMARY_PART_CHARS: usize = 24_000;
+
+/// Summaries of a meeting's chunks, written while the next chunk transcribes
+#[derive(Default)]
+struct ChunkSummaries {
+    summaries: Vec<String>,
+    in_flight: Option<tauri::async_runtime::JoinHandle<Result<String>>>,
+}
+
+impl ChunkSummaries {
+    /// Start summarizing a chunk once the previous one is done, so only one
+    /// request is in flight at a time
+    async fn push(&mut self, settings: &crate::settings::AppSettings, text: String) {
+        self.collect().await;
+        let settings = settings.clone();
+        self.in_flight = Some(tauri::async_runtime::spawn(async move {
+            complete_prompt(&settings, part_summary_prompt(&text), "summarization").await
+        }));
+    }
+
+    /// Wait for the summary in flight. A failed chunk is left out of the
+    /// final summary rather than failing it.
+    async fn collect(&mut self) {
+        let Some(in_flight) = self.in_flight.take() else {
+            return;
+        };
+        match in_flight.await {
+            Ok(Ok(summary)) => {
+                debug!("Summarized meeting chunk {}", self.summaries.len() + 1);
+                self.summaries.push(summary);
+            }
+            Ok(Err(e)) => warn!("Failed to summarize part of the meeting: {}", e),
+            Err(e) => error!("Summarization task failed: {}", e),
+        }
+    }
+
+    /// All chunk summaries, in order
+    async fn finish(mut self) -> Vec<String> {
+        self.collect().await;
+        self.summaries
+    }
+}
+
+/// Group texts, in order, into prompts of at most `max_chars` where possible
+fn pack_texts(texts: &[String], max_chars: usize) -> Vec<String> {
+    let mut groups: Vec<String> = Vec::new();
+    for text in texts {
+        match groups.last_mut() {
+            Some(group) if group.len() + text.len() + 2 <= max_chars => {
+                group.push_str("\n\n");
+                group.push_str(text);
+            }
+            _ => groups.push(text.clone()),
+        }
+    }
+    groups
+}
+
+fn part_summary_prompt(text: &str) -> String {
+    format!(
+        "The following is one part of a longer meeting transcript. Write concise notes on it, \
+keeping the topics discussed, decisions made, action items and who was involved:\n\n{}",
+        text
+    )
+}
+
+fn combine_summaries_prompt(notes: &str) -> String {
+    format!(
+        "The following are notes on consecutive parts of one meeting. Merge them into a single \
+set of concise notes, keeping the topics, decisions, action items and who was involved:\n\n{}",
+        notes
+    )
+}
+
+/// Note added to tasks created from a meeting's action items
+pub fn task_note(started_at: i64) -> String {
+    let started = chrono::DateTime::from_timestamp(started_at, 0)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    format!("Action item from the PaperFlow meeting on {}", started)
+}
+
+/// Meetings with fewer transcribed chunks are not split into topics
+const MIN_TOPIC_CHUNKS: usize = 4;
+
+/// Prompt asking for topic boundaries, given the numbered chunks
+fn topic_prompt(chunks: &[MeetingChunk]) -> String {
+    let mut prompt = String::from(
+        "Split the following meeting transcript into its main topics. Each numbered line is a part of the meeting. \
+Reply with only a JSON array, in order, of objects with a short \"title\" for the topic and the number of the \"line\" where it starts, \
+e.g. [{\"title\": \"Budget review\", \"line\": 0}].\n\n",
+    );
+    for (index, chunk) in chunks.iter().enumerate() {
+        let text = chunk.transcription.as_deref().unwrap_or("").trim();
+        prompt.push_str(&format!("{}: {}\n", index, text));
+    }
+    prompt
+}
+
+/// Topics from the LLM's reply to `topic_prompt`. Lines are mapped back to
+/// chunk times, the first topic is moved to the start of the meeting and
+/// each topic ends where the next begins.
+fn parse_topics(response: &str, chunks: &[MeetingChunk]) -> Result<Vec<MeetingTopic>> {
+    #[derive(Deserialize)]
+    struct TopicStart {
+        title: String,
+        line: usize,
+    }
+
+    let (Some(first), Some(last)) = (chunks.first(), chunks.last()) else {
+        return Ok(Vec::new());
+    };
+    let start = response
+        .find('[')
+        .ok_or_else(|| anyhow::anyhow!("Reply contains no JSON array"))?;
+    let end = response
+        .rfind(']')
+        .filter(|end| *end > start)
+        .ok_or_else(|| anyhow::anyhow!("Reply contains no JSON array"))?;
+    let mut starts: Vec<TopicStart> = serde_json::from_str(&response[start..=end])
+        .map_err(|e| anyhow::anyhow!("Failed to parse topics: {}", e))?;
+
+    starts.retain(|topic| topic.line < chunks.len() && !topic.title.trim().is_empty());
+    starts.sort_by_key(|topic| topic.line);
+    starts.dedup_by_key(|topic| topic.line);
+
+    let mut topics: Vec<MeetingTopic> = starts
+        .iter()
+        .enumerate()
+        .map(|(index, topic)| MeetingTopic {
+            title: topic.title.trim().to_string(),
+            start_ms: if index == 0 {
+                first.start_time_ms
+            } else {
+                chunks[topic.line].start_time_ms
+            },
+            end_ms: starts
+                .get(index + 1)
+                .map_or(last.end_time_ms, |next| chunks[next.line].start_time_ms),
+        })
+        .collect();
+    topics.retain(|topic| topic.end_ms > topic.start_ms);
+    Ok(topics)
+}
+
+/// Send a prompt to the configured post-processing provider. `purpose`
+/// names the feature in errors.
+pub(crate) async fn complete_prompt(
+    settings: &crate::settings::AppSettings,
+    prompt: String,
+    purpose: &str,
+) -> Result<String> {
+    let provider = settings
+        .active_post_process_provider()
+        .ok_or_else(|| anyhow::anyhow!("No post-processing provider configured for {}", purpose))?;
+
+    let api_key = settings
+        .post_process_api_keys
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    let model = settings
+        .post_process_models
+        .get(&provider.id)
+        .cloned()
+        .unwrap_or_default();
+
+    // On-device providers need no key
+    let needs_key = !crate::llm_client::is_on_device(provider);
+    if (needs_key && api_key.is_empty()) || model.is_empty() {
+        return Err(anyhow::anyhow!("LLM not configured for {}", purpose));
+    }
+
+    match crate::llm_client::send_chat_completion(provider, api_key, &model, prompt).await {
+        Ok(Some(content)) => Ok(content),
+        Ok(None) => Err(anyhow::anyhow!("Empty response from LLM")),
+        Err(e) => Err(anyhow::anyhow!("LLM request failed: {}", e)),
+    }
+}
+
 /// Internal state for the meeting manager
 struct MeetingManagerInner {
     state: MeetingState,
@@ -83,6 +472,47 @@ struct MeetingManagerInner {
     recording_start: Option<Instant>,
     last_chunk_time: Option<Instant>,
     pending_audio: Vec<f32>,
+    /// When the current pause began
+    paused_at: Option<Instant>,
+    /// Length of the pauses that already ended
+    paused_total: Duration,
+    /// Audio journal of the recording meeting
+    journal: Option<Arc<MeetingJournal>>,
+}
+
+impl MeetingManagerInner {
+    /// Time spent recording, leaving out pauses
+    fn active_elapsed(&self) -> Option<Duration> {
+        let start = self.recording_start?;
+        let current_pause = self.paused_at.map_or(Duration::ZERO, |at| at.elapsed());
+        Some(
+            start
+                .elapsed()
+                .saturating_sub(self.paused_total + current_pause),
+        )
+    }
+
+    /// Binding of the meeting `meeting_id` if it is recording or paused
+    fn active_binding(&self, meeting_id: &str) -> Result<String> {
+        match &self.state {
+            MeetingState::Recording {
+                meeting_id: current_id,
+                binding_id,
+                ..
+            }
+            | MeetingState::Paused {
+                meeting_id: current_id,
+                binding_id,
+                ..
+            } => {
+                if current_id != meeting_id {
+                    return Err(anyhow::anyhow!("Meeting ID mismatch"));
+                }
+                Ok(binding_id.clone())
+            }
+            _ => Err(anyhow::anyhow!("No meeting in progress")),
+        }
+    }
 }
 
 impl Default for MeetingManagerInner {
@@ -93,6 +523,9 @@ impl Default for MeetingManagerInner {
             recording_start: None,
             last_chunk_time: None,
             pending_audio: Vec::new(),
+            paused_at: None,
+            paused_total: Duration::ZERO,
+            journal: None,
         }
     }
 }
@@ -119,7 +552,7 @@ impl MeetingManager {
                 session.meeting_id
             );
             // Emit recovery event
-            let _ = app_handle.emit("meeting-recovery-available", &session);
+            let _ = MeetingRecoveryAvailable(session).emit(app_handle);
         }
 
         Ok(manager)
@@ -137,10 +570,10 @@ impl MeetingManager {
         inner.current_session.clone()
     }
 
-    /// Get elapsed time in seconds since meeting started
+    /// Get recorded time in seconds since meeting started, without pauses
     pub fn get_elapsed_seconds(&self) -> Option<u64> {
         let inner = self.inner.lock().unwrap();
-        inner.recording_start.map(|start| start.elapsed().as_secs())
+        inner.active_elapsed().map(|elapsed| elapsed.as_secs())
     }
 
     /// Start a new meeting
@@ -175,48 +608,209 @@ impl MeetingManager {
             full_transcript: None,
             summary: None,
             action_items: None,
+            topics: None,
             duration_seconds: None,
         };
 
+        self.begin_recording(&mut inner, session, binding_id, Vec::new())?;
+
+        // Save recovery data
+        self.save_recovery_data(&inner.current_session);
+
+        Ok(meeting_id)
+    }
+
+    /// Start recording `session`, continuing after the `recorded` audio
+    fn begin_recording(
+        &self,
+        inner: &mut MeetingManagerInner,
+        session: MeetingSession,
+        binding_id: &str,
+        recorded: Vec<f32>,
+    ) -> Result<()> {
+        let meeting_id = session.meeting_id.clone();
+        let journal = self.open_journal(&meeting_id, !recorded.is_empty());
+
         // Start audio recording
         let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
         if !rm.start_meeting_recording(binding_id) {
+            rm.set_audio_tap(None);
             return Err(anyhow::anyhow!("Failed to start audio recording"));
         }
 
+        // Time already recorded counts towards the elapsed time
+        let recorded_time = Duration::from_secs_f64(recorded.len() as f64 / 16000.0);
+        let now = Instant::now();
+
         // Update state
         inner.state = MeetingState::Recording {
             meeting_id: meeting_id.clone(),
-            started_at,
-            chunk_count: 0,
+            started_at: session.started_at,
+            chunk_count: session.chunks.len() as u32,
             binding_id: binding_id.to_string(),
         };
         inner.current_session = Some(session);
-        inner.recording_start = Some(Instant::now());
-        inner.last_chunk_time = Some(Instant::now());
-        inner.pending_audio.clear();
+        inner.recording_start = Some(now.checked_sub(recorded_time).unwrap_or(now));
+        inner.last_chunk_time = Some(now);
+        inner.pending_audio = recorded;
+        inner.paused_at = None;
+        inner.paused_total = Duration::ZERO;
+        inner.journal = journal;
 
         // Emit state change event
-        let _ = self.app_handle.emit(
-            "meeting-state-changed",
-            MeetingStateEvent {
-                state: inner.state.clone(),
-                elapsed_seconds: Some(0),
-                chunk_count: Some(0),
-            },
-        );
-
-        // Save recovery data
-        self.save_recovery_data(&inner.current_session);
+        let _ = MeetingStateEvent {
+            state: inner.state.clone(),
+            elapsed_seconds: inner.active_elapsed().map(|d| d.as_secs()),
+            chunk_count: inner
+                .current_session
+                .as_ref()
+                .map(|s| s.chunks.len() as u32),
+        }
+        .emit(&self.app_handle);
 
         // Start heartbeat timer for UI updates (elapsed time display)
         let manager_clone = self.clone();
-        let meeting_id_clone = meeting_id.clone();
         std::thread::spawn(move || {
-            manager_clone.heartbeat_loop(meeting_id_clone);
+            manager_clone.heartbeat_loop(meeting_id);
         });
 
-        Ok(meeting_id)
+        Ok(())
+    }
+
+    /// Journal the recorded audio of a meeting to disk. Without a journal
+    /// the meeting still records, it just can't be recovered after a crash.
+    fn open_journal(&self, meeting_id: &str, append: bool) -> Option<Arc<MeetingJournal>> {
+        let hm = self.app_handle.try_state::<Arc<HistoryManager>>()?;
+        let journal = match hm.open_meeting_journal(meeting_id, append) {
+            Ok(journal) => Arc::new(journal),
+            Err(e) => {
+                warn!("Failed to open meeting journal: {}", e);
+                return None;
+            }
+        };
+        self.install_journal_tap(&journal);
+        Some(journal)
+    }
+
+    /// Send what the microphone records to `journal`
+    fn install_journal_tap(&self, journal: &Arc<MeetingJournal>) {
+        let tap_journal = Arc::clone(journal);
+        self.app_handle
+            .state::<Arc<AudioRecordingManager>>()
+            .set_audio_tap(Some(Arc::new(move |samples| tap_journal.append(samples))));
+    }
+
+    /// Stop journaling, writing out what is still buffered
+    fn close_journal(&self, inner: &mut MeetingManagerInner) {
+        let Some(journal) = inner.journal.take() else {
+            return;
+        };
+        self.app_handle
+            .state::<Arc<AudioRecordingManager>>()
+            .set_audio_tap(None);
+        if let Err(e) = journal.flush() {
+            warn!("Failed to write meeting journal: {}", e);
+        }
+    }
+
+    /// Pause the current meeting. The audio so far is kept and the
+    /// microphone stops recording until the meeting is resumed.
+    pub fn pause_meeting(&self, meeting_id: &str) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let MeetingState::Recording {
+            meeting_id: current_id,
+            started_at,
+            chunk_count,
+            binding_id,
+        } = inner.state.clone()
+        else {
+            return Err(anyhow::anyhow!("No recording meeting to pause"));
+        };
+        if current_id != meeting_id {
+            return Err(anyhow::anyhow!("Meeting ID mismatch"));
+        }
+
+        info!("Pausing meeting: {}", meeting_id);
+
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        if let Some(samples) = rm.stop_recording(&binding_id) {
+            inner.pending_audio.extend(samples);
+        }
+        // A dictation while paused must not end up in the meeting's journal
+        rm.set_audio_tap(None);
+        if let Some(journal) = &inner.journal {
+            if let Err(e) = journal.flush() {
+                warn!("Failed to write meeting journal: {}", e);
+            }
+        }
+
+        inner.state = MeetingState::Paused {
+            meeting_id: current_id,
+            started_at,
+            chunk_count,
+            binding_id,
+        };
+        inner.paused_at = Some(Instant::now());
+
+        let _ = MeetingStateEvent {
+            state: inner.state.clone(),
+            elapsed_seconds: inner.active_elapsed().map(|d| d.as_secs()),
+            chunk_count: Some(chunk_count),
+        }
+        .emit(&self.app_handle);
+
+        Ok(())
+    }
+
+    /// Resume a paused meeting
+    pub fn resume_meeting(&self, meeting_id: &str) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let MeetingState::Paused {
+            meeting_id: current_id,
+            started_at,
+            chunk_count,
+            binding_id,
+        } = inner.state.clone()
+        else {
+            return Err(anyhow::anyhow!("No paused meeting to resume"));
+        };
+        if current_id != meeting_id {
+            return Err(anyhow::anyhow!("Meeting ID mismatch"));
+        }
+
+        info!("Resuming meeting: {}", meeting_id);
+
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        if let Some(journal) = &inner.journal {
+            self.install_journal_tap(journal);
+        }
+        if !rm.start_meeting_recording(&binding_id) {
+            rm.set_audio_tap(None);
+            return Err(anyhow::anyhow!(
+                "Failed to restart audio recording, is a dictation running?"
+            ));
+        }
+
+        if let Some(paused_at) = inner.paused_at.take() {
+            inner.paused_total += paused_at.elapsed();
+        }
+        inner.state = MeetingState::Recording {
+            meeting_id: current_id,
+            started_at,
+            chunk_count,
+            binding_id,
+        };
+
+        let _ = MeetingStateEvent {
+            state: inner.state.clone(),
+            elapsed_seconds: inner.active_elapsed().map(|d| d.as_secs()),
+            chunk_count: Some(chunk_count),
+        }
+        .emit(&self.app_handle);
+
+        Ok(())
     }
 
     /// Stop the current meeting and process results
@@ -224,23 +818,12 @@ impl MeetingManager {
         let mut inner = self.inner.lock().unwrap();
 
         // Verify meeting ID matches and extract binding_id
-        let binding_id = match &inner.state {
-            MeetingState::Recording {
-                meeting_id: current_id,
-                binding_id,
-                ..
-            } => {
-                if current_id != meeting_id {
-                    return Err(anyhow::anyhow!("Meeting ID mismatch"));
-                }
-                binding_id.clone()
-            }
-            _ => return Err(anyhow::anyhow!("No meeting in progress")),
-        };
+        let binding_id = inner.active_binding(meeting_id)?;
 
         info!("Stopping meeting: {}", meeting_id);
 
-        // Stop audio recording using the original binding_id
+        // Stop audio recording using the original binding_id; a paused
+        // meeting has nothing left to stop
         let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
         let final_samples = rm.stop_recording(&binding_id);
 
@@ -248,14 +831,15 @@ impl MeetingManager {
         if let Some(samples) = final_samples {
             inner.pending_audio.extend(samples);
         }
+        self.close_journal(&mut inner);
 
         // Update state to processing
         inner.state = MeetingState::Processing {
             meeting_id: meeting_id.to_string(),
         };
 
-        // Calculate duration
-        let duration = inner.recording_start.map(|s| s.elapsed().as_secs() as i64);
+        // Calculate duration, leaving out pauses
+        let duration = inner.active_elapsed().map(|d| d.as_secs() as i64);
         if let Some(ref mut session) = inner.current_session {
             session.ended_at = Some(
                 SystemTime::now()
@@ -267,17 +851,15 @@ impl MeetingManager {
         }
 
         // Emit state change
-        let _ = self.app_handle.emit(
-            "meeting-state-changed",
-            MeetingStateEvent {
-                state: inner.state.clone(),
-                elapsed_seconds: duration.map(|d| d as u64),
-                chunk_count: inner
-                    .current_session
-                    .as_ref()
-                    .map(|s| s.chunks.len() as u32),
-            },
-        );
+        let _ = MeetingStateEvent {
+            state: inner.state.clone(),
+            elapsed_seconds: duration.map(|d| d as u64),
+            chunk_count: inner
+                .current_session
+                .as_ref()
+                .map(|s| s.chunks.len() as u32),
+        }
+        .emit(&self.app_handle);
 
         // Process remaining audio
         let pending_audio = std::mem::take(&mut inner.pending_audio);
@@ -301,25 +883,14 @@ impl MeetingManager {
         let mut inner = self.inner.lock().unwrap();
 
         // Verify meeting ID matches and extract binding_id
-        let binding_id = match &inner.state {
-            MeetingState::Recording {
-                meeting_id: current_id,
-                binding_id,
-                ..
-            } => {
-                if current_id != meeting_id {
-                    return Err(anyhow::anyhow!("Meeting ID mismatch"));
-                }
-                binding_id.clone()
-            }
-            _ => return Err(anyhow::anyhow!("No meeting in progress")),
-        };
+        let binding_id = inner.active_binding(meeting_id)?;
 
         info!("Cancelling meeting: {}", meeting_id);
 
         // Stop audio recording without processing using the original binding_id
         let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
         let _ = rm.stop_recording(&binding_id);
+        self.close_journal(&mut inner);
 
         // Reset state
         inner.state = MeetingState::Idle;
@@ -327,19 +898,18 @@ impl MeetingManager {
         inner.recording_start = None;
         inner.last_chunk_time = None;
         inner.pending_audio.clear();
+        inner.paused_at = None;
 
         // Clear recovery data
         self.clear_recovery_data();
 
         // Emit state change
-        let _ = self.app_handle.emit(
-            "meeting-state-changed",
-            MeetingStateEvent {
-                state: MeetingState::Idle,
-                elapsed_seconds: None,
-                chunk_count: None,
-            },
-        );
+        let _ = MeetingStateEvent {
+            state: MeetingState::Idle,
+            elapsed_seconds: None,
+            chunk_count: None,
+        }
+        .emit(&self.app_handle);
 
         Ok(())
     }
@@ -374,6 +944,100 @@ impl MeetingManager {
         }
     }
 
+    /// The meeting interrupted by a crash, if there is one and no meeting is
+    /// in progress
+    pub fn get_recoverable_meeting(&self) -> Option<MeetingSession> {
+        if !matches!(self.get_meeting_state(), MeetingState::Idle) {
+            return None;
+        }
+        self.recover_from_crash().ok().flatten()
+    }
+
+    /// Load the interrupted meeting with the audio journaled before the crash
+    fn load_recovered_meeting(&self) -> Result<(MeetingSession, Vec<f32>)> {
+        let session = self
+            .recover_from_crash()?
+            .ok_or_else(|| anyhow::anyhow!("No interrupted meeting to recover"))?;
+        let hm = self
+            .app_handle
+            .try_state::<Arc<HistoryManager>>()
+            .ok_or_else(|| anyhow::anyhow!("History manager not initialized"))?;
+        let audio = hm.read_meeting_journal(&session.meeting_id)?;
+        Ok((session, audio))
+    }
+
+    /// Continue recording the meeting interrupted by a crash. The time the
+    /// app was down is left out, like a pause.
+    pub fn resume_recovered_meeting(&self, binding_id: &str) -> Result<String> {
+        let mut inner = self.inner.lock().unwrap();
+        if !matches!(inner.state, MeetingState::Idle) {
+            return Err(anyhow::anyhow!("Meeting already in progress"));
+        }
+
+        let (session, audio) = self.load_recovered_meeting()?;
+        let meeting_id = session.meeting_id.clone();
+        info!(
+            "Resuming interrupted meeting {} after {:.1}s of journaled audio",
+            meeting_id,
+            audio.len() as f64 / 16000.0
+        );
+
+        self.begin_recording(&mut inner, session, binding_id, audio)?;
+        Ok(meeting_id)
+    }
+
+    /// Transcribe and summarize the audio the interrupted meeting recorded
+    /// before the crash
+    pub fn finalize_recovered_meeting(&self) -> Result<String> {
+        let mut inner = self.inner.lock().unwrap();
+        if !matches!(inner.state, MeetingState::Idle) {
+            return Err(anyhow::anyhow!("Meeting already in progress"));
+        }
+
+        let (mut session, audio) = self.load_recovered_meeting()?;
+        let meeting_id = session.meeting_id.clone();
+        if audio.is_empty() {
+            self.clear_recovery_data();
+            return Err(anyhow::anyhow!(
+                "No audio was saved for the interrupted meeting"
+            ));
+        }
+
+        info!("Finalizing interrupted meeting {}", meeting_id);
+        let duration = (audio.len() / 16000) as i64;
+        session.duration_seconds = Some(duration);
+        session.ended_at = Some(session.started_at + duration);
+
+        inner.state = MeetingState::Processing {
+            meeting_id: meeting_id.clone(),
+        };
+        inner.current_session = Some(session.clone());
+        let _ = MeetingStateEvent {
+            state: inner.state.clone(),
+            elapsed_seconds: Some(duration as u64),
+            chunk_count: Some(session.chunks.len() as u32),
+        }
+        .emit(&self.app_handle);
+        drop(inner);
+
+        let manager_clone = self.clone();
+        let meeting_id_clone = meeting_id.clone();
+        tauri::async_runtime::spawn(async move {
+            manager_clone
+                .finalize_meeting(meeting_id_clone, audio, Some(session))
+                .await;
+        });
+
+        Ok(meeting_id)
+    }
+
+    /// Throw away the meeting interrupted by a crash
+    pub fn discard_recovered_meeting(&self) {
+        if matches!(self.get_meeting_state(), MeetingState::Idle) {
+            self.clear_recovery_data();
+        }
+    }
+
     /// Emit periodic heartbeat events for UI updates (elapsed time display)
     fn heartbeat_loop(&self, meeting_id: String) {
         loop {
@@ -381,12 +1045,18 @@ impl MeetingManager {
 
             let inner = self.inner.lock().unwrap();
 
-            // Check if still recording this meeting
+            // Check if still recording this meeting; paused meetings keep
+            // their heartbeat with the elapsed time standing still
             match &inner.state {
                 MeetingState::Recording {
                     meeting_id: current_id,
                     chunk_count,
                     ..
+                }
+                | MeetingState::Paused {
+                    meeting_id: current_id,
+                    chunk_count,
+                    ..
                 } => {
                     if current_id != &meeting_id {
                         debug!("Meeting ID changed, stopping heartbeat loop");
@@ -394,15 +1064,22 @@ impl MeetingManager {
                     }
 
                     // Emit heartbeat with elapsed time
-                    let elapsed = inner.recording_start.map(|s| s.elapsed().as_secs());
-                    let _ = self.app_handle.emit(
-                        "meeting-heartbeat",
-                        MeetingStateEvent {
-                            state: inner.state.clone(),
-                            elapsed_seconds: elapsed,
-                            chunk_count: Some(*chunk_count),
-                        },
-                    );
+                    let elapsed = inner.active_elapsed().map(|d| d.as_secs());
+                    let _ = MeetingHeartbeat(MeetingStateEvent {
+                        state: inner.state.clone(),
+                        elapsed_seconds: elapsed,
+                        chunk_count: Some(*chunk_count),
+                    })
+                    .emit(&self.app_handle);
+
+                    // Write the journal without holding up the meeting controls
+                    let journal = inner.journal.clone();
+                    drop(inner);
+                    if let Some(journal) = journal {
+                        if let Err(e) = journal.flush() {
+                            warn!("Failed to write meeting journal: {}", e);
+                        }
+                    }
                 }
                 _ => {
                     debug!("Meeting stopped, exiting heartbeat loop");
@@ -420,6 +1097,7 @@ impl MeetingManager {
             inner.current_session = None;
             inner.recording_start = None;
             inner.last_chunk_time = None;
+            inner.paused_at = None;
         }
 
         // Clear recovery data
@@ -430,14 +1108,12 @@ impl MeetingManager {
         change_tray_icon(&self.app_handle, TrayIconState::Idle);
 
         // Emit state change
-        let _ = self.app_handle.emit(
-            "meeting-state-changed",
-            MeetingStateEvent {
-                state: MeetingState::Idle,
-                elapsed_seconds: None,
-                chunk_count: None,
-            },
-        );
+        let _ = MeetingStateEvent {
+            state: MeetingState::Idle,
+            elapsed_seconds: None,
+            chunk_count: None,
+        }
+        .emit(&self.app_handle);
     }
 
     /// Finalize the meeting with summarization and action items
@@ -463,11 +1139,18 @@ impl MeetingManager {
             return;
         }
 
+        let final_audio = Arc::new(final_audio);
+        let speaker_segments = self.diarize_meeting(&final_audio).await;
+
         // Split audio into chunks and transcribe each
         let tm = self.app_handle.state::<Arc<TranscriptionManager>>();
         let total_samples = final_audio.len();
-        let mut offset: usize = 0;
-        let mut chunk_id: u32 = 0;
+        let plan = plan_chunks(total_samples, chunk_duration_samples, &speaker_segments);
+        // Speakers come from the meeting-wide pass, not from each chunk
+        let overrides = TranscriptionOverrides {
+            diarization_enabled: Some(false),
+            ..Default::default()
+        };
 
         info!(
             "Processing {} samples ({:.1}s) in chunks of {}s",
@@ -476,8 +1159,23 @@ impl MeetingManager {
             settings.meeting_chunk_duration_seconds
         );
 
-        while offset < total_samples {
-            let end = (offset + chunk_duration_samples).min(total_samples);
+        let chunk_total = plan.len() as u32;
+        let mut chunk_summaries = ChunkSummaries::default();
+
+        for (chunk_id, chunk_plan) in plan.into_iter().enumerate() {
+            let chunk_id = chunk_id as u32;
+            let (offset, end) = (chunk_plan.start, chunk_plan.end);
+            let remaining_seconds = (total_samples - offset) as f64 / 16000.0;
+            let _ = MeetingFinalizationProgressEvent {
+                meeting_id: meeting_id.clone(),
+                chunks_done: chunk_id,
+                chunk_total,
+                estimated_completion_at: tm
+                    .estimate_processing_seconds(None, remaining_seconds)
+                    .map(|secs| chrono::Utc::now().timestamp() + secs.ceil() as i64),
+            }
+            .emit(&self.app_handle);
+
             let chunk_audio = final_audio[offset..end].to_vec();
             let chunk_samples = chunk_audio.len();
 
@@ -487,8 +1185,11 @@ impl MeetingManager {
 
             // Transcribe this chunk using spawn_blocking to avoid blocking async runtime
             let tm_clone = Arc::clone(&*tm);
-            let transcription_result =
-                tokio::task::spawn_blocking(move || tm_clone.transcribe(chunk_audio)).await;
+            let chunk_overrides = overrides.clone();
+            let transcription_result = tokio::task::spawn_blocking(move || {
+                tm_clone.transcribe_with_overrides(chunk_audio, &chunk_overrides)
+            })
+            .await;
 
             match transcription_result {
                 Ok(Ok(transcription)) => {
@@ -505,20 +1206,31 @@ impl MeetingManager {
                             chunk_id,
                             start_time_ms,
                             end_time_ms,
-                            audio_path: None,
+                            audio_path: self.save_chunk_audio(
+                                &meeting_id,
+                                chunk_id,
+                                &final_audio[offset..end],
+                            ),
                             transcription: Some(transcription.clone()),
+                            speaker: chunk_plan.speaker,
                         };
+
+                        if settings.meeting_auto_summarize {
+                            let line = match &chunk.speaker {
+                                Some(speaker) => format!("[{}]: {}", speaker, transcription),
+                                None => transcription.clone(),
+                            };
+                            chunk_summaries.push(&settings, line).await;
+                        }
                         session.chunks.push(chunk);
 
                         // Emit chunk event
-                        let _ = self.app_handle.emit(
-                            "meeting-chunk-transcribed",
-                            MeetingChunkEvent {
-                                meeting_id: meeting_id.clone(),
-                                chunk_id,
-                                transcription,
-                            },
-                        );
+                        let _ = MeetingChunkEvent {
+                            meeting_id: meeting_id.clone(),
+                            chunk_id,
+                            transcription,
+                        }
+                        .emit(&self.app_handle);
                     }
                 }
                 Ok(Err(e)) => {
@@ -531,19 +1243,10 @@ impl MeetingManager {
                     error!("Transcription task panicked for chunk {}: {}", chunk_id, e);
                 }
             }
-
-            offset = end;
-            chunk_id += 1;
         }
 
         // Combine all transcriptions
-        let full_transcript: String = session
-            .chunks
-            .iter()
-            .filter_map(|c| c.transcription.as_ref())
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(" ");
+        let full_transcript = render_transcript(&session.chunks, &HashMap::new());
 
         session.full_transcript = Some(full_transcript.clone());
 
@@ -558,7 +1261,11 @@ impl MeetingManager {
 
         // Generate summary if enabled
         if settings.meeting_auto_summarize && !full_transcript.is_empty() {
-            match self.generate_summary(&full_transcript, &settings).await {
+            let summaries = chunk_summaries.finish().await;
+            match self
+                .generate_summary(&full_transcript, summaries, &settings)
+                .await
+            {
                 Ok(summary) => {
                     session.summary = Some(summary);
                     debug!("Generated meeting summary");
@@ -582,6 +1289,19 @@ impl MeetingManager {
             }
         }
 
+        // Split into topics if enabled
+        if settings.meeting_segment_topics && session.chunks.len() >= MIN_TOPIC_CHUNKS {
+            match self.segment_topics(&session.chunks, &settings).await {
+                Ok(topics) => {
+                    debug!("Split meeting into {} topics", topics.len());
+                    session.topics = Some(topics);
+                }
+                Err(e) => {
+                    warn!("Failed to split meeting into topics: {}", e);
+                }
+            }
+        }
+
         // Handle case where no chunks were successfully transcribed
         if full_transcript.is_empty() {
             warn!(
@@ -601,7 +1321,9 @@ impl MeetingManager {
         self.save_meeting_to_history(&session);
 
         // Emit completion event
-        let _ = self.app_handle.emit("meeting-completed", &session);
+        let _ = MeetingCompleted(session.clone()).emit(&self.app_handle);
+        self.send_webhook(&session, &settings);
+        self.push_action_items(&session, &settings);
 
         // Copy transcript to clipboard and paste it, then cleanup UI
         let app_handle = self.app_handle.clone();
@@ -622,14 +1344,12 @@ impl MeetingManager {
             change_tray_icon(&app_handle_for_closure, TrayIconState::Idle);
 
             // Emit final state change on main thread
-            let _ = app_handle_for_closure.emit(
-                "meeting-state-changed",
-                MeetingStateEvent {
-                    state: MeetingState::Idle,
-                    elapsed_seconds: session_duration.map(|d| d as u64),
-                    chunk_count: Some(chunk_count),
-                },
-            );
+            let _ = MeetingStateEvent {
+                state: MeetingState::Idle,
+                elapsed_seconds: session_duration.map(|d| d as u64),
+                chunk_count: Some(chunk_count),
+            }
+            .emit(&app_handle_for_closure);
         });
 
         // Update internal state (can happen before paste completes, but UI won't change until main thread runs)
@@ -642,41 +1362,194 @@ impl MeetingManager {
         }
     }
 
-    /// Generate a summary using the configured LLM
+    /// Post the completed meeting to the configured webhook in the background
+    fn send_webhook(&self, session: &MeetingSession, settings: &crate::settings::AppSettings) {
+        if settings.meeting_webhook_url.is_empty() {
+            return;
+        }
+        let url = settings.meeting_webhook_url.clone();
+        let secret = settings.meeting_webhook_secret.clone();
+        let session = session.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::webhook::send_meeting_completed(&url, &secret, &session).await {
+                warn!("Meeting {} webhook failed: {}", session.meeting_id, e);
+            }
+        });
+    }
+
+    /// Send the action items to the configured task managers in the background
+    fn push_action_items(&self, session: &MeetingSession, settings: &crate::settings::AppSettings) {
+        let Some(items) = session
+            .action_items
+            .clone()
+            .filter(|items| !items.is_empty())
+        else {
+            return;
+        };
+        if settings.meeting_task_destinations.is_empty() {
+            return;
+        }
+        let destinations = settings.meeting_task_destinations.clone();
+        let batch = crate::task_providers::TaskBatch {
+            titles: items,
+            note: task_note(session.started_at),
+        };
+        tauri::async_runtime::spawn(async move {
+            crate::task_providers::push_to_all(&destinations, &batch).await;
+        });
+    }
+
+    /// Keep a chunk's audio next to the other recordings, if enabled
+    fn save_chunk_audio(&self, meeting_id: &str, chunk_id: u32, samples: &[f32]) -> Option<String> {
+        if !get_settings(&self.app_handle).meeting_save_audio {
+            return None;
+        }
+        let hm = self.app_handle.try_state::<Arc<HistoryManager>>()?;
+        match hm.save_meeting_chunk_audio(meeting_id, chunk_id, samples) {
+            Ok(file_name) => Some(file_name),
+            Err(e) => {
+                warn!("Failed to save audio of meeting chunk {}: {}", chunk_id, e);
+                None
+            }
+        }
+    }
+
+    /// Speaker turns across the whole recording, if diarization is enabled
+    /// and its models are available
+    async fn diarize_meeting(&self, audio: &Arc<Vec<f32>>) -> Vec<DiarizedSegment> {
+        if !get_settings(&self.app_handle).diarization_enabled {
+            return Vec::new();
+        }
+        let Some(dm) = self.app_handle.try_state::<Arc<DiarizationManager>>() else {
+            return Vec::new();
+        };
+        if !dm.is_available() {
+            warn!("Diarization is enabled but its models are not available");
+            return Vec::new();
+        }
+
+        let dm = Arc::clone(&*dm);
+        let audio = Arc::clone(audio);
+        match tokio::task::spawn_blocking(move || dm.diarize(&audio)).await {
+            Ok(Ok(segments)) => {
+                info!("Meeting diarization found {} segments", segments.len());
+                segments
+            }
+            Ok(Err(e)) => {
+                warn!("Meeting diarization failed: {}", e);
+                Vec::new()
+            }
+            Err(e) => {
+                error!("Diarization task panicked: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Name a speaker of a saved meeting, or clear the name with an empty
+    /// string. The transcript, summary and action items are updated to match.
+    pub fn rename_speaker(&self, meeting_id: &str, label: &str, name: &str) -> Result<()> {
+        let hm = self
+            .app_handle
+            .try_state::<Arc<HistoryManager>>()
+            .ok_or_else(|| anyhow::anyhow!("History manager not initialized"))?;
+        let entry = hm
+            .get_meeting_entries()?
+            .into_iter()
+            .find(|entry| entry.meeting_id == meeting_id)
+            .ok_or_else(|| anyhow::anyhow!("Meeting not found: {}", meeting_id))?;
+        let chunks = hm.get_meeting_chunks(meeting_id)?;
+        if !chunks
+            .iter()
+            .any(|chunk| chunk.speaker.as_deref() == Some(label))
+        {
+            return Err(anyhow::anyhow!(
+                "Meeting {} has no speaker '{}'",
+                meeting_id,
+                label
+            ));
+        }
+
+        let mut names = hm.get_meeting_speaker_names(meeting_id)?;
+        let old_display = speaker_display(label, &names).to_string();
+        let name = name.trim();
+        if name.is_empty() {
+            names.remove(label);
+        } else {
+            names.insert(label.to_string(), name.to_string());
+        }
+        let new_display = speaker_display(label, &names).to_string();
+
+        let transcript = render_transcript(&chunks, &names);
+        let summary = entry
+            .summary
+            .map(|summary| replace_speaker_name(&summary, &old_display, &new_display));
+        let action_items_json = entry
+            .action_items
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| replace_speaker_name(item, &old_display, &new_display))
+                    .collect::<Vec<_>>()
+            })
+            .and_then(|items| serde_json::to_string(&items).ok());
+
+        hm.save_meeting_speaker_name(
+            meeting_id,
+            label,
+            (!name.is_empty()).then_some(name),
+            &transcript,
+            summary.as_deref(),
+            action_items_json.as_deref(),
+        )
+    }
+
+    /// Generate a summary using the configured LLM. The chunk summaries are
+    /// combined in rounds until they fit one prompt; the summary prompt is
+    /// then run on those notes. If no chunk could be summarized, the
+    /// transcript is summarized directly.
     async fn generate_summary(
         &self,
         transcript: &str,
+        mut part_summaries: Vec<String>,
         settings: &crate::settings::AppSettings,
     ) -> Result<String> {
-        let prompt = settings
-            .meeting_summary_prompt
-            .replace("${transcript}", transcript);
-
-        let provider = settings.active_post_process_provider().ok_or_else(|| {
-            anyhow::anyhow!("No post-processing provider configured for summarization")
-        })?;
-
-        let api_key = settings
-            .post_process_api_keys
-            .get(&provider.id)
-            .cloned()
-            .unwrap_or_default();
-
-        let model = settings
-            .post_process_models
-            .get(&provider.id)
-            .cloned()
-            .unwrap_or_default();
-
-        if api_key.is_empty() || model.is_empty() {
-            return Err(anyhow::anyhow!("LLM not configured for summarization"));
+        if part_summaries.is_empty() {
+            let prompt = settings
+                .meeting_summary_prompt
+                .replace("${transcript}", transcript);
+            return complete_prompt(settings, prompt, "summarization").await;
         }
 
-        match crate::llm_client::send_chat_completion(provider, api_key, &model, prompt).await {
-            Ok(Some(content)) => Ok(content),
-            Ok(None) => Err(anyhow::anyhow!("Empty response from LLM")),
-            Err(e) => Err(anyhow::anyhow!("LLM request failed: {}", e)),
+        while part_summaries.iter().map(String::len).sum::<usize>() > SUMMARY_PART_CHARS {
+            let groups = pack_texts(&part_summaries, SUMMARY_PART_CHARS);
+            if groups.len() == part_summaries.len() {
+                // Every summary fills a prompt on its own; combining won't shrink them
+                break;
+            }
+            let mut combined = Vec::with_capacity(groups.len());
+            for group in groups {
+                combined.push(
+                    complete_prompt(settings, combine_summaries_prompt(&group), "summarization")
+                        .await?,
+                );
+            }
+            debug!(
+                "Combined {} part summaries into {}",
+                part_summaries.len(),
+                combined.len()
+            );
+            part_summaries = combined;
         }
+
+        let notes = format!(
+            "Notes on each part of the meeting, in order:\n\n{}",
+            part_summaries.join("\n\n")
+        );
+        let prompt = settings
+            .meeting_summary_prompt
+            .replace("${transcript}", &notes);
+        complete_prompt(settings, prompt, "summarization").await
     }
 
     /// Extract action items using the configured LLM
@@ -688,49 +1561,34 @@ impl MeetingManager {
         let prompt = settings
             .meeting_action_items_prompt
             .replace("${transcript}", transcript);
-
-        let provider = settings.active_post_process_provider().ok_or_else(|| {
-            anyhow::anyhow!("No post-processing provider configured for action items")
-        })?;
-
-        let api_key = settings
-            .post_process_api_keys
-            .get(&provider.id)
-            .cloned()
-            .unwrap_or_default();
-
-        let model = settings
-            .post_process_models
-            .get(&provider.id)
-            .cloned()
-            .unwrap_or_default();
-
-        if api_key.is_empty() || model.is_empty() {
-            return Err(anyhow::anyhow!("LLM not configured for action items"));
-        }
-
-        match crate::llm_client::send_chat_completion(provider, api_key, &model, prompt).await {
-            Ok(Some(content)) => {
-                // Parse the response as a list of action items
-                let items: Vec<String> = content
-                    .lines()
-                    .filter(|line| !line.trim().is_empty())
-                    .map(|line| {
-                        // Remove common list prefixes
-                        line.trim()
-                            .trim_start_matches(|c: char| {
-                                c.is_numeric() || c == '.' || c == '-' || c == '*'
-                            })
-                            .trim()
-                            .to_string()
+        let content = complete_prompt(settings, prompt, "action items").await?;
+
+        // Parse the response as a list of action items
+        let items: Vec<String> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                // Remove common list prefixes
+                line.trim()
+                    .trim_start_matches(|c: char| {
+                        c.is_numeric() || c == '.' || c == '-' || c == '*'
                     })
-                    .filter(|s| !s.is_empty())
-                    .collect();
-                Ok(items)
-            }
-            Ok(None) => Err(anyhow::anyhow!("Empty response from LLM")),
-            Err(e) => Err(anyhow::anyhow!("LLM request failed: {}", e)),
-        }
+                    .trim()
+                    .to_string()
+            })
+            .filter(|s| !s.is_empty())
+            .collect();
+        Ok(items)
+    }
+
+    /// Split the meeting into topics using the configured LLM
+    async fn segment_topics(
+        &self,
+        chunks: &[MeetingChunk],
+        settings: &crate::settings::AppSettings,
+    ) -> Result<Vec<MeetingTopic>> {
+        let content = complete_prompt(settings, topic_prompt(chunks), "topic segmentation").await?;
+        parse_topics(&content, chunks)
     }
 
     /// Save recovery data to database
@@ -793,6 +1651,10 @@ impl MeetingManager {
             .action_items
             .as_ref()
             .and_then(|items| serde_json::to_string(items).ok());
+        let topics_json = session
+            .topics
+            .as_ref()
+            .and_then(|topics| serde_json::to_string(topics).ok());
 
         if let Err(e) = hm.save_meeting(
             &session.meeting_id,
@@ -802,6 +1664,7 @@ impl MeetingManager {
             session.full_transcript.as_deref().unwrap_or(""),
             session.summary.as_deref(),
             action_items_json.as_deref(),
+            topics_json.as_deref(),
             session.chunks.len() as u32,
         ) {
             error!("Failed to save meeting to history: {}", e);
@@ -817,6 +1680,7 @@ impl MeetingManager {
                     chunk.end_time_ms,
                     chunk.transcription.as_deref().unwrap_or(""),
                     chunk.audio_path.as_deref(),
+                    chunk.speaker.as_deref(),
                 ) {
                     warn!("Failed to save meeting chunk {}: {}", chunk.chunk_id, e);
                 }
@@ -824,3 +1688,170 @@ impl MeetingManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64, speaker: &str) -> DiarizedSegment {
+        DiarizedSegment {
+            start_ms,
+            end_ms,
+            speaker: speaker.to_string(),
+        }
+    }
+
+    fn chunk(speaker: Option<&str>, text: &str) -> MeetingChunk {
+        MeetingChunk {
+            chunk_id: 0,
+            start_time_ms: 0,
+            end_time_ms: 0,
+            audio_path: None,
+            transcription: Some(text.to_string()),
+            speaker: speaker.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn chunks_follow_speaker_turns() {
+        let segments = [
+            segment(500, 2_000, "Speaker 3"),
+            segment(2_000, 3_000, "Speaker 3"),
+            segment(3_500, 9_000, "Speaker 0"),
+            segment(9_500, 10_000, "Speaker 3"),
+        ];
+        let plan = plan_chunks(12 * 16_000, 4 * 16_000, &segments);
+        let spans: Vec<(usize, usize, Option<&str>)> = plan
+            .iter()
+            .map(|c| (c.start / 16, c.end / 16, c.speaker.as_deref()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 3_500, Some("Speaker 1")),
+                (3_500, 7_500, Some("Speaker 2")),
+                (7_500, 9_500, Some("Speaker 2")),
+                (9_500, 12_000, Some("Speaker 1")),
+            ]
+        );
+
+        let fixed = plan_chunks(10, 4, &[]);
+        let bounds: Vec<(usize, usize)> = fixed.iter().map(|c| (c.start, c.end)).collect();
+        assert_eq!(bounds, vec![(0, 4), (4, 8), (8, 10)]);
+        assert!(fixed.iter().all(|c| c.speaker.is_none()));
+    }
+
+    #[test]
+    fn short_turns_merge_into_their_neighbours() {
+        let segments = [
+            segment(0, 1_000, "B"),
+            segment(1_000, 6_000, "A"),
+            segment(6_000, 7_000, "B"),
+            segment(7_000, 12_000, "A"),
+            segment(12_000, 13_500, "C"),
+            segment(13_500, 20_000, "B"),
+        ];
+        let plan = plan_chunks(20 * 16_000, 20 * 16_000, &segments);
+        let spans: Vec<(usize, usize, Option<&str>)> = plan
+            .iter()
+            .map(|c| (c.start / 16, c.end / 16, c.speaker.as_deref()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 13_500, Some("Speaker 1")),
+                (13_500, 20_000, Some("Speaker 2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn transcripts_use_speaker_names() {
+        let chunks = [
+            chunk(Some("Speaker 1"), "Shall we start?"),
+            chunk(Some("Speaker 1"), "First item."),
+            chunk(Some("Speaker 2"), "Sure."),
+            chunk(Some("Speaker 2"), "  "),
+        ];
+        let names = HashMap::from([("Speaker 1".to_string(), "Alice".to_string())]);
+        assert_eq!(
+            render_transcript(&chunks, &names),
+            "[Alice]: Shall we start? First item.\n[Speaker 2]: Sure."
+        );
+
+        let plain = [chunk(None, "one"), chunk(None, "two")];
+        assert_eq!(render_transcript(&plain, &HashMap::new()), "one two");
+    }
+
+    #[test]
+    fn elapsed_time_leaves_out_pauses() {
+        let now = Instant::now();
+        let inner = MeetingManagerInner {
+            recording_start: Some(now - Duration::from_secs(100)),
+            paused_total: Duration::from_secs(30),
+            paused_at: Some(now - Duration::from_secs(20)),
+            ..Default::default()
+        };
+        let elapsed = inner.active_elapsed().unwrap().as_secs();
+        assert!((50..=51).contains(&elapsed), "elapsed {}", elapsed);
+
+        assert!(MeetingManagerInner::default().active_elapsed().is_none());
+    }
+
+    #[test]
+    fn renaming_replaces_whole_names_only() {
+        assert_eq!(
+            replace_speaker_name("Speaker 1 asked Speaker 10.", "Speaker 1", "Alice"),
+            "Alice asked Speaker 10."
+        );
+        assert_eq!(
+            replace_speaker_name("Alice, Alicia", "Alice", "Al"),
+            "Al, Alicia"
+        );
+    }
+
+    #[test]
+    fn topics_map_lines_to_chunk_times() {
+        let chunks: Vec<MeetingChunk> = (0..4u64)
+            .map(|i| MeetingChunk {
+                chunk_id: i as u32,
+                start_time_ms: i * 30_000,
+                end_time_ms: (i + 1) * 30_000,
+                ..chunk(None, "text")
+            })
+            .collect();
+        assert!(topic_prompt(&chunks).ends_with("3: text\n"));
+
+        let reply = "Here you go:\n```json\n[{\"title\": \"Budget\", \"line\": 2}, \
+            {\"title\": \"Intro\", \"line\": 1}, {\"title\": \"Bogus\", \"line\": 9}]\n```";
+        let topics = parse_topics(reply, &chunks).unwrap();
+        assert_eq!(
+            topics,
+            vec![
+                MeetingTopic {
+                    title: "Intro".to_string(),
+                    start_ms: 0,
+                    end_ms: 60_000,
+                },
+                MeetingTopic {
+                    title: "Budget".to_string(),
+                    start_ms: 60_000,
+                    end_ms: 120_000,
+                },
+            ]
+        );
+        assert!(parse_topics("No topics", &chunks).is_err());
+    }
+
+    #[test]
+    fn summaries_are_packed_in_order() {
+        let texts: Vec<String> = ["aaaa", "bbbb", "cccccccccc", "dd"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            pack_texts(&texts, 10),
+            vec!["aaaa\n\nbbbb", "cccccccccc", "dd"]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! On-disk journal of a meeting's audio, so a meeting survives a crash.
+//!
+//! Samples are buffered as they are recorded and written out about once a
+//! second as a block: a little-endian u32 length followed by the payload,
+//! the samples as f32 LE. With history encryption on, each payload is sealed
+//! with the history key. A block torn by a crash mid-write is dropped when
+//! the journal is read back.
+
+use anyhow::{anyhow, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::encryption::HistoryKey;
+
+pub struct MeetingJournal {
+    file: Mutex<File>,
+    buffer: Mutex<Vec<f32>>,
+    key: Option<HistoryKey>,
+}
+
+impl MeetingJournal {
+    /// Open the journal at `path`, continuing it with `append` or else
+    /// starting it over
+    pub fn open(path: &Path, key: Option<HistoryKey>, append: bool) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(append)
+            .write(true)
+            .truncate(!append)
+            .open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+            buffer: Mutex::new(Vec::new()),
+            key,
+        })
+    }
+
+    /// Queue samples for the next flush
+    pub fn append(&self, samples: &[f32]) {
+        self.buffer.lock().unwrap().extend_from_slice(samples);
+    }
+
+    /// Write the queued samples to disk as one block
+    pub fn flush(&self) -> Result<()> {
+        let samples = std::mem::take(&mut *self.buffer.lock().unwrap());
+        if samples.is_empty() {
+            return Ok(());
+        }
+        let block = encode_block(&samples, self.key.as_ref())?;
+        let mut file = self.file.lock().unwrap();
+        file.write_all(&block)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// All samples journaled at `path`
+    pub fn read(path: &Path, key: Option<&HistoryKey>) -> Result<Vec<f32>> {
+        decode_blocks(&fs::read(path)?, key)
+    }
+}
+
+fn encode_block(samples: &[f32], key: Option<&HistoryKey>) -> Result<Vec<u8>> {
+    let mut payload: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    if let Some(key) = key {
+        payload = key.encrypt(&payload)?;
+    }
+    let len = u32::try_from(payload.len()).map_err(|_| anyhow!("Journal block too large"))?;
+    let mut block = Vec::with_capacity(4 + payload.len());
+    block.extend_from_slice(&len.to_le_bytes());
+    block.extend_from_slice(&payload);
+    Ok(block)
+}
+
+fn decode_blocks(mut data: &[u8], key: Option<&HistoryKey>) -> Result<Vec<f32>> {
+    let mut samples = Vec::new();
+    while let Some((len, rest)) = data.split_first_chunk::<4>() {
+        let len = u32::from_le_bytes(*len) as usize;
+        let Some(payload) = rest.get(..len) else {
+            log::warn!("Dropping a torn block at the end of the meeting journal");
+            break;
+        };
+        let payload = match key {
+            Some(key) => key.decrypt(payload)?,
+            None => payload.to_vec(),
+        };
+        samples.extend(
+            payload
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+        );
+        data = &rest[len..];
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks_round_trip_and_torn_tails_are_dropped() {
+        let mut data = encode_block(&[0.5, -0.25], None).unwrap();
+        data.extend(encode_block(&[1.0], None).unwrap());
+        let torn = encode_block(&[0.75, 0.75], None).unwrap();
+        data.extend_from_slice(&torn[..torn.len() - 3]);
+
+        assert_eq!(decode_blocks(&data, None).unwrap(), vec![0.5, -0.25, 1.0]);
+        assert!(decode_blocks(&[], None).unwrap().is_empty());
+    }
+}
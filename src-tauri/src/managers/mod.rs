@@ -1,11 +1,18 @@
 pub mod audio;
 pub mod diarization;
+pub mod file_job_store;
 pub mod file_transcription;
 pub mod history;
+pub mod history_migrations;
+pub mod history_sync;
 pub mod live_preview;
 pub mod meeting;
+pub mod meeting_journal;
 pub mod model;
+pub mod punctuation;
+pub mod settings_writer;
 pub mod snippets;
 pub mod system_audio;
 pub mod transcription;
+pub mod wake_word;
 pub mod watch_folder;
@@ -1,4 +1,12 @@
-use crate::settings::{get_settings, write_settings};
+#[cfg(target_os = "macos")]
+use crate::events::{
+    CoreMLDownloadComplete, CoreMLDownloadProgress, CoreMLDownloadStarted, CoreMLExtractionStarted,
+};
+use crate::events::{
+    ModelDownloadComplete, ModelDownloadsInterrupted, ModelExtractionCompleted,
+    ModelExtractionFailed, ModelExtractionStarted,
+};
+use crate::settings::{get_settings, update_settings};
 use anyhow::Result;
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
@@ -10,9 +18,12 @@ use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use tar::Archive;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tokio::sync::Notify;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
 pub enum EngineType {
@@ -20,6 +31,9 @@ pub enum EngineType {
     Parakeet,
     Moonshine,
     GroqCloud,
+    /// GGUF language model run by the embedded llama.cpp engine for
+    /// post-processing, not for transcription
+    Llama,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -56,10 +70,79 @@ pub struct DownloadProgress {
     pub percentage: f64,
 }
 
+/// Emitted when a download stops because the user cancelled it
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DownloadCancelledEvent {
+    pub model_id: String,
+    /// Whether the partial file was kept so the download can be resumed
+    pub partial_kept: bool,
+    pub partial_size: u64,
+}
+
+/// Emitted when a download stops because of a network, HTTP or disk error
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DownloadFailedEvent {
+    pub model_id: String,
+    pub error: String,
+}
+
+/// A download that was interrupted and left a resumable partial file behind
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PartialDownload {
+    pub model_id: String,
+    pub model_name: String,
+    pub partial_size: u64,
+    pub size_mb: u64,
+}
+
+/// Cancellation request for a running download
+#[derive(Default)]
+struct DownloadControl {
+    cancelled: AtomicBool,
+    purge: AtomicBool,
+    /// Wakes the download while it waits for the next chunk
+    cancel_signal: Notify,
+}
+
+impl DownloadControl {
+    /// Stop the download, removing its partial file if `purge` is set
+    fn cancel(&self, purge: bool) {
+        self.purge.store(purge, Ordering::SeqCst);
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.cancel_signal.notify_one();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn purges(&self) -> bool {
+        self.purge.load(Ordering::SeqCst)
+    }
+
+    /// Whether the cancelled download leaves a partial file to resume from
+    fn keeps_partial(&self, partial_size: u64) -> bool {
+        !self.purges() && partial_size > 0
+    }
+}
+
+/// Error returned by a download that was cancelled rather than failed
+#[derive(Debug)]
+struct DownloadCancelled;
+
+impl std::fmt::Display for DownloadCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Download cancelled")
+    }
+}
+
+impl std::error::Error for DownloadCancelled {}
+
 pub struct ModelManager {
     app_handle: AppHandle,
     models_dir: PathBuf,
     available_models: Mutex<HashMap<String, ModelInfo>>,
+    active_downloads: Mutex<HashMap<String, Arc<DownloadControl>>>,
 }
 
 impl ModelManager {
@@ -341,10 +424,90 @@ impl ModelManager {
             },
         );
 
+        // GGUF models for local post-processing (see local_llm)
+        available_models.insert(
+            "qwen2.5-1.5b-instruct".to_string(),
+            ModelInfo {
+                id: "qwen2.5-1.5b-instruct".to_string(),
+                name: "Qwen 2.5 1.5B".to_string(),
+                description: "Small local LLM for post-processing. Fast on any machine.".to_string(),
+                filename: "qwen2.5-1.5b-instruct-q4_k_m.gguf".to_string(),
+                url: Some(
+                    "https://huggingface.co/Qwen/Qwen2.5-1.5B-Instruct-GGUF/resolve/main/qwen2.5-1.5b-instruct-q4_k_m.gguf"
+                        .to_string(),
+                ),
+                size_mb: 1066,
+                is_downloaded: false,
+                is_downloading: false,
+                partial_size: 0,
+                is_directory: false,
+                engine_type: EngineType::Llama,
+                accuracy_score: 0.60,
+                speed_score: 0.90,
+                coreml_url: None,
+                coreml_size_mb: 0,
+                is_coreml_downloaded: false,
+                is_coreml_downloading: false,
+            },
+        );
+
+        available_models.insert(
+            "qwen2.5-3b-instruct".to_string(),
+            ModelInfo {
+                id: "qwen2.5-3b-instruct".to_string(),
+                name: "Qwen 2.5 3B".to_string(),
+                description: "Local LLM for post-processing. Better rewrites, needs more memory.".to_string(),
+                filename: "qwen2.5-3b-instruct-q4_k_m.gguf".to_string(),
+                url: Some(
+                    "https://huggingface.co/Qwen/Qwen2.5-3B-Instruct-GGUF/resolve/main/qwen2.5-3b-instruct-q4_k_m.gguf"
+                        .to_string(),
+                ),
+                size_mb: 2007,
+                is_downloaded: false,
+                is_downloading: false,
+                partial_size: 0,
+                is_directory: false,
+                engine_type: EngineType::Llama,
+                accuracy_score: 0.75,
+                speed_score: 0.70,
+                coreml_url: None,
+                coreml_size_mb: 0,
+                is_coreml_downloaded: false,
+                is_coreml_downloading: false,
+            },
+        );
+
+        available_models.insert(
+            "llama-3.2-3b-instruct".to_string(),
+            ModelInfo {
+                id: "llama-3.2-3b-instruct".to_string(),
+                name: "Llama 3.2 3B".to_string(),
+                description: "Local LLM for post-processing. Strong in English.".to_string(),
+                filename: "Llama-3.2-3B-Instruct-Q4_K_M.gguf".to_string(),
+                url: Some(
+                    "https://huggingface.co/bartowski/Llama-3.2-3B-Instruct-GGUF/resolve/main/Llama-3.2-3B-Instruct-Q4_K_M.gguf"
+                        .to_string(),
+                ),
+                size_mb: 1926,
+                is_downloaded: false,
+                is_downloading: false,
+                partial_size: 0,
+                is_directory: false,
+                engine_type: EngineType::Llama,
+                accuracy_score: 0.75,
+                speed_score: 0.70,
+                coreml_url: None,
+                coreml_size_mb: 0,
+                is_coreml_downloaded: false,
+                is_coreml_downloading: false,
+            },
+        );
+
         let manager = Self {
             app_handle: app_handle.clone(),
             models_dir,
             available_models: Mutex::new(available_models),
+            active_downloads: Mutex::new(HashMap::new()),
         };
 
         // Migrate any bundled models to user directory
@@ -353,15 +516,48 @@ impl ModelManager {
         // Check which models are already downloaded
         manager.update_download_status()?;
 
+        let interrupted = manager.get_partial_downloads();
+        if !interrupted.is_empty() {
+            info!(
+                "Found {} interrupted model download(s): {}",
+                interrupted.len(),
+                interrupted
+                    .iter()
+                    .map(|d| d.model_id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let _ = ModelDownloadsInterrupted(interrupted).emit(app_handle);
+        }
+
         // Auto-select a model if none is currently selected
         manager.auto_select_model_if_needed()?;
 
         Ok(manager)
     }
 
+    /// Transcription models
     pub fn get_available_models(&self) -> Vec<ModelInfo> {
         let models = self.available_models.lock().unwrap();
-        models.values().cloned().collect()
+        models
+            .values()
+            .filter(|model| model.engine_type != EngineType::Llama)
+            .cloned()
+            .collect()
+    }
+
+    /// Language models for local post-processing, none in builds without
+    /// the `local-llm` feature
+    pub fn get_local_llm_models(&self) -> Vec<ModelInfo> {
+        if !cfg!(feature = "local-llm") {
+            return Vec::new();
+        }
+        let models = self.available_models.lock().unwrap();
+        models
+            .values()
+            .filter(|model| model.engine_type == EngineType::Llama)
+            .cloned()
+            .collect()
     }
 
     pub fn get_model_info(&self, model_id: &str) -> Option<ModelInfo> {
@@ -494,16 +690,18 @@ impl ModelManager {
         if settings.selected_model.is_empty() {
             // Find the first available (downloaded) model
             let models = self.available_models.lock().unwrap();
-            if let Some(available_model) = models.values().find(|model| model.is_downloaded) {
+            if let Some(available_model) = models
+                .values()
+                .find(|model| model.is_downloaded && model.engine_type != EngineType::Llama)
+            {
                 info!(
                     "Auto-selecting model: {} ({})",
                     available_model.id, available_model.name
                 );
 
                 // Update settings with the selected model
-                let mut updated_settings = settings;
-                updated_settings.selected_model = available_model.id.clone();
-                write_settings(&self.app_handle, updated_settings);
+                let selected_model = available_model.id.clone();
+                update_settings(&self.app_handle, move |s| s.selected_model = selected_model);
 
                 info!("Successfully auto-selected model: {}", available_model.id);
             }
@@ -513,6 +711,68 @@ impl ModelManager {
     }
 
     pub async fn download_model(&self, model_id: &str) -> Result<()> {
+        let control = Arc::new(DownloadControl::default());
+        {
+            let mut active = self.active_downloads.lock().unwrap();
+            if active.contains_key(model_id) {
+                return Err(anyhow::anyhow!(
+                    "Model is already downloading: {}",
+                    model_id
+                ));
+            }
+            active.insert(model_id.to_string(), control.clone());
+        }
+
+        let result = self.run_download(model_id, &control).await;
+        self.active_downloads.lock().unwrap().remove(model_id);
+
+        if let Err(e) = &result {
+            {
+                let mut models = self.available_models.lock().unwrap();
+                if let Some(model) = models.get_mut(model_id) {
+                    model.is_downloading = false;
+                }
+            }
+
+            if e.is::<DownloadCancelled>() {
+                let purge = control.purges();
+                if purge {
+                    let _ = self.delete_partial_download(model_id);
+                }
+                let _ = self.update_download_status();
+                let partial_size = self
+                    .get_model_info(model_id)
+                    .map(|m| m.partial_size)
+                    .unwrap_or(0);
+                info!(
+                    "Download of {} cancelled ({})",
+                    model_id,
+                    if purge {
+                        "partial removed"
+                    } else {
+                        "partial kept"
+                    }
+                );
+                let _ = DownloadCancelledEvent {
+                    model_id: model_id.to_string(),
+                    partial_kept: control.keeps_partial(partial_size),
+                    partial_size,
+                }
+                .emit(&self.app_handle);
+            } else {
+                warn!("Download of {} failed: {}", model_id, e);
+                let _ = DownloadFailedEvent {
+                    model_id: model_id.to_string(),
+                    error: e.to_string(),
+                }
+                .emit(&self.app_handle);
+            }
+        }
+
+        result
+    }
+
+    async fn run_download(&self, model_id: &str, control: &DownloadControl) -> Result<()> {
         let model_info = {
             let models = self.available_models.lock().unwrap();
             models.get(model_id).cloned()
@@ -558,7 +818,7 @@ impl ModelManager {
         }
 
         // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
+        let client = crate::proxy::client_builder().build()?;
         let mut request = client.get(&url);
 
         if resume_from > 0 {
@@ -633,22 +893,25 @@ impl ModelManager {
                 0.0
             },
         };
-        let _ = self
-            .app_handle
-            .emit("model-download-progress", &initial_progress);
+        let _ = initial_progress.emit(&self.app_handle);
+
+        // Download with progress. A stalled connection may not deliver another
+        // chunk for a long time, so wait for the cancel signal alongside it.
+        loop {
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = control.cancel_signal.notified() => None,
+            };
+            if control.is_cancelled() {
+                // Keep what we have on disk so the download can resume
+                file.flush()?;
+                return Err(DownloadCancelled.into());
+            }
+            let Some(chunk) = chunk else {
+                break;
+            };
 
-        // Download with progress
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| {
-                // Mark as not downloading on error
-                {
-                    let mut models = self.available_models.lock().unwrap();
-                    if let Some(model) = models.get_mut(model_id) {
-                        model.is_downloading = false;
-                    }
-                }
-                e
-            })?;
+            let chunk = chunk?;
 
             file.write_all(&chunk)?;
             downloaded += chunk.len() as u64;
@@ -667,7 +930,7 @@ impl ModelManager {
                 percentage,
             };
 
-            let _ = self.app_handle.emit("model-download-progress", &progress);
+            let _ = progress.emit(&self.app_handle);
         }
 
         file.flush()?;
@@ -696,7 +959,7 @@ impl ModelManager {
         // Handle directory-based models (extract tar.gz) vs file-based models
         if model_info.is_directory {
             // Emit extraction started event
-            let _ = self.app_handle.emit("model-extraction-started", model_id);
+            let _ = ModelExtractionStarted(model_id.to_string()).emit(&self.app_handle);
             info!("Extracting archive for directory-based model: {}", model_id);
 
             // Use a temporary extraction directory to ensure atomic operations
@@ -723,13 +986,11 @@ impl ModelManager {
                 let error_msg = format!("Failed to extract archive: {}", e);
                 // Clean up failed extraction
                 let _ = fs::remove_dir_all(&temp_extract_dir);
-                let _ = self.app_handle.emit(
-                    "model-extraction-failed",
-                    &serde_json::json!({
-                        "model_id": model_id,
-                        "error": error_msg
-                    }),
-                );
+                let _ = ModelExtractionFailed {
+                    model_id: model_id.to_string(),
+                    error: error_msg.clone(),
+                }
+                .emit(&self.app_handle);
                 anyhow::anyhow!(error_msg)
             })?;
 
@@ -758,7 +1019,7 @@ impl ModelManager {
 
             info!("Successfully extracted archive for model: {}", model_id);
             // Emit extraction completed event
-            let _ = self.app_handle.emit("model-extraction-completed", model_id);
+            let _ = ModelExtractionCompleted(model_id.to_string()).emit(&self.app_handle);
 
             // Remove the downloaded tar.gz file
             let _ = fs::remove_file(&partial_path);
@@ -778,7 +1039,7 @@ impl ModelManager {
         }
 
         // Emit completion event
-        let _ = self.app_handle.emit("model-download-complete", model_id);
+        let _ = ModelDownloadComplete(model_id.to_string()).emit(&self.app_handle);
 
         info!(
             "Successfully downloaded model {} to {:?}",
@@ -892,34 +1153,65 @@ impl ModelManager {
         }
     }
 
-    pub fn cancel_download(&self, model_id: &str) -> Result<()> {
-        debug!("ModelManager: cancel_download called for: {}", model_id);
-
-        let _model_info = {
-            let models = self.available_models.lock().unwrap();
-            models.get(model_id).cloned()
-        };
+    /// Stop a running download. The partial file is kept so the download can
+    /// be resumed later, unless `purge` is set. Cancelling a model that isn't
+    /// downloading with `purge` set just removes its partial file.
+    pub fn cancel_download(&self, model_id: &str, purge: bool) -> Result<()> {
+        debug!(
+            "ModelManager: cancel_download called for: {} (purge: {})",
+            model_id, purge
+        );
 
-        let _model_info =
-            _model_info.ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if self.get_model_info(model_id).is_none() {
+            return Err(anyhow::anyhow!("Model not found: {}", model_id));
+        }
 
-        // Mark as not downloading
-        {
-            let mut models = self.available_models.lock().unwrap();
-            if let Some(model) = models.get_mut(model_id) {
-                model.is_downloading = false;
+        let control = self.active_downloads.lock().unwrap().get(model_id).cloned();
+        match control {
+            Some(control) => {
+                // The download task wakes up and cleans up
+                control.cancel(purge);
+            }
+            None if purge => {
+                self.delete_partial_download(model_id)?;
+            }
+            None => {
+                debug!("No active download to cancel for: {}", model_id);
             }
         }
 
-        // Note: The actual download cancellation would need to be handled
-        // by the download task itself. This just updates the state.
-        // The partial file is kept so the download can be resumed later.
+        Ok(())
+    }
 
-        // Update download status to reflect current state
-        self.update_download_status()?;
+    /// Models with a partial download on disk that isn't currently running
+    pub fn get_partial_downloads(&self) -> Vec<PartialDownload> {
+        let active = self.active_downloads.lock().unwrap();
+        let models = self.available_models.lock().unwrap();
+        partial_downloads(models.values(), |id| active.contains_key(id))
+    }
 
-        info!("Download cancelled for: {}", model_id);
-        Ok(())
+    /// Remove the partial file of an interrupted download
+    pub fn delete_partial_download(&self, model_id: &str) -> Result<()> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+
+        if self.active_downloads.lock().unwrap().contains_key(model_id) {
+            return Err(anyhow::anyhow!(
+                "Cannot remove partial file while downloading: {}",
+                model_id
+            ));
+        }
+
+        let partial_path = self
+            .models_dir
+            .join(format!("{}.partial", &model_info.filename));
+        if partial_path.exists() {
+            info!("Deleting partial download at: {:?}", partial_path);
+            fs::remove_file(&partial_path)?;
+        }
+
+        self.update_download_status()
     }
 
     /// Get the path to the CoreML model for a given model ID (if downloaded)
@@ -990,16 +1282,14 @@ impl ModelManager {
         }
 
         // Emit download started event
-        let _ = self
-            .app_handle
-            .emit("coreml-download-started", model_id.to_string());
+        let _ = CoreMLDownloadStarted(model_id.to_string()).emit(&self.app_handle);
 
         // Download to a partial file
         let partial_path = self
             .models_dir
             .join(format!("{}.coreml.partial", &model_info.filename));
 
-        let client = reqwest::Client::new();
+        let client = crate::proxy::client_builder().build()?;
         let response = client.get(&coreml_url).send().await?;
 
         if !response.status().is_success() {
@@ -1039,15 +1329,13 @@ impl ModelManager {
                 0.0
             };
 
-            let _ = self.app_handle.emit(
-                "coreml-download-progress",
-                serde_json::json!({
-                    "model_id": model_id,
-                    "downloaded": downloaded,
-                    "total": total_size,
-                    "percentage": percentage,
-                }),
-            );
+            let _ = CoreMLDownloadProgress {
+                model_id: model_id.to_string(),
+                downloaded,
+                total: total_size,
+                percentage,
+            }
+            .emit(&self.app_handle);
         }
 
         file.flush()?;
@@ -1055,7 +1343,7 @@ impl ModelManager {
 
         // Extract the archive (supports both .tar.gz and .zip)
         info!("Extracting CoreML model for {}", model_id);
-        let _ = self.app_handle.emit("coreml-extraction-started", model_id);
+        let _ = CoreMLExtractionStarted(model_id.to_string()).emit(&self.app_handle);
 
         let temp_extract_dir = self
             .models_dir
@@ -1160,9 +1448,7 @@ impl ModelManager {
         }
 
         // Emit completion event
-        let _ = self
-            .app_handle
-            .emit("coreml-download-complete", model_id.to_string());
+        let _ = CoreMLDownloadComplete(model_id.to_string()).emit(&self.app_handle);
 
         info!("Successfully downloaded CoreML model for {}", model_id);
         Ok(())
@@ -1188,3 +1474,85 @@ impl ModelManager {
         Ok(())
     }
 }
+
+/// The models in `models` with a partial file and no running download, by id
+fn partial_downloads<'a>(
+    models: impl IntoIterator<Item = &'a ModelInfo>,
+    is_active: impl Fn(&str) -> bool,
+) -> Vec<PartialDownload> {
+    let mut partials: Vec<PartialDownload> = models
+        .into_iter()
+        .filter(|m| m.partial_size > 0 && !m.is_downloaded && !is_active(&m.id))
+        .map(|m| PartialDownload {
+            model_id: m.id.clone(),
+            model_name: m.name.clone(),
+            partial_size: m.partial_size,
+            size_mb: m.size_mb,
+        })
+        .collect();
+    partials.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+    partials
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, partial_size: u64, is_downloaded: bool) -> ModelInfo {
+        ModelInfo {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            description: String::new(),
+            filename: format!("{}.bin", id),
+            url: None,
+            size_mb: 100,
+            is_downloaded,
+            is_downloading: false,
+            partial_size,
+            is_directory: false,
+            engine_type: EngineType::Whisper,
+            accuracy_score: 0.5,
+            speed_score: 0.5,
+            coreml_url: None,
+            coreml_size_mb: 0,
+            is_coreml_downloaded: false,
+            is_coreml_downloading: false,
+        }
+    }
+
+    #[test]
+    fn cancelling_keeps_the_partial_unless_purged() {
+        let control = DownloadControl::default();
+        assert!(!control.is_cancelled());
+
+        control.cancel(false);
+        assert!(control.is_cancelled());
+        assert!(!control.purges());
+        assert!(control.keeps_partial(1024));
+        // Nothing was written yet, so there's nothing to resume
+        assert!(!control.keeps_partial(0));
+
+        let control = DownloadControl::default();
+        control.cancel(true);
+        assert!(control.is_cancelled());
+        assert!(control.purges());
+        assert!(!control.keeps_partial(1024));
+    }
+
+    #[test]
+    fn only_idle_unfinished_partials_are_listed() {
+        let models = [
+            model("small", 2048, false),
+            model("base", 1024, false),
+            model("empty", 0, false),
+            model("done", 4096, true),
+            model("running", 512, false),
+        ];
+
+        let partials = partial_downloads(&models, |id| id == "running");
+        let ids: Vec<&str> = partials.iter().map(|p| p.model_id.as_str()).collect();
+        assert_eq!(ids, ["base", "small"]);
+        assert_eq!(partials[1].partial_size, 2048);
+        assert_eq!(partials[1].model_name, "SMALL");
+    }
+}
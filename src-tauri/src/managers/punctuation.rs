@@ -0,0 +1,372 @@
+use anyhow::{anyhow, Result};
+use log::{debug, info, warn};
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::Tensor;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// Longest run of words the model punctuates in one pass
+const MAX_WORDS_PER_RUN: usize = 200;
+
+/// Status of the punctuation model
+#[derive(Clone, Debug, PartialEq)]
+pub enum PunctuationModelStatus {
+    NotDownloaded,
+    Downloading { progress: f32 },
+    Ready,
+    Error(String),
+}
+
+/// The loaded model, with the vocabulary and punctuation classes from its metadata
+struct PunctuationModel {
+    session: Session,
+    vocab: HashMap<String, i32>,
+    unk_id: i32,
+    /// The mark written for each class the model predicts, `None` for no mark
+    marks: Vec<Option<char>>,
+}
+
+/// Manager for punctuation and casing restoration
+///
+/// Parakeet and Moonshine output can come without punctuation. This runs the
+/// sherpa-onnx CT-Transformer punctuation model over such output, then
+/// capitalizes sentence starts, so the text reads well without an LLM call.
+pub struct PunctuationManager {
+    app_handle: AppHandle,
+    model_status: Arc<Mutex<PunctuationModelStatus>>,
+    model: Arc<Mutex<Option<PunctuationModel>>>,
+}
+
+impl PunctuationManager {
+    /// CT-Transformer punctuation model (Chinese and English), int8 quantized
+    const MODEL: &'static str = "punct-ct-transformer-zh-en.int8.onnx";
+    const MODEL_URL: &'static str =
+        "https://huggingface.co/csukuangfj/sherpa-onnx-punct-ct-transformer-zh-en-vocab272727-2024-04-12/resolve/main/model.int8.onnx";
+    const MODEL_SIZE: u64 = 76_000_000; // ~76MB
+
+    pub fn new(app: &AppHandle) -> Result<Self> {
+        let manager = Self {
+            app_handle: app.clone(),
+            model_status: Arc::new(Mutex::new(PunctuationModelStatus::NotDownloaded)),
+            model: Arc::new(Mutex::new(None)),
+        };
+
+        // Check if the model is already available
+        if manager
+            .get_model_path()
+            .map(|p| p.exists())
+            .unwrap_or(false)
+        {
+            if let Err(e) = manager.initialize_model() {
+                warn!("Failed to initialize punctuation model: {}", e);
+                if let Ok(mut status) = manager.model_status.lock() {
+                    *status = PunctuationModelStatus::Error(e.to_string());
+                }
+            }
+        }
+
+        Ok(manager)
+    }
+
+    fn get_model_path(&self) -> Result<PathBuf> {
+        self.app_handle
+            .path()
+            .app_data_dir()
+            .map(|p| p.join("models/punctuation").join(Self::MODEL))
+            .map_err(|e| anyhow!("Failed to resolve models directory: {}", e))
+    }
+
+    fn initialize_model(&self) -> Result<()> {
+        let model_path = self.get_model_path()?;
+        info!("Loading punctuation model from {:?}", model_path);
+
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_intra_threads(1)?
+            .commit_from_file(&model_path)?;
+
+        let (tokens, unk_symbol, punctuations) = {
+            let metadata = session.metadata()?;
+            let read = |key: &str| -> Result<String> {
+                metadata
+                    .custom(key)?
+                    .ok_or_else(|| anyhow!("Punctuation model metadata is missing '{}'", key))
+            };
+            (read("tokens")?, read("unk_symbol")?, read("punctuations")?)
+        };
+        let vocab: HashMap<String, i32> = tokens
+            .split('|')
+            .enumerate()
+            .map(|(id, token)| (token.to_string(), id as i32))
+            .collect();
+        let unk_id = *vocab
+            .get(&unk_symbol)
+            .ok_or_else(|| anyhow!("Unknown token '{}' is not in the vocabulary", unk_symbol))?;
+        let marks = punctuations
+            .split('|')
+            .map(|punctuation| match punctuation {
+                "，" | "、" | "," => Some(','),
+                "。" | "." => Some('.'),
+                "？" | "?" => Some('?'),
+                _ => None,
+            })
+            .collect();
+
+        match self.model.lock() {
+            Ok(mut guard) => {
+                *guard = Some(PunctuationModel {
+                    session,
+                    vocab,
+                    unk_id,
+                    marks,
+                })
+            }
+            Err(e) => return Err(anyhow!("Failed to acquire model lock: {}", e)),
+        }
+
+        match self.model_status.lock() {
+            Ok(mut guard) => *guard = PunctuationModelStatus::Ready,
+            Err(e) => return Err(anyhow!("Failed to acquire status lock: {}", e)),
+        }
+
+        info!("Punctuation model initialized successfully");
+        Ok(())
+    }
+
+    /// Get the current status of the punctuation model
+    pub fn get_status(&self) -> PunctuationModelStatus {
+        self.model_status
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or(PunctuationModelStatus::Error(
+                "Failed to acquire lock".to_string(),
+            ))
+    }
+
+    /// Check if punctuation restoration is available
+    pub fn is_available(&self) -> bool {
+        matches!(self.get_status(), PunctuationModelStatus::Ready)
+    }
+
+    /// Punctuates and capitalizes `text`. Text that already has sentence
+    /// punctuation is returned as is, and speaker labels like "[Speaker 1]: "
+    /// at the start of a line are kept out of the model's input.
+    pub fn restore(&self, text: &str) -> Result<String> {
+        if text.contains(['.', '?', '!']) {
+            debug!("Transcription is already punctuated, skipping restoration");
+            return Ok(text.to_string());
+        }
+
+        let mut guard = self
+            .model
+            .lock()
+            .map_err(|e| anyhow!("Failed to acquire model lock: {}", e))?;
+        let model = guard
+            .as_mut()
+            .ok_or_else(|| anyhow!("Punctuation model not loaded"))?;
+
+        let mut lines = Vec::new();
+        for line in text.lines() {
+            let (label, body) = split_speaker_label(line);
+            let words: Vec<&str> = body.split_whitespace().collect();
+            if words.is_empty() {
+                lines.push(line.to_string());
+                continue;
+            }
+
+            let mut marks = Vec::with_capacity(words.len());
+            for run in words.chunks(MAX_WORDS_PER_RUN) {
+                marks.extend(model.predict(run)?);
+            }
+            lines.push(format!("{}{}", label, punctuate(&words, &marks)));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Download the punctuation model
+    pub async fn download_model(&self) -> Result<()> {
+        use futures_util::StreamExt;
+        use std::io::Write;
+
+        let model_path = self.get_model_path()?;
+        if let Some(dir) = model_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        if model_path.exists() {
+            info!("Punctuation model already exists, initializing...");
+            return self.initialize_model();
+        }
+
+        if let Ok(mut status) = self.model_status.lock() {
+            *status = PunctuationModelStatus::Downloading { progress: 0.0 };
+        }
+
+        info!("Downloading punctuation model from {}", Self::MODEL_URL);
+        let response = crate::proxy::client_builder()
+            .build()?
+            .get(Self::MODEL_URL)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to download server: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_msg = format!("Download failed: HTTP {}", response.status());
+            if let Ok(mut status) = self.model_status.lock() {
+                *status = PunctuationModelStatus::Error(error_msg.clone());
+            }
+            return Err(anyhow!(error_msg));
+        }
+
+        let total_size = response.content_length().unwrap_or(Self::MODEL_SIZE);
+        let temp_path = model_path.with_extension("tmp");
+        let mut file = std::fs::File::create(&temp_path)
+            .map_err(|e| anyhow!("Failed to create temp file: {}", e))?;
+
+        let mut downloaded: u64 = 0;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| anyhow!("Download interrupted: {}", e))?;
+            file.write_all(&chunk)
+                .map_err(|e| anyhow!("Failed to write to temp file: {}", e))?;
+            downloaded += chunk.len() as u64;
+
+            if let Ok(mut status) = self.model_status.lock() {
+                *status = PunctuationModelStatus::Downloading {
+                    progress: (downloaded as f32 / total_size as f32).min(1.0),
+                };
+            }
+        }
+
+        drop(file);
+
+        std::fs::rename(&temp_path, &model_path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            anyhow!("Failed to finalize model file: {}", e)
+        })?;
+
+        info!("Downloaded punctuation model successfully");
+        self.initialize_model()
+    }
+
+    /// Get the model file name and its expected size
+    pub fn get_model_info() -> (&'static str, u64) {
+        (Self::MODEL, Self::MODEL_SIZE)
+    }
+}
+
+impl PunctuationModel {
+    /// The mark predicted after each of `words`
+    fn predict(&mut self, words: &[&str]) -> Result<Vec<Option<char>>> {
+        let ids: Vec<i32> = words
+            .iter()
+            .map(|word| {
+                let token = word
+                    .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                    .to_lowercase();
+                self.vocab.get(&token).copied().unwrap_or(self.unk_id)
+            })
+            .collect();
+        let len = ids.len();
+
+        let outputs = self.session.run(ort::inputs![
+            "text" => Tensor::from_array(([1usize, len], ids))?,
+            "text_lengths" => Tensor::from_array(([1usize], vec![len as i32]))?,
+        ])?;
+        let (shape, logits) = outputs["logits"].try_extract_tensor::<f32>()?;
+        let classes = shape.last().copied().unwrap_or_default() as usize;
+        if classes == 0 || logits.len() < len * classes {
+            return Err(anyhow!("Unexpected punctuation model output shape"));
+        }
+
+        Ok(logits
+            .chunks(classes)
+            .take(len)
+            .map(|scores| {
+                let best = scores
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map(|(class, _)| class)
+                    .unwrap_or_default();
+                self.marks.get(best).copied().flatten()
+            })
+            .collect())
+    }
+}
+
+/// Splits a leading "[Speaker]: " label from a line of diarized output
+fn split_speaker_label(line: &str) -> (&str, &str) {
+    if line.starts_with('[') {
+        if let Some(end) = line.find("]: ") {
+            return line.split_at(end + 3);
+        }
+    }
+    ("", line)
+}
+
+/// Joins `words`, writing each mark after its word, capitalizing the start of
+/// each sentence and the pronoun "I", and ending the text with a full stop
+/// if the model left it open
+fn punctuate(words: &[&str], marks: &[Option<char>]) -> String {
+    let mut result = String::new();
+    let mut sentence_start = true;
+
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            result.push(' ');
+        }
+
+        let lower = word.to_lowercase();
+        let is_pronoun = lower == "i" || lower.starts_with("i'");
+        let mut chars = word.chars();
+        if let (true, Some(first)) = (sentence_start || is_pronoun, chars.next()) {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        } else {
+            result.push_str(word);
+        }
+
+        let is_last = i + 1 == words.len();
+        let mark = match marks.get(i).copied().flatten() {
+            Some(',') | None if is_last => Some('.'),
+            mark => mark,
+        };
+        if let Some(mark) = mark {
+            result.push(mark);
+        }
+        sentence_start = matches!(mark, Some('.') | Some('?'));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punctuate_writes_marks_and_capitalizes_sentences() {
+        let words = ["so", "i'm", "late", "are", "you", "coming", "then"];
+        let marks = [Some(','), None, Some('.'), None, None, Some('?'), None];
+        assert_eq!(
+            punctuate(&words, &marks),
+            "So, I'm late. Are you coming? Then."
+        );
+    }
+
+    #[test]
+    fn speaker_labels_are_split_from_the_line() {
+        assert_eq!(
+            split_speaker_label("[Speaker 1]: hello there"),
+            ("[Speaker 1]: ", "hello there")
+        );
+        assert_eq!(
+            split_speaker_label("hello [there]: x"),
+            ("", "hello [there]: x")
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Single writer for the settings store.
+//!
+//! Settings commands don't write the `AppSettings` blob themselves. Each change
+//! is a closure handed to one writer thread, which applies the changes in order
+//! against the latest stored value, so options toggled in quick succession
+//! never overwrite each other. The writer persists to disk after a short
+//! debounce and emits `settings-changed` for every key that actually changed.
+//! A watchdog thread restarts the writer if it dies and logs when it stops
+//! making progress.
+
+use anyhow::Result;
+use log::{debug, error, info, warn};
+use serde_json::Value;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SendError, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
+
+use crate::events::SettingsChanged;
+use crate::settings::{self, AppSettings, SETTINGS_STORE_PATH};
+
+/// How long the writer waits after a change before saving the store to disk
+const PERSIST_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Upper bound on how long the writer blocks while idle, so its heartbeat stays fresh
+const IDLE_TICK: Duration = Duration::from_secs(1);
+/// How often the watchdog checks on the writer thread
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(2);
+/// Writer heartbeat age after which the watchdog reports a stall
+const STALL_THRESHOLD_MS: u64 = 10_000;
+/// How long a caller waits for its change to be applied before giving up waiting
+const APPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type SettingsUpdate = Box<dyn FnOnce(&mut AppSettings) + Send>;
+
+/// A queued change, shared with the caller so it can still apply the change
+/// itself if the writer goes away before taking it
+type PendingUpdate = Arc<Mutex<Option<SettingsUpdate>>>;
+
+enum WriterMessage {
+    Update {
+        update: PendingUpdate,
+        applied: SyncSender<()>,
+    },
+    Flush {
+        done: SyncSender<()>,
+    },
+    Shutdown,
+}
+
+/// Serializes every settings mutation through a supervised writer thread
+pub struct SettingsWriter {
+    app_handle: AppHandle,
+    sender: Mutex<Sender<WriterMessage>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    /// Milliseconds since the epoch of the writer's last loop iteration
+    heartbeat: Arc<AtomicU64>,
+    shutting_down: Arc<AtomicBool>,
+}
+
+/// Serializes direct applications (before the writer exists or after it died)
+/// with the ones made by the writer thread.
+static APPLY_LOCK: Mutex<()> = Mutex::new(());
+
+impl SettingsWriter {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let heartbeat = Arc::new(AtomicU64::new(now_ms()));
+        let (sender, worker) = spawn_writer(app_handle, heartbeat.clone())?;
+
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            sender: Mutex::new(sender),
+            worker: Mutex::new(Some(worker)),
+            heartbeat,
+            shutting_down: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Start the watchdog that restarts the writer if its thread exits
+    pub fn start_watchdog(self: &Arc<Self>) {
+        let writer = Arc::downgrade(self);
+        let shutting_down = self.shutting_down.clone();
+
+        let spawned = thread::Builder::new()
+            .name("settings-watchdog".into())
+            .spawn(move || {
+                let mut reported_stall = false;
+                loop {
+                    thread::sleep(WATCHDOG_INTERVAL);
+                    if shutting_down.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Some(writer) = writer.upgrade() else {
+                        break;
+                    };
+
+                    if writer.worker_exited() {
+                        error!("Settings writer thread exited unexpectedly, restarting it");
+                        if let Err(e) = writer.restart() {
+                            error!("Failed to restart settings writer: {}", e);
+                        }
+                        reported_stall = false;
+                        continue;
+                    }
+
+                    let idle_ms = now_ms().saturating_sub(writer.heartbeat.load(Ordering::Relaxed));
+                    if idle_ms > STALL_THRESHOLD_MS {
+                        if !reported_stall {
+                            warn!("Settings writer has not made progress for {}ms", idle_ms);
+                            reported_stall = true;
+                        }
+                    } else if reported_stall {
+                        info!("Settings writer recovered");
+                        reported_stall = false;
+                    }
+                }
+            });
+
+        if let Err(e) = spawned {
+            error!("Failed to spawn settings watchdog: {}", e);
+        }
+    }
+
+    /// Queue a change and wait for the writer to apply it.
+    ///
+    /// If the writer is gone the change is applied on the calling thread instead,
+    /// so a dead writer never loses a setting.
+    pub fn submit(&self, update: SettingsUpdate) {
+        let (applied_tx, applied_rx) = mpsc::sync_channel(1);
+        let pending: PendingUpdate = Arc::new(Mutex::new(Some(update)));
+        let message = WriterMessage::Update {
+            update: pending.clone(),
+            applied: applied_tx,
+        };
+
+        let send_result = self.sender.lock().unwrap().send(message);
+        match send_result {
+            Ok(()) => match applied_rx.recv_timeout(APPLY_TIMEOUT) {
+                Ok(()) => {}
+                Err(RecvTimeoutError::Timeout) => {
+                    warn!("Settings writer is slow; change will be applied when it catches up");
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    warn!("Settings writer stopped before applying a change, applying it directly");
+                    self.apply_pending(&pending);
+                }
+            },
+            Err(SendError(_)) => {
+                warn!("Settings writer unavailable, applying change directly");
+                self.apply_pending(&pending);
+            }
+        }
+    }
+
+    /// Apply a change on the calling thread unless the writer already took it
+    fn apply_pending(&self, pending: &PendingUpdate) {
+        if let Some(update) = take_pending(pending) {
+            if apply_update(&self.app_handle, update) {
+                persist(&self.app_handle);
+            }
+        }
+    }
+
+    /// Write any pending changes to disk and wait for the save to finish
+    pub fn flush(&self) {
+        let (done_tx, done_rx) = mpsc::sync_channel(1);
+        let send_result = self
+            .sender
+            .lock()
+            .unwrap()
+            .send(WriterMessage::Flush { done: done_tx });
+
+        if send_result.is_err() || done_rx.recv_timeout(APPLY_TIMEOUT).is_err() {
+            warn!("Settings writer did not flush, saving store directly");
+            persist(&self.app_handle);
+        }
+    }
+
+    /// Flush pending changes and stop the writer and watchdog threads
+    pub fn shutdown(&self) {
+        self.flush();
+        self.shutting_down.store(true, Ordering::Relaxed);
+        let _ = self.sender.lock().unwrap().send(WriterMessage::Shutdown);
+        if let Some(worker) = self.worker.lock().unwrap().take() {
+            let _ = worker.join();
+        }
+    }
+
+    fn worker_exited(&self) -> bool {
+        self.worker
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|worker| worker.is_finished())
+            .unwrap_or(false)
+    }
+
+    fn restart(&self) -> Result<()> {
+        let (sender, worker) = spawn_writer(&self.app_handle, self.heartbeat.clone())?;
+        *self.sender.lock().unwrap() = sender;
+        *self.worker.lock().unwrap() = Some(worker);
+        Ok(())
+    }
+}
+
+/// Apply a change on the calling thread. Used before the writer is managed.
+pub fn apply_directly(app: &AppHandle, update: SettingsUpdate) {
+    apply_update(app, update);
+}
+
+fn spawn_writer(
+    app_handle: &AppHandle,
+    heartbeat: Arc<AtomicU64>,
+) -> Result<(Sender<WriterMessage>, JoinHandle<()>)> {
+    let (sender, receiver) = mpsc::channel();
+    let app = app_handle.clone();
+    let worker = thread::Builder::new()
+        .name("settings-writer".into())
+        .spawn(move || run_writer(app, receiver, heartbeat))?;
+
+    Ok((sender, worker))
+}
+
+fn run_writer(app: AppHandle, receiver: Receiver<WriterMessage>, heartbeat: Arc<AtomicU64>) {
+    debug!("Settings writer started");
+    // Time of the oldest change that has not been saved to disk yet
+    let mut dirty_since: Option<Instant> = None;
+
+    loop {
+        heartbeat.store(now_ms(), Ordering::Relaxed);
+
+        let timeout = match dirty_since {
+            Some(since) => PERSIST_DEBOUNCE.saturating_sub(since.elapsed()),
+            None => IDLE_TICK,
+        };
+
+        match receiver.recv_timeout(timeout) {
+            Ok(WriterMessage::Update { update, applied }) => {
+                if let Some(update) = take_pending(&update) {
+                    if apply_update(&app, update) && dirty_since.is_none() {
+                        dirty_since = Some(Instant::now());
+                    }
+                }
+                let _ = applied.send(());
+            }
+            Ok(WriterMessage::Flush { done }) => {
+                persist(&app);
+                dirty_since = None;
+                let _ = done.send(());
+            }
+            Ok(WriterMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
+                if dirty_since.is_some() {
+                    persist(&app);
+                }
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        if dirty_since.is_some_and(|since| since.elapsed() >= PERSIST_DEBOUNCE) {
+            persist(&app);
+            dirty_since = None;
+        }
+    }
+
+    debug!("Settings writer stopped");
+}
+
+fn take_pending(pending: &PendingUpdate) -> Option<SettingsUpdate> {
+    pending.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// Apply one change against the latest stored settings.
+/// Returns true if anything changed.
+fn apply_update(app: &AppHandle, update: SettingsUpdate) -> bool {
+    let guard = APPLY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut current = settings::get_settings(app);
+    let before = serde_json::to_value(&current).unwrap_or(Value::Null);
+
+    if panic::catch_unwind(AssertUnwindSafe(|| update(&mut current))).is_err() {
+        error!("Settings update panicked; the change was discarded");
+        return false;
+    }
+
+    let after = match serde_json::to_value(&current) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("Failed to serialize updated settings: {}", e);
+            return false;
+        }
+    };
+
+    let keys = changed_keys(&before, &after);
+    if keys.is_empty() {
+        return false;
+    }
+
+    match app.store(SETTINGS_STORE_PATH) {
+        Ok(store) => store.set("settings", after.clone()),
+        Err(e) => {
+            error!("Failed to open settings store: {}", e);
+            return false;
+        }
+    }
+    drop(guard);
+
+    for key in keys {
+        let value = after.get(&key).cloned().unwrap_or(Value::Null);
+        let _ = SettingsChanged {
+            setting: key,
+            value,
+        }
+        .emit(app);
+    }
+
+    true
+}
+
+fn persist(app: &AppHandle) {
+    match app.store(SETTINGS_STORE_PATH) {
+        Ok(store) => {
+            if let Err(e) = store.save() {
+                error!("Failed to save settings store: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to open settings store: {}", e),
+    }
+}
+
+/// Top-level setting keys whose values differ between two serialized snapshots
+fn changed_keys(before: &Value, after: &Value) -> Vec<String> {
+    let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut keys: Vec<String> = after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    keys.extend(
+        before
+            .keys()
+            .filter(|key| !after.contains_key(*key))
+            .cloned(),
+    );
+    keys.sort();
+    keys
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn changed_keys_reports_only_modified_fields() {
+        let before = json!({ "debug_mode": false, "history_limit": 5, "bindings": { "a": 1 } });
+        let after = json!({ "debug_mode": true, "history_limit": 5, "bindings": { "a": 2 } });
+
+        assert_eq!(
+            changed_keys(&before, &after),
+            vec!["bindings", "debug_mode"]
+        );
+    }
+
+    #[test]
+    fn changed_keys_includes_added_and_removed_fields() {
+        let before = json!({ "old": 1, "kept": true });
+        let after = json!({ "kept": true, "new": "x" });
+
+        assert_eq!(changed_keys(&before, &after), vec!["new", "old"]);
+    }
+
+    #[test]
+    fn changed_keys_is_empty_for_identical_snapshots() {
+        let value = json!({ "debug_mode": false });
+
+        assert!(changed_keys(&value, &value).is_empty());
+        assert!(changed_keys(&Value::Null, &value).is_empty());
+    }
+}
@@ -11,8 +11,11 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
 };
+use std::time::Duration;
 use tauri::AppHandle;
 
+use crate::audio_toolkit::audio::FrameResampler;
+
 #[cfg(target_os = "macos")]
 use screencapturekit::prelude::*;
 
@@ -385,7 +388,7 @@ impl SystemAudioManager {
         info!("Converted to {} mono samples", mono.len());
 
         // Resample from 48kHz to 16kHz
-        let resampled = resample_linear(&mono, CAPTURE_SAMPLE_RATE, TARGET_SAMPLE_RATE);
+        let resampled = resample(&mono, CAPTURE_SAMPLE_RATE, TARGET_SAMPLE_RATE);
         info!(
             "Resampled from {}Hz to {}Hz: {} samples",
             CAPTURE_SAMPLE_RATE,
@@ -501,31 +504,23 @@ pub fn stereo_to_mono(stereo_samples: &[f32]) -> Vec<f32> {
         .collect()
 }
 
-/// Simple resampling using linear interpolation
-/// For production use, the existing FrameResampler with rubato is preferred
-pub fn resample_linear(samples: &[f32], from_hz: u32, to_hz: u32) -> Vec<f32> {
-    if from_hz == to_hz {
+/// Resample audio with the same rubato-based FrameResampler used for
+/// microphone capture. The output length matches the input duration exactly.
+pub fn resample(samples: &[f32], from_hz: u32, to_hz: u32) -> Vec<f32> {
+    if from_hz == to_hz || samples.is_empty() {
         return samples.to_vec();
     }
 
-    let ratio = from_hz as f64 / to_hz as f64;
-    let output_len = ((samples.len() as f64) / ratio).ceil() as usize;
+    let output_len = (samples.len() as u64 * to_hz as u64).div_ceil(from_hz as u64) as usize;
     let mut output = Vec::with_capacity(output_len);
 
-    for i in 0..output_len {
-        let src_idx = i as f64 * ratio;
-        let idx_floor = src_idx.floor() as usize;
-        let idx_ceil = (idx_floor + 1).min(samples.len() - 1);
-        let frac = src_idx - idx_floor as f64;
-
-        let sample = if idx_floor < samples.len() {
-            samples[idx_floor] * (1.0 - frac as f32) + samples[idx_ceil] * frac as f32
-        } else {
-            0.0
-        };
-        output.push(sample);
-    }
+    let frame = Duration::from_millis(30);
+    let mut resampler = FrameResampler::new(from_hz as usize, to_hz as usize, frame);
+    resampler.push(samples, |frame| output.extend_from_slice(frame));
+    resampler.finish(|frame| output.extend_from_slice(frame));
 
+    // The resampler pads its last chunk and frame with silence; trim that off
+    output.resize(output_len, 0.0);
     output
 }
 
@@ -716,7 +711,7 @@ mod tests {
         #[test]
         fn test_resample_same_rate() {
             let samples = vec![0.1, 0.2, 0.3, 0.4, 0.5];
-            let resampled = resample_linear(&samples, 48000, 48000);
+            let resampled = resample(&samples, 48000, 48000);
             assert_eq!(resampled, samples);
         }
 
@@ -724,7 +719,7 @@ mod tests {
         fn test_resample_48khz_to_16khz() {
             // 3:1 downsampling ratio
             let samples: Vec<f32> = (0..4800).map(|i| (i as f32) / 4800.0).collect();
-            let resampled = resample_linear(&samples, 48000, 16000);
+            let resampled = resample(&samples, 48000, 16000);
 
             // Output should be approximately 1/3 the length
             let expected_len = (4800.0_f64 / 3.0).ceil() as usize;
@@ -740,7 +735,7 @@ mod tests {
         fn test_resample_44100hz_to_16khz() {
             // 44100/16000 = 2.75625 downsampling ratio
             let samples: Vec<f32> = (0..4410).map(|i| (i as f32) / 4410.0).collect();
-            let resampled = resample_linear(&samples, 44100, 16000);
+            let resampled = resample(&samples, 44100, 16000);
 
             let expected_len = (4410.0_f64 * 16000.0 / 44100.0).ceil() as usize;
             assert!(
@@ -753,23 +748,47 @@ mod tests {
 
         #[test]
         fn test_resample_preserves_approximate_values() {
-            // Test that resampling preserves approximate signal shape
-            // Generate a simple ramp
-            let samples: Vec<f32> = (0..300).map(|i| i as f32 / 300.0).collect();
-            let resampled = resample_linear(&samples, 48000, 16000);
+            // A 1kHz tone is well inside the 16kHz band and should keep its level
+            let samples = sine(1000.0, 48000, 24000);
+            let resampled = resample(&samples, 48000, 16000);
 
-            // First and last values should be approximately preserved
-            assert!(resampled[0].abs() < 0.01, "First sample should be near 0");
+            let middle = &resampled[1000..7000];
             assert!(
-                (resampled[resampled.len() - 1] - 1.0).abs() < 0.1,
-                "Last sample should be near 1.0"
+                (rms(middle) - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.05,
+                "Expected RMS near 0.707, got {}",
+                rms(middle)
             );
         }
 
+        #[test]
+        fn test_resample_filters_content_above_nyquist() {
+            // A 12kHz tone cannot be represented at 16kHz; linear interpolation
+            // would alias it down to 4kHz instead of removing it
+            let samples = sine(12000.0, 48000, 24000);
+            let resampled = resample(&samples, 48000, 16000);
+
+            let middle = &resampled[1000..7000];
+            assert!(
+                rms(middle) < 0.05,
+                "Expected near silence, got {}",
+                rms(middle)
+            );
+        }
+
+        fn sine(freq: f32, rate: u32, len: usize) -> Vec<f32> {
+            (0..len)
+                .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin())
+                .collect()
+        }
+
+        fn rms(samples: &[f32]) -> f32 {
+            (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+        }
+
         #[test]
         fn test_resample_empty_input() {
             let samples: Vec<f32> = vec![];
-            let resampled = resample_linear(&samples, 48000, 16000);
+            let resampled = resample(&samples, 48000, 16000);
             assert!(resampled.is_empty());
         }
     }
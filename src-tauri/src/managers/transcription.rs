@@ -1,20 +1,31 @@
 use crate::audio_toolkit::{
-    apply_corrections, apply_custom_words, apply_formatting, filter_transcription_output,
-    FormattingRules,
+    apply_corrections, apply_custom_words, apply_formatting, apply_identifier_casing,
+    apply_inverse_text_normalization, apply_regex_rules, apply_spelling,
+    filter_transcription_output, is_itn_language, CustomWordRules, FilterRules, FormattingRules,
 };
+use crate::commands::export::{ExportFormat, TranscriptSegment};
+use crate::context::whisper_prompt;
 use crate::groq_transcription;
 use crate::managers::diarization::DiarizationManager;
 use crate::managers::model::{EngineType, ModelManager};
+use crate::managers::punctuation::PunctuationManager;
 use crate::managers::snippets::apply_snippets;
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::metrics;
+use crate::settings::{
+    get_settings, normalize_text_pipeline, AppSettings, DeveloperMode, ModelUnloadTimeout,
+    TextStage, TextStageConfig,
+};
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
 use std::time::{Duration, SystemTime};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
 use tokio::runtime::Handle;
 use transcribe_rs::{
     engines::{
@@ -27,7 +38,10 @@ use transcribe_rs::{
     TranscriptionEngine,
 };
 
-#[derive(Clone, Debug, Serialize)]
+/// Weight given to the newest measurement when updating a model's realtime factor
+const REALTIME_FACTOR_SMOOTHING: f64 = 0.3;
+
+#[derive(Clone, Debug, Serialize, Type)]
 pub struct ModelStateEvent {
     pub event_type: String,
     pub model_id: Option<String>,
@@ -36,7 +50,7 @@ pub struct ModelStateEvent {
 }
 
 /// Event emitted during CoreML model compilation (first-run takes 3-5 minutes)
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Type)]
 pub struct CoreMLCompilationEvent {
     pub event_type: String, // "started", "completed", "failed"
     pub model_id: String,
@@ -44,6 +58,250 @@ pub struct CoreMLCompilationEvent {
     pub error: Option<String>,
 }
 
+/// Per-job overrides for a transcription, e.g. from a watch folder's configuration.
+/// Unset fields fall back to the global settings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct TranscriptionOverrides {
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub diarization_enabled: Option<bool>,
+    /// Translate the speech to English
+    #[serde(default)]
+    pub translate: Option<bool>,
+    /// Formats to write next to a file job's source when it completes
+    #[serde(default)]
+    pub sidecar_formats: Option<Vec<ExportFormat>>,
+    /// Folder to write the sidecar files to instead of next to the source
+    #[serde(default)]
+    pub sidecar_folder: Option<String>,
+    /// Name of the sidecar files without extension, with `{stem}`, `{date}`
+    /// and `{model}` filled in; unset keeps the source's name
+    #[serde(default)]
+    pub sidecar_name_template: Option<String>,
+    /// Text the speech continues, given to the engine at the end of its
+    /// prompt so the transcription matches its spelling and style
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    /// App or website the speech goes to, whose profile and scoped rules
+    /// apply; looked up when needed if unset
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// Names and terms from the window the speech goes to, given to the
+    /// engine with the custom words so it recognizes them
+    #[serde(default)]
+    pub context_terms: Option<Vec<String>>,
+}
+
+impl TranscriptionOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.model_id.is_none()
+            && self.language.is_none()
+            && self.diarization_enabled.is_none()
+            && self.translate.is_none()
+            && self.sidecar_formats.is_none()
+            && self.sidecar_folder.is_none()
+            && self.sidecar_name_template.is_none()
+            && self.initial_prompt.is_none()
+            && self.app_name.is_none()
+            && self.context_terms.is_none()
+    }
+
+    /// Apply the language, diarization and translation overrides to a settings snapshot.
+    /// The model override is handled by whoever loads the model.
+    pub fn apply(&self, settings: &mut AppSettings) {
+        if let Some(language) = &self.language {
+            settings.selected_language = language.clone();
+            // An explicit language pins the transcription to it
+            settings.multilingual_mode_enabled = false;
+        }
+        if let Some(enabled) = self.diarization_enabled {
+            settings.diarization_enabled = enabled;
+        }
+        if let Some(translate) = self.translate {
+            settings.translate_to_english = translate;
+        }
+    }
+}
+
+/// A transcription before and after local processing
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TranscriptionOutput {
+    /// What the engine produced, with speaker labels if diarization ran
+    pub raw_text: String,
+    /// After custom words, filtering, snippets and formatting
+    pub text: String,
+    /// Timed segments as the engine produced them, with speakers if
+    /// diarization ran. Empty for engines that don't report timings.
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// Local clean-up applied to every transcription: spelling mode, custom words,
+/// filler and hallucination filtering, spoken corrections, written numbers, snippets,
+/// regex rules, identifier casing and formatting, as configured in `settings`, in the order of the text
+/// pipeline. `app_name` is the app dictated into, for custom words, regex rules and
+/// pipelines scoped to apps.
+pub fn apply_text_processing(
+    settings: &AppSettings,
+    text: String,
+    app_name: Option<&str>,
+) -> String {
+    text_pipeline(settings, app_name)
+        .into_iter()
+        .filter(|config| config.enabled)
+        .fold(text, |text, config| {
+            apply_text_stage(settings, config.stage, text, app_name)
+        })
+}
+
+/// The text pipeline for `app_name`, or the default one
+pub fn text_pipeline(settings: &AppSettings, app_name: Option<&str>) -> Vec<TextStageConfig> {
+    let stages = app_name
+        .and_then(|name| settings.app_profile(name).text_pipeline)
+        .unwrap_or_else(|| settings.text_pipeline.clone());
+    normalize_text_pipeline(&stages)
+}
+
+/// Runs one stage, if its own setting is on
+fn apply_text_stage(
+    settings: &AppSettings,
+    stage: TextStage,
+    text: String,
+    app_name: Option<&str>,
+) -> String {
+    match stage {
+        // Spell out spelling commands before stutter filtering and word
+        // correction, so they never see the dictated letters
+        TextStage::Spelling if settings.spell_mode_enabled => apply_spelling(&text),
+        // Apply word correction if custom words are configured, for every
+        // app or for the one dictated into
+        TextStage::CustomWords => {
+            let words = custom_words_for(settings, app_name);
+            if words.is_empty() {
+                return text;
+            }
+            let rules = CustomWordRules {
+                threshold: settings.word_correction_threshold,
+                algorithm: settings.word_match_algorithm,
+                word_thresholds: settings.word_correction_thresholds.clone(),
+            };
+            apply_custom_words(&text, &words, &rules)
+        }
+        // Filter out filler words and hallucinations, and profanity if enabled
+        TextStage::Filter => {
+            let filter_rules = FilterRules {
+                fillers: settings.filler_filter,
+                filler_words: settings.filler_words.clone(),
+                stutter_max_word_len: settings.stutter.max_word_length,
+                stutter_min_repeats: settings.stutter.min_repetitions,
+                collapse_repeated_words: settings.stutter.collapse_repeated_words,
+                profanity: settings.profanity_filter,
+                profanity_words: settings.profanity_words.clone(),
+            };
+            filter_transcription_output(&text, &filter_rules)
+        }
+        // Apply correction detection if enabled
+        TextStage::Corrections if settings.correction_detection_enabled => apply_corrections(&text),
+        // Write spoken numbers, dates and amounts in their written form
+        TextStage::Itn if settings.itn_enabled && is_itn_language(&settings.selected_language) => {
+            apply_inverse_text_normalization(&text, settings.itn_locale)
+        }
+        // Apply voice snippets if enabled
+        TextStage::Snippets if settings.snippets_enabled && !settings.snippets.is_empty() => {
+            apply_snippets(&text, &settings.snippets)
+        }
+        // Apply the user's regex rules
+        TextStage::RegexRules if !settings.regex_rules.is_empty() => {
+            apply_regex_rules(&text, &settings.regex_rules, app_name)
+        }
+        // Turn "camel case user name" into userName when dictating code
+        TextStage::IdentifierCasing if settings.developer_mode.is_active_in(app_name) => {
+            apply_identifier_casing(&text)
+        }
+        // Apply auto-formatting if enabled
+        TextStage::Formatting if settings.auto_format_enabled => {
+            let rules = FormattingRules {
+                auto_lists: settings.auto_format_lists,
+                verbal_commands: settings.verbal_commands_enabled,
+                emoji: settings.emoji_map.clone(),
+            };
+            apply_formatting(&text, &rules)
+        }
+        _ => text,
+    }
+}
+
+/// The global custom words, followed by those of the word sets scoped to
+/// `app_name`, without duplicates
+pub fn custom_words_for(settings: &AppSettings, app_name: Option<&str>) -> Vec<String> {
+    let mut words = settings.custom_words.clone();
+    for set in settings
+        .custom_word_sets
+        .iter()
+        .filter(|set| set.applies_in(app_name))
+    {
+        for word in &set.words {
+            if !words.contains(word) {
+                words.push(word.clone());
+            }
+        }
+    }
+    words
+}
+
+/// Most terms given to the engine in its prompt
+const MAX_PROMPT_TERMS: usize = 40;
+
+/// The prompt for engines that take one: the window's terms, then the custom
+/// words, as a glossary without duplicates, and the text the speech continues
+fn engine_prompt(
+    settings: &AppSettings,
+    overrides: &TranscriptionOverrides,
+    app_name: Option<&str>,
+) -> Option<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let candidates = overrides
+        .context_terms
+        .iter()
+        .flatten()
+        .cloned()
+        .chain(custom_words_for(settings, app_name));
+    for term in candidates {
+        let term = term.trim();
+        let lowercase = term.to_lowercase();
+        if term.is_empty() || terms.iter().any(|t| t.to_lowercase() == lowercase) {
+            continue;
+        }
+        terms.push(term.to_string());
+        if terms.len() == MAX_PROMPT_TERMS {
+            break;
+        }
+    }
+    whisper_prompt(&terms, overrides.initial_prompt.as_deref())
+}
+
+/// The app or website being dictated into, looked up only when a regex rule, a custom
+/// word set, an app's text pipeline or developer mode detection needs it
+fn text_processing_app_name(settings: &AppSettings) -> Option<String> {
+    let scoped = settings
+        .regex_rules
+        .iter()
+        .any(|rule| rule.enabled && !rule.apps.is_empty())
+        || settings.custom_word_sets.iter().any(|set| set.enabled)
+        || !settings.app_text_pipelines.is_empty()
+        || settings
+            .app_profiles
+            .values()
+            .any(|profile| profile.text_pipeline.is_some())
+        || settings.developer_mode == DeveloperMode::Auto;
+    if !scoped {
+        return None;
+    }
+    crate::context::get_active_app().map(|app| app.context_name().to_string())
+}
+
 enum LoadedEngine {
     Whisper(WhisperEngine),
     Parakeet(ParakeetEngine),
@@ -62,6 +320,11 @@ pub struct TranscriptionManager {
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    /// Processing time divided by audio duration, per model id
+    realtime_factors: Arc<Mutex<HashMap<String, f64>>>,
+    /// Set on a `job_engine`, whose model is not the one the app shows as
+    /// loaded: it doesn't announce model changes or unload itself
+    detached: bool,
 }
 
 impl TranscriptionManager {
@@ -81,6 +344,8 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            realtime_factors: Arc::new(Mutex::new(HashMap::new())),
+            detached: false,
         };
 
         // Start the idle watcher
@@ -119,15 +384,13 @@ impl TranscriptionManager {
                                 debug!("Starting to unload model due to inactivity");
 
                                 if let Ok(()) = manager_cloned.unload_model() {
-                                    let _ = app_handle_cloned.emit(
-                                        "model-state-changed",
-                                        ModelStateEvent {
-                                            event_type: "unloaded".to_string(),
-                                            model_id: None,
-                                            model_name: None,
-                                            error: None,
-                                        },
-                                    );
+                                    let _ = ModelStateEvent {
+                                        event_type: "unloaded".to_string(),
+                                        model_id: None,
+                                        model_name: None,
+                                        error: None,
+                                    }
+                                    .emit(&app_handle_cloned);
                                     let unload_duration = unload_start.elapsed();
                                     debug!(
                                         "Model unloaded due to inactivity (took {}ms)",
@@ -146,6 +409,31 @@ impl TranscriptionManager {
         Ok(manager)
     }
 
+    /// A separate engine for file jobs that ask for a model other than the
+    /// selected one, so a job never swaps out the model dictation uses.
+    /// Shares the speed measurements with this one.
+    pub fn job_engine(&self) -> Self {
+        Self {
+            engine: Arc::new(Mutex::new(None)),
+            model_manager: self.model_manager.clone(),
+            app_handle: self.app_handle.clone(),
+            current_model_id: Arc::new(Mutex::new(None)),
+            last_activity: Arc::new(AtomicU64::new(0)),
+            shutdown_signal: Arc::new(AtomicBool::new(false)),
+            watcher_handle: Arc::new(Mutex::new(None)),
+            is_loading: Arc::new(Mutex::new(false)),
+            loading_condvar: Arc::new(Condvar::new()),
+            realtime_factors: self.realtime_factors.clone(),
+            detached: true,
+        }
+    }
+
+    fn announce(&self, event: ModelStateEvent) {
+        if !self.detached {
+            let _ = event.emit(&self.app_handle);
+        }
+    }
+
     pub fn is_model_loaded(&self) -> bool {
         let engine = self.engine.lock().unwrap();
         engine.is_some()
@@ -175,15 +463,12 @@ impl TranscriptionManager {
         }
 
         // Emit unloaded event
-        let _ = self.app_handle.emit(
-            "model-state-changed",
-            ModelStateEvent {
-                event_type: "unloaded".to_string(),
-                model_id: None,
-                model_name: None,
-                error: None,
-            },
-        );
+        self.announce(ModelStateEvent {
+            event_type: "unloaded".to_string(),
+            model_id: None,
+            model_name: None,
+            error: None,
+        });
 
         let unload_duration = unload_start.elapsed();
         debug!(
@@ -195,6 +480,10 @@ impl TranscriptionManager {
 
     /// Unloads the model immediately if the setting is enabled and the model is loaded
     pub fn maybe_unload_immediately(&self, context: &str) {
+        // A job engine is unloaded by its file jobs once they are done
+        if self.detached {
+            return;
+        }
         let settings = get_settings(&self.app_handle);
         if settings.model_unload_timeout == ModelUnloadTimeout::Immediately
             && self.is_model_loaded()
@@ -211,15 +500,12 @@ impl TranscriptionManager {
         debug!("Starting to load model: {}", model_id);
 
         // Emit loading started event
-        let _ = self.app_handle.emit(
-            "model-state-changed",
-            ModelStateEvent {
-                event_type: "loading_started".to_string(),
-                model_id: Some(model_id.to_string()),
-                model_name: None,
-                error: None,
-            },
-        );
+        self.announce(ModelStateEvent {
+            event_type: "loading_started".to_string(),
+            model_id: Some(model_id.to_string()),
+            model_name: None,
+            error: None,
+        });
 
         let model_info = self
             .model_manager
@@ -228,15 +514,12 @@ impl TranscriptionManager {
 
         if !model_info.is_downloaded {
             let error_msg = "Model not downloaded";
-            let _ = self.app_handle.emit(
-                "model-state-changed",
-                ModelStateEvent {
-                    event_type: "loading_failed".to_string(),
-                    model_id: Some(model_id.to_string()),
-                    model_name: Some(model_info.name.clone()),
-                    error: Some(error_msg.to_string()),
-                },
-            );
+            self.announce(ModelStateEvent {
+                event_type: "loading_failed".to_string(),
+                model_id: Some(model_id.to_string()),
+                model_name: Some(model_info.name.clone()),
+                error: Some(error_msg.to_string()),
+            });
             return Err(anyhow::anyhow!(error_msg));
         }
 
@@ -277,15 +560,13 @@ impl TranscriptionManager {
                                 info!(
                                     "First-time CoreML compilation detected, this may take 3-5 minutes"
                                 );
-                                let _ = self.app_handle.emit(
-                                    "coreml-compilation-status",
-                                    CoreMLCompilationEvent {
-                                        event_type: "started".to_string(),
-                                        model_id: model_id.to_string(),
-                                        estimated_time_seconds: Some(240), // ~4 minutes
-                                        error: None,
-                                    },
-                                );
+                                let _ = CoreMLCompilationEvent {
+                                    event_type: "started".to_string(),
+                                    model_id: model_id.to_string(),
+                                    estimated_time_seconds: Some(240), // ~4 minutes
+                                    error: None,
+                                }
+                                .emit(&self.app_handle);
                             }
 
                             true
@@ -316,28 +597,23 @@ impl TranscriptionManager {
                         let _ = std::fs::write(&compiled_marker, "");
                     }
 
-                    let _ = self.app_handle.emit(
-                        "coreml-compilation-status",
-                        CoreMLCompilationEvent {
-                            event_type: "completed".to_string(),
-                            model_id: model_id.to_string(),
-                            estimated_time_seconds: None,
-                            error: None,
-                        },
-                    );
+                    let _ = CoreMLCompilationEvent {
+                        event_type: "completed".to_string(),
+                        model_id: model_id.to_string(),
+                        estimated_time_seconds: None,
+                        error: None,
+                    }
+                    .emit(&self.app_handle);
                 }
 
                 load_result.map_err(|e| {
                     let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
+                    self.announce(ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(error_msg.clone()),
+                    });
                     anyhow::anyhow!(error_msg)
                 })?;
 
@@ -353,15 +629,12 @@ impl TranscriptionManager {
                     .map_err(|e| {
                         let error_msg =
                             format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
+                        self.announce(ModelStateEvent {
+                            event_type: "loading_failed".to_string(),
+                            model_id: Some(model_id.to_string()),
+                            model_name: Some(model_info.name.clone()),
+                            error: Some(error_msg.clone()),
+                        });
                         anyhow::anyhow!(error_msg)
                     })?;
                 LoadedEngine::Parakeet(engine)
@@ -376,19 +649,22 @@ impl TranscriptionManager {
                     .map_err(|e| {
                         let error_msg =
                             format!("Failed to load moonshine model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
+                        self.announce(ModelStateEvent {
+                            event_type: "loading_failed".to_string(),
+                            model_id: Some(model_id.to_string()),
+                            model_name: Some(model_info.name.clone()),
+                            error: Some(error_msg.clone()),
+                        });
                         anyhow::anyhow!(error_msg)
                     })?;
                 LoadedEngine::Moonshine(engine)
             }
+            EngineType::Llama => {
+                return Err(anyhow::anyhow!(
+                    "{} is a post-processing model and can't transcribe",
+                    model_info.name
+                ));
+            }
             EngineType::GroqCloud => {
                 // Cloud models don't need local loading - just store the model ID
                 info!("Setting up Groq cloud model: {}", model_id);
@@ -409,15 +685,12 @@ impl TranscriptionManager {
         }
 
         // Emit loading completed event
-        let _ = self.app_handle.emit(
-            "model-state-changed",
-            ModelStateEvent {
-                event_type: "loading_completed".to_string(),
-                model_id: Some(model_id.to_string()),
-                model_name: Some(model_info.name.clone()),
-                error: None,
-            },
-        );
+        self.announce(ModelStateEvent {
+            event_type: "loading_completed".to_string(),
+            model_id: Some(model_id.to_string()),
+            model_name: Some(model_info.name.clone()),
+            error: None,
+        });
 
         let load_duration = load_start.elapsed();
         debug!(
@@ -448,12 +721,104 @@ impl TranscriptionManager {
         });
     }
 
+    /// Load `model_id` in the background in place of the loaded model, after
+    /// any load in progress. Transcriptions wait for it like for the first load.
+    pub fn initiate_model_switch(&self, model_id: &str) {
+        let self_clone = self.clone();
+        let model_id = model_id.to_string();
+        thread::spawn(move || {
+            {
+                let mut is_loading = self_clone.is_loading.lock().unwrap();
+                while *is_loading {
+                    is_loading = self_clone.loading_condvar.wait(is_loading).unwrap();
+                }
+                if self_clone.get_current_model().as_deref() == Some(model_id.as_str()) {
+                    return;
+                }
+                *is_loading = true;
+            }
+
+            info!("Switching to model {}", model_id);
+            if let Err(e) = self_clone.load_model(&model_id) {
+                error!("Failed to switch to model {}: {}", model_id, e);
+            }
+            let mut is_loading = self_clone.is_loading.lock().unwrap();
+            *is_loading = false;
+            self_clone.loading_condvar.notify_all();
+        });
+    }
+
     pub fn get_current_model(&self) -> Option<String> {
         let current_model = self.current_model_id.lock().unwrap();
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+    /// Estimate how long transcribing `audio_seconds` of audio will take with
+    /// `model_id` (or the loaded model), based on previous transcriptions.
+    /// Returns None until the model has transcribed something.
+    pub fn estimate_processing_seconds(
+        &self,
+        model_id: Option<&str>,
+        audio_seconds: f64,
+    ) -> Option<f64> {
+        let model_id = match model_id {
+            Some(id) => id.to_string(),
+            None => self.get_current_model()?,
+        };
+        let factors = self.realtime_factors.lock().unwrap();
+        factors.get(&model_id).map(|factor| factor * audio_seconds)
+    }
+
+    fn record_realtime_factor(&self, audio_seconds: f64, elapsed: Duration) {
+        // Very short clips are dominated by fixed overhead and skew the factor
+        if audio_seconds < 1.0 {
+            return;
+        }
+        let Some(model_id) = self.get_current_model() else {
+            return;
+        };
+
+        let measured = elapsed.as_secs_f64() / audio_seconds;
+        let mut factors = self.realtime_factors.lock().unwrap();
+        let factor = factors
+            .entry(model_id.clone())
+            .and_modify(|f| {
+                *f = *f * (1.0 - REALTIME_FACTOR_SMOOTHING) + measured * REALTIME_FACTOR_SMOOTHING
+            })
+            .or_insert(measured);
+        debug!("Realtime factor for {}: {:.3}", model_id, factor);
+    }
+
+    /// Transcribe with the global settings, except for the language,
+    /// diarization and translation values set in `overrides`
+    pub fn transcribe_with_overrides(
+        &self,
+        audio: Vec<f32>,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String> {
+        self.transcribe_detailed(audio, overrides)
+            .map(|output| output.text)
+    }
+
+    /// Like `transcribe_with_overrides`, also returning the raw text and the
+    /// timed segments
+    pub fn transcribe_detailed(
+        &self,
+        audio: Vec<f32>,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<TranscriptionOutput> {
+        let result = self.run_transcription(audio, overrides);
+        if result.is_err() {
+            metrics::record_transcription_error();
+        }
+        result
+    }
+
+    fn run_transcription(
+        &self,
+        audio: Vec<f32>,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<TranscriptionOutput> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -464,13 +829,14 @@ impl TranscriptionManager {
         );
 
         let st = std::time::Instant::now();
+        let audio_seconds = audio.len() as f64 / 16000.0;
 
         debug!("Audio vector length: {}", audio.len());
 
         if audio.is_empty() {
             debug!("Empty audio vector");
             self.maybe_unload_immediately("empty audio");
-            return Ok(String::new());
+            return Ok(TranscriptionOutput::default());
         }
 
         // Check if model is loaded, if not try to load it
@@ -488,7 +854,16 @@ impl TranscriptionManager {
         }
 
         // Get current settings for configuration
-        let settings = get_settings(&self.app_handle);
+        let mut settings = get_settings(&self.app_handle);
+        overrides.apply(&mut settings);
+        let app_name = overrides
+            .app_name
+            .clone()
+            .or_else(|| text_processing_app_name(&settings));
+        let prompt = engine_prompt(&settings, overrides, app_name.as_deref());
+        if let Some(ref p) = prompt {
+            debug!("Using engine prompt: {}", p);
+        }
 
         // Clone audio for diarization if enabled (before transcription consumes it)
         let audio_for_diarization = if settings.diarization_enabled {
@@ -498,15 +873,19 @@ impl TranscriptionManager {
         };
 
         // Perform transcription with the appropriate engine
-        let result = {
+        let (result, writes_punctuation) = {
             let mut engine_guard = self.engine.lock().unwrap();
             let engine = engine_guard.as_mut().ok_or_else(|| {
                 anyhow::anyhow!(
                     "Model failed to load after auto-load attempt. Please check your model settings."
                 )
             })?;
+            let writes_punctuation = !matches!(
+                engine,
+                LoadedEngine::Parakeet(_) | LoadedEngine::Moonshine(_)
+            );
 
-            match engine {
+            let result = match engine {
                 LoadedEngine::Whisper(whisper_engine) => {
                     // Multilingual mode: use language=None for auto-detection (handles code-switching)
                     // Single language mode: use selected_language
@@ -532,6 +911,7 @@ impl TranscriptionManager {
                     let params = WhisperInferenceParams {
                         language: whisper_language,
                         translate: settings.translate_to_english,
+                        initial_prompt: prompt.clone(),
                         ..Default::default()
                     };
 
@@ -577,6 +957,7 @@ impl TranscriptionManager {
                     let primary_lang = settings.primary_language.clone();
                     let secondary_lang = settings.secondary_language.clone();
                     let is_multilingual = settings.multilingual_mode_enabled;
+                    let groq_prompt = prompt.clone();
 
                     // Use block_in_place to avoid deadlock when called from async context
                     let result = tokio::task::block_in_place(|| {
@@ -588,6 +969,7 @@ impl TranscriptionManager {
                                     &audio_clone,
                                     primary_lang.as_deref(),
                                     secondary_lang.as_deref(),
+                                    groq_prompt.as_deref(),
                                 )
                                 .await
                             } else {
@@ -596,6 +978,7 @@ impl TranscriptionManager {
                                     &model_id_clone,
                                     &audio_clone,
                                     language.as_deref(),
+                                    groq_prompt.as_deref(),
                                 )
                                 .await
                             }
@@ -611,9 +994,22 @@ impl TranscriptionManager {
                         segments: None,
                     }
                 }
-            }
+            };
+            (result, writes_punctuation)
         };
 
+        let mut timed_segments: Vec<TranscriptSegment> = result
+            .segments
+            .iter()
+            .flatten()
+            .map(|s| TranscriptSegment {
+                start_ms: (s.start * 1000.0) as u64,
+                end_ms: (s.end * 1000.0) as u64,
+                text: s.text.trim().to_string(),
+                speaker: None,
+            })
+            .collect();
+
         // Apply speaker diarization if enabled and audio is available
         let diarized_text = if let Some(audio_samples) = audio_for_diarization {
             info!("Diarization enabled, attempting to run speaker diarization...");
@@ -657,6 +1053,18 @@ impl TranscriptionManager {
                                             &diarization_segments,
                                         );
 
+                                    timed_segments = labeled_segments
+                                        .iter()
+                                        .map(|(start_ms, end_ms, text, speaker)| {
+                                            TranscriptSegment {
+                                                start_ms: *start_ms,
+                                                end_ms: *end_ms,
+                                                text: text.trim().to_string(),
+                                                speaker: speaker.clone(),
+                                            }
+                                        })
+                                        .collect();
+
                                     // Format output with speaker labels
                                     let mut formatted_output = String::new();
                                     let mut last_speaker: Option<String> = None;
@@ -728,46 +1136,36 @@ impl TranscriptionManager {
         // Use diarized text if available, otherwise use original transcription
         let text_for_processing = diarized_text.unwrap_or(result.text);
 
-        // Apply word correction if custom words are configured
-        let corrected_result = if !settings.custom_words.is_empty() {
-            apply_custom_words(
-                &text_for_processing,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            )
+        // Punctuate output from engines that write none, before it is kept as
+        // the raw text, so reprocessing from history keeps the punctuation
+        let text_for_processing = if settings.punctuation_restoration_enabled && !writes_punctuation
+        {
+            match self.app_handle.try_state::<Arc<PunctuationManager>>() {
+                Some(pm) if pm.is_available() => {
+                    pm.restore(&text_for_processing).unwrap_or_else(|e| {
+                        warn!(
+                            "Punctuation restoration failed: {}. Continuing without it.",
+                            e
+                        );
+                        text_for_processing
+                    })
+                }
+                _ => {
+                    debug!("Punctuation model not available");
+                    text_for_processing
+                }
+            }
         } else {
             text_for_processing
         };
+        let raw_text = text_for_processing.clone();
 
-        // Filter out filler words and hallucinations
-        let filtered_result = filter_transcription_output(&corrected_result);
-
-        // Apply correction detection if enabled
-        let corrected_text = if settings.correction_detection_enabled {
-            apply_corrections(&filtered_result)
-        } else {
-            filtered_result
-        };
-
-        // Apply voice snippets if enabled
-        let snippets_result = if settings.snippets_enabled && !settings.snippets.is_empty() {
-            apply_snippets(&corrected_text, &settings.snippets)
-        } else {
-            corrected_text
-        };
-
-        // Apply auto-formatting if enabled
-        let formatted_result = if settings.auto_format_enabled {
-            let rules = FormattingRules {
-                auto_lists: settings.auto_format_lists,
-                verbal_commands: settings.verbal_commands_enabled,
-            };
-            apply_formatting(&snippets_result, &rules)
-        } else {
-            snippets_result
-        };
+        let formatted_result =
+            apply_text_processing(&settings, text_for_processing, app_name.as_deref());
 
         let et = std::time::Instant::now();
+        self.record_realtime_factor(audio_seconds, et - st);
+        metrics::record_transcription(audio_seconds, et - st);
         let translation_note = if settings.translate_to_english {
             " (translated)"
         } else {
@@ -789,7 +1187,11 @@ impl TranscriptionManager {
 
         self.maybe_unload_immediately("transcription");
 
-        Ok(final_result)
+        Ok(TranscriptionOutput {
+            raw_text,
+            text: final_result,
+            segments: timed_segments,
+        })
     }
 
     /// Check if the current model is a cloud-based model (requires network)
@@ -902,6 +1304,7 @@ impl TranscriptionManager {
                                 &model_id_clone,
                                 &audio,
                                 language.as_deref(),
+                                None,
                             )
                             .await
                         })
@@ -920,6 +1323,7 @@ impl TranscriptionManager {
                             &model_id_clone,
                             &audio,
                             language.as_deref(),
+                            None,
                         )
                         .await
                     })
@@ -955,3 +1359,174 @@ impl Drop for TranscriptionManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::RegexRule;
+    use crate::context::{AppCategory, ToneStyle};
+    use crate::settings::{get_default_settings, AppProfile, CustomWordSet};
+
+    #[test]
+    fn text_stages_run_in_pipeline_order_per_app() {
+        let mut settings = get_default_settings();
+        settings.regex_rules = vec![RegexRule {
+            id: "1".to_string(),
+            pattern: "um".to_string(),
+            replacement: "well".to_string(),
+            case_insensitive: false,
+            apps: Vec::new(),
+            enabled: true,
+        }];
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), None),
+            "so yes"
+        );
+
+        // Regex rules before the filler filter see the filler
+        let mut stages = settings.text_pipeline.clone();
+        let rules = stages
+            .iter()
+            .position(|c| c.stage == TextStage::RegexRules)
+            .unwrap();
+        let rules = stages.remove(rules);
+        stages.insert(0, rules);
+        settings
+            .app_text_pipelines
+            .insert("Slack".to_string(), stages);
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), Some("Slack")),
+            "so well yes"
+        );
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), Some("Mail")),
+            "so yes"
+        );
+
+        // A disabled stage is skipped
+        settings.text_pipeline = text_pipeline(&settings, None)
+            .into_iter()
+            .map(|c| TextStageConfig {
+                enabled: c.stage != TextStage::Filter,
+                ..c
+            })
+            .collect();
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), None),
+            "so well yes"
+        );
+    }
+
+    #[test]
+    fn app_profiles_win_over_the_older_mappings() {
+        let mut settings = get_default_settings();
+        settings.regex_rules = vec![RegexRule {
+            id: "1".to_string(),
+            pattern: "um".to_string(),
+            replacement: "well".to_string(),
+            case_insensitive: false,
+            apps: Vec::new(),
+            enabled: true,
+        }];
+        let mut rules_first = settings.text_pipeline.clone();
+        let rules = rules_first
+            .iter()
+            .position(|c| c.stage == TextStage::RegexRules)
+            .unwrap();
+        let rules = rules_first.remove(rules);
+        rules_first.insert(0, rules);
+        settings
+            .app_text_pipelines
+            .insert("Slack".to_string(), rules_first);
+        settings
+            .app_tone_mappings
+            .insert("Slack".to_string(), ToneStyle::Casual);
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), Some("Slack")),
+            "so well yes"
+        );
+
+        // The profile's pipeline replaces the mapped one, and what it leaves
+        // unset still comes from the mappings
+        settings.app_profiles.insert(
+            "Slack".to_string(),
+            AppProfile {
+                language: Some("de".to_string()),
+                text_pipeline: Some(settings.text_pipeline.clone()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            apply_text_processing(&settings, "so um yes".to_string(), Some("Slack")),
+            "so yes"
+        );
+        let profile = settings.app_profile("Slack");
+        assert_eq!(profile.language.as_deref(), Some("de"));
+        assert_eq!(profile.tone, Some(ToneStyle::Casual));
+        assert_eq!(settings.app_profile("Mail"), AppProfile::default());
+    }
+
+    #[test]
+    fn engine_prompt_merges_context_terms_with_custom_words() {
+        let mut settings = get_default_settings();
+        settings.custom_words = vec!["Kubernetes".to_string(), "PaperFlow".to_string()];
+        let overrides = TranscriptionOverrides {
+            context_terms: Some(vec![
+                "Priya".to_string(),
+                "kubernetes".to_string(),
+                "Priya".to_string(),
+            ]),
+            initial_prompt: Some("Thanks for the".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            engine_prompt(&settings, &overrides, None).as_deref(),
+            Some("Glossary: Priya, kubernetes, PaperFlow. Thanks for the")
+        );
+        assert_eq!(
+            engine_prompt(
+                &get_default_settings(),
+                &TranscriptionOverrides::default(),
+                None
+            ),
+            None
+        );
+
+        // The number of terms is capped
+        let overrides = TranscriptionOverrides {
+            context_terms: Some((0..100).map(|i| format!("T{}", i)).collect()),
+            ..Default::default()
+        };
+        let prompt = engine_prompt(&settings, &overrides, None).unwrap();
+        assert!(prompt.contains("T0, T1"));
+        assert!(!prompt.contains(&format!("T{}", MAX_PROMPT_TERMS)));
+    }
+
+    #[test]
+    fn custom_word_sets_apply_in_their_apps() {
+        let mut settings = get_default_settings();
+        settings.custom_words = vec!["Kubernetes".to_string()];
+        settings.custom_word_sets = vec![CustomWordSet {
+            id: "medical".to_string(),
+            name: "Medical".to_string(),
+            words: vec!["Kubernetes".to_string(), "tachycardia".to_string()],
+            categories: vec![AppCategory::Documents],
+            apps: vec!["Epic".to_string()],
+            enabled: true,
+        }];
+
+        assert_eq!(custom_words_for(&settings, None), ["Kubernetes"]);
+        assert_eq!(custom_words_for(&settings, Some("Slack")), ["Kubernetes"]);
+        assert_eq!(
+            custom_words_for(&settings, Some("Epic")),
+            ["Kubernetes", "tachycardia"]
+        );
+        assert_eq!(
+            custom_words_for(&settings, Some("Microsoft Word")),
+            ["Kubernetes", "tachycardia"]
+        );
+
+        settings.custom_word_sets[0].enabled = false;
+        assert_eq!(custom_words_for(&settings, Some("Epic")), ["Kubernetes"]);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::audio_toolkit::{SileroVad, VoiceActivityDetector, WakeWordDetector};
+use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
+use crate::settings::get_settings;
+use anyhow::{anyhow, Result};
+use log::{debug, error, info, warn};
+use ring::digest;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Passed to the actions in place of a shortcut string
+const SOURCE: &str = "wake-word";
+
+/// Speech-free time that ends a dictation started by the wake word
+const END_SILENCE: Duration = Duration::from_millis(1500);
+/// How long to wait for speech after the wake word before giving up
+const NO_SPEECH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Frames queued before the recording started still arrive as not recording
+const START_GRACE: Duration = Duration::from_secs(2);
+/// Threshold of the VAD that tells when a wake word dictation is over
+const END_VAD_THRESHOLD: f32 = 0.3;
+
+/// Status of the wake word models
+#[derive(Clone, Debug, PartialEq)]
+pub enum WakeWordModelStatus {
+    NotDownloaded,
+    Downloading { progress: f32 },
+    Ready,
+    Error(String),
+}
+
+/// Manager for wake word voice activation
+///
+/// While enabled, the microphone stays open and every frame heard between
+/// recordings goes through an openWakeWord detector. Saying the wake word
+/// starts a dictation, which ends after a pause in speech.
+///
+/// Listening is strictly local: frames go from the recorder to the detector
+/// thread and are dropped there. They are never written to disk, kept in
+/// history or sent anywhere, and only the downloaded models are loaded.
+pub struct WakeWordManager {
+    app_handle: AppHandle,
+    model_status: Arc<Mutex<WakeWordModelStatus>>,
+}
+
+impl WakeWordManager {
+    /// What to say to wake the app without a custom model
+    pub const DEFAULT_PHRASE: &'static str = "Hey Jarvis";
+
+    /// Shared openWakeWord front end, and the classifier for `DEFAULT_PHRASE`
+    const MELSPECTROGRAM: &'static str = "melspectrogram.onnx";
+    const EMBEDDING: &'static str = "embedding_model.onnx";
+    const HEY_JARVIS_MODEL: &'static str = "hey_jarvis_v0.1.onnx";
+    /// File, URL and SHA-256 of each model. A download that doesn't match
+    /// its digest is discarded; one without a pinned digest is only logged.
+    const MODELS: [(&'static str, &'static str, Option<&'static str>); 3] = [
+        (
+            Self::MELSPECTROGRAM,
+            "https://github.com/dscripka/openWakeWord/releases/download/v0.5.1/melspectrogram.onnx",
+            None,
+        ),
+        (
+            Self::EMBEDDING,
+            "https://github.com/dscripka/openWakeWord/releases/download/v0.5.1/embedding_model.onnx",
+            None,
+        ),
+        (
+            Self::HEY_JARVIS_MODEL,
+            "https://github.com/dscripka/openWakeWord/releases/download/v0.5.1/hey_jarvis_v0.1.onnx",
+            None,
+        ),
+    ];
+    const MODEL_SIZE: u64 = 3_700_000; // ~3.7MB for all three
+
+    pub fn new(app: &AppHandle) -> Result<Self> {
+        let manager = Self {
+            app_handle: app.clone(),
+            model_status: Arc::new(Mutex::new(WakeWordModelStatus::NotDownloaded)),
+        };
+
+        let downloaded = manager.models_dir().is_ok_and(|dir| {
+            Self::MODELS
+                .iter()
+                .all(|(file, _, _)| dir.join(file).exists())
+        });
+        if downloaded {
+            if let Ok(mut status) = manager.model_status.lock() {
+                *status = WakeWordModelStatus::Ready;
+            }
+        }
+
+        Ok(manager)
+    }
+
+    fn models_dir(&self) -> Result<PathBuf> {
+        self.app_handle
+            .path()
+            .app_data_dir()
+            .map(|p| p.join("models/wake_word"))
+            .map_err(|e| anyhow!("Failed to resolve models directory: {}", e))
+    }
+
+    /// Get the current status of the wake word models
+    pub fn get_status(&self) -> WakeWordModelStatus {
+        self.model_status
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or(WakeWordModelStatus::Error(
+                "Failed to acquire lock".to_string(),
+            ))
+    }
+
+    /// Check if the wake word models are downloaded
+    pub fn is_available(&self) -> bool {
+        matches!(self.get_status(), WakeWordModelStatus::Ready)
+    }
+
+    /// Start or stop listening to match the settings, reloading the model
+    /// and sensitivity
+    pub fn apply_settings(&self) -> Result<()> {
+        let settings = get_settings(&self.app_handle);
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+
+        // Dropping the tap ends the detection thread that owned it
+        rm.set_frame_tap(None);
+        let mode = if settings.keeps_microphone_open() {
+            MicrophoneMode::AlwaysOn
+        } else {
+            MicrophoneMode::OnDemand
+        };
+        if !settings.wake_word_enabled {
+            return rm.update_mode(mode);
+        }
+        if !self.is_available() {
+            return Err(anyhow!("Wake word models are not downloaded"));
+        }
+
+        let dir = self.models_dir()?;
+        let classifier = settings
+            .wake_word_model_path
+            .as_deref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| dir.join(Self::HEY_JARVIS_MODEL));
+        let detector = WakeWordDetector::new(
+            &dir.join(Self::MELSPECTROGRAM),
+            &dir.join(Self::EMBEDDING),
+            &classifier,
+        )?;
+        let vad_path = self
+            .app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow!("Failed to resolve VAD path: {}", e))?;
+        let vad = SileroVad::new(vad_path, END_VAD_THRESHOLD)?;
+        let threshold = detection_threshold(settings.wake_word_sensitivity);
+
+        let (sender, receiver) = mpsc::channel::<(Vec<f32>, bool)>();
+        let app = self.app_handle.clone();
+        thread::Builder::new()
+            .name("wake-word".into())
+            .spawn(move || listen(app, detector, vad, threshold, receiver))?;
+        rm.set_frame_tap(Some(Arc::new(move |samples, recording| {
+            let _ = sender.send((samples.to_vec(), recording));
+        })));
+        rm.update_mode(mode)?;
+
+        info!(
+            "Listening for {} with {:?} (threshold {:.2})",
+            if settings.wake_word_model_path.is_some() {
+                "the custom wake word"
+            } else {
+                Self::DEFAULT_PHRASE
+            },
+            classifier.file_name().unwrap_or_default(),
+            threshold
+        );
+        Ok(())
+    }
+
+    /// Download the openWakeWord models
+    pub async fn download_models(&self) -> Result<()> {
+        use futures_util::StreamExt;
+        use std::io::Write;
+
+        let dir = self.models_dir()?;
+        std::fs::create_dir_all(&dir)?;
+
+        if let Ok(mut status) = self.model_status.lock() {
+            *status = WakeWordModelStatus::Downloading { progress: 0.0 };
+        }
+
+        let client = crate::proxy::client_builder().build()?;
+        let mut downloaded: u64 = 0;
+        for (file, url, sha256) in Self::MODELS {
+            let model_path = dir.join(file);
+            if model_path.exists() {
+                continue;
+            }
+
+            info!("Downloading wake word model from {}", url);
+            let response = client
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| anyhow!("Failed to connect to download server: {}", e))?;
+
+            if !response.status().is_success() {
+                let error_msg = format!("Download failed: HTTP {}", response.status());
+                if let Ok(mut status) = self.model_status.lock() {
+                    *status = WakeWordModelStatus::Error(error_msg.clone());
+                }
+                return Err(anyhow!(error_msg));
+            }
+
+            let temp_path = model_path.with_extension("tmp");
+            let mut output = std::fs::File::create(&temp_path)
+                .map_err(|e| anyhow!("Failed to create temp file: {}", e))?;
+
+            let mut hasher = digest::Context::new(&digest::SHA256);
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.map_err(|e| anyhow!("Download interrupted: {}", e))?;
+                hasher.update(&chunk);
+                output
+                    .write_all(&chunk)
+                    .map_err(|e| anyhow!("Failed to write to temp file: {}", e))?;
+                downloaded += chunk.len() as u64;
+
+                if let Ok(mut status) = self.model_status.lock() {
+                    *status = WakeWordModelStatus::Downloading {
+                        progress: (downloaded as f32 / Self::MODEL_SIZE as f32).min(1.0),
+                    };
+                }
+            }
+
+            drop(output);
+
+            let actual = hex(hasher.finish().as_ref());
+            match sha256 {
+                Some(expected) if actual != expected => {
+                    let _ = std::fs::remove_file(&temp_path);
+                    let error_msg = format!("{} doesn't match its checksum", file);
+                    if let Ok(mut status) = self.model_status.lock() {
+                        *status = WakeWordModelStatus::Error(error_msg.clone());
+                    }
+                    return Err(anyhow!(error_msg));
+                }
+                Some(_) => {}
+                None => warn!(
+                    "No checksum pinned for {}, downloaded SHA-256 {}",
+                    file, actual
+                ),
+            }
+
+            std::fs::rename(&temp_path, &model_path).map_err(|e| {
+                let _ = std::fs::remove_file(&temp_path);
+                anyhow!("Failed to finalize model file: {}", e)
+            })?;
+        }
+
+        if let Ok(mut status) = self.model_status.lock() {
+            *status = WakeWordModelStatus::Ready;
+        }
+        info!("Downloaded wake word models successfully");
+        Ok(())
+    }
+
+    /// Get the expected size of the models
+    pub fn get_model_size() -> u64 {
+        Self::MODEL_SIZE
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{:02x}", byte);
+        out
+    })
+}
+
+/// The classifier score that counts as hearing the wake word
+fn detection_threshold(sensitivity: f32) -> f32 {
+    (1.0 - sensitivity).clamp(0.05, 0.95)
+}
+
+/// A dictation started by the wake word
+struct Dictation {
+    started: Instant,
+    last_speech: Option<Instant>,
+    /// Whether frames of the recording have arrived yet
+    recording_seen: bool,
+}
+
+/// Runs the detector over frames heard between recordings, and ends the
+/// dictations it starts once the speaker pauses
+fn listen(
+    app: AppHandle,
+    mut detector: WakeWordDetector,
+    mut vad: SileroVad,
+    threshold: f32,
+    frames: Receiver<(Vec<f32>, bool)>,
+) {
+    let mut dictation: Option<Dictation> = None;
+
+    for (frame, recording) in frames {
+        if let Some(current) = dictation.as_mut() {
+            if recording {
+                current.recording_seen = true;
+                if vad.is_voice(&frame).unwrap_or(true) {
+                    current.last_speech = Some(Instant::now());
+                }
+                let finished = match current.last_speech {
+                    Some(at) => at.elapsed() >= END_SILENCE,
+                    None => current.started.elapsed() >= NO_SPEECH_TIMEOUT,
+                };
+                if finished {
+                    debug!("Wake word dictation ended after a pause");
+                    dictation = None;
+                    crate::input::set_transcription(&app, false, SOURCE);
+                }
+                continue;
+            }
+            if !current.recording_seen && current.started.elapsed() < START_GRACE {
+                continue;
+            }
+            // Stopped some other way, e.g. with the shortcut
+            dictation = None;
+            detector.reset();
+        }
+
+        // A recording started by a shortcut or a meeting
+        if recording {
+            continue;
+        }
+
+        match detector.push(&frame) {
+            Ok(Some(score)) if score >= threshold => {
+                info!("Wake word detected (score {:.2})", score);
+                detector.reset();
+                vad.reset();
+                dictation = Some(Dictation {
+                    started: Instant::now(),
+                    last_speech: None,
+                    recording_seen: false,
+                });
+                crate::input::set_transcription(&app, true, SOURCE);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("Wake word detection failed, no longer listening: {}", e);
+                return;
+            }
+        }
+    }
+}
@@ -1,22 +1,54 @@
 use anyhow::{anyhow, Result};
 use log::{debug, error, info, warn};
-use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Config, Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, Manager};
-
-use super::file_transcription::FileTranscriptionManager;
-use crate::settings::{get_settings, write_settings};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event as _;
+
+use super::file_job_store::FileJobStore;
+use super::file_transcription::{glob_matches, render_name_template, FileTranscriptionManager};
+use super::history::HistoryManager;
+use super::model::ModelManager;
+use super::transcription::TranscriptionOverrides;
+use crate::commands::export::ExportFormat;
+use crate::quiet_hours;
+use crate::settings::{get_settings, update_settings, DailyWindow};
 
 /// Supported audio/video extensions for watch folder
 const SUPPORTED_EXTENSIONS: &[&str] = &[
     "mp3", "wav", "m4a", "flac", "ogg", "aac", "wma", "aiff", "mp4", "mkv", "avi", "mov", "webm",
 ];
 
+/// Poll interval for network folders that don't set one explicitly
+const DEFAULT_NETWORK_POLL_SECONDS: u32 = 10;
+
+/// Longest polling interval a folder can be set to, so new recordings still
+/// show up within the hour
+const MAX_POLL_SECONDS: u32 = 60 * 60;
+
+/// How often watched folders are checked for stale handles and reconnection
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Filesystem types that don't deliver native change notifications reliably
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "cifs",
+    "smbfs",
+    "smb3",
+    "nfs",
+    "nfs4",
+    "afpfs",
+    "webdav",
+    "fuse.sshfs",
+];
+
 /// Configuration for a watched folder
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct WatchFolderConfig {
@@ -25,6 +57,164 @@ pub struct WatchFolderConfig {
     pub enabled: bool,
     pub recursive: bool,
     pub auto_process: bool,
+    /// Model to transcribe this folder's files with instead of the selected one
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Language to use instead of the global language setting
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Whether to run speaker diarization, overriding the global setting
+    #[serde(default)]
+    pub diarization_enabled: Option<bool>,
+    /// Scan for changes every N seconds instead of relying on native file
+    /// events. Network folders (SMB/NFS) are polled even when this is unset.
+    #[serde(default)]
+    pub polling_interval_seconds: Option<u32>,
+    /// Formats written next to each file once it is transcribed, e.g. SRT and TXT
+    #[serde(default)]
+    pub sidecar_formats: Vec<ExportFormat>,
+    /// Only files matching one of these are picked up, e.g. `*.m4a` or
+    /// `calls/**/*.mp3`; empty picks up every supported file
+    #[serde(default)]
+    pub include_globs: Vec<String>,
+    /// Files matching any of these are ignored. A pattern ending in `/`,
+    /// like `archive/`, ignores everything in the folders it matches.
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    /// What happens to a file once it has been transcribed
+    #[serde(default)]
+    pub post_action: WatchFolderPostAction,
+    /// Folder the sidecar files are written to, relative to the watched
+    /// folder or absolute; unset writes them next to each file
+    #[serde(default)]
+    pub transcript_folder: Option<String>,
+    /// Name of the transcript files, e.g. `{date} {stem}`; placeholders are
+    /// `{stem}` (the file's name), `{date}` and `{model}`
+    #[serde(default)]
+    pub transcript_name_template: Option<String>,
+    /// When set and enabled, files are only queued inside this daily window,
+    /// e.g. 22:00-07:00; files found outside it wait until it opens
+    #[serde(default)]
+    pub active_hours: Option<DailyWindow>,
+}
+
+/// What happens to a watched file after it is transcribed
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchFolderPostAction {
+    /// Leave it where it is
+    #[default]
+    Keep,
+    /// Move it into a folder, relative to the watched folder (e.g. "done") or absolute
+    MoveTo { folder: String },
+    /// Rename it with the date it was transcribed in front, e.g. "2024-06-01 memo.m4a"
+    PrefixDate,
+    /// Delete it
+    Delete,
+}
+
+impl WatchFolderConfig {
+    /// Transcription overrides for files picked up from this folder
+    pub fn transcription_overrides(&self) -> TranscriptionOverrides {
+        TranscriptionOverrides {
+            model_id: self.model_id.clone(),
+            language: self.language.clone(),
+            diarization_enabled: self.diarization_enabled,
+            translate: None,
+            sidecar_formats: Some(self.sidecar_formats.clone()).filter(|f| !f.is_empty()),
+            sidecar_folder: self
+                .transcript_folder
+                .as_deref()
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(|f| self.resolve(f).to_string_lossy().to_string()),
+            sidecar_name_template: self
+                .transcript_name_template
+                .clone()
+                .filter(|t| !t.trim().is_empty()),
+            initial_prompt: None,
+            app_name: None,
+            context_terms: None,
+        }
+    }
+
+    /// Whether files found now may be queued, going by the folder's active hours
+    fn is_open_now(&self) -> bool {
+        self.active_hours
+            .as_ref()
+            .is_none_or(|window| !window.enabled || quiet_hours::is_active_now(window))
+    }
+
+    /// `folder` as an absolute path, taking relative ones from the watched folder
+    fn resolve(&self, folder: &str) -> PathBuf {
+        Path::new(&self.path).join(folder)
+    }
+
+    /// Where the post-processing action puts a transcribed file, if it moves it
+    fn post_action_target(&self, source: &Path, date: &str) -> Option<PathBuf> {
+        let name = source.file_name()?;
+        match &self.post_action {
+            WatchFolderPostAction::MoveTo { folder } => {
+                Some(self.resolve(folder.trim()).join(name))
+            }
+            WatchFolderPostAction::PrefixDate => {
+                Some(source.with_file_name(format!("{} {}", date, name.to_string_lossy())))
+            }
+            WatchFolderPostAction::Keep | WatchFolderPostAction::Delete => None,
+        }
+    }
+
+    /// The folder's include and exclude globs, compiled
+    fn path_filter(&self) -> Result<PathFilter> {
+        let compile = |glob: &str| {
+            glob::Pattern::new(glob.trim()).map_err(|e| anyhow!("Invalid glob '{}': {}", glob, e))
+        };
+        let mut filter = PathFilter {
+            root: PathBuf::from(&self.path),
+            include: Vec::new(),
+            exclude_files: Vec::new(),
+            exclude_dirs: Vec::new(),
+        };
+        for glob in self.include_globs.iter().filter(|g| !g.trim().is_empty()) {
+            filter.include.push(compile(glob)?);
+        }
+        for glob in self.exclude_globs.iter().map(|g| g.trim()) {
+            match glob.strip_suffix('/') {
+                Some(dir) if !dir.is_empty() => filter.exclude_dirs.push(compile(dir)?),
+                Some(_) => {}
+                None if !glob.is_empty() => filter.exclude_files.push(compile(glob)?),
+                None => {}
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Which files of a watch folder are picked up
+struct PathFilter {
+    root: PathBuf,
+    include: Vec<glob::Pattern>,
+    exclude_files: Vec<glob::Pattern>,
+    /// Folders whose contents are ignored
+    exclude_dirs: Vec<glob::Pattern>,
+}
+
+impl PathFilter {
+    fn accepts(&self, path: &Path) -> bool {
+        let matches =
+            |pattern: &glob::Pattern, path: &Path| glob_matches(pattern, &self.root, path);
+        if !self.include.is_empty() && !self.include.iter().any(|p| matches(p, path)) {
+            return false;
+        }
+        if self.exclude_files.iter().any(|p| matches(p, path)) {
+            return false;
+        }
+        !path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != self.root && dir.starts_with(&self.root))
+            .any(|dir| self.exclude_dirs.iter().any(|p| matches(p, dir)))
+    }
 }
 
 /// Status of a watch folder
@@ -34,6 +224,9 @@ pub struct WatchFolderStatus {
     pub is_watching: bool,
     pub last_error: Option<String>,
     pub files_processed: u32,
+    /// Whether the folder is scanned periodically rather than watched natively
+    #[serde(default)]
+    pub polling: bool,
 }
 
 /// Event payload for watch folder file detection
@@ -44,14 +237,25 @@ pub struct WatchFolderFileDetected {
     pub file_name: String,
 }
 
+/// A file held back until its folder's processing hours, kept across restarts
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeferredFile {
+    pub path: String,
+    pub folder_id: String,
+    pub overrides: TranscriptionOverrides,
+}
+
 /// Internal state for a single watcher
 struct WatcherState {
     #[allow(dead_code)]
-    watcher: RecommendedWatcher,
+    watcher: Box<dyn Watcher + Send>,
     config: WatchFolderConfig,
     is_watching: bool,
     last_error: Option<String>,
     files_processed: u32,
+    polling: bool,
+    /// Set when the folder became unreachable; the health check recreates the watcher
+    stale: bool,
 }
 
 /// Manager for watch folder functionality
@@ -62,15 +266,28 @@ pub struct WatchFolderManager {
     recent_files: Arc<Mutex<HashMap<String, Instant>>>,
     /// Debounce duration in seconds
     debounce_seconds: u64,
+    shutting_down: Arc<AtomicBool>,
+    /// Files detected outside processing hours, queued once they start
+    deferred_files: Mutex<Vec<DeferredFile>>,
+    /// Where the deferred files are saved so a restart doesn't lose them
+    store: FileJobStore,
+    /// Files moved or renamed by a post-processing action, which must not be
+    /// picked up again where they land
+    moved_files: Mutex<HashSet<PathBuf>>,
 }
 
 impl WatchFolderManager {
-    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+    pub fn new(app_handle: &AppHandle, history_manager: Arc<HistoryManager>) -> Result<Self> {
+        let store = FileJobStore::open(app_handle, history_manager)?;
         let manager = Self {
             app_handle: app_handle.clone(),
             watchers: Arc::new(Mutex::new(HashMap::new())),
             recent_files: Arc::new(Mutex::new(HashMap::new())),
             debounce_seconds: 5,
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            deferred_files: Mutex::new(store.load_deferred()),
+            moved_files: Mutex::new(HashSet::new()),
+            store,
         };
 
         Ok(manager)
@@ -136,27 +353,58 @@ impl WatchFolderManager {
         let debounce_seconds = self.debounce_seconds;
         let folder_id = config.id.clone();
         let auto_process = config.auto_process;
+        let overrides = config.transcription_overrides();
+        let filter = config.path_filter().inspect_err(|e| {
+            self.set_folder_error(&config.id, Some(e.to_string()));
+        })?;
 
-        // Create the watcher with event handler
-        let mut watcher = RecommendedWatcher::new(
-            move |result: Result<Event, notify::Error>| match result {
-                Ok(event) => {
-                    Self::handle_event(
-                        &app_handle,
-                        &recent_files,
-                        debounce_seconds,
-                        &folder_id,
-                        auto_process,
-                        event,
-                    );
-                }
-                Err(e) => {
-                    error!("Watch error: {}", e);
+        let handler = move |result: Result<Event, notify::Error>| match result {
+            Ok(event) => {
+                Self::handle_event(
+                    &app_handle,
+                    &recent_files,
+                    debounce_seconds,
+                    &folder_id,
+                    auto_process,
+                    &overrides,
+                    &filter,
+                    event,
+                );
+            }
+            Err(e) => {
+                error!("Watch error in folder {}: {}", folder_id, e);
+                if let Some(manager) = app_handle.try_state::<Arc<WatchFolderManager>>() {
+                    manager.mark_stale(&folder_id, format!("Watch error: {}", e));
                 }
-            },
-            Config::default().with_poll_interval(Duration::from_secs(2)),
-        )
-        .map_err(|e| {
+            }
+        };
+
+        // Native events don't fire reliably on network mounts, so poll those instead
+        let poll_interval = config.polling_interval_seconds.or_else(|| {
+            is_network_path(&path).then(|| {
+                info!(
+                    "{} looks like a network folder, polling for changes",
+                    config.path
+                );
+                DEFAULT_NETWORK_POLL_SECONDS
+            })
+        });
+
+        let watcher_result = match poll_interval {
+            Some(seconds) => PollWatcher::new(
+                handler,
+                Config::default().with_poll_interval(Duration::from_secs(seconds.max(1) as u64)),
+            )
+            .map(|w| Box::new(w) as Box<dyn Watcher + Send>),
+            None => RecommendedWatcher::new(
+                handler,
+                Config::default().with_poll_interval(Duration::from_secs(2)),
+            )
+            .map(|w| Box::new(w) as Box<dyn Watcher + Send>),
+        };
+
+        // Create the watcher with event handler
+        let mut watcher = watcher_result.map_err(|e| {
             let err = format!("Failed to create watcher: {}", e);
             self.set_folder_error(&config.id, Some(err.clone()));
             anyhow!(err)
@@ -176,8 +424,10 @@ impl WatchFolderManager {
         })?;
 
         info!(
-            "Started watching folder: {} (recursive: {})",
-            config.path, config.recursive
+            "Started watching folder: {} (recursive: {}, polling: {})",
+            config.path,
+            config.recursive,
+            poll_interval.is_some()
         );
 
         // Clear any previous error on successful start
@@ -190,6 +440,8 @@ impl WatchFolderManager {
             is_watching: true,
             last_error: None,
             files_processed: 0,
+            polling: poll_interval.is_some(),
+            stale: false,
         };
 
         let mut watchers = self
@@ -220,12 +472,15 @@ impl WatchFolderManager {
     }
 
     /// Handle file system events
+    #[allow(clippy::too_many_arguments)]
     fn handle_event(
         app_handle: &AppHandle,
         recent_files: &Arc<Mutex<HashMap<String, Instant>>>,
         debounce_seconds: u64,
         folder_id: &str,
         auto_process: bool,
+        overrides: &TranscriptionOverrides,
+        filter: &PathFilter,
         event: Event,
     ) {
         // We only care about file creation and modification events
@@ -246,7 +501,13 @@ impl WatchFolderManager {
                 .map(|e| e.to_lowercase())
                 .unwrap_or_default();
 
-            if !SUPPORTED_EXTENSIONS.contains(&extension.as_str()) {
+            if !SUPPORTED_EXTENSIONS.contains(&extension.as_str()) || !filter.accepts(&path) {
+                continue;
+            }
+            let moved = app_handle
+                .try_state::<Arc<WatchFolderManager>>()
+                .is_some_and(|manager| manager.was_moved(&path));
+            if moved {
                 continue;
             }
 
@@ -299,7 +560,7 @@ impl WatchFolderManager {
                 file_name: file_name.clone(),
             };
 
-            if let Err(e) = app_handle.emit("watch-folder-file-detected", &payload) {
+            if let Err(e) = payload.emit(app_handle) {
                 error!("Failed to emit watch folder event: {}", e);
             }
 
@@ -308,11 +569,21 @@ impl WatchFolderManager {
                 manager.increment_files_processed(folder_id);
             }
 
-            // Auto-process if enabled
-            if auto_process {
+            // Auto-process if enabled, holding files back during quiet hours
+            // and outside the folder's active hours
+            let manager = app_handle.try_state::<Arc<WatchFolderManager>>();
+            let folder_open = manager
+                .as_ref()
+                .is_none_or(|manager| manager.is_folder_open(folder_id));
+            if auto_process && (quiet_hours::is_active(app_handle) || !folder_open) {
+                info!("Outside processing hours, deferring {}", file_name);
+                if let Some(manager) = manager {
+                    manager.defer_file(path_str, folder_id, overrides.clone());
+                }
+            } else if auto_process {
                 if let Some(file_manager) = app_handle.try_state::<Arc<FileTranscriptionManager>>()
                 {
-                    match file_manager.queue_file(&path_str) {
+                    match file_manager.queue_watched_file(&path_str, folder_id, overrides.clone()) {
                         Ok(job) => {
                             info!(
                                 "Auto-queued file for transcription: {} (job {})",
@@ -361,6 +632,7 @@ impl WatchFolderManager {
                 is_watching: state.is_watching,
                 last_error: state.last_error.clone(),
                 files_processed: state.files_processed,
+                polling: state.polling,
             })
             .collect()
     }
@@ -369,6 +641,7 @@ impl WatchFolderManager {
     /// Should be called on app shutdown
     pub fn shutdown(&self) {
         info!("Shutting down watch folder manager...");
+        self.shutting_down.store(true, Ordering::Relaxed);
         let mut watchers = match self.watchers.lock() {
             Ok(guard) => guard,
             Err(e) => {
@@ -402,6 +675,196 @@ impl WatchFolderManager {
         }
     }
 
+    /// Record that a folder's handle went bad so the health check recreates it
+    pub fn mark_stale(&self, folder_id: &str, error: String) {
+        if let Ok(mut watchers) = self.watchers.lock() {
+            if let Some(state) = watchers.get_mut(folder_id) {
+                state.stale = true;
+                state.last_error = Some(error);
+            }
+        }
+    }
+
+    /// Start a background thread that periodically checks every enabled folder,
+    /// marks unreachable ones as stale and re-creates their watchers once the
+    /// folder is reachable again (e.g. after a network share reconnects).
+    pub fn start_health_check(self: &Arc<Self>) {
+        let manager = Arc::downgrade(self);
+        let shutting_down = self.shutting_down.clone();
+
+        let spawned = thread::Builder::new()
+            .name("watch-folder-health".into())
+            .spawn(move || loop {
+                thread::sleep(HEALTH_CHECK_INTERVAL);
+                if shutting_down.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.check_folders();
+                if !quiet_hours::is_active(&manager.app_handle) {
+                    manager.release_deferred_files();
+                }
+            });
+
+        if let Err(e) = spawned {
+            error!("Failed to spawn watch folder health check: {}", e);
+        }
+    }
+
+    fn check_folders(&self) {
+        for config in get_watch_folders(&self.app_handle) {
+            if !config.enabled {
+                continue;
+            }
+
+            let reachable = std::fs::read_dir(&config.path).map(|_| ());
+            let (watching, stale) = match self.watchers.lock() {
+                Ok(watchers) => watchers
+                    .get(&config.id)
+                    .map(|state| (state.is_watching, state.stale))
+                    .unwrap_or((false, false)),
+                Err(_) => continue,
+            };
+
+            match reachable {
+                Err(e) => {
+                    if watching && !stale {
+                        warn!("Watch folder {} became unreachable: {}", config.path, e);
+                        self.mark_stale(&config.id, format!("Folder unavailable: {}", e));
+                    }
+                }
+                Ok(()) if !watching || stale => {
+                    info!("Reconnecting watch folder: {}", config.path);
+                    let _ = self.stop_watching(&config.id);
+                    if let Err(e) = self.start_watching(&config) {
+                        debug!("Watch folder {} still not available: {}", config.path, e);
+                    }
+                }
+                Ok(()) => {}
+            }
+        }
+    }
+
+    fn defer_file(&self, path: String, folder_id: &str, overrides: TranscriptionOverrides) {
+        if let Ok(mut deferred) = self.deferred_files.lock() {
+            if !deferred.iter().any(|file| file.path == path) {
+                deferred.push(DeferredFile {
+                    path,
+                    folder_id: folder_id.to_string(),
+                    overrides,
+                });
+                self.save_deferred(&deferred);
+            }
+        }
+    }
+
+    fn save_deferred(&self, deferred: &[DeferredFile]) {
+        if let Err(e) = self.store.save_deferred(deferred) {
+            warn!("Failed to save deferred watch folder files: {}", e);
+        }
+    }
+
+    /// Whether the folder is inside its active hours
+    fn is_folder_open(&self, folder_id: &str) -> bool {
+        self.watchers.lock().map_or(true, |watchers| {
+            watchers
+                .get(folder_id)
+                .is_none_or(|state| state.config.is_open_now())
+        })
+    }
+
+    fn was_moved(&self, path: &Path) -> bool {
+        self.moved_files
+            .lock()
+            .is_ok_and(|moved| moved.contains(path))
+    }
+
+    /// Apply the folder's post-processing action to a file that has been
+    /// transcribed, returning where the file went if it was moved
+    pub fn finish_file(&self, folder_id: &str, source: &Path) -> Result<Option<PathBuf>> {
+        let Some(config) = get_watch_folders(&self.app_handle)
+            .into_iter()
+            .find(|f| f.id == folder_id)
+        else {
+            return Ok(None);
+        };
+        if !source.is_file() {
+            return Ok(None);
+        }
+
+        if config.post_action == WatchFolderPostAction::Delete {
+            std::fs::remove_file(source)?;
+            info!("Deleted transcribed file {}", source.display());
+            return Ok(None);
+        }
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let Some(target) = config.post_action_target(source, &date) else {
+            return Ok(None);
+        };
+        if target.exists() {
+            return Err(anyhow!("{} already exists", target.display()));
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if let Ok(mut moved) = self.moved_files.lock() {
+            moved.insert(target.clone());
+        }
+        // A rename can't cross filesystems, so fall back to copying
+        if std::fs::rename(source, &target).is_err() {
+            std::fs::copy(source, &target)?;
+            std::fs::remove_file(source)?;
+        }
+        info!("Moved transcribed file to {}", target.display());
+        Ok(Some(target))
+    }
+
+    /// Queue the files that were held back during quiet hours, except those
+    /// whose folder is still outside its active hours
+    pub fn release_deferred_files(&self) {
+        let folders = get_watch_folders(&self.app_handle);
+        let deferred = match self.deferred_files.lock() {
+            Ok(mut deferred) => {
+                let (release, hold): (Vec<_>, Vec<_>) = std::mem::take(&mut *deferred)
+                    .into_iter()
+                    .partition(|file| {
+                        folders
+                            .iter()
+                            .find(|f| f.id == file.folder_id)
+                            .is_none_or(|f| f.is_open_now())
+                    });
+                *deferred = hold;
+                if !release.is_empty() {
+                    self.save_deferred(&deferred);
+                }
+                release
+            }
+            Err(_) => return,
+        };
+        if deferred.is_empty() {
+            return;
+        }
+        let Some(file_manager) = self.app_handle.try_state::<Arc<FileTranscriptionManager>>()
+        else {
+            return;
+        };
+
+        info!("Queueing {} deferred files", deferred.len());
+        for file in deferred {
+            if !Path::new(&file.path).is_file() {
+                debug!("Deferred file no longer exists: {}", file.path);
+                continue;
+            }
+            if let Err(e) =
+                file_manager.queue_watched_file(&file.path, &file.folder_id, file.overrides)
+            {
+                warn!("Failed to queue deferred file {}: {}", file.path, e);
+            }
+        }
+    }
+
     /// Increment the files processed counter for a folder
     pub fn increment_files_processed(&self, folder_id: &str) {
         if let Ok(mut watchers) = self.watchers.lock() {
@@ -423,6 +886,8 @@ pub fn add_watch_folder(
     app_handle: &AppHandle,
     path: String,
     recursive: bool,
+    include_globs: Vec<String>,
+    exclude_globs: Vec<String>,
 ) -> Result<WatchFolderConfig> {
     let path_buf = PathBuf::from(&path);
     if !path_buf.exists() {
@@ -433,8 +898,7 @@ pub fn add_watch_folder(
         return Err(anyhow!("Path is not a directory: {}", path));
     }
 
-    let mut settings = get_settings(app_handle);
-    let mut folders = settings.watch_folders.unwrap_or_default();
+    let folders = get_settings(app_handle).watch_folders.unwrap_or_default();
 
     // Check for duplicates
     if folders.iter().any(|f| f.path == path) {
@@ -447,47 +911,263 @@ pub fn add_watch_folder(
         enabled: true,
         recursive,
         auto_process: true,
+        model_id: None,
+        language: None,
+        diarization_enabled: None,
+        polling_interval_seconds: None,
+        sidecar_formats: Vec::new(),
+        include_globs,
+        exclude_globs,
+        post_action: WatchFolderPostAction::Keep,
+        transcript_folder: None,
+        transcript_name_template: None,
+        active_hours: None,
     };
+    config.path_filter()?;
 
-    folders.push(config.clone());
-    settings.watch_folders = Some(folders);
-    write_settings(app_handle, settings);
+    let stored = config.clone();
+    update_settings(app_handle, move |s| {
+        s.watch_folders.get_or_insert_with(Vec::new).push(stored);
+    });
 
     Ok(config)
 }
 
 /// Remove a watch folder
 pub fn remove_watch_folder(app_handle: &AppHandle, folder_id: &str) -> Result<()> {
-    let mut settings = get_settings(app_handle);
-    let mut folders = settings.watch_folders.unwrap_or_default();
-
-    let original_len = folders.len();
-    folders.retain(|f| f.id != folder_id);
-
-    if folders.len() == original_len {
+    let folders = get_settings(app_handle).watch_folders.unwrap_or_default();
+    if !folders.iter().any(|f| f.id == folder_id) {
         return Err(anyhow!("Watch folder not found: {}", folder_id));
     }
 
-    settings.watch_folders = Some(folders);
-    write_settings(app_handle, settings);
+    let folder_id = folder_id.to_string();
+    update_settings(app_handle, move |s| {
+        if let Some(folders) = s.watch_folders.as_mut() {
+            folders.retain(|f| f.id != folder_id);
+        }
+    });
 
     Ok(())
 }
 
 /// Update a watch folder configuration
 pub fn update_watch_folder(app_handle: &AppHandle, config: WatchFolderConfig) -> Result<()> {
-    let mut settings = get_settings(app_handle);
-    let mut folders = settings.watch_folders.unwrap_or_default();
-
-    let folder = folders
-        .iter_mut()
-        .find(|f| f.id == config.id)
-        .ok_or_else(|| anyhow!("Watch folder not found: {}", config.id))?;
+    let folders = get_settings(app_handle).watch_folders.unwrap_or_default();
+    if !folders.iter().any(|f| f.id == config.id) {
+        return Err(anyhow!("Watch folder not found: {}", config.id));
+    }
 
-    *folder = config;
+    if let Some(model_id) = &config.model_id {
+        let known = app_handle
+            .try_state::<Arc<ModelManager>>()
+            .map(|models| models.get_model_info(model_id).is_some())
+            .unwrap_or(true);
+        if !known {
+            return Err(anyhow!("Unknown model: {}", model_id));
+        }
+    }
+    config.path_filter()?;
+    if matches!(&config.post_action, WatchFolderPostAction::MoveTo { folder } if folder.trim().is_empty())
+    {
+        return Err(anyhow!("Choose a folder to move transcribed files to"));
+    }
+    if let Some(template) = config
+        .transcript_name_template
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    {
+        render_name_template(template, "memo", "2024-01-01", "model")?;
+    }
+    if let Some(seconds) = config.polling_interval_seconds {
+        if !(1..=MAX_POLL_SECONDS).contains(&seconds) {
+            return Err(anyhow!(
+                "Polling interval must be between 1 and {} seconds",
+                MAX_POLL_SECONDS
+            ));
+        }
+    }
+    if let Some(window) = &config.active_hours {
+        window
+            .validate()
+            .map_err(|e| anyhow!("Invalid active hours: {}", e))?;
+    }
 
-    settings.watch_folders = Some(folders);
-    write_settings(app_handle, settings);
+    update_settings(app_handle, move |s| {
+        let folder = s
+            .watch_folders
+            .as_mut()
+            .and_then(|folders| folders.iter_mut().find(|f| f.id == config.id));
+        if let Some(folder) = folder {
+            *folder = config;
+        }
+    });
 
     Ok(())
 }
+
+/// Whether a path lives on a network filesystem (SMB, NFS, ...)
+#[cfg(target_os = "windows")]
+fn is_network_path(path: &Path) -> bool {
+    // UNC paths like \\server\share; mapped drive letters aren't detected
+    path.to_string_lossy().starts_with(r"\\")
+}
+
+/// Whether a path lives on a network filesystem (SMB, NFS, ...)
+#[cfg(target_os = "linux")]
+fn is_network_path(path: &Path) -> bool {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return false;
+    };
+    network_mount_for(path, proc_mount_points(&mounts))
+}
+
+/// Mount points and filesystem types listed in `/proc/mounts`, which
+/// escapes spaces in paths as `\040`
+#[cfg(target_os = "linux")]
+fn proc_mount_points(mounts: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    mounts.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let _device = fields.next()?;
+        let mount_point = fields.next()?.replace("\\040", " ");
+        let fs_type = fields.next()?.to_string();
+        Some((mount_point, fs_type))
+    })
+}
+
+/// Whether a path lives on a network filesystem (SMB, NFS, ...)
+#[cfg(target_os = "macos")]
+fn is_network_path(path: &Path) -> bool {
+    let Ok(output) = std::process::Command::new("/sbin/mount").output() else {
+        return false;
+    };
+    // Lines look like: //user@server/share on /Volumes/share (smbfs, nodev, ...)
+    let mounts = String::from_utf8_lossy(&output.stdout);
+    let mount_points = mounts.lines().filter_map(|line| {
+        let (_, rest) = line.split_once(" on ")?;
+        let (mount_point, options) = rest.rsplit_once(" (")?;
+        let fs_type = options.split(',').next()?.trim_end_matches(')').to_string();
+        Some((mount_point.to_string(), fs_type))
+    });
+    network_mount_for(path, mount_points)
+}
+
+/// Whether a path lives on a network filesystem (SMB, NFS, ...)
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn is_network_path(_path: &Path) -> bool {
+    false
+}
+
+/// Find the longest mount point containing `path` and check its filesystem type
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn network_mount_for(path: &Path, mounts: impl Iterator<Item = (String, String)>) -> bool {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    mounts
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .map(|(_, fs_type)| NETWORK_FILESYSTEMS.contains(&fs_type.as_str()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn include_and_exclude_globs_filter_files() {
+        let root = PathBuf::from("/recordings");
+        let config = WatchFolderConfig {
+            id: "folder".to_string(),
+            path: root.to_string_lossy().to_string(),
+            enabled: true,
+            recursive: true,
+            auto_process: true,
+            model_id: None,
+            language: None,
+            diarization_enabled: None,
+            polling_interval_seconds: None,
+            sidecar_formats: Vec::new(),
+            include_globs: vec!["**/*.m4a".to_string(), "*.mp3".to_string()],
+            exclude_globs: vec!["archive/".to_string(), "*-draft.*".to_string()],
+            post_action: WatchFolderPostAction::Keep,
+            transcript_folder: None,
+            transcript_name_template: None,
+            active_hours: None,
+        };
+        let filter = config.path_filter().unwrap();
+
+        assert!(filter.accepts(&root.join("memo.m4a")));
+        assert!(filter.accepts(&root.join("calls/2024/call.M4A")));
+        assert!(filter.accepts(&root.join("calls/call.mp3")));
+        assert!(!filter.accepts(&root.join("calls/call.wav")));
+        assert!(!filter.accepts(&root.join("archive/memo.m4a")));
+        assert!(!filter.accepts(&root.join("calls/archive/old.mp3")));
+        assert!(!filter.accepts(&root.join("memo-draft.m4a")));
+
+        let invalid = WatchFolderConfig {
+            include_globs: vec!["[".to_string()],
+            ..config
+        };
+        assert!(invalid.path_filter().is_err());
+    }
+
+    #[test]
+    fn post_actions_pick_where_files_go() {
+        let root = PathBuf::from("/recordings");
+        let source = root.join("calls/memo.m4a");
+        let mut config = WatchFolderConfig {
+            id: "folder".to_string(),
+            path: root.to_string_lossy().to_string(),
+            enabled: true,
+            recursive: true,
+            auto_process: true,
+            model_id: None,
+            language: None,
+            diarization_enabled: None,
+            polling_interval_seconds: None,
+            sidecar_formats: Vec::new(),
+            include_globs: Vec::new(),
+            exclude_globs: Vec::new(),
+            post_action: WatchFolderPostAction::MoveTo {
+                folder: "done".to_string(),
+            },
+            transcript_folder: Some("transcripts".to_string()),
+            transcript_name_template: None,
+            active_hours: None,
+        };
+
+        assert_eq!(
+            config.post_action_target(&source, "2024-06-01"),
+            Some(root.join("done/memo.m4a"))
+        );
+        assert_eq!(
+            config.transcription_overrides().sidecar_folder,
+            Some(root.join("transcripts").to_string_lossy().to_string())
+        );
+        config.post_action = WatchFolderPostAction::PrefixDate;
+        assert_eq!(
+            config.post_action_target(&source, "2024-06-01"),
+            Some(root.join("calls/2024-06-01 memo.m4a"))
+        );
+        config.post_action = WatchFolderPostAction::Delete;
+        assert_eq!(config.post_action_target(&source, "2024-06-01"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn network_mounts_are_found_by_longest_prefix() {
+        let mounts = "\
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+//nas/media /mnt/nas cifs rw,vers=3.0 0 0
+/dev/sdb1 /mnt/nas/local\\040cache ext4 rw 0 0
+nas:/export/voice\\040memos /mnt/voice\\040memos nfs4 rw 0 0
+";
+        let is_network = |path: &str| network_mount_for(Path::new(path), proc_mount_points(mounts));
+
+        assert!(!is_network("/home/me/recordings"));
+        assert!(is_network("/mnt/nas/recordings"));
+        // A local disk mounted inside the share isn't on the network
+        assert!(!is_network("/mnt/nas/local cache/recordings"));
+        assert!(is_network("/mnt/voice memos/today"));
+        assert!(!is_network("/mnt/voice"));
+    }
+}
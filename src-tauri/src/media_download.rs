@@ -0,0 +1,289 @@
+//! Download media from a URL for file transcription.
+//!
+//! Direct links to audio or video (podcast enclosures, file hosts) are
+//! fetched over HTTP. When the URL turns out to be a web page, as with
+//! YouTube or Vimeo, the download is handed to yt-dlp if it is bundled or on
+//! `PATH`, which picks the best audio-only stream.
+
+use anyhow::{anyhow, Result};
+use futures_util::StreamExt;
+use log::{debug, info};
+use reqwest::header::CONTENT_TYPE;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::audio_toolkit::audio::ffmpeg;
+use crate::managers::file_transcription::FileTranscriptionManager;
+use crate::proxy;
+
+/// Prefix of the progress lines yt-dlp is asked to print
+const YTDLP_PROGRESS_PREFIX: &str = "PROGRESS ";
+
+/// Media types mapped to the extension their downloads are saved with
+const MEDIA_TYPES: &[(&str, &str)] = &[
+    ("audio/mpeg", "mp3"),
+    ("audio/mp3", "mp3"),
+    ("audio/mp4", "m4a"),
+    ("audio/x-m4a", "m4a"),
+    ("audio/aac", "aac"),
+    ("audio/ogg", "ogg"),
+    ("audio/opus", "opus"),
+    ("audio/flac", "flac"),
+    ("audio/x-flac", "flac"),
+    ("audio/wav", "wav"),
+    ("audio/x-wav", "wav"),
+    ("audio/webm", "webm"),
+    ("video/mp4", "mp4"),
+    ("video/quicktime", "mov"),
+    ("video/webm", "webm"),
+    ("video/x-matroska", "mkv"),
+];
+
+/// Called with the fraction downloaded so far, when the size is known
+pub type ProgressFn = Arc<dyn Fn(f32) + Send + Sync>;
+
+/// Check a URL before queueing it
+pub fn validate_url(url: &str) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| anyhow!("Invalid URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        scheme => Err(anyhow!("URL must use http or https, not {}", scheme)),
+    }
+}
+
+/// Download `url` into `dir` as `<stem>.<ext>` and return the file's path
+pub async fn download(
+    url: &str,
+    dir: &Path,
+    stem: &str,
+    cancel_flag: Arc<AtomicBool>,
+    on_progress: ProgressFn,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let client = crate::proxy::client_builder()
+        .user_agent("PaperFlow/1.0 (+https://github.com/solomonshalom/PaperFlow)")
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| anyhow!("Failed to connect: {}", e))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Download failed: HTTP {}", response.status()));
+    }
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    if content_type.starts_with("text/html") {
+        drop(response);
+        return download_with_ytdlp(url, dir, stem, cancel_flag, on_progress).await;
+    }
+
+    let ext = extension_for(response.url().path(), &content_type)
+        .ok_or_else(|| anyhow!("Unsupported media type: {}", content_type))?;
+    let path = dir.join(format!("{}.{}", stem, ext));
+    let temp_path = path.with_extension(format!("{}.part", ext));
+    info!("Downloading {} to {}", url, path.display());
+
+    let total = response.content_length();
+    let mut downloaded: u64 = 0;
+    let mut file = std::fs::File::create(&temp_path)?;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        if cancel_flag.load(Ordering::SeqCst) {
+            drop(file);
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(anyhow!("Download cancelled"));
+        }
+        let chunk = chunk.map_err(|e| anyhow!("Download interrupted: {}", e))?;
+        file.write_all(&chunk)?;
+        downloaded += chunk.len() as u64;
+        if let Some(total) = total.filter(|t| *t > 0) {
+            on_progress((downloaded as f32 / total as f32).min(1.0));
+        }
+    }
+    drop(file);
+
+    std::fs::rename(&temp_path, &path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        anyhow!("Failed to save download: {}", e)
+    })?;
+    Ok(path)
+}
+
+/// Extension to save a download with, from the URL's path or else its media type
+fn extension_for(url_path: &str, content_type: &str) -> Option<String> {
+    let url_path = Path::new(url_path);
+    let from_path = Some(url_path)
+        .filter(|p| FileTranscriptionManager::is_supported_file(p))
+        .and_then(|p| p.extension())
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    from_path.or_else(|| {
+        let media_type = content_type.split(';').next().unwrap_or("").trim();
+        MEDIA_TYPES
+            .iter()
+            .find(|(mime, _)| *mime == media_type)
+            .map(|(_, ext)| ext.to_string())
+    })
+}
+
+/// Download the best audio stream of a web page with yt-dlp
+async fn download_with_ytdlp(
+    url: &str,
+    dir: &Path,
+    stem: &str,
+    cancel_flag: Arc<AtomicBool>,
+    on_progress: ProgressFn,
+) -> Result<PathBuf> {
+    let ytdlp = ffmpeg::find_executable("yt-dlp").ok_or_else(|| {
+        anyhow!(
+            "The URL is a web page, not a media file. Install yt-dlp to download from video sites."
+        )
+    })?;
+    info!("Downloading {} with yt-dlp", url);
+
+    let url = url.to_string();
+    let output_template = dir.join(format!("{}.%(ext)s", stem));
+    tokio::task::spawn_blocking(move || {
+        run_ytdlp(&ytdlp, &url, &output_template, &cancel_flag, &on_progress)
+    })
+    .await
+    .map_err(|e| anyhow!("yt-dlp task failed: {}", e))?
+}
+
+fn run_ytdlp(
+    ytdlp: &Path,
+    url: &str,
+    output_template: &Path,
+    cancel_flag: &AtomicBool,
+    on_progress: &ProgressFn,
+) -> Result<PathBuf> {
+    let mut child = Command::new(ytdlp)
+        .envs(proxy::child_env())
+        .args([
+            "--no-playlist",
+            "--newline",
+            "--progress",
+            "--progress-template",
+            &format!(
+                "download:{}%(progress._percent_str)s",
+                YTDLP_PROGRESS_PREFIX
+            ),
+            "-f",
+            "bestaudio/best",
+            "--print",
+            "after_move:filepath",
+            "-o",
+        ])
+        .arg(output_template)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start yt-dlp: {}", e))?;
+
+    // Progress may arrive on either stream depending on the yt-dlp version
+    let (tx, rx) = mpsc::channel::<String>();
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|stream| {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+                let _ = tx.send(line);
+            }
+        })
+    })
+    .collect();
+    drop(tx);
+
+    let mut path = None;
+    let mut last_line = String::new();
+    loop {
+        if cancel_flag.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("Download cancelled"));
+        }
+        match rx.recv_timeout(Duration::from_millis(200)) {
+            Ok(line) => {
+                if let Some(progress) = parse_ytdlp_progress(&line) {
+                    on_progress(progress);
+                } else if Path::new(line.trim()).is_file() {
+                    path = Some(PathBuf::from(line.trim()));
+                } else if !line.trim().is_empty() {
+                    debug!("yt-dlp: {}", line);
+                    last_line = line;
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow!("yt-dlp failed: {}", last_line.trim()));
+    }
+    path.ok_or_else(|| anyhow!("yt-dlp did not report the downloaded file"))
+}
+
+fn parse_ytdlp_progress(line: &str) -> Option<f32> {
+    let percent = line
+        .trim()
+        .strip_prefix(YTDLP_PROGRESS_PREFIX)?
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f32>()
+        .ok()?;
+    Some((percent / 100.0).clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions_come_from_the_path_or_media_type() {
+        assert_eq!(
+            extension_for("/episodes/42.MP3", "application/octet-stream"),
+            Some("mp3".into())
+        );
+        assert_eq!(
+            extension_for("/feed/latest", "audio/mpeg; charset=binary"),
+            Some("mp3".into())
+        );
+        assert_eq!(extension_for("/feed/latest", "application/json"), None);
+    }
+
+    #[test]
+    fn ytdlp_progress_lines_are_parsed() {
+        assert_eq!(parse_ytdlp_progress("PROGRESS  42.5%"), Some(0.425));
+        assert_eq!(parse_ytdlp_progress("PROGRESS 100.0%"), Some(1.0));
+        assert_eq!(parse_ytdlp_progress("[youtube] abc: Downloading"), None);
+    }
+}
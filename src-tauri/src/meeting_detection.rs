@@ -0,0 +1,176 @@
+//! Notice video calls and suggest meeting mode.
+//!
+//! A monitor thread checks the active app while `meeting_detection_enabled`
+//! is on. When a Zoom, Teams, Meet, ... call window has stayed in front for
+//! `MIN_CALL_DWELL` and no meeting is recording, `meeting-detected` is
+//! emitted so the frontend can offer to start one, or the meeting is
+//! started right away with `meeting_auto_start`. Each service is suggested
+//! at most once per cooldown, so switching back and forth during a call
+//! stays quiet.
+
+use log::{error, info};
+use serde::Serialize;
+use specta::Type;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::actions::ACTION_MAP;
+use crate::context::{detect_video_call, get_active_app};
+use crate::managers::meeting::{MeetingManager, MeetingState};
+use crate::settings::get_settings;
+
+/// How often the active app is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time before the same service is suggested again
+const SUGGESTION_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+/// How long a call window has to stay in front before it is suggested, so
+/// glancing at one doesn't count
+const MIN_CALL_DWELL: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MeetingDetectedEvent {
+    /// Video call service, e.g. "Zoom"
+    pub service: String,
+    /// Name or window title of the app that was detected
+    pub app_name: String,
+    /// Whether the meeting was started without asking
+    pub auto_started: bool,
+}
+
+/// Services suggested recently, and the call in front right now
+#[derive(Debug, Default)]
+struct Suggestions {
+    last: Vec<(&'static str, Instant)>,
+    /// Service of the call window in front, and since when
+    in_front: Option<(&'static str, Instant)>,
+}
+
+impl Suggestions {
+    /// Note the call in front, if any. Returns the service to suggest once
+    /// its call has stayed in front for `MIN_CALL_DWELL`.
+    fn observe(&mut self, service: Option<&'static str>, now: Instant) -> Option<&'static str> {
+        let Some(service) = service else {
+            self.in_front = None;
+            return None;
+        };
+        let since = match self.in_front {
+            Some((seen, since)) if seen == service => since,
+            _ => {
+                self.in_front = Some((service, now));
+                now
+            }
+        };
+        (now.duration_since(since) >= MIN_CALL_DWELL && self.should_suggest(service, now))
+            .then_some(service)
+    }
+
+    /// Whether to suggest `service` now, recording it if so
+    fn should_suggest(&mut self, service: &'static str, now: Instant) -> bool {
+        self.last
+            .retain(|(_, at)| now.duration_since(*at) < SUGGESTION_COOLDOWN);
+        if self.last.iter().any(|(seen, _)| *seen == service) {
+            return false;
+        }
+        self.last.push((service, now));
+        true
+    }
+}
+
+/// Watch for video calls
+pub fn start_monitor(app: &AppHandle) {
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("meeting-detection".into())
+        .spawn(move || {
+            let mut suggestions = Suggestions::default();
+            loop {
+                thread::sleep(CHECK_INTERVAL);
+                check(&app, &mut suggestions);
+            }
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn meeting detection monitor: {}", e);
+    }
+}
+
+fn check(app: &AppHandle, suggestions: &mut Suggestions) {
+    let settings = get_settings(app);
+    if !settings.meeting_mode_enabled || !settings.meeting_detection_enabled {
+        return;
+    }
+    let Some(mm) = app.try_state::<Arc<MeetingManager>>() else {
+        return;
+    };
+    if !matches!(mm.get_meeting_state(), MeetingState::Idle) {
+        return;
+    }
+
+    let Some(active_app) = get_active_app() else {
+        return;
+    };
+    let detected = detect_video_call(&active_app);
+    let Some(service) = suggestions.observe(detected, Instant::now()) else {
+        return;
+    };
+
+    info!("Detected a {} call in '{}'", service, active_app.name);
+    let auto_started = settings.meeting_auto_start && start_meeting(app);
+    let event = MeetingDetectedEvent {
+        service: service.to_string(),
+        app_name: active_app.name,
+        auto_started,
+    };
+    if let Err(e) = event.emit(app) {
+        error!("Failed to emit meeting detected event: {}", e);
+    }
+}
+
+/// Start a meeting the way the meeting shortcut does
+fn start_meeting(app: &AppHandle) -> bool {
+    let binding_id = "meeting";
+    let Some(action) = ACTION_MAP.get(binding_id) else {
+        error!("No action defined in ACTION_MAP for binding ID '{binding_id}'");
+        return false;
+    };
+    action.start(app, binding_id, "meeting-detection");
+    app.try_state::<Arc<MeetingManager>>()
+        .is_some_and(|mm| matches!(mm.get_meeting_state(), MeetingState::Recording { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_are_suggested_once_per_cooldown() {
+        let mut suggestions = Suggestions::default();
+        let start = Instant::now();
+        assert!(suggestions.should_suggest("Zoom", start));
+        assert!(!suggestions.should_suggest("Zoom", start + Duration::from_secs(60)));
+        assert!(suggestions.should_suggest("Webex", start + Duration::from_secs(60)));
+        assert!(suggestions.should_suggest("Zoom", start + SUGGESTION_COOLDOWN));
+    }
+
+    #[test]
+    fn calls_are_suggested_after_staying_in_front() {
+        let mut suggestions = Suggestions::default();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        assert_eq!(suggestions.observe(Some("Zoom"), at(0)), None);
+        assert_eq!(suggestions.observe(Some("Zoom"), at(10)), None);
+        assert_eq!(suggestions.observe(Some("Zoom"), at(20)), Some("Zoom"));
+        assert_eq!(suggestions.observe(Some("Zoom"), at(30)), None);
+
+        // Looking away restarts the wait
+        assert_eq!(suggestions.observe(Some("Webex"), at(40)), None);
+        assert_eq!(suggestions.observe(None, at(50)), None);
+        assert_eq!(suggestions.observe(Some("Webex"), at(60)), None);
+        assert_eq!(suggestions.observe(Some("Webex"), at(80)), Some("Webex"));
+    }
+}
@@ -0,0 +1,323 @@
+//! Prometheus-style metrics endpoint.
+//!
+//! Counters are process-wide atomics bumped from the transcription path.
+//! When enabled in settings, a small HTTP listener bound to 127.0.0.1 serves
+//! them at `/metrics` in the Prometheus text format, together with gauges
+//! read from the managers at scrape time.
+
+use anyhow::Result;
+use log::{debug, info, warn};
+use std::fmt::Write as _;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::file_transcription::{FileTranscriptionManager, FileTranscriptionStatus};
+use crate::managers::meeting::{MeetingManager, MeetingState};
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+
+/// How long the accept loop sleeps when no connection is waiting
+const ACCEPT_POLL: Duration = Duration::from_millis(200);
+/// Read/write timeout for a single scrape
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+static TRANSCRIPTIONS: AtomicU64 = AtomicU64::new(0);
+static TRANSCRIPTION_ERRORS: AtomicU64 = AtomicU64::new(0);
+static INFERENCE_MICROS: AtomicU64 = AtomicU64::new(0);
+static AUDIO_MILLIS: AtomicU64 = AtomicU64::new(0);
+
+/// Count a finished transcription and the time spent on it
+pub fn record_transcription(audio_seconds: f64, inference: Duration) {
+    TRANSCRIPTIONS.fetch_add(1, Ordering::Relaxed);
+    INFERENCE_MICROS.fetch_add(inference.as_micros() as u64, Ordering::Relaxed);
+    AUDIO_MILLIS.fetch_add((audio_seconds * 1000.0) as u64, Ordering::Relaxed);
+}
+
+/// Count a transcription that returned an error
+pub fn record_transcription_error() {
+    TRANSCRIPTION_ERRORS.fetch_add(1, Ordering::Relaxed);
+}
+
+struct RunningServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+/// Serves `/metrics` on localhost while the setting is enabled
+pub struct MetricsServer {
+    app_handle: AppHandle,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl MetricsServer {
+    pub fn new(app_handle: &AppHandle) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Start, restart or stop the listener to match the current settings
+    pub fn apply_settings(&self) -> Result<()> {
+        let settings = get_settings(&self.app_handle);
+        let mut running = self.running.lock().unwrap();
+
+        let wanted_port = settings
+            .metrics_endpoint_enabled
+            .then_some(settings.metrics_endpoint_port);
+        if running.as_ref().map(|server| server.port) == wanted_port {
+            return Ok(());
+        }
+
+        if let Some(server) = running.take() {
+            server.stop.store(true, Ordering::Relaxed);
+            let _ = server.thread.join();
+            info!("Metrics endpoint stopped");
+        }
+
+        let Some(port) = wanted_port else {
+            return Ok(());
+        };
+
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let app_handle = self.app_handle.clone();
+        let thread_stop = stop.clone();
+        let thread = thread::Builder::new()
+            .name("metrics-endpoint".into())
+            .spawn(move || serve(listener, &app_handle, &thread_stop))?;
+
+        info!(
+            "Metrics endpoint listening on http://127.0.0.1:{}/metrics",
+            port
+        );
+        *running = Some(RunningServer { port, stop, thread });
+        Ok(())
+    }
+
+    pub fn shutdown(&self) {
+        if let Some(server) = self.running.lock().unwrap().take() {
+            server.stop.store(true, Ordering::Relaxed);
+            let _ = server.thread.join();
+        }
+    }
+}
+
+fn serve(listener: TcpListener, app: &AppHandle, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = handle_connection(stream, app) {
+                    debug!("Metrics request failed: {}", e);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(ACCEPT_POLL),
+            Err(e) => {
+                warn!("Metrics endpoint accept error: {}", e);
+                thread::sleep(ACCEPT_POLL);
+            }
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, app: &AppHandle) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+
+    // Only the request line matters; scrapers send small GET requests
+    let mut buf = [0u8; 1024];
+    let len = stream.read(&mut buf)?;
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let mut parts = request.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            render(app),
+        ),
+        ("GET", _) => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method not allowed\n".to_string(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Render all metrics in the Prometheus text exposition format
+fn render(app: &AppHandle) -> String {
+    let mut out = String::new();
+    render_counters(&mut out);
+
+    if let Some(files) = app.try_state::<Arc<FileTranscriptionManager>>() {
+        let statuses: Vec<FileTranscriptionStatus> =
+            files.get_jobs().into_iter().map(|job| job.status).collect();
+        file_jobs(&mut out, &statuses);
+    }
+
+    if let Some(recording) = app.try_state::<Arc<AudioRecordingManager>>() {
+        gauge(
+            &mut out,
+            "paperflow_recording_active",
+            "Whether dictation is recording",
+            recording.is_recording() as u8 as f64,
+        );
+    }
+
+    if let Some(meetings) = app.try_state::<Arc<MeetingManager>>() {
+        let active = !matches!(meetings.get_meeting_state(), MeetingState::Idle);
+        gauge(
+            &mut out,
+            "paperflow_meeting_active",
+            "Whether a meeting is recording or being processed",
+            active as u8 as f64,
+        );
+    }
+
+    if let Some(transcription) = app.try_state::<Arc<TranscriptionManager>>() {
+        gauge(
+            &mut out,
+            "paperflow_model_loaded",
+            "Whether a transcription model is loaded",
+            transcription.is_model_loaded() as u8 as f64,
+        );
+    }
+
+    out
+}
+
+/// The process-wide transcription counters
+fn render_counters(out: &mut String) {
+    counter(
+        &mut out,
+        "paperflow_transcriptions_total",
+        "Completed transcriptions",
+        TRANSCRIPTIONS.load(Ordering::Relaxed) as f64,
+    );
+    counter(
+        &mut out,
+        "paperflow_transcription_errors_total",
+        "Transcriptions that failed",
+        TRANSCRIPTION_ERRORS.load(Ordering::Relaxed) as f64,
+    );
+    counter(
+        &mut out,
+        "paperflow_inference_seconds_total",
+        "Time spent transcribing",
+        INFERENCE_MICROS.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+    );
+    counter(
+        &mut out,
+        "paperflow_audio_seconds_total",
+        "Duration of audio transcribed",
+        AUDIO_MILLIS.load(Ordering::Relaxed) as f64 / 1000.0,
+    );
+}
+
+/// Gauge of the file transcription jobs with each status
+fn file_jobs(out: &mut String, statuses: &[FileTranscriptionStatus]) {
+    let _ = writeln!(
+        out,
+        "# HELP paperflow_file_jobs File transcription jobs by status"
+    );
+    let _ = writeln!(out, "# TYPE paperflow_file_jobs gauge");
+    for (status, label) in [
+        (FileTranscriptionStatus::Queued, "queued"),
+        (FileTranscriptionStatus::Processing, "processing"),
+        (FileTranscriptionStatus::Completed, "completed"),
+        (FileTranscriptionStatus::Failed, "failed"),
+        (FileTranscriptionStatus::Cancelled, "cancelled"),
+    ] {
+        let count = statuses.iter().filter(|s| **s == status).count();
+        let _ = writeln!(out, "paperflow_file_jobs{{status=\"{}\"}} {}", label, count);
+    }
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: f64) {
+    metric(out, name, help, "counter", value);
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: f64) {
+    metric(out, name, help, "gauge", value);
+}
+
+fn metric(out: &mut String, name: &str, help: &str, kind: &str, value: f64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_render_in_exposition_format() {
+        record_transcription(90.0, Duration::from_millis(1500));
+        record_transcription(30.0, Duration::from_millis(500));
+        record_transcription_error();
+
+        let mut out = String::new();
+        render_counters(&mut out);
+        file_jobs(
+            &mut out,
+            &[
+                FileTranscriptionStatus::Queued,
+                FileTranscriptionStatus::Queued,
+                FileTranscriptionStatus::Completed,
+            ],
+        );
+        gauge(
+            &mut out,
+            "paperflow_recording_active",
+            "Whether dictation is recording",
+            1.0,
+        );
+
+        let lines: Vec<&str> = out.lines().collect();
+        for expected in [
+            "# HELP paperflow_transcriptions_total Completed transcriptions",
+            "# TYPE paperflow_transcriptions_total counter",
+            "paperflow_transcriptions_total 2",
+            "paperflow_transcription_errors_total 1",
+            "paperflow_inference_seconds_total 2",
+            "paperflow_audio_seconds_total 120",
+            "# TYPE paperflow_file_jobs gauge",
+            "paperflow_file_jobs{status=\"queued\"} 2",
+            "paperflow_file_jobs{status=\"processing\"} 0",
+            "paperflow_file_jobs{status=\"completed\"} 1",
+            "# HELP paperflow_recording_active Whether dictation is recording",
+            "# TYPE paperflow_recording_active gauge",
+            "paperflow_recording_active 1",
+        ] {
+            assert!(
+                lines.contains(&expected),
+                "missing {:?} in:\n{}",
+                expected,
+                out
+            );
+        }
+    }
+}
@@ -1,7 +1,10 @@
+use crate::events::{HideOverlay, MicLevel, ShowOverlay};
+use crate::focus_mode;
 use crate::input;
 use crate::settings;
 use crate::settings::OverlayPosition;
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize};
+use tauri_specta::Event;
 
 #[cfg(not(target_os = "macos"))]
 use log::debug;
@@ -211,6 +214,10 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
     if settings.overlay_position == OverlayPosition::None {
         return;
     }
+    // Keep it off screen during a Focus or screen share
+    if focus_mode::should_suppress_overlay(app_handle) {
+        return;
+    }
 
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Update position before showing to prevent flicker from position changes
@@ -226,7 +233,7 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
         force_overlay_topmost(&overlay_window);
 
         // Emit event to trigger fade-in animation with recording state
-        let _ = overlay_window.emit("show-overlay", "recording");
+        let _ = ShowOverlay::Recording.emit(&overlay_window);
     }
 }
 
@@ -237,6 +244,10 @@ pub fn show_meeting_overlay(app_handle: &AppHandle) {
     if settings.overlay_position == OverlayPosition::None {
         return;
     }
+    // Keep it off screen during a Focus or screen share
+    if focus_mode::should_suppress_overlay(app_handle) {
+        return;
+    }
 
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Update position before showing to prevent flicker from position changes
@@ -252,7 +263,7 @@ pub fn show_meeting_overlay(app_handle: &AppHandle) {
         force_overlay_topmost(&overlay_window);
 
         // Emit event to trigger meeting state
-        let _ = overlay_window.emit("show-overlay", "meeting");
+        let _ = ShowOverlay::Meeting.emit(&overlay_window);
     }
 }
 
@@ -263,6 +274,10 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
     if settings.overlay_position == OverlayPosition::None {
         return;
     }
+    // Keep it off screen during a Focus or screen share
+    if focus_mode::should_suppress_overlay(app_handle) {
+        return;
+    }
 
     update_overlay_position(app_handle);
 
@@ -274,7 +289,7 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
         force_overlay_topmost(&overlay_window);
 
         // Emit event to switch to transcribing state
-        let _ = overlay_window.emit("show-overlay", "transcribing");
+        let _ = ShowOverlay::Transcribing.emit(&overlay_window);
     }
 }
 
@@ -294,7 +309,7 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     // we still want to hide it properly
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Emit event to trigger fade-out animation
-        let _ = overlay_window.emit("hide-overlay", ());
+        let _ = HideOverlay.emit(&overlay_window);
         // Hide the window after a short delay to allow animation to complete
         let window_clone = overlay_window.clone();
         std::thread::spawn(move || {
@@ -304,12 +319,14 @@ pub fn hide_recording_overlay(app_handle: &AppHandle) {
     }
 }
 
-pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
+pub fn emit_levels(app_handle: &AppHandle, levels: &[f32]) {
+    let event = MicLevel(levels.to_vec());
+
     // emit levels to main app
-    let _ = app_handle.emit("mic-level", levels);
+    let _ = event.emit(app_handle);
 
     // also emit to the recording overlay if it's open
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
-        let _ = overlay_window.emit("mic-level", levels);
+        let _ = event.emit(&overlay_window);
     }
 }
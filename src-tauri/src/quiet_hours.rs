@@ -0,0 +1,78 @@
+//! App-level "do not disturb" schedule.
+//!
+//! While quiet hours are active, feedback sounds are muted and files picked up
+//! by watch folders are held instead of being transcribed right away. Manual
+//! dictation keeps working. A monitor thread emits `quiet-hours-changed` on
+//! every transition so the frontend can hold back its own toasts, and queues
+//! the held files once the window ends.
+
+use chrono::{Datelike, Local, Timelike};
+use log::{error, info};
+use serde::Serialize;
+use specta::Type;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::managers::watch_folder::WatchFolderManager;
+use crate::settings::{get_settings, DailyWindow};
+
+/// How often the monitor re-evaluates the schedule
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct QuietHoursChangedEvent {
+    pub active: bool,
+}
+
+/// Whether quiet hours are in effect right now
+pub fn is_active(app: &AppHandle) -> bool {
+    is_active_now(&get_settings(app).quiet_hours)
+}
+
+/// Whether a daily window covers the current local time
+pub fn is_active_now(window: &DailyWindow) -> bool {
+    let now = Local::now();
+    let weekday = now.weekday().num_days_from_monday() as u8;
+    let minute = (now.hour() * 60 + now.minute()) as u16;
+    window.is_active_at(weekday, minute)
+}
+
+/// Watch the schedule and react when quiet hours start or end
+pub fn start_monitor(app: &AppHandle) {
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("quiet-hours".into())
+        .spawn(move || {
+            let mut was_active = false;
+            loop {
+                let active = is_active(&app);
+                if active != was_active {
+                    on_transition(&app, active);
+                    was_active = active;
+                }
+                thread::sleep(CHECK_INTERVAL);
+            }
+        });
+
+    if let Err(e) = spawned {
+        error!("Failed to spawn quiet hours monitor: {}", e);
+    }
+}
+
+fn on_transition(app: &AppHandle, active: bool) {
+    if active {
+        info!("Quiet hours started");
+    } else {
+        info!("Quiet hours ended");
+        if let Some(watch_folders) = app.try_state::<Arc<WatchFolderManager>>() {
+            watch_folders.release_deferred_files();
+        }
+    }
+
+    if let Err(e) = (QuietHoursChangedEvent { active }).emit(app) {
+        error!("Failed to emit quiet hours change: {}", e);
+    }
+}
@@ -0,0 +1,202 @@
+//! `recording://` protocol the history and meeting players stream audio from.
+//!
+//! Recordings are read, decrypted and, for FLAC, decoded to WAV in memory,
+//! so playing back an encrypted archive never leaves plaintext audio on
+//! disk. The address is the recording's file name, prefixed with `wav/` when
+//! the player needs WAV. Range requests are answered so players can seek.
+
+use log::warn;
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{Manager, Runtime, UriSchemeContext, UriSchemeResponder};
+
+use crate::managers::history::HistoryManager;
+
+pub const SCHEME: &str = "recording";
+
+const WAV_PREFIX: &str = "wav/";
+
+/// Players issue several range requests per recording, so the last one
+/// served is kept instead of decrypting and decoding it for each
+static LAST_SERVED: Mutex<Option<(String, Arc<Vec<u8>>)>> = Mutex::new(None);
+
+/// Protocol address of a recording, as the raw file or decoded to WAV
+pub fn address(file_name: &str, as_wav: bool) -> String {
+    if as_wav {
+        format!("{}{}", WAV_PREFIX, file_name)
+    } else {
+        file_name.to_string()
+    }
+}
+
+/// Handler for `register_asynchronous_uri_scheme_protocol`
+pub fn handle<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let Some(history) = ctx
+        .app_handle()
+        .try_state::<Arc<HistoryManager>>()
+        .map(|state| state.inner().clone())
+    else {
+        responder.respond(status(StatusCode::SERVICE_UNAVAILABLE));
+        return;
+    };
+    let address = percent_decode(request.uri().path().trim_start_matches('/'));
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    // Decoding a long FLAC recording takes a while; keep it off the webview's thread
+    std::thread::spawn(move || {
+        let response = match load(&history, &address) {
+            Ok(data) => respond_with(&data, range.as_deref()),
+            Err(e) => {
+                warn!("Failed to serve recording '{}': {}", address, e);
+                status(StatusCode::NOT_FOUND)
+            }
+        };
+        responder.respond(response);
+    });
+}
+
+fn load(history: &HistoryManager, address: &str) -> anyhow::Result<Arc<Vec<u8>>> {
+    if let Some((cached, data)) = LAST_SERVED.lock().unwrap().as_ref() {
+        if cached == address {
+            return Ok(data.clone());
+        }
+    }
+
+    let (file_name, as_wav) = match address.strip_prefix(WAV_PREFIX) {
+        Some(file_name) => (file_name, true),
+        None => (address, false),
+    };
+    if file_name.is_empty() || file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+        return Err(anyhow::anyhow!("Invalid recording name"));
+    }
+
+    let data = Arc::new(if as_wav {
+        history.read_recording_as_wav(file_name)?
+    } else {
+        history.read_recording(file_name)?
+    });
+    *LAST_SERVED.lock().unwrap() = Some((address.to_string(), data.clone()));
+    Ok(data)
+}
+
+fn respond_with(data: &[u8], range: Option<&str>) -> Response<Cow<'static, [u8]>> {
+    let total = data.len();
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, content_type(data))
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    let response = match range.map(|range| parse_range(range, total)) {
+        None => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, total)
+            .body(Cow::Owned(data.to_vec())),
+        Some(Some((start, end))) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_LENGTH, end - start + 1)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, total),
+            )
+            .body(Cow::Owned(data[start..=end].to_vec())),
+        Some(None) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", total))
+            .body(Cow::Borrowed(&[][..])),
+    };
+    response.unwrap_or_else(|_| status(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+fn status(code: StatusCode) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(code)
+        .body(Cow::Borrowed(&[][..]))
+        .unwrap()
+}
+
+fn content_type(data: &[u8]) -> &'static str {
+    if data.starts_with(b"fLaC") {
+        "audio/flac"
+    } else {
+        "audio/wav"
+    }
+}
+
+/// Inclusive byte range of a single-range `Range` header, `None` when it
+/// cannot be satisfied
+fn parse_range(header: &str, total: usize) -> Option<(usize, usize)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    // Players only ever ask for one range
+    let (start, end) = spec.split(',').next()?.trim().split_once('-')?;
+    let last = total.checked_sub(1)?;
+
+    let (start, end) = if start.is_empty() {
+        // Suffix range: the last N bytes
+        let len: usize = end.parse().ok()?;
+        (total.saturating_sub(len), last)
+    } else {
+        let start: usize = start.parse().ok()?;
+        let end = if end.is_empty() {
+            last
+        } else {
+            end.parse::<usize>().ok()?.min(last)
+        };
+        (start, end)
+    };
+    (start <= end).then_some((start, end))
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_are_clamped_to_the_recording() {
+        assert_eq!(parse_range("bytes=0-", 100), Some((0, 99)));
+        assert_eq!(parse_range("bytes=10-19", 100), Some((10, 19)));
+        assert_eq!(parse_range("bytes=90-200", 100), Some((90, 99)));
+        assert_eq!(parse_range("bytes=-10", 100), Some((90, 99)));
+        assert_eq!(parse_range("bytes=100-", 100), None);
+        assert_eq!(parse_range("bytes=0-", 0), None);
+        assert_eq!(parse_range("items=0-1", 100), None);
+    }
+
+    #[test]
+    fn addresses_survive_url_encoding() {
+        let address = address("paperflow-1700000000.flac.enc", true);
+        let encoded = address.replace('/', "%2F");
+        assert_eq!(percent_decode(&encoded), address);
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+}
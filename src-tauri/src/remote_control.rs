@@ -0,0 +1,177 @@
+//! Driving PaperFlow from Stream Deck plugins, scripts and other controllers.
+//!
+//! Running the app again with `--command <name>` while it is open hands the
+//! arguments to the running instance over the single-instance channel, which
+//! runs the command instead of showing the main window:
+//!
+//! ```text
+//! paperflow --command toggle-dictation
+//! ```
+//!
+//! Nothing listens on the network; only processes that can already start
+//! the app as the user can send commands.
+
+use log::{debug, info, warn};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::actions::ACTION_MAP;
+use crate::cycle::{self, CycleKind};
+use crate::managers::meeting::{MeetingManager, MeetingState};
+
+/// Passed to the actions in place of a shortcut string
+const SOURCE: &str = "remote-control";
+
+const COMMAND_FLAG: &str = "--command";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteCommand {
+    ToggleDictation,
+    StartDictation,
+    StopDictation,
+    Cancel,
+    ToggleMeeting,
+    StartMeeting,
+    StopMeeting,
+    PasteLastTranscript,
+    ToggleLivePreview,
+    CycleMicrophone,
+    CycleModel,
+    CycleLanguage,
+}
+
+const COMMANDS: &[(&str, RemoteCommand)] = &[
+    ("toggle-dictation", RemoteCommand::ToggleDictation),
+    ("start-dictation", RemoteCommand::StartDictation),
+    ("stop-dictation", RemoteCommand::StopDictation),
+    ("cancel", RemoteCommand::Cancel),
+    ("toggle-meeting", RemoteCommand::ToggleMeeting),
+    ("start-meeting", RemoteCommand::StartMeeting),
+    ("stop-meeting", RemoteCommand::StopMeeting),
+    ("paste-last-transcript", RemoteCommand::PasteLastTranscript),
+    ("toggle-live-preview", RemoteCommand::ToggleLivePreview),
+    ("cycle-microphone", RemoteCommand::CycleMicrophone),
+    ("cycle-model", RemoteCommand::CycleModel),
+    ("cycle-language", RemoteCommand::CycleLanguage),
+];
+
+/// The command in a launch's arguments, as `--command <name>` or
+/// `--command=<name>`. `None` when there is no command flag.
+pub fn parse_args(args: &[String]) -> Option<Result<RemoteCommand, String>> {
+    let mut args = args.iter();
+    let name = loop {
+        let arg = args.next()?;
+        if arg == COMMAND_FLAG {
+            break args.next().map(String::as_str).unwrap_or_default();
+        }
+        if let Some(name) = arg
+            .strip_prefix(COMMAND_FLAG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            break name;
+        }
+    };
+
+    let name = name.trim().to_lowercase();
+    Some(
+        COMMANDS
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, command)| *command)
+            .ok_or_else(|| format!("Unknown command '{}'", name)),
+    )
+}
+
+/// Handle the arguments of a second launch. Returns false when they hold
+/// no command, so the caller can fall back to showing the window.
+pub fn handle_args(app: &AppHandle, args: &[String]) -> bool {
+    match parse_args(args) {
+        None => false,
+        Some(Err(e)) => {
+            warn!("Ignoring remote command: {}", e);
+            true
+        }
+        Some(Ok(command)) => {
+            // Actions may block, keep the single-instance callback free
+            let app = app.clone();
+            std::thread::spawn(move || run(&app, command));
+            true
+        }
+    }
+}
+
+fn run_action(app: &AppHandle, binding_id: &str, pressed: bool) {
+    let Some(action) = ACTION_MAP.get(binding_id) else {
+        warn!("No action defined in ACTION_MAP for binding ID '{binding_id}'");
+        return;
+    };
+    if pressed {
+        action.start(app, binding_id, SOURCE);
+    } else {
+        action.stop(app, binding_id, SOURCE);
+    }
+}
+
+fn meeting_running(app: &AppHandle) -> Option<bool> {
+    let mm = app.try_state::<Arc<MeetingManager>>()?;
+    match mm.get_meeting_state() {
+        MeetingState::Idle => Some(false),
+        MeetingState::Recording { .. } | MeetingState::Paused { .. } => Some(true),
+        MeetingState::Processing { .. } => None,
+    }
+}
+
+pub fn run(app: &AppHandle, command: RemoteCommand) {
+    info!("Remote command: {:?}", command);
+    match command {
+        RemoteCommand::ToggleDictation => crate::input::toggle_transcription(app, SOURCE),
+        RemoteCommand::StartDictation => crate::input::set_transcription(app, true, SOURCE),
+        RemoteCommand::StopDictation => crate::input::set_transcription(app, false, SOURCE),
+        RemoteCommand::Cancel => crate::utils::cancel_current_operation(app),
+        RemoteCommand::ToggleMeeting => run_action(app, "meeting", true),
+        RemoteCommand::StartMeeting | RemoteCommand::StopMeeting => {
+            let start = command == RemoteCommand::StartMeeting;
+            // The meeting action toggles, so only run it to change the state
+            match meeting_running(app) {
+                Some(running) if running != start => run_action(app, "meeting", true),
+                _ => debug!("Meeting already in the requested state"),
+            }
+        }
+        // The action pastes on release, once the shortcut's keys are up
+        RemoteCommand::PasteLastTranscript => run_action(app, "paste_last_transcript", false),
+        RemoteCommand::ToggleLivePreview => run_action(app, "toggle_live_preview", true),
+        RemoteCommand::CycleMicrophone => cycle::cycle(app, CycleKind::Microphone),
+        RemoteCommand::CycleModel => cycle::cycle(app, CycleKind::Model),
+        RemoteCommand::CycleLanguage => cycle::cycle(app, CycleKind::Language),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_finds_the_command_flag() {
+        assert_eq!(
+            parse_args(&args(&["paperflow", "--command", "toggle-dictation"])),
+            Some(Ok(RemoteCommand::ToggleDictation))
+        );
+        assert_eq!(
+            parse_args(&args(&["paperflow", "--command=Start-Meeting"])),
+            Some(Ok(RemoteCommand::StartMeeting))
+        );
+        assert!(matches!(
+            parse_args(&args(&["paperflow", "--command", "explode"])),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            parse_args(&args(&["paperflow", "--command"])),
+            Some(Err(_))
+        ));
+        assert_eq!(parse_args(&args(&["paperflow"])), None);
+    }
+}
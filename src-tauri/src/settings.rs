@@ -1,15 +1,25 @@
-use crate::context::ToneStyle;
+use crate::audio_toolkit::{
+    FillerFilter, ItnLocale, ProfanityFilter, RegexRule, WordMatchAlgorithm, DEFAULT_FILLER_WORDS,
+};
+use crate::context::{categorize_app, AppCategory, ToneStyle};
+use crate::managers::settings_writer::{self, SettingsWriter};
 use crate::managers::snippets::Snippet;
 use log::{debug, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
 use std::collections::HashMap;
-use tauri::AppHandle;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_store::StoreExt;
 
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
 pub const APPLE_INTELLIGENCE_DEFAULT_MODEL_ID: &str = "Apple Intelligence";
+/// Post-processing with a downloaded GGUF model in the embedded llama.cpp
+pub const LOCAL_LLM_PROVIDER_ID: &str = "local_llm";
+/// Providers spoken to through their own API instead of the OpenAI one
+pub const ANTHROPIC_PROVIDER_ID: &str = "anthropic";
+pub const GEMINI_PROVIDER_ID: &str = "gemini";
 
 #[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
@@ -87,6 +97,17 @@ pub struct ShortcutBinding {
     pub current_binding: String,
 }
 
+/// A named set of shortcuts, like one for the laptop keyboard and one for an
+/// external keyboard
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct ShortcutProfile {
+    pub id: String,
+    pub name: String,
+    /// Shortcut per binding id
+    #[serde(default)]
+    pub bindings: HashMap<String, String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct LLMPrompt {
     pub id: String,
@@ -94,6 +115,83 @@ pub struct LLMPrompt {
     pub prompt: String,
 }
 
+/// One step of a post-processing pipeline: a prompt run on its own
+/// provider and model, with `${output}` standing for the previous step's text
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct PipelineStep {
+    pub name: String,
+    pub prompt: String,
+    pub provider_id: String,
+    pub model: String,
+}
+
+/// Prompts run one after another, e.g. clean up, translate, format as email
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct PostProcessPipeline {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<PipelineStep>,
+}
+
+/// A stage of local text processing, run on every transcription in the
+/// order the pipeline lists them
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TextStage {
+    Spelling,
+    CustomWords,
+    Filter,
+    Corrections,
+    Itn,
+    Snippets,
+    RegexRules,
+    IdentifierCasing,
+    Formatting,
+}
+
+impl TextStage {
+    /// Every stage, in the default order
+    pub const ALL: [TextStage; 9] = [
+        TextStage::Spelling,
+        TextStage::CustomWords,
+        TextStage::Filter,
+        TextStage::Corrections,
+        TextStage::Itn,
+        TextStage::Snippets,
+        TextStage::RegexRules,
+        TextStage::IdentifierCasing,
+        TextStage::Formatting,
+    ];
+}
+
+/// A stage's place in the text pipeline. A disabled stage is skipped even
+/// when its own setting is on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+pub struct TextStageConfig {
+    pub stage: TextStage,
+    pub enabled: bool,
+}
+
+/// `stages` with duplicates dropped and any stage missing, such as one added
+/// in a later version, appended enabled
+pub fn normalize_text_pipeline(stages: &[TextStageConfig]) -> Vec<TextStageConfig> {
+    let mut normalized: Vec<TextStageConfig> = Vec::new();
+    for config in stages {
+        if !normalized.iter().any(|c| c.stage == config.stage) {
+            normalized.push(*config);
+        }
+    }
+    for stage in TextStage::ALL {
+        if !normalized.iter().any(|c| c.stage == stage) {
+            normalized.push(TextStageConfig {
+                stage,
+                enabled: true,
+            });
+        }
+    }
+    normalized
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct PostProcessProvider {
     pub id: String,
@@ -153,6 +251,409 @@ pub enum RecordingRetentionPeriod {
     Months3,
 }
 
+/// Container used for history recordings
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    Wav,
+    /// Lossless and roughly half the size of WAV
+    #[default]
+    Flac,
+}
+
+/// Proxy used for every outbound HTTP request: cloud transcription,
+/// post-processing and model downloads
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct ProxySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// `http://`, `https://`, `socks5://` or `socks5h://` URL with port
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Hosts, domains (`.corp.example`) and CIDR ranges reached directly
+    #[serde(default = "default_proxy_bypass")]
+    pub bypass: Vec<String>,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            username: None,
+            password: None,
+            bypass: default_proxy_bypass(),
+        }
+    }
+}
+
+/// Custom words used only when dictating into some apps, on top of the global
+/// custom words
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Type)]
+pub struct CustomWordSet {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// App categories the set applies in, like every messaging app
+    #[serde(default)]
+    pub categories: Vec<AppCategory>,
+    /// App names the set applies in, on top of its categories
+    #[serde(default)]
+    pub apps: Vec<String>,
+    #[serde(default = "default_custom_word_set_enabled")]
+    pub enabled: bool,
+}
+
+impl CustomWordSet {
+    /// Whether the set is used when dictating into `app_name`
+    pub fn applies_in(&self, app_name: Option<&str>) -> bool {
+        let Some(name) = app_name else {
+            return false;
+        };
+        self.enabled
+            && (self.apps.iter().any(|app| app == name)
+                || self.categories.contains(&categorize_app(name)))
+    }
+}
+
+/// Settings used instead of the global ones when dictating into an app or
+/// website. Unset fields keep the global setting.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Type)]
+pub struct AppProfile {
+    /// Language to transcribe in
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Model to transcribe with, loaded when the recording starts
+    #[serde(default)]
+    pub model_id: Option<String>,
+    /// Prompt to post-process with, instead of the selected prompt or
+    /// pipeline
+    #[serde(default)]
+    pub post_process_prompt_id: Option<String>,
+    #[serde(default)]
+    pub tone: Option<ToneStyle>,
+    /// Formatting stages and their order
+    #[serde(default)]
+    pub text_pipeline: Option<Vec<TextStageConfig>>,
+    #[serde(default)]
+    pub paste_method: Option<PasteMethod>,
+}
+
+/// How stutters like "wh wh wh" are collapsed to a single word
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct StutterSettings {
+    /// Longest word, in letters, that is collapsed when repeated
+    #[serde(default = "default_stutter_max_word_length")]
+    pub max_word_length: usize,
+    /// Repetitions in a row that count as a stutter
+    #[serde(default = "default_stutter_min_repetitions")]
+    pub min_repetitions: usize,
+    /// Also collapse longer repeated words, like "the the the"
+    #[serde(default)]
+    pub collapse_repeated_words: bool,
+}
+
+impl Default for StutterSettings {
+    fn default() -> Self {
+        Self {
+            max_word_length: default_stutter_max_word_length(),
+            min_repetitions: default_stutter_min_repetitions(),
+            collapse_repeated_words: false,
+        }
+    }
+}
+
+/// Recurring daily span of local time, such as quiet hours or a watch
+/// folder's active hours. Times are minutes after local midnight; a window
+/// whose end is earlier than its start runs past midnight.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct DailyWindow {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_window_start")]
+    pub start_minute: u16,
+    #[serde(default = "default_window_end")]
+    pub end_minute: u16,
+    /// Days the window starts on, 0 = Monday through 6 = Sunday. Empty means every day.
+    #[serde(default)]
+    pub days: Vec<u8>,
+}
+
+impl Default for DailyWindow {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_minute: default_window_start(),
+            end_minute: default_window_end(),
+            days: Vec::new(),
+        }
+    }
+}
+
+impl DailyWindow {
+    /// Check the times fall within a day and the days are real weekdays
+    pub fn validate(&self) -> Result<(), String> {
+        const MINUTES_PER_DAY: u16 = 24 * 60;
+        if self.start_minute >= MINUTES_PER_DAY || self.end_minute >= MINUTES_PER_DAY {
+            return Err("Times must be within a single day".to_string());
+        }
+        if self.enabled && self.start_minute == self.end_minute {
+            return Err("The window must start and end at different times".to_string());
+        }
+        if self.days.iter().any(|day| *day > 6) {
+            return Err("Days must be between 0 (Monday) and 6 (Sunday)".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the window covers the given weekday (0 = Monday) and minute of the day
+    pub fn is_active_at(&self, weekday: u8, minute: u16) -> bool {
+        if !self.enabled || self.start_minute == self.end_minute {
+            return false;
+        }
+        let starts_on = |day: u8| self.days.is_empty() || self.days.contains(&day);
+
+        if self.start_minute < self.end_minute {
+            starts_on(weekday) && minute >= self.start_minute && minute < self.end_minute
+        } else if minute >= self.start_minute {
+            starts_on(weekday)
+        } else {
+            // Early morning part of a window that started the day before
+            minute < self.end_minute && starts_on((weekday + 6) % 7)
+        }
+    }
+}
+
+/// What a hardware key does when pressed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareKeyAction {
+    Disabled,
+    /// Start or stop dictation, like the transcribe shortcut in toggle mode
+    ToggleRecording,
+    /// Mute or unmute system audio during the current recording
+    MuteWhileRecording,
+}
+
+/// Keyboard mic-mute and media keys, handled outside the global shortcuts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct HardwareKeys {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_mic_mute_key_action")]
+    pub mic_mute: HardwareKeyAction,
+    #[serde(default = "default_play_pause_key_action")]
+    pub play_pause: HardwareKeyAction,
+    /// Light the mic-mute LED while recording, where the system exposes it
+    #[serde(default = "default_sync_mic_mute_led")]
+    pub sync_led: bool,
+}
+
+impl Default for HardwareKeys {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mic_mute: default_mic_mute_key_action(),
+            play_pause: default_play_pause_key_action(),
+            sync_led: default_sync_mic_mute_led(),
+        }
+    }
+}
+
+/// What an extra mouse button does
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseButtonAction {
+    Disabled,
+    /// Start or stop dictation on each click, like the transcribe shortcut in toggle mode
+    ToggleRecording,
+    /// Dictate while the button is held down
+    PushToTalk,
+}
+
+/// The back and forward side buttons (MB4/MB5), handled outside the global shortcuts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct MouseButtons {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_back_mouse_button_action")]
+    pub back: MouseButtonAction,
+    #[serde(default = "default_forward_mouse_button_action")]
+    pub forward: MouseButtonAction,
+}
+
+impl Default for MouseButtons {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            back: default_back_mouse_button_action(),
+            forward: default_forward_mouse_button_action(),
+        }
+    }
+}
+
+/// Pacing for the "type" paste method, for apps that drop keystrokes sent
+/// too quickly
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct TypingSettings {
+    /// 0 types as fast as the system takes it
+    #[serde(default)]
+    pub chars_per_second: u32,
+    /// Characters typed at once; 0 types everything in one go
+    #[serde(default)]
+    pub chunk_size: u32,
+    /// Pause after each chunk
+    #[serde(default)]
+    pub chunk_delay_ms: u32,
+    /// Stop typing when another window takes focus between chunks
+    #[serde(default = "default_stop_typing_on_focus_change")]
+    pub stop_on_focus_change: bool,
+}
+
+impl Default for TypingSettings {
+    fn default() -> Self {
+        Self {
+            chars_per_second: 0,
+            chunk_size: 0,
+            chunk_delay_ms: 0,
+            stop_on_focus_change: default_stop_typing_on_focus_change(),
+        }
+    }
+}
+
+fn default_stop_typing_on_focus_change() -> bool {
+    true
+}
+
+/// The selections the cycle shortcuts step through, in order
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Type)]
+pub struct CycleLists {
+    /// Device names, with "default" for the system default
+    #[serde(default)]
+    pub microphones: Vec<String>,
+    /// Model IDs
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Language codes, with "auto" for detection
+    #[serde(default)]
+    pub languages: Vec<String>,
+}
+
+/// What a button on a HID device does
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HidButtonAction {
+    /// Start or stop dictation on each press, like the transcribe shortcut in toggle mode
+    ToggleRecording,
+    /// Dictate while the button is held down
+    PushToTalk,
+    /// Only start dictation, for pedals with a separate stop button
+    StartRecording,
+    /// Only stop dictation
+    StopRecording,
+}
+
+/// A button on a foot pedal, presenter or other HID device, identified by
+/// the bit it sets in the device's input report
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct HidBinding {
+    pub id: String,
+    pub name: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    /// Offset of the byte holding the button in the input report
+    pub byte_index: u32,
+    /// Bits of that byte that are set while the button is down
+    pub mask: u8,
+    pub action: HidButtonAction,
+}
+
+/// What a retention rule does to the entries it matches
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Remove the entry and its recording
+    DeleteEntry,
+    /// Remove the recording but keep the transcript
+    DeleteAudio,
+    /// Exempt the entry from every other rule, e.g. to keep saved entries forever
+    Keep,
+}
+
+/// History cleanup rule. Entries older than `older_than_days` that pass every
+/// filter get `action`, unless a `Keep` rule matches them too; filters left
+/// empty match everything.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct RetentionRule {
+    pub id: String,
+    #[serde(default = "default_retention_rule_enabled")]
+    pub enabled: bool,
+    pub older_than_days: u32,
+    pub action: RetentionAction,
+    /// Only match saved (`true`) or unsaved (`false`) entries
+    #[serde(default)]
+    pub saved: Option<bool>,
+    /// Only match transcripts containing this text, ignoring case
+    #[serde(default)]
+    pub text_contains: Option<String>,
+}
+
+/// What to hold back while a macOS Focus is on or the screen is shared
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct FocusSuppression {
+    #[serde(default = "default_focus_suppression_flag")]
+    pub enabled: bool,
+    #[serde(default = "default_focus_suppression_flag")]
+    pub respect_focus: bool,
+    #[serde(default = "default_focus_suppression_flag")]
+    pub respect_presenting: bool,
+    #[serde(default = "default_focus_suppression_flag")]
+    pub suppress_overlay: bool,
+    #[serde(default = "default_focus_suppression_flag")]
+    pub suppress_sounds: bool,
+    #[serde(default = "default_focus_suppression_flag")]
+    pub suppress_notifications: bool,
+}
+
+/// Task manager meeting action items can be sent to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskProviderKind {
+    Todoist,
+    Things,
+    Reminders,
+}
+
+/// Where meeting action items are sent
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct TaskDestination {
+    pub provider: TaskProviderKind,
+    /// Todoist project ID, or Things/Reminders list name; empty for the default
+    #[serde(default)]
+    pub list: String,
+    /// For providers with an API (Todoist)
+    #[serde(default)]
+    pub api_token: String,
+}
+
+impl Default for FocusSuppression {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            respect_focus: true,
+            respect_presenting: true,
+            suppress_overlay: true,
+            suppress_sounds: true,
+            suppress_notifications: true,
+        }
+    }
+}
+
 impl Default for ModelUnloadTimeout {
     fn default() -> Self {
         ModelUnloadTimeout::Never
@@ -207,6 +708,18 @@ pub enum SoundTheme {
     Custom,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PostProcessMode {
+    /// The selected pipeline, or else the selected prompt
+    #[default]
+    Custom,
+    /// Only fix grammar and punctuation, see `crate::grammar`
+    GrammarOnly,
+    /// Translate into the target language, see `crate::translation`
+    Translate,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum DeveloperMode {
@@ -219,6 +732,17 @@ pub enum DeveloperMode {
     Always,
 }
 
+impl DeveloperMode {
+    /// Whether developer mode applies when dictating into `app_name`
+    pub fn is_active_in(&self, app_name: Option<&str>) -> bool {
+        match self {
+            DeveloperMode::Off => false,
+            DeveloperMode::Always => true,
+            DeveloperMode::Auto => app_name.is_some_and(crate::context::is_developer_context),
+        }
+    }
+}
+
 impl SoundTheme {
     fn as_str(&self) -> &'static str {
         match self {
@@ -241,7 +765,22 @@ impl SoundTheme {
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct AppSettings {
     pub bindings: HashMap<String, ShortcutBinding>,
+    #[serde(default)]
+    pub shortcut_profiles: Vec<ShortcutProfile>,
+    /// The profile whose shortcuts are in `bindings`
+    #[serde(default)]
+    pub active_shortcut_profile_id: Option<String>,
     pub push_to_talk: bool,
+    /// With push-to-talk, a quick press latches the recording on until the
+    /// next press, and only a hold of `ptt_latch_threshold_ms` or longer
+    /// stops on release
+    #[serde(default)]
+    pub ptt_hybrid_mode: bool,
+    #[serde(default = "default_ptt_latch_threshold_ms")]
+    pub ptt_latch_threshold_ms: u32,
+    /// Longest gap between the taps of a `double_tap:` shortcut, and longest tap
+    #[serde(default = "default_double_tap_timeout_ms")]
+    pub double_tap_timeout_ms: u32,
     pub audio_feedback: bool,
     #[serde(default = "default_audio_feedback_volume")]
     pub audio_feedback_volume: f32,
@@ -257,6 +796,17 @@ pub struct AppSettings {
     pub selected_model: String,
     #[serde(default = "default_always_on_microphone")]
     pub always_on_microphone: bool,
+    /// Listen for the wake word while idle; keeps the microphone open. The
+    /// downloaded model wakes on "Hey Jarvis".
+    #[serde(default)]
+    pub wake_word_enabled: bool,
+    /// 0 to 1; higher wakes on fainter matches, and on more false ones
+    #[serde(default = "default_wake_word_sensitivity")]
+    pub wake_word_sensitivity: f32,
+    /// A custom openWakeWord model, e.g. one trained on "Hey PaperFlow",
+    /// in place of the downloaded "Hey Jarvis" one
+    #[serde(default)]
+    pub wake_word_model_path: Option<String>,
     #[serde(default)]
     pub selected_microphone: Option<String>,
     #[serde(default)]
@@ -277,24 +827,57 @@ pub struct AppSettings {
     pub log_level: LogLevel,
     #[serde(default)]
     pub custom_words: Vec<String>,
+    /// Custom words scoped to apps, merged with `custom_words` in those apps
+    #[serde(default)]
+    pub custom_word_sets: Vec<CustomWordSet>,
+    #[serde(default)]
+    pub filler_filter: FillerFilter,
+    #[serde(default = "default_filler_words")]
+    pub filler_words: Vec<String>,
+    #[serde(default)]
+    pub stutter: StutterSettings,
+    #[serde(default)]
+    pub profanity_filter: ProfanityFilter,
+    /// Words filtered as profanity on top of the built-in list
+    #[serde(default)]
+    pub profanity_words: Vec<String>,
     #[serde(default)]
     pub model_unload_timeout: ModelUnloadTimeout,
     #[serde(default = "default_word_correction_threshold")]
     pub word_correction_threshold: f64,
+    #[serde(default)]
+    pub word_match_algorithm: WordMatchAlgorithm,
+    /// Correction thresholds for single custom words, in place of
+    /// `word_correction_threshold`
+    #[serde(default)]
+    pub word_correction_thresholds: HashMap<String, f64>,
     #[serde(default = "default_history_limit")]
     pub history_limit: usize,
     #[serde(default = "default_recording_retention_period")]
     pub recording_retention_period: RecordingRetentionPeriod,
     #[serde(default)]
+    pub recording_format: RecordingFormat,
+    #[serde(default)]
     pub paste_method: PasteMethod,
     #[serde(default)]
     pub clipboard_handling: ClipboardHandling,
+    /// Paste into the window that was focused when the recording started,
+    /// even if focus moved while transcribing
+    #[serde(default)]
+    pub paste_into_recording_window: bool,
+    /// How long after the paste keystroke the previous clipboard is put
+    /// back, for apps that read the clipboard slowly
+    #[serde(default = "default_clipboard_restore_delay_ms")]
+    pub clipboard_restore_delay_ms: u32,
     #[serde(default = "default_post_process_enabled")]
     pub post_process_enabled: bool,
     #[serde(default = "default_post_process_provider_id")]
     pub post_process_provider_id: String,
     #[serde(default = "default_post_process_providers")]
     pub post_process_providers: Vec<PostProcessProvider>,
+    /// Provider tried when the selected one fails, with its configured model
+    #[serde(default)]
+    pub post_process_fallback_provider_id: Option<String>,
     #[serde(default = "default_post_process_api_keys")]
     pub post_process_api_keys: HashMap<String, String>,
     #[serde(default = "default_post_process_models")]
@@ -304,6 +887,29 @@ pub struct AppSettings {
     #[serde(default)]
     pub post_process_selected_prompt_id: Option<String>,
     #[serde(default)]
+    pub post_process_pipelines: Vec<PostProcessPipeline>,
+    /// Runs instead of the selected prompt when set
+    #[serde(default)]
+    pub post_process_selected_pipeline_id: Option<String>,
+    #[serde(default)]
+    pub post_process_mode: PostProcessMode,
+    /// Prompt id per app name, used instead of the selected prompt or
+    /// pipeline when dictating into that app. A profile's prompt wins.
+    #[serde(default)]
+    pub app_prompt_mappings: HashMap<String, String>,
+    /// Share of the words a grammar-only correction may change before it is
+    /// rejected
+    #[serde(default = "default_grammar_max_edit_ratio")]
+    pub grammar_max_edit_ratio: f64,
+    #[serde(default = "default_translation_target_language")]
+    pub translation_target_language: String,
+    /// Target language per app name, overriding the global one
+    #[serde(default)]
+    pub app_translation_languages: HashMap<String, String>,
+    /// Reuse the reply to a prompt that was already sent to the same model
+    #[serde(default = "default_llm_cache_enabled")]
+    pub llm_cache_enabled: bool,
+    #[serde(default)]
     pub mute_while_recording: bool,
     #[serde(default)]
     pub append_trailing_space: bool,
@@ -314,6 +920,23 @@ pub struct AppSettings {
     pub snippets: Vec<Snippet>,
     #[serde(default)]
     pub snippets_enabled: bool,
+    // === Regex Rules ===
+    /// Find and replace rules, run in order before formatting
+    #[serde(default)]
+    pub regex_rules: Vec<RegexRule>,
+    // === Text Processing Pipeline ===
+    /// Order and enablement of the local text processing stages
+    #[serde(default = "default_text_pipeline")]
+    pub text_pipeline: Vec<TextStageConfig>,
+    /// Pipelines used instead of `text_pipeline` when dictating into an app,
+    /// keyed on app name
+    #[serde(default)]
+    pub app_text_pipelines: HashMap<String, Vec<TextStageConfig>>,
+    // === Spelling Mode ===
+    /// Write "spell mode ... end spell" and "spell that ..." dictation as
+    /// the letters, digits and symbols spelled
+    #[serde(default)]
+    pub spell_mode_enabled: bool,
     // === Auto-Formatting ===
     #[serde(default)]
     pub auto_format_enabled: bool,
@@ -321,6 +944,16 @@ pub struct AppSettings {
     pub auto_format_lists: bool,
     #[serde(default)]
     pub verbal_commands_enabled: bool,
+    /// Emoji inserted by saying "<name> emoji" with verbal commands on
+    #[serde(default = "default_emoji_map")]
+    pub emoji_map: HashMap<String, String>,
+    // === Inverse Text Normalization ===
+    /// Write spoken numbers, dates, times, currencies and percentages in
+    /// their written form
+    #[serde(default)]
+    pub itn_enabled: bool,
+    #[serde(default)]
+    pub itn_locale: ItnLocale,
     // === Context-Aware Tone ===
     #[serde(default)]
     pub tone_adjustment_enabled: bool,
@@ -328,6 +961,9 @@ pub struct AppSettings {
     pub default_tone: ToneStyle,
     #[serde(default)]
     pub app_tone_mappings: HashMap<String, ToneStyle>,
+    /// Profiles per app or website name, chosen when a recording starts
+    #[serde(default)]
+    pub app_profiles: HashMap<String, AppProfile>,
     // === Developer Mode ===
     #[serde(default)]
     pub developer_mode: DeveloperMode,
@@ -341,8 +977,18 @@ pub struct AppSettings {
     // === Context Awareness (Privacy) ===
     #[serde(default)]
     pub context_awareness_enabled: bool,
+    /// Apps and websites whose focused text field, or window when screen OCR
+    /// is on, may be read
     #[serde(default)]
     pub context_per_app_permissions: HashMap<String, bool>,
+    /// Also give the text before the cursor to the post-processing LLM,
+    /// which may be a cloud provider
+    #[serde(default)]
+    pub surrounding_text_in_llm_prompt: bool,
+    /// Recognize the text shown in the active window, for names and terms
+    /// to bias transcription toward
+    #[serde(default)]
+    pub screen_ocr_enabled: bool,
     // === Cloud Transcription ===
     #[serde(default)]
     pub groq_transcription_api_key: String,
@@ -370,6 +1016,27 @@ pub struct AppSettings {
     pub meeting_summary_prompt: String,
     #[serde(default = "default_meeting_action_items_prompt")]
     pub meeting_action_items_prompt: String,
+    /// Keep each chunk's audio so meetings can be played back
+    #[serde(default = "default_meeting_save_audio")]
+    pub meeting_save_audio: bool,
+    /// Split finished meetings into topic sections with the LLM
+    #[serde(default)]
+    pub meeting_segment_topics: bool,
+    /// Completed meetings are POSTed here as JSON; empty turns this off
+    #[serde(default)]
+    pub meeting_webhook_url: String,
+    /// Signs webhook bodies with HMAC-SHA256 when set
+    #[serde(default)]
+    pub meeting_webhook_secret: String,
+    /// Suggest meeting mode when a video call app comes to the front
+    #[serde(default)]
+    pub meeting_detection_enabled: bool,
+    /// Start the meeting when a call is detected instead of suggesting it
+    #[serde(default)]
+    pub meeting_auto_start: bool,
+    /// Task managers that receive each meeting's action items
+    #[serde(default)]
+    pub meeting_task_destinations: Vec<TaskDestination>,
     // === Live Preview ===
     #[serde(default)]
     pub live_preview_enabled: bool,
@@ -378,19 +1045,80 @@ pub struct AppSettings {
     // === Watch Folders ===
     #[serde(default)]
     pub watch_folders: Option<Vec<crate::managers::watch_folder::WatchFolderConfig>>,
+    // === File Transcription ===
+    /// ffmpeg binary used to decode files; empty looks for a bundled copy or one on PATH
+    #[serde(default)]
+    pub ffmpeg_path: String,
+    /// Split completed transcriptions of long files into chapters with the LLM
+    #[serde(default)]
+    pub file_transcription_detect_chapters: bool,
     // === Whisper Mode ===
     #[serde(default)]
     pub whisper_mode_enabled: bool,
     #[serde(default = "default_vad_threshold")]
     pub vad_threshold: f32,
+    // === Recording Limits ===
+    #[serde(default = "default_max_recording_duration_minutes")]
+    pub max_recording_duration_minutes: u32,
     // === Speaker Diarization ===
     #[serde(default)]
     pub diarization_enabled: bool,
+    // === Punctuation Restoration ===
+    /// Punctuate and capitalize output from engines that write none
+    #[serde(default)]
+    pub punctuation_restoration_enabled: bool,
     // === CoreML Acceleration (macOS only) ===
     #[serde(default = "default_coreml_enabled")]
     pub coreml_enabled: bool,
     #[serde(default = "default_auto_download_coreml")]
     pub auto_download_coreml: bool,
+    // === Metrics Endpoint ===
+    #[serde(default)]
+    pub metrics_endpoint_enabled: bool,
+    #[serde(default = "default_metrics_endpoint_port")]
+    pub metrics_endpoint_port: u16,
+    // === Network ===
+    #[serde(default)]
+    pub proxy: ProxySettings,
+    // === Quiet Hours ===
+    #[serde(default)]
+    pub quiet_hours: DailyWindow,
+    // === Focus / Presentation ===
+    #[serde(default)]
+    pub focus_suppression: FocusSuppression,
+    // === History Encryption ===
+    #[serde(default)]
+    pub history_encryption_enabled: bool,
+    // === History Sync ===
+    #[serde(default)]
+    pub history_sync_enabled: bool,
+    #[serde(default)]
+    pub history_sync_folder: Option<String>,
+    // === Language Learning ===
+    #[serde(default)]
+    pub dual_script_enabled: bool,
+    #[serde(default = "default_dual_script_language")]
+    pub dual_script_language: String,
+    // === Retention Rules ===
+    #[serde(default)]
+    pub retention_rules: Vec<RetentionRule>,
+    // === Hardware Keys ===
+    #[serde(default)]
+    pub hardware_keys: HardwareKeys,
+    // === Mouse Buttons ===
+    #[serde(default)]
+    pub mouse_buttons: MouseButtons,
+    // === HID Triggers ===
+    #[serde(default)]
+    pub hid_triggers_enabled: bool,
+    #[serde(default)]
+    pub hid_bindings: Vec<HidBinding>,
+    // === Cycle Shortcuts ===
+    #[serde(default)]
+    pub cycle_lists: CycleLists,
+    // === Typing ===
+    #[serde(default)]
+    pub typing: TypingSettings,
 }
 
 fn default_model() -> String {
@@ -401,6 +1129,10 @@ fn default_always_on_microphone() -> bool {
     false
 }
 
+fn default_wake_word_sensitivity() -> f32 {
+    0.5
+}
+
 fn default_translate_to_english() -> bool {
     false
 }
@@ -440,6 +1172,25 @@ fn default_word_correction_threshold() -> f64 {
     0.18
 }
 
+fn default_filler_words() -> Vec<String> {
+    DEFAULT_FILLER_WORDS
+        .iter()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+fn default_custom_word_set_enabled() -> bool {
+    true
+}
+
+fn default_stutter_max_word_length() -> usize {
+    2
+}
+
+fn default_stutter_min_repetitions() -> usize {
+    3
+}
+
 fn default_history_limit() -> usize {
     5
 }
@@ -482,6 +1233,10 @@ fn default_meeting_action_items_prompt() -> String {
     "Extract all action items from the following meeting transcript. List each action item on a separate line with the responsible person if mentioned:\n\n${transcript}".to_string()
 }
 
+fn default_meeting_save_audio() -> bool {
+    true
+}
+
 fn default_live_preview_interval_ms() -> u32 {
     2000 // 2 seconds
 }
@@ -490,6 +1245,122 @@ fn default_vad_threshold() -> f32 {
     0.3 // Normal VAD threshold, whisper mode uses 0.15
 }
 
+fn default_max_recording_duration_minutes() -> u32 {
+    0 // No limit unless the user sets one
+}
+
+fn default_metrics_endpoint_port() -> u16 {
+    9464 // The port commonly used by Prometheus exporters
+}
+
+fn default_grammar_max_edit_ratio() -> f64 {
+    0.3
+}
+
+fn default_translation_target_language() -> String {
+    "English".to_string()
+}
+
+fn default_llm_cache_enabled() -> bool {
+    true
+}
+
+fn default_text_pipeline() -> Vec<TextStageConfig> {
+    normalize_text_pipeline(&[])
+}
+
+fn default_emoji_map() -> HashMap<String, String> {
+    [
+        ("smiley", "😊"),
+        ("smile", "😄"),
+        ("grin", "😁"),
+        ("laughing", "😂"),
+        ("wink", "😉"),
+        ("sad", "😢"),
+        ("crying", "😭"),
+        ("thinking", "🤔"),
+        ("shrug", "🤷"),
+        ("heart", "❤️"),
+        ("thumbs up", "👍"),
+        ("thumbs down", "👎"),
+        ("clap", "👏"),
+        ("wave", "👋"),
+        ("pray", "🙏"),
+        ("ok hand", "👌"),
+        ("eyes", "👀"),
+        ("fire", "🔥"),
+        ("party", "🎉"),
+        ("rocket", "🚀"),
+        ("star", "⭐"),
+        ("hundred", "💯"),
+        ("check mark", "✅"),
+        ("cross mark", "❌"),
+    ]
+    .into_iter()
+    .map(|(name, emoji)| (name.to_string(), emoji.to_string()))
+    .collect()
+}
+
+fn default_dual_script_language() -> String {
+    "English".to_string()
+}
+
+fn default_mic_mute_key_action() -> HardwareKeyAction {
+    HardwareKeyAction::ToggleRecording
+}
+
+fn default_play_pause_key_action() -> HardwareKeyAction {
+    HardwareKeyAction::Disabled
+}
+
+fn default_sync_mic_mute_led() -> bool {
+    true
+}
+
+fn default_double_tap_timeout_ms() -> u32 {
+    300
+}
+
+fn default_clipboard_restore_delay_ms() -> u32 {
+    150
+}
+
+fn default_ptt_latch_threshold_ms() -> u32 {
+    400
+}
+
+fn default_back_mouse_button_action() -> MouseButtonAction {
+    MouseButtonAction::PushToTalk
+}
+
+fn default_forward_mouse_button_action() -> MouseButtonAction {
+    MouseButtonAction::Disabled
+}
+
+fn default_retention_rule_enabled() -> bool {
+    true
+}
+
+fn default_focus_suppression_flag() -> bool {
+    true
+}
+
+fn default_proxy_bypass() -> Vec<String> {
+    vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ]
+}
+
+fn default_window_start() -> u16 {
+    22 * 60
+}
+
+fn default_window_end() -> u16 {
+    7 * 60
+}
+
 fn default_coreml_enabled() -> bool {
     // Enable CoreML by default on macOS for Apple Neural Engine acceleration
     #[cfg(target_os = "macos")]
@@ -527,12 +1398,19 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
             models_endpoint: Some("/models".to_string()),
         },
         PostProcessProvider {
-            id: "anthropic".to_string(),
+            id: ANTHROPIC_PROVIDER_ID.to_string(),
             label: "Anthropic".to_string(),
             base_url: "https://api.anthropic.com/v1".to_string(),
             allow_base_url_edit: false,
             models_endpoint: Some("/models".to_string()),
         },
+        PostProcessProvider {
+            id: GEMINI_PROVIDER_ID.to_string(),
+            label: "Google Gemini".to_string(),
+            base_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+            allow_base_url_edit: false,
+            models_endpoint: Some("/models".to_string()),
+        },
         PostProcessProvider {
             id: "groq".to_string(),
             label: "Groq".to_string(),
@@ -564,6 +1442,15 @@ fn default_post_process_providers() -> Vec<PostProcessProvider> {
         });
     }
 
+    #[cfg(feature = "local-llm")]
+    providers.push(PostProcessProvider {
+        id: LOCAL_LLM_PROVIDER_ID.to_string(),
+        label: "Local (llama.cpp)".to_string(),
+        base_url: "llama-cpp://local".to_string(),
+        allow_base_url_edit: false,
+        models_endpoint: None,
+    });
+
     // Custom provider always comes last
     providers.push(PostProcessProvider {
         id: "custom".to_string(),
@@ -704,9 +1591,60 @@ pub fn get_default_settings() -> AppSettings {
         },
     );
 
+    // Unassigned until the user picks a shortcut, so they don't take
+    // combinations other apps rely on
+    for (id, name, description) in [
+        (
+            "transcribe_file",
+            "Transcribe File",
+            "Opens a picker to queue audio or video files for transcription.",
+        ),
+        (
+            "paste_last_transcript",
+            "Paste Last Transcript",
+            "Pastes the most recent transcription again.",
+        ),
+        (
+            "toggle_live_preview",
+            "Toggle Live Preview",
+            "Turns the live transcription preview on or off.",
+        ),
+        (
+            "cycle_microphone",
+            "Cycle Microphone",
+            "Switches to the next microphone in your list.",
+        ),
+        (
+            "cycle_model",
+            "Cycle Model",
+            "Switches to the next model in your list.",
+        ),
+        (
+            "cycle_language",
+            "Cycle Language",
+            "Switches to the next language in your list.",
+        ),
+    ] {
+        bindings.insert(
+            id.to_string(),
+            ShortcutBinding {
+                id: id.to_string(),
+                name: name.to_string(),
+                description: description.to_string(),
+                default_binding: String::new(),
+                current_binding: String::new(),
+            },
+        );
+    }
+
     AppSettings {
         bindings,
+        shortcut_profiles: Vec::new(),
+        active_shortcut_profile_id: None,
         push_to_talk: true,
+        ptt_hybrid_mode: false,
+        ptt_latch_threshold_ms: default_ptt_latch_threshold_ms(),
+        double_tap_timeout_ms: default_double_tap_timeout_ms(),
         audio_feedback: false,
         audio_feedback_volume: default_audio_feedback_volume(),
         sound_theme: default_sound_theme(),
@@ -715,6 +1653,9 @@ pub fn get_default_settings() -> AppSettings {
         update_checks_enabled: default_update_checks_enabled(),
         selected_model: "".to_string(),
         always_on_microphone: false,
+        wake_word_enabled: false,
+        wake_word_sensitivity: default_wake_word_sensitivity(),
+        wake_word_model_path: None,
         selected_microphone: None,
         clamshell_microphone: None,
         selected_output_device: None,
@@ -725,36 +1666,66 @@ pub fn get_default_settings() -> AppSettings {
         debug_mode: false,
         log_level: default_log_level(),
         custom_words: Vec::new(),
+        custom_word_sets: Vec::new(),
+        filler_filter: FillerFilter::default(),
+        filler_words: default_filler_words(),
+        stutter: StutterSettings::default(),
+        profanity_filter: ProfanityFilter::default(),
+        profanity_words: Vec::new(),
         model_unload_timeout: ModelUnloadTimeout::Never,
         word_correction_threshold: default_word_correction_threshold(),
+        word_match_algorithm: WordMatchAlgorithm::default(),
+        word_correction_thresholds: HashMap::new(),
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
+        recording_format: RecordingFormat::default(),
         paste_method: PasteMethod::default(),
         clipboard_handling: ClipboardHandling::default(),
+        paste_into_recording_window: false,
+        clipboard_restore_delay_ms: default_clipboard_restore_delay_ms(),
         post_process_enabled: default_post_process_enabled(),
         post_process_provider_id: default_post_process_provider_id(),
         post_process_providers: default_post_process_providers(),
+        post_process_fallback_provider_id: None,
         post_process_api_keys: default_post_process_api_keys(),
         post_process_models: default_post_process_models(),
         post_process_prompts: default_post_process_prompts(),
         post_process_selected_prompt_id: None,
+        post_process_pipelines: Vec::new(),
+        post_process_selected_pipeline_id: None,
+        post_process_mode: PostProcessMode::default(),
+        app_prompt_mappings: HashMap::new(),
+        grammar_max_edit_ratio: default_grammar_max_edit_ratio(),
+        translation_target_language: default_translation_target_language(),
+        app_translation_languages: HashMap::new(),
+        llm_cache_enabled: default_llm_cache_enabled(),
         mute_while_recording: false,
         append_trailing_space: false,
         app_language: default_app_language(),
         snippets: Vec::new(),
         snippets_enabled: false,
+        regex_rules: Vec::new(),
+        text_pipeline: default_text_pipeline(),
+        app_text_pipelines: HashMap::new(),
+        spell_mode_enabled: false,
         auto_format_enabled: false,
         auto_format_lists: false,
         verbal_commands_enabled: false,
+        emoji_map: default_emoji_map(),
+        itn_enabled: false,
+        itn_locale: ItnLocale::default(),
         tone_adjustment_enabled: false,
         default_tone: ToneStyle::default(),
         app_tone_mappings: HashMap::new(),
+        app_profiles: HashMap::new(),
         developer_mode: DeveloperMode::default(),
         preserve_code_syntax: false,
         developer_dictionary: Vec::new(),
         correction_detection_enabled: false,
         context_awareness_enabled: false,
         context_per_app_permissions: HashMap::new(),
+        surrounding_text_in_llm_prompt: false,
+        screen_ocr_enabled: false,
         groq_transcription_api_key: String::new(),
         multilingual_mode_enabled: false,
         primary_language: None,
@@ -767,14 +1738,42 @@ pub fn get_default_settings() -> AppSettings {
         meeting_extract_action_items: false,
         meeting_summary_prompt: default_meeting_summary_prompt(),
         meeting_action_items_prompt: default_meeting_action_items_prompt(),
+        meeting_save_audio: default_meeting_save_audio(),
+        meeting_segment_topics: false,
+        meeting_webhook_url: String::new(),
+        meeting_webhook_secret: String::new(),
+        meeting_detection_enabled: false,
+        meeting_auto_start: false,
+        meeting_task_destinations: Vec::new(),
         live_preview_enabled: false,
         live_preview_interval_ms: default_live_preview_interval_ms(),
         watch_folders: None,
+        ffmpeg_path: String::new(),
+        file_transcription_detect_chapters: false,
         whisper_mode_enabled: false,
         vad_threshold: default_vad_threshold(),
+        max_recording_duration_minutes: default_max_recording_duration_minutes(),
         diarization_enabled: false,
+        punctuation_restoration_enabled: false,
         coreml_enabled: default_coreml_enabled(),
         auto_download_coreml: default_auto_download_coreml(),
+        metrics_endpoint_enabled: false,
+        metrics_endpoint_port: default_metrics_endpoint_port(),
+        proxy: ProxySettings::default(),
+        quiet_hours: DailyWindow::default(),
+        focus_suppression: FocusSuppression::default(),
+        history_encryption_enabled: false,
+        history_sync_enabled: false,
+        history_sync_folder: None,
+        dual_script_enabled: false,
+        dual_script_language: default_dual_script_language(),
+        retention_rules: Vec::new(),
+        hardware_keys: HardwareKeys::default(),
+        mouse_buttons: MouseButtons::default(),
+        hid_triggers_enabled: false,
+        hid_bindings: Vec::new(),
+        cycle_lists: CycleLists::default(),
+        typing: TypingSettings::default(),
     }
 }
 
@@ -785,12 +1784,69 @@ impl AppSettings {
             .find(|provider| provider.id == self.post_process_provider_id)
     }
 
+    pub fn fallback_post_process_provider(&self) -> Option<&PostProcessProvider> {
+        self.post_process_provider(self.post_process_fallback_provider_id.as_deref()?)
+    }
+
     pub fn post_process_provider(&self, provider_id: &str) -> Option<&PostProcessProvider> {
         self.post_process_providers
             .iter()
             .find(|provider| provider.id == provider_id)
     }
 
+    /// Whether the text before the cursor may be read when dictating into
+    /// `app_name`, which needs context awareness on and the app opted in
+    pub fn reads_surrounding_text_in(&self, app_name: &str) -> bool {
+        self.context_awareness_enabled
+            && self
+                .context_per_app_permissions
+                .get(app_name)
+                .copied()
+                .unwrap_or(false)
+    }
+
+    /// Whether the microphone stays open between recordings
+    pub fn keeps_microphone_open(&self) -> bool {
+        self.always_on_microphone || self.wake_word_enabled
+    }
+
+    /// Whether the window of `app_name` may be captured for screen OCR,
+    /// under the same opt-in as reading its text
+    pub fn captures_screen_in(&self, app_name: &str) -> bool {
+        self.screen_ocr_enabled && self.reads_surrounding_text_in(app_name)
+    }
+
+    /// The profile for `app_name`, with the prompt, tone and text pipeline
+    /// it leaves unset taken from the older per-app mappings
+    pub fn app_profile(&self, app_name: &str) -> AppProfile {
+        let mut profile = self.app_profiles.get(app_name).cloned().unwrap_or_default();
+        if profile.post_process_prompt_id.is_none() {
+            profile.post_process_prompt_id = self.app_prompt_mappings.get(app_name).cloned();
+        }
+        if profile.tone.is_none() {
+            profile.tone = self.app_tone_mappings.get(app_name).copied();
+        }
+        if profile.text_pipeline.is_none() {
+            profile.text_pipeline = self.app_text_pipelines.get(app_name).cloned();
+        }
+        profile
+    }
+
+    /// The prompt routed to `app_name`, if it still exists
+    pub fn app_post_process_prompt(&self, app_name: &str) -> Option<&LLMPrompt> {
+        let id = self.app_profile(app_name).post_process_prompt_id?;
+        self.post_process_prompts
+            .iter()
+            .find(|prompt| prompt.id == id)
+    }
+
+    pub fn active_post_process_pipeline(&self) -> Option<&PostProcessPipeline> {
+        let id = self.post_process_selected_pipeline_id.as_ref()?;
+        self.post_process_pipelines
+            .iter()
+            .find(|pipeline| &pipeline.id == id)
+    }
+
     pub fn post_process_provider_mut(
         &mut self,
         provider_id: &str,
@@ -876,12 +1932,19 @@ pub fn get_settings(app: &AppHandle) -> AppSettings {
     settings
 }
 
-pub fn write_settings(app: &AppHandle, settings: AppSettings) {
-    let store = app
-        .store(SETTINGS_STORE_PATH)
-        .expect("Failed to initialize store");
-
-    store.set("settings", serde_json::to_value(&settings).unwrap());
+/// Apply a change to the stored settings.
+///
+/// Changes go through the settings writer, which applies them one at a time
+/// against the latest stored value so concurrent commands can't overwrite each
+/// other with stale snapshots.
+pub fn update_settings<F>(app: &AppHandle, update: F)
+where
+    F: FnOnce(&mut AppSettings) + Send + 'static,
+{
+    match app.try_state::<Arc<SettingsWriter>>() {
+        Some(writer) => writer.submit(Box::new(update)),
+        None => settings_writer::apply_directly(app, Box::new(update)),
+    }
 }
 
 pub fn get_bindings(app: &AppHandle) -> HashMap<String, ShortcutBinding> {
@@ -908,3 +1971,66 @@ pub fn get_recording_retention_period(app: &AppHandle) -> RecordingRetentionPeri
     let settings = get_settings(app);
     settings.recording_retention_period
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(start: u16, end: u16, days: Vec<u8>) -> DailyWindow {
+        DailyWindow {
+            enabled: true,
+            start_minute: start,
+            end_minute: end,
+            days,
+        }
+    }
+
+    #[test]
+    fn same_day_window() {
+        let quiet = window(13 * 60, 14 * 60, vec![]);
+        assert!(quiet.is_active_at(2, 13 * 60));
+        assert!(quiet.is_active_at(2, 13 * 60 + 59));
+        assert!(!quiet.is_active_at(2, 14 * 60));
+        assert!(!quiet.is_active_at(2, 12 * 60));
+    }
+
+    #[test]
+    fn overnight_window_belongs_to_the_start_day() {
+        // Friday night only
+        let quiet = window(22 * 60, 7 * 60, vec![4]);
+        assert!(quiet.is_active_at(4, 23 * 60));
+        assert!(quiet.is_active_at(5, 6 * 60));
+        assert!(!quiet.is_active_at(5, 23 * 60));
+        assert!(!quiet.is_active_at(4, 6 * 60));
+    }
+
+    #[test]
+    fn overnight_window_wraps_the_week() {
+        // Sunday night carries into Monday morning
+        let quiet = window(22 * 60, 7 * 60, vec![6]);
+        assert!(quiet.is_active_at(0, 60));
+        assert!(!quiet.is_active_at(6, 60));
+    }
+
+    #[test]
+    fn disabled_or_empty_window_is_never_active() {
+        let mut quiet = window(0, 0, vec![]);
+        assert!(!quiet.is_active_at(0, 0));
+
+        quiet.end_minute = 24 * 60 - 1;
+        quiet.enabled = false;
+        assert!(!quiet.is_active_at(0, 60));
+    }
+
+    #[test]
+    fn windows_are_validated() {
+        assert!(window(22 * 60, 7 * 60, vec![0, 6]).validate().is_ok());
+        assert!(window(0, 24 * 60, vec![]).validate().is_err());
+        assert!(window(60, 60, vec![]).validate().is_err());
+        assert!(window(60, 120, vec![7]).validate().is_err());
+
+        let mut empty = window(60, 60, vec![]);
+        empty.enabled = false;
+        assert!(empty.validate().is_ok());
+    }
+}
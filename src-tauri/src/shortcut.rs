@@ -2,22 +2,35 @@ use log::{error, warn};
 use serde::Serialize;
 use specta::Type;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
 use tauri_plugin_autostart::ManagerExt;
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_specta::Event;
 
 use crate::actions::ACTION_MAP;
+use crate::audio_toolkit::{
+    FillerFilter, ItnLocale, ProfanityFilter, RegexRule, WordMatchAlgorithm,
+};
 use crate::context::ToneStyle;
+use crate::events::WhisperModeChanged;
+use crate::input::double_tap;
+use crate::input::hid::{self, HidButton, HidDeviceInfo};
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::model::ModelManager;
 use crate::managers::snippets::Snippet;
-use crate::settings::ShortcutBinding;
+use crate::metrics::MetricsServer;
 use crate::settings::{
-    self, get_settings, ClipboardHandling, DeveloperMode, LLMPrompt, OverlayPosition, PasteMethod,
-    SoundTheme, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    self, get_settings, AppProfile, AppSettings, ClipboardHandling, CustomWordSet, CycleLists,
+    DailyWindow, DeveloperMode, FocusSuppression, HardwareKeyAction, HardwareKeys, HidBinding,
+    HidButtonAction, LLMPrompt, MouseButtonAction, MouseButtons, OverlayPosition, PasteMethod,
+    PipelineStep, PostProcessMode, PostProcessPipeline, ProxySettings, SoundTheme, StutterSettings,
+    TextStageConfig, TypingSettings, APPLE_INTELLIGENCE_PROVIDER_ID, LOCAL_LLM_PROVIDER_ID,
 };
+use crate::settings::{ShortcutBinding, ShortcutProfile};
 use crate::tray;
 use crate::ManagedToggleState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 pub fn init_shortcuts(app: &AppHandle) {
     let default_bindings = settings::get_default_settings().bindings;
@@ -45,6 +58,131 @@ pub struct BindingResponse {
     success: bool,
     binding: Option<ShortcutBinding>,
     error: Option<String>,
+    /// What holds the shortcut, when that is why it couldn't be set
+    conflict: Option<ShortcutConflict>,
+}
+
+impl BindingResponse {
+    fn failed(error: String, conflict: Option<ShortcutConflict>) -> Self {
+        Self {
+            success: false,
+            binding: None,
+            error: Some(error),
+            conflict,
+        }
+    }
+
+    fn succeeded(binding: ShortcutBinding) -> Self {
+        Self {
+            success: true,
+            binding: Some(binding),
+            error: None,
+            conflict: None,
+        }
+    }
+}
+
+/// Who already uses a shortcut
+#[derive(Serialize, Debug, Clone, PartialEq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShortcutConflict {
+    /// Another PaperFlow action
+    Binding { id: String, name: String },
+    /// Another action in a saved shortcut profile, once switched to
+    Profile { id: String, name: String },
+    /// A hardware key or mouse button PaperFlow already listens to
+    Device { name: String },
+    /// The system or another app, which refused to hand it over
+    System,
+}
+
+/// Whether two binding strings trigger on the same keys, however they are
+/// written
+fn same_shortcut(a: &str, b: &str) -> bool {
+    if a.trim().is_empty() || b.trim().is_empty() {
+        return false;
+    }
+    match (double_tap::is_double_tap(a), double_tap::is_double_tap(b)) {
+        (true, true) => matches!(
+            (double_tap::parse(a), double_tap::parse(b)),
+            (Ok(x), Ok(y)) if x == y
+        ),
+        (false, false) => match (a.parse::<Shortcut>(), b.parse::<Shortcut>()) {
+            (Ok(x), Ok(y)) => x == y,
+            _ => a.trim().eq_ignore_ascii_case(b.trim()),
+        },
+        _ => false,
+    }
+}
+
+/// Keys the hardware key and mouse button listeners act on. Mic-mute keys
+/// can't be registered as shortcuts, so only play/pause is listed; many mice
+/// send their side buttons as the browser back and forward keys as well.
+fn device_keys(settings: &AppSettings) -> Vec<(Code, &'static str)> {
+    let mut keys = Vec::new();
+    let hardware = &settings.hardware_keys;
+    if hardware.enabled && hardware.play_pause != HardwareKeyAction::Disabled {
+        keys.push((Code::MediaPlayPause, "the play/pause key"));
+    }
+    let mouse = &settings.mouse_buttons;
+    if mouse.enabled && mouse.back != MouseButtonAction::Disabled {
+        keys.push((Code::BrowserBack, "the back mouse button"));
+    }
+    if mouse.enabled && mouse.forward != MouseButtonAction::Disabled {
+        keys.push((Code::BrowserForward, "the forward mouse button"));
+    }
+    keys
+}
+
+/// What already uses `shortcut`, other than binding `id` itself: another
+/// binding, another binding in a profile that keeps this one's shortcut, or
+/// a device trigger
+fn find_conflict(settings: &AppSettings, id: &str, shortcut: &str) -> Option<ShortcutConflict> {
+    if let Some(other) = settings
+        .bindings
+        .values()
+        .filter(|other| other.id != id)
+        .find(|other| same_shortcut(&other.current_binding, shortcut))
+    {
+        return Some(ShortcutConflict::Binding {
+            id: other.id.clone(),
+            name: other.name.clone(),
+        });
+    }
+
+    // The active profile is updated along with the binding
+    let active_id = settings.active_shortcut_profile_id.as_ref();
+    if let Some(profile) = settings.shortcut_profiles.iter().find(|profile| {
+        Some(&profile.id) != active_id
+            && !profile.bindings.contains_key(id)
+            && profile
+                .bindings
+                .values()
+                .any(|other| same_shortcut(other, shortcut))
+    }) {
+        return Some(ShortcutConflict::Profile {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+        });
+    }
+
+    if double_tap::is_double_tap(shortcut) {
+        return None;
+    }
+    let key = shortcut.parse::<Shortcut>().ok()?.key;
+    device_keys(settings)
+        .into_iter()
+        .find(|(code, _)| *code == key)
+        .map(|(_, name)| ShortcutConflict::Device {
+            name: name.to_string(),
+        })
+}
+
+/// Whether a registration error means something else holds the keys, rather
+/// than the shortcut being rejected for another reason
+fn is_already_registered(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("already registered") || error.contains("already in use")
 }
 
 #[tauri::command]
@@ -54,7 +192,7 @@ pub fn change_binding(
     id: String,
     binding: String,
 ) -> Result<BindingResponse, String> {
-    let mut settings = settings::get_settings(&app);
+    let settings = settings::get_settings(&app);
 
     // Get the binding to modify
     let binding_to_modify = match settings.bindings.get(&id) {
@@ -62,26 +200,49 @@ pub fn change_binding(
         None => {
             let error_msg = format!("Binding with id '{}' not found", id);
             warn!("change_binding error: {}", error_msg);
-            return Ok(BindingResponse {
-                success: false,
-                binding: None,
-                error: Some(error_msg),
-            });
+            return Ok(BindingResponse::failed(error_msg, None));
         }
     };
+
+    // Validate the new shortcut before we touch the current registration.
+    // An empty one leaves the action unassigned.
+    if !binding.trim().is_empty() {
+        if let Err(e) = validate_shortcut_string(&binding) {
+            warn!("change_binding validation error: {}", e);
+            return Err(e);
+        }
+        if !double_tap::is_double_tap(&binding) {
+            if let Err(e) = binding.parse::<Shortcut>() {
+                let error_msg = format!("Failed to parse shortcut '{}': {}", binding, e);
+                warn!("change_binding error: {}", error_msg);
+                return Ok(BindingResponse::failed(error_msg, None));
+            }
+        }
+    }
+
+    // Two triggers on one shortcut would silently shadow each other
+    if let Some(conflict) = find_conflict(&settings, &id, &binding) {
+        let user = match &conflict {
+            ShortcutConflict::Binding { name, .. } => name.clone(),
+            ShortcutConflict::Profile { name, .. } => format!("the '{}' profile", name),
+            ShortcutConflict::Device { name } => name.clone(),
+            ShortcutConflict::System => "another app".to_string(),
+        };
+        let error_msg = format!("Shortcut '{}' is already used by {}", binding, user);
+        warn!("change_binding conflict: {}", error_msg);
+        return Ok(BindingResponse::failed(error_msg, Some(conflict)));
+    }
+
     // If this is the cancel binding, just update the settings and return
     // It's managed dynamically, so we don't register/unregister here
     if id == "cancel" {
-        if let Some(mut b) = settings.bindings.get(&id).cloned() {
-            b.current_binding = binding;
-            settings.bindings.insert(id.clone(), b.clone());
-            settings::write_settings(&app, settings);
-            return Ok(BindingResponse {
-                success: true,
-                binding: Some(b.clone()),
-                error: None,
-            });
-        }
+        let mut b = binding_to_modify;
+        b.current_binding = binding;
+        let stored = b.clone();
+        settings::update_settings(&app, move |s| {
+            s.bindings.insert(id, stored);
+        });
+        return Ok(BindingResponse::succeeded(b));
     }
 
     // Unregister the existing binding
@@ -90,39 +251,40 @@ pub fn change_binding(
         error!("change_binding error: {}", error_msg);
     }
 
-    // Validate the new shortcut before we touch the current registration
-    if let Err(e) = validate_shortcut_string(&binding) {
-        warn!("change_binding validation error: {}", e);
-        return Err(e);
-    }
-
     // Create an updated binding
-    let mut updated_binding = binding_to_modify;
+    let mut updated_binding = binding_to_modify.clone();
     updated_binding.current_binding = binding;
 
     // Register the new binding
     if let Err(e) = register_shortcut(&app, updated_binding.clone()) {
         let error_msg = format!("Failed to register shortcut: {}", e);
         error!("change_binding error: {}", error_msg);
-        return Ok(BindingResponse {
-            success: false,
-            binding: None,
-            error: Some(error_msg),
-        });
+        // Keep the action reachable on its old shortcut
+        if let Err(e) = register_shortcut(&app, binding_to_modify) {
+            warn!("Failed to restore the previous shortcut: {}", e);
+        }
+        let conflict = is_already_registered(&e).then_some(ShortcutConflict::System);
+        return Ok(BindingResponse::failed(error_msg, conflict));
     }
 
-    // Update the binding in the settings
-    settings.bindings.insert(id, updated_binding.clone());
-
-    // Save the settings
-    settings::write_settings(&app, settings);
+    // Update the binding in the settings, and in the active profile
+    let stored = updated_binding.clone();
+    settings::update_settings(&app, move |s| {
+        let active_id = s.active_shortcut_profile_id.clone();
+        if let Some(profile) = s
+            .shortcut_profiles
+            .iter_mut()
+            .find(|p| Some(&p.id) == active_id.as_ref())
+        {
+            profile
+                .bindings
+                .insert(id.clone(), stored.current_binding.clone());
+        }
+        s.bindings.insert(id, stored);
+    });
 
     // Return the updated binding
-    Ok(BindingResponse {
-        success: true,
-        binding: Some(updated_binding),
-        error: None,
-    })
+    Ok(BindingResponse::succeeded(updated_binding))
 }
 
 #[tauri::command]
@@ -133,42 +295,165 @@ pub fn reset_binding(app: AppHandle, id: String) -> Result<BindingResponse, Stri
     return change_binding(app, id, binding.default_binding);
 }
 
+// === Shortcut Profile Commands ===
+
+/// Save the current shortcuts as a new profile
 #[tauri::command]
 #[specta::specta]
-pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
+pub fn add_shortcut_profile(app: AppHandle, name: String) -> Result<ShortcutProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Shortcut profile name cannot be empty".to_string());
+    }
+
+    let settings = settings::get_settings(&app);
+    let profile = ShortcutProfile {
+        id: format!("shortcut_profile_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        bindings: settings
+            .bindings
+            .iter()
+            .map(|(id, binding)| (id.clone(), binding.current_binding.clone()))
+            .collect(),
+    };
+
+    let stored = profile.clone();
+    settings::update_settings(&app, move |s| {
+        // The first profile holds the shortcuts in use, so it starts active
+        if s.active_shortcut_profile_id.is_none() {
+            s.active_shortcut_profile_id = Some(stored.id.clone());
+        }
+        s.shortcut_profiles.push(stored);
+    });
+    tray::update_tray_menu(&app, &tray::TrayIconState::Idle, None);
+    Ok(profile)
+}
+
+/// Delete a shortcut profile. Deleting the active one keeps its shortcuts
+/// in use.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_shortcut_profile(app: AppHandle, id: String) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    if !settings.shortcut_profiles.iter().any(|p| p.id == id) {
+        return Err(format!("Shortcut profile '{}' not found", id));
+    }
+
+    settings::update_settings(&app, move |s| {
+        s.shortcut_profiles.retain(|p| p.id != id);
+        if s.active_shortcut_profile_id.as_ref() == Some(&id) {
+            s.active_shortcut_profile_id = None;
+        }
+    });
+    tray::update_tray_menu(&app, &tray::TrayIconState::Idle, None);
+    Ok(())
+}
+
+/// Register the shortcuts of profile `id` in place of the current ones
+#[tauri::command]
+#[specta::specta]
+pub fn switch_shortcut_profile(app: AppHandle, id: String) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    let profile = settings
+        .shortcut_profiles
+        .iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Shortcut profile '{}' not found", id))?;
+    // A push-to-talk release would go to a shortcut that is no longer registered
+    if app.state::<Arc<AudioRecordingManager>>().is_recording() {
+        return Err("Shortcut profiles can't be switched while recording".to_string());
+    }
+
+    let mut bindings = settings.bindings.clone();
+    for (binding_id, shortcut) in &profile.bindings {
+        if let Some(binding) = bindings.get_mut(binding_id) {
+            binding.current_binding = shortcut.clone();
+        }
+    }
+
+    // The cancel shortcut is only registered while recording
+    for (binding_id, binding) in &settings.bindings {
+        if binding_id != "cancel" {
+            let _ = unregister_shortcut(&app, binding.clone());
+        }
+    }
+    for (binding_id, binding) in &bindings {
+        if binding_id == "cancel" {
+            continue;
+        }
+        if let Err(e) = register_shortcut(&app, binding.clone()) {
+            error!(
+                "Failed to register shortcut {} of profile '{}': {}",
+                binding_id, profile.name, e
+            );
+        }
+    }
+
+    settings::update_settings(&app, move |s| {
+        s.bindings = bindings;
+        s.active_shortcut_profile_id = Some(id);
+    });
+    tray::update_tray_menu(&app, &tray::TrayIconState::Idle, None);
+    Ok(())
+}
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
     // TODO if the setting is currently false, we probably want to
     // cancel any ongoing recordings or actions
-    settings.push_to_talk = enabled;
+    settings::update_settings(&app, move |s| s.push_to_talk = enabled);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_ptt_hybrid_mode_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.ptt_hybrid_mode = enabled);
+    Ok(())
+}
 
-    settings::write_settings(&app, settings);
+/// How long a hybrid push-to-talk press must be held to stop on release
+#[tauri::command]
+#[specta::specta]
+pub fn change_ptt_latch_threshold_setting(app: AppHandle, threshold_ms: u32) -> Result<(), String> {
+    if !(100..=2000).contains(&threshold_ms) {
+        return Err("Latch threshold must be between 100 and 2000 ms".to_string());
+    }
+    settings::update_settings(&app, move |s| s.ptt_latch_threshold_ms = threshold_ms);
+    Ok(())
+}
 
+/// How quickly the taps of a `double_tap:` shortcut must follow each other
+#[tauri::command]
+#[specta::specta]
+pub fn change_double_tap_timeout_setting(app: AppHandle, timeout_ms: u32) -> Result<(), String> {
+    if !(100..=1000).contains(&timeout_ms) {
+        return Err("Double-tap timeout must be between 100 and 1000 ms".to_string());
+    }
+    settings::update_settings(&app, move |s| s.double_tap_timeout_ms = timeout_ms);
+    double_tap::set_timeout(timeout_ms);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_audio_feedback_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.audio_feedback = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.audio_feedback = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_audio_feedback_volume_setting(app: AppHandle, volume: f32) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.audio_feedback_volume = volume;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.audio_feedback_volume = volume);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_sound_theme_setting(app: AppHandle, theme: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
     let parsed = match theme.as_str() {
         "marimba" => SoundTheme::Marimba,
         "pop" => SoundTheme::Pop,
@@ -178,33 +463,27 @@ pub fn change_sound_theme_setting(app: AppHandle, theme: String) -> Result<(), S
             SoundTheme::Marimba
         }
     };
-    settings.sound_theme = parsed;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.sound_theme = parsed);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_translate_to_english_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.translate_to_english = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.translate_to_english = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_selected_language_setting(app: AppHandle, language: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.selected_language = language;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.selected_language = language);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
     let parsed = match position.as_str() {
         "none" => OverlayPosition::None,
         "top" => OverlayPosition::Top,
@@ -214,8 +493,7 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
             OverlayPosition::Bottom
         }
     };
-    settings.overlay_position = parsed;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.overlay_position = parsed);
 
     // Update overlay position without recreating window
     crate::utils::update_overlay_position(&app);
@@ -226,47 +504,21 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
 #[tauri::command]
 #[specta::specta]
 pub fn change_debug_mode_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.debug_mode = enabled;
-    settings::write_settings(&app, settings);
-
-    // Emit event to notify frontend of debug mode change
-    let _ = app.emit(
-        "settings-changed",
-        serde_json::json!({
-            "setting": "debug_mode",
-            "value": enabled
-        }),
-    );
-
+    settings::update_settings(&app, move |s| s.debug_mode = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_start_hidden_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.start_hidden = enabled;
-    settings::write_settings(&app, settings);
-
-    // Notify frontend
-    let _ = app.emit(
-        "settings-changed",
-        serde_json::json!({
-            "setting": "start_hidden",
-            "value": enabled
-        }),
-    );
-
+    settings::update_settings(&app, move |s| s.start_hidden = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_autostart_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.autostart_enabled = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.autostart_enabled = enabled);
 
     // Apply the autostart setting immediately
     let autostart_manager = app.autolaunch();
@@ -276,42 +528,83 @@ pub fn change_autostart_setting(app: AppHandle, enabled: bool) -> Result<(), Str
         let _ = autostart_manager.disable();
     }
 
-    // Notify frontend
-    let _ = app.emit(
-        "settings-changed",
-        serde_json::json!({
-            "setting": "autostart_enabled",
-            "value": enabled
-        }),
-    );
-
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_update_checks_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.update_checks_enabled = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.update_checks_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.custom_words = words);
+    Ok(())
+}
+
+/// Replace the custom word sets scoped to apps
+#[tauri::command]
+#[specta::specta]
+pub fn update_custom_word_sets(app: AppHandle, sets: Vec<CustomWordSet>) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for set in &sets {
+        if set.name.trim().is_empty() {
+            return Err("Custom word sets need a name".to_string());
+        }
+        if !ids.insert(set.id.as_str()) {
+            return Err(format!("Duplicate custom word set id '{}'", set.id));
+        }
+    }
 
-    let _ = app.emit(
-        "settings-changed",
-        serde_json::json!({
-            "setting": "update_checks_enabled",
-            "value": enabled
-        }),
-    );
+    settings::update_settings(&app, move |s| s.custom_word_sets = sets);
+    Ok(())
+}
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_filler_filter_setting(app: AppHandle, filter: FillerFilter) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.filler_filter = filter);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn update_custom_words(app: AppHandle, words: Vec<String>) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.custom_words = words;
-    settings::write_settings(&app, settings);
+pub fn update_filler_words(app: AppHandle, words: Vec<String>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.filler_words = words);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_stutter_settings(app: AppHandle, stutter: StutterSettings) -> Result<(), String> {
+    if stutter.min_repetitions < 2 {
+        return Err("A stutter needs at least 2 repetitions".to_string());
+    }
+    if stutter.max_word_length == 0 {
+        return Err("Maximum word length must be at least 1".to_string());
+    }
+
+    settings::update_settings(&app, move |s| s.stutter = stutter);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_profanity_filter_setting(
+    app: AppHandle,
+    filter: ProfanityFilter,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.profanity_filter = filter);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_profanity_words(app: AppHandle, words: Vec<String>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.profanity_words = words);
     Ok(())
 }
 
@@ -321,16 +614,41 @@ pub fn change_word_correction_threshold_setting(
     app: AppHandle,
     threshold: f64,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.word_correction_threshold = threshold;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.word_correction_threshold = threshold);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_word_match_algorithm_setting(
+    app: AppHandle,
+    algorithm: WordMatchAlgorithm,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.word_match_algorithm = algorithm);
+    Ok(())
+}
+
+/// Replace the correction thresholds of single custom words
+#[tauri::command]
+#[specta::specta]
+pub fn update_word_correction_thresholds(
+    app: AppHandle,
+    thresholds: HashMap<String, f64>,
+) -> Result<(), String> {
+    if let Some((word, _)) = thresholds
+        .iter()
+        .find(|(_, threshold)| !(0.0..=1.0).contains(*threshold))
+    {
+        return Err(format!("Threshold for '{}' must be between 0 and 1", word));
+    }
+
+    settings::update_settings(&app, move |s| s.word_correction_thresholds = thresholds);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
     let parsed = match method.as_str() {
         "ctrl_v" => PasteMethod::CtrlV,
         "direct" => PasteMethod::Direct,
@@ -342,15 +660,13 @@ pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(),
             PasteMethod::CtrlV
         }
     };
-    settings.paste_method = parsed;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.paste_method = parsed);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
     let parsed = match handling.as_str() {
         "dont_modify" => ClipboardHandling::DontModify,
         "copy_to_clipboard" => ClipboardHandling::CopyToClipboard,
@@ -362,17 +678,35 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
             ClipboardHandling::DontModify
         }
     };
-    settings.clipboard_handling = parsed;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.clipboard_handling = parsed);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_paste_into_recording_window_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.paste_into_recording_window = enabled);
+    Ok(())
+}
+
+/// How long to wait after pasting before putting the previous clipboard back
+#[tauri::command]
+#[specta::specta]
+pub fn change_clipboard_restore_delay_setting(app: AppHandle, delay_ms: u32) -> Result<(), String> {
+    if delay_ms > 5000 {
+        return Err("Clipboard restore delay must be at most 5000 ms".to_string());
+    }
+    settings::update_settings(&app, move |s| s.clipboard_restore_delay_ms = delay_ms);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn change_post_process_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.post_process_enabled = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.post_process_enabled = enabled);
     Ok(())
 }
 
@@ -383,25 +717,23 @@ pub fn change_post_process_base_url_setting(
     provider_id: String,
     base_url: String,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    let label = settings
+    let settings = settings::get_settings(&app);
+    let provider = settings
         .post_process_provider(&provider_id)
-        .map(|provider| provider.label.clone())
         .ok_or_else(|| format!("Provider '{}' not found", provider_id))?;
 
-    let provider = settings
-        .post_process_provider_mut(&provider_id)
-        .expect("Provider looked up above must exist");
-
     if provider.id != "custom" {
         return Err(format!(
             "Provider '{}' does not allow editing the base URL",
-            label
+            provider.label
         ));
     }
 
-    provider.base_url = base_url;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| {
+        if let Some(provider) = s.post_process_provider_mut(&provider_id) {
+            provider.base_url = base_url;
+        }
+    });
     Ok(())
 }
 
@@ -427,10 +759,10 @@ pub fn change_post_process_api_key_setting(
     provider_id: String,
     api_key: String,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    validate_provider_exists(&settings, &provider_id)?;
-    settings.post_process_api_keys.insert(provider_id, api_key);
-    settings::write_settings(&app, settings);
+    validate_provider_exists(&settings::get_settings(&app), &provider_id)?;
+    settings::update_settings(&app, move |s| {
+        s.post_process_api_keys.insert(provider_id, api_key);
+    });
     Ok(())
 }
 
@@ -441,20 +773,105 @@ pub fn change_post_process_model_setting(
     provider_id: String,
     model: String,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    validate_provider_exists(&settings, &provider_id)?;
-    settings.post_process_models.insert(provider_id, model);
-    settings::write_settings(&app, settings);
+    validate_provider_exists(&settings::get_settings(&app), &provider_id)?;
+    settings::update_settings(&app, move |s| {
+        s.post_process_models.insert(provider_id, model);
+    });
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
 pub fn set_post_process_provider(app: AppHandle, provider_id: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    validate_provider_exists(&settings, &provider_id)?;
-    settings.post_process_provider_id = provider_id;
-    settings::write_settings(&app, settings);
+    validate_provider_exists(&settings::get_settings(&app), &provider_id)?;
+    settings::update_settings(&app, move |s| s.post_process_provider_id = provider_id);
+    Ok(())
+}
+
+/// Provider to retry post-processing on when the selected one fails, or
+/// `None` to fall back to the unprocessed transcription
+#[tauri::command]
+#[specta::specta]
+pub fn set_post_process_fallback_provider(
+    app: AppHandle,
+    provider_id: Option<String>,
+) -> Result<(), String> {
+    if let Some(provider_id) = &provider_id {
+        validate_provider_exists(&settings::get_settings(&app), provider_id)?;
+    }
+    settings::update_settings(&app, move |s| {
+        s.post_process_fallback_provider_id = provider_id
+    });
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_post_process_mode_setting(
+    app: AppHandle,
+    mode: PostProcessMode,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.post_process_mode = mode);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_grammar_max_edit_ratio_setting(app: AppHandle, ratio: f64) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err("Edit ratio must be between 0 and 1".to_string());
+    }
+    settings::update_settings(&app, move |s| s.grammar_max_edit_ratio = ratio);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_translation_target_language_setting(
+    app: AppHandle,
+    language: String,
+) -> Result<(), String> {
+    if language.trim().is_empty() {
+        return Err("Target language cannot be empty".to_string());
+    }
+    settings::update_settings(&app, move |s| s.translation_target_language = language);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_app_translation_languages(
+    app: AppHandle,
+    mappings: HashMap<String, String>,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.app_translation_languages = mappings);
+    Ok(())
+}
+
+/// Route apps, by name, to the prompt to post-process their dictation with
+#[tauri::command]
+#[specta::specta]
+pub fn update_app_prompt_mappings(
+    app: AppHandle,
+    mappings: HashMap<String, String>,
+) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    if let Some(id) = mappings
+        .values()
+        .find(|id| !settings.post_process_prompts.iter().any(|p| &p.id == *id))
+    {
+        return Err(format!("Prompt with id '{}' not found", id));
+    }
+
+    settings::update_settings(&app, move |s| s.app_prompt_mappings = mappings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_llm_cache_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    crate::llm_client::cache::set_enabled(enabled);
+    settings::update_settings(&app, move |s| s.llm_cache_enabled = enabled);
     Ok(())
 }
 
@@ -465,8 +882,6 @@ pub fn add_post_process_prompt(
     name: String,
     prompt: String,
 ) -> Result<LLMPrompt, String> {
-    let mut settings = settings::get_settings(&app);
-
     // Generate unique ID using timestamp and random component
     let id = format!("prompt_{}", chrono::Utc::now().timestamp_millis());
 
@@ -476,8 +891,8 @@ pub fn add_post_process_prompt(
         prompt,
     };
 
-    settings.post_process_prompts.push(new_prompt.clone());
-    settings::write_settings(&app, settings);
+    let stored = new_prompt.clone();
+    settings::update_settings(&app, move |s| s.post_process_prompts.push(stored));
 
     Ok(new_prompt)
 }
@@ -490,47 +905,150 @@ pub fn update_post_process_prompt(
     name: String,
     prompt: String,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
+    let settings = settings::get_settings(&app);
 
-    if let Some(existing_prompt) = settings
-        .post_process_prompts
-        .iter_mut()
-        .find(|p| p.id == id)
-    {
-        existing_prompt.name = name;
-        existing_prompt.prompt = prompt;
-        settings::write_settings(&app, settings);
-        Ok(())
-    } else {
-        Err(format!("Prompt with id '{}' not found", id))
+    if !settings.post_process_prompts.iter().any(|p| p.id == id) {
+        return Err(format!("Prompt with id '{}' not found", id));
     }
-}
 
-#[tauri::command]
+    settings::update_settings(&app, move |s| {
+        if let Some(existing_prompt) = s.post_process_prompts.iter_mut().find(|p| p.id == id) {
+            existing_prompt.name = name;
+            existing_prompt.prompt = prompt;
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
 #[specta::specta]
 pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
+    let settings = settings::get_settings(&app);
 
     // Don't allow deleting the last prompt
     if settings.post_process_prompts.len() <= 1 {
         return Err("Cannot delete the last prompt".to_string());
     }
 
-    // Find and remove the prompt
-    let original_len = settings.post_process_prompts.len();
-    settings.post_process_prompts.retain(|p| p.id != id);
-
-    if settings.post_process_prompts.len() == original_len {
+    if !settings.post_process_prompts.iter().any(|p| p.id == id) {
         return Err(format!("Prompt with id '{}' not found", id));
     }
 
-    // If the deleted prompt was selected, select the first one or None
-    if settings.post_process_selected_prompt_id.as_ref() == Some(&id) {
-        settings.post_process_selected_prompt_id =
-            settings.post_process_prompts.first().map(|p| p.id.clone());
+    settings::update_settings(&app, move |s| {
+        // Find and remove the prompt, and the apps routed to it
+        s.post_process_prompts.retain(|p| p.id != id);
+        s.app_prompt_mappings
+            .retain(|_, prompt_id| prompt_id != &id);
+
+        // If the deleted prompt was selected, select the first one or None
+        if s.post_process_selected_prompt_id.as_ref() == Some(&id) {
+            s.post_process_selected_prompt_id =
+                s.post_process_prompts.first().map(|p| p.id.clone());
+        }
+    });
+    Ok(())
+}
+
+fn validate_pipeline_steps(
+    settings: &settings::AppSettings,
+    steps: &[PipelineStep],
+) -> Result<(), String> {
+    if steps.is_empty() {
+        return Err("A pipeline needs at least one step".to_string());
+    }
+    for step in steps {
+        validate_provider_exists(settings, &step.provider_id)?;
+        if step.prompt.trim().is_empty() {
+            return Err(format!("Step '{}' has no prompt", step.name));
+        }
+        if step.model.trim().is_empty() {
+            return Err(format!("Step '{}' has no model", step.name));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_post_process_pipeline(
+    app: AppHandle,
+    name: String,
+    steps: Vec<PipelineStep>,
+) -> Result<PostProcessPipeline, String> {
+    validate_pipeline_steps(&settings::get_settings(&app), &steps)?;
+
+    let pipeline = PostProcessPipeline {
+        id: format!("pipeline_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        steps,
+    };
+
+    let stored = pipeline.clone();
+    settings::update_settings(&app, move |s| s.post_process_pipelines.push(stored));
+
+    Ok(pipeline)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_post_process_pipeline(
+    app: AppHandle,
+    id: String,
+    name: String,
+    steps: Vec<PipelineStep>,
+) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+
+    if !settings.post_process_pipelines.iter().any(|p| p.id == id) {
+        return Err(format!("Pipeline with id '{}' not found", id));
     }
+    validate_pipeline_steps(&settings, &steps)?;
 
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| {
+        if let Some(pipeline) = s.post_process_pipelines.iter_mut().find(|p| p.id == id) {
+            pipeline.name = name;
+            pipeline.steps = steps;
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_post_process_pipeline(app: AppHandle, id: String) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+
+    if !settings.post_process_pipelines.iter().any(|p| p.id == id) {
+        return Err(format!("Pipeline with id '{}' not found", id));
+    }
+
+    settings::update_settings(&app, move |s| {
+        s.post_process_pipelines.retain(|p| p.id != id);
+
+        // Go back to the selected prompt if the pipeline was in use
+        if s.post_process_selected_pipeline_id.as_ref() == Some(&id) {
+            s.post_process_selected_pipeline_id = None;
+        }
+    });
+    Ok(())
+}
+
+/// Run the pipeline `id` instead of the selected prompt, or the prompt again
+/// when `id` is `None`
+#[tauri::command]
+#[specta::specta]
+pub fn set_post_process_selected_pipeline(
+    app: AppHandle,
+    id: Option<String>,
+) -> Result<(), String> {
+    if let Some(id) = &id {
+        let settings = settings::get_settings(&app);
+        if !settings.post_process_pipelines.iter().any(|p| &p.id == id) {
+            return Err(format!("Pipeline with id '{}' not found", id));
+        }
+    }
+
+    settings::update_settings(&app, move |s| s.post_process_selected_pipeline_id = id);
     Ok(())
 }
 
@@ -552,7 +1070,11 @@ pub async fn fetch_post_process_models(
     if provider.id == APPLE_INTELLIGENCE_PROVIDER_ID {
         #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
         {
-            return Ok(vec![APPLE_INTELLIGENCE_DEFAULT_MODEL_ID.to_string()]);
+            // Each on-device task is offered as a model
+            return Ok(crate::apple_intelligence::AppleIntelligenceTask::ALL
+                .iter()
+                .map(|task| task.model_id().to_string())
+                .collect());
         }
 
         #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
@@ -561,6 +1083,17 @@ pub async fn fetch_post_process_models(
         }
     }
 
+    // Local models are the downloaded GGUF models
+    if provider.id == LOCAL_LLM_PROVIDER_ID {
+        let model_manager = app.state::<Arc<ModelManager>>();
+        return Ok(model_manager
+            .get_local_llm_models()
+            .into_iter()
+            .filter(|model| model.is_downloaded)
+            .map(|model| model.id)
+            .collect());
+    }
+
     // Get API key
     let api_key = settings
         .post_process_api_keys
@@ -582,404 +1115,818 @@ pub async fn fetch_post_process_models(
 #[tauri::command]
 #[specta::specta]
 pub fn set_post_process_selected_prompt(app: AppHandle, id: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
+    let settings = settings::get_settings(&app);
 
     // Verify the prompt exists
     if !settings.post_process_prompts.iter().any(|p| p.id == id) {
         return Err(format!("Prompt with id '{}' not found", id));
     }
 
-    settings.post_process_selected_prompt_id = Some(id);
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.post_process_selected_prompt_id = Some(id));
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.mute_while_recording = enabled);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.append_trailing_space = enabled);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_app_language_setting(app: AppHandle, language: String) -> Result<(), String> {
+    let app_language = language.clone();
+    settings::update_settings(&app, move |s| s.app_language = app_language);
+
+    // Refresh the tray menu with the new language
+    tray::update_tray_menu(&app, &tray::TrayIconState::Idle, Some(&language));
+
+    Ok(())
+}
+
+// === Voice Snippets Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_snippets_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.snippets_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_snippets(app: AppHandle, snippets: Vec<Snippet>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.snippets = snippets);
+    Ok(())
+}
+
+// === Regex Rules Commands ===
+
+/// Replace the regex rules, in the order they run
+#[tauri::command]
+#[specta::specta]
+pub fn update_regex_rules(app: AppHandle, rules: Vec<RegexRule>) -> Result<(), String> {
+    for rule in &rules {
+        rule.compile()?;
+    }
+
+    settings::update_settings(&app, move |s| s.regex_rules = rules);
+    Ok(())
+}
+
+/// Run one rule on `text`, for previewing it before saving, regardless of
+/// its app scope
+#[tauri::command]
+#[specta::specta]
+pub fn test_regex_rule(rule: RegexRule, text: String) -> Result<String, String> {
+    let regex = rule.compile()?;
+    Ok(regex.replace_all(&text, &rule.replacement).into_owned())
+}
+
+// === Text Pipeline Commands ===
+
+/// The text processing stages, in order, for `app_name` or by default
+#[tauri::command]
+#[specta::specta]
+pub fn get_text_pipeline(app: AppHandle, app_name: Option<String>) -> Vec<TextStageConfig> {
+    let settings = get_settings(&app);
+    crate::managers::transcription::text_pipeline(&settings, app_name.as_deref())
+}
+
+/// Reorder or toggle the text processing stages, for `app_name` or by default
+#[tauri::command]
+#[specta::specta]
+pub fn update_text_pipeline(
+    app: AppHandle,
+    app_name: Option<String>,
+    stages: Vec<TextStageConfig>,
+) -> Result<(), String> {
+    let stages = settings::normalize_text_pipeline(&stages);
+    settings::update_settings(&app, move |s| match app_name {
+        Some(name) => {
+            s.app_text_pipelines.insert(name, stages);
+        }
+        None => s.text_pipeline = stages,
+    });
+    Ok(())
+}
+
+/// Go back to the default text pipeline for `app_name`
+#[tauri::command]
+#[specta::specta]
+pub fn remove_app_text_pipeline(app: AppHandle, app_name: String) -> Result<(), String> {
+    settings::update_settings(&app, move |s| {
+        s.app_text_pipelines.remove(&app_name);
+    });
+    Ok(())
+}
+
+// === Auto-Formatting Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_format_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.auto_format_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_format_lists_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.auto_format_lists = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_verbal_commands_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.verbal_commands_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_emoji_map(app: AppHandle, emoji_map: HashMap<String, String>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.emoji_map = emoji_map);
+    Ok(())
+}
+
+// === Spelling Mode Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_spell_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.spell_mode_enabled = enabled);
+    Ok(())
+}
+
+// === Inverse Text Normalization Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_itn_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.itn_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_itn_locale_setting(app: AppHandle, locale: ItnLocale) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.itn_locale = locale);
+    Ok(())
+}
+
+// === Context-Aware Tone Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_tone_adjustment_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.tone_adjustment_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_default_tone_setting(app: AppHandle, tone: ToneStyle) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.default_tone = tone);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_app_tone_mappings(
+    app: AppHandle,
+    mappings: HashMap<String, ToneStyle>,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.app_tone_mappings = mappings);
+    Ok(())
+}
+
+/// Set the profiles used when dictating into apps, keyed on app or website
+/// name
+#[tauri::command]
+#[specta::specta]
+pub fn update_app_profiles(
+    app: AppHandle,
+    profiles: HashMap<String, AppProfile>,
+) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    let model_manager = app.state::<Arc<ModelManager>>();
+    for profile in profiles.values() {
+        if let Some(id) = &profile.post_process_prompt_id {
+            if !settings.post_process_prompts.iter().any(|p| &p.id == id) {
+                return Err(format!("Prompt with id '{}' not found", id));
+            }
+        }
+        if let Some(id) = &profile.model_id {
+            if model_manager.get_model_info(id).is_none() {
+                return Err(format!("Model with id '{}' not found", id));
+            }
+        }
+    }
+
+    let profiles = profiles
+        .into_iter()
+        .map(|(name, mut profile)| {
+            profile.text_pipeline = profile
+                .text_pipeline
+                .map(|stages| settings::normalize_text_pipeline(&stages));
+            (name, profile)
+        })
+        .collect();
+    settings::update_settings(&app, move |s| s.app_profiles = profiles);
+    Ok(())
+}
+
+// === Developer Mode Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_developer_mode_setting(app: AppHandle, mode: DeveloperMode) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.developer_mode = mode);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_preserve_code_syntax_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.preserve_code_syntax = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_developer_dictionary(app: AppHandle, words: Vec<String>) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.developer_dictionary = words);
+    Ok(())
+}
+
+// === Corrections Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_correction_detection_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.correction_detection_enabled = enabled);
+    Ok(())
+}
+
+// === Context Awareness Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_context_awareness_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.context_awareness_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn update_context_per_app_permissions(
+    app: AppHandle,
+    permissions: std::collections::HashMap<String, bool>,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.context_per_app_permissions = permissions);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_surrounding_text_in_llm_prompt_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.surrounding_text_in_llm_prompt = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_screen_ocr_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.screen_ocr_enabled = enabled);
+    Ok(())
+}
+
+// === Cloud Transcription Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_groq_transcription_api_key_setting(
+    app: AppHandle,
+    api_key: String,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.groq_transcription_api_key = api_key);
+    Ok(())
+}
+
+// === Multi-Language Mixing Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_multilingual_mode_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.multilingual_mode_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_primary_language_setting(
+    app: AppHandle,
+    language: Option<String>,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.primary_language = language);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_secondary_language_setting(
+    app: AppHandle,
+    language: Option<String>,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.secondary_language = language);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_language_detection_sensitivity_setting(
+    app: AppHandle,
+    sensitivity: f32,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| {
+        s.language_detection_sensitivity = sensitivity.clamp(0.0, 1.0)
+    });
+    Ok(())
+}
+
+// === Meeting Mode Commands ===
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_show_meeting_menu_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.show_meeting_menu = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_meeting_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_mode_enabled = enabled);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_meeting_chunk_duration_setting(app: AppHandle, seconds: u32) -> Result<(), String> {
+    let seconds = seconds.clamp(10, 300); // 10s to 5min
+    settings::update_settings(&app, move |s| s.meeting_chunk_duration_seconds = seconds);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.mute_while_recording = enabled;
-    settings::write_settings(&app, settings);
-
+pub fn change_meeting_auto_summarize_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_auto_summarize = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.append_trailing_space = enabled;
-    settings::write_settings(&app, settings);
-
+pub fn change_meeting_extract_action_items_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_extract_action_items = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_app_language_setting(app: AppHandle, language: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.app_language = language.clone();
-    settings::write_settings(&app, settings);
-
-    // Refresh the tray menu with the new language
-    tray::update_tray_menu(&app, &tray::TrayIconState::Idle, Some(&language));
-
+pub fn change_meeting_summary_prompt_setting(app: AppHandle, prompt: String) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_summary_prompt = prompt);
     Ok(())
 }
 
-// === Voice Snippets Commands ===
-
 #[tauri::command]
 #[specta::specta]
-pub fn change_snippets_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.snippets_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_action_items_prompt_setting(
+    app: AppHandle,
+    prompt: String,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_action_items_prompt = prompt);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn update_snippets(app: AppHandle, snippets: Vec<Snippet>) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.snippets = snippets;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_save_audio_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_save_audio = enabled);
     Ok(())
 }
 
-// === Auto-Formatting Commands ===
-
 #[tauri::command]
 #[specta::specta]
-pub fn change_auto_format_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.auto_format_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_segment_topics_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_segment_topics = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_auto_format_lists_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.auto_format_lists = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_webhook_url_setting(app: AppHandle, url: String) -> Result<(), String> {
+    let url = url.trim().to_string();
+    crate::webhook::validate_url(&url)?;
+    settings::update_settings(&app, move |s| s.meeting_webhook_url = url);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_verbal_commands_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.verbal_commands_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_webhook_secret_setting(app: AppHandle, secret: String) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_webhook_secret = secret);
     Ok(())
 }
 
-// === Context-Aware Tone Commands ===
-
 #[tauri::command]
 #[specta::specta]
-pub fn change_tone_adjustment_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.tone_adjustment_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_detection_enabled_setting(
+    app: AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_detection_enabled = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_default_tone_setting(app: AppHandle, tone: ToneStyle) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.default_tone = tone;
-    settings::write_settings(&app, settings);
+pub fn change_meeting_auto_start_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.meeting_auto_start = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn update_app_tone_mappings(
+pub fn change_meeting_task_destinations_setting(
     app: AppHandle,
-    mappings: HashMap<String, ToneStyle>,
+    destinations: Vec<settings::TaskDestination>,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.app_tone_mappings = mappings;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.meeting_task_destinations = destinations);
     Ok(())
 }
 
-// === Developer Mode Commands ===
+// === Live Preview Commands ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_developer_mode_setting(app: AppHandle, mode: DeveloperMode) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.developer_mode = mode;
-    settings::write_settings(&app, settings);
+pub fn change_live_preview_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.live_preview_enabled = enabled);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_preserve_code_syntax_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.preserve_code_syntax = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_live_preview_interval_setting(
+    app: AppHandle,
+    interval_ms: u32,
+) -> Result<(), String> {
+    let interval_ms = interval_ms.clamp(1000, 4000); // 1s to 4s
+    settings::update_settings(&app, move |s| s.live_preview_interval_ms = interval_ms);
     Ok(())
 }
 
+// === File Transcription Commands ===
+
 #[tauri::command]
 #[specta::specta]
-pub fn update_developer_dictionary(app: AppHandle, words: Vec<String>) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.developer_dictionary = words;
-    settings::write_settings(&app, settings);
+pub fn change_ffmpeg_path_setting(app: AppHandle, path: String) -> Result<(), String> {
+    let path = path.trim().to_string();
+    if !path.is_empty() && !std::path::Path::new(&path).is_file() {
+        return Err(format!("ffmpeg not found at {}", path));
+    }
+    settings::update_settings(&app, move |s| s.ffmpeg_path = path);
     Ok(())
 }
 
-// === Corrections Commands ===
-
 #[tauri::command]
 #[specta::specta]
-pub fn change_correction_detection_enabled_setting(
+pub fn change_file_transcription_detect_chapters_setting(
     app: AppHandle,
     enabled: bool,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.correction_detection_enabled = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| {
+        s.file_transcription_detect_chapters = enabled
+    });
     Ok(())
 }
 
-// === Context Awareness Commands ===
+// === Whisper Mode Commands ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_context_awareness_enabled_setting(
-    app: AppHandle,
-    enabled: bool,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.context_awareness_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_whisper_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.whisper_mode_enabled = enabled);
+
+    // Refresh the audio recorder to apply new VAD settings immediately
+    let audio_manager = app.state::<Arc<AudioRecordingManager>>();
+    if let Err(e) = audio_manager.refresh_vad_settings() {
+        error!("Failed to refresh VAD settings: {}", e);
+        // Don't fail the setting change - it will apply on next recording
+    }
+
+    // Notify frontend that whisper mode changed
+    let _ = WhisperModeChanged(enabled).emit(&app);
+
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn update_context_per_app_permissions(
-    app: AppHandle,
-    permissions: std::collections::HashMap<String, bool>,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.context_per_app_permissions = permissions;
-    settings::write_settings(&app, settings);
+pub fn change_vad_threshold_setting(app: AppHandle, threshold: f32) -> Result<(), String> {
+    let threshold = threshold.clamp(0.05, 0.9); // Reasonable range for VAD threshold
+    settings::update_settings(&app, move |s| s.vad_threshold = threshold);
+
+    // Refresh the audio recorder to apply new VAD threshold
+    let audio_manager = app.state::<Arc<AudioRecordingManager>>();
+    if let Err(e) = audio_manager.refresh_vad_settings() {
+        error!("Failed to refresh VAD settings: {}", e);
+        // Don't fail the setting change - it will apply on next recording
+    }
+
     Ok(())
 }
 
-// === Cloud Transcription Commands ===
+// === Recording Limits ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_groq_transcription_api_key_setting(
-    app: AppHandle,
-    api_key: String,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.groq_transcription_api_key = api_key;
-    settings::write_settings(&app, settings);
+pub fn change_max_recording_duration_setting(app: AppHandle, minutes: u32) -> Result<(), String> {
+    let minutes = minutes.min(120); // 0 disables the limit, at most 2 hours
+    settings::update_settings(&app, move |s| s.max_recording_duration_minutes = minutes);
     Ok(())
 }
 
-// === Multi-Language Mixing Commands ===
+// === Metrics Endpoint ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_multilingual_mode_enabled_setting(
+pub fn change_metrics_endpoint_setting(
     app: AppHandle,
     enabled: bool,
+    port: u16,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.multilingual_mode_enabled = enabled;
-    settings::write_settings(&app, settings);
-    Ok(())
+    if enabled && port < 1024 {
+        return Err(format!("Port {} is reserved, use 1024 or higher", port));
+    }
+
+    settings::update_settings(&app, move |s| {
+        s.metrics_endpoint_enabled = enabled;
+        s.metrics_endpoint_port = port;
+    });
+
+    match app.try_state::<Arc<MetricsServer>>() {
+        Some(server) => server.apply_settings().map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
 }
 
+// === Network ===
+
 #[tauri::command]
 #[specta::specta]
-pub fn change_primary_language_setting(
-    app: AppHandle,
-    language: Option<String>,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.primary_language = language;
-    settings::write_settings(&app, settings);
+pub fn change_proxy_settings(app: AppHandle, proxy: ProxySettings) -> Result<(), String> {
+    if proxy.enabled {
+        crate::proxy::build_proxy(&proxy)?;
+    }
+
+    crate::proxy::configure(&proxy);
+    settings::update_settings(&app, move |s| s.proxy = proxy);
     Ok(())
 }
 
+// === Quiet Hours ===
+
 #[tauri::command]
 #[specta::specta]
-pub fn change_secondary_language_setting(
-    app: AppHandle,
-    language: Option<String>,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.secondary_language = language;
-    settings::write_settings(&app, settings);
+pub fn change_quiet_hours_setting(app: AppHandle, quiet_hours: DailyWindow) -> Result<(), String> {
+    quiet_hours.validate()?;
+
+    settings::update_settings(&app, move |s| s.quiet_hours = quiet_hours);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_language_detection_sensitivity_setting(
-    app: AppHandle,
-    sensitivity: f32,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.language_detection_sensitivity = sensitivity.clamp(0.0, 1.0);
-    settings::write_settings(&app, settings);
-    Ok(())
+pub fn get_quiet_hours_active(app: AppHandle) -> bool {
+    crate::quiet_hours::is_active(&app)
 }
 
-// === Meeting Mode Commands ===
+// === Focus / Presentation ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_show_meeting_menu_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.show_meeting_menu = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_focus_suppression_setting(
+    app: AppHandle,
+    focus_suppression: FocusSuppression,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.focus_suppression = focus_suppression);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_meeting_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_mode_enabled = enabled;
-    settings::write_settings(&app, settings);
-    Ok(())
+pub fn get_focus_state() -> crate::focus_mode::FocusState {
+    crate::focus_mode::current_state()
 }
 
-#[tauri::command]
-#[specta::specta]
-pub fn change_meeting_chunk_duration_setting(app: AppHandle, seconds: u32) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_chunk_duration_seconds = seconds.clamp(10, 300); // 10s to 5min
-    settings::write_settings(&app, settings);
-    Ok(())
-}
+// === Hardware Keys ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_meeting_auto_summarize_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_auto_summarize = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_hardware_keys_setting(
+    app: AppHandle,
+    hardware_keys: HardwareKeys,
+) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.hardware_keys = hardware_keys);
+    crate::input::hardware_keys::start(&app);
     Ok(())
 }
 
+// === Mouse Buttons ===
+
 #[tauri::command]
 #[specta::specta]
-pub fn change_meeting_extract_action_items_setting(
+pub fn change_mouse_buttons_setting(
     app: AppHandle,
-    enabled: bool,
+    mouse_buttons: MouseButtons,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_extract_action_items = enabled;
-    settings::write_settings(&app, settings);
+    settings::update_settings(&app, move |s| s.mouse_buttons = mouse_buttons);
+    crate::input::mouse_buttons::start(&app);
     Ok(())
 }
 
+// === Typing ===
+
+/// Set how the "type" paste method paces its keystrokes
 #[tauri::command]
 #[specta::specta]
-pub fn change_meeting_summary_prompt_setting(app: AppHandle, prompt: String) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_summary_prompt = prompt;
-    settings::write_settings(&app, settings);
+pub fn change_typing_settings(app: AppHandle, typing: TypingSettings) -> Result<(), String> {
+    if typing.chars_per_second > 2000 {
+        return Err("Typing speed must be at most 2000 characters per second".to_string());
+    }
+    if typing.chunk_delay_ms > 10_000 {
+        return Err("Chunk delay must be at most 10 seconds".to_string());
+    }
+    settings::update_settings(&app, move |s| s.typing = typing);
     Ok(())
 }
 
+// === Cycle Shortcuts ===
+
+/// Set the microphones, models and languages the cycle shortcuts step through
 #[tauri::command]
 #[specta::specta]
-pub fn change_meeting_action_items_prompt_setting(
-    app: AppHandle,
-    prompt: String,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.meeting_action_items_prompt = prompt;
-    settings::write_settings(&app, settings);
+pub fn change_cycle_lists_setting(app: AppHandle, cycle_lists: CycleLists) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.cycle_lists = cycle_lists);
     Ok(())
 }
 
-// === Live Preview Commands ===
+// === HID Triggers ===
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_live_preview_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.live_preview_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub fn change_hid_triggers_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    settings::update_settings(&app, move |s| s.hid_triggers_enabled = enabled);
+    hid::start(&app);
     Ok(())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub fn change_live_preview_interval_setting(
-    app: AppHandle,
-    interval_ms: u32,
-) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.live_preview_interval_ms = interval_ms.clamp(1000, 4000); // 1s to 4s
-    settings::write_settings(&app, settings);
-    Ok(())
+pub fn get_hid_devices() -> Result<Vec<HidDeviceInfo>, String> {
+    hid::list_devices()
 }
 
-// === Whisper Mode Commands ===
-
+/// Wait for the user to click a button on the device, to bind it
 #[tauri::command]
 #[specta::specta]
-pub fn change_whisper_mode_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.whisper_mode_enabled = enabled;
-    settings::write_settings(&app, settings);
+pub async fn capture_hid_button(vendor_id: u16, product_id: u16) -> Result<HidButton, String> {
+    tauri::async_runtime::spawn_blocking(move || hid::capture_button(vendor_id, product_id))
+        .await
+        .map_err(|e| format!("HID capture failed: {}", e))?
+}
 
-    // Refresh the audio recorder to apply new VAD settings immediately
-    let audio_manager = app.state::<Arc<AudioRecordingManager>>();
-    if let Err(e) = audio_manager.refresh_vad_settings() {
-        error!("Failed to refresh VAD settings: {}", e);
-        // Don't fail the setting change - it will apply on next recording
+#[tauri::command]
+#[specta::specta]
+pub fn add_hid_binding(
+    app: AppHandle,
+    name: String,
+    vendor_id: u16,
+    product_id: u16,
+    byte_index: u32,
+    mask: u8,
+    action: HidButtonAction,
+) -> Result<HidBinding, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("HID binding name cannot be empty".to_string());
+    }
+    if mask == 0 {
+        return Err("HID binding needs at least one button bit".to_string());
+    }
+    let settings = settings::get_settings(&app);
+    if let Some(other) = settings.hid_bindings.iter().find(|b| {
+        b.vendor_id == vendor_id
+            && b.product_id == product_id
+            && b.byte_index == byte_index
+            && b.mask & mask != 0
+    }) {
+        return Err(format!("That button is already bound to '{}'", other.name));
     }
 
-    // Notify frontend that whisper mode changed
-    let _ = app.emit("whisper-mode-changed", enabled);
+    let binding = HidBinding {
+        id: format!("hid_binding_{}", chrono::Utc::now().timestamp_millis()),
+        name,
+        vendor_id,
+        product_id,
+        byte_index,
+        mask,
+        action,
+    };
+    let stored = binding.clone();
+    settings::update_settings(&app, move |s| s.hid_bindings.push(stored));
+    hid::start(&app);
+    Ok(binding)
+}
 
+#[tauri::command]
+#[specta::specta]
+pub fn remove_hid_binding(app: AppHandle, id: String) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    if !settings.hid_bindings.iter().any(|b| b.id == id) {
+        return Err(format!("HID binding '{}' not found", id));
+    }
+    settings::update_settings(&app, move |s| s.hid_bindings.retain(|b| b.id != id));
     Ok(())
 }
 
+// === Language Learning ===
+
 #[tauri::command]
 #[specta::specta]
-pub fn change_vad_threshold_setting(app: AppHandle, threshold: f32) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
-    settings.vad_threshold = threshold.clamp(0.05, 0.9); // Reasonable range for VAD threshold
-    settings::write_settings(&app, settings);
-
-    // Refresh the audio recorder to apply new VAD threshold
-    let audio_manager = app.state::<Arc<AudioRecordingManager>>();
-    if let Err(e) = audio_manager.refresh_vad_settings() {
-        error!("Failed to refresh VAD settings: {}", e);
-        // Don't fail the setting change - it will apply on next recording
+pub fn change_dual_script_setting(
+    app: AppHandle,
+    enabled: bool,
+    language: String,
+) -> Result<(), String> {
+    let language = language.trim().to_string();
+    if language.is_empty() {
+        return Err("Translation language cannot be empty".to_string());
     }
 
+    settings::update_settings(&app, move |s| {
+        s.dual_script_enabled = enabled;
+        s.dual_script_language = language;
+    });
     Ok(())
 }
 
+/// Turn any transcript into dual-script study notes, e.g. before exporting
+#[tauri::command]
+#[specta::specta]
+pub async fn convert_to_dual_script(app: AppHandle, text: String) -> Result<String, String> {
+    let settings = settings::get_settings(&app);
+    crate::dual_script::dual_script(&settings, &text).await
+}
+
 /// Validate that a shortcut contains at least one non-modifier key.
 /// The tauri-plugin-global-shortcut library requires at least one main key.
+/// Double-tap shortcuts are the exception and must name a single modifier.
 fn validate_shortcut_string(raw: &str) -> Result<(), String> {
     if raw.trim().is_empty() {
         return Err("Shortcut cannot be empty".into());
     }
+    if double_tap::is_double_tap(raw) {
+        return double_tap::parse(raw).map(|_| ());
+    }
 
     let modifiers = [
         "ctrl", "control", "shift", "alt", "option", "meta", "command", "cmd", "super", "win",
@@ -1065,6 +2012,11 @@ pub fn unregister_cancel_shortcut(app: &AppHandle) {
 }
 
 pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    // Unassigned, nothing to listen for
+    if binding.current_binding.trim().is_empty() {
+        return Ok(());
+    }
+
     // Validate human-level rules first
     if let Err(e) = validate_shortcut_string(&binding.current_binding) {
         warn!(
@@ -1074,6 +2026,11 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
         return Err(e);
     }
 
+    // Modifier-only triggers are watched by the input listener instead
+    if double_tap::is_double_tap(&binding.current_binding) {
+        return double_tap::register(app, &binding);
+    }
+
     // Parse shortcut and return error if it fails
     let shortcut = match binding.current_binding.parse::<Shortcut>() {
         Ok(s) => s,
@@ -1101,61 +2058,19 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
         .on_shortcut(shortcut, move |ah, scut, event| {
             if scut == &shortcut {
                 let shortcut_string = scut.into_string();
-                let settings = get_settings(ah);
-
-                if let Some(action) = ACTION_MAP.get(&binding_id_for_closure) {
-                    if binding_id_for_closure == "cancel" {
-                        let audio_manager = ah.state::<Arc<AudioRecordingManager>>();
-                        if audio_manager.is_recording() && event.state == ShortcutState::Pressed {
-                            action.start(ah, &binding_id_for_closure, &shortcut_string);
-                        }
-                        return;
-                    } else if settings.push_to_talk {
-                        if event.state == ShortcutState::Pressed {
-                            action.start(ah, &binding_id_for_closure, &shortcut_string);
-                        } else if event.state == ShortcutState::Released {
-                            action.stop(ah, &binding_id_for_closure, &shortcut_string);
-                        }
-                    } else {
-                        // Toggle mode: toggle on press only
-                        if event.state == ShortcutState::Pressed {
-                            // Determine action and update state while holding the lock,
-                            // but RELEASE the lock before calling the action to avoid deadlocks.
-                            // (Actions may need to acquire the lock themselves, e.g., cancel_current_operation)
-                            let should_start: bool;
-                            {
-                                let toggle_state_manager = ah.state::<ManagedToggleState>();
-                                let mut states = toggle_state_manager
-                                    .lock()
-                                    .expect("Failed to lock toggle state manager");
-
-                                let is_currently_active = states
-                                    .active_toggles
-                                    .entry(binding_id_for_closure.clone())
-                                    .or_insert(false);
-
-                                should_start = !*is_currently_active;
-                                *is_currently_active = should_start;
-                            } // Lock released here
-
-                            // Now call the action without holding the lock
-                            if should_start {
-                                action.start(ah, &binding_id_for_closure, &shortcut_string);
-                            } else {
-                                action.stop(ah, &binding_id_for_closure, &shortcut_string);
-                            }
-                        }
-                    }
-                } else {
-                    warn!(
-                        "No action defined in ACTION_MAP for shortcut ID '{}'. Shortcut: '{}', State: {:?}",
-                        binding_id_for_closure, shortcut_string, event.state
-                    );
-                }
+                handle_shortcut_event(
+                    ah,
+                    &binding_id_for_closure,
+                    &shortcut_string,
+                    event.state == ShortcutState::Pressed,
+                );
             }
         })
         .map_err(|e| {
-            let error_msg = format!("Couldn't register shortcut '{}': {}", binding.current_binding, e);
+            let error_msg = format!(
+                "Couldn't register shortcut '{}': {}",
+                binding.current_binding, e
+            );
             error!("_register_shortcut registration error: {}", error_msg);
             error_msg
         })?;
@@ -1163,7 +2078,138 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
     Ok(())
 }
 
+/// Run the action of `binding_id` for a press or release of its shortcut
+pub fn handle_shortcut_event(
+    ah: &AppHandle,
+    binding_id: &str,
+    shortcut_string: &str,
+    pressed: bool,
+) {
+    let settings = get_settings(ah);
+
+    if let Some(action) = ACTION_MAP.get(binding_id) {
+        if binding_id == "cancel" {
+            let audio_manager = ah.state::<Arc<AudioRecordingManager>>();
+            if audio_manager.is_recording() && pressed {
+                action.start(ah, binding_id, shortcut_string);
+            }
+        } else if settings.push_to_talk && settings.ptt_hybrid_mode && !action.is_instant() {
+            let threshold = Duration::from_millis(settings.ptt_latch_threshold_ms as u64);
+            // Release the lock before calling the action, which may take it itself
+            let step = {
+                let toggle_state_manager = ah.state::<ManagedToggleState>();
+                let mut states = toggle_state_manager
+                    .lock()
+                    .expect("Failed to lock toggle state manager");
+                let mut active = states
+                    .active_toggles
+                    .get(binding_id)
+                    .copied()
+                    .unwrap_or(false);
+                let mut held_since = states.held_since.remove(binding_id);
+                let step = hybrid_ptt_step(
+                    &mut active,
+                    &mut held_since,
+                    pressed,
+                    Instant::now(),
+                    threshold,
+                );
+                states.active_toggles.insert(binding_id.to_string(), active);
+                if let Some(at) = held_since {
+                    states.held_since.insert(binding_id.to_string(), at);
+                }
+                step
+            };
+
+            match step {
+                Some(true) => action.start(ah, binding_id, shortcut_string),
+                Some(false) => action.stop(ah, binding_id, shortcut_string),
+                None => {}
+            }
+        } else if settings.push_to_talk || action.is_instant() {
+            if pressed {
+                action.start(ah, binding_id, shortcut_string);
+            } else {
+                action.stop(ah, binding_id, shortcut_string);
+            }
+        } else {
+            // Toggle mode: toggle on press only
+            if pressed {
+                // Determine action and update state while holding the lock,
+                // but RELEASE the lock before calling the action to avoid deadlocks.
+                // (Actions may need to acquire the lock themselves, e.g., cancel_current_operation)
+                let should_start: bool;
+                {
+                    let toggle_state_manager = ah.state::<ManagedToggleState>();
+                    let mut states = toggle_state_manager
+                        .lock()
+                        .expect("Failed to lock toggle state manager");
+
+                    let is_currently_active = states
+                        .active_toggles
+                        .entry(binding_id.to_string())
+                        .or_insert(false);
+
+                    should_start = !*is_currently_active;
+                    *is_currently_active = should_start;
+                } // Lock released here
+
+                // Now call the action without holding the lock
+                if should_start {
+                    action.start(ah, binding_id, shortcut_string);
+                } else {
+                    action.stop(ah, binding_id, shortcut_string);
+                }
+            }
+        }
+    } else {
+        warn!(
+            "No action defined in ACTION_MAP for shortcut ID '{}'. Shortcut: '{}', Pressed: {}",
+            binding_id, shortcut_string, pressed
+        );
+    }
+}
+
+/// Advances a hybrid push-to-talk shortcut on a press or release and returns
+/// whether to start (`Some(true)`) or stop (`Some(false)`) the action.
+///
+/// A press starts recording when idle and stops it when latched. Its release
+/// stops a recording held for at least `threshold`, and otherwise leaves it
+/// latched on hands-free. `held_since` is the time of the press that started
+/// the recording, until its release.
+fn hybrid_ptt_step(
+    active: &mut bool,
+    held_since: &mut Option<Instant>,
+    pressed: bool,
+    now: Instant,
+    threshold: Duration,
+) -> Option<bool> {
+    if pressed {
+        if *active && held_since.is_some() {
+            // Key repeat while holding
+            return None;
+        }
+        *active = !*active;
+        *held_since = active.then_some(now);
+        return Some(*active);
+    }
+
+    let held = held_since.take()?;
+    if *active && now.duration_since(held) >= threshold {
+        *active = false;
+        return Some(false);
+    }
+    None
+}
+
 pub fn unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    if binding.current_binding.trim().is_empty() {
+        return Ok(());
+    }
+    if double_tap::is_double_tap(&binding.current_binding) {
+        return double_tap::unregister(&binding);
+    }
+
     let shortcut = match binding.current_binding.parse::<Shortcut>() {
         Ok(s) => s,
         Err(e) => {
@@ -1187,3 +2233,159 @@ pub fn unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_millis(400);
+
+    fn binding(id: &str, shortcut: &str) -> ShortcutBinding {
+        ShortcutBinding {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            default_binding: shortcut.to_string(),
+            current_binding: shortcut.to_string(),
+        }
+    }
+
+    #[test]
+    fn double_tap_conflicts_match_the_modifier() {
+        assert!(same_shortcut(
+            "double_tap:right_ctrl",
+            " double_tap:Right_Ctrl"
+        ));
+        assert!(!same_shortcut(
+            "double_tap:right_ctrl",
+            "double_tap:left_ctrl"
+        ));
+        assert!(!same_shortcut("double_tap:right_ctrl", "ctrl+space"));
+        // Unassigned bindings never conflict
+        assert!(!same_shortcut("", ""));
+    }
+
+    #[test]
+    fn shortcuts_match_however_they_are_written() {
+        assert!(same_shortcut("ctrl+space", "Control+Space"));
+        assert!(same_shortcut("shift+alt+k", "Alt+Shift+K"));
+        assert!(!same_shortcut("ctrl+space", "ctrl+shift+space"));
+    }
+
+    #[test]
+    fn binding_conflict_skips_the_binding_being_changed() {
+        let mut settings = settings::get_default_settings();
+        settings.bindings = [
+            binding("transcribe", "double_tap:right_ctrl"),
+            binding("meeting", "double_tap:left_ctrl"),
+            binding("paste_last_transcript", ""),
+        ]
+        .into_iter()
+        .map(|b| (b.id.clone(), b))
+        .collect();
+
+        assert_eq!(
+            find_conflict(&settings, "meeting", "double_tap:right_ctrl"),
+            Some(ShortcutConflict::Binding {
+                id: "transcribe".to_string(),
+                name: "transcribe".to_string(),
+            })
+        );
+        assert!(find_conflict(&settings, "transcribe", "double_tap:right_ctrl").is_none());
+        assert!(find_conflict(&settings, "meeting", "").is_none());
+    }
+
+    #[test]
+    fn profiles_and_devices_conflict_too() {
+        let mut settings = settings::get_default_settings();
+        settings.bindings = [binding("transcribe", "ctrl+space")]
+            .into_iter()
+            .map(|b| (b.id.clone(), b))
+            .collect();
+        settings.shortcut_profiles = vec![ShortcutProfile {
+            id: "laptop".to_string(),
+            name: "Laptop".to_string(),
+            bindings: HashMap::from([("meeting".to_string(), "alt+m".to_string())]),
+        }];
+        settings.hardware_keys.enabled = true;
+        settings.hardware_keys.play_pause = HardwareKeyAction::ToggleRecording;
+
+        assert_eq!(
+            find_conflict(&settings, "transcribe", "Alt+M"),
+            Some(ShortcutConflict::Profile {
+                id: "laptop".to_string(),
+                name: "Laptop".to_string(),
+            })
+        );
+        // A profile that sets its own shortcut for the binding isn't affected
+        assert!(find_conflict(&settings, "meeting", "alt+m").is_none());
+        assert_eq!(
+            find_conflict(&settings, "transcribe", "MediaPlayPause"),
+            Some(ShortcutConflict::Device {
+                name: "the play/pause key".to_string(),
+            })
+        );
+        settings.hardware_keys.play_pause = HardwareKeyAction::Disabled;
+        assert!(find_conflict(&settings, "transcribe", "MediaPlayPause").is_none());
+    }
+
+    #[test]
+    fn only_taken_keys_are_system_conflicts() {
+        assert!(is_already_registered(
+            "Couldn't register shortcut 'ctrl+space': HotKey already registered: HotKey { .. }"
+        ));
+        assert!(is_already_registered(
+            "Shortcut 'ctrl+space' is already in use"
+        ));
+        assert!(!is_already_registered(
+            "Failed to parse shortcut 'ctrl+foo': Couldn't recognize \"foo\" as a valid key"
+        ));
+    }
+
+    #[test]
+    fn hybrid_ptt_short_press_latches_until_next_press() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let (mut active, mut held) = (false, None);
+
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, true, at(0), THRESHOLD),
+            Some(true)
+        );
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, false, at(150), THRESHOLD),
+            None
+        );
+        assert!(active);
+
+        // The press that ends it stops right away and its release does nothing
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, true, at(5000), THRESHOLD),
+            Some(false)
+        );
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, false, at(5100), THRESHOLD),
+            None
+        );
+        assert!(!active);
+    }
+
+    #[test]
+    fn hybrid_ptt_long_hold_stops_on_release() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let (mut active, mut held) = (false, None);
+
+        hybrid_ptt_step(&mut active, &mut held, true, at(0), THRESHOLD);
+        // Key repeat while holding
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, true, at(300), THRESHOLD),
+            None
+        );
+        assert_eq!(
+            hybrid_ptt_step(&mut active, &mut held, false, at(2000), THRESHOLD),
+            Some(false)
+        );
+        assert!(!active);
+    }
+}
@@ -0,0 +1,80 @@
+//! Send meeting action items to task managers.
+//!
+//! Each task manager is a `TaskProvider`. The destinations in
+//! `meeting_task_destinations` pick a provider and, optionally, the project
+//! or list the tasks go to. After a meeting is finalized its action items
+//! are pushed to every destination; a failing destination doesn't stop the
+//! others.
+
+mod reminders;
+mod things;
+mod todoist;
+
+use futures_util::future::BoxFuture;
+use log::{info, warn};
+
+use crate::settings::{TaskDestination, TaskProviderKind};
+
+/// Tasks created from one meeting
+#[derive(Debug, Clone)]
+pub struct TaskBatch {
+    pub titles: Vec<String>,
+    /// Added to each task, e.g. which meeting it came from
+    pub note: String,
+}
+
+pub trait TaskProvider: Send + Sync {
+    /// Shown in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Create one task per title in the destination's list
+    fn push<'a>(
+        &'a self,
+        destination: &'a TaskDestination,
+        batch: &'a TaskBatch,
+    ) -> BoxFuture<'a, Result<(), String>>;
+}
+
+fn provider_for(kind: &TaskProviderKind) -> Box<dyn TaskProvider> {
+    match kind {
+        TaskProviderKind::Todoist => Box::new(todoist::Todoist),
+        TaskProviderKind::Things => Box::new(things::Things),
+        TaskProviderKind::Reminders => Box::new(reminders::Reminders),
+    }
+}
+
+/// Push the batch to every destination, returning the errors by provider
+pub async fn push_to_all(
+    destinations: &[TaskDestination],
+    batch: &TaskBatch,
+) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    if batch.titles.is_empty() {
+        return errors;
+    }
+    for destination in destinations {
+        let provider = provider_for(&destination.provider);
+        match provider.push(destination, batch).await {
+            Ok(()) => info!(
+                "Sent {} action items to {}",
+                batch.titles.len(),
+                provider.name()
+            ),
+            Err(e) => {
+                warn!("Failed to send action items to {}: {}", provider.name(), e);
+                errors.push((provider.name(), e));
+            }
+        }
+    }
+    errors
+}
+
+/// Run a blocking command off the async runtime
+async fn run_blocking<F>(f: F) -> Result<(), String>
+where
+    F: FnOnce() -> Result<(), String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("Task panicked: {}", e))?
+}
@@ -0,0 +1,88 @@
+//! Apple Reminders on macOS, scripted with AppleScript. The list is the
+//! name of a Reminders list; empty uses the default list.
+
+use futures_util::future::BoxFuture;
+
+use super::{run_blocking, TaskBatch, TaskProvider};
+use crate::settings::TaskDestination;
+
+pub struct Reminders;
+
+/// AppleScript string literal
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn script(destination: &TaskDestination, batch: &TaskBatch) -> String {
+    let list = if destination.list.is_empty() {
+        "default list".to_string()
+    } else {
+        format!("list {}", quote(&destination.list))
+    };
+    let mut script = format!(
+        "tell application \"Reminders\"\nset targetList to {}\n",
+        list
+    );
+    for title in &batch.titles {
+        script.push_str(&format!(
+            "make new reminder at end of targetList with properties {{name:{}, body:{}}}\n",
+            quote(title),
+            quote(&batch.note)
+        ));
+    }
+    script.push_str("end tell");
+    script
+}
+
+impl TaskProvider for Reminders {
+    fn name(&self) -> &'static str {
+        "Reminders"
+    }
+
+    fn push<'a>(
+        &'a self,
+        destination: &'a TaskDestination,
+        batch: &'a TaskBatch,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        let script = script(destination, batch);
+        Box::pin(async move {
+            if !cfg!(target_os = "macos") {
+                return Err("Reminders is only available on macOS".to_string());
+            }
+            run_blocking(move || {
+                let output = std::process::Command::new("osascript")
+                    .args(["-e", &script])
+                    .output()
+                    .map_err(|e| format!("Failed to run osascript: {}", e))?;
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+                }
+            })
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::TaskProviderKind;
+
+    #[test]
+    fn script_quotes_titles_and_lists() {
+        let destination = TaskDestination {
+            provider: TaskProviderKind::Reminders,
+            list: "Work".to_string(),
+            api_token: String::new(),
+        };
+        let batch = TaskBatch {
+            titles: vec!["Review \"Q3\" plan".to_string()],
+            note: String::new(),
+        };
+        let script = script(&destination, &batch);
+        assert!(script.contains("set targetList to list \"Work\""));
+        assert!(script.contains("{name:\"Review \\\"Q3\\\" plan\", body:\"\"}"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Things 3 on macOS, through its `things:///add` URL scheme. The list is
+//! the name of a project or area; empty puts tasks in the Inbox.
+
+use futures_util::future::BoxFuture;
+
+use super::{run_blocking, TaskBatch, TaskProvider};
+use crate::settings::TaskDestination;
+use crate::utils::percent_encode;
+
+pub struct Things;
+
+/// URL adding every title as its own to-do
+fn add_url(destination: &TaskDestination, batch: &TaskBatch) -> String {
+    let mut url = format!(
+        "things:///add?titles={}&notes={}",
+        percent_encode(&batch.titles.join("\n")),
+        percent_encode(&batch.note)
+    );
+    if !destination.list.is_empty() {
+        url.push_str(&format!("&list={}", percent_encode(&destination.list)));
+    }
+    url
+}
+
+impl TaskProvider for Things {
+    fn name(&self) -> &'static str {
+        "Things"
+    }
+
+    fn push<'a>(
+        &'a self,
+        destination: &'a TaskDestination,
+        batch: &'a TaskBatch,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        let url = add_url(destination, batch);
+        Box::pin(async move {
+            if !cfg!(target_os = "macos") {
+                return Err("Things is only available on macOS".to_string());
+            }
+            run_blocking(move || {
+                let status = std::process::Command::new("open")
+                    .args(["-g", &url])
+                    .status()
+                    .map_err(|e| format!("Failed to open Things: {}", e))?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err("Things is not installed".to_string())
+                }
+            })
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::TaskProviderKind;
+
+    #[test]
+    fn url_adds_every_title_to_the_list() {
+        let destination = TaskDestination {
+            provider: TaskProviderKind::Things,
+            list: "Work".to_string(),
+            api_token: String::new(),
+        };
+        let batch = TaskBatch {
+            titles: vec!["Send invite".to_string(), "Book room".to_string()],
+            note: "From standup".to_string(),
+        };
+        assert_eq!(
+            add_url(&destination, &batch),
+            "things:///add?titles=Send%20invite%0ABook%20room&notes=From%20standup&list=Work"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Todoist, through its REST API. Needs an API token from Todoist's
+//! integration settings; the list is an optional project ID.
+
+use futures_util::future::BoxFuture;
+use serde::Serialize;
+use std::time::Duration;
+
+use super::{TaskBatch, TaskProvider};
+use crate::settings::TaskDestination;
+
+const TASKS_URL: &str = "https://api.todoist.com/rest/v2/tasks";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Serialize)]
+struct NewTask<'a> {
+    content: &'a str,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<&'a str>,
+}
+
+pub struct Todoist;
+
+impl TaskProvider for Todoist {
+    fn name(&self) -> &'static str {
+        "Todoist"
+    }
+
+    fn push<'a>(
+        &'a self,
+        destination: &'a TaskDestination,
+        batch: &'a TaskBatch,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            if destination.api_token.is_empty() {
+                return Err("No Todoist API token configured".to_string());
+            }
+            let client = crate::proxy::client_builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            let project_id = Some(destination.list.as_str()).filter(|id| !id.is_empty());
+
+            for title in &batch.titles {
+                let response = client
+                    .post(TASKS_URL)
+                    .bearer_auth(&destination.api_token)
+                    .json(&NewTask {
+                        content: title,
+                        description: &batch.note,
+                        project_id,
+                    })
+                    .send()
+                    .await
+                    .map_err(|e| format!("Request failed: {}", e))?;
+                if !response.status().is_success() {
+                    return Err(format!("Todoist returned {}", response.status()));
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -4,7 +4,7 @@ use crate::tray_i18n::get_tray_translations;
 use log::{error, info, warn};
 use std::sync::Arc;
 use tauri::image::Image;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::tray::TrayIcon;
 use tauri::{AppHandle, Manager, Theme};
 use tauri_plugin_clipboard_manager::ClipboardExt;
@@ -79,6 +79,9 @@ pub fn change_tray_icon(app: &AppHandle, icon: TrayIconState) {
     update_tray_menu(app, &icon, None);
 }
 
+/// Prefix of the ids of the shortcut profile items, followed by the profile id
+pub const SHORTCUT_PROFILE_ITEM_PREFIX: &str = "shortcut_profile:";
+
 pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&str>) {
     let settings = settings::get_settings(app);
 
@@ -127,11 +130,12 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
         .expect("failed to create quit item");
     let separator = || PredefinedMenuItem::separator(app).expect("failed to create separator");
 
-    let menu = match state {
+    // Position of the settings item, which the shortcut profiles go above
+    let (menu, settings_position) = match state {
         TrayIconState::Recording | TrayIconState::Transcribing => {
             let cancel_i = MenuItem::with_id(app, "cancel", &strings.cancel, true, None::<&str>)
                 .expect("failed to create cancel item");
-            Menu::with_items(
+            let menu = Menu::with_items(
                 app,
                 &[
                     &version_i,
@@ -146,23 +150,60 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
                     &quit_i,
                 ],
             )
-            .expect("failed to create menu")
+            .expect("failed to create menu");
+            (menu, 6)
+        }
+        TrayIconState::Idle => {
+            let menu = Menu::with_items(
+                app,
+                &[
+                    &version_i,
+                    &separator(),
+                    &copy_last_transcript_i,
+                    &separator(),
+                    &settings_i,
+                    &check_updates_i,
+                    &separator(),
+                    &quit_i,
+                ],
+            )
+            .expect("failed to create menu");
+            (menu, 4)
         }
-        TrayIconState::Idle => Menu::with_items(
+    };
+
+    // Switching shortcut profiles, once there is more than one
+    if settings.shortcut_profiles.len() > 1 {
+        let profile_items: Vec<CheckMenuItem<_>> = settings
+            .shortcut_profiles
+            .iter()
+            .map(|profile| {
+                CheckMenuItem::with_id(
+                    app,
+                    format!("{}{}", SHORTCUT_PROFILE_ITEM_PREFIX, profile.id),
+                    &profile.name,
+                    true,
+                    settings.active_shortcut_profile_id.as_ref() == Some(&profile.id),
+                    None::<&str>,
+                )
+                .expect("failed to create shortcut profile item")
+            })
+            .collect();
+        let profile_item_refs: Vec<&dyn IsMenuItem<_>> = profile_items
+            .iter()
+            .map(|item| item as &dyn IsMenuItem<_>)
+            .collect();
+        let shortcut_profiles_i = Submenu::with_id_and_items(
             app,
-            &[
-                &version_i,
-                &separator(),
-                &copy_last_transcript_i,
-                &separator(),
-                &settings_i,
-                &check_updates_i,
-                &separator(),
-                &quit_i,
-            ],
+            "shortcut_profiles",
+            &strings.shortcut_profiles,
+            true,
+            &profile_item_refs,
         )
-        .expect("failed to create menu"),
-    };
+        .expect("failed to create shortcut profiles submenu");
+        menu.insert_items(&[&shortcut_profiles_i, &separator()], settings_position)
+            .expect("failed to add shortcut profiles submenu");
+    }
 
     let tray = app.state::<TrayIcon>();
     let _ = tray.set_menu(Some(menu));
@@ -176,21 +217,28 @@ fn last_transcript_text(entry: &HistoryEntry) -> &str {
         .unwrap_or(&entry.transcription_text)
 }
 
-pub fn copy_last_transcript(app: &AppHandle) {
+/// The text of the most recent transcription, post-processed if it was
+pub fn latest_transcript(app: &AppHandle) -> Option<String> {
     let history_manager = app.state::<Arc<HistoryManager>>();
-    let entry = match history_manager.get_latest_entry() {
-        Ok(Some(entry)) => entry,
+    match history_manager.get_latest_entry() {
+        Ok(Some(entry)) => Some(last_transcript_text(&entry).to_string()),
         Ok(None) => {
-            warn!("No transcription history entries available for tray copy.");
-            return;
+            warn!("No transcription history entries available.");
+            None
         }
         Err(err) => {
             error!("Failed to fetch last transcription entry: {}", err);
-            return;
+            None
         }
+    }
+}
+
+pub fn copy_last_transcript(app: &AppHandle) {
+    let Some(text) = latest_transcript(app) else {
+        return;
     };
 
-    if let Err(err) = app.clipboard().write_text(last_transcript_text(&entry)) {
+    if let Err(err) = app.clipboard().write_text(text) {
         error!("Failed to copy last transcript to clipboard: {}", err);
         return;
     }
@@ -211,8 +259,12 @@ mod tests {
             saved: false,
             title: "Recording".to_string(),
             transcription_text: transcription.to_string(),
+            raw_text: None,
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
+            app_name: None,
+            app_domain: None,
+            duration_ms: None,
         }
     }
 
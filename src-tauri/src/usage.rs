@@ -0,0 +1,236 @@
+//! Usage of paid cloud APIs, for seeing what dictation costs.
+//!
+//! Every chat completion and Groq transcription is recorded in the
+//! `api_usage` table of the history database, with its token counts or audio
+//! length. `llm_client` and `groq_transcription` have no app handle, so the
+//! history manager is registered here once at startup. The cost is an
+//! estimate from list prices; models without a known price are counted but
+//! not priced.
+
+use anyhow::Result;
+use chrono::Utc;
+use log::warn;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use specta::Type;
+use std::sync::{Arc, OnceLock};
+
+use crate::managers::history::HistoryManager;
+
+/// Where usage is recorded, set once at startup
+static HISTORY: OnceLock<Arc<HistoryManager>> = OnceLock::new();
+
+/// USD per million input and output tokens. Matched anywhere in the model id
+/// so router prefixes and dated versions still match; more specific ids
+/// come first.
+const TOKEN_PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("claude-3-5-haiku-latest", 0.80, 4.00),
+    ("claude-3-5-sonnet-latest", 3.00, 15.00),
+    ("llama-3.1-8b-instant", 0.05, 0.08),
+    ("llama-3.3-70b-versatile", 0.59, 0.79),
+];
+
+/// USD per hour of audio for the Groq transcription models, matched the same
+/// way
+const AUDIO_PRICES: &[(&str, f64)] = &[
+    ("distil-whisper-large-v3-en", 0.02),
+    ("whisper-large-v3-turbo", 0.04),
+    ("whisper-large-v3", 0.111),
+];
+
+/// One request to a cloud API
+#[derive(Clone, Debug, Default)]
+pub struct ApiUsage {
+    pub provider_id: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub audio_seconds: f64,
+}
+
+/// Usage of one provider on one day
+#[derive(Clone, Debug, PartialEq, Serialize, Type)]
+pub struct ApiUsageStats {
+    /// Local date, `YYYY-MM-DD`
+    pub day: String,
+    pub provider_id: String,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub audio_seconds: f64,
+    pub estimated_cost_usd: f64,
+    /// Requests to models without a known price, left out of the cost
+    pub unpriced_requests: i64,
+}
+
+/// Make `history` the database usage is recorded in
+pub fn register(history: Arc<HistoryManager>) {
+    let _ = HISTORY.set(history);
+}
+
+/// Record one request. Failing to record never fails the request itself.
+pub fn record(usage: ApiUsage) {
+    let Some(history) = HISTORY.get() else {
+        return;
+    };
+    let result = history
+        .get_connection()
+        .and_then(|conn| insert_usage(&conn, &usage, Utc::now().timestamp()));
+    if let Err(e) = result {
+        warn!("Failed to record API usage: {}", e);
+    }
+}
+
+fn insert_usage(conn: &Connection, usage: &ApiUsage, timestamp: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO api_usage (timestamp, provider_id, model, input_tokens, output_tokens, audio_seconds)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            timestamp,
+            usage.provider_id,
+            usage.model,
+            usage.input_tokens as i64,
+            usage.output_tokens as i64,
+            usage.audio_seconds
+        ],
+    )?;
+    Ok(())
+}
+
+/// Rough token count for replies whose provider reports no usage
+pub fn estimate_tokens(text: &str) -> u64 {
+    text.chars().count().div_ceil(4) as u64
+}
+
+/// Estimated cost of usage of `model`, or `None` without a known price
+fn estimate_cost(
+    model: &str,
+    input_tokens: i64,
+    output_tokens: i64,
+    audio_seconds: f64,
+) -> Option<f64> {
+    if let Some((_, per_hour)) = AUDIO_PRICES.iter().find(|(id, _)| model.contains(id)) {
+        return Some(audio_seconds / 3600.0 * per_hour);
+    }
+    TOKEN_PRICES
+        .iter()
+        .find(|(id, _, _)| model.contains(id))
+        .map(|(_, input, output)| {
+            (input_tokens as f64 * input + output_tokens as f64 * output) / 1_000_000.0
+        })
+}
+
+/// Usage per provider and local day since `since` (unix seconds), newest
+/// day first
+pub fn get_usage_stats(conn: &Connection, since: i64) -> Result<Vec<ApiUsageStats>> {
+    let mut stmt = conn.prepare(
+        "SELECT date(timestamp, 'unixepoch', 'localtime') AS day, provider_id, model,
+                COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(audio_seconds)
+         FROM api_usage
+         WHERE timestamp >= ?1
+         GROUP BY day, provider_id, model
+         ORDER BY day DESC, provider_id",
+    )?;
+    let rows = stmt.query_map(params![since], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, i64>(3)?,
+            row.get::<_, i64>(4)?,
+            row.get::<_, i64>(5)?,
+            row.get::<_, f64>(6)?,
+        ))
+    })?;
+
+    let mut stats: Vec<ApiUsageStats> = Vec::new();
+    for row in rows {
+        let (day, provider_id, model, requests, input_tokens, output_tokens, audio_seconds) = row?;
+        let cost = estimate_cost(&model, input_tokens, output_tokens, audio_seconds);
+        let entry = match stats.last_mut() {
+            Some(last) if last.day == day && last.provider_id == provider_id => last,
+            _ => {
+                stats.push(ApiUsageStats {
+                    day,
+                    provider_id,
+                    requests: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    audio_seconds: 0.0,
+                    estimated_cost_usd: 0.0,
+                    unpriced_requests: 0,
+                });
+                stats.last_mut().unwrap()
+            }
+        };
+        entry.requests += requests;
+        entry.input_tokens += input_tokens;
+        entry.output_tokens += output_tokens;
+        entry.audio_seconds += audio_seconds;
+        match cost {
+            Some(cost) => entry.estimated_cost_usd += cost,
+            None => entry.unpriced_requests += requests,
+        }
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_sum_usage_per_provider_and_day() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE api_usage (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                provider_id TEXT NOT NULL,
+                model TEXT NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                audio_seconds REAL NOT NULL DEFAULT 0
+            );",
+        )
+        .unwrap();
+
+        let chat = |provider: &str, model: &str, input, output| ApiUsage {
+            provider_id: provider.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            audio_seconds: 0.0,
+        };
+        let now = Utc::now().timestamp();
+        insert_usage(&conn, &chat("openai", "gpt-4o-mini", 1_000_000, 0), now).unwrap();
+        insert_usage(&conn, &chat("openai", "my-finetune", 10, 20), now).unwrap();
+        insert_usage(&conn, &chat("openrouter", "openai/gpt-4o", 0, 100_000), now).unwrap();
+        let groq = ApiUsage {
+            provider_id: "groq".to_string(),
+            model: "whisper-large-v3".to_string(),
+            audio_seconds: 1800.0,
+            ..Default::default()
+        };
+        insert_usage(&conn, &groq, now).unwrap();
+        // Outside the requested range
+        insert_usage(&conn, &chat("openai", "gpt-4o", 5, 5), now - 100 * 86_400).unwrap();
+
+        let stats = get_usage_stats(&conn, now - 86_400).unwrap();
+        assert_eq!(stats.len(), 3);
+        let provider = |id: &str| stats.iter().find(|s| s.provider_id == id).unwrap();
+
+        let openai = provider("openai");
+        assert_eq!(openai.requests, 2);
+        assert_eq!(openai.input_tokens, 1_000_010);
+        assert_eq!(openai.unpriced_requests, 1);
+        assert!((openai.estimated_cost_usd - 0.15).abs() < 1e-9);
+        assert!((provider("openrouter").estimated_cost_usd - 1.0).abs() < 1e-9);
+        assert!((provider("groq").estimated_cost_usd - 0.0555).abs() < 1e-9);
+    }
+}
@@ -31,9 +31,11 @@ pub fn cancel_current_operation(app: &AppHandle) {
         warn!("Failed to lock toggle state manager during cancellation");
     }
 
-    // Cancel any ongoing meeting if in recording state
+    // Cancel any ongoing meeting if recording or paused
     if let Some(mm) = app.try_state::<Arc<MeetingManager>>() {
-        if let MeetingState::Recording { meeting_id, .. } = mm.get_meeting_state() {
+        if let MeetingState::Recording { meeting_id, .. }
+        | MeetingState::Paused { meeting_id, .. } = mm.get_meeting_state()
+        {
             info!("Cancelling meeting: {}", meeting_id);
             if let Err(e) = mm.cancel_meeting(&meeting_id) {
                 warn!("Failed to cancel meeting: {}", e);
@@ -46,6 +48,9 @@ pub fn cancel_current_operation(app: &AppHandle) {
         lpm.stop();
     }
 
+    // Abort a post-processing reply that is still streaming in
+    crate::llm_client::cancel_streams();
+
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
@@ -69,3 +74,29 @@ pub fn is_wayland() -> bool {
             .map(|v| v.to_lowercase() == "wayland")
             .unwrap_or(false)
 }
+
+/// Percent-encode a URL component, keeping only unreserved characters
+pub fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_encoding_escapes_everything_but_unreserved() {
+        assert_eq!(percent_encode("a-b_c.d~"), "a-b_c.d~");
+        assert_eq!(
+            percent_encode("Send invite\n& café"),
+            "Send%20invite%0A%26%20caf%C3%A9"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Webhooks fired when a meeting completes.
+//!
+//! The meeting is POSTed as JSON to the configured URL. With a secret set,
+//! the body is signed with HMAC-SHA256 and the hex digest is sent in the
+//! `X-PaperFlow-Signature` header as `sha256=<digest>`, so receivers can
+//! check the request came from this app.
+
+use log::{debug, warn};
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
+use ring::hmac;
+use serde::Serialize;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::managers::meeting::{MeetingSession, MeetingTopic};
+
+const SIGNATURE_HEADER: &str = "X-PaperFlow-Signature";
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Attempts per delivery; failures are retried after a doubling delay
+const MAX_ATTEMPTS: u32 = 3;
+
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize)]
+struct MeetingCompletedPayload<'a> {
+    event: &'static str,
+    meeting_id: &'a str,
+    started_at: i64,
+    ended_at: Option<i64>,
+    duration_seconds: Option<i64>,
+    transcript: &'a str,
+    summary: Option<&'a str>,
+    action_items: &'a [String],
+    topics: &'a [MeetingTopic],
+}
+
+impl<'a> MeetingCompletedPayload<'a> {
+    fn new(session: &'a MeetingSession) -> Self {
+        Self {
+            event: "meeting.completed",
+            meeting_id: &session.meeting_id,
+            started_at: session.started_at,
+            ended_at: session.ended_at,
+            duration_seconds: session.duration_seconds,
+            transcript: session.full_transcript.as_deref().unwrap_or(""),
+            summary: session.summary.as_deref(),
+            action_items: session.action_items.as_deref().unwrap_or(&[]),
+            topics: session.topics.as_deref().unwrap_or(&[]),
+        }
+    }
+}
+
+/// Check a webhook URL before saving it. Empty turns webhooks off.
+pub fn validate_url(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Ok(());
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!(
+            "Webhook URL must use http or https, not {}",
+            scheme
+        )),
+    }
+}
+
+/// `sha256=<hex digest>` of the body keyed with the secret
+fn signature(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body);
+    tag.as_ref()
+        .iter()
+        .fold(String::from("sha256="), |mut out, byte| {
+            let _ = write!(out, "{:02x}", byte);
+            out
+        })
+}
+
+/// POST a completed meeting to the webhook, retrying network errors and
+/// server errors
+pub async fn send_meeting_completed(
+    url: &str,
+    secret: &str,
+    session: &MeetingSession,
+) -> Result<(), String> {
+    let body = serde_json::to_vec(&MeetingCompletedPayload::new(session))
+        .map_err(|e| format!("Failed to serialize meeting: {}", e))?;
+    let client = crate::proxy::client_builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut delay = INITIAL_RETRY_DELAY;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(
+                USER_AGENT,
+                "PaperFlow/1.0 (+https://github.com/solomonshalom/PaperFlow)",
+            )
+            .body(body.clone());
+        if !secret.is_empty() {
+            request = request.header(SIGNATURE_HEADER, signature(secret, &body));
+        }
+
+        let error = match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                debug!(
+                    "Meeting {} webhook delivered ({})",
+                    session.meeting_id,
+                    response.status()
+                );
+                return Ok(());
+            }
+            Ok(response) if !response.status().is_server_error() => {
+                return Err(format!(
+                    "Webhook rejected the meeting: {}",
+                    response.status()
+                ));
+            }
+            Ok(response) => format!("Webhook server error: {}", response.status()),
+            Err(e) => format!("Webhook request failed: {}", e),
+        };
+
+        if attempt == MAX_ATTEMPTS {
+            return Err(error);
+        }
+        warn!(
+            "{} (attempt {}/{}), retrying in {:?}",
+            error, attempt, MAX_ATTEMPTS, delay
+        );
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+    }
+    unreachable!("the last attempt always returns")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_is_hmac_sha256_hex() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn only_http_urls_are_accepted() {
+        assert!(validate_url("").is_ok());
+        assert!(validate_url("https://example.com/hooks/meetings").is_ok());
+        assert!(validate_url("ftp://example.com").is_err());
+        assert!(validate_url("not a url").is_err());
+    }
+}
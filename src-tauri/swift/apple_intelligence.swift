@@ -47,9 +47,11 @@ public func isAppleIntelligenceAvailable() -> Int32 {
 @_cdecl("process_text_with_apple_llm")
 public func processTextWithAppleLLM(
     _ prompt: UnsafePointer<CChar>,
+    instructions: UnsafePointer<CChar>?,
     maxTokens: Int32
 ) -> UnsafeMutablePointer<AppleLLMResponse> {
     let swiftPrompt = String(cString: prompt)
+    let swiftInstructions = instructions.map { String(cString: $0) }
     let responsePtr = ResponsePointer.allocate(capacity: 1)
     responsePtr.initialize(to: AppleLLMResponse(response: nil, success: 0, error_message: nil))
 
@@ -81,7 +83,12 @@ public func processTextWithAppleLLM(
     Task.detached(priority: .userInitiated) {
         defer { semaphore.signal() }
         do {
-            let session = LanguageModelSession(model: model)
+            let session: LanguageModelSession
+            if let swiftInstructions = swiftInstructions {
+                session = LanguageModelSession(model: model, instructions: swiftInstructions)
+            } else {
+                session = LanguageModelSession(model: model)
+            }
             let generation = try await session.respond(to: swiftPrompt)
             var output = generation.content
 
@@ -16,8 +16,10 @@ typedef struct {
 // Check if Apple Intelligence is available on the device
 int is_apple_intelligence_available(void);
 
-// Process text using Apple's on-device LLM
-AppleLLMResponse* process_text_with_apple_llm(const char* prompt, int max_tokens);
+// Process text using Apple's on-device LLM. `instructions` may be NULL;
+// otherwise it sets the session's instructions, which the model weighs above
+// the prompt.
+AppleLLMResponse* process_text_with_apple_llm(const char* prompt, const char* instructions, int max_tokens);
 
 // Free memory allocated by the Apple LLM response
 void free_apple_llm_response(AppleLLMResponse* response);
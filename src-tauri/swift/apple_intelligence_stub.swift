@@ -14,6 +14,7 @@ public func isAppleIntelligenceAvailable() -> Int32 {
 @_cdecl("process_text_with_apple_llm")
 public func processTextWithAppleLLM(
     _ prompt: UnsafePointer<CChar>,
+    instructions: UnsafePointer<CChar>?,
     maxTokens: Int32
 ) -> UnsafeMutablePointer<AppleLLMResponse> {
     let responsePtr = ResponsePointer.allocate(capacity: 1)
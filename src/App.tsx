@@ -1,6 +1,5 @@
 import { useEffect, useState, useRef, useCallback } from "react";
 import { Toaster } from "sonner";
-import { listen } from "@tauri-apps/api/event";
 import "./App.css";
 import AccessibilityPermissions from "./components/AccessibilityPermissions";
 import Footer from "./components/footer";
@@ -8,7 +7,7 @@ import Onboarding, { AccessibilityOnboarding } from "./components/onboarding";
 import { Sidebar, SidebarSection, SECTIONS_CONFIG } from "./components/Sidebar";
 import { useSettings } from "./hooks/useSettings";
 import { useSettingsStore } from "./stores/settingsStore";
-import { commands } from "@/bindings";
+import { commands, events } from "@/bindings";
 import {
   CommandPaletteProvider,
   CommandPalette,
@@ -124,7 +123,7 @@ function AppContent() {
 
   // Listen for tray menu navigation to settings
   useEffect(() => {
-    const unlisten = listen("navigate-to-settings", () => {
+    const unlisten = events.navigateToSettings.listen(() => {
       setCurrentView("general");
     });
 
@@ -21,6 +21,40 @@ async resetBinding(id: string) : Promise<Result<BindingResponse, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Save the current shortcuts as a new profile
+ */
+async addShortcutProfile(name: string) : Promise<Result<ShortcutProfile, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_shortcut_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a shortcut profile. Deleting the active one keeps its shortcuts
+ * in use.
+ */
+async deleteShortcutProfile(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_shortcut_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Register the shortcuts of profile `id` in place of the current ones
+ */
+async switchShortcutProfile(id: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_shortcut_profile", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePttSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_ptt_setting", { enabled }) };
@@ -29,6 +63,36 @@ async changePttSetting(enabled: boolean) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+async changePttHybridModeSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_ptt_hybrid_mode_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * How long a hybrid push-to-talk press must be held to stop on release
+ */
+async changePttLatchThresholdSetting(thresholdMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_ptt_latch_threshold_setting", { thresholdMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * How quickly the taps of a `double_tap:` shortcut must follow each other
+ */
+async changeDoubleTapTimeoutSetting(timeoutMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_double_tap_timeout_setting", { timeoutMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changeAudioFeedbackSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_audio_feedback_setting", { enabled }) };
@@ -109,6 +173,25 @@ async changeWordCorrectionThresholdSetting(threshold: number) : Promise<Result<n
     else return { status: "error", error: e  as any };
 }
 },
+async changeWordMatchAlgorithmSetting(algorithm: WordMatchAlgorithm) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_word_match_algorithm_setting", { algorithm }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace the correction thresholds of single custom words
+ */
+async updateWordCorrectionThresholds(thresholds: Partial<{ [key in string]: number }>) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_word_correction_thresholds", { thresholds }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePasteMethodSetting(method: string) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_paste_method_setting", { method }) };
@@ -125,6 +208,25 @@ async changeClipboardHandlingSetting(handling: string) : Promise<Result<null, st
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * How long to wait after pasting before putting the previous clipboard back
+ */
+async changeClipboardRestoreDelaySetting(delayMs: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_clipboard_restore_delay_setting", { delayMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changePasteIntoRecordingWindowSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_paste_into_recording_window_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async changePostProcessEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("change_post_process_enabled_setting", { enabled }) };
@@ -165,1230 +267,3159 @@ async setPostProcessProvider(providerId: string) : Promise<Result<null, string>>
     else return { status: "error", error: e  as any };
 }
 },
-async fetchPostProcessModels(providerId: string) : Promise<Result<string[], string>> {
+/**
+ * Provider to retry post-processing on when the selected one fails, or
+ * `None` to fall back to the unprocessed transcription
+ */
+async setPostProcessFallbackProvider(providerId: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("fetch_post_process_models", { providerId }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_post_process_fallback_provider", { providerId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async addPostProcessPrompt(name: string, prompt: string) : Promise<Result<LLMPrompt, string>> {
+async changePostProcessModeSetting(mode: PostProcessMode) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("add_post_process_prompt", { name, prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_post_process_mode_setting", { mode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updatePostProcessPrompt(id: string, name: string, prompt: string) : Promise<Result<null, string>> {
+async changeGrammarMaxEditRatioSetting(ratio: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt", { id, name, prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_grammar_max_edit_ratio_setting", { ratio }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
+async changeTranslationTargetLanguageSetting(language: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_post_process_prompt", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_translation_target_language_setting", { language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setPostProcessSelectedPrompt(id: string) : Promise<Result<null, string>> {
+async updateAppTranslationLanguages(mappings: Partial<{ [key in string]: string }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_post_process_selected_prompt", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_app_translation_languages", { mappings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateCustomWords(words: string[]) : Promise<Result<null, string>> {
+async fetchPostProcessModels(providerId: string) : Promise<Result<string[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_custom_words", { words }) };
+    return { status: "ok", data: await TAURI_INVOKE("fetch_post_process_models", { providerId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Temporarily unregister a binding while the user is editing it in the UI.
- * This avoids firing the action while keys are being recorded.
- */
-async suspendBinding(id: string) : Promise<Result<null, string>> {
+async addPostProcessPrompt(name: string, prompt: string) : Promise<Result<LLMPrompt, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("suspend_binding", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_post_process_prompt", { name, prompt }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Re-register the binding after the user has finished editing.
- */
-async resumeBinding(id: string) : Promise<Result<null, string>> {
+async updatePostProcessPrompt(id: string, name: string, prompt: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("resume_binding", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_prompt", { id, name, prompt }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMuteWhileRecordingSetting(enabled: boolean) : Promise<Result<null, string>> {
+async deletePostProcessPrompt(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_mute_while_recording_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_post_process_prompt", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeAppendTrailingSpaceSetting(enabled: boolean) : Promise<Result<null, string>> {
+async setPostProcessSelectedPrompt(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_append_trailing_space_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_post_process_selected_prompt", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeAppLanguageSetting(language: string) : Promise<Result<null, string>> {
+/**
+ * Route apps, by name, to the prompt to post-process their dictation with
+ */
+async updateAppPromptMappings(mappings: Partial<{ [key in string]: string }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_app_language_setting", { language }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_app_prompt_mappings", { mappings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeUpdateChecksSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeLlmCacheEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_update_checks_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_llm_cache_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeSnippetsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async addPostProcessPipeline(name: string, steps: PipelineStep[]) : Promise<Result<PostProcessPipeline, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_snippets_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_post_process_pipeline", { name, steps }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateSnippets(snippets: Snippet[]) : Promise<Result<null, string>> {
+async updatePostProcessPipeline(id: string, name: string, steps: PipelineStep[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_snippets", { snippets }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_post_process_pipeline", { id, name, steps }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeAutoFormatEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async deletePostProcessPipeline(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_auto_format_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_post_process_pipeline", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeAutoFormatListsSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Run the pipeline `id` instead of the selected prompt, or the prompt again
+ * when `id` is `None`
+ */
+async setPostProcessSelectedPipeline(id: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_auto_format_lists_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_post_process_selected_pipeline", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeVerbalCommandsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async updateCustomWords(words: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_verbal_commands_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_custom_words", { words }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeToneAdjustmentEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Replace the custom word sets scoped to apps
+ */
+async updateCustomWordSets(sets: CustomWordSet[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_tone_adjustment_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_custom_word_sets", { sets }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeDefaultToneSetting(tone: ToneStyle) : Promise<Result<null, string>> {
+async changeFillerFilterSetting(filter: FillerFilter) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_default_tone_setting", { tone }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_filler_filter_setting", { filter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateAppToneMappings(mappings: Partial<{ [key in string]: ToneStyle }>) : Promise<Result<null, string>> {
+async updateFillerWords(words: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_app_tone_mappings", { mappings }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_filler_words", { words }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeDeveloperModeSetting(mode: DeveloperMode) : Promise<Result<null, string>> {
+async changeStutterSettings(stutter: StutterSettings) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_developer_mode_setting", { mode }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_stutter_settings", { stutter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changePreserveCodeSyntaxSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeProfanityFilterSetting(filter: ProfanityFilter) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_preserve_code_syntax_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_profanity_filter_setting", { filter }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateDeveloperDictionary(words: string[]) : Promise<Result<null, string>> {
+async updateProfanityWords(words: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_developer_dictionary", { words }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_profanity_words", { words }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeCorrectionDetectionEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Temporarily unregister a binding while the user is editing it in the UI.
+ * This avoids firing the action while keys are being recorded.
+ */
+async suspendBinding(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_correction_detection_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("suspend_binding", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeContextAwarenessEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Re-register the binding after the user has finished editing.
+ */
+async resumeBinding(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_context_awareness_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("resume_binding", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateContextPerAppPermissions(permissions: Partial<{ [key in string]: boolean }>) : Promise<Result<null, string>> {
+async changeMuteWhileRecordingSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_context_per_app_permissions", { permissions }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_mute_while_recording_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeGroqTranscriptionApiKeySetting(apiKey: string) : Promise<Result<null, string>> {
+async changeAppendTrailingSpaceSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_groq_transcription_api_key_setting", { apiKey }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_append_trailing_space_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMultilingualModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeAppLanguageSetting(language: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_multilingual_mode_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_app_language_setting", { language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changePrimaryLanguageSetting(language: string | null) : Promise<Result<null, string>> {
+async changeUpdateChecksSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_primary_language_setting", { language }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_update_checks_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeSecondaryLanguageSetting(language: string | null) : Promise<Result<null, string>> {
+async changeSnippetsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_secondary_language_setting", { language }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_snippets_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeLanguageDetectionSensitivitySetting(sensitivity: number) : Promise<Result<null, string>> {
+async updateSnippets(snippets: Snippet[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_language_detection_sensitivity_setting", { sensitivity }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_snippets", { snippets }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeShowMeetingMenuSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Replace the regex rules, in the order they run
+ */
+async updateRegexRules(rules: RegexRule[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_show_meeting_menu_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_regex_rules", { rules }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Run one rule on `text`, for previewing it before saving, regardless of
+ * its app scope
+ */
+async testRegexRule(rule: RegexRule, text: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_mode_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("test_regex_rule", { rule, text }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingChunkDurationSetting(seconds: number) : Promise<Result<null, string>> {
+/**
+ * The text processing stages, in order, for `app_name` or by default
+ */
+async getTextPipeline(appName: string | null) : Promise<TextStageConfig[]> {
+    return await TAURI_INVOKE("get_text_pipeline", { appName });
+},
+/**
+ * Reorder or toggle the text processing stages, for `app_name` or by default
+ */
+async updateTextPipeline(appName: string | null, stages: TextStageConfig[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_chunk_duration_setting", { seconds }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_text_pipeline", { appName, stages }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingAutoSummarizeSetting(enabled: boolean) : Promise<Result<null, string>> {
+/**
+ * Go back to the default text pipeline for `app_name`
+ */
+async removeAppTextPipeline(appName: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_auto_summarize_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_app_text_pipeline", { appName }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingExtractActionItemsSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeAutoFormatEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_extract_action_items_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_auto_format_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingSummaryPromptSetting(prompt: string) : Promise<Result<null, string>> {
+async changeAutoFormatListsSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_summary_prompt_setting", { prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_auto_format_lists_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeMeetingActionItemsPromptSetting(prompt: string) : Promise<Result<null, string>> {
+async changeVerbalCommandsEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_meeting_action_items_prompt_setting", { prompt }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_verbal_commands_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeLivePreviewEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async updateEmojiMap(emojiMap: Partial<{ [key in string]: string }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_live_preview_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_emoji_map", { emojiMap }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeLivePreviewIntervalSetting(intervalMs: number) : Promise<Result<null, string>> {
+async changeSpellModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_live_preview_interval_setting", { intervalMs }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_spell_mode_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeWhisperModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeItnEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_whisper_mode_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_itn_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async changeVadThresholdSetting(threshold: number) : Promise<Result<null, string>> {
+async changeItnLocaleSetting(locale: ItnLocale) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_vad_threshold_setting", { threshold }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_itn_locale_setting", { locale }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Get the current meeting state
- */
-async getMeetingState() : Promise<Result<MeetingState, string>> {
+async changeToneAdjustmentEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_state") };
+    return { status: "ok", data: await TAURI_INVOKE("change_tone_adjustment_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Get the current meeting session if any
- */
-async getCurrentMeetingSession() : Promise<Result<MeetingSession | null, string>> {
+async changeDefaultToneSetting(tone: ToneStyle) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_current_meeting_session") };
+    return { status: "ok", data: await TAURI_INVOKE("change_default_tone_setting", { tone }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Get elapsed time in seconds since meeting started
- */
-async getMeetingElapsedSeconds() : Promise<Result<number | null, string>> {
+async updateAppToneMappings(mappings: Partial<{ [key in string]: ToneStyle }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_elapsed_seconds") };
+    return { status: "ok", data: await TAURI_INVOKE("update_app_tone_mappings", { mappings }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Start a new meeting
+ * Set the profiles used when dictating into apps, keyed on app or website
+ * name
  */
-async startMeeting(bindingId: string) : Promise<Result<string, string>> {
+async updateAppProfiles(profiles: Partial<{ [key in string]: AppProfile }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_meeting", { bindingId }) };
+    return { status: "ok", data: await TAURI_INVOKE("update_app_profiles", { profiles }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Stop the current meeting
- */
-async stopMeeting(meetingId: string) : Promise<Result<null, string>> {
+async changeDeveloperModeSetting(mode: DeveloperMode) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_meeting", { meetingId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_developer_mode_setting", { mode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Cancel the current meeting without processing
- */
-async cancelMeeting(meetingId: string) : Promise<Result<null, string>> {
+async changePreserveCodeSyntaxSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_meeting", { meetingId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_preserve_code_syntax_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Get meeting history entries
- */
-async getMeetingHistory() : Promise<Result<MeetingHistoryEntry[], string>> {
+async updateDeveloperDictionary(words: string[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_meeting_history") };
+    return { status: "ok", data: await TAURI_INVOKE("update_developer_dictionary", { words }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Delete a meeting from history
- */
-async deleteMeeting(meetingId: string) : Promise<Result<null, string>> {
+async changeCorrectionDetectionEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_meeting", { meetingId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_correction_detection_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async triggerUpdateCheck() : Promise<Result<null, string>> {
+async changeContextAwarenessEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("trigger_update_check") };
+    return { status: "ok", data: await TAURI_INVOKE("change_context_awareness_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelOperation() : Promise<void> {
-    await TAURI_INVOKE("cancel_operation");
-},
-async getAppDirPath() : Promise<Result<string, string>> {
+async updateContextPerAppPermissions(permissions: Partial<{ [key in string]: boolean }>) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_app_dir_path") };
+    return { status: "ok", data: await TAURI_INVOKE("update_context_per_app_permissions", { permissions }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAppSettings() : Promise<Result<AppSettings, string>> {
+async changeSurroundingTextInLlmPromptSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_app_settings") };
+    return { status: "ok", data: await TAURI_INVOKE("change_surrounding_text_in_llm_prompt_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getDefaultSettings() : Promise<Result<AppSettings, string>> {
+async changeScreenOcrEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_default_settings") };
+    return { status: "ok", data: await TAURI_INVOKE("change_screen_ocr_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getLogDirPath() : Promise<Result<string, string>> {
+async changeGroqTranscriptionApiKeySetting(apiKey: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_log_dir_path") };
+    return { status: "ok", data: await TAURI_INVOKE("change_groq_transcription_api_key_setting", { apiKey }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setLogLevel(level: LogLevel) : Promise<Result<null, string>> {
+async changeMultilingualModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_log_level", { level }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_multilingual_mode_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openRecordingsFolder() : Promise<Result<null, string>> {
+async changePrimaryLanguageSetting(language: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_recordings_folder") };
+    return { status: "ok", data: await TAURI_INVOKE("change_primary_language_setting", { language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openLogDir() : Promise<Result<null, string>> {
+async changeSecondaryLanguageSetting(language: string | null) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_log_dir") };
+    return { status: "ok", data: await TAURI_INVOKE("change_secondary_language_setting", { language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async openAppDataDir() : Promise<Result<null, string>> {
+async changeLanguageDetectionSensitivitySetting(sensitivity: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("open_app_data_dir") };
+    return { status: "ok", data: await TAURI_INVOKE("change_language_detection_sensitivity_setting", { sensitivity }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Check if Apple Intelligence is available on this device.
- * Called by the frontend when the user selects Apple Intelligence provider.
- */
-async checkAppleIntelligenceAvailable() : Promise<boolean> {
-    return await TAURI_INVOKE("check_apple_intelligence_available");
-},
-/**
- * Try to initialize Enigo (keyboard/mouse simulation).
- * On macOS, this will return an error if accessibility permissions are not granted.
- */
-async initializeEnigo() : Promise<Result<null, string>> {
+async changeShowMeetingMenuSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("initialize_enigo") };
+    return { status: "ok", data: await TAURI_INVOKE("change_show_meeting_menu_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAvailableModels() : Promise<Result<ModelInfo[], string>> {
+async changeMeetingModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_available_models") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_mode_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getModelInfo(modelId: string) : Promise<Result<ModelInfo | null, string>> {
+async changeMeetingChunkDurationSetting(seconds: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_model_info", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_chunk_duration_setting", { seconds }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async downloadModel(modelId: string) : Promise<Result<null, string>> {
+async changeMeetingAutoSummarizeSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_auto_summarize_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteModel(modelId: string) : Promise<Result<null, string>> {
+async changeMeetingExtractActionItemsSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_extract_action_items_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async cancelDownload(modelId: string) : Promise<Result<null, string>> {
+async changeMeetingSummaryPromptSetting(prompt: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_summary_prompt_setting", { prompt }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setActiveModel(modelId: string) : Promise<Result<null, string>> {
+async changeMeetingActionItemsPromptSetting(prompt: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_active_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_action_items_prompt_setting", { prompt }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getCurrentModel() : Promise<Result<string, string>> {
+async changeMeetingSaveAudioSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_current_model") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_save_audio_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTranscriptionModelStatus() : Promise<Result<string | null, string>> {
+async changeMeetingSegmentTopicsSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_transcription_model_status") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_segment_topics_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async isModelLoading() : Promise<Result<boolean, string>> {
+async changeMeetingWebhookUrlSetting(url: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_model_loading") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_webhook_url_setting", { url }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async hasAnyModelsAvailable() : Promise<Result<boolean, string>> {
+async changeMeetingWebhookSecretSetting(secret: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("has_any_models_available") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_webhook_secret_setting", { secret }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async hasAnyModelsOrDownloads() : Promise<Result<boolean, string>> {
+async changeMeetingDetectionEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("has_any_models_or_downloads") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_detection_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getRecommendedFirstModel() : Promise<Result<string, string>> {
+async changeMeetingAutoStartSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_recommended_first_model") };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_auto_start_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Download CoreML model for Apple Neural Engine acceleration (macOS only)
- */
-async downloadCoremlModel(modelId: string) : Promise<Result<null, string>> {
+async changeMeetingTaskDestinationsSetting(destinations: TaskDestination[]) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_coreml_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_meeting_task_destinations_setting", { destinations }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Delete CoreML model (macOS only)
- */
-async deleteCoremlModel(modelId: string) : Promise<Result<null, string>> {
+async changeLivePreviewEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_coreml_model", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_live_preview_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Check if running on macOS (for UI to show/hide CoreML options)
- */
-async isCoremlAvailable() : Promise<boolean> {
-    return await TAURI_INVOKE("is_coreml_available");
-},
-/**
- * Validate a Groq API key before using it
- * Returns Ok(()) if valid, or an error message if invalid
- */
-async validateGroqApiKey(apiKey: string) : Promise<Result<null, string>> {
+async changeLivePreviewIntervalSetting(intervalMs: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("validate_groq_api_key", { apiKey }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_live_preview_interval_setting", { intervalMs }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Check if a model requires an API key (cloud models)
- */
-async modelRequiresApiKey(modelId: string) : Promise<Result<boolean, string>> {
+async changeFfmpegPathSetting(path: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("model_requires_api_key", { modelId }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_ffmpeg_path_setting", { path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Check if Groq API key is configured in settings
- */
-async isGroqApiKeyConfigured() : Promise<Result<boolean, string>> {
+async changeFileTranscriptionDetectChaptersSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("is_groq_api_key_configured") };
+    return { status: "ok", data: await TAURI_INVOKE("change_file_transcription_detect_chapters_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateMicrophoneMode(alwaysOn: boolean) : Promise<Result<null, string>> {
+async changeWhisperModeEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_microphone_mode", { alwaysOn }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_whisper_mode_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMicrophoneMode() : Promise<Result<boolean, string>> {
+async changeVadThresholdSetting(threshold: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_microphone_mode") };
+    return { status: "ok", data: await TAURI_INVOKE("change_vad_threshold_setting", { threshold }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAvailableMicrophones() : Promise<Result<AudioDevice[], string>> {
+async changeMaxRecordingDurationSetting(minutes: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_available_microphones") };
+    return { status: "ok", data: await TAURI_INVOKE("change_max_recording_duration_setting", { minutes }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setSelectedMicrophone(deviceName: string) : Promise<Result<null, string>> {
+async changeMetricsEndpointSetting(enabled: boolean, port: number) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_selected_microphone", { deviceName }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_metrics_endpoint_setting", { enabled, port }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getSelectedMicrophone() : Promise<Result<string, string>> {
+async changeProxySettings(proxy: ProxySettings) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_selected_microphone") };
+    return { status: "ok", data: await TAURI_INVOKE("change_proxy_settings", { proxy }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAvailableOutputDevices() : Promise<Result<AudioDevice[], string>> {
+async changeQuietHoursSetting(quietHours: DailyWindow) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_available_output_devices") };
+    return { status: "ok", data: await TAURI_INVOKE("change_quiet_hours_setting", { quietHours }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setSelectedOutputDevice(deviceName: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("set_selected_output_device", { deviceName }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
+async getQuietHoursActive() : Promise<boolean> {
+    return await TAURI_INVOKE("get_quiet_hours_active");
 },
-async getSelectedOutputDevice() : Promise<Result<string, string>> {
+async changeFocusSuppressionSetting(focusSuppression: FocusSuppression) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_selected_output_device") };
+    return { status: "ok", data: await TAURI_INVOKE("change_focus_suppression_setting", { focusSuppression }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async playTestSound(soundType: string) : Promise<void> {
-    await TAURI_INVOKE("play_test_sound", { soundType });
-},
-async checkCustomSounds() : Promise<CustomSounds> {
-    return await TAURI_INVOKE("check_custom_sounds");
+async getFocusState() : Promise<FocusState> {
+    return await TAURI_INVOKE("get_focus_state");
 },
-async setClamshellMicrophone(deviceName: string) : Promise<Result<null, string>> {
+async changeHardwareKeysSetting(hardwareKeys: HardwareKeys) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_clamshell_microphone", { deviceName }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_hardware_keys_setting", { hardwareKeys }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getClamshellMicrophone() : Promise<Result<string, string>> {
+async changeMouseButtonsSetting(mouseButtons: MouseButtons) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_clamshell_microphone") };
+    return { status: "ok", data: await TAURI_INVOKE("change_mouse_buttons_setting", { mouseButtons }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async isRecording() : Promise<boolean> {
-    return await TAURI_INVOKE("is_recording");
-},
-/**
- * Get information about system audio capture for the current platform
- */
-async getSystemAudioInfo() : Promise<SystemAudioInfo> {
-    return await TAURI_INVOKE("get_system_audio_info");
-},
 /**
- * Check if native system audio capture is available
- */
-async isNativeSystemAudioAvailable() : Promise<boolean> {
-    return await TAURI_INVOKE("is_native_system_audio_available");
-},
-/**
- * Start native system audio capture
+ * Set the microphones, models and languages the cycle shortcuts step through
  */
-async startSystemAudioCapture() : Promise<Result<null, string>> {
+async changeCycleListsSetting(cycleLists: CycleLists) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("start_system_audio_capture") };
+    return { status: "ok", data: await TAURI_INVOKE("change_cycle_lists_setting", { cycleLists }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Stop native system audio capture and return samples
+ * Set how the "type" paste method paces its keystrokes
  */
-async stopSystemAudioCapture() : Promise<Result<number[], string>> {
+async changeTypingSettings(typing: TypingSettings) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_system_audio_capture") };
+    return { status: "ok", data: await TAURI_INVOKE("change_typing_settings", { typing }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Check if currently capturing system audio
- */
-async isCapturingSystemAudio() : Promise<boolean> {
-    return await TAURI_INVOKE("is_capturing_system_audio");
-},
-/**
- * Get the current diarization status
- */
-async getDiarizationStatus() : Promise<DiarizationStatus> {
-    return await TAURI_INVOKE("get_diarization_status");
-},
-/**
- * Enable or disable diarization
- */
-async changeDiarizationEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+async changeHidTriggersEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("change_diarization_enabled_setting", { enabled }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_hid_triggers_enabled_setting", { enabled }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-/**
- * Get information about required models
- */
-async getDiarizationModelInfo() : Promise<DiarizationModelInfo[]> {
-    return await TAURI_INVOKE("get_diarization_model_info");
-},
-/**
- * Download diarization models
- */
-async downloadDiarizationModels() : Promise<Result<null, string>> {
+async getHidDevices() : Promise<Result<HidDeviceInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("download_diarization_models") };
+    return { status: "ok", data: await TAURI_INVOKE("get_hid_devices") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async setModelUnloadTimeout(timeout: ModelUnloadTimeout) : Promise<void> {
-    await TAURI_INVOKE("set_model_unload_timeout", { timeout });
-},
-async getModelLoadStatus() : Promise<Result<ModelLoadStatus, string>> {
+/**
+ * Wait for the user to click a button on the device, to bind it
+ */
+async captureHidButton(vendorId: number, productId: number) : Promise<Result<HidButton, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_model_load_status") };
+    return { status: "ok", data: await TAURI_INVOKE("capture_hid_button", { vendorId, productId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async unloadModelManually() : Promise<Result<null, string>> {
+async addHidBinding(name: string, vendorId: number, productId: number, byteIndex: number, mask: number, action: HidButtonAction) : Promise<Result<HidBinding, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("unload_model_manually") };
+    return { status: "ok", data: await TAURI_INVOKE("add_hid_binding", { name, vendorId, productId, byteIndex, mask, action }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getHistoryEntries() : Promise<Result<HistoryEntry[], string>> {
+async removeHidBinding(id: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_history_entries") };
+    return { status: "ok", data: await TAURI_INVOKE("remove_hid_binding", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async toggleHistoryEntrySaved(id: number) : Promise<Result<null, string>> {
+async changeDualScriptSetting(enabled: boolean, language: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("toggle_history_entry_saved", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("change_dual_script_setting", { enabled, language }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getAudioFilePath(fileName: string) : Promise<Result<string, string>> {
+/**
+ * Turn any transcript into dual-script study notes, e.g. before exporting
+ */
+async convertToDualScript(text: string) : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_audio_file_path", { fileName }) };
+    return { status: "ok", data: await TAURI_INVOKE("convert_to_dual_script", { text }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async deleteHistoryEntry(id: number) : Promise<Result<null, string>> {
+/**
+ * Get the current meeting state
+ */
+async getMeetingState() : Promise<Result<MeetingState, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("delete_history_entry", { id }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_state") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
+/**
+ * Get the current meeting session if any
+ */
+async getCurrentMeetingSession() : Promise<Result<MeetingSession | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_current_meeting_session") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async updateRecordingRetentionPeriod(period: string) : Promise<Result<null, string>> {
+/**
+ * Get elapsed time in seconds since meeting started
+ */
+async getMeetingElapsedSeconds() : Promise<Result<number | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("update_recording_retention_period", { period }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_elapsed_seconds") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Get list of supported file extensions
+ * Start a new meeting
  */
-async getSupportedFileExtensions() : Promise<string[]> {
-    return await TAURI_INVOKE("get_supported_file_extensions");
+async startMeeting(bindingId: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_meeting", { bindingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
 /**
- * Queue a file for transcription
+ * Stop the current meeting
  */
-async queueFileForTranscription(filePath: string) : Promise<Result<FileTranscriptionJob, string>> {
+async stopMeeting(meetingId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("queue_file_for_transcription", { filePath }) };
+    return { status: "ok", data: await TAURI_INVOKE("stop_meeting", { meetingId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Queue multiple files for transcription
+ * Pause the current meeting
  */
-async queueFilesForTranscription(filePaths: string[]) : Promise<Result<FileTranscriptionJob[], string>> {
+async pauseMeeting(meetingId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("queue_files_for_transcription", { filePaths }) };
+    return { status: "ok", data: await TAURI_INVOKE("pause_meeting", { meetingId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Process the next queued file
+ * Resume a paused meeting
  */
-async processNextFile() : Promise<Result<string | null, string>> {
+async resumeMeeting(meetingId: string) : Promise<Result<null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("process_next_file") };
+    return { status: "ok", data: await TAURI_INVOKE("resume_meeting", { meetingId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Process all queued files (runs on background thread to avoid blocking UI)
+ * The meeting interrupted by a crash, if any
  */
-async processAllFiles() : Promise<Result<null, string>> {
+async getRecoverableMeeting() : Promise<Result<MeetingSession | null, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("process_all_files") };
+    return { status: "ok", data: await TAURI_INVOKE("get_recoverable_meeting") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Cancel the current file transcription
+ * Continue recording the meeting interrupted by a crash
  */
-async cancelFileTranscription() : Promise<void> {
-    await TAURI_INVOKE("cancel_file_transcription");
+async resumeRecoveredMeeting(bindingId: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_recovered_meeting", { bindingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
 /**
- * Cancel a specific job by ID
+ * Process the audio the interrupted meeting recorded before the crash
  */
-async cancelFileTranscriptionJob(jobId: string) : Promise<Result<null, string>> {
+async finalizeRecoveredMeeting() : Promise<Result<string, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("cancel_file_transcription_job", { jobId }) };
+    return { status: "ok", data: await TAURI_INVOKE("finalize_recovered_meeting") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Get all transcription jobs
+ * Throw away the meeting interrupted by a crash
  */
-async getFileTranscriptionJobs() : Promise<FileTranscriptionJob[]> {
-    return await TAURI_INVOKE("get_file_transcription_jobs");
+async discardRecoveredMeeting() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discard_recovered_meeting") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
 /**
- * Get a specific job by ID
+ * Send a saved meeting's action items to the configured task managers.
+ * Providers that failed are listed in the error.
  */
-async getFileTranscriptionJob(jobId: string) : Promise<FileTranscriptionJob | null> {
-    return await TAURI_INVOKE("get_file_transcription_job", { jobId });
+async pushMeetingActionItems(meetingId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("push_meeting_action_items", { meetingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
 /**
- * Clear completed, failed, and cancelled jobs
+ * Cancel the current meeting without processing
  */
-async clearCompletedFileJobs() : Promise<void> {
-    await TAURI_INVOKE("clear_completed_file_jobs");
+async cancelMeeting(meetingId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_meeting", { meetingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get meeting history entries
+ */
+async getMeetingHistory() : Promise<Result<MeetingHistoryEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a meeting from history
+ */
+async deleteMeeting(meetingId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_meeting", { meetingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the speakers of a meeting in order of first appearance
+ */
+async getMeetingSpeakers(meetingId: string) : Promise<Result<MeetingSpeaker[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_speakers", { meetingId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Name a meeting speaker, or clear their name with an empty string
+ */
+async renameMeetingSpeaker(meetingId: string, label: string, name: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rename_meeting_speaker", { meetingId, label, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Address of a meeting chunk's audio as WAV, for playing it through the
+ * `recording` protocol
+ */
+async getMeetingChunkAudioPath(meetingId: string, chunkId: number) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_meeting_chunk_audio_path", { meetingId, chunkId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async triggerUpdateCheck() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("trigger_update_check") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelOperation() : Promise<void> {
+    await TAURI_INVOKE("cancel_operation");
+},
+async getAppDirPath() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_dir_path") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAppSettings() : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_app_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getDefaultSettings() : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_default_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLogDirPath() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_log_dir_path") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setLogLevel(level: LogLevel) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_log_level", { level }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openRecordingsFolder() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_recordings_folder") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openLogDir() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_log_dir") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async openAppDataDir() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_app_data_dir") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if Apple Intelligence is available on this device.
+ * Called by the frontend when the user selects Apple Intelligence provider.
+ */
+async checkAppleIntelligenceAvailable() : Promise<boolean> {
+    return await TAURI_INVOKE("check_apple_intelligence_available");
+},
+/**
+ * Try to initialize Enigo (keyboard/mouse simulation).
+ * On macOS, this will return an error if accessibility permissions are not granted.
+ */
+async initializeEnigo() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("initialize_enigo") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAvailableModels() : Promise<Result<ModelInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_available_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * GGUF models the embedded llama.cpp engine can post-process with
+ */
+async getLocalLlmModels() : Promise<Result<ModelInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_local_llm_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getModelInfo(modelId: string) : Promise<Result<ModelInfo | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_model_info", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async downloadModel(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteModel(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async cancelDownload(modelId: string, purge: boolean | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_download", { modelId, purge }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getInterruptedDownloads() : Promise<Result<PartialDownload[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_interrupted_downloads") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 },
+async deletePartialDownload(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_partial_download", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setActiveModel(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_active_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCurrentModel() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_current_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getTranscriptionModelStatus() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_transcription_model_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async isModelLoading() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_model_loading") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async hasAnyModelsAvailable() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("has_any_models_available") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async hasAnyModelsOrDownloads() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("has_any_models_or_downloads") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getRecommendedFirstModel() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_recommended_first_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download CoreML model for Apple Neural Engine acceleration (macOS only)
+ */
+async downloadCoremlModel(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_coreml_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete CoreML model (macOS only)
+ */
+async deleteCoremlModel(modelId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_coreml_model", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if running on macOS (for UI to show/hide CoreML options)
+ */
+async isCoremlAvailable() : Promise<boolean> {
+    return await TAURI_INVOKE("is_coreml_available");
+},
+/**
+ * Validate a Groq API key before using it
+ * Returns Ok(()) if valid, or an error message if invalid
+ */
+async validateGroqApiKey(apiKey: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("validate_groq_api_key", { apiKey }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if a model requires an API key (cloud models)
+ */
+async modelRequiresApiKey(modelId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("model_requires_api_key", { modelId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if Groq API key is configured in settings
+ */
+async isGroqApiKeyConfigured() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_groq_api_key_configured") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateMicrophoneMode(alwaysOn: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_microphone_mode", { alwaysOn }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getMicrophoneMode() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_microphone_mode") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAvailableMicrophones() : Promise<Result<AudioDevice[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_available_microphones") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setSelectedMicrophone(deviceName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_selected_microphone", { deviceName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSelectedMicrophone() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_selected_microphone") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getAvailableOutputDevices() : Promise<Result<AudioDevice[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_available_output_devices") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setSelectedOutputDevice(deviceName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_selected_output_device", { deviceName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSelectedOutputDevice() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_selected_output_device") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async playTestSound(soundType: string) : Promise<void> {
+    await TAURI_INVOKE("play_test_sound", { soundType });
+},
+async checkCustomSounds() : Promise<CustomSounds> {
+    return await TAURI_INVOKE("check_custom_sounds");
+},
+async setClamshellMicrophone(deviceName: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_clamshell_microphone", { deviceName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getClamshellMicrophone() : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_clamshell_microphone") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async isRecording() : Promise<boolean> {
+    return await TAURI_INVOKE("is_recording");
+},
+/**
+ * Record from an audio file instead of the microphone (developer tooling,
+ * only available with developer mode on). Pass `None` to go back to the
+ * microphone.
+ */
+async setSimulatedInput(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_simulated_input", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSimulatedInput() : Promise<string | null> {
+    return await TAURI_INVOKE("get_simulated_input");
+},
+/**
+ * Get information about system audio capture for the current platform
+ */
+async getSystemAudioInfo() : Promise<SystemAudioInfo> {
+    return await TAURI_INVOKE("get_system_audio_info");
+},
+/**
+ * Check if native system audio capture is available
+ */
+async isNativeSystemAudioAvailable() : Promise<boolean> {
+    return await TAURI_INVOKE("is_native_system_audio_available");
+},
+/**
+ * Start native system audio capture
+ */
+async startSystemAudioCapture() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_system_audio_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop native system audio capture and return samples
+ */
+async stopSystemAudioCapture() : Promise<Result<number[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_system_audio_capture") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if currently capturing system audio
+ */
+async isCapturingSystemAudio() : Promise<boolean> {
+    return await TAURI_INVOKE("is_capturing_system_audio");
+},
+/**
+ * Get the current diarization status
+ */
+async getDiarizationStatus() : Promise<DiarizationStatus> {
+    return await TAURI_INVOKE("get_diarization_status");
+},
+/**
+ * Enable or disable diarization
+ */
+async changeDiarizationEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_diarization_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get information about required models
+ */
+async getDiarizationModelInfo() : Promise<DiarizationModelInfo[]> {
+    return await TAURI_INVOKE("get_diarization_model_info");
+},
+/**
+ * Download diarization models
+ */
+async downloadDiarizationModels() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_diarization_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the current punctuation restoration status
+ */
+async getPunctuationStatus() : Promise<PunctuationStatus> {
+    return await TAURI_INVOKE("get_punctuation_status");
+},
+/**
+ * Enable or disable punctuation restoration
+ */
+async changePunctuationRestorationSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_punctuation_restoration_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download the punctuation model
+ */
+async downloadPunctuationModel() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_punctuation_model") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the current wake word status
+ */
+async getWakeWordStatus() : Promise<WakeWordStatus> {
+    return await TAURI_INVOKE("get_wake_word_status");
+},
+/**
+ * Start or stop listening for the wake word
+ */
+async changeWakeWordEnabledSetting(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_wake_word_enabled_setting", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async changeWakeWordSensitivitySetting(sensitivity: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_wake_word_sensitivity_setting", { sensitivity }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Use a custom openWakeWord model, or the downloaded one with `None`
+ */
+async changeWakeWordModelSetting(path: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("change_wake_word_model_setting", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download the wake word models
+ */
+async downloadWakeWordModels() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("download_wake_word_models") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setModelUnloadTimeout(timeout: ModelUnloadTimeout) : Promise<void> {
+    await TAURI_INVOKE("set_model_unload_timeout", { timeout });
+},
+async getModelLoadStatus() : Promise<Result<ModelLoadStatus, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_model_load_status") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async unloadModelManually() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unload_model_manually") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getHistoryEntries() : Promise<Result<HistoryEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_entries") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * One page of history for the history view, see `HistoryQuery`
+ */
+async getHistoryPage(query: HistoryQuery) : Promise<Result<HistoryPage, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_page", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Number of entries matching the filters of `query`
+ */
+async countHistoryEntries(query: HistoryQuery) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("count_history_entries", { query }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async toggleHistoryEntrySaved(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("toggle_history_entry_saved", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Address of a recording for the `recording` protocol, which decrypts it
+ * in memory for playback
+ */
+async getAudioFilePath(fileName: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_audio_file_path", { fileName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Address of an entry's recording as WAV, for playing it through the
+ * `recording` protocol
+ */
+async getHistoryPlaybackPath(id: number) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_playback_path", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Waveform peaks of an entry's recording, merged into `buckets` bars
+ */
+async getHistoryWaveform(id: number, buckets: number) : Promise<Result<Waveform, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_waveform", { id, buckets }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteHistoryEntry(id: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_history_entry", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Run an entry through the current custom words, formatting and
+ * post-processing prompt again. The previous texts are kept as a version.
+ */
+async reprocessHistoryEntry(id: number) : Promise<Result<HistoryEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reprocess_history_entry", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getHistoryEntryVersions(id: number) : Promise<Result<HistoryEntryVersion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_entry_versions", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add a transcript from a JSON export, e.g. one made on another machine,
+ * to history. Returns the id of the new entry.
+ */
+async importTranscript(path: string) : Promise<Result<number, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_transcript", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cloud API usage and estimated cost per provider and day, for the last
+ * `days` days (30 by default)
+ */
+async getApiUsageStats(days: number | null) : Promise<Result<ApiUsageStats[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_api_usage_stats", { days }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forget every cached post-processing reply
+ */
+async clearLlmCache() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_llm_cache") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateHistoryLimit(limit: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_history_limit", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRecordingRetentionPeriod(period: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_recording_retention_period", { period }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRetentionRules(rules: RetentionRule[]) : Promise<Result<RetentionReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_retention_rules", { rules }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async applyRetentionRules() : Promise<Result<RetentionReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("apply_retention_rules") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateRecordingFormat(format: RecordingFormat) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_recording_format", { format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setHistoryEncryption(enabled: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_history_encryption", { enabled }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setHistorySync(enabled: boolean, folder: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_history_sync", { enabled, folder }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async syncHistoryNow() : Promise<Result<SyncReport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_history_now") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get list of supported file extensions
+ */
+async getSupportedFileExtensions() : Promise<string[]> {
+    return await TAURI_INVOKE("get_supported_file_extensions");
+},
+/**
+ * Queue a file for transcription. `overrides` replace the global model,
+ * language, diarization and translation settings for this job only.
+ */
+async queueFileForTranscription(filePath: string, overrides: TranscriptionOverrides | null) : Promise<Result<FileTranscriptionJob, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("queue_file_for_transcription", { filePath, overrides }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queue multiple files for transcription, all with the same overrides
+ */
+async queueFilesForTranscription(filePaths: string[], overrides: TranscriptionOverrides | null) : Promise<Result<FileTranscriptionJob[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("queue_files_for_transcription", { filePaths, overrides }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Download the media at a URL (a podcast episode, a YouTube video, ...) and
+ * queue it for transcription once downloaded
+ */
+async queueUrlForTranscription(url: string, overrides: TranscriptionOverrides | null) : Promise<Result<FileTranscriptionJob, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("queue_url_for_transcription", { url, overrides }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Queue the supported files in a folder, optionally walking its
+ * subfolders and keeping only files that match `glob`
+ */
+async queueFolderForTranscription(path: string, recursive: boolean, glob: string | null, overrides: TranscriptionOverrides | null) : Promise<Result<FolderQueueResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("queue_folder_for_transcription", { path, recursive, glob, overrides }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Process the next queued file
+ */
+async processNextFile() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("process_next_file") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Process all queued files (runs on background thread to avoid blocking UI)
+ */
+async processAllFiles() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("process_all_files") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancel the current file transcription
+ */
+async cancelFileTranscription() : Promise<void> {
+    await TAURI_INVOKE("cancel_file_transcription");
+},
+/**
+ * Cancel a specific job by ID
+ */
+async cancelFileTranscriptionJob(jobId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_file_transcription_job", { jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get all transcription jobs
+ */
+async getFileTranscriptionJobs() : Promise<FileTranscriptionJob[]> {
+    return await TAURI_INVOKE("get_file_transcription_jobs");
+},
+/**
+ * Get a specific job by ID
+ */
+async getFileTranscriptionJob(jobId: string) : Promise<FileTranscriptionJob | null> {
+    return await TAURI_INVOKE("get_file_transcription_job", { jobId });
+},
+/**
+ * Clear completed, failed, and cancelled jobs
+ */
+async clearCompletedFileJobs() : Promise<void> {
+    await TAURI_INVOKE("clear_completed_file_jobs");
+},
+/**
+ * Remove a specific job
+ */
+async removeFileTranscriptionJob(jobId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_file_transcription_job", { jobId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if file transcription is currently processing
+ */
+async isFileTranscriptionProcessing() : Promise<boolean> {
+    return await TAURI_INVOKE("is_file_transcription_processing");
+},
+/**
+ * Move a queued job to `position` among the queued jobs
+ */
+async moveFileTranscriptionJob(jobId: string, position: number) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_file_transcription_job", { jobId, position }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Mark a queued job as high priority so it runs before the normal jobs
+ */
+async setFileTranscriptionJobPriority(jobId: string, highPriority: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_file_transcription_job_priority", { jobId, highPriority }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Pause the queue; running jobs finish but no new ones start
+ */
+async pauseFileTranscriptionQueue() : Promise<void> {
+    await TAURI_INVOKE("pause_file_transcription_queue");
+},
+/**
+ * Resume a paused queue and carry on processing it
+ */
+async resumeFileTranscriptionQueue() : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_file_transcription_queue") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check if the file transcription queue is paused
+ */
+async isFileTranscriptionQueuePaused() : Promise<boolean> {
+    return await TAURI_INVOKE("is_file_transcription_queue_paused");
+},
+/**
+ * Export a transcript in the specified format
+ */
+async exportTranscript(text: string, format: ExportFormat, title: string | null, sourceFile: string | null, durationMs: number | null, segments: TranscriptSegment[] | null, chapters: TranscriptChapter[] | null) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transcript", { text, format, title, sourceFile, durationMs, segments, chapters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a transcript and save to file
+ */
+async exportTranscriptToFile(text: string, format: ExportFormat, filePath: string, title: string | null, sourceFile: string | null, durationMs: number | null, segments: TranscriptSegment[] | null, chapters: TranscriptChapter[] | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transcript_to_file", { text, format, filePath, title, sourceFile, durationMs, segments, chapters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a transcript onto the clipboard. HTML goes on as rich text, with a
+ * plain text version for apps that can't take it.
+ */
+async exportTranscriptToClipboard(text: string, format: ExportFormat, title: string | null, sourceFile: string | null, durationMs: number | null, segments: TranscriptSegment[] | null, chapters: TranscriptChapter[] | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transcript_to_clipboard", { text, format, title, sourceFile, durationMs, segments, chapters }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the appropriate file extension for a format
+ */
+async getExportFileExtension(format: ExportFormat) : Promise<string> {
+    return await TAURI_INVOKE("get_export_file_extension", { format });
+},
+/**
+ * Get all available export formats
+ */
+async getAvailableExportFormats() : Promise<ExportFormat[]> {
+    return await TAURI_INVOKE("get_available_export_formats");
+},
+/**
+ * Export the dictations and meetings of a local day (YYYY-MM-DD)
+ */
+async exportTimeline(date: string, format: ExportFormat) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_timeline", { date, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a day's timeline and save it to a file
+ */
+async exportTimelineToFile(date: string, format: ExportFormat, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_timeline_to_file", { date, format, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export a saved meeting with its summary, action items, topics and
+ * transcript
+ */
+async exportMeeting(meetingId: string, format: ExportFormat, filePath: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_meeting", { meetingId, format, filePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copy a saved meeting to the clipboard, e.g. as HTML to paste its summary
+ * into an email
+ */
+async exportMeetingToClipboard(meetingId: string, format: ExportFormat) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_meeting_to_clipboard", { meetingId, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export several history or meeting entries in one format. `dest` is a
+ * folder for separate files, or a `.zip` file to package them in.
+ */
+async exportTranscriptsBatch(entryIds: BatchExportEntry[], format: ExportFormat, dest: string) : Promise<Result<BatchExportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_transcripts_batch", { entryIds, format, dest }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get all configured watch folders
+ */
+async getWatchFolders() : Promise<WatchFolderConfig[]> {
+    return await TAURI_INVOKE("get_watch_folders");
+},
+/**
+ * Add a new watch folder
+ */
+async addWatchFolder(path: string, recursive: boolean, includeGlobs: string[] | null, excludeGlobs: string[] | null) : Promise<Result<WatchFolderConfig, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_watch_folder", { path, recursive, includeGlobs, excludeGlobs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Remove a watch folder
+ */
+async removeWatchFolder(folderId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_watch_folder", { folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Update a watch folder configuration
+ */
+async updateWatchFolder(config: WatchFolderConfig) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_watch_folder", { config }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Get the status of all watch folders
+ */
+async getWatchFolderStatus() : Promise<WatchFolderStatus[]> {
+    return await TAURI_INVOKE("get_watch_folder_status");
+},
+/**
+ * Start watching a specific folder
+ */
+async startWatchFolder(folderId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("start_watch_folder", { folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop watching a specific folder
+ */
+async stopWatchFolder(folderId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("stop_watch_folder", { folderId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Checks if the Mac is a laptop by detecting battery presence
+ * 
+ * This uses pmset to check for battery information.
+ * Returns true if a battery is detected (laptop), false otherwise (desktop)
+ */
+async isLaptop() : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("is_laptop") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+checkForUpdates: CheckForUpdates,
+coremlCompilationStatus: CoreMLCompilationEvent,
+coremlDownloadComplete: CoreMLDownloadComplete,
+coremlDownloadProgress: CoreMLDownloadProgress,
+coremlDownloadStarted: CoreMLDownloadStarted,
+coremlExtractionStarted: CoreMLExtractionStarted,
+fileTranscriptionEta: FileTranscriptionEtaEvent,
+fileTranscriptionUpdate: FileTranscriptionEvent,
+focusStateChanged: FocusStateChangedEvent,
+hideOverlay: HideOverlay,
+historyUpdated: HistoryUpdated,
+livePreviewError: LivePreviewErrorEvent,
+livePreviewUpdate: LivePreviewEvent,
+meetingChunkTranscribed: MeetingChunkEvent,
+meetingCompleted: MeetingCompleted,
+meetingDetected: MeetingDetectedEvent,
+meetingFinalizationProgress: MeetingFinalizationProgressEvent,
+meetingHeartbeat: MeetingHeartbeat,
+meetingHistoryUpdated: MeetingHistoryUpdated,
+meetingRecoveryAvailable: MeetingRecoveryAvailable,
+meetingStateChanged: MeetingStateEvent,
+micLevel: MicLevel,
+modelDownloadCancelled: DownloadCancelledEvent,
+modelDownloadComplete: ModelDownloadComplete,
+modelDownloadFailed: DownloadFailedEvent,
+modelDownloadProgress: DownloadProgress,
+modelDownloadsInterrupted: ModelDownloadsInterrupted,
+modelExtractionCompleted: ModelExtractionCompleted,
+modelExtractionFailed: ModelExtractionFailed,
+modelExtractionStarted: ModelExtractionStarted,
+modelStateChanged: ModelStateEvent,
+navigateToSettings: NavigateToSettings,
+postProcessFailed: PostProcessFailed,
+postProcessFallback: PostProcessFallback,
+postProcessStream: PostProcessStream,
+quietHoursChanged: QuietHoursChangedEvent,
+recordingLimitReached: RecordingLimitReachedEvent,
+selectionCycled: SelectionCycledEvent,
+settingsChanged: SettingsChanged,
+showOverlay: ShowOverlay,
+watchFolderFileDetected: WatchFolderFileDetected,
+whisperModeChanged: WhisperModeChanged
+}>({
+checkForUpdates: "check-for-updates",
+coremlCompilationStatus: "coreml-compilation-status",
+coremlDownloadComplete: "coreml-download-complete",
+coremlDownloadProgress: "coreml-download-progress",
+coremlDownloadStarted: "coreml-download-started",
+coremlExtractionStarted: "coreml-extraction-started",
+fileTranscriptionEta: "file-transcription-eta",
+fileTranscriptionUpdate: "file-transcription-update",
+focusStateChanged: "focus-state-changed",
+hideOverlay: "hide-overlay",
+historyUpdated: "history-updated",
+livePreviewError: "live-preview-error",
+livePreviewUpdate: "live-preview-update",
+meetingChunkTranscribed: "meeting-chunk-transcribed",
+meetingCompleted: "meeting-completed",
+meetingDetected: "meeting-detected",
+meetingFinalizationProgress: "meeting-finalization-progress",
+meetingHeartbeat: "meeting-heartbeat",
+meetingHistoryUpdated: "meeting-history-updated",
+meetingRecoveryAvailable: "meeting-recovery-available",
+meetingStateChanged: "meeting-state-changed",
+micLevel: "mic-level",
+modelDownloadCancelled: "model-download-cancelled",
+modelDownloadComplete: "model-download-complete",
+modelDownloadFailed: "model-download-failed",
+modelDownloadProgress: "model-download-progress",
+modelDownloadsInterrupted: "model-downloads-interrupted",
+modelExtractionCompleted: "model-extraction-completed",
+modelExtractionFailed: "model-extraction-failed",
+modelExtractionStarted: "model-extraction-started",
+modelStateChanged: "model-state-changed",
+navigateToSettings: "navigate-to-settings",
+postProcessFailed: "post-process-failed",
+postProcessFallback: "post-process-fallback",
+postProcessStream: "post-process-stream",
+quietHoursChanged: "quiet-hours-changed",
+recordingLimitReached: "recording-limit-reached",
+selectionCycled: "selection-cycled",
+settingsChanged: "settings-changed",
+showOverlay: "show-overlay",
+watchFolderFileDetected: "watch-folder-file-detected",
+whisperModeChanged: "whisper-mode-changed"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Usage of one provider on one day
+ */
+export type ApiUsageStats = { 
+/**
+ * Local date, `YYYY-MM-DD`
+ */
+day: string; provider_id: string; requests: number; input_tokens: number; output_tokens: number; audio_seconds: number; estimated_cost_usd: number; 
+/**
+ * Requests to models without a known price, left out of the cost
+ */
+unpriced_requests: number }
+/**
+ * Application categories for tone and behavior adjustments
+ */
+export type AppCategory = 
+/**
+ * Email clients (Mail, Outlook, Gmail) → Formal tone
+ */
+"email" | 
+/**
+ * Messaging apps (Slack, Discord, Messages) → Casual tone
+ */
+"messaging" | 
+/**
+ * Document editors (Word, Docs, Notion) → Formal tone
+ */
+"documents" | 
+/**
+ * IDEs and code editors → Technical/Developer mode
+ */
+"ide" | 
+/**
+ * Terminal and shell applications
+ */
+"terminal" | 
+/**
+ * Web browsers → Context-dependent
+ */
+"browser" | 
+/**
+ * Other applications → Neutral
+ */
+"other"
+/**
+ * Settings used instead of the global ones when dictating into an app or
+ * website. Unset fields keep the global setting.
+ */
+export type AppProfile = { 
+/**
+ * Language to transcribe in
+ */
+language?: string | null; 
+/**
+ * Model to transcribe with, loaded when the recording starts
+ */
+model_id?: string | null; 
+/**
+ * Prompt to post-process with, instead of the selected prompt or
+ * pipeline
+ */
+post_process_prompt_id?: string | null; tone?: ToneStyle | null; 
+/**
+ * Formatting stages and their order
+ */
+text_pipeline?: TextStageConfig[] | null; paste_method?: PasteMethod | null }
+export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; shortcut_profiles?: ShortcutProfile[]; 
+/**
+ * The profile whose shortcuts are in `bindings`
+ */
+active_shortcut_profile_id?: string | null; push_to_talk: boolean; 
+/**
+ * With push-to-talk, a quick press latches the recording on until the
+ * next press, and only a hold of `ptt_latch_threshold_ms` or longer
+ * stops on release
+ */
+ptt_hybrid_mode?: boolean; ptt_latch_threshold_ms?: number; 
+/**
+ * Longest gap between the taps of a `double_tap:` shortcut, and longest tap
+ */
+double_tap_timeout_ms?: number; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; 
+/**
+ * Listen for the wake word while idle; keeps the microphone open. The
+ * downloaded model wakes on "Hey Jarvis".
+ */
+wake_word_enabled?: boolean; 
+/**
+ * 0 to 1; higher wakes on fainter matches, and on more false ones
+ */
+wake_word_sensitivity?: number; 
+/**
+ * A custom openWakeWord model, e.g. one trained on "Hey PaperFlow",
+ * in place of the downloaded "Hey Jarvis" one
+ */
+wake_word_model_path?: string | null; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; show_sidebar?: boolean; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; 
+/**
+ * Custom words scoped to apps, merged with `custom_words` in those apps
+ */
+custom_word_sets?: CustomWordSet[]; filler_filter?: FillerFilter; filler_words?: string[]; stutter?: StutterSettings; profanity_filter?: ProfanityFilter; 
+/**
+ * Words filtered as profanity on top of the built-in list
+ */
+profanity_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; word_match_algorithm?: WordMatchAlgorithm; 
+/**
+ * Correction thresholds for single custom words, in place of
+ * `word_correction_threshold`
+ */
+word_correction_thresholds?: Partial<{ [key in string]: number }>; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; recording_format?: RecordingFormat; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; 
+/**
+ * Paste into the window that was focused when the recording started,
+ * even if focus moved while transcribing
+ */
+paste_into_recording_window?: boolean; 
+/**
+ * How long after the paste keystroke the previous clipboard is put
+ * back, for apps that read the clipboard slowly
+ */
+clipboard_restore_delay_ms?: number; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; 
+/**
+ * Provider tried when the selected one fails, with its configured model
+ */
+post_process_fallback_provider_id?: string | null; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; post_process_pipelines?: PostProcessPipeline[]; 
+/**
+ * Runs instead of the selected prompt when set
+ */
+post_process_selected_pipeline_id?: string | null; post_process_mode?: PostProcessMode; 
+/**
+ * Prompt id per app name, used instead of the selected prompt or
+ * pipeline when dictating into that app. A profile's prompt wins.
+ */
+app_prompt_mappings?: Partial<{ [key in string]: string }>; 
+/**
+ * Share of the words a grammar-only correction may change before it is
+ * rejected
+ */
+grammar_max_edit_ratio?: number; translation_target_language?: string; 
+/**
+ * Target language per app name, overriding the global one
+ */
+app_translation_languages?: Partial<{ [key in string]: string }>; 
+/**
+ * Reuse the reply to a prompt that was already sent to the same model
+ */
+llm_cache_enabled?: boolean; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; snippets?: Snippet[]; snippets_enabled?: boolean; 
+/**
+ * Find and replace rules, run in order before formatting
+ */
+regex_rules?: RegexRule[]; 
+/**
+ * Order and enablement of the local text processing stages
+ */
+text_pipeline?: TextStageConfig[]; 
+/**
+ * Pipelines used instead of `text_pipeline` when dictating into an app,
+ * keyed on app name
+ */
+app_text_pipelines?: Partial<{ [key in string]: TextStageConfig[] }>; 
+/**
+ * Write "spell mode ... end spell" and "spell that ..." dictation as
+ * the letters, digits and symbols spelled
+ */
+spell_mode_enabled?: boolean; auto_format_enabled?: boolean; auto_format_lists?: boolean; verbal_commands_enabled?: boolean; 
+/**
+ * Emoji inserted by saying "<name> emoji" with verbal commands on
+ */
+emoji_map?: Partial<{ [key in string]: string }>; 
+/**
+ * Write spoken numbers, dates, times, currencies and percentages in
+ * their written form
+ */
+itn_enabled?: boolean; itn_locale?: ItnLocale; tone_adjustment_enabled?: boolean; default_tone?: ToneStyle; app_tone_mappings?: Partial<{ [key in string]: ToneStyle }>; 
+/**
+ * Profiles per app or website name, chosen when a recording starts
+ */
+app_profiles?: Partial<{ [key in string]: AppProfile }>; developer_mode?: DeveloperMode; preserve_code_syntax?: boolean; developer_dictionary?: string[]; correction_detection_enabled?: boolean; context_awareness_enabled?: boolean; 
+/**
+ * Apps and websites whose focused text field, or window when screen OCR
+ * is on, may be read
+ */
+context_per_app_permissions?: Partial<{ [key in string]: boolean }>; 
+/**
+ * Also give the text before the cursor to the post-processing LLM,
+ * which may be a cloud provider
+ */
+surrounding_text_in_llm_prompt?: boolean; 
+/**
+ * Recognize the text shown in the active window, for names and terms
+ * to bias transcription toward
+ */
+screen_ocr_enabled?: boolean; groq_transcription_api_key?: string; multilingual_mode_enabled?: boolean; primary_language?: string | null; secondary_language?: string | null; language_detection_sensitivity?: number; show_meeting_menu?: boolean; meeting_mode_enabled?: boolean; meeting_chunk_duration_seconds?: number; meeting_auto_summarize?: boolean; meeting_extract_action_items?: boolean; meeting_summary_prompt?: string; meeting_action_items_prompt?: string; 
+/**
+ * Keep each chunk's audio so meetings can be played back
+ */
+meeting_save_audio?: boolean; 
+/**
+ * Split finished meetings into topic sections with the LLM
+ */
+meeting_segment_topics?: boolean; 
+/**
+ * Completed meetings are POSTed here as JSON; empty turns this off
+ */
+meeting_webhook_url?: string; 
+/**
+ * Signs webhook bodies with HMAC-SHA256 when set
+ */
+meeting_webhook_secret?: string; 
+/**
+ * Suggest meeting mode when a video call app comes to the front
+ */
+meeting_detection_enabled?: boolean; 
+/**
+ * Start the meeting when a call is detected instead of suggesting it
+ */
+meeting_auto_start?: boolean; 
+/**
+ * Task managers that receive each meeting's action items
+ */
+meeting_task_destinations?: TaskDestination[]; live_preview_enabled?: boolean; live_preview_interval_ms?: number; watch_folders?: WatchFolderConfig[] | null; 
+/**
+ * ffmpeg binary used to decode files; empty looks for a bundled copy or one on PATH
+ */
+ffmpeg_path?: string; 
+/**
+ * Split completed transcriptions of long files into chapters with the LLM
+ */
+file_transcription_detect_chapters?: boolean; whisper_mode_enabled?: boolean; vad_threshold?: number; max_recording_duration_minutes?: number; diarization_enabled?: boolean; 
+/**
+ * Punctuate and capitalize output from engines that write none
+ */
+punctuation_restoration_enabled?: boolean; coreml_enabled?: boolean; auto_download_coreml?: boolean; metrics_endpoint_enabled?: boolean; metrics_endpoint_port?: number; proxy?: ProxySettings; quiet_hours?: DailyWindow; focus_suppression?: FocusSuppression; history_encryption_enabled?: boolean; history_sync_enabled?: boolean; history_sync_folder?: string | null; dual_script_enabled?: boolean; dual_script_language?: string; retention_rules?: RetentionRule[]; hardware_keys?: HardwareKeys; mouse_buttons?: MouseButtons; hid_triggers_enabled?: boolean; hid_bindings?: HidBinding[]; cycle_lists?: CycleLists; typing?: TypingSettings }
+export type AudioDevice = { index: string; name: string; is_default: boolean; device_type: AudioDeviceType }
+/**
+ * Device type classification for audio devices
+ */
+export type AudioDeviceType = "microphone" | "system_loopback" | "virtual_device" | "unknown"
+/**
+ * An entry to export
+ */
+export type BatchExportEntry = { type: "history"; id: number } | { type: "meeting"; meeting_id: string }
+export type BatchExportResult = { 
+/**
+ * Names of the written files, inside the archive when exporting a zip
+ */
+files: string[]; 
+/**
+ * Entries that could not be exported, with the reason
+ */
+errors: string[] }
+export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null; 
+/**
+ * What holds the shortcut, when that is why it couldn't be set
+ */
+conflict: ShortcutConflict | null }
+/**
+ * Ask the frontend to run an update check
+ */
+export type CheckForUpdates = null
+export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
+/**
+ * Event emitted during CoreML model compilation (first-run takes 3-5 minutes)
+ */
+export type CoreMLCompilationEvent = { event_type: string; model_id: string; estimated_time_seconds: number | null; error: string | null }
+export type CoreMLDownloadComplete = string
+export type CoreMLDownloadProgress = { model_id: string; downloaded: number; total: number; percentage: number }
+export type CoreMLDownloadStarted = string
+export type CoreMLExtractionStarted = string
+export type CustomSounds = { start: boolean; stop: boolean }
+/**
+ * Custom words used only when dictating into some apps, on top of the global
+ * custom words
+ */
+export type CustomWordSet = { id: string; name: string; words?: string[]; 
+/**
+ * App categories the set applies in, like every messaging app
+ */
+categories?: AppCategory[]; 
+/**
+ * App names the set applies in, on top of its categories
+ */
+apps?: string[]; enabled?: boolean }
+/**
+ * What a cycle shortcut steps through
+ */
+export type CycleKind = "microphone" | "model" | "language"
+/**
+ * The selections the cycle shortcuts step through, in order
+ */
+export type CycleLists = { 
+/**
+ * Device names, with "default" for the system default
+ */
+microphones?: string[]; 
+/**
+ * Model IDs
+ */
+models?: string[]; 
+/**
+ * Language codes, with "auto" for detection
+ */
+languages?: string[] }
+/**
+ * Recurring daily span of local time, such as quiet hours or a watch
+ * folder's active hours. Times are minutes after local midnight; a window
+ * whose end is earlier than its start runs past midnight.
+ */
+export type DailyWindow = { enabled?: boolean; start_minute?: number; end_minute?: number; 
+/**
+ * Days the window starts on, 0 = Monday through 6 = Sunday. Empty means every day.
+ */
+days?: number[] }
+export type DeveloperMode = 
+/**
+ * Developer mode is disabled
+ */
+"off" | 
+/**
+ * Auto-detect IDE/terminal apps
+ */
+"auto" | 
+/**
+ * Always use developer mode
+ */
+"always"
+/**
+ * Model info for display in UI
+ */
+export type DiarizationModelInfo = { name: string; description: string; size_bytes: number }
+/**
+ * Diarization status response
+ */
+export type DiarizationStatus = { available: boolean; enabled: boolean; models_downloaded: boolean; download_progress: number | null; error: string | null }
+/**
+ * Emitted when a download stops because the user cancelled it
+ */
+export type DownloadCancelledEvent = { model_id: string; 
+/**
+ * Whether the partial file was kept so the download can be resumed
+ */
+partial_kept: boolean; partial_size: number }
+/**
+ * Emitted when a download stops because of a network, HTTP or disk error
+ */
+export type DownloadFailedEvent = { model_id: string; error: string }
+export type DownloadProgress = { model_id: string; downloaded: number; total: number; percentage: number }
+export type EngineType = "Whisper" | "Parakeet" | "Moonshine" | "GroqCloud" | 
+/**
+ * GGUF language model run by the embedded llama.cpp engine for
+ * post-processing, not for transcription
+ */
+"Llama"
+/**
+ * Export format options
+ */
+export type ExportFormat = "txt" | "srt" | "vtt" | "json" | "markdown" | "csv" | "html" | "docx" | 
+/**
+ * OpenDocument text, for LibreOffice
+ */
+"odt" | "pdf" | 
+/**
+ * "0:00 Title" lines for a YouTube description
+ */
+"youtubechapters" | 
+/**
+ * Podcasting 2.0 chapters JSON
+ */
+"podcastchapters" | 
+/**
+ * W3C Timed Text Markup Language, for broadcast workflows
+ */
+"ttml" | 
+/**
+ * Apple iTunes Timed Text, the TTML profile used by iTunes and Final Cut
+ */
+"itt" | 
+/**
+ * WebVTT with a timestamp per word, so players highlight words as they
+ * are spoken
+ */
+"karaokevtt"
+/**
+ * Event emitted whenever the queue's completion estimates change
+ */
+export type FileTranscriptionEtaEvent = { jobs: FileTranscriptionJobEta[]; 
+/**
+ * When the whole queue is expected to be done, if every job could be estimated
+ */
+queue_completion_at: number | null }
+/**
+ * Event emitted during file transcription
+ */
+export type FileTranscriptionEvent = { job_id: string; status: FileTranscriptionStatus; progress: number; transcription: string | null; error: string | null; estimated_completion_at: number | null }
+/**
+ * A single file transcription job
+ */
+export type FileTranscriptionJob = { id: string; file_path: string; file_name: string; file_size: number; status: FileTranscriptionStatus; progress: number; transcription: string | null; error: string | null; duration_seconds: number | null; created_at: number; completed_at: number | null; 
+/**
+ * Model, language and diarization overrides for this job
+ */
+overrides?: TranscriptionOverrides | null; 
+/**
+ * Expected transcription time for this job alone, from the model's realtime factor
+ */
+estimated_processing_seconds?: number | null; 
+/**
+ * Unix timestamp at which this job is expected to finish, counting the jobs ahead of it
+ */
+estimated_completion_at?: number | null; 
+/**
+ * URL the media was downloaded from, for jobs queued from a URL
+ */
+source_url?: string | null; 
+/**
+ * High-priority jobs are kept ahead of the rest of the queue
+ */
+high_priority?: boolean; 
+/**
+ * Timed segments of the transcription, with speakers when diarized
+ */
+segments?: TranscriptSegment[] | null; 
+/**
+ * Chapters found in the transcription, when chapter detection is on
+ */
+chapters?: TranscriptChapter[] | null; 
+/**
+ * Watch folder the file was picked up from, which is told once it completes
+ */
+watch_folder_id?: string | null }
+/**
+ * Estimated completion time of a single queued or running job
+ */
+export type FileTranscriptionJobEta = { job_id: string; estimated_completion_at: number | null }
+/**
+ * Status of a file transcription job
+ */
+export type FileTranscriptionStatus = 
+/**
+ * Fetching the media of a job queued from a URL
+ */
+"downloading" | "queued" | "processing" | "completed" | "failed" | "cancelled"
+/**
+ * How eagerly filler words are removed
+ */
+export type FillerFilter = "off" | 
+/**
+ * Remove the filler word list
+ */
+"conservative" | 
+/**
+ * Also remove "like" and "you know" where commas set them off
+ */
+"aggressive"
+export type FocusState = { 
+/**
+ * A macOS Focus is turned on
+ */
+focus_active: boolean; 
+/**
+ * A presentation or screen share is running
+ */
+presenting: boolean }
+export type FocusStateChangedEvent = { state: FocusState; 
+/**
+ * Whether the frontend should hold back its notifications
+ */
+suppress_notifications: boolean }
+/**
+ * What to hold back while a macOS Focus is on or the screen is shared
+ */
+export type FocusSuppression = { enabled?: boolean; respect_focus?: boolean; respect_presenting?: boolean; suppress_overlay?: boolean; suppress_sounds?: boolean; suppress_notifications?: boolean }
+/**
+ * Outcome of queueing a folder
+ */
+export type FolderQueueResult = { 
+/**
+ * Jobs added for the folder's files
+ */
+jobs: FileTranscriptionJob[]; 
+/**
+ * Files left out: unsupported, not matching the glob, already queued
+ * or failing to queue
+ */
+skipped: number }
+/**
+ * What a hardware key does when pressed
+ */
+export type HardwareKeyAction = "disabled" | 
+/**
+ * Start or stop dictation, like the transcribe shortcut in toggle mode
+ */
+"toggle_recording" | 
+/**
+ * Mute or unmute system audio during the current recording
+ */
+"mute_while_recording"
+/**
+ * Keyboard mic-mute and media keys, handled outside the global shortcuts
+ */
+export type HardwareKeys = { enabled?: boolean; mic_mute?: HardwareKeyAction; play_pause?: HardwareKeyAction; 
+/**
+ * Light the mic-mute LED while recording, where the system exposes it
+ */
+sync_led?: boolean }
+/**
+ * A button on a foot pedal, presenter or other HID device, identified by
+ * the bit it sets in the device's input report
+ */
+export type HidBinding = { id: string; name: string; vendor_id: number; product_id: number; 
+/**
+ * Offset of the byte holding the button in the input report
+ */
+byte_index: number; 
+/**
+ * Bits of that byte that are set while the button is down
+ */
+mask: number; action: HidButtonAction }
+/**
+ * The bit a button sets in its device's input report
+ */
+export type HidButton = { byte_index: number; mask: number }
+/**
+ * What a button on a HID device does
+ */
+export type HidButtonAction = 
+/**
+ * Start or stop dictation on each press, like the transcribe shortcut in toggle mode
+ */
+"toggle_recording" | 
+/**
+ * Dictate while the button is held down
+ */
+"push_to_talk" | 
+/**
+ * Only start dictation, for pedals with a separate stop button
+ */
+"start_recording" | 
+/**
+ * Only stop dictation
+ */
+"stop_recording"
+/**
+ * A connected HID device that can be bound
+ */
+export type HidDeviceInfo = { vendor_id: number; product_id: number; name: string }
+export type HideOverlay = null
+export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; 
+/**
+ * Text after custom words, filtering and formatting
+ */
+transcription_text: string; 
+/**
+ * What the engine produced, before any local processing. Missing for
+ * entries saved before it was recorded.
+ */
+raw_text: string | null; 
+/**
+ * LLM or script conversion output, when it changed the text
+ */
+post_processed_text: string | null; post_process_prompt: string | null; 
+/**
+ * App that was in front when the dictation finished
+ */
+app_name: string | null; 
+/**
+ * Domain of the browser tab the dictation went to, when known
+ */
+app_domain: string | null; 
+/**
+ * Length of the recording
+ */
+duration_ms: number | null }
+/**
+ * Texts an entry had before it was re-processed
+ */
+export type HistoryEntryVersion = { id: number; 
+/**
+ * When these texts were replaced
+ */
+replaced_at: number; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null }
+export type HistoryPage = { entries: HistoryEntry[]; 
+/**
+ * Cursor for the following page, `None` on the last page
+ */
+next_cursor: string | null }
+/**
+ * Which entries a history page or count covers
+ */
+export type HistoryQuery = { sort?: HistorySort; 
+/**
+ * `next_cursor` of the previous page, `None` for the first page
+ */
+cursor?: string | null; limit: number; saved_only?: boolean; 
+/**
+ * Only entries whose text contains this, ignoring case
+ */
+search?: string | null }
+/**
+ * Order of history pages. Ties are broken by id so cursors stay stable.
+ */
+export type HistorySort = "newest" | "oldest" | "longest" | "shortest"
+export type HistoryUpdated = null
+/**
+ * How converted text is written
+ */
+export type ItnLocale = 
+/**
+ * March 3rd, 2024 and 3:30 PM
+ */
+"en_us" | 
+/**
+ * 3 March 2024, 3:30 pm and pounds as £
+ */
+"en_gb"
+export type LLMPrompt = { id: string; name: string; prompt: string }
+export type LivePreviewErrorEvent = { error_type: string; message: string; is_fatal: boolean }
+export type LivePreviewEvent = { text: string; is_final: boolean }
+export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
+/**
+ * A single chunk of meeting audio
+ */
+export type MeetingChunk = { chunk_id: number; start_time_ms: number; end_time_ms: number; audio_path: string | null; transcription: string | null; 
+/**
+ * Diarization label ("Speaker 1") when speakers were told apart
+ */
+speaker?: string | null }
+/**
+ * Event emitted when a chunk is transcribed
+ */
+export type MeetingChunkEvent = { meeting_id: string; chunk_id: number; transcription: string }
+export type MeetingCompleted = MeetingSession
+export type MeetingDetectedEvent = { 
+/**
+ * Video call service, e.g. "Zoom"
+ */
+service: string; 
+/**
+ * Name or window title of the app that was detected
+ */
+app_name: string; 
+/**
+ * Whether the meeting was started without asking
+ */
+auto_started: boolean }
+/**
+ * Event emitted while a stopped meeting is being transcribed
+ */
+export type MeetingFinalizationProgressEvent = { meeting_id: string; chunks_done: number; chunk_total: number; 
+/**
+ * Unix timestamp at which transcription is expected to finish, once the
+ * model has a measured speed
+ */
+estimated_completion_at: number | null }
+/**
+ * Periodic update while a meeting is recording, same shape as a state change
+ */
+export type MeetingHeartbeat = MeetingStateEvent
+/**
+ * A meeting history entry for the frontend
+ */
+export type MeetingHistoryEntry = { id: number; meeting_id: string; started_at: number; ended_at: number; duration_seconds: number; full_transcript: string; summary: string | null; action_items: string[] | null; topics: MeetingTopic[] | null; chunk_count: number; saved: boolean }
+export type MeetingHistoryUpdated = null
+/**
+ * A meeting from a previous run was interrupted and can be recovered
+ */
+export type MeetingRecoveryAvailable = MeetingSession
+/**
+ * A complete meeting session
+ */
+export type MeetingSession = { meeting_id: string; started_at: number; ended_at: number | null; chunks: MeetingChunk[]; full_transcript: string | null; summary: string | null; action_items: string[] | null; topics?: MeetingTopic[] | null; duration_seconds: number | null }
+/**
+ * A speaker told apart in a meeting and the name given to them
+ */
+export type MeetingSpeaker = { 
+/**
+ * Diarization label, e.g. "Speaker 1"
+ */
+label: string; name: string | null }
+/**
+ * State of the meeting mode
+ */
+export type MeetingState = 
+/**
+ * No meeting in progress
+ */
+{ state: "Idle" } | 
+/**
+ * Meeting is currently recording
+ */
+{ state: "Recording"; meeting_id: string; started_at: number; chunk_count: number; binding_id: string } | 
+/**
+ * Meeting is paused; nothing is recorded until it is resumed
+ */
+{ state: "Paused"; meeting_id: string; started_at: number; chunk_count: number; binding_id: string } | 
+/**
+ * Meeting is being processed (transcription/summarization)
+ */
+{ state: "Processing"; meeting_id: string }
+/**
+ * Event emitted when meeting state changes
+ */
+export type MeetingStateEvent = { state: MeetingState; elapsed_seconds: number | null; chunk_count: number | null }
+/**
+ * A section of a meeting about one topic
+ */
+export type MeetingTopic = { title: string; 
+/**
+ * Offsets from the start of the recording
+ */
+start_ms: number; end_ms: number }
+/**
+ * Microphone levels for the overlay's level meter
+ */
+export type MicLevel = number[]
+/**
+ * Model id of a finished download
+ */
+export type ModelDownloadComplete = string
+/**
+ * Downloads that were interrupted by a previous run and can be resumed
+ */
+export type ModelDownloadsInterrupted = PartialDownload[]
+export type ModelExtractionCompleted = string
+export type ModelExtractionFailed = { model_id: string; error: string }
+export type ModelExtractionStarted = string
+export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; coreml_url?: string | null; coreml_size_mb?: number; is_coreml_downloaded?: boolean; is_coreml_downloading?: boolean }
+export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
+export type ModelStateEvent = { event_type: string; model_id: string | null; model_name: string | null; error: string | null }
+export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
+/**
+ * What an extra mouse button does
+ */
+export type MouseButtonAction = "disabled" | 
+/**
+ * Start or stop dictation on each click, like the transcribe shortcut in toggle mode
+ */
+"toggle_recording" | 
+/**
+ * Dictate while the button is held down
+ */
+"push_to_talk"
+/**
+ * The back and forward side buttons (MB4/MB5), handled outside the global shortcuts
+ */
+export type MouseButtons = { enabled?: boolean; back?: MouseButtonAction; forward?: MouseButtonAction }
+/**
+ * Open the settings page from the tray menu
+ */
+export type NavigateToSettings = null
+export type OverlayPosition = "none" | "top" | "bottom"
+/**
+ * A download that was interrupted and left a resumable partial file behind
+ */
+export type PartialDownload = { model_id: string; model_name: string; partial_size: number; size_mb: number }
+export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v"
+/**
+ * One step of a post-processing pipeline: a prompt run on its own
+ * provider and model, with `${output}` standing for the previous step's text
+ */
+export type PipelineStep = { name: string; prompt: string; provider_id: string; model: string }
+/**
+ * Post-processing failed on every provider; the transcription is used as is
+ */
+export type PostProcessFailed = { provider_id: string; error: string }
+/**
+ * The post-processing provider failed and the fallback provider was used
+ */
+export type PostProcessFallback = { failed_provider_id: string; provider_id: string; error: string }
+export type PostProcessMode = 
+/**
+ * The selected pipeline, or else the selected prompt
+ */
+"custom" | 
+/**
+ * Only fix grammar and punctuation, see `crate::grammar`
+ */
+"grammar_only" | 
+/**
+ * Translate into the target language, see `crate::translation`
+ */
+"translate"
+/**
+ * Prompts run one after another, e.g. clean up, translate, format as email
+ */
+export type PostProcessPipeline = { id: string; name: string; steps: PipelineStep[] }
+export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null }
+/**
+ * Post-processing reply as it arrives. `text` is everything received so
+ * far; the last event has `done` set and an empty `delta`.
+ */
+export type PostProcessStream = { delta: string; text: string; done: boolean }
+/**
+ * What the profanity filter does with the words it catches
+ */
+export type ProfanityFilter = "off" | 
 /**
- * Remove a specific job
+ * Replace each letter with `*`
  */
-async removeFileTranscriptionJob(jobId: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_file_transcription_job", { jobId }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+"mask" | 
 /**
- * Check if file transcription is currently processing
+ * Drop the word
  */
-async isFileTranscriptionProcessing() : Promise<boolean> {
-    return await TAURI_INVOKE("is_file_transcription_processing");
-},
+"remove"
 /**
- * Export a transcript in the specified format
+ * Proxy used for every outbound HTTP request: cloud transcription,
+ * post-processing and model downloads
  */
-async exportTranscript(text: string, format: ExportFormat, title: string | null, sourceFile: string | null, durationMs: number | null, segments: TranscriptSegment[] | null) : Promise<Result<string, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("export_transcript", { text, format, title, sourceFile, durationMs, segments }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+export type ProxySettings = { enabled?: boolean; 
 /**
- * Export a transcript and save to file
+ * `http://`, `https://`, `socks5://` or `socks5h://` URL with port
  */
-async exportTranscriptToFile(text: string, format: ExportFormat, filePath: string, title: string | null, sourceFile: string | null, durationMs: number | null, segments: TranscriptSegment[] | null) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("export_transcript_to_file", { text, format, filePath, title, sourceFile, durationMs, segments }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+url?: string; username?: string | null; password?: string | null; 
 /**
- * Get the appropriate file extension for a format
+ * Hosts, domains (`.corp.example`) and CIDR ranges reached directly
  */
-async getExportFileExtension(format: ExportFormat) : Promise<string> {
-    return await TAURI_INVOKE("get_export_file_extension", { format });
-},
+bypass?: string[] }
 /**
- * Get all available export formats
+ * Punctuation restoration status response
  */
-async getAvailableExportFormats() : Promise<ExportFormat[]> {
-    return await TAURI_INVOKE("get_available_export_formats");
-},
+export type PunctuationStatus = { available: boolean; enabled: boolean; model_downloaded: boolean; model_size_bytes: number; download_progress: number | null; error: string | null }
+export type QuietHoursChangedEvent = { active: boolean }
 /**
- * Get all configured watch folders
+ * Container used for history recordings
  */
-async getWatchFolders() : Promise<WatchFolderConfig[]> {
-    return await TAURI_INVOKE("get_watch_folders");
-},
+export type RecordingFormat = "wav" | 
 /**
- * Add a new watch folder
+ * Lossless and roughly half the size of WAV
  */
-async addWatchFolder(path: string, recursive: boolean) : Promise<Result<WatchFolderConfig, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("add_watch_folder", { path, recursive }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+"flac"
 /**
- * Remove a watch folder
+ * Event payload emitted when a recording hits the maximum duration and is
+ * stopped automatically
  */
-async removeWatchFolder(folderId: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("remove_watch_folder", { folderId }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+export type RecordingLimitReachedEvent = { binding_id: string; max_duration_seconds: number }
+export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
 /**
- * Update a watch folder configuration
+ * A user-defined find and replace, run in order with the other rules.
+ * `replacement` can refer to capture groups as `$1` or `${name}`.
  */
-async updateWatchFolder(config: WatchFolderConfig) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("update_watch_folder", { config }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+export type RegexRule = { id: string; pattern: string; replacement: string; case_insensitive?: boolean; 
 /**
- * Get the status of all watch folders
+ * App names the rule applies in; empty for every app
  */
-async getWatchFolderStatus() : Promise<WatchFolderStatus[]> {
-    return await TAURI_INVOKE("get_watch_folder_status");
-},
+apps?: string[]; enabled?: boolean }
 /**
- * Start watching a specific folder
+ * What a retention rule does to the entries it matches
  */
-async startWatchFolder(folderId: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("start_watch_folder", { folderId }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+export type RetentionAction = 
 /**
- * Stop watching a specific folder
+ * Remove the entry and its recording
  */
-async stopWatchFolder(folderId: string) : Promise<Result<null, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("stop_watch_folder", { folderId }) };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-},
+"delete_entry" | 
 /**
- * Checks if the Mac is a laptop by detecting battery presence
- * 
- * This uses pmset to check for battery information.
- * Returns true if a battery is detected (laptop), false otherwise (desktop)
+ * Remove the recording but keep the transcript
  */
-async isLaptop() : Promise<Result<boolean, string>> {
-    try {
-    return { status: "ok", data: await TAURI_INVOKE("is_laptop") };
-} catch (e) {
-    if(e instanceof Error) throw e;
-    else return { status: "error", error: e  as any };
-}
-}
-}
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type AppSettings = { bindings: Partial<{ [key in string]: ShortcutBinding }>; push_to_talk: boolean; audio_feedback: boolean; audio_feedback_volume?: number; sound_theme?: SoundTheme; start_hidden?: boolean; autostart_enabled?: boolean; update_checks_enabled?: boolean; selected_model?: string; always_on_microphone?: boolean; selected_microphone?: string | null; clamshell_microphone?: string | null; selected_output_device?: string | null; translate_to_english?: boolean; selected_language?: string; overlay_position?: OverlayPosition; show_sidebar?: boolean; debug_mode?: boolean; log_level?: LogLevel; custom_words?: string[]; model_unload_timeout?: ModelUnloadTimeout; word_correction_threshold?: number; history_limit?: number; recording_retention_period?: RecordingRetentionPeriod; paste_method?: PasteMethod; clipboard_handling?: ClipboardHandling; post_process_enabled?: boolean; post_process_provider_id?: string; post_process_providers?: PostProcessProvider[]; post_process_api_keys?: Partial<{ [key in string]: string }>; post_process_models?: Partial<{ [key in string]: string }>; post_process_prompts?: LLMPrompt[]; post_process_selected_prompt_id?: string | null; mute_while_recording?: boolean; append_trailing_space?: boolean; app_language?: string; snippets?: Snippet[]; snippets_enabled?: boolean; auto_format_enabled?: boolean; auto_format_lists?: boolean; verbal_commands_enabled?: boolean; tone_adjustment_enabled?: boolean; default_tone?: ToneStyle; app_tone_mappings?: Partial<{ [key in string]: ToneStyle }>; developer_mode?: DeveloperMode; preserve_code_syntax?: boolean; developer_dictionary?: string[]; correction_detection_enabled?: boolean; context_awareness_enabled?: boolean; context_per_app_permissions?: Partial<{ [key in string]: boolean }>; groq_transcription_api_key?: string; multilingual_mode_enabled?: boolean; primary_language?: string | null; secondary_language?: string | null; language_detection_sensitivity?: number; show_meeting_menu?: boolean; meeting_mode_enabled?: boolean; meeting_chunk_duration_seconds?: number; meeting_auto_summarize?: boolean; meeting_extract_action_items?: boolean; meeting_summary_prompt?: string; meeting_action_items_prompt?: string; live_preview_enabled?: boolean; live_preview_interval_ms?: number; watch_folders?: WatchFolderConfig[] | null; whisper_mode_enabled?: boolean; vad_threshold?: number; diarization_enabled?: boolean; coreml_enabled?: boolean; auto_download_coreml?: boolean }
-export type AudioDevice = { index: string; name: string; is_default: boolean; device_type: AudioDeviceType }
+"delete_audio" | 
 /**
- * Device type classification for audio devices
+ * Exempt the entry from every other rule, e.g. to keep saved entries forever
  */
-export type AudioDeviceType = "microphone" | "system_loopback" | "virtual_device" | "unknown"
-export type BindingResponse = { success: boolean; binding: ShortcutBinding | null; error: string | null }
-export type ClipboardHandling = "dont_modify" | "copy_to_clipboard"
-export type CustomSounds = { start: boolean; stop: boolean }
-export type DeveloperMode = 
+"keep"
 /**
- * Developer mode is disabled
+ * What a pass of the retention rules removed
  */
-"off" | 
+export type RetentionReport = { entries_deleted: number; audio_purged: number }
 /**
- * Auto-detect IDE/terminal apps
+ * History cleanup rule. Entries older than `older_than_days` that pass every
+ * filter get `action`, unless a `Keep` rule matches them too; filters left
+ * empty match everything.
  */
-"auto" | 
+export type RetentionRule = { id: string; enabled?: boolean; older_than_days: number; action: RetentionAction; 
 /**
- * Always use developer mode
+ * Only match saved (`true`) or unsaved (`false`) entries
  */
-"always"
+saved?: boolean | null; 
 /**
- * Model info for display in UI
+ * Only match transcripts containing this text, ignoring case
  */
-export type DiarizationModelInfo = { name: string; description: string; size_bytes: number }
+text_contains?: string | null }
+export type SelectionCycledEvent = { kind: CycleKind; 
 /**
- * Diarization status response
+ * The setting's new value: a device name, model ID or language code
  */
-export type DiarizationStatus = { available: boolean; enabled: boolean; models_downloaded: boolean; download_progress: number | null; error: string | null }
-export type EngineType = "Whisper" | "Parakeet" | "Moonshine" | "GroqCloud"
+value: string; 
 /**
- * Export format options
+ * What to show for it
  */
-export type ExportFormat = "txt" | "srt" | "vtt" | "json" | "markdown" | "csv" | "html" | "docx" | "pdf"
+label: string }
 /**
- * A single file transcription job
+ * A top-level setting changed; `setting` is the `AppSettings` field name
  */
-export type FileTranscriptionJob = { id: string; file_path: string; file_name: string; file_size: number; status: FileTranscriptionStatus; progress: number; transcription: string | null; error: string | null; duration_seconds: number | null; created_at: number; completed_at: number | null }
+export type SettingsChanged = { setting: string; value: any }
+export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
 /**
- * Status of a file transcription job
+ * Who already uses a shortcut
  */
-export type FileTranscriptionStatus = "queued" | "processing" | "completed" | "failed" | "cancelled"
-export type HistoryEntry = { id: number; file_name: string; timestamp: number; saved: boolean; title: string; transcription_text: string; post_processed_text: string | null; post_process_prompt: string | null }
-export type LLMPrompt = { id: string; name: string; prompt: string }
-export type LogLevel = "trace" | "debug" | "info" | "warn" | "error"
+export type ShortcutConflict = 
 /**
- * A single chunk of meeting audio
+ * Another PaperFlow action
  */
-export type MeetingChunk = { chunk_id: number; start_time_ms: number; end_time_ms: number; audio_path: string | null; transcription: string | null }
+{ type: "binding"; id: string; name: string } | 
 /**
- * A meeting history entry for the frontend
+ * Another action in a saved shortcut profile, once switched to
  */
-export type MeetingHistoryEntry = { id: number; meeting_id: string; started_at: number; ended_at: number; duration_seconds: number; full_transcript: string; summary: string | null; action_items: string[] | null; chunk_count: number; saved: boolean }
+{ type: "profile"; id: string; name: string } | 
 /**
- * A complete meeting session
+ * A hardware key or mouse button PaperFlow already listens to
  */
-export type MeetingSession = { meeting_id: string; started_at: number; ended_at: number | null; chunks: MeetingChunk[]; full_transcript: string | null; summary: string | null; action_items: string[] | null; duration_seconds: number | null }
+{ type: "device"; name: string } | 
 /**
- * State of the meeting mode
+ * The system or another app, which refused to hand it over
  */
-export type MeetingState = 
+{ type: "system" }
 /**
- * No meeting in progress
+ * A named set of shortcuts, like one for the laptop keyboard and one for an
+ * external keyboard
  */
-{ state: "Idle" } | 
+export type ShortcutProfile = { id: string; name: string; 
 /**
- * Meeting is currently recording
+ * Shortcut per binding id
  */
-{ state: "Recording"; meeting_id: string; started_at: number; chunk_count: number; binding_id: string } | 
+bindings?: Partial<{ [key in string]: string }> }
 /**
- * Meeting is being processed (transcription/summarization)
+ * Which state the recording overlay should show
  */
-{ state: "Processing"; meeting_id: string }
-export type ModelInfo = { id: string; name: string; description: string; filename: string; url: string | null; size_mb: number; is_downloaded: boolean; is_downloading: boolean; partial_size: number; is_directory: boolean; engine_type: EngineType; accuracy_score: number; speed_score: number; coreml_url?: string | null; coreml_size_mb?: number; is_coreml_downloaded?: boolean; is_coreml_downloading?: boolean }
-export type ModelLoadStatus = { is_loaded: boolean; current_model: string | null }
-export type ModelUnloadTimeout = "never" | "immediately" | "min_2" | "min_5" | "min_10" | "min_15" | "hour_1" | "sec_5"
-export type OverlayPosition = "none" | "top" | "bottom"
-export type PasteMethod = "ctrl_v" | "direct" | "none" | "shift_insert" | "ctrl_shift_v"
-export type PostProcessProvider = { id: string; label: string; base_url: string; allow_base_url_edit?: boolean; models_endpoint?: string | null }
-export type RecordingRetentionPeriod = "never" | "preserve_limit" | "days_3" | "weeks_2" | "months_3"
-export type ShortcutBinding = { id: string; name: string; description: string; default_binding: string; current_binding: string }
+export type ShowOverlay = "recording" | "meeting" | "transcribing"
 /**
  * A voice snippet that expands a trigger phrase into full text
  */
@@ -1414,6 +3445,31 @@ case_sensitive?: boolean;
  */
 whole_word?: boolean }
 export type SoundTheme = "marimba" | "pop" | "custom"
+/**
+ * How stutters like "wh wh wh" are collapsed to a single word
+ */
+export type StutterSettings = { 
+/**
+ * Longest word, in letters, that is collapsed when repeated
+ */
+max_word_length?: number; 
+/**
+ * Repetitions in a row that count as a stutter
+ */
+min_repetitions?: number; 
+/**
+ * Also collapse longer repeated words, like "the the the"
+ */
+collapse_repeated_words?: boolean }
+export type SyncReport = { 
+/**
+ * Local changes written to the sync folder
+ */
+exported: number; 
+/**
+ * Changes from other devices applied locally
+ */
+imported: number }
 /**
  * Information about system audio capture capabilities
  */
@@ -1442,6 +3498,32 @@ devices: string[];
  * Platform-specific info about native capture
  */
 native_info: string }
+/**
+ * Where meeting action items are sent
+ */
+export type TaskDestination = { provider: TaskProviderKind; 
+/**
+ * Todoist project ID, or Things/Reminders list name; empty for the default
+ */
+list?: string; 
+/**
+ * For providers with an API (Todoist)
+ */
+api_token?: string }
+/**
+ * Task manager meeting action items can be sent to
+ */
+export type TaskProviderKind = "todoist" | "things" | "reminders"
+/**
+ * A stage of local text processing, run on every transcription in the
+ * order the pipeline lists them
+ */
+export type TextStage = "spelling" | "custom_words" | "filter" | "corrections" | "itn" | "snippets" | "regex_rules" | "identifier_casing" | "formatting"
+/**
+ * A stage's place in the text pipeline. A disabled stage is skipped even
+ * when its own setting is on.
+ */
+export type TextStageConfig = { stage: TextStage; enabled: boolean }
 /**
  * Tone styles for transcription adjustment
  */
@@ -1462,18 +3544,192 @@ export type ToneStyle =
  * Minimal changes, faithful transcription
  */
 "neutral"
+/**
+ * A chapter of a long transcript, running until the next one starts
+ */
+export type TranscriptChapter = { title: string; start_ms: number }
 /**
  * Segment with timing information for SRT/VTT export
  */
 export type TranscriptSegment = { start_ms: number; end_ms: number; text: string; speaker: string | null }
+/**
+ * Per-job overrides for a transcription, e.g. from a watch folder's configuration.
+ * Unset fields fall back to the global settings.
+ */
+export type TranscriptionOverrides = { model_id?: string | null; language?: string | null; diarization_enabled?: boolean | null; 
+/**
+ * Translate the speech to English
+ */
+translate?: boolean | null; 
+/**
+ * Formats to write next to a file job's source when it completes
+ */
+sidecar_formats?: ExportFormat[] | null; 
+/**
+ * Folder to write the sidecar files to instead of next to the source
+ */
+sidecar_folder?: string | null; 
+/**
+ * Name of the sidecar files without extension, with `{stem}`, `{date}`
+ * and `{model}` filled in; unset keeps the source's name
+ */
+sidecar_name_template?: string | null; 
+/**
+ * Text the speech continues, given to the engine at the end of its
+ * prompt so the transcription matches its spelling and style
+ */
+initial_prompt?: string | null; 
+/**
+ * App or website the speech goes to, whose profile and scoped rules
+ * apply; looked up when needed if unset
+ */
+app_name?: string | null; 
+/**
+ * Names and terms from the window the speech goes to, given to the
+ * engine with the custom words so it recognizes them
+ */
+context_terms?: string[] | null }
+/**
+ * Pacing for the "type" paste method, for apps that drop keystrokes sent
+ * too quickly
+ */
+export type TypingSettings = { 
+/**
+ * 0 types as fast as the system takes it
+ */
+chars_per_second?: number; 
+/**
+ * Characters typed at once; 0 types everything in one go
+ */
+chunk_size?: number; 
+/**
+ * Pause after each chunk
+ */
+chunk_delay_ms?: number; 
+/**
+ * Stop typing when another window takes focus between chunks
+ */
+stop_on_focus_change?: boolean }
+/**
+ * Wake word status response
+ */
+export type WakeWordStatus = { available: boolean; enabled: boolean; 
+/**
+ * What to say to wake the app; unknown with a custom model
+ */
+phrase: string | null; model_downloaded: boolean; model_size_bytes: number; download_progress: number | null; error: string | null }
 /**
  * Configuration for a watched folder
  */
-export type WatchFolderConfig = { id: string; path: string; enabled: boolean; recursive: boolean; auto_process: boolean }
+export type WatchFolderConfig = { id: string; path: string; enabled: boolean; recursive: boolean; auto_process: boolean; 
+/**
+ * Model to transcribe this folder's files with instead of the selected one
+ */
+model_id?: string | null; 
+/**
+ * Language to use instead of the global language setting
+ */
+language?: string | null; 
+/**
+ * Whether to run speaker diarization, overriding the global setting
+ */
+diarization_enabled?: boolean | null; 
+/**
+ * Scan for changes every N seconds instead of relying on native file
+ * events. Network folders (SMB/NFS) are polled even when this is unset.
+ */
+polling_interval_seconds?: number | null; 
+/**
+ * Formats written next to each file once it is transcribed, e.g. SRT and TXT
+ */
+sidecar_formats?: ExportFormat[]; 
+/**
+ * Only files matching one of these are picked up, e.g. `*.m4a` or
+ * `calls/**/*.mp3`; empty picks up every supported file
+ */
+include_globs?: string[]; 
+/**
+ * Files matching any of these are ignored. A pattern ending in `/`,
+ * like `archive/`, ignores everything in the folders it matches.
+ */
+exclude_globs?: string[]; 
+/**
+ * What happens to a file once it has been transcribed
+ */
+post_action?: WatchFolderPostAction; 
+/**
+ * Folder the sidecar files are written to, relative to the watched
+ * folder or absolute; unset writes them next to each file
+ */
+transcript_folder?: string | null; 
+/**
+ * Name of the transcript files, e.g. `{date} {stem}`; placeholders are
+ * `{stem}` (the file's name), `{date}` and `{model}`
+ */
+transcript_name_template?: string | null; 
+/**
+ * When set and enabled, files are only queued inside this daily window,
+ * e.g. 22:00-07:00; files found outside it wait until it opens
+ */
+active_hours?: DailyWindow | null }
+/**
+ * Event payload for watch folder file detection
+ */
+export type WatchFolderFileDetected = { folder_id: string; file_path: string; file_name: string }
+/**
+ * What happens to a watched file after it is transcribed
+ */
+export type WatchFolderPostAction = 
+/**
+ * Leave it where it is
+ */
+{ type: "keep" } | 
+/**
+ * Move it into a folder, relative to the watched folder (e.g. "done") or absolute
+ */
+{ type: "move_to"; folder: string } | 
+/**
+ * Rename it with the date it was transcribed in front, e.g. "2024-06-01 memo.m4a"
+ */
+{ type: "prefix_date" } | 
+/**
+ * Delete it
+ */
+{ type: "delete" }
 /**
  * Status of a watch folder
  */
-export type WatchFolderStatus = { folder_id: string; is_watching: boolean; last_error: string | null; files_processed: number }
+export type WatchFolderStatus = { folder_id: string; is_watching: boolean; last_error: string | null; files_processed: number; 
+/**
+ * Whether the folder is scanned periodically rather than watched natively
+ */
+polling?: boolean }
+/**
+ * Peaks of a recording for drawing its scrubber
+ */
+export type Waveform = { duration_ms: number; 
+/**
+ * Loudest sample per bar, from 0.0 to 1.0
+ */
+peaks: number[] }
+export type WhisperModeChanged = boolean
+/**
+ * How transcribed words are compared with custom words
+ */
+export type WordMatchAlgorithm = 
+/**
+ * Levenshtein distance, boosted for words with the same Soundex code
+ */
+"soundex" | 
+/**
+ * Levenshtein distance, boosted for words with a Double Metaphone code
+ * in common. Fewer false matches on names that are not English.
+ */
+"double_metaphone" | 
+/**
+ * Jaro-Winkler distance alone, which favours words with the same start
+ */
+"jaro_winkler"
 
 /** tauri-specta globals **/
 
@@ -1,8 +1,11 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
-import { listen } from "@tauri-apps/api/event";
-import type { ModelInfo } from "@/bindings";
-import { commands } from "@/bindings";
+import type {
+  CoreMLDownloadProgress,
+  DownloadProgress,
+  ModelInfo,
+} from "@/bindings";
+import { commands, events } from "@/bindings";
 import { formatModelSize } from "../../lib/utils/format";
 import {
   getTranslatedModelName,
@@ -11,20 +14,6 @@ import {
 import { ProgressBar } from "../shared";
 import { useSettings } from "../../hooks/useSettings";
 
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
-
-interface CoreMLDownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
-
 interface ModelDropdownProps {
   models: ModelInfo[];
   currentModelId: string;
@@ -76,29 +65,23 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
 
   // Listen for CoreML download progress events
   useEffect(() => {
-    const progressUnlisten = listen<CoreMLDownloadProgress>(
-      "coreml-download-progress",
-      (event) => {
-        const progress = event.payload;
-        setCoremlDownloadProgress((prev) => {
-          const newMap = new Map(prev);
-          newMap.set(progress.model_id, progress);
-          return newMap;
-        });
-      },
-    );
+    const progressUnlisten = events.coremlDownloadProgress.listen((event) => {
+      const progress = event.payload;
+      setCoremlDownloadProgress((prev) => {
+        const newMap = new Map(prev);
+        newMap.set(progress.model_id, progress);
+        return newMap;
+      });
+    });
 
-    const completeUnlisten = listen<string>(
-      "coreml-download-complete",
-      (event) => {
-        const modelId = event.payload;
-        setCoremlDownloadProgress((prev) => {
-          const newMap = new Map(prev);
-          newMap.delete(modelId);
-          return newMap;
-        });
-      },
-    );
+    const completeUnlisten = events.coremlDownloadComplete.listen((event) => {
+      const modelId = event.payload;
+      setCoremlDownloadProgress((prev) => {
+        const newMap = new Map(prev);
+        newMap.delete(modelId);
+        return newMap;
+      });
+    });
 
     return () => {
       progressUnlisten.then((fn) => fn());
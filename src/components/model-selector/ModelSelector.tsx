@@ -1,33 +1,16 @@
 import React, { useState, useRef, useEffect } from "react";
 import { useTranslation } from "react-i18next";
-import { listen } from "@tauri-apps/api/event";
-import { commands, type ModelInfo } from "@/bindings";
+import {
+  commands,
+  events,
+  type DownloadProgress,
+  type ModelInfo,
+} from "@/bindings";
 import { getTranslatedModelName } from "../../lib/utils/modelTranslation";
 import ModelStatusButton from "./ModelStatusButton";
 import ModelDropdown from "./ModelDropdown";
 import DownloadProgressDisplay from "./DownloadProgressDisplay";
 
-interface ModelStateEvent {
-  event_type: string;
-  model_id?: string;
-  model_name?: string;
-  error?: string;
-}
-
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
-
-interface CoreMLCompilationEvent {
-  event_type: string; // "started" | "completed" | "failed"
-  model_id: string;
-  estimated_time_seconds?: number;
-  error?: string;
-}
-
 type ModelStatus =
   | "ready"
   | "loading"
@@ -77,36 +60,32 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     loadCurrentModel();
 
     // Listen for model state changes
-    const modelStateUnlisten = listen<ModelStateEvent>(
-      "model-state-changed",
-      (event) => {
-        const { event_type, model_id, model_name, error } = event.payload;
-
-        switch (event_type) {
-          case "loading_started":
-            setModelStatus("loading");
-            setModelError(null);
-            break;
-          case "loading_completed":
-            setModelStatus("ready");
-            setModelError(null);
-            if (model_id) setCurrentModelId(model_id);
-            break;
-          case "loading_failed":
-            setModelStatus("error");
-            setModelError(error || "Failed to load model");
-            break;
-          case "unloaded":
-            setModelStatus("unloaded");
-            setModelError(null);
-            break;
-        }
-      },
-    );
+    const modelStateUnlisten = events.modelStateChanged.listen((event) => {
+      const { event_type, model_id, model_name, error } = event.payload;
+
+      switch (event_type) {
+        case "loading_started":
+          setModelStatus("loading");
+          setModelError(null);
+          break;
+        case "loading_completed":
+          setModelStatus("ready");
+          setModelError(null);
+          if (model_id) setCurrentModelId(model_id);
+          break;
+        case "loading_failed":
+          setModelStatus("error");
+          setModelError(error || "Failed to load model");
+          break;
+        case "unloaded":
+          setModelStatus("unloaded");
+          setModelError(null);
+          break;
+      }
+    });
 
     // Listen for model download progress
-    const downloadProgressUnlisten = listen<DownloadProgress>(
-      "model-download-progress",
+    const downloadProgressUnlisten = events.modelDownloadProgress.listen(
       (event) => {
         const progress = event.payload;
         setModelDownloadProgress((prev) => {
@@ -160,8 +139,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     );
 
     // Listen for model download completion
-    const downloadCompleteUnlisten = listen<string>(
-      "model-download-complete",
+    const downloadCompleteUnlisten = events.modelDownloadComplete.listen(
       (event) => {
         const modelId = event.payload;
         setModelDownloadProgress((prev) => {
@@ -189,8 +167,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     );
 
     // Listen for extraction events
-    const extractionStartedUnlisten = listen<string>(
-      "model-extraction-started",
+    const extractionStartedUnlisten = events.modelExtractionStarted.listen(
       (event) => {
         const modelId = event.payload;
         setExtractingModels((prev) => new Set(prev.add(modelId)));
@@ -198,8 +175,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
       },
     );
 
-    const extractionCompletedUnlisten = listen<string>(
-      "model-extraction-completed",
+    const extractionCompletedUnlisten = events.modelExtractionCompleted.listen(
       (event) => {
         const modelId = event.payload;
         setExtractingModels((prev) => {
@@ -221,23 +197,21 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
       },
     );
 
-    const extractionFailedUnlisten = listen<{
-      model_id: string;
-      error: string;
-    }>("model-extraction-failed", (event) => {
-      const modelId = event.payload.model_id;
-      setExtractingModels((prev) => {
-        const next = new Set(prev);
-        next.delete(modelId);
-        return next;
-      });
-      setModelError(`Failed to extract model: ${event.payload.error}`);
-      setModelStatus("error");
-    });
+    const extractionFailedUnlisten = events.modelExtractionFailed.listen(
+      (event) => {
+        const modelId = event.payload.model_id;
+        setExtractingModels((prev) => {
+          const next = new Set(prev);
+          next.delete(modelId);
+          return next;
+        });
+        setModelError(`Failed to extract model: ${event.payload.error}`);
+        setModelStatus("error");
+      },
+    );
 
     // Listen for CoreML compilation events (first-run takes 3-5 minutes)
-    const coremlCompilationUnlisten = listen<CoreMLCompilationEvent>(
-      "coreml-compilation-status",
+    const coremlCompilationUnlisten = events.coremlCompilationStatus.listen(
       (event) => {
         const { event_type, model_id, estimated_time_seconds, error } =
           event.payload;
@@ -245,7 +219,7 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
           case "started":
             setCoremlCompiling({
               modelId: model_id,
-              estimatedSeconds: estimated_time_seconds,
+              estimatedSeconds: estimated_time_seconds ?? undefined,
             });
             setModelStatus("coreml_compiling");
             break;
@@ -115,6 +115,7 @@ export const ExportDropdown: React.FC<ExportDropdownProps> = ({
           sourceFile ?? null,
           durationMs ?? null,
           null, // segments - let backend generate them
+          null,
         );
 
         if (result.status !== "ok") {
@@ -17,18 +17,10 @@ import {
   ChevronDown,
 } from "lucide-react";
 import { Button } from "../../ui/Button";
-import { commands, type FileTranscriptionJob } from "@/bindings";
+import { commands, events, type FileTranscriptionJob } from "@/bindings";
 import { ExportDropdown } from "./ExportDropdown";
 import { WatchFolderSettings } from "./WatchFolderSettings";
 
-interface FileTranscriptionEventPayload {
-  job_id: string;
-  status: string;
-  progress: number;
-  transcription: string | null;
-  error: string | null;
-}
-
 const StatusIcon: React.FC<{ status: string }> = ({ status }) => {
   switch (status) {
     case "queued":
@@ -87,8 +79,7 @@ export const FileTranscriptionSettings: React.FC = () => {
   // Listen for job updates
   useEffect(() => {
     const setupListener = async () => {
-      const unlisten = await listen<FileTranscriptionEventPayload>(
-        "file-transcription-update",
+      const unlisten = await events.fileTranscriptionUpdate.listen(
         async (event) => {
           const payload = event.payload;
 
@@ -111,7 +102,7 @@ export const FileTranscriptionSettings: React.FC = () => {
             const updatedJobs = [...prevJobs];
             updatedJobs[existingIndex] = {
               ...updatedJobs[existingIndex],
-              status: payload.status as FileTranscriptionJob["status"],
+              status: payload.status,
               progress: payload.progress,
               transcription: payload.transcription,
               error: payload.error,
@@ -159,7 +150,7 @@ export const FileTranscriptionSettings: React.FC = () => {
 
           if (filteredPaths.length > 0) {
             const result =
-              await commands.queueFilesForTranscription(filteredPaths);
+              await commands.queueFilesForTranscription(filteredPaths, null);
             if (result.status === "ok") {
               setJobs((prev) => [...prev, ...result.data]);
             }
@@ -197,7 +188,8 @@ export const FileTranscriptionSettings: React.FC = () => {
       if (selected) {
         const paths = Array.isArray(selected) ? selected : [selected];
         if (paths.length > 0) {
-          const result = await commands.queueFilesForTranscription(paths);
+          const result =
+            await commands.queueFilesForTranscription(paths, null);
           if (result.status === "ok") {
             setJobs((prev) => [...prev, ...result.data]);
           } else {
@@ -1,6 +1,5 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
-import { listen } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-dialog";
 import { toast } from "sonner";
 import {
@@ -15,16 +14,11 @@ import {
 import { Button } from "../../ui/Button";
 import {
   commands,
+  events,
   type WatchFolderConfig,
   type WatchFolderStatus,
 } from "@/bindings";
 
-interface WatchFolderFileDetectedPayload {
-  folder_id: string;
-  file_path: string;
-  file_name: string;
-}
-
 export const WatchFolderSettings: React.FC = () => {
   const { t } = useTranslation();
   const [folders, setFolders] = useState<WatchFolderConfig[]>([]);
@@ -55,14 +49,11 @@ export const WatchFolderSettings: React.FC = () => {
   // Listen for file detection events
   useEffect(() => {
     const setupListener = async () => {
-      const unlisten = await listen<WatchFolderFileDetectedPayload>(
-        "watch-folder-file-detected",
-        async () => {
-          // Refresh status when a file is detected
-          const statusResult = await commands.getWatchFolderStatus();
-          setStatuses(statusResult);
-        },
-      );
+      const unlisten = await events.watchFolderFileDetected.listen(async () => {
+        // Refresh status when a file is detected
+        const statusResult = await commands.getWatchFolderStatus();
+        setStatuses(statusResult);
+      });
 
       return unlisten;
     };
@@ -86,7 +77,8 @@ export const WatchFolderSettings: React.FC = () => {
       });
 
       if (selected && typeof selected === "string") {
-        const result = await commands.addWatchFolder(selected, false);
+        const result =
+          await commands.addWatchFolder(selected, false, null, null);
         if (result.status === "ok") {
           setFolders((prev) => [...prev, result.data]);
           // Refresh statuses
@@ -4,8 +4,7 @@ import { AudioPlayer } from "../../ui/AudioPlayer";
 import { Button } from "../../ui/Button";
 import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
-import { listen } from "@tauri-apps/api/event";
-import { commands, type HistoryEntry } from "@/bindings";
+import { commands, events, type HistoryEntry } from "@/bindings";
 import { formatDateTime } from "@/utils/dateFormat";
 import { ExportDropdown } from "../files/ExportDropdown";
 
@@ -53,7 +52,7 @@ export const HistorySettings: React.FC = () => {
 
     // Listen for history update events
     const setupListener = async () => {
-      const unlisten = await listen("history-updated", () => {
+      const unlisten = await events.historyUpdated.listen(() => {
         console.log("History updated, reloading entries...");
         loadHistoryEntries();
       });
@@ -94,7 +93,7 @@ export const HistorySettings: React.FC = () => {
     try {
       const result = await commands.getAudioFilePath(fileName);
       if (result.status === "ok") {
-        return convertFileSrc(`${result.data}`, "asset");
+        return convertFileSrc(result.data, "recording");
       }
       return null;
     } catch (error) {
@@ -1,8 +1,7 @@
 import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { Copy, Check, ChevronDown, Trash2 } from "lucide-react";
-import { listen } from "@tauri-apps/api/event";
-import { commands, type MeetingHistoryEntry } from "@/bindings";
+import { commands, events, type MeetingHistoryEntry } from "@/bindings";
 import { formatDateTime } from "@/utils/dateFormat";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 
@@ -297,7 +296,7 @@ export const MeetingHistory: React.FC = () => {
     loadEntries();
 
     const setupListener = async () => {
-      const unlisten = await listen("meeting-history-updated", () => {
+      const unlisten = await events.meetingHistoryUpdated.listen(() => {
         loadEntries();
       });
       return unlisten;
@@ -1,6 +1,6 @@
 import React, { useState, useEffect, useRef } from "react";
 import { useTranslation } from "react-i18next";
-import { listen } from "@tauri-apps/api/event";
+import { events } from "@/bindings";
 import { getVersion } from "@tauri-apps/api/app";
 import { openUrl } from "@tauri-apps/plugin-opener";
 import { useSettings } from "../../hooks/useSettings";
@@ -46,7 +46,7 @@ const UpdateChecker: React.FC<UpdateCheckerProps> = ({ className = "" }) => {
 
     checkForUpdates();
 
-    const updateUnlisten = listen("check-for-updates", () => {
+    const updateUnlisten = events.checkForUpdates.listen(() => {
       handleManualUpdateCheck();
     });
 
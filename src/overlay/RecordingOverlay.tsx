@@ -1,4 +1,3 @@
-import { listen } from "@tauri-apps/api/event";
 import React, { useEffect, useRef, useState } from "react";
 import { useTranslation } from "react-i18next";
 import {
@@ -7,21 +6,10 @@ import {
   CancelIcon,
 } from "../components/icons";
 import "./RecordingOverlay.css";
-import { commands } from "@/bindings";
+import { commands, events, type ShowOverlay } from "@/bindings";
 import { syncLanguageFromSettings } from "@/i18n";
 
-type OverlayState = "recording" | "transcribing" | "meeting";
-
-interface LivePreviewEvent {
-  text: string;
-  is_final: boolean;
-}
-
-interface LivePreviewErrorEvent {
-  error_type: string;
-  message: string;
-  is_fatal: boolean;
-}
+type OverlayState = ShowOverlay;
 
 const RecordingOverlay: React.FC = () => {
   const { t } = useTranslation();
@@ -53,19 +41,18 @@ const RecordingOverlay: React.FC = () => {
     const setupEventListeners = async () => {
       try {
         // Listen for show-overlay event from Rust
-        unlistenShow = await listen("show-overlay", async (event) => {
+        unlistenShow = await events.showOverlay.listen(async (event) => {
           if (!isMounted) return;
           // Sync language from settings each time overlay is shown
           await syncLanguageFromSettings();
-          const overlayState = event.payload as OverlayState;
-          setState(overlayState);
+          setState(event.payload);
           setPreviewText(""); // Clear preview text when showing new overlay
           setPreviewError(false); // Clear error state
           setIsVisible(true);
         });
 
         // Listen for hide-overlay event from Rust
-        unlistenHide = await listen("hide-overlay", () => {
+        unlistenHide = await events.hideOverlay.listen(() => {
           if (!isMounted) return;
           setIsVisible(false);
           setPreviewText(""); // Clear preview text when hiding
@@ -73,36 +60,30 @@ const RecordingOverlay: React.FC = () => {
         });
 
         // Listen for live preview updates
-        unlistenPreview = await listen<LivePreviewEvent>(
-          "live-preview-update",
-          (event) => {
-            if (!isMounted) return;
-            // Only update if we have valid text
-            if (event.payload.text) {
-              setPreviewText(event.payload.text);
-              setPreviewError(false); // Clear error on successful update
-            }
-          },
-        );
+        unlistenPreview = await events.livePreviewUpdate.listen((event) => {
+          if (!isMounted) return;
+          // Only update if we have valid text
+          if (event.payload.text) {
+            setPreviewText(event.payload.text);
+            setPreviewError(false); // Clear error on successful update
+          }
+        });
 
         // Listen for live preview errors
-        unlistenPreviewError = await listen<LivePreviewErrorEvent>(
-          "live-preview-error",
-          (event) => {
-            if (!isMounted) return;
-            console.warn("Live preview error:", event.payload);
-            if (event.payload.is_fatal) {
-              // Fatal error - clear preview text and show bars instead
-              setPreviewText("");
-              setPreviewError(true);
-            }
-          },
-        );
+        unlistenPreviewError = await events.livePreviewError.listen((event) => {
+          if (!isMounted) return;
+          console.warn("Live preview error:", event.payload);
+          if (event.payload.is_fatal) {
+            // Fatal error - clear preview text and show bars instead
+            setPreviewText("");
+            setPreviewError(true);
+          }
+        });
 
         // Listen for mic-level updates
-        unlistenLevel = await listen<number[]>("mic-level", (event) => {
+        unlistenLevel = await events.micLevel.listen((event) => {
           if (!isMounted) return;
-          const newLevels = event.payload as number[];
+          const newLevels = event.payload;
 
           // Apply smoothing to reduce jitter
           const smoothed = smoothedLevelsRef.current.map((prev, i) => {
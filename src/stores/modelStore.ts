@@ -1,14 +1,11 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
-import { listen } from "@tauri-apps/api/event";
-import { commands, type ModelInfo } from "@/bindings";
-
-interface DownloadProgress {
-  model_id: string;
-  downloaded: number;
-  total: number;
-  percentage: number;
-}
+import {
+  commands,
+  events,
+  type DownloadProgress,
+  type ModelInfo,
+} from "@/bindings";
 
 interface ModelStore {
   models: ModelInfo[];
@@ -216,17 +213,17 @@ export const useModelStore = create<ModelStore>()(
 );
 
 // Set up event listeners at module load
-listen("model-state-changed", () => {
+events.modelStateChanged.listen(() => {
   useModelStore.getState().loadCurrentModel();
 });
 
-listen<DownloadProgress>("model-download-progress", (event) => {
+events.modelDownloadProgress.listen((event) => {
   useModelStore
     .getState()
     .setDownloadProgress(event.payload.model_id, event.payload);
 });
 
-listen<string>("model-download-complete", (event) => {
+events.modelDownloadComplete.listen((event) => {
   const modelId = event.payload;
   const state = useModelStore.getState();
   state.removeDownloadingModel(modelId);
@@ -234,21 +231,18 @@ listen<string>("model-download-complete", (event) => {
   state.loadModels();
 });
 
-listen<string>("model-extraction-started", (event) => {
+events.modelExtractionStarted.listen((event) => {
   useModelStore.getState().addExtractingModel(event.payload);
 });
 
-listen<string>("model-extraction-completed", (event) => {
+events.modelExtractionCompleted.listen((event) => {
   const state = useModelStore.getState();
   state.removeExtractingModel(event.payload);
   state.loadModels();
 });
 
-listen<{ model_id: string; error: string }>(
-  "model-extraction-failed",
-  (event) => {
-    const state = useModelStore.getState();
-    state.removeExtractingModel(event.payload.model_id);
-    state.setError(`Failed to extract model: ${event.payload.error}`);
-  },
-);
+events.modelExtractionFailed.listen((event) => {
+  const state = useModelStore.getState();
+  state.removeExtractingModel(event.payload.model_id);
+  state.setError(`Failed to extract model: ${event.payload.error}`);
+});